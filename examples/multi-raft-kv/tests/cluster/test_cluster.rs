@@ -19,6 +19,7 @@ use multi_raft_kv::router::Router;
 use openraft::BasicNode;
 use openraft::async_runtime::WatchReceiver;
 use openraft::type_config::TypeConfigExt;
+use openraft_multi::AppliedToken;
 use tracing_subscriber::EnvFilter;
 
 pub fn log_panic(panic: &PanicHookInfo) {
@@ -133,7 +134,17 @@ async fn run_test(node1_rafts: &[Raft], node2_rafts: &[Raft], group_ids: &[Group
     node1_rafts[2].client_write(types_kv::Request::set("product:B", "Gadget")).await.unwrap();
     println!("  ✓ Group 'products': wrote product:A=Widget, product:B=Gadget");
 
-    TypeConfig::sleep(Duration::from_millis(500)).await;
+    // =========================================================================
+    // Wait for Node 2 to apply the writes to all groups
+    // =========================================================================
+    println!("\n=== Waiting for Node 2 to reach the applied token ===\n");
+
+    let token = AppliedToken::capture(group_ids.iter().cloned().zip(node1_rafts.iter()));
+    println!("  Applied token on Node 1: {}", token);
+
+    let node2_raft = |g: &GroupId| group_ids.iter().position(|x| x == g).map(|i| &node2_rafts[i]);
+    token.wait(node2_raft, Some(Duration::from_secs(5))).await.unwrap();
+    println!("  ✓ Node 2 reached the applied token");
 
     // =========================================================================
     // Verify replication
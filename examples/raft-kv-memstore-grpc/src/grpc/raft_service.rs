@@ -2,6 +2,7 @@ use std::pin::Pin;
 
 use futures::Stream;
 use futures::StreamExt;
+use openraft::raft::StreamAppendError;
 use tonic::Request;
 use tonic::Response;
 use tonic::Status;
@@ -91,6 +92,10 @@ impl RaftService for RaftServiceImpl {
             .await
             .map_err(|e| Status::internal(format!("Append entries operation failed: {}", e)))?;
 
+        if let AppendEntriesResponse::Malformed(e) = append_resp {
+            return Err(Status::invalid_argument(format!("Malformed append entries request: {}", e)));
+        }
+
         debug!("Append entries request processed successfully");
         Ok(Response::new(append_resp.into()))
     }
@@ -131,6 +136,7 @@ impl RaftService for RaftServiceImpl {
                     meta.last_membership.unwrap().into(),
                 ),
                 snapshot_id: meta.snapshot_id,
+                base_snapshot_id: None,
             };
         }
 
@@ -184,6 +190,9 @@ impl RaftService for RaftServiceImpl {
         // Convert Result<StreamAppendResult, Fatal> to pb::AppendEntriesResponse
         #[allow(clippy::result_large_err)]
         let output_stream = output.map(|result| match result {
+            Ok(Err(StreamAppendError::Malformed(e))) => {
+                Err(Status::invalid_argument(format!("Malformed append entries request: {}", e)))
+            }
             Ok(stream_result) => Ok(stream_result.into()),
             Err(fatal) => Err(Status::internal(format!("Fatal Raft error: {}", fatal))),
         });
@@ -15,11 +15,14 @@ use openraft::errors::ReplicationClosed;
 use openraft::errors::Unreachable;
 use openraft::network::Backoff;
 use openraft::network::NetBackoff;
+use openraft::network::NetReadIndex;
 use openraft::network::NetSnapshot;
 use openraft::network::NetStreamAppend;
 use openraft::network::NetTransferLeader;
 use openraft::network::NetVote;
 use openraft::network::RPCOption;
+use openraft::raft::ReadIndexRequest;
+use openraft::raft::ReadIndexResponse;
 use openraft::raft::StreamAppendError;
 use openraft::raft::StreamAppendResult;
 use openraft::raft::TransferLeaderRequest;
@@ -223,3 +226,15 @@ impl NetTransferLeader<TypeConfig> for NetworkConnection {
         ))))
     }
 }
+
+impl NetReadIndex<TypeConfig> for NetworkConnection {
+    async fn read_index(
+        &mut self,
+        _req: ReadIndexRequest<TypeConfig>,
+        _option: RPCOption,
+    ) -> Result<ReadIndexResponse<TypeConfig>, RPCError> {
+        Err(RPCError::Unreachable(Unreachable::new(&AnyError::error(
+            "read_index not implemented",
+        ))))
+    }
+}
@@ -8,6 +8,9 @@ impl From<pb::AppendEntriesRequest> for AppendEntriesRequest {
             prev_log_id: proto_req.prev_log_id.map(|log_id| log_id.into()),
             entries: proto_req.entries,
             leader_commit: proto_req.leader_commit.map(|log_id| log_id.into()),
+            backup_barrier: proto_req.backup_barrier.map(|log_id| log_id.into()),
+            after_snapshot: proto_req.after_snapshot.map(|log_id| log_id.into()),
+            config_digest: proto_req.config_digest.map(|d| d.into()),
         }
     }
 }
@@ -19,6 +22,9 @@ impl From<AppendEntriesRequest> for pb::AppendEntriesRequest {
             prev_log_id: value.prev_log_id.map(|log_id| log_id.into()),
             entries: value.entries,
             leader_commit: value.leader_commit.map(|log_id| log_id.into()),
+            backup_barrier: value.backup_barrier.map(|log_id| log_id.into()),
+            after_snapshot: value.after_snapshot.map(|log_id| log_id.into()),
+            config_digest: value.config_digest.map(|d| d.into()),
         }
     }
 }
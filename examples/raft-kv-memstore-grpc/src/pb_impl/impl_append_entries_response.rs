@@ -45,6 +45,9 @@ impl From<AppendEntriesResponse> for pb::AppendEntriesResponse {
                 conflict: false,
                 last_log_id: None,
             },
+            AppendEntriesResponse::Malformed(e) => {
+                unreachable!("malformed request is answered with Status::invalid_argument: {}", e)
+            }
         }
     }
 }
@@ -72,6 +75,9 @@ impl From<StreamAppendResult> for pb::AppendEntriesResponse {
                 conflict: false,
                 last_log_id: None,
             },
+            Err(StreamAppendError::Malformed(e)) => {
+                unreachable!("malformed request is answered with Status::invalid_argument: {}", e)
+            }
         }
     }
 }
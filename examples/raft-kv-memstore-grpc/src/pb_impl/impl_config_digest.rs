@@ -0,0 +1,25 @@
+use openraft::ConfigDigest;
+
+use crate::pb;
+
+impl From<ConfigDigest> for pb::ConfigDigest {
+    fn from(digest: ConfigDigest) -> Self {
+        pb::ConfigDigest {
+            election_timeout_min: digest.election_timeout_min,
+            election_timeout_max: digest.election_timeout_max,
+            relaxed_durability: digest.relaxed_durability,
+            leader_id_mode: digest.leader_id_mode,
+        }
+    }
+}
+
+impl From<pb::ConfigDigest> for ConfigDigest {
+    fn from(proto_digest: pb::ConfigDigest) -> Self {
+        ConfigDigest {
+            election_timeout_min: proto_digest.election_timeout_min,
+            election_timeout_max: proto_digest.election_timeout_max,
+            relaxed_durability: proto_digest.relaxed_durability,
+            leader_id_mode: proto_digest.leader_id_mode,
+        }
+    }
+}
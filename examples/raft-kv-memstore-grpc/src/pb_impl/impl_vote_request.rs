@@ -7,6 +7,8 @@ impl From<VoteRequest> for pb::VoteRequest {
             vote: Some(vote_req.vote),
             last_log_id: vote_req.last_log_id.map(|log_id| log_id.into()),
             leadership_transfer: vote_req.leadership_transfer,
+            relaxed_durability: vote_req.relaxed_durability,
+            config_digest: vote_req.config_digest.map(|d| d.into()),
         }
     }
 }
@@ -19,6 +21,8 @@ impl From<pb::VoteRequest> for VoteRequest {
             vote,
             last_log_id,
             leadership_transfer: proto_vote_req.leadership_transfer,
+            relaxed_durability: proto_vote_req.relaxed_durability,
+            config_digest: proto_vote_req.config_digest.map(|d| d.into()),
         }
     }
 }
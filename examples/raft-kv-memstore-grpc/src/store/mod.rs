@@ -81,6 +81,7 @@ impl RaftSnapshotBuilder<TypeConfig> for Arc<StateMachineStore> {
             last_log_id: last_applied,
             last_membership,
             snapshot_id,
+            base_snapshot_id: None,
         };
 
         let stored = StoredSnapshot {
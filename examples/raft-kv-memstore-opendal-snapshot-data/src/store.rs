@@ -106,6 +106,7 @@ impl RaftSnapshotBuilder<TypeConfig> for Arc<StateMachineStore> {
             last_log_id: last_applied_log,
             last_membership,
             snapshot_id: snapshot_id.clone(),
+            base_snapshot_id: None,
         };
 
         let snapshot = StoredSnapshot {
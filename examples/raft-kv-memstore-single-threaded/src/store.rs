@@ -169,6 +169,7 @@ impl RaftSnapshotBuilder<TypeConfig> for Rc<StateMachineStore> {
             last_log_id: last_applied_log,
             last_membership,
             snapshot_id,
+            base_snapshot_id: None,
         };
 
         let snapshot = StoredSnapshot {
@@ -75,6 +75,7 @@ impl RaftSnapshotBuilder<TypeConfig> for StateMachineStore {
             last_log_id: last_applied_log,
             last_membership,
             snapshot_id,
+            base_snapshot_id: None,
         };
 
         let snapshot = StoredSnapshot {
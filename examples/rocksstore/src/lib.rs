@@ -7,6 +7,7 @@
 #![allow(clippy::uninlined_format_args)]
 
 pub mod log_store;
+pub mod options;
 pub mod state_machine;
 
 #[cfg(test)]
@@ -22,6 +23,7 @@ use rocksdb::DB;
 use rocksdb::Options;
 
 use crate::log_store::RocksLogStore;
+pub use crate::options::RocksStoreOptions;
 pub use crate::state_machine::RocksStateMachine;
 
 pub type RocksNodeId = u64;
@@ -36,25 +38,37 @@ openraft::declare_raft_types!(
 /// Create a pair of `RocksLogStore` and `RocksStateMachine` that are backed by a same rocks db
 /// instance.
 pub async fn new<C, P: AsRef<Path>>(db_path: P) -> Result<(RocksLogStore<C>, RocksStateMachine), io::Error>
+where C: RaftTypeConfig {
+    open(RocksStoreOptions::new(db_path.as_ref())).await
+}
+
+/// Create a pair of `RocksLogStore` and `RocksStateMachine` with the WAL and the snapshot files
+/// stored where `options` specifies.
+pub async fn open<C>(options: RocksStoreOptions) -> Result<(RocksLogStore<C>, RocksStateMachine), io::Error>
 where C: RaftTypeConfig {
     let mut db_opts = Options::default();
     db_opts.create_missing_column_families(true);
     db_opts.create_if_missing(true);
 
+    if let Some(wal_dir) = &options.wal_dir {
+        db_opts.set_wal_dir(wal_dir);
+    }
+    if let Some(rate) = options.db_write_rate {
+        // Refill every 100ms; fairness 10 is the rocks-db default.
+        db_opts.set_ratelimiter(rate as i64, 100_000, 10);
+    }
+
     let meta = ColumnFamilyDescriptor::new("meta", Options::default());
     let sm_meta = ColumnFamilyDescriptor::new("sm_meta", Options::default());
     let sm_data = ColumnFamilyDescriptor::new("sm_data", Options::default());
     let logs = ColumnFamilyDescriptor::new("logs", Options::default());
 
-    let db_path = db_path.as_ref();
-    let snapshot_dir = db_path.join("snapshots");
-
-    let db =
-        DB::open_cf_descriptors(&db_opts, db_path, vec![meta, sm_meta, sm_data, logs]).map_err(io::Error::other)?;
+    let db = DB::open_cf_descriptors(&db_opts, &options.db_dir, vec![meta, sm_meta, sm_data, logs])
+        .map_err(io::Error::other)?;
 
     let db = Arc::new(db);
     Ok((
-        RocksLogStore::new(db.clone()),
-        RocksStateMachine::new(db, snapshot_dir).await?,
+        RocksLogStore::new(db.clone()).with_fsync(options.log_fsync),
+        RocksStateMachine::new(db, &options).await?,
     ))
 }
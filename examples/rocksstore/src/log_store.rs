@@ -29,6 +29,10 @@ pub struct RocksLogStore<C>
 where C: RaftTypeConfig
 {
     db: Arc<DB>,
+
+    /// Whether to fsync the WAL before a write is reported as persisted.
+    fsync: bool,
+
     _p: PhantomData<C>,
 }
 
@@ -41,10 +45,17 @@ where C: RaftTypeConfig
 
         Self {
             db,
+            fsync: true,
             _p: Default::default(),
         }
     }
 
+    /// Set whether to fsync the WAL before a log append or a vote is reported as persisted.
+    pub fn with_fsync(mut self, fsync: bool) -> Self {
+        self.fsync = fsync;
+        self
+    }
+
     fn cf_meta(&self) -> &ColumnFamily {
         self.db.cf_handle("meta").unwrap()
     }
@@ -155,7 +166,8 @@ where C: RaftTypeConfig
 
         // Vote must be persisted to disk before returning.
         let db = self.db.clone();
-        C::spawn_blocking(move || db.flush_wal(true).map_err(|e| io::Error::other(e.to_string()))).await??;
+        let fsync = self.fsync;
+        C::spawn_blocking(move || db.flush_wal(fsync).map_err(|e| io::Error::other(e.to_string()))).await??;
 
         Ok(())
     }
@@ -178,8 +190,9 @@ where C: RaftTypeConfig
         // But the above `pub_cf()` must be called in this function, not in another task.
         // Because when the function returns, it requires the log entries can be read.
         let db = self.db.clone();
+        let fsync = self.fsync;
         std::thread::spawn(move || {
-            let res = db.flush_wal(true).map_err(io::Error::other);
+            let res = db.flush_wal(fsync).map_err(io::Error::other);
             callback.io_completed(res);
         });
 
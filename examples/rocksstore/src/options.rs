@@ -0,0 +1,64 @@
+//! Where and how [`open()`](crate::open) stores logs and snapshots.
+
+use std::path::PathBuf;
+
+/// Options to open a rocks-db backed store with.
+///
+/// By default everything is stored under `db_dir`. Writing the WAL sequentially while large
+/// snapshot files are written or compacted on the same device makes the latency of appending logs
+/// spiky; to avoid it, put [`wal_dir`](Self::wal_dir) and [`snapshot_dir`](Self::snapshot_dir) on
+/// different devices, and limit the write rate of the bulk IO.
+#[derive(Debug, Clone)]
+pub struct RocksStoreOptions {
+    /// The directory of the rocks-db instance that stores the logs, the vote and the state
+    /// machine.
+    pub db_dir: PathBuf,
+
+    /// The directory of the rocks-db write-ahead log, which every log append is persisted to.
+    ///
+    /// `None` puts the WAL in `db_dir`.
+    pub wal_dir: Option<PathBuf>,
+
+    /// The directory of the snapshot files.
+    ///
+    /// `None` puts them in `db_dir/snapshots`.
+    pub snapshot_dir: Option<PathBuf>,
+
+    /// Whether appending logs and saving the vote fsync the WAL before reporting them as
+    /// persisted.
+    ///
+    /// Turning it off is only safe if losing the most recent writes on a power loss is
+    /// acceptable, e.g., the device has a non-volatile write cache. Defaults to `true`.
+    pub log_fsync: bool,
+
+    /// Whether a snapshot file is fsync-ed after it is written. Defaults to `true`.
+    pub snapshot_fsync: bool,
+
+    /// The max number of bytes per second rocks-db writes flushing and compacting data in
+    /// `db_dir`. `None` does not limit it.
+    ///
+    /// The WAL is not limited, so that log appends are not delayed by it.
+    pub db_write_rate: Option<u64>,
+
+    /// The max number of bytes per second written to a snapshot file. `None` does not limit it.
+    pub snapshot_write_rate: Option<u64>,
+}
+
+impl RocksStoreOptions {
+    /// Store everything under `db_dir`, with the default options.
+    pub fn new(db_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            db_dir: db_dir.into(),
+            wal_dir: None,
+            snapshot_dir: None,
+            log_fsync: true,
+            snapshot_fsync: true,
+            db_write_rate: None,
+            snapshot_write_rate: None,
+        }
+    }
+
+    pub(crate) fn snapshot_dir(&self) -> PathBuf {
+        self.snapshot_dir.clone().unwrap_or_else(|| self.db_dir.join("snapshots"))
+    }
+}
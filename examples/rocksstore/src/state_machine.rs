@@ -4,12 +4,16 @@ use std::fmt::Debug;
 use std::fs;
 use std::io;
 use std::io::Cursor;
+use std::io::Write;
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 use futures::Stream;
 use futures::TryStreamExt;
 use openraft::EntryPayload;
+use openraft::Instant;
 use openraft::OptionalSend;
 use openraft::RaftSnapshotBuilder;
 use openraft::StorageError;
@@ -27,6 +31,7 @@ use rocksdb::DB;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::RocksStoreOptions;
 use crate::TypeConfig;
 
 /// State machine backed by RocksDB for full persistence.
@@ -36,18 +41,59 @@ use crate::TypeConfig;
 pub struct RocksStateMachine {
     db: Arc<DB>,
     snapshot_dir: PathBuf,
+
+    /// Whether to fsync a snapshot file after writing it.
+    snapshot_fsync: bool,
+
+    /// The max bytes per second to write a snapshot file at.
+    snapshot_write_rate: Option<u64>,
 }
 
+/// The size of a chunk a snapshot file is written in, when its write rate is limited.
+const SNAPSHOT_WRITE_CHUNK: usize = 1024 * 1024;
+
 impl RocksStateMachine {
-    pub(crate) async fn new(db: Arc<DB>, snapshot_dir: PathBuf) -> Result<RocksStateMachine, io::Error> {
+    pub(crate) async fn new(db: Arc<DB>, options: &RocksStoreOptions) -> Result<RocksStateMachine, io::Error> {
         // Validate column families exist at construction time
         db.cf_handle("sm_meta").ok_or_else(|| io::Error::other("column family `sm_meta` not found"))?;
         db.cf_handle("sm_data").ok_or_else(|| io::Error::other("column family `sm_data` not found"))?;
 
         // Create snapshot directory if it doesn't exist
+        let snapshot_dir = options.snapshot_dir();
         fs::create_dir_all(&snapshot_dir)?;
 
-        Ok(Self { db, snapshot_dir })
+        Ok(Self {
+            db,
+            snapshot_dir,
+            snapshot_fsync: options.snapshot_fsync,
+            snapshot_write_rate: options.snapshot_write_rate.filter(|r| *r > 0),
+        })
+    }
+
+    /// Write a snapshot file, at most at `snapshot_write_rate` and fsync it if `snapshot_fsync`.
+    async fn write_snapshot_file(&self, path: &Path, bytes: &[u8]) -> Result<(), io::Error> {
+        let mut f = fs::File::create(path)?;
+
+        match self.snapshot_write_rate {
+            None => f.write_all(bytes)?,
+            Some(rate) => {
+                for chunk in bytes.chunks(SNAPSHOT_WRITE_CHUNK) {
+                    let started = TypeConfig::now();
+                    f.write_all(chunk)?;
+
+                    let cost = Duration::from_nanos((chunk.len() as u128 * 1_000_000_000 / rate as u128) as u64);
+                    let elapsed = started.elapsed();
+                    if cost > elapsed {
+                        TypeConfig::sleep(cost - elapsed).await;
+                    }
+                }
+            }
+        }
+
+        if self.snapshot_fsync {
+            f.sync_all()?;
+        }
+        Ok(())
     }
 
     fn cf_sm_meta(&self) -> &rocksdb::ColumnFamily {
@@ -116,6 +162,7 @@ impl RaftSnapshotBuilder<TypeConfig> for RocksStateMachine {
             last_log_id: last_applied_log,
             last_membership,
             snapshot_id: snapshot_id.clone(),
+            base_snapshot_id: None,
         };
 
         // Use RocksDB snapshot for consistent point-in-time view
@@ -149,7 +196,7 @@ impl RaftSnapshotBuilder<TypeConfig> for RocksStateMachine {
 
         // Write complete snapshot to file
         let snapshot_path = self.snapshot_dir.join(&snapshot_id);
-        fs::write(&snapshot_path, &file_bytes).map_err(|e| {
+        self.write_snapshot_file(&snapshot_path, &file_bytes).await.map_err(|e| {
             StorageError::<TypeConfig>::write_snapshot(Some(meta.signature()), TypeConfig::err_from_error(&e))
         })?;
 
@@ -307,7 +354,7 @@ impl RaftStateMachine<TypeConfig> for RocksStateMachine {
             serialize(&snapshot_file).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
 
         let snapshot_path = self.snapshot_dir.join(&meta.snapshot_id);
-        fs::write(&snapshot_path, &file_bytes)?;
+        self.write_snapshot_file(&snapshot_path, &file_bytes).await?;
 
         Ok(())
     }
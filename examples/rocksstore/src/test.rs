@@ -5,6 +5,7 @@ use openraft::type_config::TypeConfigExt;
 use tempfile::TempDir;
 
 use crate::RocksStateMachine;
+use crate::RocksStoreOptions;
 use crate::TypeConfig;
 use crate::log_store::RocksLogStore;
 
@@ -19,9 +20,35 @@ impl StoreBuilder<TypeConfig, RocksLogStore<TypeConfig>, RocksStateMachine, Temp
     }
 }
 
+/// Store the WAL and the snapshot files in directories separate from the db.
+struct RocksSplitDirBuilder {}
+
+impl StoreBuilder<TypeConfig, RocksLogStore<TypeConfig>, RocksStateMachine, TempDir> for RocksSplitDirBuilder {
+    async fn build(&self) -> Result<(TempDir, RocksLogStore<TypeConfig>, RocksStateMachine), StorageError<TypeConfig>> {
+        let td = TempDir::new().map_err(|e| StorageError::read(TypeConfig::err_from_error(&e)))?;
+
+        let mut options = RocksStoreOptions::new(td.path().join("db"));
+        options.wal_dir = Some(td.path().join("wal"));
+        options.snapshot_dir = Some(td.path().join("snapshots"));
+        options.snapshot_write_rate = Some(64 * 1024 * 1024);
+        options.db_write_rate = Some(64 * 1024 * 1024);
+
+        let (log_store, sm) =
+            crate::open(options).await.map_err(|e| StorageError::read(TypeConfig::err_from_error(&e)))?;
+        Ok((td, log_store, sm))
+    }
+}
+
 #[test]
 pub fn test_rocks_store() {
     TypeConfig::run(async {
         Suite::test_all(RocksBuilder {}).await.unwrap();
     });
 }
+
+#[test]
+pub fn test_rocks_store_split_dirs() {
+    TypeConfig::run(async {
+        Suite::test_all(RocksSplitDirBuilder {}).await.unwrap();
+    });
+}
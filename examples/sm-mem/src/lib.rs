@@ -121,6 +121,7 @@ where C: RaftTypeConfig<
             last_log_id: inner.last_applied_log.clone(),
             last_membership: inner.last_membership.clone(),
             snapshot_id,
+            base_snapshot_id: None,
         };
 
         let snapshot = StoredSnapshot {
@@ -7,6 +7,7 @@ use std::time::Duration;
 use futures::FutureExt;
 use openraft::ErrorSubject;
 use openraft::ErrorVerb;
+use openraft::Instant;
 use openraft::OptionalSend;
 use openraft::RaftTypeConfig;
 use openraft::ToStorageResult;
@@ -30,6 +31,9 @@ use super::RaftNetwork;
 ///
 /// This is the client-side (Leader) component for chunk-based snapshot transport.
 /// It splits a snapshot into chunks and sends them incrementally to a follower.
+///
+/// The snapshot id identifies a transfer: before sending any data, the follower is asked how much
+/// of the snapshot it has received, and a transfer interrupted earlier resumes from there.
 pub struct Sender<C>(std::marker::PhantomData<C>)
 where C: RaftTypeConfig;
 
@@ -63,6 +67,10 @@ where
         let mut offset = 0;
         let end = snapshot.snapshot.seek(SeekFrom::End(0)).await.sto_res(subject_verb)?;
 
+        // The first request carries no data: it asks the follower how much of this snapshot it
+        // has already received, e.g., from a transfer that was interrupted.
+        let mut probing = end > 0;
+
         let mut c = std::pin::pin!(cancel);
         loop {
             // If canceled, return at once
@@ -78,7 +86,11 @@ where
 
             // Safe unwrap(): this function is called only by default implementation of
             // `RaftNetwork::full_snapshot()` and it is always set.
-            let chunk_size = option.snapshot_chunk_size().unwrap();
+            let chunk_size = if probing {
+                0
+            } else {
+                option.snapshot_chunk_size().unwrap()
+            };
             let mut buf = Vec::with_capacity(chunk_size);
             while buf.capacity() > buf.len() {
                 let n = snapshot.snapshot.read_buf(&mut buf).await.sto_res(subject_verb)?;
@@ -107,6 +119,8 @@ where
                 req.done
             );
 
+            let sent_at = C::now();
+
             #[allow(deprecated)]
             let res = C::timeout(option.hard_ttl(), net.install_snapshot(req, option.clone())).await;
 
@@ -154,7 +168,26 @@ where
                 return Ok(SnapshotResponse::new(resp.vote));
             }
 
-            offset += n_read as u64;
+            // Resume from where the follower is, if it tells.
+            offset = match resp.received {
+                Some(received) if received <= end => received,
+                _ => offset + n_read as u64,
+            };
+
+            if probing {
+                probing = false;
+                tracing::info!("resume sending snapshot from offset: {}, end: {}", offset, end);
+                continue;
+            }
+
+            // Give the chunk the time it takes at the max rate before sending the next one.
+            if let Some(rate) = option.max_bytes_rate() {
+                let cost = Duration::from_nanos((n_read as u128 * 1_000_000_000 / rate as u128) as u64);
+                let elapsed = C::now().saturating_duration_since(sent_at);
+                if cost > elapsed {
+                    C::sleep(cost - elapsed).await;
+                }
+            }
         }
     }
 }
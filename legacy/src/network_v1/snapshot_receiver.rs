@@ -53,6 +53,10 @@ where C::SnapshotData: tokio::io::AsyncRead + tokio::io::AsyncWrite + tokio::io:
     /// 2. Receiving chunks via `Streaming::receive_chunk()`
     /// 3. When all chunks are received, calling `Raft::install_full_snapshot()`
     ///
+    /// The progress of a snapshot is kept until it is finished or another snapshot begins, and is
+    /// returned in [`InstallSnapshotResponse::received`], so that an interrupted transfer of the
+    /// same snapshot id resumes from there.
+    ///
     /// # Returns
     ///
     /// - `Ok(response)` with the current vote on success
@@ -116,6 +120,7 @@ where C::SnapshotData: tokio::io::AsyncRead + tokio::io::AsyncWrite + tokio::io:
 
         // Write the chunk
         streaming.as_mut().unwrap().receive_chunk(&req).await?;
+        let received = streaming.as_ref().unwrap().received();
 
         tracing::info!("Received snapshot chunk");
 
@@ -144,7 +149,10 @@ where C::SnapshotData: tokio::io::AsyncRead + tokio::io::AsyncWrite + tokio::io:
         // Return response with current vote from metrics
         let my_vote = self.metrics().borrow_watched().vote.clone();
 
-        Ok(InstallSnapshotResponse { vote: my_vote })
+        Ok(InstallSnapshotResponse {
+            vote: my_vote,
+            received: Some(received),
+        })
     }
 }
 
@@ -27,6 +27,9 @@ where C: RaftTypeConfig
     /// The offset of the last byte written to the snapshot.
     offset: u64,
 
+    /// The end of the furthest chunk written, from which an interrupted transfer resumes.
+    received: u64,
+
     /// The ID of the snapshot being written.
     snapshot_id: SnapshotId,
 
@@ -41,6 +44,7 @@ where C: RaftTypeConfig
     pub fn new(snapshot_id: SnapshotId, snapshot_data: C::SnapshotData) -> Self {
         Self {
             offset: 0,
+            received: 0,
             snapshot_id,
             snapshot_data,
         }
@@ -51,6 +55,12 @@ where C: RaftTypeConfig
         &self.snapshot_id
     }
 
+    /// Get the number of bytes of the snapshot received.
+    #[since(version = "0.10.0")]
+    pub fn received(&self) -> u64 {
+        self.received
+    }
+
     /// Consumes the `Streaming` and returns the snapshot data.
     pub fn into_snapshot_data(self) -> C::SnapshotData {
         self.snapshot_data
@@ -88,6 +98,7 @@ where
             ));
         }
         self.offset += req.data.len() as u64;
+        self.received = std::cmp::max(self.received, self.offset);
 
         Ok(req.done)
     }
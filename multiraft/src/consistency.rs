@@ -0,0 +1,196 @@
+//! Applied-index consistency tokens across Raft groups.
+//!
+//! - [`AppliedToken`] - The applied log id of each of several Raft groups, captured after an
+//!   operation that wrote to them
+//! - [`WaitTokenError`] - Error returned when waiting for an [`AppliedToken`]
+//!
+//! A read that queries several groups waits until every group on the serving node has applied up
+//! to the token, so that it observes all the writes the token was captured after.
+
+use std::error::Error;
+use std::fmt;
+use std::time::Duration;
+
+use openraft::Raft;
+use openraft::RaftTypeConfig;
+use openraft::async_runtime::WatchReceiver;
+use openraft::metrics::Metric;
+use openraft::metrics::WaitError;
+use openraft::type_config::TypeConfigExt;
+use openraft::type_config::alias::LogIdOf;
+
+/// A list of `(group, applied log id)` pairs, one for each Raft group an operation touched.
+///
+/// Capture it on a node after writing to several groups, pass it along with a later read, and
+/// call [`wait()`](Self::wait) on the serving node before reading, so that the read observes the
+/// state of every listed group at least as new as when the token was captured.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppliedToken<C, G>
+where C: RaftTypeConfig
+{
+    tokens: Vec<(G, Option<LogIdOf<C>>)>,
+}
+
+impl<C, G> Default for AppliedToken<C, G>
+where C: RaftTypeConfig
+{
+    fn default() -> Self {
+        Self { tokens: Vec::new() }
+    }
+}
+
+impl<C, G> AppliedToken<C, G>
+where C: RaftTypeConfig
+{
+    /// Create an empty token.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Capture the current applied log id of each group.
+    pub fn capture<'a, SM: 'a>(groups: impl IntoIterator<Item = (G, &'a Raft<C, SM>)>) -> Self {
+        let tokens = groups
+            .into_iter()
+            .map(|(group, raft)| {
+                let applied = raft.metrics().borrow_watched().last_applied.clone();
+                (group, applied)
+            })
+            .collect();
+
+        Self { tokens }
+    }
+
+    /// Add the applied log id of a group.
+    ///
+    /// If the group is already listed, the greater log id is kept.
+    pub fn push(&mut self, group: G, applied: Option<LogIdOf<C>>)
+    where G: PartialEq {
+        match self.tokens.iter_mut().find(|(g, _)| g == &group) {
+            Some((_, a)) => {
+                if applied > *a {
+                    *a = applied;
+                }
+            }
+            None => self.tokens.push((group, applied)),
+        }
+    }
+
+    /// Merge another token into this one, keeping the greater log id of each group.
+    pub fn merge(&mut self, other: Self)
+    where G: PartialEq {
+        for (group, applied) in other.tokens {
+            self.push(group, applied);
+        }
+    }
+
+    /// Iterate over the `(group, applied log id)` pairs.
+    pub fn iter(&self) -> impl Iterator<Item = &(G, Option<LogIdOf<C>>)> {
+        self.tokens.iter()
+    }
+
+    /// Returns the number of groups in this token.
+    pub fn len(&self) -> usize {
+        self.tokens.len()
+    }
+
+    /// Returns `true` if this token lists no group.
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// Wait until every listed group has applied up to its log id on this node.
+    ///
+    /// `get_raft` returns the local Raft instance of a group. `timeout` bounds the whole wait,
+    /// not each group; `None` waits for a long time, as [`Raft::wait()`] does.
+    pub async fn wait<'a, SM: 'a>(
+        &self,
+        get_raft: impl Fn(&G) -> Option<&'a Raft<C, SM>>,
+        timeout: Option<Duration>,
+    ) -> Result<(), WaitTokenError<G>>
+    where
+        G: Clone,
+    {
+        let timeout = timeout.unwrap_or(Duration::from_secs(86400 * 365 * 100));
+        let deadline = C::now() + timeout;
+
+        for (group, applied) in self.tokens.iter() {
+            let Some(raft) = get_raft(group) else {
+                return Err(WaitTokenError::GroupNotFound(group.clone()));
+            };
+
+            let remaining = deadline.saturating_duration_since(C::now());
+
+            let want = Metric::Applied(applied.clone());
+            let res = raft.wait(Some(remaining)).ge(want, "applied token").await;
+
+            res.map_err(|error| WaitTokenError::Wait {
+                group: group.clone(),
+                error,
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<C, G> FromIterator<(G, Option<LogIdOf<C>>)> for AppliedToken<C, G>
+where
+    C: RaftTypeConfig,
+    G: PartialEq,
+{
+    fn from_iter<T: IntoIterator<Item = (G, Option<LogIdOf<C>>)>>(iter: T) -> Self {
+        let mut token = Self::new();
+        for (group, applied) in iter {
+            token.push(group, applied);
+        }
+        token
+    }
+}
+
+impl<C, G> fmt::Display for AppliedToken<C, G>
+where
+    C: RaftTypeConfig,
+    G: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[")?;
+        for (i, (group, applied)) in self.tokens.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            match applied {
+                Some(applied) => write!(f, "{}:{}", group, applied)?,
+                None => write!(f, "{}:None", group)?,
+            }
+        }
+        write!(f, "]")
+    }
+}
+
+/// Error returned by [`AppliedToken::wait()`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum WaitTokenError<G> {
+    /// The group is not found on this node.
+    GroupNotFound(G),
+
+    /// Waiting for the group to apply up to its log id failed, e.g., timed out.
+    Wait {
+        /// The group being waited for.
+        group: G,
+        /// The underlying error.
+        error: WaitError,
+    },
+}
+
+impl<G> fmt::Display for WaitTokenError<G>
+where G: fmt::Display
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WaitTokenError::GroupNotFound(group) => write!(f, "group not found: {}", group),
+            WaitTokenError::Wait { group, error } => write!(f, "group {}: {}", group, error),
+        }
+    }
+}
+
+impl<G> Error for WaitTokenError<G> where G: fmt::Debug + fmt::Display {}
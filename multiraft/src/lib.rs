@@ -1,5 +1,8 @@
+mod consistency;
 mod network;
 
+pub use consistency::AppliedToken;
+pub use consistency::WaitTokenError;
 pub use network::GroupNetworkAdapter;
 pub use network::GroupNetworkFactory;
 pub use network::GroupRouter;
@@ -21,6 +21,8 @@ use openraft::network::RPCOption;
 use openraft::network::v2::RaftNetworkV2;
 use openraft::raft::AppendEntriesRequest;
 use openraft::raft::AppendEntriesResponse;
+use openraft::raft::ReadIndexRequest;
+use openraft::raft::ReadIndexResponse;
 use openraft::raft::SnapshotResponse;
 use openraft::raft::TransferLeaderRequest;
 use openraft::raft::TransferLeaderResponse;
@@ -81,6 +83,22 @@ where C: RaftTypeConfig
         }
     }
 
+    /// Send ReadIndex to the leader for a specific group.
+    /// Default: returns "not implemented" error.
+    fn read_index(
+        &self,
+        _target: C::NodeId,
+        _group_id: G,
+        _req: ReadIndexRequest<C>,
+        _option: RPCOption,
+    ) -> impl Future<Output = Result<ReadIndexResponse<C>, RPCError<C>>> + OptionalSend {
+        async {
+            Err(RPCError::Unreachable(Unreachable::new(&anyerror::AnyError::error(
+                "read_index not implemented",
+            ))))
+        }
+    }
+
     /// Backoff strategy for retries. Default: `None`, delegating to
     /// [`Config::backoff`](openraft::Config::backoff).
     fn backoff(&self) -> Option<Backoff> {
@@ -188,6 +206,14 @@ where
         self.router.transfer_leader(self.target.clone(), self.group_id.clone(), req, option).await
     }
 
+    async fn read_index(
+        &mut self,
+        req: ReadIndexRequest<C>,
+        option: RPCOption,
+    ) -> Result<ReadIndexResponse<C>, RPCError<C>> {
+        self.router.read_index(self.target.clone(), self.group_id.clone(), req, option).await
+    }
+
     fn backoff(&self) -> Option<Backoff> {
         self.router.backoff()
     }
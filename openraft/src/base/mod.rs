@@ -8,6 +8,7 @@
 //! - [`OptionalSend`] - `Send` when not `single-threaded`, empty otherwise
 //! - [`OptionalSync`] - `Sync` when not `single-threaded`, empty otherwise
 //! - [`OptionalSerde`] - Serde traits when `serde` feature enabled
+//! - [`OptionalEncodeContent`] - [`EncodeContent`] when `audit-log-chain` feature enabled
 //! - [`OptionalFeatures`] - Combines all optional traits
 //!
 //! ## Type Aliases
@@ -23,6 +24,8 @@
 //! - **Multi-threaded** contexts (default): Types are `Send` + `Sync`
 //! - **Single-threaded** contexts (feature `single-threaded`): No `Send` + `Sync` bounds
 //! - **With/without serde** (feature `serde`): Optional serialization support
+//! - **With/without log chain** (feature `audit-log-chain`): Optional canonical encoding of log
+//!   entries
 //!
 //! Applications rarely need to use these types directly - they're used internally
 //! to make Openraft flexible across different environments.
@@ -33,8 +36,10 @@ pub(crate) mod range_map;
 pub(crate) mod range_values;
 pub(crate) mod shared_id_generator;
 
+pub use encode_content_able::OptionalEncodeContent;
 pub use openraft_rt::BoxAny;
 pub use openraft_rt::BoxAsyncOnceMut;
+pub use openraft_rt::BoxFnMut;
 pub use openraft_rt::BoxFuture;
 pub use openraft_rt::BoxIterator;
 pub use openraft_rt::BoxMaybeAsyncOnceMut;
@@ -44,6 +49,9 @@ pub use openraft_rt::OptionalSend;
 pub use openraft_rt::OptionalSync;
 pub use serde_able::OptionalSerde;
 
+#[cfg(doc)]
+use crate::entry::EncodeContent;
+
 #[cfg(not(feature = "serde"))]
 mod serde_able {
     /// A trait that extends `Serialize` and `Deserialize` if the `serde` feature flag
@@ -60,6 +68,24 @@ mod serde_able {
     impl<T> OptionalSerde for T where T: serde::Serialize + for<'a> serde::Deserialize<'a> {}
 }
 
+#[cfg(not(feature = "audit-log-chain"))]
+mod encode_content_able {
+    /// A trait that extends [`EncodeContent`](crate::entry::EncodeContent) if the
+    /// `audit-log-chain` feature flag is enabled, otherwise it is an empty trait.
+    pub trait OptionalEncodeContent {}
+    impl<T> OptionalEncodeContent for T {}
+}
+
+#[cfg(feature = "audit-log-chain")]
+mod encode_content_able {
+    use crate::entry::EncodeContent;
+
+    /// A trait that extends [`EncodeContent`] if the `audit-log-chain` feature flag is enabled,
+    /// otherwise it is an empty trait.
+    pub trait OptionalEncodeContent: EncodeContent {}
+    impl<T> OptionalEncodeContent for T where T: EncodeContent {}
+}
+
 /// A trait that combines all optional features.
 pub trait OptionalFeatures: OptionalSend + OptionalSync + OptionalSerde {}
 
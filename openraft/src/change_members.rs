@@ -67,8 +67,23 @@ where
     ///
     /// If a node is still a voter, it returns
     /// [`error::LearnerNotFound`](`crate::error::LearnerNotFound`) error.
+    ///
+    /// Unlike `RemoveLearners`, it is rejected with
+    /// [`error::InProgress`](`crate::error::InProgress`) while a previous membership change is not
+    /// yet committed.
     RemoveNodes(BTreeSet<NID>),
 
+    /// Remove learners from membership, without touching the voters.
+    ///
+    /// Learners are not in any quorum, thus it does not need a joint config, and it is accepted
+    /// even while a previous membership change is not yet committed: the voter configs of the
+    /// pending membership, joint or not, are kept as they are.
+    ///
+    /// If a node is a voter, it returns
+    /// [`error::LearnerNotFound`](`crate::error::LearnerNotFound`) error.
+    #[since(version = "0.10.0")]
+    RemoveLearners(BTreeSet<NID>),
+
     /// Replace all nodes with a new set.
     ///
     /// Every voter has to have a corresponding node in the new
@@ -81,6 +96,46 @@ where
     /// And it still finishes in a two-step joint config change.
     #[since(version = "0.10.0")]
     Batch(Vec<ChangeMembers<NID, N>>),
+
+    /// Add nodes as learners, and let the leader promote each of them to a voter once it has
+    /// caught up.
+    ///
+    /// The change completes when the nodes are added as learners, like `AddNodes`. Then the leader
+    /// proposes `AddVoterIds` for a learner as soon as its replication lag is within
+    /// [`Config::promote_lag_threshold`](crate::Config::promote_lag_threshold).
+    ///
+    /// A pending promotion is dropped if the node is removed before it catches up, or if the
+    /// leader loses leadership.
+    #[since(version = "0.10.0")]
+    AddVotersWhenCaughtUp(BTreeMap<NID, N>),
+
+    /// Add voters with corresponding nodes, as witnesses that only store log metadata.
+    ///
+    /// See [`NodeRole::Witness`](crate::NodeRole::Witness). A node that is already in the
+    /// membership is not turned into a witness: it becomes a voter like with `AddVoters`.
+    #[since(version = "0.10.0")]
+    AddWitnesses(BTreeMap<NID, N>),
+
+    /// Add voters with corresponding nodes, as log-only nodes that have no state machine.
+    ///
+    /// See [`NodeRole::LogOnly`](crate::NodeRole::LogOnly). A node that is already in the
+    /// membership is not turned into a log-only node: it becomes a voter like with `AddVoters`.
+    ///
+    /// A log-only node does not install snapshot: it returns
+    /// [`LogOnlyLogsPurged`](crate::errors::LogOnlyLogsPurged) if the leader has purged logs.
+    #[since(version = "0.10.0")]
+    AddLogOnly(BTreeMap<NID, N>),
+
+    /// Set the failure domains of nodes in a domain-aware membership, e.g., of the voters being
+    /// added in the same [`Batch`](Self::Batch).
+    ///
+    /// The failure domain of a node that already has one is not changed. Every voter of a
+    /// domain-aware membership must have a failure domain. It returns
+    /// [`FailureDomainError::NotDomainAware`](crate::errors::FailureDomainError::NotDomainAware) if
+    /// the membership is not domain-aware. See
+    /// [`Membership::new_domain_aware()`](crate::Membership::new_domain_aware).
+    #[since(version = "0.10.0")]
+    SetFailureDomains(BTreeMap<NID, String>),
 }
 
 /// Convert a series of ids to a `Replace` operation.
@@ -124,12 +179,42 @@ where
             ChangeMembers::RemoveNodes(ids) => {
                 write!(f, "RemoveNodes({})", ids.display())
             }
+            ChangeMembers::RemoveLearners(ids) => {
+                write!(f, "RemoveLearners({})", ids.display())
+            }
             ChangeMembers::ReplaceAllNodes(nodes) => {
                 write!(f, "ReplaceAllNodes({})", nodes.display())
             }
             ChangeMembers::Batch(changes) => {
                 write!(f, "Batch({})", changes.as_slice().display_n(1024))
             }
+            ChangeMembers::AddVotersWhenCaughtUp(nodes) => {
+                write!(f, "AddVotersWhenCaughtUp({})", nodes.display())
+            }
+            ChangeMembers::AddWitnesses(nodes) => {
+                write!(f, "AddWitnesses({})", nodes.display())
+            }
+            ChangeMembers::AddLogOnly(nodes) => {
+                write!(f, "AddLogOnly({})", nodes.display())
+            }
+            ChangeMembers::SetFailureDomains(domains) => {
+                write!(f, "SetFailureDomains({})", domains.display())
+            }
+        }
+    }
+}
+
+impl<NID, N> ChangeMembers<NID, N>
+where
+    NID: NodeId,
+    N: Node,
+{
+    /// Returns the ids of the nodes to promote to voters once they have caught up.
+    pub(crate) fn promote_when_caught_up_ids(&self) -> BTreeSet<NID> {
+        match self {
+            ChangeMembers::AddVotersWhenCaughtUp(nodes) => nodes.keys().cloned().collect(),
+            ChangeMembers::Batch(changes) => changes.iter().flat_map(|c| c.promote_when_caught_up_ids()).collect(),
+            _ => BTreeSet::new(),
         }
     }
 }
@@ -12,11 +12,15 @@ use rand::RngExt;
 use crate::AsyncRuntime;
 use crate::LogId;
 use crate::LogIdOptionExt;
+use crate::config::Profile;
+use crate::config::PurgePolicy;
 use crate::config::StepDownPolicy;
 use crate::config::error::ConfigError;
 #[cfg(feature = "clap")]
 use crate::config::parser::parse_bytes_with_unit;
 #[cfg(feature = "clap")]
+use crate::config::parser::parse_purge_policy;
+#[cfg(feature = "clap")]
 use crate::config::parser::parse_snapshot_policy;
 #[cfg(feature = "clap")]
 use crate::config::parser::parse_step_down_policy;
@@ -194,6 +198,18 @@ pub struct Config {
     #[cfg_attr(feature = "clap", clap(long, default_value_t = DEFAULTS.heartbeat_interval))]
     pub heartbeat_interval: u64,
 
+    /// The timeout in milliseconds for an `AppendEntries` RPC that carries log entries.
+    ///
+    /// An `AppendEntries` without entries, such as a heartbeat or a commit index update, is small
+    /// and should fail fast: its timeout is [`heartbeat_interval`](Self::heartbeat_interval). A
+    /// batch of entries takes longer to transfer and to persist on the follower, and is given
+    /// this longer budget so that it is not failed and resent while still in progress.
+    ///
+    /// Defaults to [`election_timeout_min`](Self::election_timeout_min).
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long))]
+    pub append_entries_timeout: Option<u64>,
+
     /// The timeout for sending then installing the last snapshot segment,
     /// in millisecond. It is also used as the timeout for sending a non-last segment if
     /// `send_snapshot_timeout` is 0.
@@ -218,6 +234,62 @@ pub struct Config {
     #[cfg_attr(feature = "clap", clap(long, default_value_t = DEFAULTS.max_payload_entries))]
     pub max_payload_entries: u64,
 
+    /// The maximum number of bytes of log entries per payload transmitted during replication.
+    ///
+    /// A leader stops adding log entries to an `AppendEntries` request once their total
+    /// [`RaftEntry::approx_size`](crate::entry::RaftEntry::approx_size) would exceed this, so that
+    /// a few large entries do not exceed the message size limit of the network. A request carries
+    /// at least one entry, even if it is larger. Both this and
+    /// [`max_payload_entries`](Self::max_payload_entries) apply.
+    ///
+    /// `None` (the default) does not limit the bytes per payload.
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long))]
+    pub max_payload_bytes: Option<u64>,
+
+    /// The minimum number of entries per payload when the payload size adapts to the follower.
+    ///
+    /// When set, the number of entries a leader sends to a follower in one `AppendEntries`
+    /// request starts at this value and adapts within `[min_payload_entries,
+    /// max_payload_entries]`: it grows while the follower acknowledges requests within
+    /// [`payload_target_rtt`](Self::payload_target_rtt), and shrinks when a request times out or
+    /// the follower accepts only part of it. A fast follower soon receives full payloads, while a
+    /// slow one, or one behind a slow link, receives requests it can acknowledge before they time
+    /// out.
+    ///
+    /// `None` (the default) always sends up to [`max_payload_entries`](Self::max_payload_entries).
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long))]
+    pub min_payload_entries: Option<u64>,
+
+    /// The round-trip time in milliseconds below which an acknowledged `AppendEntries` request
+    /// grows the payload size, see [`min_payload_entries`](Self::min_payload_entries).
+    ///
+    /// Defaults to [`heartbeat_interval`](Self::heartbeat_interval).
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long))]
+    pub payload_target_rtt: Option<u64>,
+
+    /// The maximum number of bytes per second a leader replicates to a single follower.
+    ///
+    /// A follower that lags far behind is otherwise sent logs as fast as the network allows,
+    /// which may saturate the leader's NIC and delay replicating client writes to the other
+    /// followers. With a limit, `AppendEntries` requests to each follower are paced by the total
+    /// [`RaftEntry::approx_size`](crate::entry::RaftEntry::approx_size) of their entries.
+    /// Requests without entries, such as heartbeats, are never delayed.
+    ///
+    /// A snapshot is sent by [`RaftNetworkV2::full_snapshot`], which gets the limit from
+    /// [`RPCOption::max_bytes_rate`]; the chunked snapshot transport in `openraft-legacy` paces
+    /// its chunks by it.
+    ///
+    /// `None` (the default) or `0` does not limit the replication rate.
+    ///
+    /// [`RaftNetworkV2::full_snapshot`]: crate::network::v2::RaftNetworkV2::full_snapshot
+    /// [`RPCOption::max_bytes_rate`]: crate::network::RPCOption::max_bytes_rate
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long))]
+    pub max_replication_rate: Option<u64>,
+
     /// The maximum number of log entries per append I/O operation.
     ///
     /// When multiple `AppendEntries` commands are queued, Openraft can merge them into
@@ -232,6 +304,74 @@ pub struct Config {
     #[cfg_attr(feature = "clap", clap(long, default_value = "4096"))]
     pub max_append_entries: Option<u64>,
 
+    /// The maximum number of log entries a leader keeps in flight to a single follower.
+    ///
+    /// An entry is in flight from when it is sent in an `AppendEntries` request until the
+    /// follower acknowledges it. Once the window is full, the replication stream holds back new
+    /// requests until earlier ones are acknowledged, so that a slow follower is not overrun with
+    /// a backlog it cannot persist before the RPC times out. The window may be exceeded by at
+    /// most one payload of [`max_payload_entries`](Self::max_payload_entries).
+    ///
+    /// `None` (the default) does not limit the in-flight entries.
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long))]
+    pub append_receive_window: Option<u64>,
+
+    /// The maximum number of `AppendEntries` requests a leader keeps in flight to a single
+    /// follower.
+    ///
+    /// A request is in flight from when it is sent until the follower acknowledges it. The leader
+    /// sends the following requests without waiting for the earlier ones to be acknowledged, until
+    /// this many are in flight, so that the replication throughput is not bounded by the
+    /// round-trip time. If a follower rejects a request, e.g., because of a conflicting log, the
+    /// requests sent after it are discarded, and replication restarts from the conflict.
+    ///
+    /// `None` (the default) does not limit the in-flight requests. If
+    /// [`append_receive_window`](Self::append_receive_window) is also set, both limits apply.
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long))]
+    pub max_inflight_append_entries: Option<u64>,
+
+    /// Stream the log entries following a snapshot while the snapshot is still being sent.
+    ///
+    /// By default, when a follower lags behind the purged logs, the leader sends it a snapshot
+    /// and only starts sending the following log entries after the snapshot is installed. With
+    /// this enabled, the leader starts streaming the log entries after its current snapshot at
+    /// once, and the follower holds them until the snapshot is installed, so that a new voter
+    /// catches up without waiting for the two phases one after another.
+    ///
+    /// A follower holds at most [`max_append_entries`](Self::max_append_entries) entries this
+    /// way; beyond that, or if the request times out, the leader falls back to sending the log
+    /// entries after the snapshot is installed. Set
+    /// [`append_receive_window`](Self::append_receive_window) below it, and
+    /// [`append_entries_timeout`](Self::append_entries_timeout) long enough to cover sending a
+    /// snapshot, to keep the log entries in flight.
+    ///
+    /// Defaults to `false`.
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    ))]
+    pub pipeline_snapshot_tail: Option<bool>,
+
+    /// The minimum interval in milliseconds between two commit-only notifications a leader sends
+    /// to a follower.
+    ///
+    /// While log entries are being replicated, the committed log id rides on the `AppendEntries`
+    /// requests that carry them. Once a follower has all the entries, a leader tells it about a
+    /// later commit with an `AppendEntries` request without entries, sent as soon as the commit
+    /// advances, independent of [`heartbeat_interval`](Self::heartbeat_interval): followers that
+    /// serve reads apply without waiting for the next heartbeat. With this option set, commit
+    /// advances within the interval are coalesced into one such notification at the end of it,
+    /// so that a commit-only request is sent to a follower at most once per interval.
+    ///
+    /// `None` (the default) or `0` notifies every commit advance at once.
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long))]
+    pub commit_notify_interval: Option<u64>,
+
     /// The distance behind in log replication a follower must fall before it is considered lagging
     ///
     /// - Followers that fall behind this index are replicated with a snapshot.
@@ -256,7 +396,9 @@ pub struct Config {
 
     /// The maximum number of logs to keep that are already included in **snapshot**.
     ///
-    /// Logs that are not in a snapshot will never be purged.
+    /// Logs that are not in a snapshot will never be purged, unless
+    /// [`purge_policy`](Self::purge_policy) is [`PurgePolicy::DurableApplied`], in which case this
+    /// many logs are kept before the last persisted applied log.
     #[cfg_attr(feature = "clap", clap(long, default_value_t = DEFAULTS.max_in_snapshot_log_to_keep))]
     pub max_in_snapshot_log_to_keep: u64,
 
@@ -311,6 +453,20 @@ pub struct Config {
     #[cfg_attr(feature = "clap", clap(long))]
     pub log_stage_capacity: Option<u64>,
 
+    /// The target time in milliseconds for a log entry appended by the leader to be acknowledged
+    /// by a quorum.
+    ///
+    /// Entries taking longer are counted in
+    /// [`QuorumAckLatencyMetrics::slo_breaches`](crate::metrics::QuorumAckLatencyMetrics::slo_breaches)
+    /// and reported to
+    /// [`MetricsRecorder::increment_quorum_ack_slo_breach`](crate::metrics::MetricsRecorder::increment_quorum_ack_slo_breach).
+    /// Unlike the apply latency, this covers only local storage and replication.
+    ///
+    /// `None` (the default) does not count breaches; the latency percentiles are reported anyway.
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long))]
+    pub quorum_ack_slo: Option<u64>,
+
     /// Enable or disable tick.
     ///
     /// If ticking is disabled, timeout-based events are all disabled:
@@ -415,7 +571,9 @@ pub struct Config {
     /// Pre-Vote uses a separate network RPC
     /// ([`RaftNetworkV2::pre_vote`](crate::network::v2::RaftNetworkV2::pre_vote)). A peer whose
     /// network does not implement it is counted as granting the Pre-Vote, so a cluster mid-upgrade
-    /// stays live.
+    /// stays live. Once a voter responds without advertising
+    /// [`Capabilities::PRE_VOTE`](crate::raft::Capabilities::PRE_VOTE), e.g., one running an older
+    /// version, this node runs plain elections until that voter is seen with it.
     #[since(version = "0.10.0")]
     // clap 4 requires `num_args = 0..=1`, or it complains about missing arg error
     // https://github.com/clap-rs/clap/discussions/4374
@@ -426,6 +584,595 @@ pub struct Config {
     ))]
     pub enable_pre_vote: Option<bool>,
 
+    /// The number of failed elections within
+    /// [`election_storm_window`](Self::election_storm_window) that trips the election storm circuit
+    /// breaker.
+    ///
+    /// An election fails if no leader is established before this node starts the next one, e.g.,
+    /// when voters keep splitting their votes. Once tripped, the delay before the next election
+    /// doubles for every further failed election, up to `64 * election_timeout`, instead of
+    /// bumping the term every election timeout and disturbing any leader that does form. The
+    /// backoff is cleared as soon as a leader is established.
+    ///
+    /// `None` (the default) disables the circuit breaker.
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long))]
+    pub election_storm_threshold: Option<u64>,
+
+    /// The sliding window in milliseconds in which failed elections are counted towards
+    /// [`election_storm_threshold`](Self::election_storm_threshold).
+    ///
+    /// Defaults to 10000.
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long))]
+    pub election_storm_window: Option<u64>,
+
+    /// Whether to adapt the range the election timeout is drawn from to the observed stability of
+    /// leadership.
+    ///
+    /// The election timeout is drawn at random from
+    /// [`election_timeout_min`](Self::election_timeout_min) to
+    /// [`election_timeout_max`](Self::election_timeout_max). A wide range makes split votes
+    /// unlikely, a narrow one detects a lost leader sooner; which one fits depends on the network
+    /// and load of a deployment. With this option, every time a leader has been stable for 64
+    /// election timeouts, the upper bound of the range is moved half way towards
+    /// `election_timeout_min`, down to `1/8` of the configured range. Every time an election
+    /// started by this node fails, the range is widened back by a step, up to the configured
+    /// range. The election timeout is redrawn from the new range every time it changes.
+    ///
+    /// Defaults to `false`.
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    ))]
+    pub adaptive_election_timeout: Option<bool>,
+
+    /// Whether to break ties between the two voters of a 2-voter cluster by their node ids,
+    /// instead of by randomized election timeouts.
+    ///
+    /// With two voters, a leader needs the votes of both. When both time out at about the same
+    /// time, e.g., after a partition heals, each votes for itself, and the election is repeated
+    /// until the randomized timeouts happen to be far enough apart. With this option, the voter
+    /// with the smaller node id times out after
+    /// [`election_timeout_min`](Self::election_timeout_min), without randomization, so that it
+    /// detects that the leader is gone as early as possible. The other voter times out after
+    /// [`election_timeout_max`](Self::election_timeout_max), so that it does not compete with
+    /// it. The gap between the two must be longer than a vote round trip.
+    ///
+    /// The cost is availability: when the voter with the smaller id is the one that is lost, the
+    /// other one waits longer before it starts an election it can not win anyway.
+    ///
+    /// It applies only while the effective membership has exactly two voters.
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    ))]
+    pub two_voter_tie_breaker: Option<bool>,
+
+    /// Whether a Leader steps down when it has not heard from a quorum of voters for an election
+    /// timeout.
+    ///
+    /// A Leader that is partitioned away from a quorum, or that can only send but not receive,
+    /// keeps believing it is the Leader while the rest of the cluster may elect another one.
+    /// With this option, the Leader checks on every tick when a quorum last acknowledged a
+    /// heartbeat or a replication request. If it is more than an election timeout ago, the
+    /// Leader stops leading and starts an election at the next term, in which it is re-elected
+    /// only if it can reach a quorum again. Thus it stops accepting writes and serving lease
+    /// reads it can no longer be sure of.
+    ///
+    /// A Leader that steps down becomes a Follower of the same term, and elects when its election
+    /// timer expires, through a Pre-Vote if [`enable_pre_vote`](Self::enable_pre_vote) is set.
+    /// It does not increase the term at once, which would disrupt the quorum it is partitioned
+    /// from.
+    ///
+    /// A Leader only learns that a quorum is reachable from the responses to the requests it
+    /// sends: this option requires [`enable_heartbeat`](Self::enable_heartbeat), otherwise a
+    /// Leader of an idle cluster steps down.
+    ///
+    /// Defaults to `false`.
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    ))]
+    pub check_quorum: Option<bool>,
+
+    /// The delay in milliseconds after which a Vote or Pre-Vote request that has not been answered
+    /// yet is sent again over a second connection.
+    ///
+    /// A stalled connection to a voter delays an election by up to an RPC timeout, exactly when
+    /// the cluster has no leader. With this option, the candidate creates a second connection to
+    /// every voter with [`RaftNetworkFactory::new_client()`], and sends the same request on it if
+    /// the first one does not respond within this delay. The first successful response is used.
+    /// Sending a vote request twice is safe because a voter grants the same vote repeatedly.
+    ///
+    /// `None` or `0` (the default) disables hedging.
+    ///
+    /// [`RaftNetworkFactory::new_client()`]: crate::network::RaftNetworkFactory::new_client
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long))]
+    pub vote_hedge_delay: Option<u64>,
+
+    /// The maximum clock drift in milliseconds between nodes, subtracted from the leader lease
+    /// when serving a [`ReadPolicy::LeaseRead`].
+    ///
+    /// A leader serves a lease read without contacting followers only while its lease, i.e.,
+    /// [`election_timeout_max`](Self::election_timeout_max) since the last time a quorum
+    /// acknowledged it, has not expired. A follower measures the same lease with its own clock; if
+    /// that clock runs faster, another leader may be elected before the leader's lease expires.
+    /// This margin shortens the lease the leader relies on, so that a read is rejected before any
+    /// follower may vote for another candidate.
+    ///
+    /// It must be smaller than `election_timeout_max`. Defaults to 0.
+    ///
+    /// [`ReadPolicy::LeaseRead`]: crate::ReadPolicy::LeaseRead
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long))]
+    pub lease_read_clock_drift: Option<u64>,
+
+    /// The number of most recent [`RaftMetrics`](crate::RaftMetrics) snapshots to retain.
+    ///
+    /// The retained snapshots can be queried with
+    /// [`Raft::metrics_history()`](crate::Raft::metrics_history), e.g., to inspect what happened
+    /// right before a failure, without an external scraper having been running. Consecutive
+    /// identical snapshots are recorded only once.
+    ///
+    /// `None` (the default) or `0` disables the history.
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long))]
+    pub metrics_history_size: Option<u64>,
+
+    /// The minimum interval in milliseconds between two publications of the metrics.
+    ///
+    /// By default the metrics are published every time `RaftCore` finishes handling a batch of
+    /// events, which under heavy write load means a [`RaftMetrics`](crate::RaftMetrics) is built
+    /// and sent on every state change. With this option set, changes within the interval are
+    /// coalesced into one publication at the end of it; the metrics never lag behind for longer
+    /// than this interval.
+    ///
+    /// `None` (the default) or `0` publishes on every change.
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long))]
+    pub metrics_flush_interval: Option<u64>,
+
+    /// Delay in milliseconds before a committed log entry is applied on this node, when it is not
+    /// the leader.
+    ///
+    /// This makes the node a time-delayed replica: it still accepts and acknowledges replicated
+    /// logs at once, but its state machine lags behind the cluster by this delay. When an operator
+    /// error is committed, e.g., a bad write, the delayed replica can still be read, or a snapshot
+    /// of it taken, before the write is applied to it.
+    ///
+    /// Such a node should be a learner: a delayed voter that becomes leader applies without delay,
+    /// but it first has to apply all the logs it delayed before it can serve clients.
+    ///
+    /// Getting, receiving or installing a snapshot does not wait for the delay: to install one,
+    /// the delayed logs before it are applied at once.
+    ///
+    /// `None` (the default) or `0` applies at once.
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long))]
+    pub apply_delay: Option<u64>,
+
+    /// The max number of log entries applied per second on this node, when it is not the leader.
+    ///
+    /// A follower that rejoins after a long absence has to apply all the logs it missed. Without a
+    /// limit it applies them as fast as it can, which may saturate a disk shared with other
+    /// services. With a limit, its state machine lags behind the committed log instead, which is
+    /// visible in [`RaftMetrics`](crate::metrics::RaftMetrics) as `last_applied` behind
+    /// `committed`, and as
+    /// [`apply_throttled_until`](crate::metrics::RaftMetrics::apply_throttled_until).
+    ///
+    /// A leader is never throttled: clients are waiting for the results.
+    ///
+    /// `None` (the default) or `0` does not limit the apply rate.
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long))]
+    pub max_apply_rate: Option<u64>,
+
+    /// The number of most recently applied entries whose responses are retained for lookup.
+    ///
+    /// A client that lost the response to a write, e.g., to a network error, can retrieve it with
+    /// [`Raft::applied_result()`](crate::Raft::applied_result) by the log id, or with
+    /// [`Raft::applied_result_by_correlation_id()`](crate::Raft::applied_result_by_correlation_id),
+    /// instead of proposing the write again. Only the responses the state machine sends with
+    /// [`ApplyResponder::send_and_cache()`](crate::storage::ApplyResponder::send_and_cache) are
+    /// retained, i.e., those of the entries proposed on this node.
+    ///
+    /// `None` (the default) or `0` disables the cache.
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long))]
+    pub applied_result_cache_size: Option<u64>,
+
+    /// The time in milliseconds a client write is held on this node while no leader is known,
+    /// instead of being rejected at once.
+    ///
+    /// During a leader failover, a follower or a learner does not know the leader, and rejects a
+    /// client write with a [`ForwardToLeader`](crate::errors::ForwardToLeader) error that carries
+    /// no leader: the client has to retry until a leader is elected. With this option set, the
+    /// write is held until a leader is known. It is then proposed if this node became the leader,
+    /// or rejected with a `ForwardToLeader` error carrying the new leader, so that the client can
+    /// forward it at once. A write held for longer than this is rejected as before.
+    ///
+    /// At most [`max_held_writes`](Self::max_held_writes) entries are held.
+    ///
+    /// `None` (the default) or `0` rejects at once.
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long))]
+    pub leaderless_write_hold: Option<u64>,
+
+    /// The max number of log entries held by
+    /// [`leaderless_write_hold`](Self::leaderless_write_hold); a write beyond it is rejected at
+    /// once.
+    ///
+    /// Defaults to 1024.
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long))]
+    pub max_held_writes: Option<u64>,
+
+    /// The write rate, in log entries appended per second, above which a snapshot build triggered
+    /// by [`snapshot_policy`](Self::snapshot_policy) is deferred.
+    ///
+    /// Building a snapshot competes with writes for disk and CPU, so it is postponed to a quieter
+    /// period, for at most [`snapshot_max_defer`](Self::snapshot_max_defer). Snapshots triggered
+    /// with [`Raft::trigger()`](crate::Raft::trigger) are never deferred.
+    ///
+    /// `None` (the default) does not defer on the write rate.
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long))]
+    pub snapshot_defer_write_rate: Option<u64>,
+
+    /// The number of committed but not yet applied log entries above which a snapshot build
+    /// triggered by [`snapshot_policy`](Self::snapshot_policy) is deferred.
+    ///
+    /// Like [`snapshot_defer_write_rate`](Self::snapshot_defer_write_rate), the build is deferred
+    /// for at most [`snapshot_max_defer`](Self::snapshot_max_defer).
+    ///
+    /// `None` (the default) does not defer on the apply backlog.
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long))]
+    pub snapshot_defer_apply_backlog: Option<u64>,
+
+    /// The maximum time in milliseconds a snapshot build is deferred because of the load.
+    ///
+    /// Once reached, the snapshot is built even if the node is still busy, so that the log does
+    /// not grow without bound under a sustained load.
+    ///
+    /// Defaults to 60000.
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long))]
+    pub snapshot_max_defer: Option<u64>,
+
+    /// The maximum disk space in bytes the logs and snapshots of this node may use, e.g., `10GiB`.
+    ///
+    /// The usage is reported by the
+    /// [`StorageUsageProbe`](crate::storage::StorageUsageProbe) installed with
+    /// [`Raft::set_storage_usage_probe()`](crate::Raft::set_storage_usage_probe). When it exceeds
+    /// the quota, a snapshot is built and all the logs it includes are purged. If the usage is
+    /// still above the quota, client writes are rejected with
+    /// [`StorageFull`](crate::errors::StorageFull) until it is back under the quota. Membership
+    /// changes are still accepted, so that capacity can be added to the cluster.
+    ///
+    /// `None` (the default) enforces no quota.
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long, value_parser=parse_bytes_with_unit))]
+    pub storage_quota: Option<u64>,
+
+    /// The approximate size in bytes of the commands queued in `RaftCore` above which it stops
+    /// taking new client and API requests, e.g., `64MiB`.
+    ///
+    /// When storage or the state machine falls behind, the commands the engine outputs, such as
+    /// appending entries, and the responses waiting for an IO to complete pile up in memory. With a
+    /// limit, `RaftCore` stops reading its input channel until the queue drains below it, so
+    /// that the channel fills up and callers wait, instead of the queue growing without bound.
+    /// IO completions are still processed, so that the queue drains. The size counts the
+    /// in-memory size of each command and of the entries it carries, not the heap data the
+    /// entries own.
+    ///
+    /// The queued size and the number of stalls are recorded in
+    /// [`RuntimeStats`](crate::stats::RuntimeStats).
+    ///
+    /// `None` (the default) or `0` does not limit the queue.
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long, value_parser=parse_bytes_with_unit))]
+    pub max_command_queue_bytes: Option<u64>,
+
+    /// Keep a follower participating read-only when its storage fails, instead of stopping it.
+    ///
+    /// By default a storage error stops `RaftCore` with
+    /// [`Fatal::StorageError`](crate::errors::Fatal::StorageError). With this enabled, a follower
+    /// or learner hitting one stops all IO and runs degraded, answering every request at once:
+    /// - It acknowledges the heartbeats of its leader whose `prev_log_id` is already durable, so
+    ///   that a transient disk hiccup does not shrink the quorum keeping the leader's lease.
+    /// - It rejects vote requests, because granting one requires persisting the vote, but still
+    ///   answers pre-vote requests.
+    /// - It answers client writes, membership changes and reads with a
+    ///   [`ForwardToLeader`](crate::errors::ForwardToLeader) error.
+    /// - Any other request fails with the storage error.
+    ///
+    /// A request that needs IO, such as appending entries or installing a snapshot, retries the
+    /// storage: if the vote can be saved again and the durable log matches the in-memory one, the
+    /// node leaves degraded mode and handles the request as usual. A state machine error is not
+    /// retried.
+    ///
+    /// The error is reported in [`RaftMetrics::storage_error`](crate::RaftMetrics::storage_error)
+    /// while degraded. A leader or a candidate still stops.
+    ///
+    /// Defaults to `false`.
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    ))]
+    pub degrade_on_storage_error: Option<bool>,
+
+    /// Acknowledge replicated log entries on receipt, without waiting for them to be flushed to
+    /// disk. **Only enable it for a cluster whose data can be rebuilt**, such as a cache or a
+    /// cluster of derived data.
+    ///
+    /// A follower in this mode acknowledges `AppendEntries` as soon as the entries are submitted
+    /// to [`RaftLogStorage`](crate::storage::RaftLogStorage), which cuts the fsync out of the
+    /// commit latency. In exchange, a committed log entry is lost if a quorum of nodes crash
+    /// before flushing it.
+    ///
+    /// All the nodes of a cluster must use the same mode. The mode is carried in vote requests,
+    /// and a node does not grant its vote to a candidate in the other mode, so a mixed-mode
+    /// cluster can only elect a leader from the nodes agreeing with a quorum. A follower
+    /// acknowledges on receipt only to a Leader it voted for in this mode; to any other Leader it
+    /// acknowledges after the flush.
+    ///
+    /// Defaults to `false`.
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    ))]
+    pub relaxed_durability: Option<bool>,
+
+    /// Stamp each log entry with the leader's wall-clock time when it is proposed.
+    ///
+    /// The timestamp, in milliseconds since the UNIX epoch, is carried in the entry header rather
+    /// than the payload, and is replicated with the entry. The state machine reads it with
+    /// [`RaftEntry::timestamp()`](crate::entry::RaftEntry::timestamp) when applying the entry, so
+    /// every node sees the same time for an entry, e.g., to expire keys with a TTL.
+    ///
+    /// All the entries proposed in one batch share the same timestamp. Timestamps come from the
+    /// leader's clock: they may go backward when the leadership changes to a node whose clock is
+    /// behind.
+    ///
+    /// Ignored if the log entry type does not store a timestamp, see
+    /// [`RaftEntry::set_timestamp()`](crate::entry::RaftEntry::set_timestamp).
+    ///
+    /// Defaults to `false`.
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    ))]
+    pub entry_timestamp: Option<bool>,
+
+    /// Chain each log entry to the entry before it with a hash, to make the log tamper-evident.
+    ///
+    /// When an entry is appended to the local log, it is given a SHA-256 chain hash computed with
+    /// [`entry::chain_hash()`] from the chain hash of the entry before it and the hash of its
+    /// [`RaftEntry::encode_content()`]. The leader computes it; a follower verifies the chain hash
+    /// an entry carries, and stops with a [`StorageError`] if it does not follow the local log.
+    /// The chain is verified again when the entries are applied to the state machine, and it can
+    /// be verified on demand with [`Raft::verify_log_chain()`], or by an external auditor with
+    /// [`entry::verify_log_chain()`].
+    ///
+    /// The chain can not be verified across a snapshot: the first entry after the logs purged
+    /// by a snapshot is trusted as the anchor of the chain.
+    ///
+    /// It requires the `audit-log-chain` feature, otherwise [`Config::validate()`] returns
+    /// [`ConfigError::AuditLogChainDisabled`]. It is ignored if the log entry type does not store a
+    /// chain hash, see [`RaftEntry::set_chain_hash()`], or can not encode its content: the
+    /// built-in [`Entry`](crate::Entry) encodes it with [`EncodeContent`]. All the nodes of a
+    /// cluster should enable it together.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// [`entry::chain_hash()`]: crate::entry::chain_hash
+    /// [`entry::verify_log_chain()`]: crate::entry::verify_log_chain
+    /// [`RaftEntry::encode_content()`]: crate::entry::RaftEntry::encode_content
+    /// [`RaftEntry::set_chain_hash()`]: crate::entry::RaftEntry::set_chain_hash
+    /// [`EncodeContent`]: crate::entry::EncodeContent
+    /// [`StorageError`]: crate::StorageError
+    /// [`Raft::verify_log_chain()`]: crate::Raft::verify_log_chain
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    ))]
+    pub audit_log_chain: Option<bool>,
+
+    /// The maximum number of log entries a learner added with
+    /// [`ChangeMembers::AddVotersWhenCaughtUp`] may lag behind the leader's last log when it is
+    /// promoted to a voter.
+    ///
+    /// The leader checks the replication progress of such a learner on every replication
+    /// response, and proposes the membership change that makes it a voter as soon as it is within
+    /// this many entries of the leader's last log.
+    ///
+    /// Defaults to [`replication_lag_threshold`](Self::replication_lag_threshold).
+    ///
+    /// [`ChangeMembers::AddVotersWhenCaughtUp`]: crate::ChangeMembers::AddVotersWhenCaughtUp
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long))]
+    pub promote_lag_threshold: Option<u64>,
+
+    /// The max time in milliseconds [`Raft::replace_node()`] waits for the new node to catch up
+    /// with the leader before swapping it in.
+    ///
+    /// If the new node does not catch up in time, the replacement is rolled back and fails with
+    /// [`LearnerNotCaughtUp`].
+    ///
+    /// Defaults to 60 seconds.
+    ///
+    /// [`Raft::replace_node()`]: crate::Raft::replace_node
+    /// [`LearnerNotCaughtUp`]: crate::errors::LearnerNotCaughtUp
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long))]
+    pub replace_node_timeout: Option<u64>,
+
+    /// The maximum number of snapshots a leader sends to followers or learners at the same time.
+    ///
+    /// Sending a snapshot reads the whole state machine snapshot and keeps it in flight until it
+    /// is installed; several lagging nodes requesting a snapshot at once can exhaust the memory or
+    /// the IO of the leader. A snapshot transfer requested while this many are running is queued
+    /// and started when one of them finishes. The state of every transfer is reported in
+    /// [`RaftMetrics::snapshot_transfers`](crate::RaftMetrics::snapshot_transfers).
+    ///
+    /// `None` (the default) or `0` does not limit the number of snapshot transfers.
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long))]
+    pub max_inflight_snapshots: Option<u64>,
+
+    /// Warm up the state machine after installing a snapshot, before reporting it ready for reads.
+    ///
+    /// When enabled, [`RaftStateMachine::warm_up()`] is called after a snapshot is installed,
+    /// e.g., to populate block caches. Until it returns,
+    /// [`RaftMetrics::warming_up`](crate::RaftMetrics::warming_up) is the last log id of the
+    /// snapshot, so that a load balancer does not send reads to a cold replica. The start and the
+    /// end of the warm-up are also delivered as [`RaftEvent`](crate::RaftEvent)s.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// [`RaftStateMachine::warm_up()`]: crate::storage::RaftStateMachine::warm_up
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    ))]
+    pub warm_up_after_install: Option<bool>,
+
+    /// The size in bytes of an application entry above which it is applied from a stream of its
+    /// payload, instead of being decoded in memory, e.g., `64MiB`.
+    ///
+    /// The state machine worker reads the size of the entries to apply with
+    /// [`RaftLogReader::get_log_meta()`]. An entry at least this large is applied with
+    /// [`RaftStateMachine::apply_payload_stream()`], reading its payload from
+    /// [`RaftLogReader::payload_stream()`], so that applying a huge value does not need as much
+    /// memory at once. The log storage should implement these methods without decoding the
+    /// payload, and the state machine must implement `apply_payload_stream()`.
+    ///
+    /// Entries are not streamed with [`audit_log_chain`](Self::audit_log_chain) enabled, which
+    /// verifies each decoded entry before applying it.
+    ///
+    /// `None` (the default) or `0` applies every entry decoded.
+    ///
+    /// [`RaftLogReader::get_log_meta()`]: crate::storage::RaftLogReader::get_log_meta
+    /// [`RaftLogReader::payload_stream()`]: crate::storage::RaftLogReader::payload_stream
+    /// [`RaftStateMachine::apply_payload_stream()`]: crate::storage::RaftStateMachine::apply_payload_stream
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long, value_parser=parse_bytes_with_unit))]
+    pub stream_payload_threshold: Option<u64>,
+
+    /// The number of snapshots to keep, including the current one.
+    ///
+    /// Openraft tracks the meta of the most recent snapshots, readable with
+    /// [`Raft::snapshot_history()`](crate::Raft::snapshot_history). When a new snapshot is built
+    /// or installed and more than this many are tracked, the oldest is dropped from the history
+    /// and [`RaftStateMachine::remove_snapshots_before()`] is called with the oldest one still
+    /// kept, so that the state machine deletes the older snapshot data it stored. Keeping more
+    /// than one allows restoring to an earlier point in time, and lets a snapshot being sent to a
+    /// lagging follower finish after a newer one is built.
+    ///
+    /// `None` (the default) never asks the state machine to remove a snapshot; a value less
+    /// than `1` is treated as `1`.
+    ///
+    /// [`RaftStateMachine::remove_snapshots_before()`]: crate::storage::RaftStateMachine::remove_snapshots_before
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long))]
+    pub snapshot_keep_count: Option<u64>,
+
+    /// Reject a membership in which two nodes have equal node info, e.g., the same address.
+    ///
+    /// Giving two node ids the same address is a frequent operator error: both ids then reach
+    /// the same process, which may vote twice or silently stop replicating to one of them. When
+    /// enabled, [`Raft::initialize()`] and [`Raft::change_membership()`] return a
+    /// [`DuplicateNode`] error for such a membership. Disable it where address reuse is
+    /// intentional, e.g., several Raft groups sharing one endpoint.
+    ///
+    /// Nodes are compared with `C::Node`'s `PartialEq`: with [`BasicNode`] this compares the
+    /// address, while a `Node` type without any info, such as [`EmptyNode`], always compares
+    /// equal and must leave this disabled.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// [`Raft::initialize()`]: crate::Raft::initialize
+    /// [`Raft::change_membership()`]: crate::Raft::change_membership
+    /// [`DuplicateNode`]: crate::errors::DuplicateNode
+    /// [`BasicNode`]: crate::BasicNode
+    /// [`EmptyNode`]: crate::EmptyNode
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    ))]
+    pub reject_duplicate_nodes: Option<bool>,
+
+    /// The policy for deciding which applied logs may be purged.
+    ///
+    /// - [`Snapshot`](PurgePolicy::Snapshot): only logs included in the last snapshot are purged.
+    /// - [`DurableApplied`](PurgePolicy::DurableApplied): logs the state machine reports as applied
+    ///   and persisted are purged too, for a state machine that does not need a snapshot to be
+    ///   restored, e.g., one backed by RocksDB.
+    ///
+    /// In CLI it is `snapshot` or `durable_applied`, e.g., `--purge-policy=durable_applied`.
+    ///
+    /// Defaults to `Snapshot`.
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long, value_parser = parse_purge_policy))]
+    pub purge_policy: Option<PurgePolicy>,
+
+    /// Evict a node from the membership once it has not acknowledged the leader for this many
+    /// milliseconds.
+    ///
+    /// The leader tracks when each voter and learner last acknowledged a replication or heartbeat
+    /// RPC. Once a node has been unreachable for longer than this, the leader proposes a
+    /// membership change that removes it, or demotes it to a learner if it is a voter and
+    /// [`evict_demote_voters`](Self::evict_demote_voters) is enabled, and emits
+    /// [`RaftEvent::NodeEvicted`]. The leader never evicts itself, and does not evict while
+    /// another membership change is in progress.
+    ///
+    /// Removing a voter shrinks the quorum, so this should be long enough to ride out a restart
+    /// or a network glitch. It must be greater than `election_timeout_max`.
+    ///
+    /// `None`, the default, disables eviction.
+    ///
+    /// [`RaftEvent::NodeEvicted`]: crate::raft::RaftEvent::NodeEvicted
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long))]
+    pub evict_unreachable_after: Option<u64>,
+
+    /// Demote an unreachable voter to a learner instead of removing it from the membership.
+    ///
+    /// A demoted node keeps receiving logs and can be promoted again once it is back. When
+    /// enabled, unreachable learners are left in place too, so that a demoted node is not
+    /// removed later on.
+    ///
+    /// It has no effect unless [`evict_unreachable_after`](Self::evict_unreachable_after) is
+    /// set. Defaults to `false`.
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "clap", clap(long,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    ))]
+    pub evict_demote_voters: Option<bool>,
+
     /// Default backoff policy used when
     /// [`RaftNetworkV2::backoff`](crate::network::RaftNetworkV2::backoff) returns `None`.
     ///
@@ -547,9 +1294,13 @@ impl Default for Config {
             election_timeout_min: DEFAULTS.election_timeout_min,
             election_timeout_max: DEFAULTS.election_timeout_max,
             heartbeat_interval: DEFAULTS.heartbeat_interval,
+            append_entries_timeout: None,
             install_snapshot_timeout: DEFAULTS.install_snapshot_timeout,
             send_snapshot_timeout: DEFAULTS.send_snapshot_timeout,
             max_payload_entries: DEFAULTS.max_payload_entries,
+            max_payload_bytes: None,
+            min_payload_entries: None,
+            payload_target_rtt: None,
             max_append_entries: Some(DEFAULTS.max_append_entries),
             replication_lag_threshold: DEFAULTS.replication_lag_threshold,
             snapshot_policy: DEFAULTS.snapshot_policy.clone(),
@@ -562,11 +1313,50 @@ impl Default for Config {
             notification_channel_size: Some(DEFAULTS.notification_channel_size),
             state_machine_channel_size: Some(DEFAULTS.state_machine_channel_size),
             log_stage_capacity: None,
+            quorum_ack_slo: None,
             enable_tick: DEFAULTS.enable_tick,
             enable_heartbeat: DEFAULTS.enable_heartbeat,
             enable_elect: DEFAULTS.enable_elect,
             removed_leader_step_down: DEFAULTS.removed_leader_step_down.clone(),
             enable_pre_vote: DEFAULTS.enable_pre_vote,
+            election_storm_threshold: None,
+            election_storm_window: None,
+            adaptive_election_timeout: None,
+            two_voter_tie_breaker: None,
+            check_quorum: None,
+            vote_hedge_delay: None,
+            lease_read_clock_drift: None,
+            metrics_history_size: None,
+            metrics_flush_interval: None,
+            apply_delay: None,
+            max_apply_rate: None,
+            max_replication_rate: None,
+            applied_result_cache_size: None,
+            leaderless_write_hold: None,
+            max_held_writes: None,
+            snapshot_defer_write_rate: None,
+            snapshot_defer_apply_backlog: None,
+            snapshot_max_defer: None,
+            append_receive_window: None,
+            max_inflight_append_entries: None,
+            pipeline_snapshot_tail: None,
+            commit_notify_interval: None,
+            storage_quota: None,
+            max_command_queue_bytes: None,
+            degrade_on_storage_error: None,
+            relaxed_durability: None,
+            entry_timestamp: None,
+            audit_log_chain: None,
+            promote_lag_threshold: None,
+            replace_node_timeout: None,
+            max_inflight_snapshots: None,
+            warm_up_after_install: None,
+            stream_payload_threshold: None,
+            snapshot_keep_count: None,
+            reject_duplicate_nodes: None,
+            purge_policy: None,
+            evict_unreachable_after: None,
+            evict_demote_voters: None,
             backoff: DEFAULTS.backoff.to_string(),
             allow_log_reversion: None,
             enable_leader_restore: None,
@@ -575,11 +1365,85 @@ impl Default for Config {
 }
 
 impl Config {
+    /// Build a config with vetted values for a common deployment [`Profile`].
+    ///
+    /// A preset sets the heartbeat and election timeouts, which also bound the leader lease, the
+    /// payload limits, and the snapshot and purge policies. The other fields are the defaults.
+    /// Any field can still be overridden:
+    ///
+    /// ```ignore
+    /// use openraft::Config;
+    /// use openraft::Profile;
+    ///
+    /// let config = Config {
+    ///     cluster_name: "geo".to_string(),
+    ///     heartbeat_interval: 300,
+    ///     ..Config::preset(Profile::WanGeo)
+    /// };
+    ///
+    /// let config = config.validate()?;
+    /// ```
+    #[since(version = "0.10.0")]
+    pub fn preset(profile: Profile) -> Self {
+        let default = Self::default();
+
+        match profile {
+            Profile::LanLowLatency => Self {
+                heartbeat_interval: 20,
+                election_timeout_min: 100,
+                election_timeout_max: 200,
+                install_snapshot_timeout: 500,
+                max_payload_entries: 300,
+                enable_pre_vote: Some(true),
+                backoff: "10ms 20ms ...500ms".to_string(),
+                ..default
+            },
+            Profile::WanGeo => Self {
+                heartbeat_interval: 250,
+                election_timeout_min: 1500,
+                election_timeout_max: 3000,
+                install_snapshot_timeout: 10_000,
+                max_payload_entries: 2000,
+                replication_lag_threshold: 20_000,
+                snapshot_policy: SnapshotPolicy::LogsSinceLast(20_000),
+                max_in_snapshot_log_to_keep: 5000,
+                enable_pre_vote: Some(true),
+                backoff: "200ms 400ms ...10s".to_string(),
+                ..default
+            },
+            Profile::SingleNode => Self {
+                snapshot_policy: SnapshotPolicy::LogsSinceLast(50_000),
+                max_in_snapshot_log_to_keep: 0,
+                purge_batch_size: 1024,
+                ..default
+            },
+            Profile::TestFast => Self {
+                heartbeat_interval: 10,
+                election_timeout_min: 50,
+                election_timeout_max: 100,
+                install_snapshot_timeout: 1000,
+                max_payload_entries: 64,
+                snapshot_policy: SnapshotPolicy::LogsSinceLast(100),
+                max_in_snapshot_log_to_keep: 10,
+                backoff: "5ms 10ms ...100ms".to_string(),
+                ..default
+            },
+        }
+    }
+
     /// Generate a new random election timeout within the configured min and max values.
     pub fn new_rand_election_timeout<RT: AsyncRuntime>(&self) -> u64 {
         RT::thread_rng().random_range(self.election_timeout_min..self.election_timeout_max)
     }
 
+    /// Get the timeout for an `AppendEntries` RPC that carries log entries.
+    ///
+    /// Defaults to `election_timeout_min` if not specified.
+    #[since(version = "0.10.0")]
+    pub fn append_entries_timeout(&self) -> Duration {
+        Duration::from_millis(self.append_entries_timeout.unwrap_or(self.election_timeout_min))
+    }
+
     /// Get the timeout for sending and installing the last snapshot segment.
     pub fn install_snapshot_timeout(&self) -> Duration {
         Duration::from_millis(self.install_snapshot_timeout)
@@ -608,6 +1472,125 @@ impl Config {
         self.allow_log_reversion.unwrap_or(false)
     }
 
+    /// Whether a follower or learner keeps running degraded when its storage fails.
+    ///
+    /// By default, a storage error stops `RaftCore`.
+    pub(crate) fn degrade_on_storage_error(&self) -> bool {
+        self.degrade_on_storage_error.unwrap_or(false)
+    }
+
+    /// Whether a leader streams the log entries following a snapshot while sending the snapshot.
+    ///
+    /// By default, the log entries are sent after the snapshot is installed.
+    pub(crate) fn pipeline_snapshot_tail(&self) -> bool {
+        self.pipeline_snapshot_tail.unwrap_or(false)
+    }
+
+    /// Get the minimum interval between two commit-only notifications to a follower.
+    ///
+    /// Returns `None` if every commit advance is notified at once, which is the default.
+    pub(crate) fn commit_notify_interval(&self) -> Option<Duration> {
+        match self.commit_notify_interval {
+            None | Some(0) => None,
+            Some(ms) => Some(Duration::from_millis(ms)),
+        }
+    }
+
+    /// Whether to acknowledge replicated log entries before they are flushed.
+    ///
+    /// By default, entries are acknowledged only after they are flushed.
+    pub(crate) fn relaxed_durability(&self) -> bool {
+        self.relaxed_durability.unwrap_or(false)
+    }
+
+    /// Whether to stamp log entries with the leader's wall-clock time when they are proposed.
+    ///
+    /// By default, entries are not stamped.
+    pub(crate) fn entry_timestamp(&self) -> bool {
+        self.entry_timestamp.unwrap_or(false)
+    }
+
+    /// Whether to chain log entries with a hash.
+    ///
+    /// By default, entries are not chained.
+    pub(crate) fn audit_log_chain(&self) -> bool {
+        self.audit_log_chain.unwrap_or(false)
+    }
+
+    /// Get the maximum lag of a learner to be promoted to a voter automatically.
+    ///
+    /// Defaults to [`replication_lag_threshold`](Self::replication_lag_threshold) if not
+    /// specified.
+    pub(crate) fn promote_lag_threshold(&self) -> u64 {
+        self.promote_lag_threshold.unwrap_or(self.replication_lag_threshold)
+    }
+
+    /// Get the max time to wait for the new node of a replacement to catch up.
+    ///
+    /// Defaults to 60 seconds if not specified.
+    pub(crate) fn replace_node_timeout(&self) -> Duration {
+        Duration::from_millis(self.replace_node_timeout.unwrap_or(60_000))
+    }
+
+    /// Get the maximum number of snapshots a leader sends at the same time.
+    ///
+    /// Returns `None` if the number is not limited, which is the default.
+    pub(crate) fn max_inflight_snapshots(&self) -> Option<usize> {
+        match self.max_inflight_snapshots {
+            None | Some(0) => None,
+            Some(n) => Some(n as usize),
+        }
+    }
+
+    /// Whether to warm up the state machine after installing a snapshot.
+    ///
+    /// By default, the state machine is not warmed up.
+    pub(crate) fn warm_up_after_install(&self) -> bool {
+        self.warm_up_after_install.unwrap_or(false)
+    }
+
+    /// Get the size of an application entry above which it is applied from a payload stream.
+    ///
+    /// Returns `None` if entries are never streamed, which is the default.
+    pub(crate) fn stream_payload_threshold(&self) -> Option<u64> {
+        self.stream_payload_threshold.filter(|n| *n > 0)
+    }
+
+    /// Get the number of snapshots to keep, including the current one.
+    ///
+    /// Returns `None` if snapshots are never removed, which is the default.
+    pub(crate) fn snapshot_keep_count(&self) -> Option<usize> {
+        self.snapshot_keep_count.map(|n| n.max(1) as usize)
+    }
+
+    /// Whether to reject a membership in which two nodes have equal node info.
+    ///
+    /// By default, such a membership is accepted.
+    pub(crate) fn reject_duplicate_nodes(&self) -> bool {
+        self.reject_duplicate_nodes.unwrap_or(false)
+    }
+
+    /// Get the policy for deciding which applied logs may be purged.
+    ///
+    /// By default, only logs included in a snapshot are purged.
+    pub(crate) fn purge_policy(&self) -> PurgePolicy {
+        self.purge_policy.clone().unwrap_or_default()
+    }
+
+    /// Get how long a node may be unreachable before the leader evicts it from the membership.
+    ///
+    /// Returns `None` if nodes are never evicted, which is the default.
+    pub(crate) fn evict_unreachable_after(&self) -> Option<Duration> {
+        self.evict_unreachable_after.map(Duration::from_millis)
+    }
+
+    /// Whether to demote an unreachable voter to a learner instead of removing it.
+    ///
+    /// By default, an unreachable voter is removed.
+    pub(crate) fn evict_demote_voters(&self) -> bool {
+        self.evict_demote_voters.unwrap_or(false)
+    }
+
     /// Whether a node that was a leader before a restart restores leadership at startup, without
     /// an election.
     ///
@@ -625,6 +1608,144 @@ impl Config {
         self.enable_pre_vote.unwrap_or(false)
     }
 
+    /// Whether the election timeout range adapts to the observed stability of leadership.
+    pub(crate) fn adaptive_election_timeout(&self) -> bool {
+        self.adaptive_election_timeout.unwrap_or(false)
+    }
+
+    /// Whether the two voters of a 2-voter cluster break election ties by their node ids.
+    pub(crate) fn two_voter_tie_breaker(&self) -> bool {
+        self.two_voter_tie_breaker.unwrap_or(false)
+    }
+
+    /// Whether a Leader steps down when it has not heard from a quorum for an election timeout.
+    pub(crate) fn check_quorum(&self) -> bool {
+        self.check_quorum.unwrap_or(false)
+    }
+
+    /// The sliding window in which failed elections are counted by the election storm circuit
+    /// breaker.
+    ///
+    /// Defaults to 10 seconds if not specified.
+    pub(crate) fn election_storm_window(&self) -> Duration {
+        Duration::from_millis(self.election_storm_window.unwrap_or(10_000))
+    }
+
+    /// Get the quorum acknowledgment SLO, if set.
+    pub(crate) fn quorum_ack_slo(&self) -> Option<Duration> {
+        self.quorum_ack_slo.map(Duration::from_millis)
+    }
+
+    /// Get the round-trip time below which the adaptive payload size grows.
+    pub(crate) fn payload_target_rtt(&self) -> Duration {
+        Duration::from_millis(self.payload_target_rtt.unwrap_or(self.heartbeat_interval))
+    }
+
+    /// Get the delay before a vote request is sent again over a second connection.
+    ///
+    /// Returns `None` if vote requests are not hedged, which is the default.
+    pub(crate) fn vote_hedge_delay(&self) -> Option<Duration> {
+        match self.vote_hedge_delay {
+            None | Some(0) => None,
+            Some(ms) => Some(Duration::from_millis(ms)),
+        }
+    }
+
+    /// Get the clock drift margin subtracted from the leader lease for a lease read.
+    ///
+    /// Defaults to 0 if not specified.
+    pub(crate) fn lease_read_clock_drift(&self) -> Duration {
+        Duration::from_millis(self.lease_read_clock_drift.unwrap_or(0))
+    }
+
+    /// Get the number of [`RaftMetrics`](crate::RaftMetrics) snapshots to retain.
+    ///
+    /// Defaults to 0, i.e., disabled, if not specified.
+    pub(crate) fn metrics_history_size(&self) -> usize {
+        self.metrics_history_size.unwrap_or(0) as usize
+    }
+
+    /// Get the number of applied responses to retain.
+    ///
+    /// Defaults to 0, i.e., disabled, if not specified.
+    pub(crate) fn applied_result_cache_size(&self) -> usize {
+        self.applied_result_cache_size.unwrap_or(0) as usize
+    }
+
+    /// Get the time a client write is held while no leader is known.
+    ///
+    /// Returns `None` if writes are not held, which is the default.
+    pub(crate) fn leaderless_write_hold(&self) -> Option<Duration> {
+        match self.leaderless_write_hold {
+            None | Some(0) => None,
+            Some(ms) => Some(Duration::from_millis(ms)),
+        }
+    }
+
+    /// Get the max number of log entries held while no leader is known.
+    ///
+    /// Defaults to 1024 if not specified.
+    pub(crate) fn max_held_writes(&self) -> u64 {
+        self.max_held_writes.unwrap_or(1024)
+    }
+
+    /// Get the minimum interval between two publications of the metrics.
+    ///
+    /// Returns `None` if metrics are published on every change, which is the default.
+    pub(crate) fn metrics_flush_interval(&self) -> Option<Duration> {
+        match self.metrics_flush_interval {
+            None | Some(0) => None,
+            Some(ms) => Some(Duration::from_millis(ms)),
+        }
+    }
+
+    /// Get the delay before a committed log entry is applied on a non-leader node.
+    ///
+    /// Returns `None` if entries are applied at once, which is the default.
+    pub(crate) fn apply_delay(&self) -> Option<Duration> {
+        match self.apply_delay {
+            None | Some(0) => None,
+            Some(ms) => Some(Duration::from_millis(ms)),
+        }
+    }
+
+    /// Get the max number of log entries applied per second on a non-leader node.
+    ///
+    /// Returns `None` if the apply rate is not limited, which is the default.
+    pub(crate) fn max_apply_rate(&self) -> Option<u64> {
+        match self.max_apply_rate {
+            None | Some(0) => None,
+            Some(rate) => Some(rate),
+        }
+    }
+
+    /// Get the max number of bytes per second replicated to a follower.
+    ///
+    /// Returns `None` if the replication rate is not limited, which is the default.
+    pub(crate) fn max_replication_rate(&self) -> Option<u64> {
+        match self.max_replication_rate {
+            None | Some(0) => None,
+            Some(rate) => Some(rate),
+        }
+    }
+
+    /// Get the queued command size in bytes above which `RaftCore` stops taking new requests.
+    ///
+    /// Returns `None` if the command queue is not limited, which is the default.
+    pub(crate) fn max_command_queue_bytes(&self) -> Option<u64> {
+        match self.max_command_queue_bytes {
+            None | Some(0) => None,
+            Some(bytes) => Some(bytes),
+        }
+    }
+
+    /// Get the maximum time a snapshot build is deferred because of the load.
+    ///
+    /// Defaults to 60 seconds if not specified.
+    pub(crate) fn snapshot_max_defer(&self) -> Duration {
+        Duration::from_millis(self.snapshot_max_defer.unwrap_or(60_000))
+    }
+
     /// Get the API channel size for bounded MPSC channel.
     ///
     /// Defaults to 65536 if not specified.
@@ -681,6 +1802,53 @@ impl Config {
             return Err(ConfigError::MaxPayloadIs0);
         }
 
+        if self.max_payload_bytes == Some(0) {
+            return Err(ConfigError::MaxPayloadBytesIs0);
+        }
+
+        if let Some(min) = self.min_payload_entries
+            && (min == 0 || min > self.max_payload_entries)
+        {
+            return Err(ConfigError::MinPayloadEntries {
+                min_payload_entries: min,
+                max_payload_entries: self.max_payload_entries,
+            });
+        }
+
+        if self.election_storm_threshold == Some(0) {
+            return Err(ConfigError::ElectionStormThresholdIs0);
+        }
+
+        if self.append_receive_window == Some(0) {
+            return Err(ConfigError::AppendReceiveWindowIs0);
+        }
+
+        if self.max_inflight_append_entries == Some(0) {
+            return Err(ConfigError::MaxInflightAppendEntriesIs0);
+        }
+
+        if let Some(clock_drift) = self.lease_read_clock_drift
+            && clock_drift >= self.election_timeout_max
+        {
+            return Err(ConfigError::LeaseReadClockDrift {
+                clock_drift,
+                election_timeout_max: self.election_timeout_max,
+            });
+        }
+
+        if let Some(evict_after) = self.evict_unreachable_after
+            && evict_after <= self.election_timeout_max
+        {
+            return Err(ConfigError::EvictUnreachableAfter {
+                evict_unreachable_after: evict_after,
+                election_timeout_max: self.election_timeout_max,
+            });
+        }
+
+        if cfg!(not(feature = "audit-log-chain")) && self.audit_log_chain() {
+            return Err(ConfigError::AuditLogChainDisabled);
+        }
+
         // Validate the backoff policy string up-front so build_backoff() can assume it parses.
         BackoffSeries::parse(&self.backoff)?;
 
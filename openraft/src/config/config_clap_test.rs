@@ -6,6 +6,7 @@
 use core::time::Duration;
 
 use crate::Config;
+use crate::PurgePolicy;
 use crate::SnapshotPolicy;
 use crate::StepDownPolicy;
 
@@ -103,6 +104,24 @@ fn test_config_removed_leader_step_down() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_config_purge_policy() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(None, config.purge_policy);
+    assert_eq!(PurgePolicy::Snapshot, config.purge_policy());
+
+    let config = Config::build(&["foo", "--purge-policy=durable_applied"])?;
+    assert_eq!(Some(PurgePolicy::DurableApplied), config.purge_policy);
+
+    let config = Config::build(&["foo", "--purge-policy=snapshot"])?;
+    assert_eq!(Some(PurgePolicy::Snapshot), config.purge_policy);
+
+    let res = Config::build(&["foo", "--purge-policy=bar"]);
+    assert!(res.is_err());
+
+    Ok(())
+}
+
 #[test]
 fn test_config_enable_tick() -> anyhow::Result<()> {
     let config = Config::build(&["foo", "--enable-tick=false"])?;
@@ -0,0 +1,113 @@
+//! A digest of the config every node of a cluster must agree on.
+
+use std::fmt;
+
+use openraft_macros::since;
+
+use crate::Config;
+use crate::RaftTypeConfig;
+
+/// The safety-relevant part of the config of a node.
+///
+/// A node sends its digest along with `AppendEntries` and `Vote` requests. Nodes running with
+/// different election timeouts, durability modes or leader id types keep working at first, but
+/// may disrupt each other with elections, lose acknowledged writes, or misinterpret each other's
+/// votes; which is easy to end up with after a partial rollout of a new config.
+///
+/// A receiver compares the digest of the sender with its own, and reports a mismatch with a
+/// warning log, in [`RaftMetrics::config_mismatches`] and with
+/// [`MetricsRecorder::increment_config_mismatch`]. It does not reject the request.
+///
+/// [`RaftMetrics::config_mismatches`]: crate::metrics::RaftMetrics::config_mismatches
+/// [`MetricsRecorder::increment_config_mismatch`]: crate::metrics::MetricsRecorder::increment_config_mismatch
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ConfigDigest {
+    /// [`Config::election_timeout_min`].
+    pub election_timeout_min: u64,
+
+    /// [`Config::election_timeout_max`].
+    pub election_timeout_max: u64,
+
+    /// Whether [`Config::relaxed_durability`] is enabled.
+    pub relaxed_durability: bool,
+
+    /// A fingerprint of the [`RaftTypeConfig::LeaderId`] type, which decides whether more than
+    /// one leader can be elected in a term.
+    pub leader_id_mode: u64,
+}
+
+impl ConfigDigest {
+    /// Build the digest of a node running with `config`.
+    pub fn new<C>(config: &Config) -> Self
+    where C: RaftTypeConfig {
+        Self {
+            election_timeout_min: config.election_timeout_min,
+            election_timeout_max: config.election_timeout_max,
+            relaxed_durability: config.relaxed_durability(),
+            leader_id_mode: fnv1a(std::any::type_name::<C::LeaderId>()),
+        }
+    }
+
+    /// Returns the names of the fields that differ from `other`.
+    pub fn diff(&self, other: &Self) -> Vec<&'static str> {
+        let mut fields = vec![];
+
+        if self.election_timeout_min != other.election_timeout_min
+            || self.election_timeout_max != other.election_timeout_max
+        {
+            fields.push("election_timeout");
+        }
+        if self.relaxed_durability != other.relaxed_durability {
+            fields.push("relaxed_durability");
+        }
+        if self.leader_id_mode != other.leader_id_mode {
+            fields.push("leader_id_mode");
+        }
+
+        fields
+    }
+}
+
+impl fmt::Display for ConfigDigest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{election_timeout:[{}, {}), relaxed_durability:{}, leader_id_mode:{:016x}}}",
+            self.election_timeout_min, self.election_timeout_max, self.relaxed_durability, self.leader_id_mode
+        )
+    }
+}
+
+/// FNV-1a hash, which, unlike `DefaultHasher`, is the same on every build.
+fn fnv1a(s: &str) -> u64 {
+    s.bytes().fold(0xcbf29ce484222325, |h, b| (h ^ b as u64).wrapping_mul(0x100000001b3))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ConfigDigest;
+    use crate::Config;
+    use crate::engine::testing::UTConfig;
+
+    #[test]
+    fn test_config_digest_diff() {
+        let a = ConfigDigest::new::<UTConfig>(&Config::default());
+        assert_eq!(a, ConfigDigest::new::<UTConfig>(&Config::default()));
+        assert!(a.diff(&a).is_empty());
+
+        let b = ConfigDigest::new::<UTConfig>(&Config {
+            election_timeout_max: 1000,
+            relaxed_durability: Some(true),
+            ..Default::default()
+        });
+        assert_eq!(vec!["election_timeout", "relaxed_durability"], a.diff(&b));
+
+        let c = ConfigDigest {
+            leader_id_mode: a.leader_id_mode + 1,
+            ..a
+        };
+        assert_eq!(vec!["leader_id_mode"], a.diff(&c));
+    }
+}
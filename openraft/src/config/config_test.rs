@@ -1,6 +1,13 @@
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
 use crate::Config;
+use crate::ConfigSource;
+use crate::EffectiveConfig;
+use crate::Profile;
 use crate::SnapshotPolicy;
 use crate::StepDownPolicy;
+use crate::config::RuntimeConfig;
 use crate::config::error::ConfigError;
 
 #[test]
@@ -61,3 +68,230 @@ fn test_invalid_election_timeout_config_produces_expected_error() {
         heartbeat_interval: 1500
     });
 }
+
+#[test]
+fn test_election_storm_threshold_0_is_invalid() {
+    let config = Config {
+        election_storm_threshold: Some(0),
+        ..Default::default()
+    };
+
+    let res = config.validate();
+    assert_eq!(res.unwrap_err(), ConfigError::ElectionStormThresholdIs0);
+}
+
+#[test]
+fn test_max_payload_bytes_0_is_invalid() {
+    let config = Config {
+        max_payload_bytes: Some(0),
+        ..Default::default()
+    };
+
+    let res = config.validate();
+    assert_eq!(res.unwrap_err(), ConfigError::MaxPayloadBytesIs0);
+}
+
+#[test]
+fn test_min_payload_entries_out_of_range_is_invalid() {
+    for min in [0, 301] {
+        let config = Config {
+            min_payload_entries: Some(min),
+            ..Default::default()
+        };
+
+        let res = config.validate();
+        assert_eq!(res.unwrap_err(), ConfigError::MinPayloadEntries {
+            min_payload_entries: min,
+            max_payload_entries: 300,
+        });
+    }
+
+    let config = Config {
+        min_payload_entries: Some(300),
+        ..Default::default()
+    };
+    assert!(config.validate().is_ok());
+}
+
+#[test]
+fn test_append_receive_window_0_is_invalid() {
+    let config = Config {
+        append_receive_window: Some(0),
+        ..Default::default()
+    };
+
+    let res = config.validate();
+    assert_eq!(res.unwrap_err(), ConfigError::AppendReceiveWindowIs0);
+}
+
+#[test]
+fn test_max_inflight_append_entries_0_is_invalid() {
+    let config = Config {
+        max_inflight_append_entries: Some(0),
+        ..Default::default()
+    };
+
+    let res = config.validate();
+    assert_eq!(res.unwrap_err(), ConfigError::MaxInflightAppendEntriesIs0);
+}
+
+#[test]
+fn test_audit_log_chain_requires_feature() {
+    let config = Config {
+        audit_log_chain: Some(true),
+        ..Default::default()
+    };
+
+    let res = config.validate();
+
+    #[cfg(feature = "audit-log-chain")]
+    assert!(res.is_ok());
+
+    #[cfg(not(feature = "audit-log-chain"))]
+    assert_eq!(res.unwrap_err(), ConfigError::AuditLogChainDisabled);
+}
+
+#[test]
+fn test_append_entries_timeout() {
+    let cfg = Config {
+        election_timeout_min: 200,
+        ..Default::default()
+    };
+    assert_eq!(Duration::from_millis(200), cfg.append_entries_timeout());
+
+    let cfg = Config {
+        append_entries_timeout: Some(1000),
+        ..Default::default()
+    };
+    assert_eq!(Duration::from_millis(1000), cfg.append_entries_timeout());
+}
+
+#[test]
+fn test_config_presets_are_valid() -> anyhow::Result<()> {
+    for profile in [
+        Profile::LanLowLatency,
+        Profile::WanGeo,
+        Profile::SingleNode,
+        Profile::TestFast,
+    ] {
+        let cfg = Config::preset(profile).validate()?;
+        assert!(cfg.heartbeat_interval * 3 <= cfg.election_timeout_min, "{:?}", profile);
+    }
+
+    let cfg = Config {
+        heartbeat_interval: 300,
+        ..Config::preset(Profile::WanGeo)
+    };
+    assert_eq!(300, cfg.heartbeat_interval);
+    assert_eq!(1500, cfg.election_timeout_min);
+
+    Ok(())
+}
+
+#[test]
+fn test_effective_config_sources() -> anyhow::Result<()> {
+    let config = Config {
+        heartbeat_interval: 100,
+        enable_elect: false,
+        ..Default::default()
+    }
+    .validate()?;
+
+    let runtime = RuntimeConfig::new(&config);
+
+    let effective = EffectiveConfig::new(&config, &runtime, true);
+    assert_eq!(Some(ConfigSource::Default), effective.source("election_timeout_min"));
+    assert_eq!(Some(ConfigSource::Configured), effective.source("heartbeat_interval"));
+    assert_eq!(Some(ConfigSource::Configured), effective.source("enable_elect"));
+    assert_eq!(None, effective.source("no_such_field"));
+    assert_eq!(
+        vec![
+            ("enable_elect", ConfigSource::Configured),
+            ("heartbeat_interval", ConfigSource::Configured),
+        ],
+        effective.overridden().collect::<Vec<_>>()
+    );
+
+    // Runtime updates override the startup value.
+    runtime.enable_elect.store(true, Ordering::Relaxed);
+    runtime.enable_heartbeat.store(false, Ordering::Relaxed);
+    runtime.enable_pre_vote.store(true, Ordering::Relaxed);
+
+    let effective = EffectiveConfig::new(&config, &runtime, false);
+    assert_eq!(
+        vec![
+            ("enable_elect", ConfigSource::Runtime),
+            ("enable_heartbeat", ConfigSource::Runtime),
+            ("enable_pre_vote", ConfigSource::Runtime),
+            ("enable_tick", ConfigSource::Runtime),
+            ("heartbeat_interval", ConfigSource::Configured),
+        ],
+        effective.overridden().collect::<Vec<_>>()
+    );
+    assert!(effective.config().enable_elect);
+    assert!(!effective.config().enable_heartbeat);
+    assert_eq!(Some(true), effective.config().enable_pre_vote);
+    assert!(!effective.config().enable_tick);
+
+    assert_eq!(
+        "EffectiveConfig{enable_elect(runtime), enable_heartbeat(runtime), enable_pre_vote(runtime), enable_tick(runtime), heartbeat_interval(configured)}",
+        effective.to_string()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_lease_read_clock_drift_must_be_smaller_than_lease() -> anyhow::Result<()> {
+    let config = Config {
+        election_timeout_max: 300,
+        lease_read_clock_drift: Some(300),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        ConfigError::LeaseReadClockDrift {
+            clock_drift: 300,
+            election_timeout_max: 300,
+        },
+        config.validate().unwrap_err()
+    );
+
+    let config = Config {
+        election_timeout_max: 300,
+        lease_read_clock_drift: Some(299),
+        ..Default::default()
+    }
+    .validate()?;
+    assert_eq!(Duration::from_millis(299), config.lease_read_clock_drift());
+
+    Ok(())
+}
+
+#[test]
+fn test_evict_unreachable_after_must_be_greater_than_election_timeout() -> anyhow::Result<()> {
+    let config = Config {
+        election_timeout_max: 300,
+        evict_unreachable_after: Some(300),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        ConfigError::EvictUnreachableAfter {
+            evict_unreachable_after: 300,
+            election_timeout_max: 300,
+        },
+        config.validate().unwrap_err()
+    );
+
+    let config = Config {
+        election_timeout_max: 300,
+        evict_unreachable_after: Some(301),
+        ..Default::default()
+    }
+    .validate()?;
+    assert_eq!(Some(Duration::from_millis(301)), config.evict_unreachable_after());
+    assert!(!config.evict_demote_voters());
+
+    Ok(())
+}
@@ -0,0 +1,170 @@
+//! The config a Raft node is actually running, with the source of every field.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::atomic::Ordering;
+
+use openraft_macros::since;
+
+use crate::Config;
+use crate::config::RuntimeConfig;
+
+/// Where the effective value of a [`Config`] field comes from.
+#[since(version = "0.10.0")]
+#[derive(Clone, Copy, Debug)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ConfigSource {
+    /// The field has the value of [`Config::default()`].
+    ///
+    /// A field explicitly set to its default value is also reported as `Default`.
+    Default,
+
+    /// The field is set to a non-default value in the [`Config`] the node is started with, e.g.,
+    /// by the application, a [`Config::preset()`], command line arguments or a config file.
+    Configured,
+
+    /// The field is updated after startup with
+    /// [`Raft::runtime_config()`](crate::Raft::runtime_config), and differs from the value the
+    /// node is started with.
+    Runtime,
+}
+
+impl fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigSource::Default => write!(f, "default"),
+            ConfigSource::Configured => write!(f, "configured"),
+            ConfigSource::Runtime => write!(f, "runtime"),
+        }
+    }
+}
+
+/// The fully resolved config of a running Raft node, with the [`ConfigSource`] of every field.
+///
+/// Returned by [`Raft::effective_config()`](crate::Raft::effective_config). Fields updatable at
+/// runtime hold their current value, not the value the node is started with.
+#[since(version = "0.10.0")]
+#[derive(Clone, Debug)]
+pub struct EffectiveConfig {
+    config: Config,
+    sources: BTreeMap<&'static str, ConfigSource>,
+}
+
+/// Build the source of every [`Config`] field by comparing it with the default.
+macro_rules! config_sources {
+    ($config:expr, $default:expr, $($field:ident),* $(,)?) => {{
+        // Destructure exhaustively so that a new field fails to compile until it is listed here.
+        let Config { $($field: _),* } = $config;
+
+        let mut sources = BTreeMap::new();
+        $(
+            let source = if $config.$field == $default.$field {
+                ConfigSource::Default
+            } else {
+                ConfigSource::Configured
+            };
+            sources.insert(stringify!($field), source);
+        )*
+        sources
+    }};
+}
+
+impl EffectiveConfig {
+    /// Resolve the effective config from the startup `config` and the current runtime values.
+    pub(crate) fn new(config: &Config, runtime: &RuntimeConfig, tick_enabled: bool) -> Self {
+        let default = Config::default();
+
+        #[allow(deprecated)]
+        #[rustfmt::skip]
+        let sources = config_sources!(config, default,
+            cluster_name, election_timeout_min, election_timeout_max, heartbeat_interval,
+            append_entries_timeout, install_snapshot_timeout, send_snapshot_timeout,
+            max_payload_entries, max_payload_bytes, min_payload_entries, payload_target_rtt,
+            max_replication_rate, max_append_entries, append_receive_window,
+            max_inflight_append_entries,
+            pipeline_snapshot_tail, commit_notify_interval, replication_lag_threshold, snapshot_policy,
+            snapshot_max_chunk_size, max_in_snapshot_log_to_keep, purge_batch_size,
+            api_channel_size, api_batch_capacity, api_batch_linger_ms, notification_channel_size,
+            state_machine_channel_size, log_stage_capacity, quorum_ack_slo, enable_tick,
+            enable_heartbeat, enable_elect, removed_leader_step_down, enable_pre_vote,
+            election_storm_threshold, election_storm_window, adaptive_election_timeout,
+            two_voter_tie_breaker, check_quorum, vote_hedge_delay, lease_read_clock_drift,
+            metrics_history_size, metrics_flush_interval, apply_delay, max_apply_rate,
+            applied_result_cache_size, leaderless_write_hold, max_held_writes,
+            snapshot_defer_write_rate, snapshot_defer_apply_backlog, snapshot_max_defer, storage_quota,
+            max_command_queue_bytes, degrade_on_storage_error, relaxed_durability, entry_timestamp,
+            audit_log_chain, promote_lag_threshold, replace_node_timeout, max_inflight_snapshots, warm_up_after_install,
+            stream_payload_threshold, snapshot_keep_count, reject_duplicate_nodes, purge_policy, evict_unreachable_after,
+            evict_demote_voters,
+            backoff,
+            allow_log_reversion, enable_leader_restore,
+        );
+
+        let mut effective = Self {
+            config: config.clone(),
+            sources,
+        };
+
+        effective.apply_runtime("enable_tick", |c| &mut c.enable_tick, tick_enabled);
+        effective.apply_runtime(
+            "enable_heartbeat",
+            |c| &mut c.enable_heartbeat,
+            runtime.enable_heartbeat.load(Ordering::Relaxed),
+        );
+        effective.apply_runtime(
+            "enable_elect",
+            |c| &mut c.enable_elect,
+            runtime.enable_elect.load(Ordering::Relaxed),
+        );
+
+        let pre_vote = runtime.enable_pre_vote.load(Ordering::Relaxed);
+        if pre_vote != config.get_enable_pre_vote() {
+            effective.config.enable_pre_vote = Some(pre_vote);
+            effective.sources.insert("enable_pre_vote", ConfigSource::Runtime);
+        }
+
+        effective
+    }
+
+    fn apply_runtime(&mut self, field: &'static str, get: impl Fn(&mut Config) -> &mut bool, value: bool) {
+        let v = get(&mut self.config);
+        if *v != value {
+            *v = value;
+            self.sources.insert(field, ConfigSource::Runtime);
+        }
+    }
+
+    /// The config with the current value of every field.
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// The source of a field, by its name in [`Config`], or `None` if there is no such field.
+    pub fn source(&self, field: &str) -> Option<ConfigSource> {
+        self.sources.get(field).copied()
+    }
+
+    /// Iterate over every field name and its source, ordered by field name.
+    pub fn sources(&self) -> impl Iterator<Item = (&'static str, ConfigSource)> + '_ {
+        self.sources.iter().map(|(k, v)| (*k, *v))
+    }
+
+    /// Iterate over the fields whose value is not the default, ordered by field name.
+    pub fn overridden(&self) -> impl Iterator<Item = (&'static str, ConfigSource)> + '_ {
+        self.sources().filter(|(_, source)| *source != ConfigSource::Default)
+    }
+}
+
+impl fmt::Display for EffectiveConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "EffectiveConfig{{")?;
+        for (i, (field, source)) in self.overridden().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}({})", field, source)?;
+        }
+        write!(f, "}}")
+    }
+}
@@ -29,6 +29,63 @@ pub enum ConfigError {
     #[error("max_payload_entries must be > 0")]
     MaxPayloadIs0,
 
+    /// The `max_payload_bytes` configuration must be greater than 0.
+    #[since(version = "0.10.0")]
+    #[error("max_payload_bytes must be > 0")]
+    MaxPayloadBytesIs0,
+
+    /// The `min_payload_entries` configuration must be in `[1, max_payload_entries]`.
+    #[since(version = "0.10.0")]
+    #[error("min_payload_entries({min_payload_entries}) must be in [1, max_payload_entries({max_payload_entries})]")]
+    MinPayloadEntries {
+        /// The configured minimum payload size.
+        min_payload_entries: u64,
+        /// The configured maximum payload size.
+        max_payload_entries: u64,
+    },
+
+    /// The `election_storm_threshold` configuration must be greater than 0.
+    #[since(version = "0.10.0")]
+    #[error("election_storm_threshold must be > 0")]
+    ElectionStormThresholdIs0,
+
+    /// The `append_receive_window` configuration must be greater than 0.
+    #[since(version = "0.10.0")]
+    #[error("append_receive_window must be > 0")]
+    AppendReceiveWindowIs0,
+
+    /// The `max_inflight_append_entries` configuration must be greater than 0.
+    #[since(version = "0.10.0")]
+    #[error("max_inflight_append_entries must be > 0")]
+    MaxInflightAppendEntriesIs0,
+
+    /// The `lease_read_clock_drift` must be smaller than the leader lease, `election_timeout_max`.
+    #[since(version = "0.10.0")]
+    #[error("lease_read_clock_drift({clock_drift}) must be < election_timeout_max({election_timeout_max})")]
+    LeaseReadClockDrift {
+        /// The configured clock drift margin.
+        clock_drift: u64,
+        /// Maximum election timeout value, which is the leader lease.
+        election_timeout_max: u64,
+    },
+
+    /// The `evict_unreachable_after` must be greater than `election_timeout_max`.
+    #[since(version = "0.10.0")]
+    #[error(
+        "evict_unreachable_after({evict_unreachable_after}) must be > election_timeout_max({election_timeout_max})"
+    )]
+    EvictUnreachableAfter {
+        /// The configured eviction grace period.
+        evict_unreachable_after: u64,
+        /// Maximum election timeout value.
+        election_timeout_max: u64,
+    },
+
+    /// The `audit_log_chain` configuration requires the `audit-log-chain` feature.
+    #[since(version = "0.10.0")]
+    #[error("audit_log_chain requires the `audit-log-chain` feature")]
+    AuditLogChainDisabled,
+
     /// Election timeout must be greater than heartbeat interval.
     #[error("election_timeout_min({election_timeout_min}) must be > heartbeat_interval({heartbeat_interval})")]
     ElectionTimeoutLTHeartBeat {
@@ -47,6 +104,16 @@ pub enum ConfigError {
         syntax: String,
     },
 
+    /// Invalid purge policy string.
+    #[since(version = "0.10.0")]
+    #[error("purge policy string is invalid: '{invalid:?}' expect: '{syntax}'")]
+    InvalidPurgePolicy {
+        /// The invalid policy string provided.
+        invalid: String,
+        /// The expected syntax format.
+        syntax: String,
+    },
+
     /// Failed to parse a number from string.
     #[error("{reason} when parsing {invalid:?}")]
     InvalidNumber {
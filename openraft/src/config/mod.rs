@@ -5,8 +5,12 @@
 //! ## Key Types
 //!
 //! - [`Config`] - Main configuration for Raft runtime behavior
+//! - [`ConfigDigest`] - The safety-relevant config every node of a cluster must agree on
+//! - [`EffectiveConfig`] - The config a node is running, with the [`ConfigSource`] of every field
 //! - [`SnapshotPolicy`] - Policy for triggering automatic snapshots
 //! - [`StepDownPolicy`] - Policy for stepping down a removed Leader
+//! - [`PurgePolicy`] - Policy for deciding which logs may be purged
+//! - [`Profile`] - Common deployment profiles for [`Config::preset()`]
 //! - [`RuntimeConfig`] - Dynamic configuration that can be changed at runtime
 //! - [`ConfigError`] - Configuration validation errors
 //!
@@ -38,9 +42,13 @@
 
 #[allow(clippy::module_inception)]
 mod config;
+mod config_digest;
+mod effective_config;
 mod error;
 #[cfg(feature = "clap")]
 mod parser;
+mod profile;
+mod purge_policy;
 mod runtime_config;
 mod step_down_policy;
 
@@ -52,6 +60,11 @@ mod config_clap_test;
 
 pub use config::Config;
 pub use config::SnapshotPolicy;
+pub use config_digest::ConfigDigest;
+pub use effective_config::ConfigSource;
+pub use effective_config::EffectiveConfig;
 pub use error::ConfigError;
+pub use profile::Profile;
+pub use purge_policy::PurgePolicy;
 pub(crate) use runtime_config::RuntimeConfig;
 pub use step_down_policy::StepDownPolicy;
@@ -9,6 +9,7 @@ use anyerror::AnyError;
 use clap::Parser;
 
 use crate::Config;
+use crate::PurgePolicy;
 use crate::SnapshotPolicy;
 use crate::StepDownPolicy;
 use crate::config::error::ConfigError;
@@ -50,6 +51,18 @@ pub(super) fn parse_snapshot_policy(src: &str) -> Result<SnapshotPolicy, ConfigE
     Ok(SnapshotPolicy::LogsSinceLast(n_logs))
 }
 
+/// Parse a purge policy: `snapshot` or `durable_applied`.
+pub(super) fn parse_purge_policy(src: &str) -> Result<PurgePolicy, ConfigError> {
+    match src {
+        "snapshot" => Ok(PurgePolicy::Snapshot),
+        "durable_applied" => Ok(PurgePolicy::DurableApplied),
+        _ => Err(ConfigError::InvalidPurgePolicy {
+            syntax: "snapshot|durable_applied".to_string(),
+            invalid: src.to_string(),
+        }),
+    }
+}
+
 /// Parse a step-down policy: a "never" literal (`never`, `no`, `none`, `off` or `false`,
 /// case-insensitive), or the number of milliseconds to wait.
 pub(super) fn parse_step_down_policy(src: &str) -> Result<StepDownPolicy, ConfigError> {
@@ -0,0 +1,34 @@
+//! Deployment profiles for [`Config::preset()`](crate::Config::preset).
+
+use openraft_macros::since;
+
+/// A common deployment profile, used to build a vetted [`Config`](crate::Config) with
+/// [`Config::preset()`](crate::Config::preset).
+#[since(version = "0.10.0")]
+#[derive(Clone, Copy, Debug)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Profile {
+    /// Nodes in one data center, with a sub-millisecond round-trip time.
+    ///
+    /// Failures are detected quickly with short heartbeat and election timeouts.
+    LanLowLatency,
+
+    /// Nodes spread over regions, with a round-trip time of up to a few hundred milliseconds.
+    ///
+    /// Timeouts are long enough to not elect on a slow link, and larger payloads are sent to
+    /// amortize the round-trip time.
+    WanGeo,
+
+    /// A cluster with a single voter, e.g., for development or an embedded deployment.
+    ///
+    /// There is no peer to replicate to, so snapshots are rare and logs are purged in large
+    /// batches.
+    SingleNode,
+
+    /// A cluster in a test, with all nodes in one process.
+    ///
+    /// Everything happens quickly, and snapshots are built and logs purged often so that the
+    /// related paths are exercised.
+    TestFast,
+}
@@ -0,0 +1,31 @@
+//! Policy for deciding which logs may be purged.
+
+use openraft_macros::since;
+
+/// Policy for deciding which applied logs may be purged.
+///
+/// It is the value of [`Config::purge_policy`](crate::Config::purge_policy).
+#[since(version = "0.10.0")]
+#[derive(Clone, Debug, Default)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum PurgePolicy {
+    /// Purge only the logs included in the last snapshot.
+    ///
+    /// This is the default: a state machine that is not persisted is restored from the snapshot
+    /// and the logs after it.
+    #[default]
+    Snapshot,
+
+    /// Also purge the logs the state machine has applied and persisted, even if no snapshot
+    /// includes them.
+    ///
+    /// For a state machine that persists every applied entry by itself, e.g., backed by RocksDB,
+    /// so that it does not need a snapshot to be restored after a restart. The state machine
+    /// reports the last log id it has persisted with
+    /// [`RaftStateMachine::durable_applied()`](crate::storage::RaftStateMachine::durable_applied).
+    ///
+    /// A follower that needs a purged log still receives a snapshot: if the current one does not
+    /// include the last purged log, a newer one is built.
+    DurableApplied,
+}
@@ -0,0 +1,85 @@
+//! Paces applying log entries on a non-leader node.
+
+use std::time::Duration;
+
+use crate::RaftTypeConfig;
+use crate::type_config::alias::InstantOf;
+
+/// Paces the apply of log entries to a maximum number of entries per second.
+///
+/// A follower catching up a long log would otherwise apply it as fast as the disk allows,
+/// saturating IO shared with other services. Each batch of entries is scheduled after the previous
+/// one has been given the time it takes at the maximum rate, thus the state machine lags behind
+/// the committed log instead.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct ApplyThrottle<C>
+where C: RaftTypeConfig
+{
+    /// The earliest time the next batch may be applied.
+    next_apply_at: Option<InstantOf<C>>,
+}
+
+impl<C> ApplyThrottle<C>
+where C: RaftTypeConfig
+{
+    /// The max number of entries to send to the state machine in one batch, at `rate` entries per
+    /// second, so that a batch takes at most 100 milliseconds at the rate.
+    pub(crate) fn batch_size(rate: u64) -> u64 {
+        std::cmp::max(rate / 10, 1)
+    }
+
+    /// Schedule a batch of `n` entries at `now`, at `rate` entries per second.
+    ///
+    /// Returns the time the batch may be applied, or `None` if it may be applied at once.
+    pub(crate) fn schedule(&mut self, now: InstantOf<C>, n: u64, rate: u64) -> Option<InstantOf<C>> {
+        let start = match self.next_apply_at {
+            Some(t) if t > now => t,
+            _ => now,
+        };
+
+        let cost = Duration::from_nanos((n as u128 * 1_000_000_000 / rate as u128) as u64);
+        self.next_apply_at = Some(start + cost);
+
+        if start > now { Some(start) } else { None }
+    }
+
+    /// Until when scheduled batches are delayed by the throttle, `None` if they are not.
+    pub(crate) fn throttled_until(&self, now: InstantOf<C>) -> Option<InstantOf<C>> {
+        self.next_apply_at.filter(|t| *t > now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::engine::testing::UTConfig;
+    use crate::type_config::TypeConfigExt;
+
+    type ApplyThrottle = super::ApplyThrottle<UTConfig>;
+
+    #[test]
+    fn test_apply_throttle_schedule() {
+        let now = UTConfig::<()>::now();
+
+        let mut t = ApplyThrottle::default();
+        assert_eq!(None, t.throttled_until(now));
+
+        assert_eq!(None, t.schedule(now, 10, 100), "the first batch is applied at once");
+        assert_eq!(Some(now + Duration::from_millis(100)), t.throttled_until(now));
+
+        assert_eq!(Some(now + Duration::from_millis(100)), t.schedule(now, 20, 100));
+        assert_eq!(Some(now + Duration::from_millis(300)), t.throttled_until(now));
+
+        let later = now + Duration::from_secs(1);
+        assert_eq!(None, t.throttled_until(later));
+        assert_eq!(None, t.schedule(later, 10, 100), "the rate is not reached");
+    }
+
+    #[test]
+    fn test_apply_throttle_batch_size() {
+        assert_eq!(1, ApplyThrottle::batch_size(1));
+        assert_eq!(1, ApplyThrottle::batch_size(10));
+        assert_eq!(100, ApplyThrottle::batch_size(1000));
+    }
+}
@@ -86,6 +86,12 @@ impl<T> ClientResponderQueue<T> {
         self.responders.drain(start_pos..)
     }
 
+    /// Removes and returns the responder at `index`, if there is one.
+    pub(crate) fn remove(&mut self, index: u64) -> Option<T> {
+        let pos = self.responders.binary_search_by_key(&index, |(index, _)| *index).ok()?;
+        self.responders.remove(pos).map(|(_, responder)| responder)
+    }
+
     /// Returns the first (smallest) log index in the queue, if any.
     #[allow(dead_code)]
     pub(crate) fn first_index(&self) -> Option<u64> {
@@ -236,4 +242,15 @@ mod tests {
         assert_eq!(result, vec![(20, 200)]);
         assert_eq!(queue.first_index(), Some(10));
     }
+
+    #[test]
+    fn test_remove() {
+        let mut queue = TestQueue::new();
+        queue.extend(vec![(10, 100), (20, 200), (30, 300)]);
+
+        assert_eq!(queue.remove(15), None);
+        assert_eq!(queue.remove(20), Some(200));
+        assert_eq!(queue.remove(20), None);
+        assert_eq!(queue.drain_upto(30), vec![(10, 100), (30, 300)]);
+    }
 }
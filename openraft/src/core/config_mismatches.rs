@@ -0,0 +1,99 @@
+//! Tracks the peers whose config differs from this node's.
+
+use std::collections::BTreeMap;
+
+use crate::ConfigDigest;
+use crate::RaftTypeConfig;
+
+/// The peers whose [`ConfigDigest`] differs from the local one.
+#[derive(Debug, Clone)]
+pub(crate) struct ConfigMismatches<C>
+where C: RaftTypeConfig
+{
+    /// The digest of this node.
+    local: ConfigDigest,
+
+    /// The last seen digest of every peer that differs from `local`.
+    peers: BTreeMap<C::NodeId, ConfigDigest>,
+}
+
+impl<C> ConfigMismatches<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new(local: ConfigDigest) -> Self {
+        Self {
+            local,
+            peers: BTreeMap::new(),
+        }
+    }
+
+    pub(crate) fn local(&self) -> ConfigDigest {
+        self.local
+    }
+
+    /// Compare the digest received from `peer` with the local one.
+    ///
+    /// Returns the names of the differing fields if the mismatch is new, i.e., the peer was not
+    /// known to differ, or it now differs in another way; so that a mismatch is reported once,
+    /// not on every request. A request without a digest, e.g., from an older version, is ignored.
+    pub(crate) fn check(&mut self, peer: &C::NodeId, digest: Option<&ConfigDigest>) -> Option<Vec<&'static str>> {
+        let digest = digest?;
+
+        if digest == &self.local {
+            self.peers.remove(peer);
+            return None;
+        }
+
+        if self.peers.get(peer) == Some(digest) {
+            return None;
+        }
+
+        self.peers.insert(peer.clone(), *digest);
+        Some(self.local.diff(digest))
+    }
+
+    /// The digest of every mismatching peer, for metrics.
+    pub(crate) fn peers(&self) -> BTreeMap<C::NodeId, ConfigDigest> {
+        self.peers.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Config;
+    use crate::ConfigDigest;
+    use crate::engine::testing::UTConfig;
+
+    type ConfigMismatches = super::ConfigMismatches<UTConfig>;
+
+    #[test]
+    fn test_config_mismatches_check() {
+        let local = ConfigDigest::new::<UTConfig>(&Config::default());
+        let relaxed = ConfigDigest {
+            relaxed_durability: true,
+            ..local
+        };
+        let slow = ConfigDigest {
+            election_timeout_max: 1000,
+            ..local
+        };
+
+        let mut m = ConfigMismatches::new(local);
+
+        assert_eq!(None, m.check(&2, None));
+        assert_eq!(None, m.check(&2, Some(&local)));
+        assert!(m.peers().is_empty());
+
+        // Reported once.
+        assert_eq!(Some(vec!["relaxed_durability"]), m.check(&2, Some(&relaxed)));
+        assert_eq!(None, m.check(&2, Some(&relaxed)));
+
+        // Reported again if it differs in another way.
+        assert_eq!(Some(vec!["election_timeout"]), m.check(&2, Some(&slow)));
+        assert_eq!(Some(&slow), m.peers().get(&2));
+
+        // Fixed.
+        assert_eq!(None, m.check(&2, Some(&local)));
+        assert!(m.peers().is_empty());
+    }
+}
@@ -1,13 +1,20 @@
 use crate::RaftTypeConfig;
+use crate::StorageError;
 #[cfg(doc)]
 use crate::core::RaftCore;
+use crate::core::apply_throttle::ApplyThrottle;
+use crate::core::election_storm::ElectionStorm;
+use crate::core::election_tuner::ElectionTuner;
+use crate::core::snapshot_deferral::SnapshotDeferral;
+use crate::core::storage_quota_state::StorageQuotaState;
+use crate::type_config::alias::InstantOf;
 use crate::type_config::alias::LogIdOf;
 
 /// State for [`RaftCore`] that does not directly affect consensus.
 ///
 /// Handles behavior not in [`Engine`](crate::engine::Engine), such as snapshot triggering and log
 /// purging.
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Clone)]
 pub(crate) struct CoreState<C>
 where C: RaftTypeConfig
 {
@@ -15,4 +22,76 @@ where C: RaftTypeConfig
     ///
     /// Prevents repeated attempts when the state machine declines to build a snapshot.
     pub(crate) snapshot_tried_at: Option<LogIdOf<C>>,
+
+    /// When this node last built a snapshot.
+    pub(crate) snapshot_built_at: Option<InstantOf<C>>,
+
+    /// Write load tracking, to defer policy-triggered snapshot builds while busy.
+    pub(crate) snapshot_deferral: SnapshotDeferral<C>,
+
+    /// Failed elections started by this node, for the election storm circuit breaker.
+    pub(crate) election_storm: ElectionStorm<C>,
+
+    /// The range the election timeout is drawn from, if it adapts to the stability of leadership.
+    pub(crate) election_tuner: ElectionTuner<C>,
+
+    /// The last known leader, to detect leader changes.
+    pub(crate) observed_leader: Option<C::NodeId>,
+
+    /// When the metrics were last published, if publication is throttled.
+    pub(crate) metrics_flushed_at: Option<InstantOf<C>>,
+
+    /// Whether a metrics publication was skipped since the last one, because of throttling.
+    pub(crate) metrics_flush_pending: bool,
+
+    /// Enforcement of the storage quota.
+    pub(crate) storage_quota: StorageQuotaState,
+
+    /// The last backup barrier proposed by this node as leader, or received from a leader.
+    ///
+    /// A snapshot is built right after the log entry with this log id is applied.
+    pub(crate) backup_barrier: Option<LogIdOf<C>>,
+
+    /// The barrier of the last backup snapshot built on this node.
+    pub(crate) last_backup: Option<LogIdOf<C>>,
+
+    /// Paces applying log entries when this node is not the leader.
+    pub(crate) apply_throttle: ApplyThrottle<C>,
+
+    /// The last log id of the snapshot the state machine is warming up after installing it.
+    pub(crate) warming_up: Option<LogIdOf<C>>,
+
+    /// Until when the state machine reported it is compacting.
+    pub(crate) compacting_until: Option<InstantOf<C>>,
+
+    /// Whether taking RaftMsg is stopped because the command queue is full.
+    pub(crate) input_stalled: bool,
+
+    /// The storage error this node runs degraded with, if any.
+    pub(crate) storage_error: Option<StorageError<C>>,
+}
+
+impl<C> Default for CoreState<C>
+where C: RaftTypeConfig
+{
+    fn default() -> Self {
+        Self {
+            snapshot_tried_at: None,
+            snapshot_built_at: None,
+            snapshot_deferral: SnapshotDeferral::default(),
+            election_storm: ElectionStorm::default(),
+            election_tuner: ElectionTuner::default(),
+            observed_leader: None,
+            metrics_flushed_at: None,
+            metrics_flush_pending: false,
+            storage_quota: StorageQuotaState::default(),
+            backup_barrier: None,
+            last_backup: None,
+            apply_throttle: ApplyThrottle::default(),
+            compacting_until: None,
+            warming_up: None,
+            input_stalled: false,
+            storage_error: None,
+        }
+    }
 }
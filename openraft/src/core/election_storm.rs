@@ -0,0 +1,151 @@
+//! Detects election storms: repeated elections that fail to establish leadership.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use crate::RaftTypeConfig;
+use crate::type_config::alias::InstantOf;
+
+/// The maximum exponent of the election backoff: the extra delay is at most `2^6` times the base
+/// election timeout.
+const MAX_BACKOFF_SHIFT: u32 = 6;
+
+/// Tracks the elections started by this node since leadership was last established.
+///
+/// When `threshold` elections are started within `window` without any leader being
+/// established, the node is considered to be in an election storm, e.g., voters repeatedly split
+/// their votes. While in a storm, the delay before the next election grows exponentially, so that
+/// this node stops bumping the term and disturbing a leader that does manage to form.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct ElectionStorm<C>
+where C: RaftTypeConfig
+{
+    /// Start time of every failed election within the window, oldest first.
+    attempts: VecDeque<InstantOf<C>>,
+
+    /// Whether the storm has been reported since leadership was last established.
+    reported: bool,
+}
+
+impl<C> ElectionStorm<C>
+where C: RaftTypeConfig
+{
+    /// Forget all recorded elections, because a leader has been established since.
+    pub(crate) fn reset(&mut self) {
+        self.attempts.clear();
+        self.reported = false;
+    }
+
+    /// Record an election started at `now` and drop the ones that are out of `window`.
+    ///
+    /// Returns `true` if this election trips the circuit breaker for the first time since the last
+    /// [`reset`](Self::reset), i.e., the caller should report the storm.
+    pub(crate) fn record(&mut self, now: InstantOf<C>, window: Duration, threshold: u64) -> bool {
+        self.expire(now, window);
+        self.attempts.push_back(now);
+
+        if self.is_tripped(threshold) && !self.reported {
+            self.reported = true;
+            return true;
+        }
+        false
+    }
+
+    /// The number of failed elections within the window.
+    pub(crate) fn attempts(&self) -> u64 {
+        self.attempts.len() as u64
+    }
+
+    /// The extra delay to add to `election_timeout` before the next election.
+    ///
+    /// It is zero until `threshold` elections failed within `window`; then it is `election_timeout`
+    /// and doubles for every further failed election, up to `2^6 * election_timeout`.
+    pub(crate) fn backoff(
+        &mut self,
+        now: InstantOf<C>,
+        window: Duration,
+        threshold: u64,
+        election_timeout: Duration,
+    ) -> Duration {
+        self.expire(now, window);
+
+        if !self.is_tripped(threshold) {
+            return Duration::default();
+        }
+
+        let exceeded = self.attempts() - threshold;
+        let shift = std::cmp::min(exceeded, MAX_BACKOFF_SHIFT as u64) as u32;
+        election_timeout * (1 << shift)
+    }
+
+    fn is_tripped(&self, threshold: u64) -> bool {
+        self.attempts() >= threshold
+    }
+
+    fn expire(&mut self, now: InstantOf<C>, window: Duration) {
+        while let Some(first) = self.attempts.front() {
+            if now < *first + window {
+                break;
+            }
+            self.attempts.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::engine::testing::UTConfig;
+    use crate::type_config::TypeConfigExt;
+
+    type ElectionStorm = super::ElectionStorm<UTConfig>;
+
+    #[test]
+    fn test_election_storm_trip_and_backoff() {
+        let window = Duration::from_secs(10);
+        let timeout = Duration::from_millis(100);
+        let now = UTConfig::<()>::now();
+
+        let mut s = ElectionStorm::default();
+
+        assert!(!s.record(now, window, 3));
+        assert!(!s.record(now, window, 3));
+        assert_eq!(Duration::default(), s.backoff(now, window, 3, timeout));
+
+        assert!(s.record(now, window, 3), "tripped at the 3rd election");
+        assert_eq!(Duration::from_millis(100), s.backoff(now, window, 3, timeout));
+
+        assert!(!s.record(now, window, 3), "reported only once");
+        assert_eq!(Duration::from_millis(200), s.backoff(now, window, 3, timeout));
+
+        for _ in 0..10 {
+            s.record(now, window, 3);
+        }
+        assert_eq!(
+            Duration::from_millis(6400),
+            s.backoff(now, window, 3, timeout),
+            "capped"
+        );
+
+        s.reset();
+        assert_eq!(0, s.attempts());
+        assert_eq!(Duration::default(), s.backoff(now, window, 3, timeout));
+    }
+
+    #[test]
+    fn test_election_storm_expire() {
+        let window = Duration::from_secs(10);
+        let timeout = Duration::from_millis(100);
+        let now = UTConfig::<()>::now();
+
+        let mut s = ElectionStorm::default();
+        s.record(now, window, 2);
+        s.record(now, window, 2);
+        assert_eq!(2, s.attempts());
+
+        let later = now + Duration::from_secs(11);
+        assert_eq!(Duration::default(), s.backoff(later, window, 2, timeout));
+        assert_eq!(0, s.attempts());
+    }
+}
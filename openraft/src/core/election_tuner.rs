@@ -0,0 +1,181 @@
+//! Adapts the election timeout range to the observed stability of leadership.
+
+use std::time::Duration;
+
+use crate::RaftTypeConfig;
+use crate::type_config::alias::InstantOf;
+use crate::type_config::alias::VoteOf;
+use crate::vote::RaftVote;
+
+/// The maximum narrowing level: the narrowest range is `1/2^3` of the configured one.
+const MAX_NARROW_LEVEL: u32 = 3;
+
+/// The number of `election_timeout_max` a leadership has to last to narrow the range by a level.
+const STABLE_ELECTION_TIMEOUTS: u32 = 64;
+
+/// Adapts the range the election timeout is drawn from, within the configured
+/// `[election_timeout_min, election_timeout_max)`.
+///
+/// The range starts at the configured one. Every time leadership has been stable for
+/// [`STABLE_ELECTION_TIMEOUTS`] election timeouts, the upper bound is moved half way towards
+/// `election_timeout_min`, so that a lost leader is detected sooner. Every time an election
+/// started by this node fails, i.e., this node starts another one without a leader being
+/// established, the range is widened back by a level, so that voters are less likely to split
+/// their votes again.
+#[derive(Debug, Clone)]
+pub(crate) struct ElectionTuner<C>
+where C: RaftTypeConfig
+{
+    /// How many times the range has been narrowed, `0` is the configured range.
+    level: u32,
+
+    /// The committed vote observed and since when it has been observed, or since when the range
+    /// was last narrowed.
+    stable_since: Option<(VoteOf<C>, InstantOf<C>)>,
+}
+
+impl<C> Default for ElectionTuner<C>
+where C: RaftTypeConfig
+{
+    fn default() -> Self {
+        Self {
+            level: 0,
+            stable_since: None,
+        }
+    }
+}
+
+impl<C> ElectionTuner<C>
+where C: RaftTypeConfig
+{
+    /// The current narrowing level.
+    pub(crate) fn level(&self) -> u32 {
+        self.level
+    }
+
+    /// The range `[min, max)` in milliseconds to draw the election timeout from at the current
+    /// level.
+    pub(crate) fn range(&self, min: u64, max: u64) -> (u64, u64) {
+        let width = (max - min) >> self.level;
+        (min, min + std::cmp::max(width, 1))
+    }
+
+    /// Observe the vote of this node at `now`.
+    ///
+    /// Returns `true` if the range is narrowed, because the same leader has been established for
+    /// [`STABLE_ELECTION_TIMEOUTS`] times `election_timeout`.
+    pub(crate) fn observe(&mut self, vote: &VoteOf<C>, now: InstantOf<C>, election_timeout: Duration) -> bool {
+        if !vote.is_committed() {
+            self.stable_since = None;
+            return false;
+        }
+
+        let Some((stable_vote, since)) = &self.stable_since else {
+            self.stable_since = Some((vote.clone(), now));
+            return false;
+        };
+
+        if stable_vote != vote {
+            self.stable_since = Some((vote.clone(), now));
+            return false;
+        }
+
+        if now < *since + election_timeout * STABLE_ELECTION_TIMEOUTS {
+            return false;
+        }
+
+        self.stable_since = Some((vote.clone(), now));
+
+        if self.level >= MAX_NARROW_LEVEL {
+            return false;
+        }
+        self.level += 1;
+        true
+    }
+
+    /// Record that this node starts an election while the previous one did not establish a
+    /// leader.
+    ///
+    /// Returns `true` if the range is widened.
+    pub(crate) fn on_failed_election(&mut self) -> bool {
+        self.stable_since = None;
+
+        if self.level == 0 {
+            return false;
+        }
+        self.level -= 1;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::Vote;
+    use crate::engine::testing::UTConfig;
+    use crate::type_config::TypeConfigExt;
+
+    type ElectionTuner = super::ElectionTuner<UTConfig>;
+
+    #[test]
+    fn test_election_tuner_narrow_and_widen() {
+        let timeout = Duration::from_millis(100);
+        let now = UTConfig::<()>::now();
+        let stable = timeout * super::STABLE_ELECTION_TIMEOUTS;
+
+        let committed = Vote::new_committed(1, 2);
+        let mut t = ElectionTuner::default();
+
+        assert_eq!((100, 300), t.range(100, 300));
+
+        assert!(!t.observe(&committed, now, timeout));
+        assert!(!t.observe(&committed, now + stable / 2, timeout));
+        assert!(
+            t.observe(&committed, now + stable, timeout),
+            "narrowed after stable leadership"
+        );
+        assert_eq!(1, t.level());
+        assert_eq!((100, 200), t.range(100, 300));
+
+        for i in 2..=5 {
+            t.observe(&committed, now + stable * i, timeout);
+        }
+        assert_eq!(3, t.level(), "capped");
+        assert_eq!((100, 125), t.range(100, 300));
+
+        assert!(t.on_failed_election());
+        assert_eq!(2, t.level());
+
+        for _ in 0..5 {
+            t.on_failed_election();
+        }
+        assert_eq!(0, t.level(), "widened up to the configured range");
+        assert!(!t.on_failed_election());
+    }
+
+    #[test]
+    fn test_election_tuner_leader_change_restarts_stable_period() {
+        let timeout = Duration::from_millis(100);
+        let now = UTConfig::<()>::now();
+        let stable = timeout * super::STABLE_ELECTION_TIMEOUTS;
+
+        let mut t = ElectionTuner::default();
+
+        t.observe(&Vote::new_committed(1, 2), now, timeout);
+        assert!(!t.observe(&Vote::new_committed(2, 3), now + stable, timeout));
+        assert!(!t.observe(&Vote::new(3, 3), now + stable * 2, timeout));
+        assert!(!t.observe(&Vote::new_committed(3, 3), now + stable * 2, timeout));
+        assert!(t.observe(&Vote::new_committed(3, 3), now + stable * 3, timeout));
+        assert_eq!(1, t.level());
+    }
+
+    #[test]
+    fn test_election_tuner_range_never_empty() {
+        let t = ElectionTuner {
+            level: 3,
+            ..Default::default()
+        };
+        assert_eq!((100, 101), t.range(100, 104));
+    }
+}
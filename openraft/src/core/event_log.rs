@@ -0,0 +1,108 @@
+//! The most recent [`RaftEvent`]s, shared with event subscribers.
+
+use std::collections::VecDeque;
+
+use crate::RaftTypeConfig;
+use crate::errors::EventsLagged;
+use crate::raft::RaftEvent;
+
+/// The number of most recent events kept for subscribers.
+const EVENT_LOG_CAPACITY: usize = 1024;
+
+/// The most recent events emitted by `RaftCore`, each identified by a sequence number.
+///
+/// `RaftCore` appends to it through a watch channel; every subscriber reads from it at its own
+/// position, thus a slow subscriber never blocks `RaftCore` or another subscriber. The oldest
+/// events are discarded once there are more than [`EVENT_LOG_CAPACITY`].
+#[derive(Debug, Clone)]
+pub(crate) struct EventLog<C>
+where C: RaftTypeConfig
+{
+    /// The sequence number of the first event in `events`.
+    first_seq: u64,
+    events: VecDeque<RaftEvent<C>>,
+}
+
+impl<C> Default for EventLog<C>
+where C: RaftTypeConfig
+{
+    fn default() -> Self {
+        Self {
+            first_seq: 0,
+            events: VecDeque::new(),
+        }
+    }
+}
+
+impl<C> EventLog<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn push(&mut self, event: RaftEvent<C>) {
+        if self.events.len() >= EVENT_LOG_CAPACITY {
+            self.events.pop_front();
+            self.first_seq += 1;
+        }
+        self.events.push_back(event);
+    }
+
+    /// The sequence number of the next event to push.
+    pub(crate) fn next_seq(&self) -> u64 {
+        self.first_seq + self.events.len() as u64
+    }
+
+    /// Return the events from sequence number `seq`.
+    ///
+    /// If some of them are already discarded, it returns the number of them and the sequence
+    /// number of the first event kept.
+    pub(crate) fn read_from(&self, seq: u64) -> Result<Vec<RaftEvent<C>>, (EventsLagged, u64)> {
+        if seq < self.first_seq {
+            let lagged = EventsLagged {
+                missed: self.first_seq - seq,
+            };
+            return Err((lagged, self.first_seq));
+        }
+
+        let start = (seq - self.first_seq) as usize;
+        Ok(self.events.iter().skip(start).cloned().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EVENT_LOG_CAPACITY;
+    use super::EventLog;
+    use crate::engine::testing::UTConfig;
+    use crate::engine::testing::log_id;
+    use crate::errors::EventsLagged;
+    use crate::raft::RaftEvent;
+
+    fn purged(index: u64) -> RaftEvent<UTConfig> {
+        RaftEvent::LogPurged {
+            upto: log_id(1, 1, index),
+        }
+    }
+
+    #[test]
+    fn test_event_log() {
+        let mut log = EventLog::<UTConfig>::default();
+        assert_eq!(0, log.next_seq());
+        assert_eq!(Ok(vec![]), log.read_from(0));
+
+        log.push(purged(1));
+        log.push(purged(2));
+        assert_eq!(2, log.next_seq());
+        assert_eq!(Ok(vec![purged(1), purged(2)]), log.read_from(0));
+        assert_eq!(Ok(vec![purged(2)]), log.read_from(1));
+        assert_eq!(Ok(vec![]), log.read_from(2));
+
+        for i in 0..EVENT_LOG_CAPACITY as u64 {
+            log.push(purged(3 + i));
+        }
+        assert_eq!(2 + EVENT_LOG_CAPACITY as u64, log.next_seq());
+        assert_eq!(Err((EventsLagged { missed: 2 }, 2)), log.read_from(0));
+        assert_eq!(
+            Ok(vec![purged(2 + EVENT_LOG_CAPACITY as u64)]),
+            log.read_from(1 + EVENT_LOG_CAPACITY as u64)
+        );
+    }
+}
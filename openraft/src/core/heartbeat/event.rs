@@ -30,6 +30,10 @@ where C: RaftTypeConfig
     /// When there are no new logs to replicate, the Leader sends a heartbeat to replicate committed
     /// log id to followers to update their committed log id.
     pub(crate) cluster_committed: Option<LogIdOf<C>>,
+
+    /// The last backup barrier proposed by the Leader, sent along with the committed log id so
+    /// that a follower knows it before applying it.
+    pub(crate) backup_barrier: Option<LogIdOf<C>>,
 }
 
 impl<C> fmt::Display for HeartbeatEvent<C>
@@ -38,10 +42,11 @@ where C: RaftTypeConfig
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "(time={}, matching: {}, cluster_committed: {})",
+            "(time={}, matching: {}, cluster_committed: {}, backup_barrier: {})",
             self.time.display(),
             self.matching.display(),
-            self.cluster_committed.display()
+            self.cluster_committed.display(),
+            self.backup_barrier.display()
         )
     }
 }
@@ -6,6 +6,7 @@ use futures_util::FutureExt;
 use futures_util::StreamExt;
 
 use crate::Config;
+use crate::ConfigDigest;
 use crate::RaftTypeConfig;
 use crate::async_runtime::watch::WatchReceiver;
 use crate::core::heartbeat::errors::RaftCoreClosed;
@@ -110,6 +111,9 @@ where
                 // following line to `prev_log_id = heartbeat.cluster_committed.clone()`.
                 prev_log_id: heartbeat.matching.clone(),
                 leader_commit: heartbeat.cluster_committed.clone(),
+                backup_barrier: heartbeat.backup_barrier.clone(),
+                after_snapshot: None,
+                config_digest: Some(ConfigDigest::new::<C>(&self.config)),
                 entries: vec![],
             };
 
@@ -181,6 +185,10 @@ where
                 self.send_notification(noti, "Seeing conflict").await?;
                 self.send_heartbeat_progress(heartbeat).await?;
             }
+            Err(StreamAppendError::Malformed(e)) => {
+                // Not acknowledged: the heartbeat was rejected before being applied.
+                tracing::error!("{} rejected a malformed heartbeat: {}", self, e);
+            }
         }
         Ok(())
     }
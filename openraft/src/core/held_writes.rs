@@ -0,0 +1,144 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use crate::RaftTypeConfig;
+use crate::batch::Batch;
+use crate::raft::responder::core_responder::CoreResponder;
+use crate::type_config::alias::BatchOf;
+use crate::type_config::alias::EntryPayloadOf;
+use crate::type_config::alias::InstantOf;
+
+/// A client write held while no leader is known.
+pub(crate) struct HeldWrite<C>
+where C: RaftTypeConfig
+{
+    pub(crate) payloads: BatchOf<C, EntryPayloadOf<C>>,
+    pub(crate) responders: BatchOf<C, Option<CoreResponder<C>>>,
+    #[cfg(feature = "runtime-stats")]
+    pub(crate) proposed_at: InstantOf<C>,
+
+    /// When the write was held.
+    held_at: InstantOf<C>,
+}
+
+impl<C> HeldWrite<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new(
+        payloads: BatchOf<C, EntryPayloadOf<C>>,
+        responders: BatchOf<C, Option<CoreResponder<C>>>,
+        #[cfg(feature = "runtime-stats")] proposed_at: InstantOf<C>,
+        held_at: InstantOf<C>,
+    ) -> Self {
+        Self {
+            payloads,
+            responders,
+            #[cfg(feature = "runtime-stats")]
+            proposed_at,
+            held_at,
+        }
+    }
+}
+
+/// Client writes held on a non-leader until a leader is known, in the order they were received.
+///
+/// See [`Config::leaderless_write_hold`](crate::Config::leaderless_write_hold).
+pub(crate) struct HeldWrites<C>
+where C: RaftTypeConfig
+{
+    writes: VecDeque<HeldWrite<C>>,
+
+    /// The total number of log entries in `writes`.
+    entries: u64,
+}
+
+impl<C> Default for HeldWrites<C>
+where C: RaftTypeConfig
+{
+    fn default() -> Self {
+        Self {
+            writes: VecDeque::new(),
+            entries: 0,
+        }
+    }
+}
+
+impl<C> HeldWrites<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn is_empty(&self) -> bool {
+        self.writes.is_empty()
+    }
+
+    /// The total number of log entries held.
+    pub(crate) fn entries(&self) -> u64 {
+        self.entries
+    }
+
+    pub(crate) fn push(&mut self, write: HeldWrite<C>) {
+        self.entries += write.payloads.len() as u64;
+        self.writes.push_back(write);
+    }
+
+    /// Remove and return the first held write, if `release_all` is `true` or it has been held
+    /// for at least `hold` as of `now`.
+    pub(crate) fn pop(&mut self, release_all: bool, now: InstantOf<C>, hold: Duration) -> Option<HeldWrite<C>> {
+        let first = self.writes.front()?;
+        if !release_all && now < first.held_at + hold {
+            return None;
+        }
+
+        let write = self.writes.pop_front()?;
+        self.entries -= write.payloads.len() as u64;
+        Some(write)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::HeldWrite;
+    use super::HeldWrites;
+    use crate::EntryPayload;
+    use crate::batch::Batch;
+    use crate::engine::testing::UTConfig;
+    use crate::type_config::TypeConfigExt;
+    use crate::type_config::alias::InstantOf;
+
+    fn write(n: usize, held_at: InstantOf<UTConfig>) -> HeldWrite<UTConfig> {
+        HeldWrite::new(
+            Batch::of((0..n).map(|_| EntryPayload::Blank)),
+            Batch::of((0..n).map(|_| None)),
+            #[cfg(feature = "runtime-stats")]
+            held_at,
+            held_at,
+        )
+    }
+
+    #[test]
+    fn test_held_writes_pop() {
+        let hold = Duration::from_millis(100);
+        let now = <UTConfig as TypeConfigExt>::now();
+
+        let mut held = HeldWrites::<UTConfig>::default();
+        held.push(write(2, now));
+        held.push(write(3, now + Duration::from_millis(50)));
+        assert_eq!(5, held.entries());
+
+        assert!(held.pop(false, now + Duration::from_millis(99), hold).is_none());
+
+        let w = held.pop(false, now + Duration::from_millis(100), hold).unwrap();
+        assert_eq!(2, w.payloads.len());
+        assert_eq!(3, held.entries());
+
+        assert!(held.pop(false, now + Duration::from_millis(100), hold).is_none());
+
+        let w = held.pop(true, now, hold).unwrap();
+        assert_eq!(3, w.payloads.len());
+        assert_eq!(0, held.entries());
+        assert!(held.is_empty());
+
+        assert!(held.pop(true, now, hold).is_none());
+    }
+}
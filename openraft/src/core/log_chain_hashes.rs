@@ -0,0 +1,262 @@
+use std::collections::BTreeMap;
+
+use crate::RaftLogReader;
+use crate::RaftTypeConfig;
+use crate::StorageError;
+use crate::batch::Batch;
+use crate::entry::ChainHash;
+use crate::entry::RaftEntry;
+use crate::entry::chain_hash;
+use crate::entry::raft_entry_ext::RaftEntryExt;
+use crate::errors::LogChainError;
+use crate::errors::StorageIOResult;
+use crate::type_config::alias::BatchOf;
+use crate::type_config::alias::CommittedVoteOf;
+
+/// The chain hashes of the local log entries that can still be truncated, from the committed one
+/// to the last one, to chain the next entries without reading the log on the `RaftCore` task.
+///
+/// See [`Config::audit_log_chain`](crate::Config::audit_log_chain).
+#[derive(Debug, Clone, Default)]
+pub(crate) struct LogChainHashes {
+    /// Log index to the chain hash of the entry, `None` if the entry is not chained.
+    hashes: BTreeMap<u64, Option<ChainHash>>,
+}
+
+impl LogChainHashes {
+    /// Read the chain hashes of the entries in `[start, end)` from the log, before `RaftCore`
+    /// starts.
+    pub(crate) async fn load<C, LR>(log_reader: &mut LR, start: u64, end: u64) -> Result<Self, StorageError<C>>
+    where
+        C: RaftTypeConfig,
+        LR: RaftLogReader<C>,
+    {
+        /// The number of entries to read at a time.
+        const CHUNK_SIZE: u64 = 1024;
+
+        let mut hashes = BTreeMap::new();
+        let mut next = start;
+
+        while next < end {
+            let chunk_end = std::cmp::min(next + CHUNK_SIZE, end);
+            let entries = log_reader.try_get_log_entries(next..chunk_end).await.sto_read_logs()?;
+
+            let Some(last) = entries.last() else {
+                break;
+            };
+
+            next = last.index() + 1;
+            hashes.extend(entries.iter().map(|e| (e.index(), e.chain_hash())));
+        }
+
+        Ok(Self { hashes })
+    }
+
+    /// Returns the chain hash of the entry before `index`, `None` if it is not chained or not in
+    /// the log.
+    pub(crate) fn before(&self, index: u64) -> Option<ChainHash> {
+        let prev = index.checked_sub(1)?;
+        self.hashes.get(&prev).copied().flatten()
+    }
+
+    pub(crate) fn insert(&mut self, index: u64, hash: Option<ChainHash>) {
+        self.hashes.insert(index, hash);
+    }
+
+    /// Forget the entries since `index`, which are truncated from the log.
+    pub(crate) fn truncate(&mut self, index: u64) {
+        self.hashes.split_off(&index);
+    }
+
+    /// Forget the entries before `index`, which can no longer be truncated.
+    pub(crate) fn purge(&mut self, index: u64) {
+        self.hashes = self.hashes.split_off(&index);
+    }
+}
+
+/// The entries to append, chained by [`chain_entries()`] in a separate task, so that encoding and
+/// hashing them does not block `RaftCore`.
+pub(crate) struct ChainedEntries<C>
+where C: RaftTypeConfig
+{
+    /// The id of the task that chained the entries, see [`LogChaining::start()`].
+    pub(crate) id: u64,
+
+    /// The vote of the leader the entries are appended for.
+    pub(crate) committed_vote: CommittedVoteOf<C>,
+
+    /// The chained entries along with the chain hash of each of them, or the error if an entry
+    /// does not follow the local log.
+    pub(crate) result: Result<(BatchOf<C, C::Entry>, Vec<Option<ChainHash>>), LogChainError<C>>,
+}
+
+/// The task chaining the entries to append, which holds the queued commands until the entries are
+/// appended, to keep the order of the log IO.
+pub(crate) struct LogChaining<C>
+where C: RaftTypeConfig
+{
+    /// The id of the last task started.
+    last_id: u64,
+
+    /// The id of the running task, `None` if there is none.
+    running: Option<u64>,
+
+    /// The entries chained by the task, to append at the next run of the commands.
+    done: Option<ChainedEntries<C>>,
+}
+
+impl<C> Default for LogChaining<C>
+where C: RaftTypeConfig
+{
+    fn default() -> Self {
+        Self {
+            last_id: 0,
+            running: None,
+            done: None,
+        }
+    }
+}
+
+impl<C> LogChaining<C>
+where C: RaftTypeConfig
+{
+    /// Start a task and return its id.
+    pub(crate) fn start(&mut self) -> u64 {
+        self.last_id += 1;
+        self.running = Some(self.last_id);
+        self.last_id
+    }
+
+    /// Keep the entries chained by the running task; those of a cancelled task are dropped.
+    pub(crate) fn finish(&mut self, chained: ChainedEntries<C>) {
+        if self.running == Some(chained.id) {
+            self.running = None;
+            self.done = Some(chained);
+        } else {
+            tracing::debug!("drop the entries chained by cancelled task: {}", chained.id);
+        }
+    }
+
+    /// Take the chained entries to append.
+    pub(crate) fn take_done(&mut self) -> Option<ChainedEntries<C>> {
+        self.done.take()
+    }
+
+    /// Whether the queued commands have to wait for the entries being chained to be appended.
+    pub(crate) fn is_pending(&self) -> bool {
+        self.running.is_some() || self.done.is_some()
+    }
+
+    /// Forget the running task and the chained entries, which are no longer appended.
+    pub(crate) fn cancel(&mut self) {
+        self.running = None;
+        self.done = None;
+    }
+}
+
+/// Set or verify the chain hash of each of `entries` before they are appended to the local log,
+/// and return them along with the chain hash of each of them. `prev` is the chain hash of the
+/// entry before them.
+///
+/// An entry proposed by this node is given a chain hash. An entry replicated from the leader
+/// already carries one, which must follow the entry before it in the local log: otherwise the
+/// local log or the received entry has been tampered with, and an error is returned.
+pub(crate) fn chain_entries<C>(
+    prev: Option<ChainHash>,
+    entries: BatchOf<C, C::Entry>,
+) -> Result<(BatchOf<C, C::Entry>, Vec<Option<ChainHash>>), LogChainError<C>>
+where
+    C: RaftTypeConfig,
+{
+    let mut prev = prev;
+
+    let mut chained = Vec::with_capacity(entries.len());
+    let mut hashes = Vec::with_capacity(entries.len());
+
+    for mut entry in entries {
+        prev = match (entry.content_hash(), entry.chain_hash()) {
+            // This entry type can not be chained.
+            (None, _) => None,
+            (Some(content), None) => {
+                let h = chain_hash(prev, content);
+                entry.set_chain_hash(Some(h)).then_some(h)
+            }
+            (Some(content), Some(h)) => {
+                let expected = chain_hash(prev, content);
+                if prev.is_some() && h != expected {
+                    return Err(LogChainError::Broken {
+                        log_id: entry.log_id(),
+                        expected: Some(expected),
+                        actual: Some(h),
+                    });
+                }
+                Some(h)
+            }
+        };
+
+        hashes.push(prev);
+        chained.push(entry);
+    }
+
+    Ok((Batch::of(chained), hashes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ChainedEntries;
+    use super::LogChaining;
+    use super::chain_entries;
+    use crate::RaftEntry;
+    use crate::Vote;
+    use crate::batch::Batch;
+    use crate::engine::testing::UTConfig;
+    use crate::engine::testing::log_id;
+    use crate::entry::ChainHash;
+    use crate::errors::LogChainError;
+    use crate::testing::blank_ent;
+    use crate::vote::raft_vote::RaftVoteExt;
+
+    fn chained(id: u64) -> ChainedEntries<UTConfig> {
+        ChainedEntries {
+            id,
+            committed_vote: Vote::new_committed(1, 1).into_committed(),
+            result: Ok((Batch::of([]), vec![])),
+        }
+    }
+
+    #[test]
+    fn test_log_chaining() {
+        let mut c = LogChaining::<UTConfig>::default();
+        assert!(!c.is_pending());
+
+        let id = c.start();
+        assert!(c.is_pending());
+
+        // The entries of a cancelled task are dropped.
+        c.cancel();
+        let id2 = c.start();
+        c.finish(chained(id));
+        assert!(c.take_done().is_none());
+        assert!(c.is_pending());
+
+        c.finish(chained(id2));
+        assert!(c.is_pending(), "pending until the chained entries are appended");
+        assert_eq!(Some(id2), c.take_done().map(|x| x.id));
+        assert!(!c.is_pending());
+    }
+
+    #[test]
+    fn test_chain_entries() {
+        let entries = Batch::of([blank_ent::<UTConfig>(1, 1, 1), blank_ent::<UTConfig>(1, 1, 2)]);
+        let (chained, hashes) = chain_entries::<UTConfig>(None, entries).unwrap();
+        let chained = chained.into_iter().collect::<Vec<_>>();
+        assert_eq!(vec![chained[0].chain_hash(), chained[1].chain_hash()], hashes);
+        assert!(hashes.iter().all(|h| h.is_some()));
+
+        // A replicated entry that does not follow the local log.
+        let mut ent = blank_ent::<UTConfig>(1, 1, 3);
+        ent.set_chain_hash(Some(ChainHash([0; 32])));
+        let res = chain_entries::<UTConfig>(hashes[1], Batch::of([ent]));
+        assert!(matches!(res, Err(LogChainError::Broken { log_id: l, .. }) if l == log_id(1, 1, 3)));
+    }
+}
@@ -0,0 +1,76 @@
+//! Log indexes that log subscribers still need, so that they are not purged.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+/// The first log index each [`LogSubscription`] has not yet consumed.
+///
+/// It is shared between `RaftCore`, which holds back the policy-based purge at the smallest index,
+/// and the subscriptions, which advance or remove their hold. A removed or advanced hold takes
+/// effect the next time `RaftCore` refreshes it.
+///
+/// [`LogSubscription`]: crate::raft::LogSubscription
+#[derive(Debug, Clone, Default)]
+pub(crate) struct LogHolds {
+    inner: Arc<Mutex<Holds>>,
+}
+
+#[derive(Debug, Default)]
+struct Holds {
+    next_id: u64,
+    by_id: BTreeMap<u64, u64>,
+}
+
+impl LogHolds {
+    /// Hold logs at and after `index`, returns the id of the hold.
+    pub(crate) fn add(&self, index: u64) -> u64 {
+        let mut holds = self.inner.lock().unwrap();
+        let id = holds.next_id;
+        holds.next_id += 1;
+        holds.by_id.insert(id, index);
+        id
+    }
+
+    /// Move the hold `id` forward to `index`; it never moves backward.
+    pub(crate) fn advance(&self, id: u64, index: u64) {
+        let mut holds = self.inner.lock().unwrap();
+        if let Some(held) = holds.by_id.get_mut(&id) {
+            *held = std::cmp::max(*held, index);
+        }
+    }
+
+    pub(crate) fn remove(&self, id: u64) {
+        self.inner.lock().unwrap().by_id.remove(&id);
+    }
+
+    /// The smallest held index, logs at and after it must not be purged.
+    pub(crate) fn min_index(&self) -> Option<u64> {
+        self.inner.lock().unwrap().by_id.values().min().copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LogHolds;
+
+    #[test]
+    fn test_log_holds() {
+        let holds = LogHolds::default();
+        assert_eq!(None, holds.min_index());
+
+        let a = holds.add(5);
+        let b = holds.add(3);
+        assert_eq!(Some(3), holds.min_index());
+
+        holds.advance(b, 10);
+        assert_eq!(Some(5), holds.min_index());
+
+        holds.advance(b, 1);
+        holds.remove(a);
+        assert_eq!(Some(10), holds.min_index(), "a hold does not move backward");
+
+        holds.remove(b);
+        assert_eq!(None, holds.min_index());
+    }
+}
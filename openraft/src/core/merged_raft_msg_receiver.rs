@@ -1,9 +1,10 @@
-//! Batching receiver for RaftMsg that merges consecutive ClientWrite messages.
+//! Batching receiver for RaftMsg that merges consecutive ClientWrite and AppendEntries messages.
 //!
 //! This module provides [`BatchRaftMsgReceiver`], a wrapper around an mpsc receiver
 //! that automatically batches consecutive `RaftMsg::ClientWrite` messages with the
 //! same `expected_leader` into a single message, improving throughput by reducing
-//! per-message overhead.
+//! per-message overhead. On a follower, consecutive `RaftMsg::AppendEntries` from the same
+//! leader are merged the same way.
 
 use std::ops::Add;
 use std::time::Duration;
@@ -14,6 +15,7 @@ use crate::RaftTypeConfig;
 use crate::async_runtime::MpscReceiver;
 use crate::async_runtime::TryRecvError;
 use crate::core::raft_msg::RaftMsg;
+use crate::entry::RaftEntry;
 use crate::errors::Fatal;
 use crate::type_config::TypeConfigExt;
 use crate::type_config::alias::MpscReceiverOf;
@@ -26,10 +28,13 @@ use crate::type_config::alias::MpscReceiverOf;
 ///
 /// # Batching Rules
 ///
-/// - Only `RaftMsg::ClientWrite` messages are batched
-/// - Messages are only merged if they have the same `expected_leader` value
-/// - Non-`ClientWrite` messages stop the batching and are buffered for the next recv
-/// - Maximum batch size is 4096 messages
+/// - Only `RaftMsg::ClientWrite` and `RaftMsg::AppendEntries` messages are batched
+/// - `ClientWrite` messages are only merged if they have the same `expected_leader` value
+/// - `AppendEntries` messages are only merged if they have the same `vote` and each one starts
+///   right after the last log id of the previous one. They are merged only if already queued, i.e.,
+///   without waiting for the linger timeout
+/// - Other messages stop the batching and are buffered for the next recv
+/// - Maximum batch size is `capacity` messages
 ///
 /// # Usage Pattern
 ///
@@ -110,6 +115,7 @@ where C: RaftTypeConfig
         };
 
         self.merge_client_writes(&mut msg).await?;
+        self.merge_append_entries(&mut msg)?;
 
         Ok(Some(msg))
     }
@@ -230,6 +236,58 @@ where C: RaftTypeConfig
 
         Ok(())
     }
+
+    /// Merges already queued consecutive `AppendEntries` messages from the same leader.
+    ///
+    /// A message is merged into `msg` if it has the same `vote` and `after_snapshot`, and its
+    /// `prev_log_id` is the last log id of `msg`, so that the merged entries are still contiguous.
+    /// Its `leader_commit`, `backup_barrier` and `config_digest` replace the previous ones, since
+    /// it is sent later by the same leader.
+    /// Merging stops when:
+    /// - A message that can not be merged is encountered (buffered for next recv)
+    /// - Maximum batch size is reached
+    /// - No more messages are available
+    fn merge_append_entries(&mut self, msg: &mut RaftMsg<C>) -> Result<(), Fatal<C>> {
+        let (batch_rpc, batch_txs) = match msg {
+            RaftMsg::AppendEntries { rpc, txs } => (rpc, txs),
+            _ => return Ok(()),
+        };
+
+        debug_assert!(self.buffered.is_none());
+
+        for _ in 1..self.capacity {
+            let Some(next) = self.inner_try_recv()? else {
+                break;
+            };
+
+            let batch_last = batch_rpc.entries.last().map(|e| e.log_id()).or_else(|| batch_rpc.prev_log_id.clone());
+
+            let mergeable = matches!(
+                &next,
+                RaftMsg::AppendEntries { rpc, .. } if rpc.vote == batch_rpc.vote
+                    && rpc.after_snapshot == batch_rpc.after_snapshot
+                    && rpc.prev_log_id == batch_last
+            );
+
+            if !mergeable {
+                self.buffered = Some(next);
+                break;
+            }
+
+            match next {
+                RaftMsg::AppendEntries { rpc, txs } => {
+                    batch_rpc.entries.extend(rpc.entries);
+                    batch_rpc.leader_commit = rpc.leader_commit;
+                    batch_rpc.backup_barrier = rpc.backup_barrier;
+                    batch_rpc.config_digest = rpc.config_digest;
+                    batch_txs.extend(txs);
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -237,15 +295,21 @@ mod tests {
     use rt::Instant;
 
     use super::*;
+    use crate::Vote;
     use crate::async_runtime::MpscSender;
     use crate::batch::Batch;
     use crate::engine::testing::UTConfig;
     use crate::engine::testing::log_id;
     use crate::entry::EntryPayload;
+    use crate::raft::AppendEntriesRequest;
+    use crate::raft::StreamAppendResult;
+    use crate::testing::blank_ent;
     use crate::type_config::TypeConfigExt;
     use crate::type_config::alias::BatchOf;
     use crate::type_config::alias::CommittedLeaderIdOf;
     use crate::type_config::alias::EntryPayloadOf;
+    use crate::type_config::alias::OneshotReceiverOf;
+    use crate::type_config::alias::VoteOf;
 
     type C = UTConfig<()>;
 
@@ -267,6 +331,27 @@ mod tests {
         }
     }
 
+    fn append_entries(
+        vote: VoteOf<C>,
+        prev: Option<(u64, u64)>,
+        indexes: std::ops::Range<u64>,
+    ) -> (RaftMsg<C>, OneshotReceiverOf<C, Option<StreamAppendResult<C>>>) {
+        let (tx, rx) = C::oneshot();
+        let msg = RaftMsg::AppendEntries {
+            rpc: AppendEntriesRequest {
+                vote,
+                prev_log_id: prev.map(|(term, index)| log_id(term, 1, index)),
+                entries: indexes.map(|i| blank_ent::<C>(1, 1, i)).collect(),
+                leader_commit: None,
+                backup_barrier: None,
+                after_snapshot: None,
+                config_digest: None,
+            },
+            txs: Batch::of([tx]),
+        };
+        (msg, rx)
+    }
+
     fn extract_payload_data(payloads: &BatchOf<C, EntryPayloadOf<C>>) -> Vec<u64> {
         payloads
             .as_ref()
@@ -502,4 +587,64 @@ mod tests {
             assert_eq!(Duration::ZERO, LINGER.saturating_sub(now.elapsed()))
         });
     }
+
+    #[test]
+    fn test_merge_consecutive_append_entries() {
+        C::run(async {
+            let (tx, rx) = C::mpsc(100);
+            let mut receiver: BatchRaftMsgReceiver<C> = default_msg_receiver(rx);
+
+            let (m1, _rx1) = append_entries(Vote::new_committed(1, 1), Some((1, 0)), 1..3);
+            let (m2, _rx2) = append_entries(Vote::new_committed(1, 1), Some((1, 2)), 3..5);
+            // Not contiguous with the previous one
+            let (m3, _rx3) = append_entries(Vote::new_committed(1, 1), Some((1, 2)), 3..4);
+            tx.send(m1).await.unwrap();
+            tx.send(m2).await.unwrap();
+            tx.send(m3).await.unwrap();
+
+            receiver.ensure_buffered().await.unwrap();
+
+            let RaftMsg::AppendEntries { rpc, txs } = receiver.try_recv().await.unwrap().unwrap() else {
+                panic!("expected AppendEntries");
+            };
+            assert_eq!(Some(log_id(1, 1, 0)), rpc.prev_log_id);
+            assert_eq!(
+                vec![1, 2, 3, 4],
+                rpc.entries.iter().map(|e| e.log_id.index).collect::<Vec<_>>()
+            );
+            assert_eq!(2, txs.len());
+
+            let RaftMsg::AppendEntries { rpc, txs } = receiver.try_recv().await.unwrap().unwrap() else {
+                panic!("expected AppendEntries");
+            };
+            assert_eq!(Some(log_id(1, 1, 2)), rpc.prev_log_id);
+            assert_eq!(1, txs.len());
+        });
+    }
+
+    #[test]
+    fn test_no_merge_append_entries_when_vote_differs() {
+        C::run(async {
+            let (tx, rx) = C::mpsc(100);
+            let mut receiver: BatchRaftMsgReceiver<C> = default_msg_receiver(rx);
+
+            let (m1, _rx1) = append_entries(Vote::new_committed(1, 1), Some((1, 0)), 1..3);
+            let (m2, _rx2) = append_entries(Vote::new_committed(2, 1), Some((1, 2)), 3..5);
+            tx.send(m1).await.unwrap();
+            tx.send(m2).await.unwrap();
+
+            receiver.ensure_buffered().await.unwrap();
+
+            let RaftMsg::AppendEntries { txs, .. } = receiver.try_recv().await.unwrap().unwrap() else {
+                panic!("expected AppendEntries");
+            };
+            assert_eq!(1, txs.len());
+
+            let RaftMsg::AppendEntries { rpc, txs } = receiver.try_recv().await.unwrap().unwrap() else {
+                panic!("expected AppendEntries");
+            };
+            assert_eq!(Vote::new_committed(2, 1), rpc.vote);
+            assert_eq!(1, txs.len());
+        });
+    }
 }
@@ -23,16 +23,35 @@
 //! See the [Engine/Runtime architecture guide](crate::docs::components::engine_runtime) for
 //! details.
 
+pub(crate) mod apply_throttle;
 pub(crate) mod balancer;
+pub(crate) mod config_mismatches;
 pub(crate) mod core_state;
+pub(crate) mod election_storm;
+pub(crate) mod election_tuner;
+pub(crate) mod event_log;
 pub(crate) mod heartbeat;
+pub(crate) mod held_writes;
 pub(crate) mod io_flush_tracking;
+#[cfg(feature = "audit-log-chain")]
+pub(crate) mod log_chain_hashes;
+pub(crate) mod log_holds;
 pub(crate) mod merged_raft_msg_receiver;
 pub(crate) mod notification;
+pub(crate) mod peer_capabilities;
+pub(crate) mod quorum_ack_latency;
 pub(crate) mod raft_msg;
+pub(crate) mod replication_throughput;
 pub(crate) mod runtime_stats;
+pub(crate) mod shared_metrics;
 pub(crate) mod sm;
+pub(crate) mod snapshot_deferral;
+pub(crate) mod snapshot_history;
+pub(crate) mod snapshot_meta_cache;
+pub(crate) mod snapshot_tail;
+pub(crate) mod snapshot_transfers;
 pub(crate) mod stage;
+pub(crate) mod storage_quota_state;
 
 mod client_responder_queue;
 mod notification_name;
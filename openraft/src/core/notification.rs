@@ -1,10 +1,15 @@
 use std::fmt;
 
 use display_more::DisplayOptionExt;
+#[cfg(feature = "audit-log-chain")]
+use display_more::DisplaySliceExt;
 
 use crate::RaftTypeConfig;
+use crate::SnapshotId;
 use crate::StorageError;
 use crate::core::NotificationName;
+#[cfg(feature = "audit-log-chain")]
+use crate::core::log_chain_hashes::ChainedEntries;
 use crate::core::sm;
 use crate::display_ext::DisplayInstantExt;
 use crate::progress::inflight_id::InflightId;
@@ -84,9 +89,30 @@ where C: RaftTypeConfig
         target: C::NodeId,
     },
 
+    /// A snapshot transmitting task has quit, successfully or not.
+    ///
+    /// It frees a slot for a queued snapshot transfer, see
+    /// [`Config::max_inflight_snapshots`](crate::Config::max_inflight_snapshots).
+    SnapshotTransmitDone {
+        target: C::NodeId,
+
+        /// The `InflightId` of the snapshot transfer.
+        inflight_id: InflightId,
+
+        /// The vote of the leader that sent the snapshot.
+        leader_vote: CommittedVoteOf<C>,
+
+        /// The id of the snapshot the target has installed, `None` if the transfer failed.
+        installed: Option<SnapshotId>,
+    },
+
     /// Result of executing a command sent from a state machine worker.
     StateMachine { command_result: sm::CommandResult<C> },
 
+    /// The entries to append are chained in a separate task.
+    #[cfg(feature = "audit-log-chain")]
+    LogChained { chained: ChainedEntries<C> },
+
     /// A tick event to wake up RaftCore to check timeout etc.
     Tick {
         /// ith tick
@@ -111,7 +137,10 @@ where C: RaftTypeConfig
             Self::LocalIO { .. } => NotificationName::LocalIO,
             Self::ReplicationProgress { .. } => NotificationName::ReplicationProgress,
             Self::HeartbeatProgress { .. } => NotificationName::HeartbeatProgress,
+            Self::SnapshotTransmitDone { .. } => NotificationName::SnapshotTransmitDone,
             Self::StateMachine { .. } => NotificationName::StateMachine,
+            #[cfg(feature = "audit-log-chain")]
+            Self::LogChained { .. } => NotificationName::LogChained,
             Self::Tick { .. } => NotificationName::Tick,
         }
     }
@@ -173,9 +202,34 @@ where C: RaftTypeConfig
                     sending_time.display(),
                 )
             }
+            Self::SnapshotTransmitDone {
+                target,
+                inflight_id,
+                leader_vote,
+                installed,
+            } => {
+                write!(
+                    f,
+                    "SnapshotTransmitDone: target={}, inflight_id: {}, leader_vote: {}, installed: {}",
+                    target,
+                    inflight_id,
+                    leader_vote,
+                    installed.display()
+                )
+            }
             Self::StateMachine { command_result } => {
                 write!(f, "{}", command_result)
             }
+            #[cfg(feature = "audit-log-chain")]
+            Self::LogChained { chained } => match &chained.result {
+                Ok((entries, _)) => write!(
+                    f,
+                    "LogChained: vote: {}, entries: {}",
+                    chained.committed_vote,
+                    entries.as_ref().display_n(10)
+                ),
+                Err(e) => write!(f, "LogChained: vote: {}, error: {}", chained.committed_vote, e),
+            },
             Self::Tick { i } => {
                 write!(f, "Tick {}", i)
             }
@@ -12,14 +12,16 @@ pub enum NotificationName {
     LocalIO,
     ReplicationProgress,
     HeartbeatProgress,
+    SnapshotTransmitDone,
     StateMachine,
+    LogChained,
     Tick,
 }
 
 impl NotificationName {
     /// Total number of variants.
     #[allow(dead_code)]
-    pub const COUNT: usize = 9;
+    pub const COUNT: usize = 11;
 
     /// All variants in canonical order.
     #[allow(dead_code)]
@@ -31,7 +33,9 @@ impl NotificationName {
         NotificationName::LocalIO,
         NotificationName::ReplicationProgress,
         NotificationName::HeartbeatProgress,
+        NotificationName::SnapshotTransmitDone,
         NotificationName::StateMachine,
+        NotificationName::LogChained,
         NotificationName::Tick,
     ];
 
@@ -46,8 +50,10 @@ impl NotificationName {
             NotificationName::LocalIO => 4,
             NotificationName::ReplicationProgress => 5,
             NotificationName::HeartbeatProgress => 6,
-            NotificationName::StateMachine => 7,
-            NotificationName::Tick => 8,
+            NotificationName::SnapshotTransmitDone => 7,
+            NotificationName::StateMachine => 8,
+            NotificationName::LogChained => 9,
+            NotificationName::Tick => 10,
         }
     }
 
@@ -61,7 +67,9 @@ impl NotificationName {
             NotificationName::LocalIO => "Notify::LocalIO",
             NotificationName::ReplicationProgress => "Notify::ReplicationProgress",
             NotificationName::HeartbeatProgress => "Notify::HeartbeatProgress",
+            NotificationName::SnapshotTransmitDone => "Notify::SnapshotTransmitDone",
             NotificationName::StateMachine => "Notify::StateMachine",
+            NotificationName::LogChained => "Notify::LogChained",
             NotificationName::Tick => "Notify::Tick",
         }
     }
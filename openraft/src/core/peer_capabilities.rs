@@ -0,0 +1,87 @@
+//! Tracks the protocol features every peer advertises.
+
+use std::collections::BTreeMap;
+
+use crate::RaftTypeConfig;
+use crate::raft::Capabilities;
+
+/// The last [`Capabilities`] received from every peer.
+#[derive(Debug, Clone)]
+pub(crate) struct PeerCapabilities<C>
+where C: RaftTypeConfig
+{
+    peers: BTreeMap<C::NodeId, Capabilities>,
+}
+
+impl<C> Default for PeerCapabilities<C>
+where C: RaftTypeConfig
+{
+    fn default() -> Self {
+        Self { peers: BTreeMap::new() }
+    }
+}
+
+impl<C> PeerCapabilities<C>
+where C: RaftTypeConfig
+{
+    /// Record the capabilities received from `peer`.
+    ///
+    /// Returns the previously known ones if they changed, so that a change, e.g., a peer being
+    /// upgraded or downgraded, is reported once.
+    pub(crate) fn update(&mut self, peer: &C::NodeId, capabilities: Capabilities) -> Option<Option<Capabilities>> {
+        let prev = self.peers.insert(peer.clone(), capabilities);
+        if prev == Some(capabilities) {
+            return None;
+        }
+        Some(prev)
+    }
+
+    /// Returns the peers in `nodes` that are known not to support `capability`.
+    ///
+    /// A peer that has not responded yet is assumed to support it.
+    pub(crate) fn lacking(
+        &self,
+        nodes: impl IntoIterator<Item = C::NodeId>,
+        capability: Capabilities,
+    ) -> Vec<C::NodeId> {
+        nodes.into_iter().filter(|id| self.peers.get(id).is_some_and(|c| !c.contains(capability))).collect()
+    }
+
+    /// The capabilities of every peer heard from, for metrics.
+    pub(crate) fn peers(&self) -> BTreeMap<C::NodeId, Capabilities> {
+        self.peers.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::testing::UTConfig;
+    use crate::raft::Capabilities;
+
+    type PeerCapabilities = super::PeerCapabilities<UTConfig>;
+
+    #[test]
+    fn test_peer_capabilities() {
+        let mut p = PeerCapabilities::default();
+
+        assert!(
+            p.lacking([1, 2, 3], Capabilities::PRE_VOTE).is_empty(),
+            "unknown peers are assumed capable"
+        );
+
+        assert_eq!(Some(None), p.update(&1, Capabilities::supported()));
+        assert_eq!(None, p.update(&1, Capabilities::supported()));
+        assert_eq!(Some(None), p.update(&2, Capabilities::empty()));
+
+        assert_eq!(vec![2], p.lacking([1, 2, 3], Capabilities::PRE_VOTE));
+        assert!(p.lacking([1, 3], Capabilities::PRE_VOTE).is_empty());
+
+        // Upgraded.
+        assert_eq!(
+            Some(Some(Capabilities::empty())),
+            p.update(&2, Capabilities::supported())
+        );
+        assert!(p.lacking([1, 2, 3], Capabilities::PRE_VOTE).is_empty());
+        assert_eq!(2, p.peers().len());
+    }
+}
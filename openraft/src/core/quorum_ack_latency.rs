@@ -0,0 +1,232 @@
+//! Measures how long log entries proposed by a leader take to be acknowledged by a quorum.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use crate::Instant;
+use crate::RaftTypeConfig;
+use crate::metrics::QuorumAckLatencyMetrics;
+use crate::type_config::alias::InstantOf;
+
+/// The number of most recent log entries the percentiles are computed over.
+const WINDOW_ENTRIES: u64 = 1024;
+
+/// Tracks the time from a leader appending log entries to them being committed.
+///
+/// Every batch of entries appended by the leader is recorded with the time it is submitted to
+/// storage. When the committed index covers a batch, the elapsed time is recorded for each entry
+/// of it, in a window of the last [`WINDOW_ENTRIES`] entries, and entries taking longer than the
+/// SLO are counted.
+#[derive(Debug, Clone)]
+pub(crate) struct QuorumAckLatency<C>
+where C: RaftTypeConfig
+{
+    slo: Option<Duration>,
+
+    /// Appended batches not yet committed: `[start, end)` log index range and when appended.
+    pending: VecDeque<(u64, u64, InstantOf<C>)>,
+
+    /// Recent latencies and the number of entries with each of them.
+    window: VecDeque<(Duration, u64)>,
+
+    window_entries: u64,
+
+    metrics: QuorumAckLatencyMetrics,
+}
+
+impl<C> QuorumAckLatency<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new(slo: Option<Duration>) -> Self {
+        Self {
+            slo,
+            pending: VecDeque::new(),
+            window: VecDeque::new(),
+            window_entries: 0,
+            metrics: QuorumAckLatencyMetrics::default(),
+        }
+    }
+
+    pub(crate) fn metrics(&self) -> QuorumAckLatencyMetrics {
+        self.metrics
+    }
+
+    /// Record that the leader appended entries in `[start, end)` at `now`.
+    pub(crate) fn appended(&mut self, start: u64, end: u64, now: InstantOf<C>) {
+        // Entries overwriting pending ones, which is not expected of a leader, restart tracking.
+        if self.pending.back().is_some_and(|(_, e, _)| *e > start) {
+            self.pending.clear();
+        }
+        self.pending.push_back((start, end, now));
+    }
+
+    /// Forget the appended entries, e.g., when this node is no longer the leader and they may
+    /// never be committed.
+    pub(crate) fn clear_pending(&mut self) {
+        self.pending.clear();
+    }
+
+    /// Record that entries before `end` are committed at `now`.
+    ///
+    /// `on_batch` is called with the latency and the number of entries of every batch committed.
+    /// Returns the number of entries that breached the SLO.
+    pub(crate) fn committed(&mut self, end: u64, now: InstantOf<C>, mut on_batch: impl FnMut(Duration, u64)) -> u64 {
+        let mut breaches = 0;
+        let mut changed = false;
+
+        while let Some((start, batch_end, at)) = self.pending.front().cloned() {
+            if start >= end {
+                break;
+            }
+
+            let n = std::cmp::min(batch_end, end) - start;
+            let latency = now.saturating_duration_since(at);
+
+            if self.slo.is_some_and(|slo| latency > slo) {
+                breaches += n;
+            }
+            self.record(latency, n);
+            on_batch(latency, n);
+            changed = true;
+
+            if batch_end <= end {
+                self.pending.pop_front();
+            } else {
+                self.pending[0].0 = end;
+            }
+        }
+
+        if changed {
+            self.metrics = self.compute(self.metrics.slo_breaches + breaches);
+        }
+        breaches
+    }
+
+    fn record(&mut self, latency: Duration, n: u64) {
+        self.window.push_back((latency, n));
+        self.window_entries += n;
+
+        while let Some((_, front_n)) = self.window.front() {
+            if self.window_entries - front_n < WINDOW_ENTRIES {
+                break;
+            }
+            self.window_entries -= front_n;
+            self.window.pop_front();
+        }
+    }
+
+    fn compute(&self, slo_breaches: u64) -> QuorumAckLatencyMetrics {
+        let mut sorted = self.window.iter().copied().collect::<Vec<_>>();
+        sorted.sort_unstable();
+
+        let total = self.window_entries;
+        let percentile = |p: u64| {
+            // The rank of the entry at the percentile, counting from 1.
+            let rank = std::cmp::max((total * p).div_ceil(100), 1);
+            let mut seen = 0;
+            for (latency, n) in sorted.iter() {
+                seen += n;
+                if seen >= rank {
+                    return *latency;
+                }
+            }
+            Duration::default()
+        };
+
+        QuorumAckLatencyMetrics {
+            samples: total,
+            p50: percentile(50),
+            p90: percentile(90),
+            p99: percentile(99),
+            max: sorted.last().map(|(latency, _)| *latency).unwrap_or_default(),
+            slo_breaches,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::engine::testing::UTConfig;
+    use crate::type_config::TypeConfigExt;
+
+    type QuorumAckLatency = super::QuorumAckLatency<UTConfig>;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn test_quorum_ack_latency_percentiles() {
+        let now = UTConfig::<()>::now();
+        let mut q = QuorumAckLatency::new(Some(ms(50)));
+
+        // 90 entries acked after 10ms, 10 entries after 100ms
+        q.appended(1, 91, now);
+        q.appended(91, 101, now + ms(10));
+
+        assert_eq!(0, q.committed(1, now + ms(5), |_, _| {}), "nothing before 1");
+        assert_eq!(0, q.metrics().samples);
+
+        assert_eq!(0, q.committed(91, now + ms(10), |_, _| {}));
+        assert_eq!(10, q.committed(101, now + ms(110), |_, _| {}));
+
+        let m = q.metrics();
+        assert_eq!(100, m.samples);
+        assert_eq!(ms(10), m.p50);
+        assert_eq!(ms(10), m.p90);
+        assert_eq!(ms(100), m.p99);
+        assert_eq!(ms(100), m.max);
+        assert_eq!(10, m.slo_breaches);
+    }
+
+    #[test]
+    fn test_quorum_ack_latency_partial_commit() {
+        let now = UTConfig::<()>::now();
+        let mut q = QuorumAckLatency::new(None);
+
+        let mut batches = vec![];
+
+        q.appended(1, 11, now);
+        assert_eq!(0, q.committed(4, now + ms(1), |l, n| batches.push((l, n))));
+        assert_eq!(3, q.metrics().samples);
+
+        let breaches = q.committed(11, now + ms(200), |l, n| batches.push((l, n)));
+        assert_eq!(0, breaches, "no SLO, no breach");
+        assert_eq!(vec![(ms(1), 3), (ms(200), 7)], batches);
+        let m = q.metrics();
+        assert_eq!(10, m.samples);
+        assert_eq!(ms(200), m.p50);
+        assert_eq!(ms(200), m.max);
+        assert_eq!(0, m.slo_breaches);
+    }
+
+    #[test]
+    fn test_quorum_ack_latency_window() {
+        let now = UTConfig::<()>::now();
+        let mut q = QuorumAckLatency::new(Some(ms(50)));
+
+        q.appended(0, 2000, now);
+        q.committed(2000, now + ms(100), |_, _| {});
+
+        q.appended(2000, 2000 + super::WINDOW_ENTRIES, now + ms(100));
+        q.committed(2000 + super::WINDOW_ENTRIES, now + ms(101), |_, _| {});
+
+        let m = q.metrics();
+        assert_eq!(super::WINDOW_ENTRIES, m.samples, "old samples are evicted");
+        assert_eq!(ms(1), m.max);
+        assert_eq!(2000, m.slo_breaches, "breaches are cumulative");
+    }
+
+    #[test]
+    fn test_quorum_ack_latency_clear_pending() {
+        let now = UTConfig::<()>::now();
+        let mut q = QuorumAckLatency::new(None);
+
+        q.appended(1, 11, now);
+        q.clear_pending();
+        q.committed(11, now + ms(1), |_, _| {});
+        assert_eq!(0, q.metrics().samples);
+    }
+}
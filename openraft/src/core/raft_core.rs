@@ -1,7 +1,10 @@
 use std::collections::BTreeMap;
 use std::fmt;
 use std::fmt::Debug;
+#[cfg(feature = "audit-log-chain")]
+use std::ops::Range;
 use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 use std::time::Duration;
 
@@ -11,12 +14,17 @@ use futures_util::FutureExt;
 use futures_util::StreamExt;
 use futures_util::TryFutureExt;
 use futures_util::stream::FuturesUnordered;
+use itertools::Itertools;
 use maplit::btreeset;
+use rand::RngExt;
 use tracing::Instrument;
 use tracing::Level;
 use tracing::Span;
 
+use crate::AsyncRuntime;
 use crate::ChangeMembers;
+use crate::ConfigDigest;
+use crate::ErrorSubject;
 use crate::Instant;
 use crate::Membership;
 use crate::RaftTypeConfig;
@@ -27,27 +35,53 @@ use crate::async_runtime::TryRecvError;
 use crate::async_runtime::watch::WatchSender;
 use crate::batch::Batch;
 use crate::config::Config;
+use crate::config::PurgePolicy;
 use crate::config::RuntimeConfig;
 use crate::core::ClientResponderQueue;
 use crate::core::ServerState;
 use crate::core::SharedReplicateBatch;
+use crate::core::apply_throttle::ApplyThrottle;
 use crate::core::balancer::Balancer;
+use crate::core::config_mismatches::ConfigMismatches;
 use crate::core::core_state::CoreState;
+use crate::core::event_log::EventLog;
 use crate::core::heartbeat::event::HeartbeatEvent;
 use crate::core::heartbeat::handle::HeartbeatWorkersHandle;
+use crate::core::held_writes::HeldWrite;
+use crate::core::held_writes::HeldWrites;
 use crate::core::io_flush_tracking::IoProgressSender;
+#[cfg(feature = "audit-log-chain")]
+use crate::core::log_chain_hashes::ChainedEntries;
+#[cfg(feature = "audit-log-chain")]
+use crate::core::log_chain_hashes::LogChainHashes;
+#[cfg(feature = "audit-log-chain")]
+use crate::core::log_chain_hashes::LogChaining;
+#[cfg(feature = "audit-log-chain")]
+use crate::core::log_chain_hashes::chain_entries;
+use crate::core::log_holds::LogHolds;
 use crate::core::merged_raft_msg_receiver::BatchRaftMsgReceiver;
 use crate::core::notification::Notification;
+use crate::core::peer_capabilities::PeerCapabilities;
+use crate::core::quorum_ack_latency::QuorumAckLatency;
 use crate::core::raft_msg::AppendEntriesTx;
 use crate::core::raft_msg::ClientReadTx;
 use crate::core::raft_msg::RaftMsg;
 use crate::core::raft_msg::ResultSender;
 use crate::core::raft_msg::VoteTx;
 use crate::core::raft_msg::external_command::ExternalCommand;
+use crate::core::replication_throughput::ReplicationThroughput;
 use crate::core::runtime_stats::RuntimeStats;
+use crate::core::shared_metrics::SharedMetrics;
 use crate::core::sm;
+use crate::core::snapshot_history::SnapshotHistory;
+use crate::core::snapshot_meta_cache::SnapshotMetaCache;
+use crate::core::snapshot_tail::SnapshotTail;
+use crate::core::snapshot_transfers::QueuedSnapshot;
+use crate::core::snapshot_transfers::SnapshotTransfers;
 use crate::core::stage::Stage;
+use crate::core::storage_quota_state::StorageQuotaState;
 use crate::display_ext::DisplayInstantExt;
+use crate::engine::AppendEntriesSender;
 use crate::engine::Command;
 use crate::engine::Condition;
 use crate::engine::Engine;
@@ -55,45 +89,76 @@ use crate::engine::Respond;
 use crate::engine::TargetProgress;
 use crate::engine::handler::leader_handler::LeaderHandler;
 use crate::engine::leader_log_ids::LeaderLogIds;
+use crate::entry::ApplyScope;
+#[cfg(feature = "audit-log-chain")]
+use crate::entry::ChainHash;
 use crate::entry::RaftEntry;
 use crate::entry::payload::EntryPayload;
+#[cfg(feature = "audit-log-chain")]
+use crate::entry::verify_log_chain;
 use crate::errors::AllowNextRevertError;
+use crate::errors::ApplyScopeUnsupported;
 use crate::errors::ClientWriteError;
 use crate::errors::Fatal;
 use crate::errors::ForwardToLeader;
 use crate::errors::Infallible;
 use crate::errors::InitializeError;
+#[cfg(feature = "audit-log-chain")]
+use crate::errors::LogChainError;
+use crate::errors::LogOnlyLogsPurged;
 use crate::errors::NetworkError;
 use crate::errors::QuorumNotEnough;
 use crate::errors::RPCError;
+use crate::errors::StaleRead;
+use crate::errors::StorageFull;
 use crate::errors::StorageIOResult;
 use crate::errors::Timeout;
 use crate::impls::ProgressResponder;
 use crate::log_id::option_raft_log_id_ext::OptionRaftLogIdExt;
+use crate::membership::QuorumKind;
+use crate::membership::check_quorum_policy;
+use crate::metrics::CandidateMetrics;
 use crate::metrics::HeartbeatMetrics;
+use crate::metrics::MetricsHistory;
 use crate::metrics::MetricsRecorder;
 use crate::metrics::RaftDataMetrics;
 use crate::metrics::RaftMetrics;
 use crate::metrics::RaftServerMetrics;
 use crate::metrics::ReplicationMetrics;
+use crate::metrics::ReplicationTargetMetrics;
+use crate::metrics::ReplicationTargetsMetrics;
 use crate::metrics::SerdeInstant;
+use crate::network::NetReadIndex;
+use crate::network::NetSnapshot;
 use crate::network::NetStreamAppend;
 use crate::network::NetTransferLeader;
 use crate::network::NetVote;
 use crate::network::RPCOption;
 use crate::network::RPCTypes;
 use crate::network::RaftNetworkFactory;
+use crate::network::hedge;
 use crate::progress::Progress;
 use crate::progress::stream_id::StreamId;
+use crate::proposer::LeaderQuorumSet;
 use crate::quorum::QuorumSet;
 use crate::raft::AppendEntriesRequest;
+use crate::raft::Capabilities;
 use crate::raft::ClientWriteResult;
+use crate::raft::CorrelationId;
+use crate::raft::FetchSnapshotRequest;
+use crate::raft::FetchSnapshotResponse;
 use crate::raft::LogSegment;
+use crate::raft::RaftEvent;
+use crate::raft::ReadOptions;
 use crate::raft::ReadPolicy;
+use crate::raft::SeedSnapshotRequest;
+use crate::raft::ShutdownReport;
 use crate::raft::StreamAppendError;
+use crate::raft::StreamAppendResult;
 use crate::raft::VoteRequest;
 use crate::raft::VoteResponse;
 use crate::raft::linearizable_read::Linearizer;
+use crate::raft::message::ReadIndexRequest;
 use crate::raft::message::TransferLeaderRequest;
 use crate::raft::responder::Responder;
 use crate::raft::responder::core_responder::CoreResponder;
@@ -110,8 +175,14 @@ use crate::replication::replication_progress;
 use crate::replication::snapshot_transmitter::SnapshotTransmitter;
 use crate::runtime::RaftRuntime;
 use crate::storage::IOFlushed;
+use crate::storage::LogEntryMeta;
+use crate::storage::RaftLogReader;
 use crate::storage::RaftLogStorage;
+use crate::storage::SnapshotTrigger;
+use crate::storage::SnapshotTriggerContext;
+use crate::storage::StorageUsageProbe;
 use crate::type_config::TypeConfigExt;
+use crate::type_config::alias::AsyncRuntimeOf;
 use crate::type_config::alias::BatchOf;
 use crate::type_config::alias::CommittedLeaderIdOf;
 use crate::type_config::alias::CommittedVoteOf;
@@ -121,12 +192,15 @@ use crate::type_config::alias::LogIdOf;
 use crate::type_config::alias::MpscReceiverOf;
 use crate::type_config::alias::MpscSenderOf;
 use crate::type_config::alias::OneshotReceiverOf;
+use crate::type_config::alias::OneshotSenderOf;
+use crate::type_config::alias::SnapshotMetaOf;
 use crate::type_config::alias::UncommittedVoteOf;
 use crate::type_config::alias::VoteOf;
 use crate::type_config::alias::WatchReceiverOf;
 use crate::type_config::alias::WatchSenderOf;
 use crate::type_config::async_runtime::mpsc::MpscSender;
 use crate::vote::RaftLeaderId;
+use crate::vote::RaftTerm;
 use crate::vote::RaftVote;
 use crate::vote::raft_vote::RaftVoteExt;
 use crate::vote::vote_status::VoteStatus;
@@ -136,6 +210,13 @@ pub(crate) struct ApplyResult<C: RaftTypeConfig> {
     pub(crate) since: u64,
     pub(crate) end: u64,
     pub(crate) last_applied: LogIdOf<C>,
+
+    /// The last applied log id the state machine has persisted, reported by
+    /// [`RaftStateMachine::durable_applied()`](crate::storage::RaftStateMachine::durable_applied).
+    pub(crate) durable_applied: Option<LogIdOf<C>>,
+
+    /// The number of log entries the state machine standby lags behind, if there is a standby.
+    pub(crate) standby_lag: Option<u64>,
 }
 
 impl<C: RaftTypeConfig> Debug for ApplyResult<C> {
@@ -144,6 +225,8 @@ impl<C: RaftTypeConfig> Debug for ApplyResult<C> {
             .field("since", &self.since)
             .field("end", &self.end)
             .field("last_applied", &self.last_applied)
+            .field("durable_applied", &self.durable_applied)
+            .field("standby_lag", &self.standby_lag)
             .finish()
     }
 }
@@ -230,6 +313,14 @@ where
     /// For broadcast committed log id to replication task.
     pub(crate) committed_tx: WatchSenderOf<C, Option<LogIdOf<C>>>,
 
+    /// For broadcast the last backup barrier to replication task.
+    pub(crate) backup_barrier_tx: WatchSenderOf<C, Option<LogIdOf<C>>>,
+
+    /// For publishing events to the subscribers of [`Raft::subscribe_events`].
+    ///
+    /// [`Raft::subscribe_events`]: crate::Raft::subscribe_events
+    pub(crate) tx_events: WatchSenderOf<C, EventLog<C>>,
+
     pub(crate) tx_metrics: WatchSenderOf<C, RaftMetrics<C>>,
     pub(crate) tx_data_metrics: WatchSenderOf<C, RaftDataMetrics<C>>,
     pub(crate) tx_server_metrics: WatchSenderOf<C, RaftServerMetrics<C>>,
@@ -255,6 +346,82 @@ where
     /// [`Raft::set_metrics_recorder`]: crate::Raft::set_metrics_recorder
     pub(crate) metrics_recorder: Option<Arc<dyn MetricsRecorder>>,
 
+    /// Reports the storage usage, to enforce [`Config::storage_quota`].
+    ///
+    /// Installed with [`Raft::set_storage_usage_probe`].
+    ///
+    /// [`Raft::set_storage_usage_probe`]: crate::Raft::set_storage_usage_probe
+    pub(crate) storage_usage_probe: Option<Arc<dyn StorageUsageProbe>>,
+
+    /// Decides when to build a snapshot, in addition to [`Config::snapshot_policy`].
+    ///
+    /// Installed with [`Raft::set_snapshot_trigger`].
+    ///
+    /// [`Raft::set_snapshot_trigger`]: crate::Raft::set_snapshot_trigger
+    pub(crate) snapshot_trigger: Option<Arc<dyn SnapshotTrigger<C>>>,
+
+    /// Checks the leader lease invariant on every lease read served.
+    ///
+    /// Installed with [`Raft::set_lease_checker`].
+    ///
+    /// [`Raft::set_lease_checker`]: crate::Raft::set_lease_checker
+    #[cfg(feature = "lease-check")]
+    pub(crate) lease_checker: Option<crate::testing::lease_check::LeaseChecker<C>>,
+
+    /// The most recent metrics snapshots, shared with the `Raft` handle.
+    pub(crate) metrics_history: MetricsHistory<C>,
+
+    /// The log indexes still needed by log subscribers, shared with the `Raft` handle.
+    pub(crate) log_holds: LogHolds,
+
+    /// The meta of the current snapshot, shared with the `Raft` handle.
+    pub(crate) snapshot_meta_cache: SnapshotMetaCache<C>,
+
+    /// The meta of the most recent snapshots, shared with the `Raft` handle.
+    pub(crate) snapshot_history: SnapshotHistory<C>,
+
+    /// The replication throughput measured by the replication tasks, shared with the `Raft`
+    /// handle.
+    pub(crate) replication_throughput: ReplicationThroughput<C>,
+
+    /// The log entries sent along with a snapshot, held until the snapshot is installed.
+    pub(crate) snapshot_tail: SnapshotTail<C>,
+
+    /// The client writes received while no leader is known, held until one is.
+    pub(crate) held_writes: HeldWrites<C>,
+
+    /// The chain hashes of the local log entries that can still be truncated, to chain the next
+    /// entries without reading the log. See [`Config::audit_log_chain`].
+    #[cfg(feature = "audit-log-chain")]
+    pub(crate) log_chain_hashes: LogChainHashes,
+
+    /// The task chaining the entries to append off `RaftCore`.
+    #[cfg(feature = "audit-log-chain")]
+    pub(crate) log_chaining: LogChaining<C>,
+
+    /// The running and queued snapshot transfers, limited by [`Config::max_inflight_snapshots`].
+    pub(crate) snapshot_transfers: SnapshotTransfers<C>,
+
+    /// The callers of [`Trigger::snapshot()`] waiting for the snapshot being built.
+    ///
+    /// [`Trigger::snapshot()`]: crate::raft::trigger::Trigger::snapshot
+    pub(crate) snapshot_waiters: Vec<OneshotSenderOf<C, Option<SnapshotMetaOf<C>>>>,
+
+    /// The peers whose safety-relevant config differs from this node's.
+    pub(crate) config_mismatches: ConfigMismatches<C>,
+
+    /// The protocol features every peer advertised in its last `VoteResponse`.
+    pub(crate) peer_capabilities: PeerCapabilities<C>,
+
+    /// The time entries appended by this node as a leader take to be committed.
+    pub(crate) quorum_ack_latency: QuorumAckLatency<C>,
+
+    /// The maps in the last published metrics, shared with the next ones if unchanged.
+    pub(crate) shared_metrics: SharedMetrics<C>,
+
+    /// The report of the final state, filled when `RaftCore` quits, shared with the `Raft` handle.
+    pub(crate) shutdown_report: Arc<std::sync::Mutex<Option<ShutdownReport<C>>>>,
+
     pub(crate) span: Span,
 }
 
@@ -273,6 +440,23 @@ impl VoteRequestKind {
             VoteRequestKind::PreVote => "pre-vote",
         }
     }
+
+    /// Send a Vote or Pre-Vote request to `client`.
+    async fn send<C, N>(
+        self,
+        client: &mut N,
+        req: VoteRequest<C>,
+        option: RPCOption,
+    ) -> Result<VoteResponse<C>, RPCError<C>>
+    where
+        C: RaftTypeConfig,
+        N: NetVote<C>,
+    {
+        match self {
+            VoteRequestKind::Vote => client.vote(req, option).await,
+            VoteRequestKind::PreVote => client.pre_vote(req, option).await,
+        }
+    }
 }
 
 impl<C, NF, LS, SM> RaftCore<C, NF, LS, SM>
@@ -283,9 +467,20 @@ where
     SM: 'static,
 {
     /// The main loop of the Raft protocol.
-    pub(crate) async fn main(mut self, rx_shutdown: OneshotReceiverOf<C, ()>) -> Result<Infallible, Fatal<C>> {
+    pub(crate) async fn main(mut self, mut rx_shutdown: OneshotReceiverOf<C, ()>) -> Result<Infallible, Fatal<C>> {
         let span = tracing::span!(parent: &self.span, Level::DEBUG, "main");
-        let res = self.do_main(rx_shutdown).instrument(span).await;
+        let mut res = self.do_main(&mut rx_shutdown).instrument(span.clone()).await;
+
+        while let Err(Fatal::StorageError(error)) = &res
+            && self.can_degrade()
+        {
+            if let Err(e) = self.degraded_loop(error.clone(), &mut rx_shutdown).instrument(span.clone()).await {
+                res = Err(e);
+                break;
+            }
+            // The storage works again.
+            res = self.resume_main(&mut rx_shutdown).instrument(span.clone()).await;
+        }
 
         // Flush buffered metrics
         self.flush_metrics();
@@ -308,14 +503,34 @@ where
             self.tx_metrics.send(curr).ok();
         }
 
+        let report = self.build_shutdown_report(err.clone());
+        tracing::info!("RaftCore shutdown: {}", report);
+        *self.shutdown_report.lock().unwrap() = Some(report);
+
         tracing::info!("RaftCore shutdown complete");
 
         Err(err)
     }
 
+    /// Build the report of the final state when `RaftCore` quits for `reason`.
+    fn build_shutdown_report(&self, reason: Fatal<C>) -> ShutdownReport<C> {
+        let state = &self.engine.state;
+
+        ShutdownReport {
+            id: self.id.clone(),
+            vote: state.vote_ref().clone(),
+            last_log_id: state.last_log_id().cloned(),
+            committed: state.local_committed().cloned(),
+            applied: state.io_applied().cloned(),
+            snapshot: state.snapshot_meta.last_log_id.is_some().then(|| state.snapshot_meta.clone()),
+            pending_proposals: self.client_responders.len() as u64 + self.held_writes.entries(),
+            reason,
+        }
+    }
+
     #[tracing::instrument(level = "trace", skip_all, fields(id=display(&self.id), cluster=%self.config.cluster_name
     ))]
-    async fn do_main(&mut self, rx_shutdown: OneshotReceiverOf<C, ()>) -> Result<Infallible, Fatal<C>> {
+    async fn do_main(&mut self, rx_shutdown: &mut OneshotReceiverOf<C, ()>) -> Result<Infallible, Fatal<C>> {
         tracing::debug!("raft node is initializing");
 
         self.engine.startup();
@@ -328,6 +543,16 @@ where
         self.runtime_loop(rx_shutdown).await
     }
 
+    /// Go on running the main loop after leaving the degraded mode.
+    async fn resume_main(&mut self, rx_shutdown: &mut OneshotReceiverOf<C, ()>) -> Result<Infallible, Fatal<C>> {
+        tracing::info!("raft node resumes from degraded mode");
+
+        self.run_engine_commands().await?;
+        self.flush_metrics();
+
+        self.runtime_loop(rx_shutdown).await
+    }
+
     /// Handle `is_leader` requests.
     ///
     /// Send heartbeat to all voters. We respond once we have
@@ -363,10 +588,19 @@ where
 
         if read_policy == ReadPolicy::LeaseRead {
             let now = C::now();
+            // Shorten the lease by the clock drift margin, in case a follower's clock runs faster.
+            let lease =
+                self.engine.config.timer_config.leader_lease.saturating_sub(self.config.lease_read_clock_drift());
             // Check if the lease is expired.
             if let Some(last_quorum_acked_time) = self.last_quorum_acked_time()
-                && now < last_quorum_acked_time + self.engine.config.timer_config.leader_lease
+                && now < last_quorum_acked_time + lease
             {
+                #[cfg(feature = "lease-check")]
+                if let Some(checker) = &self.lease_checker {
+                    let leader_id = self.engine.state.vote_ref().leader_id().clone();
+                    checker.on_lease_read(self.id.clone(), leader_id, now);
+                }
+
                 tx.send(Ok(resp)).ok();
                 return;
             }
@@ -381,12 +615,17 @@ where
         let my_vote = self.engine.state.vote_ref().clone();
         let ttl = Duration::from_millis(self.config.heartbeat_interval);
         let eff_mem = self.engine.state.membership_state.effective().clone();
+        let quorum_set = LeaderQuorumSet::new(
+            Arc::new((*eff_mem.membership()).clone()),
+            self.engine.config.quorum_policy.as_ref(),
+            QuorumKind::Commit,
+        );
         let core_tx = self.tx_notification.clone();
 
         let mut granted = btreeset! {my_id.clone()};
 
         // single-node quorum, fast path, return quickly.
-        if eff_mem.is_quorum(granted.iter()) {
+        if quorum_set.is_quorum(granted.iter()) {
             tx.send(Ok(resp)).ok();
             return;
         }
@@ -412,6 +651,9 @@ where
                 prev_log_id: progress.matching().cloned(),
                 entries: vec![],
                 leader_commit: self.engine.state.cluster_committed().cloned(),
+                backup_barrier: self.core_state.backup_barrier.clone(),
+                after_snapshot: None,
+                config_digest: Some(self.config_mismatches.local()),
             };
 
             // Safe unwrap(): target is in membership
@@ -513,7 +755,7 @@ where
                 // Success or Conflict both confirm leadership (got valid response from follower)
                 granted.insert(target);
 
-                if eff_mem.is_quorum(granted.iter()) {
+                if quorum_set.is_quorum(granted.iter()) {
                     tx.send(Ok(resp)).ok();
                     return;
                 }
@@ -537,6 +779,247 @@ where
         let _ = C::spawn(waiting_fu.instrument(tracing::debug_span!("spawn_is_leader_waiting")));
     }
 
+    /// Chain `entries` in a separate task before they are appended to the local log, so that
+    /// encoding and hashing them does not block `RaftCore`. See [`Config::audit_log_chain`].
+    ///
+    /// The queued commands are held until the chained entries are appended, see
+    /// [`Self::append_chained_entries()`]. Thus the chain hash of the entry before them is already
+    /// in [`LogChainHashes`], which is loaded before `RaftCore` starts, so the log is not read.
+    #[cfg(feature = "audit-log-chain")]
+    fn spawn_chain_entries(&mut self, committed_vote: CommittedVoteOf<C>, entries: BatchOf<C, C::Entry>) {
+        let prev = entries.as_ref().first().and_then(|e| self.log_chain_hashes.before(e.index()));
+        let id = self.log_chaining.start();
+        let tx = self.tx_notification.clone();
+
+        let fu = async move {
+            let chained = ChainedEntries {
+                id,
+                committed_vote,
+                result: chain_entries::<C>(prev, entries),
+            };
+            tx.send(Notification::LogChained { chained }).await.ok();
+        };
+
+        // False positive lint warning(`non-binding `let` on a future`): https://github.com/rust-lang/rust-clippy/issues/9932
+        #[allow(clippy::let_underscore_future)]
+        let _ = C::spawn(fu.instrument(tracing::debug_span!("spawn_chain_entries")));
+    }
+
+    /// Append the entries chained by [`Self::spawn_chain_entries()`].
+    ///
+    /// A replicated entry that does not follow the local log means the local log or the received
+    /// entry has been tampered with, and a [`StorageError`] is returned.
+    #[cfg(feature = "audit-log-chain")]
+    async fn append_chained_entries(&mut self, chained: ChainedEntries<C>) -> Result<(), StorageError<C>> {
+        let (entries, hashes) = chained.result.map_err(|err| {
+            tracing::error!("{}", err);
+            StorageError::write_logs(C::err_from_error(&err))
+        })?;
+
+        for (entry, hash) in entries.as_ref().iter().zip(hashes) {
+            self.log_chain_hashes.insert(entry.index(), hash);
+        }
+
+        // Committed entries are never truncated, thus the entries before them are not looked up.
+        if let Some(committed) = self.engine.state.committed() {
+            self.log_chain_hashes.purge(committed.index());
+        }
+
+        self.submit_append_entries(chained.committed_vote, entries).await
+    }
+
+    /// Read what the last `n` log entries are in a separate task, so that reading the storage
+    /// does not block `RaftCore`.
+    async fn spawn_get_log_tail(
+        &mut self,
+        n: u64,
+        tx: OneshotSenderOf<C, Result<Vec<LogEntryMeta<C>>, StorageError<C>>>,
+    ) {
+        let st = &self.engine.state;
+        let end = st.last_log_id().next_index();
+        let start = std::cmp::max(st.last_purged_log_id().next_index(), end.saturating_sub(n));
+
+        if start >= end {
+            tx.send(Ok(vec![])).ok();
+            return;
+        }
+
+        let mut log_reader = self.log_store.get_log_reader().await;
+        let fu = async move {
+            let res = log_reader.get_log_meta(start..=end - 1).await.sto_read_logs();
+            tx.send(res).ok();
+        };
+
+        // False positive lint warning(`non-binding `let` on a future`): https://github.com/rust-lang/rust-clippy/issues/9932
+        #[allow(clippy::let_underscore_future)]
+        let _ = C::spawn(fu.instrument(tracing::debug_span!("spawn_get_log_tail")));
+    }
+
+    /// Verify the chain hash of the log entries in `range` in a separate task, so that reading the
+    /// storage does not block `RaftCore`.
+    ///
+    /// The range is limited to the entries in the local log. The first entry is verified with the
+    /// entry before it, if it is in the log; otherwise it is trusted as the anchor of the chain.
+    #[cfg(feature = "audit-log-chain")]
+    async fn spawn_verify_log_chain(
+        &mut self,
+        range: Range<u64>,
+        tx: OneshotSenderOf<C, Result<Option<ChainHash>, LogChainError<C>>>,
+    ) {
+        /// The number of entries to read at a time.
+        const CHUNK_SIZE: u64 = 1024;
+
+        let st = &self.engine.state;
+        let first_index = st.last_purged_log_id().next_index();
+        let start = std::cmp::max(range.start, first_index);
+        let end = std::cmp::min(range.end, st.last_log_id().next_index());
+
+        if start >= end {
+            tx.send(Ok(None)).ok();
+            return;
+        }
+
+        let mut log_reader = self.log_store.get_log_reader().await;
+        let fu = async move {
+            let res = async {
+                let mut prev = None;
+                if start > first_index {
+                    let entries = log_reader.try_get_log_entries(start - 1..start).await.sto_read_logs()?;
+                    prev = entries.first().and_then(|e| e.chain_hash());
+                }
+
+                let mut next = start;
+                while next < end {
+                    let chunk_end = std::cmp::min(next + CHUNK_SIZE, end);
+                    let entries = log_reader.try_get_log_entries(next..chunk_end).await.sto_read_logs()?;
+
+                    let (Some(first), Some(last)) = (entries.first(), entries.last()) else {
+                        break;
+                    };
+
+                    // The entries before `first` are purged while verifying.
+                    if first.index() != next {
+                        prev = None;
+                    }
+
+                    next = last.index() + 1;
+                    prev = verify_log_chain::<C>(prev, &entries)?;
+                }
+
+                Ok::<_, LogChainError<C>>(prev)
+            }
+            .await;
+
+            tx.send(res).ok();
+        };
+
+        // False positive lint warning(`non-binding `let` on a future`): https://github.com/rust-lang/rust-clippy/issues/9932
+        #[allow(clippy::let_underscore_future)]
+        let _ = C::spawn(fu.instrument(tracing::debug_span!("spawn_verify_log_chain")));
+    }
+
+    /// Get a [`Linearizer`] for a read served on this node, by asking the leader for the read log
+    /// id.
+    ///
+    /// On the leader it is the same as [`Self::handle_ensure_linearizable_read()`]. Otherwise a
+    /// [`ReadIndexRequest`] is sent to the current leader. If the leader is unknown or can not be
+    /// reached, or this node is a witness, a [`ForwardToLeader`] error is returned, so that the
+    /// read can be forwarded to the leader instead.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(super) async fn handle_follower_read_index(&mut self, read_policy: ReadPolicy, tx: ClientReadTx<C>) {
+        if self.engine.leader.is_some() {
+            self.handle_ensure_linearizable_read(read_policy, tx).await;
+            return;
+        }
+
+        let forward = self.engine.state.forward_to_leader();
+
+        // A witness or a log-only node has no state machine to read from: the read has to be
+        // served elsewhere.
+        if self.is_witness() || self.is_log_only() {
+            tx.send(Err(forward.into())).ok();
+            return;
+        }
+
+        let (Some(leader_id), Some(leader_node)) = (forward.leader_id.clone(), forward.leader_node.clone()) else {
+            tx.send(Err(forward.into())).ok();
+            return;
+        };
+
+        let mut client = self.network_factory.new_client(leader_id.clone(), &leader_node).await;
+
+        let req = ReadIndexRequest::new(self.id.clone(), read_policy);
+        let ttl = Duration::from_millis(self.config.election_timeout_max);
+        let option = RPCOption::new(ttl);
+        let my_id = self.id.clone();
+        let applied = self.engine.state.io_applied().cloned();
+
+        let fut = async move {
+            let res = C::timeout(ttl, client.read_index(req, option)).await;
+
+            let res = match res {
+                Ok(Ok(resp)) => resp,
+                Ok(Err(e)) => {
+                    tracing::warn!("error sending read_index: {}, leader: {}", e, leader_id);
+                    Err(forward.into())
+                }
+                Err(timeout) => {
+                    tracing::warn!("timeout sending read_index: {}, leader: {}", timeout, leader_id);
+                    Err(forward.into())
+                }
+            };
+
+            let res = res.map(|read_log_id| Linearizer::new(my_id, read_log_id, applied));
+            tx.send(res).ok();
+        };
+
+        // False positive lint warning(`non-binding `let` on a future`): https://github.com/rust-lang/rust-clippy/issues/9932
+        #[allow(clippy::let_underscore_future)]
+        let _ = C::spawn(fut.instrument(tracing::debug_span!("spawn_follower_read_index")));
+    }
+
+    /// Fetch a snapshot from the seed node a leader asked this node to fetch from.
+    ///
+    /// `None` is sent back if the seed can not be reached or has no snapshot that includes
+    /// `req.min_last_log_id`, so that the leader sends its own snapshot instead.
+    pub(super) async fn spawn_fetch_seed_snapshot(
+        &mut self,
+        req: SeedSnapshotRequest<C>,
+        tx: OneshotSenderOf<C, FetchSnapshotResponse<C>>,
+    ) {
+        let seed_id = req.seed_id;
+        let mut client = self.network_factory.new_client(seed_id.clone(), &req.seed_node).await;
+
+        let fetch_req = FetchSnapshotRequest {
+            vote: req.vote,
+            min_last_log_id: req.min_last_log_id,
+        };
+        let ttl = self.config.install_snapshot_timeout();
+        let option = RPCOption::new(ttl);
+
+        let fut = async move {
+            let res = C::timeout(ttl, client.fetch_snapshot(fetch_req, option)).await;
+
+            let snapshot = match res {
+                Ok(Ok(snapshot)) => snapshot,
+                Ok(Err(e)) => {
+                    tracing::warn!("error fetching snapshot: {}, seed: {}", e, seed_id);
+                    None
+                }
+                Err(timeout) => {
+                    tracing::warn!("timeout fetching snapshot: {}, seed: {}", timeout, seed_id);
+                    None
+                }
+            };
+
+            tx.send(snapshot).ok();
+        };
+
+        // False positive lint warning(`non-binding `let` on a future`): https://github.com/rust-lang/rust-clippy/issues/9932
+        #[allow(clippy::let_underscore_future)]
+        let _ = C::spawn(fut.instrument(tracing::debug_span!("spawn_fetch_seed_snapshot")));
+    }
+
     /// Submit change-membership by writing a Membership log entry.
     ///
     /// If `retain` is `true`, removed `voter` will becomes `learner`. Otherwise they will
@@ -559,9 +1042,25 @@ where
         &mut self,
         changes: ChangeMembers<C::NodeId, C::Node>,
         retain: bool,
+        correlation_id: Option<CorrelationId>,
         tx: ProgressResponder<C, ClientWriteResult<C>>,
     ) {
-        let res = self.engine.state.membership_state.change_handler().apply(changes, retain);
+        let promote_ids = changes.promote_when_caught_up_ids();
+
+        let res = self
+            .engine
+            .state
+            .membership_state
+            .change_handler()
+            .reject_duplicate_nodes(self.config.reject_duplicate_nodes())
+            .apply(changes, retain);
+        let res = res.and_then(|m| {
+            if let Some(policy) = &self.engine.config.quorum_policy {
+                check_quorum_policy(policy.as_ref(), &m)?;
+            }
+            self.check_log_only_logs(&m)?;
+            Ok(m)
+        });
         let new_membership = match res {
             Ok(x) => x,
             Err(e) => {
@@ -570,12 +1069,30 @@ where
             }
         };
 
-        self.write_entries(
-            Batch::of([EntryPayload::Membership(new_membership)]),
-            Batch::of([Some(CoreResponder::Progress(tx))]),
-            #[cfg(feature = "runtime-stats")]
-            C::now(),
-        );
+        // A membership entry is not a client write: it does not go through `write_entries()`, so
+        // that it is not counted as a client write batch and its failure is reported only to `tx`.
+        let mut lh = match self.ensure_writable_leader_handler() {
+            Ok(lh) => lh,
+            Err(forward_err) => {
+                tx.on_complete(Err(ClientWriteError::ForwardToLeader(forward_err)));
+                return;
+            }
+        };
+
+        let log_id = lh.leader_append_internal(EntryPayload::Membership(new_membership));
+        lh.leader.promote_when_caught_up.extend(promote_ids);
+
+        if let Some(correlation_id) = correlation_id {
+            tracing::info!(
+                correlation_id = display(correlation_id),
+                log_id = display(&log_id),
+                "membership change proposed"
+            );
+        }
+
+        let mut responder = CoreResponder::progress(tx).with_correlation_id(correlation_id);
+        responder.on_accept(log_id.clone());
+        self.client_responders.push(log_id.index(), responder);
     }
 
     /// Ensure this node is a writable leader and return a leader handler.
@@ -606,15 +1123,41 @@ where
     /// if provided. The calling side may not receive a result if raft is shut down.
     ///
     /// The responder is either Responder type of [`RaftTypeConfig::Responder`]
-    /// (application-defined) or [`ProgressResponder`] (general-purpose); the former is for
-    /// application-defined entries like user data, the latter is for membership configuration
-    /// changes.
-    #[tracing::instrument(level = "debug", skip_all, fields(id = display(&self.id)))]
+    /// (application-defined) or [`ProgressResponder`] (general-purpose) for a blocking client
+    /// write. Membership entries are proposed by [`Self::change_membership`] instead.
     pub fn write_entries(
         &mut self,
         payloads: BatchOf<C, EntryPayloadOf<C>>,
         responders: BatchOf<C, Option<CoreResponder<C>>>,
         #[cfg(feature = "runtime-stats")] proposed_at: InstantOf<C>,
+    ) -> Option<LeaderLogIds<CommittedLeaderIdOf<C>>> {
+        self.write_entries_in_scope(
+            payloads,
+            responders,
+            None,
+            None,
+            #[cfg(feature = "runtime-stats")]
+            proposed_at,
+        )
+    }
+
+    /// Write log entries that are applied only by the nodes in `scope`, or by every node if it is
+    /// `None`.
+    ///
+    /// If `reserved_index` is `Some`, the entries fill the log indexes reserved with
+    /// [`LeaderHandler::reserve_log_indexes()`] from this index, and are rejected with
+    /// [`ReservedIndexMismatch`](crate::errors::ReservedIndexMismatch) if they do not continue the
+    /// reservation.
+    ///
+    /// See [`ApplyScope`].
+    #[tracing::instrument(level = "debug", skip_all, fields(id = display(&self.id)))]
+    pub(crate) fn write_entries_in_scope(
+        &mut self,
+        payloads: BatchOf<C, EntryPayloadOf<C>>,
+        responders: BatchOf<C, Option<CoreResponder<C>>>,
+        scope: Option<ApplyScope<C::NodeId>>,
+        reserved_index: Option<u64>,
+        #[cfg(feature = "runtime-stats")] proposed_at: InstantOf<C>,
     ) -> Option<LeaderLogIds<CommittedLeaderIdOf<C>>> {
         debug_assert_eq!(
             payloads.len(),
@@ -624,6 +1167,11 @@ where
 
         tracing::debug!("write {} entries", payloads.len());
 
+        let storage_full = match &self.core_state.storage_quota {
+            StorageQuotaState::Full(e) => Some(e.clone()),
+            _ => None,
+        };
+
         let mut lh = match self.ensure_writable_leader_handler() {
             Ok(lh) => lh,
             Err(forward_err) => {
@@ -635,10 +1183,48 @@ where
             }
         };
 
+        if let Some(e) = storage_full {
+            tracing::debug!("reject {} entries: {}", payloads.len(), e);
+            let err = ClientWriteError::StorageFull(e);
+            for tx in responders.into_iter().flatten() {
+                tx.on_complete(Err(err.clone()))
+            }
+            return None;
+        }
+
+        if scope.is_some() && !lh.supports_apply_scope() {
+            let err = ClientWriteError::ApplyScopeUnsupported(ApplyScopeUnsupported {});
+            for tx in responders.into_iter().flatten() {
+                tx.on_complete(Err(err.clone()))
+            }
+            return None;
+        }
+
+        // The time left to the deadline of each write, stored in its entry.
+        let now = C::now();
+        let deadlines = responders
+            .as_ref()
+            .iter()
+            .map(|tx| tx.as_ref()?.deadline().map(|d| d.saturating_duration_since(now)))
+            .collect::<Vec<_>>();
+
         // TODO: it should returns membership config error etc. currently this is done by the
         //       caller.
         let entry_count = payloads.len() as u64;
-        let log_ids = lh.leader_append_entries(payloads)?;
+        let log_ids = match reserved_index {
+            None => lh.leader_append_entries_in_scope(payloads, scope, &deadlines)?,
+            Some(index) => match lh.leader_append_reserved_entries(index, payloads, scope, &deadlines) {
+                Ok(log_ids) => log_ids?,
+                Err(e) => {
+                    tracing::debug!("reject reserved entries: {}", e);
+                    let err = ClientWriteError::ReservedIndexMismatch(e);
+                    for tx in responders.into_iter().flatten() {
+                        tx.on_complete(Err(err.clone()))
+                    }
+                    return None;
+                }
+            },
+        };
 
         #[cfg(feature = "runtime-stats")]
         {
@@ -653,9 +1239,17 @@ where
         }
 
         for (log_id, resp_tx) in log_ids.clone().into_iter().zip(responders) {
-            if let Some(tx) = resp_tx {
+            if let Some(mut tx) = resp_tx {
                 let index = log_id.index();
                 tracing::debug!("write entries: push tx to responders, log_id: {}", log_id);
+                if let Some(correlation_id) = tx.correlation_id() {
+                    tracing::info!(
+                        correlation_id = display(correlation_id),
+                        log_id = display(&log_id),
+                        "write proposed"
+                    );
+                }
+                tx.on_accept(log_id);
                 self.client_responders.push(index, tx);
             }
         }
@@ -717,7 +1311,79 @@ where
             (None, None)
         };
 
-        self.report_metrics(replication, heartbeat);
+        let replication_targets = self.replication_targets_metrics();
+
+        self.report_metrics(replication, replication_targets, heartbeat);
+    }
+
+    /// Build the replication state of every target of the leader, or `None` if it is not a leader.
+    fn replication_targets_metrics(&self) -> Option<ReplicationTargetsMetrics<C>> {
+        let leader = self.engine.leader.as_ref()?;
+        let last_log_id = self.engine.state.last_log_id();
+        let last_next = last_log_id.next_index();
+
+        let targets = leader
+            .progress
+            .iter()
+            .filter(|item| item.id != self.id)
+            .map(|item| {
+                let id = &item.id;
+                let (next_index, inflight_entries) = item.val.sending_position(last_next);
+                let est = self.replication_throughput.estimate(id, item.val.matching(), last_log_id, None, None);
+
+                let m = ReplicationTargetMetrics {
+                    matched: item.val.matching().cloned(),
+                    next_index,
+                    inflight_entries,
+                    last_ack: leader.clock_progress.try_get(id).cloned().flatten().map(SerdeInstant::new),
+                    rtt: leader.round_trips.get(id).copied(),
+                    sending_snapshot: item.val.inflight.is_sending_snapshot(),
+                    lag_entries: est.lag_entries,
+                    lag_bytes: est.bytes_per_entry.map(|b| b * est.lag_entries),
+                };
+                (id.clone(), m)
+            })
+            .collect();
+
+        Some(targets)
+    }
+
+    /// Flush metrics, unless the last flush is within [`Config::metrics_flush_interval`].
+    ///
+    /// A skipped flush is left pending and is done once the interval expires.
+    fn flush_metrics_throttled(&mut self) {
+        let Some(interval) = self.config.metrics_flush_interval() else {
+            self.flush_metrics();
+            return;
+        };
+
+        let now = C::now();
+        if let Some(flushed_at) = self.core_state.metrics_flushed_at
+            && now < flushed_at + interval
+        {
+            self.core_state.metrics_flush_pending = true;
+            return;
+        }
+
+        self.core_state.metrics_flushed_at = Some(now);
+        self.core_state.metrics_flush_pending = false;
+        self.flush_metrics();
+    }
+
+    /// When the pending throttled metrics flush is due, or `None` if there is no pending flush.
+    fn pending_metrics_flush_deadline(&self) -> Option<InstantOf<C>> {
+        if !self.core_state.metrics_flush_pending {
+            return None;
+        }
+        let interval = self.config.metrics_flush_interval()?;
+        self.core_state.metrics_flushed_at.map(|t| t + interval)
+    }
+
+    async fn sleep_until_opt(deadline: Option<InstantOf<C>>) {
+        match deadline {
+            Some(deadline) => C::sleep_until(deadline).await,
+            None => futures_util::future::pending().await,
+        }
     }
 
     /// Report a metrics payload on the current state of the Raft node.
@@ -725,10 +1391,36 @@ where
     pub(crate) fn report_metrics(
         &mut self,
         replication: Option<ReplicationMetrics<C>>,
+        replication_targets: Option<ReplicationTargetsMetrics<C>>,
         heartbeat: Option<HeartbeatMetrics<C>>,
     ) {
+        // The maps are shared by the metrics published until they change, instead of being cloned
+        // for each.
+        let shared = &mut self.shared_metrics;
+        let replication = shared.replication.share_opt(replication);
+        let replication_targets = shared.replication_targets.share_opt(replication_targets);
+        let heartbeat = shared.heartbeat.share_opt(heartbeat);
+        let snapshot_transfers = shared.snapshot_transfers.share(self.snapshot_transfers.states());
+        let config_mismatches = shared.config_mismatches.share(self.config_mismatches.peers());
+        let peer_capabilities = shared.peer_capabilities.share(self.peer_capabilities.peers());
+
         let last_quorum_acked = self.last_quorum_acked_time();
+        let leader_since = self.engine.leader.as_ref().map(|l| l.leader_since);
         let millis_since_quorum_ack = last_quorum_acked.map(|t| t.elapsed().as_millis() as u64);
+        let snapshot_deferred_since = self.core_state.snapshot_deferral.deferred_since().map(SerdeInstant::new);
+        let apply_throttled_until = self.core_state.apply_throttle.throttled_until(C::now()).map(SerdeInstant::new);
+        let compacting_until = self.compacting_until(C::now()).map(SerdeInstant::new);
+
+        let candidate = {
+            let real = self.engine.candidate_ref().map(|c| (c, false));
+            let pre = || self.engine.pre_candidate_ref().map(|c| (c, true));
+            real.or_else(pre).map(|(c, pre_vote)| CandidateMetrics {
+                term: c.vote_ref().term(),
+                pre_vote,
+                granted: c.granters().collect(),
+                started_at: SerdeInstant::new(c.starting_time()),
+            })
+        };
 
         let st = &self.engine.state;
 
@@ -747,6 +1439,7 @@ where
         #[allow(deprecated)]
         let m = RaftMetrics {
             running_state: Ok(()),
+            storage_error: self.core_state.storage_error.clone(),
             id: self.id.clone(),
 
             // --- data ---
@@ -759,6 +1452,11 @@ where
             last_applied: st.io_applied().cloned(),
             snapshot: st.io_snapshot_last_log_id().cloned(),
             purged: st.io_purged().cloned(),
+            snapshot_deferred_since,
+            last_backup: self.core_state.last_backup.clone(),
+            apply_throttled_until,
+            compacting_until,
+            warming_up: self.core_state.warming_up.clone(),
 
             #[cfg(feature = "metrics-logids")]
             log_id_list: st.log_ids.clone(),
@@ -768,12 +1466,20 @@ where
             current_leader: current_leader.clone(),
             millis_since_quorum_ack,
             last_quorum_acked: last_quorum_acked.map(SerdeInstant::new),
+            quorum_ack_latency: self.quorum_ack_latency.metrics(),
+            leader_since,
+            candidate,
+            term_exhausted: st.vote_ref().term().checked_next().is_none(),
             membership_config: membership_config.clone(),
             committed_membership_config: committed_membership_config.clone(),
             heartbeat: heartbeat.clone(),
 
             // --- replication ---
             replication: replication.clone(),
+            replication_targets,
+            snapshot_transfers,
+            config_mismatches,
+            peer_capabilities,
         };
 
         #[allow(deprecated)]
@@ -785,6 +1491,11 @@ where
             last_applied: st.io_applied().cloned(),
             snapshot: st.io_snapshot_last_log_id().cloned(),
             purged: st.io_purged().cloned(),
+            snapshot_deferred_since,
+            last_backup: self.core_state.last_backup.clone(),
+            apply_throttled_until,
+            compacting_until,
+            warming_up: self.core_state.warming_up.clone(),
 
             #[cfg(feature = "metrics-logids")]
             log_id_list: st.log_ids.clone(),
@@ -830,6 +1541,8 @@ where
             false
         });
 
+        self.metrics_history.record(&m);
+
         tracing::debug!("report metrics: {}", m);
         let res = self.tx_metrics.send(m);
 
@@ -847,14 +1560,16 @@ where
     #[tracing::instrument(level = "debug", skip(self, tx))]
     pub(crate) fn handle_initialize(
         &mut self,
-        member_nodes: BTreeMap<C::NodeId, C::Node>,
+        membership: Membership<C::NodeId, C::Node>,
         tx: ResultSender<C, (), InitializeError<C>>,
     ) {
-        tracing::debug!("{}: member_nodes: {:?}", func_name!(), member_nodes);
-
-        let membership = Membership::from(member_nodes);
+        tracing::debug!("{}: membership: {}", func_name!(), membership);
 
-        let res = self.engine.initialize(membership);
+        let res = match &self.engine.config.quorum_policy {
+            Some(policy) => check_quorum_policy(policy.as_ref(), &membership).map_err(InitializeError::from),
+            None => Ok(()),
+        };
+        let res = res.and_then(|_| self.engine.initialize(membership));
 
         let has_error = res.is_err();
 
@@ -896,45 +1611,204 @@ where
     #[tracing::instrument(level = "debug", skip(self))]
     pub(crate) fn trigger_snapshot(&mut self) {
         tracing::debug!("{}", func_name!());
-        self.engine.snapshot_handler().trigger_snapshot();
-    }
 
-    /// Trigger routine actions that need to be checked after processing messages.
-    ///
-    /// This is called in the main event loop after processing messages and running engine commands.
-    /// It performs routine checks and triggers corresponding actions:
-    /// - Snapshot building based on `SnapshotPolicy`
-    /// - Initiate replication if the replication stream is idle (for leader)
-    ///
-    /// Unlike tick-based triggers, this runs after every message batch, making it independent of
-    /// the tick configuration and more responsive to state changes.
-    #[tracing::instrument(level = "debug", skip(self))]
-    pub(crate) fn trigger_routine_actions(&mut self) {
-        // Check snapshot policy and trigger snapshot if needed
-        if let Some(at) = self
-            .config
-            .snapshot_policy
-            .should_snapshot(&self.engine.state, self.core_state.snapshot_tried_at.as_ref())
-        {
-            tracing::debug!("snapshot policy triggered at: {}", at);
-            self.core_state.snapshot_tried_at = Some(at);
-            self.trigger_snapshot();
+        if self.is_log_only() {
+            tracing::debug!("{}: skip snapshot, a log-only node has no state machine", func_name!());
+            return;
         }
 
-        // Keep replicating to a target if the replication stream to it is idle
-        if let Ok(mut lh) = self.engine.try_leader_handler() {
-            lh.replication_handler().initiate_replication();
+        if self.is_witness() {
+            tracing::debug!("{}: skip snapshot, a witness has no state machine", func_name!());
+            return;
         }
 
-        // Broadcast I/O progress so replication tasks can read submitted logs.
-        if let Some(submitted) = self.engine.state.log_progress().submitted().cloned() {
-            self.io_submitted_tx.send_if_greater(submitted);
-        }
+        self.engine.snapshot_handler().trigger_snapshot();
     }
 
-    /// Return the current leader node ID based on the committed vote.
-    ///
-    /// In OpenRaft, a leader does not have to be a voter — it can be a learner
+    /// Returns the log id to build a snapshot at, if [`Config::snapshot_policy`] or the installed
+    /// [`SnapshotTrigger`] decides to build one.
+    fn should_snapshot(&self, now: InstantOf<C>) -> Option<LogIdOf<C>> {
+        let st = &self.engine.state;
+        let tried_at = self.core_state.snapshot_tried_at.as_ref();
+
+        if let Some(at) = self.config.snapshot_policy.should_snapshot(st, tried_at) {
+            return Some(at);
+        }
+
+        let trigger = self.snapshot_trigger.as_ref()?;
+
+        let snapshot_last_log_id = tried_at.max(st.snapshot_last_log_id());
+        let committed = st.committed();
+        if committed <= snapshot_last_log_id {
+            return None;
+        }
+
+        let ctx = SnapshotTriggerContext {
+            committed: committed.cloned(),
+            snapshot_last_log_id: snapshot_last_log_id.cloned(),
+            logs_since_snapshot: committed.next_index() - snapshot_last_log_id.next_index(),
+            since_last_build: self.core_state.snapshot_built_at.map(|t| now.saturating_duration_since(t)),
+        };
+
+        if trigger.should_snapshot(&ctx) {
+            tracing::debug!("snapshot trigger decides to build a snapshot: {:?}", ctx);
+            committed.cloned()
+        } else {
+            None
+        }
+    }
+
+    /// Check that a log-only node added by `membership` can receive every log from this node.
+    ///
+    /// A log-only node does not install snapshot, thus it can not be added once a log is purged.
+    fn check_log_only_logs(
+        &self,
+        membership: &Membership<C::NodeId, C::Node>,
+    ) -> Result<(), LogOnlyLogsPurged<C::NodeId>> {
+        let Some(last_purged) = self.engine.state.last_purged_log_id() else {
+            return Ok(());
+        };
+
+        let current = self.engine.state.membership_state.effective().membership();
+
+        for node_id in membership.log_only_ids() {
+            if !current.is_log_only(&node_id) {
+                return Err(LogOnlyLogsPurged {
+                    node_id,
+                    last_purged_index: last_purged.index(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Whether this node stores the log but has no state machine. See [`NodeRole::LogOnly`].
+    ///
+    /// [`NodeRole::LogOnly`]: crate::NodeRole::LogOnly
+    fn is_log_only(&self) -> bool {
+        self.engine.state.membership_state.effective().membership().is_log_only(&self.id)
+    }
+
+    /// Whether this node only stores log metadata and has no state machine. See
+    /// [`NodeRole::Witness`].
+    ///
+    /// [`NodeRole::Witness`]: crate::NodeRole::Witness
+    fn is_witness(&self) -> bool {
+        self.engine.state.membership_state.effective().membership().is_witness(&self.id)
+    }
+
+    /// Load the smallest log index held by log subscribers, and the first log index log-only
+    /// voters may still need, so that purging keeps them.
+    pub(crate) fn refresh_purge_hold(&mut self) {
+        self.engine.state.purge_hold = self.log_holds.min_index();
+        self.engine.state.log_only_hold = self.log_only_purge_hold();
+    }
+
+    /// Returns the first log index a log-only voter may not yet have replicated.
+    ///
+    /// A log-only voter does not install snapshot, so no node may purge the logs it still needs,
+    /// since any voter may become the leader that replicates them. The leader holds the logs from
+    /// the smallest matching of log-only voters. A follower does not know their progress, so it
+    /// keeps the hold it had as the last leader, or, if it has not been one, purges no more logs.
+    fn log_only_purge_hold(&self) -> Option<u64> {
+        let membership = self.engine.state.membership_state.effective().membership();
+        if membership.log_only_ids().next().is_none() {
+            return None;
+        }
+
+        let Some(leader) = self.engine.leader.as_ref() else {
+            let purged = self.engine.state.last_purged_log_id().next_index();
+            return Some(self.engine.state.log_only_hold.unwrap_or(purged));
+        };
+
+        membership
+            .log_only_ids()
+            .map(|id| leader.progress.try_get(&id).map(|p| p.matching().next_index()).unwrap_or_default())
+            .min()
+    }
+
+    /// Publish the current snapshot meta after a snapshot is built or installed.
+    ///
+    /// If it is a new snapshot and the oldest tracked one is dropped from the history, the state
+    /// machine is told to remove the snapshots before the oldest one still kept.
+    pub(crate) fn on_snapshot_updated(&mut self) {
+        let meta = &self.engine.state.snapshot_meta;
+        self.snapshot_meta_cache.set(meta);
+
+        if let Some(keep_from) = self.snapshot_history.push(meta) {
+            tracing::info!("remove snapshots before: {}", keep_from);
+            self.engine.output.push_command(Command::from(sm::Command::remove_snapshots_before(keep_from)));
+        }
+    }
+
+    /// Trigger routine actions that need to be checked after processing messages.
+    ///
+    /// This is called in the main event loop after processing messages and running engine commands.
+    /// It performs routine checks and triggers corresponding actions:
+    /// - Snapshot building based on `SnapshotPolicy` and the installed `SnapshotTrigger`
+    /// - Initiate replication if the replication stream is idle (for leader)
+    /// - Promote the state machine standby if the leader changes
+    ///
+    /// Unlike tick-based triggers, this runs after every message batch, making it independent of
+    /// the tick configuration and more responsive to state changes.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub(crate) fn trigger_routine_actions(&mut self) {
+        self.release_held_writes();
+        self.engine.abandon_expired_writes();
+        self.check_storage_quota();
+        self.refresh_purge_hold();
+
+        // Check snapshot policy and trigger snapshot if needed
+        let now = C::now();
+        let next_index = self.engine.state.last_log_id().next_index();
+        self.core_state.snapshot_deferral.sample(now, next_index);
+
+        if let Some(at) = self.should_snapshot(now) {
+            let busy = self.is_busy_for_snapshot();
+            let max_defer = self.config.snapshot_max_defer();
+
+            if self.compacting_until(now).is_some() {
+                tracing::debug!(
+                    "snapshot policy triggered at: {}, deferred because the state machine is compacting",
+                    at
+                );
+            } else if self.core_state.snapshot_deferral.should_defer(now, busy, max_defer) {
+                tracing::debug!("snapshot policy triggered at: {}, deferred because of the load", at);
+            } else {
+                tracing::debug!("snapshot policy triggered at: {}", at);
+                self.core_state.snapshot_tried_at = Some(at);
+                self.trigger_snapshot();
+            }
+        } else {
+            self.core_state.snapshot_deferral.cancel();
+        }
+
+        // Keep replicating to a target if the replication stream to it is idle
+        if let Ok(mut lh) = self.engine.try_leader_handler() {
+            lh.replication_handler().initiate_replication();
+        }
+
+        // Swap in the state machine standby when the leader changes. A log-only node does not
+        // run its state machine.
+        let leader = self.current_leader();
+        if leader.is_some() && leader != self.core_state.observed_leader {
+            if self.core_state.observed_leader.is_some() && !self.is_log_only() {
+                tracing::info!("leader changed to {}, promote state machine standby", leader.display());
+                self.engine.output.push_command(Command::from(sm::Command::promote_standby()));
+            }
+            self.core_state.observed_leader = leader;
+        }
+
+        // Broadcast I/O progress so replication tasks can read submitted logs.
+        if let Some(submitted) = self.engine.state.log_progress().submitted().cloned() {
+            self.io_submitted_tx.send_if_greater(submitted);
+        }
+    }
+
+    /// Return the current leader node ID based on the committed vote.
+    ///
+    /// In OpenRaft, a leader does not have to be a voter — it can be a learner
     /// or even a node outside the membership. Leadership is determined solely by
     /// a committed vote (i.e., a vote granted by a quorum), following Paxos
     /// semantics. Therefore, this method does not check voter or membership
@@ -972,6 +1846,38 @@ where
         leading.and_then(|l| l.last_quorum_acked_time())
     }
 
+    /// Check if this node meets `options` to serve a read from its local state machine.
+    ///
+    /// Returns the applied log id if it does, otherwise how stale this node is. It does not wait
+    /// for this node to catch up. A witness has no state machine and always rejects it.
+    fn check_local_read(&mut self, options: &ReadOptions) -> Result<Option<LogIdOf<C>>, StaleRead<C>> {
+        let applied = self.engine.state.io_applied().cloned();
+
+        let since_leader_contact = if self.engine.state.is_leader(&self.id) {
+            self.last_quorum_acked_time().map(|t| t.elapsed())
+        } else if self.engine.state.vote_ref().is_committed() {
+            self.engine.state.vote_last_modified().map(|t| t.elapsed())
+        } else {
+            None
+        };
+
+        let witness = self.is_witness();
+        let applied_ok = options.min_applied_index.is_none_or(|index| applied.next_index() > index);
+        let staleness_ok = options.max_staleness.is_none_or(|max| since_leader_contact.is_some_and(|d| d <= max));
+
+        if applied_ok && staleness_ok && !witness && !self.is_log_only() {
+            return Ok(applied);
+        }
+
+        Err(StaleRead {
+            applied,
+            min_applied_index: options.min_applied_index,
+            since_leader_contact,
+            max_staleness: options.max_staleness,
+            witness,
+        })
+    }
+
     pub(crate) fn get_leader_node(&self, leader_id: Option<C::NodeId>) -> Option<C::Node> {
         let leader_id = leader_id?;
 
@@ -979,12 +1885,94 @@ where
     }
 
     /// Apply log entries to the state machine, from the `first`(inclusive) to `last`(inclusive).
+    ///
+    /// If the backup barrier is in this range, the apply is split at it and a snapshot is built in
+    /// between, so that the snapshot is at exactly the barrier.
     #[tracing::instrument(level = "debug", skip_all)]
     pub(crate) async fn apply_to_state_machine(
         &mut self,
         first: LogIdOf<C>,
         last: LogIdOf<C>,
     ) -> Result<(), StorageError<C>> {
+        let Some(barrier) = self.backup_barrier_in(&first, &last) else {
+            return self.send_apply(first, last).await;
+        };
+
+        tracing::info!("{}: build backup snapshot at barrier: {}", func_name!(), barrier);
+
+        let next_index = barrier.index() + 1;
+        self.send_apply(first, barrier).await?;
+
+        self.engine.state.io_state_mut().set_building_snapshot(true);
+        self.sm_handle
+            .send(sm::Command::build_backup_snapshot())
+            .await
+            .map_err(|_e| StorageError::write_state_machine(C::err_from_string("cannot send to sm::Worker")))?;
+
+        if next_index <= last.index() {
+            let next = self.engine.state.get_log_id(next_index).unwrap();
+            self.send_apply(next, last).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Return the backup barrier if it is the log id of an entry in `[first, last]`.
+    ///
+    /// A barrier that is not the log id of the entry at its index, e.g., one proposed by a stale
+    /// leader whose entry was then replaced, is ignored.
+    fn backup_barrier_in(&self, first: &LogIdOf<C>, last: &LogIdOf<C>) -> Option<LogIdOf<C>> {
+        let barrier = self.core_state.backup_barrier.as_ref()?;
+
+        if barrier.index() < first.index() || barrier.index() > last.index() {
+            return None;
+        }
+
+        if self.engine.state.get_log_id(barrier.index()).as_ref() != Some(barrier) {
+            return None;
+        }
+
+        Some(barrier.clone())
+    }
+
+    /// Send `Apply` commands for the log entries from the `first`(inclusive) to `last`(inclusive)
+    /// to the state machine worker.
+    ///
+    /// If the apply rate is limited, the entries are sent in small batches, each scheduled by the
+    /// [`ApplyThrottle`].
+    async fn send_apply(&mut self, first: LogIdOf<C>, last: LogIdOf<C>) -> Result<(), StorageError<C>> {
+        let Some(rate) = self.apply_rate_limit() else {
+            return self.send_apply_batch(first, last).await;
+        };
+
+        let batch_size = ApplyThrottle::<C>::batch_size(rate);
+        let mut start = first;
+
+        loop {
+            let end_index = std::cmp::min(start.index() + batch_size - 1, last.index());
+            let end = self.engine.state.get_log_id(end_index).unwrap();
+            self.send_apply_batch(start, end).await?;
+
+            if end_index == last.index() {
+                return Ok(());
+            }
+            start = self.engine.state.get_log_id(end_index + 1).unwrap();
+        }
+    }
+
+    /// The max number of log entries to apply per second, `None` if it is not limited.
+    ///
+    /// A leader does not throttle apply: clients are waiting for the result.
+    fn apply_rate_limit(&self) -> Option<u64> {
+        if self.engine.leader.is_some() {
+            return None;
+        }
+        self.config.max_apply_rate()
+    }
+
+    /// Send an `Apply` command for the log entries from the `first`(inclusive) to `last`(inclusive)
+    /// to the state machine worker.
+    async fn send_apply_batch(&mut self, first: LogIdOf<C>, last: LogIdOf<C>) -> Result<(), StorageError<C>> {
         tracing::debug!("{}: {}..={}", func_name!(), first, last);
 
         debug_assert!(
@@ -1021,12 +2009,190 @@ where
             responder.on_commit(log_id);
         }
 
-        let cmd = sm::Command::apply(first, last.clone(), responders);
+        // A leader does not delay apply: clients are waiting for the result.
+        let not_before = if self.engine.leader.is_some() {
+            None
+        } else {
+            let now = C::now();
+            let delayed = self.config.apply_delay().map(|delay| now + delay);
+            let throttled = self
+                .apply_rate_limit()
+                .and_then(|rate| self.core_state.apply_throttle.schedule(now, entry_count, rate));
+            std::cmp::max(delayed, throttled)
+        };
+
+        // The first entries of the leaders after the entries to apply, up to the committed one,
+        // decide if an entry with a deadline expires. Only entries with a timestamp expire.
+        let leader_starts = match self.engine.state.committed() {
+            Some(committed) if self.config.entry_timestamp() => {
+                let starts = self.engine.state.log_ids.leader_starts(first.index(), committed.index());
+                // Only the first leader after the last entry to apply is needed.
+                starts.take_while_inclusive(|index| *index <= last.index()).collect()
+            }
+            _ => vec![],
+        };
+
+        let cmd = sm::Command::apply(first, last.clone(), responders)
+            .with_not_before(not_before)
+            .with_leader_starts(leader_starts);
         self.sm_handle.send(cmd).await.map_err(|e| StorageError::apply(last, C::err_from_string(e)))?;
 
         Ok(())
     }
 
+    /// Until when the state machine reported it is compacting, `None` if it is not compacting at
+    /// `now`.
+    ///
+    /// See [`Raft::notify_compaction()`](crate::Raft::notify_compaction).
+    fn compacting_until(&self, now: InstantOf<C>) -> Option<InstantOf<C>> {
+        self.core_state.compacting_until.filter(|until| now < *until)
+    }
+
+    /// Whether the write rate or the apply backlog is above the threshold to defer a snapshot
+    /// build.
+    fn is_busy_for_snapshot(&self) -> bool {
+        if let Some(rate) = self.config.snapshot_defer_write_rate
+            && self.core_state.snapshot_deferral.write_rate() > rate
+        {
+            return true;
+        }
+
+        if let Some(backlog) = self.config.snapshot_defer_apply_backlog {
+            let st = &self.engine.state;
+            let unapplied = st.local_committed().next_index().saturating_sub(st.io_applied().next_index());
+            if unapplied > backlog {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Enforce [`Config::storage_quota`] with the usage reported by the storage usage probe.
+    ///
+    /// When the usage rises above the quota, a snapshot is built and the logs it includes are
+    /// purged. If the usage is still above the quota once that is done, client writes are rejected
+    /// until the usage is back under the quota.
+    pub(crate) fn check_storage_quota(&mut self) {
+        let (Some(probe), Some(quota)) = (&self.storage_usage_probe, self.config.storage_quota) else {
+            self.core_state.storage_quota = StorageQuotaState::Normal;
+            return;
+        };
+
+        let usage = probe.usage();
+        if let Some(r) = &self.metrics_recorder {
+            r.set_storage_usage(usage);
+        }
+
+        if usage <= quota {
+            if self.core_state.storage_quota != StorageQuotaState::Normal {
+                tracing::info!("storage usage {} is back under quota {}", usage, quota);
+                self.core_state.storage_quota = StorageQuotaState::Normal;
+            }
+            return;
+        }
+
+        match self.core_state.storage_quota {
+            StorageQuotaState::Normal => {
+                tracing::warn!(
+                    "storage usage {} exceeds quota {}, build a snapshot and purge logs",
+                    usage,
+                    quota
+                );
+                if let Some(r) = &self.metrics_recorder {
+                    r.increment_storage_quota_exceeded();
+                }
+                self.trigger_snapshot();
+                self.core_state.storage_quota = StorageQuotaState::Reclaiming;
+            }
+            StorageQuotaState::Reclaiming => {
+                let st = &self.engine.state;
+                if st.io_state().building_snapshot() {
+                    return;
+                }
+
+                // Logs a log-only voter still needs are not reclaimed.
+                let mut upto = st.snapshot_last_log_id().index();
+                if let Some(hold) = st.log_only_hold {
+                    upto = std::cmp::min(upto, hold.checked_sub(1));
+                }
+
+                if let Some(upto) = upto
+                    && st.io_purged().index() < Some(upto)
+                {
+                    self.engine.trigger_purge_log(upto);
+                    return;
+                }
+
+                tracing::error!(
+                    "storage usage {} still exceeds quota {} after purging logs, reject client writes",
+                    usage,
+                    quota
+                );
+                self.core_state.storage_quota = StorageQuotaState::Full(StorageFull { usage, quota });
+            }
+            StorageQuotaState::Full(_) => {
+                self.core_state.storage_quota = StorageQuotaState::Full(StorageFull { usage, quota });
+            }
+        }
+    }
+
+    /// Hold a client write if no leader is known, to propose or reject it once one is.
+    ///
+    /// The write is returned if it is not held: holding is disabled, a leader is known, or too
+    /// many entries are already held.
+    ///
+    /// See [`Config::leaderless_write_hold`](crate::Config::leaderless_write_hold).
+    fn try_hold_write(&mut self, write: HeldWrite<C>) -> Result<(), HeldWrite<C>> {
+        if self.config.leaderless_write_hold().is_none() || self.current_leader().is_some() {
+            return Err(write);
+        }
+
+        let entries = self.held_writes.entries() + write.payloads.len() as u64;
+        if entries > self.config.max_held_writes() {
+            tracing::info!(
+                "no leader is known, and {} held entries exceed max_held_writes, reject the write",
+                entries
+            );
+            return Err(write);
+        }
+
+        tracing::debug!("no leader is known, hold {} entries", write.payloads.len());
+        self.held_writes.push(write);
+        Ok(())
+    }
+
+    /// Release the held client writes once a leader is known, or once they are held for too long.
+    ///
+    /// A released write is proposed if this node is the leader, otherwise it is rejected with a
+    /// `ForwardToLeader` error, carrying the leader if it is known.
+    pub(crate) fn release_held_writes(&mut self) {
+        if self.held_writes.is_empty() {
+            return;
+        }
+
+        let Some(hold) = self.config.leaderless_write_hold() else {
+            return;
+        };
+
+        let leader_known = self.current_leader().is_some();
+        let now = C::now();
+
+        while let Some(write) = self.held_writes.pop(leader_known, now, hold) {
+            tracing::debug!(
+                "release {} held entries, leader known: {}",
+                write.payloads.len(),
+                leader_known
+            );
+            self.write_entries(
+                write.payloads,
+                write.responders,
+                #[cfg(feature = "runtime-stats")]
+                write.proposed_at,
+            );
+        }
+    }
+
     /// Spawn a new replication stream returning its replication state handle.
     #[tracing::instrument(level = "debug", skip(self))]
     #[allow(clippy::type_complexity)]
@@ -1072,7 +2238,12 @@ where
 
         let context = self.new_replication_context(leader_vote, prog, cancel_rx);
 
-        let handle = ReplicationHandle::new(prog.progress.stream_id, replicate_tx, cancel_tx);
+        let handle = ReplicationHandle::new(
+            prog.progress.stream_id,
+            replicate_tx,
+            cancel_tx,
+            context.witness.clone(),
+        );
 
         (handle, context)
     }
@@ -1085,15 +2256,20 @@ where
     ) -> ReplicationContext<C> {
         let id = self.id.clone();
 
+        let witness = self.engine.state.membership_state.effective().membership().is_witness(&prog.target);
+
         ReplicationContext {
             id,
             target: prog.target.clone(),
             leader_vote,
+            witness: Arc::new(AtomicBool::new(witness)),
             stream_id: prog.progress.stream_id,
             config: self.config.clone(),
             tx_notify: self.tx_notification.clone(),
             cancel_rx,
             replicate_batch: self.shared_replicate_batch.clone(),
+            throughput: self.replication_throughput.clone(),
+            commit_notified: Default::default(),
         }
     }
 
@@ -1101,6 +2277,7 @@ where
         EventWatcher {
             replicate_rx,
             committed_rx: self.committed_tx.subscribe(),
+            backup_barrier_rx: self.backup_barrier_tx.subscribe(),
             io_accepted_rx: self.io_accepted_tx.subscribe(),
             io_submitted_rx: self.io_submitted_tx.subscribe(),
         }
@@ -1122,7 +2299,20 @@ where
 
         self.send_satisfied_responds();
 
+        self.runtime_stats.command_queue_bytes.record(self.engine.output.queued_bytes());
+
+        #[cfg(feature = "audit-log-chain")]
+        if let Some(chained) = self.log_chaining.take_done() {
+            self.append_chained_entries(chained).await?;
+        }
+
         loop {
+            // The queued commands wait for the entries being chained to be appended.
+            #[cfg(feature = "audit-log-chain")]
+            if self.log_chaining.is_pending() {
+                break;
+            }
+
             // Batch commands for better I/O performance (e.g., merge consecutive AppendEntries)
             self.engine.output.sched_commands(&self.config);
 
@@ -1166,9 +2356,26 @@ where
         // Progress driven commands run at last because some command may generate progress changes.
         self.run_progress_driven_command().await?;
 
+        self.publish_events();
+
         Ok(())
     }
 
+    /// Publish the events emitted by the engine or by running commands to the event subscribers.
+    fn publish_events(&mut self) {
+        let events = self.engine.output.take_events();
+        if events.is_empty() {
+            return;
+        }
+
+        self.tx_events.send_if_modified(|log| {
+            for event in events {
+                log.push(event);
+            }
+            true
+        });
+    }
+
     /// Run all commands that are automatically generated by progress changes.
     async fn run_progress_driven_command(&mut self) -> Result<(), StorageError<C>> {
         while let Some(cmd) = self.engine.next_progress_driven_command() {
@@ -1208,16 +2415,38 @@ where
         }
     }
 
+    /// Whether the queued commands exceed [`Config::max_command_queue_bytes`], in which case no
+    /// more RaftMsg is taken until they drain.
+    fn is_command_queue_full(&self) -> bool {
+        let Some(max) = self.config.max_command_queue_bytes() else {
+            return false;
+        };
+        self.engine.output.queued_bytes() >= max
+    }
+
+    /// Wait until a RaftMsg is buffered, or forever if the input is stalled.
+    async fn wait_raft_msg(rx_api: &mut BatchRaftMsgReceiver<C>, stalled: bool) -> Result<(), Fatal<C>> {
+        if stalled {
+            return futures_util::future::pending().await;
+        }
+        rx_api.ensure_buffered().await
+    }
+
     /// Run an event handling loop
     ///
     /// It always returns a [`Fatal`] error upon returning.
     #[tracing::instrument(level = "debug", skip_all, fields(id=display(&self.id)))]
-    async fn runtime_loop(&mut self, mut rx_shutdown: OneshotReceiverOf<C, ()>) -> Result<Infallible, Fatal<C>> {
+    async fn runtime_loop(&mut self, rx_shutdown: &mut OneshotReceiverOf<C, ()>) -> Result<Infallible, Fatal<C>> {
         // Ratio control the ratio of number of RaftMsg to process to number of Notification to process.
         let mut balancer = Balancer::new(10_000);
 
         loop {
-            self.flush_metrics();
+            self.flush_metrics_throttled();
+
+            let metrics_deadline = self.pending_metrics_flush_deadline();
+
+            // While the command queue is full, only notifications, which drain it, wake up the loop.
+            let input_stalled = self.is_command_queue_full();
 
             tracing::debug!(
                 "RAFT_stats id={:<2} log_io: {}",
@@ -1235,7 +2464,7 @@ where
             // We want to check shutdown prior to other channels.
             // See: https://docs.rs/tokio/latest/tokio/macro.select.html#fairness
             futures_util::select_biased! {
-                _ = (&mut rx_shutdown).fuse() => {
+                _ = (&mut *rx_shutdown).fuse() => {
                     tracing::info!("recv from rx_shutdown");
                     return Err(Fatal::Stopped);
                 }
@@ -1250,9 +2479,13 @@ where
                     };
                 }
 
-                msg_res = self.rx_api.ensure_buffered().fuse() => {
+                msg_res = Self::wait_raft_msg(&mut self.rx_api, input_stalled).fuse() => {
                     msg_res?;
                 }
+
+                _ = Self::sleep_until_opt(metrics_deadline).fuse() => {
+                    // A throttled metrics publication is due: it is flushed at the loop start.
+                }
             };
 
             self.run_engine_commands().await?;
@@ -1282,6 +2515,226 @@ where
         }
     }
 
+    /// Whether this node keeps running degraded after a storage error, instead of stopping.
+    ///
+    /// Only a follower or a learner does: it has no leadership or election depending on the
+    /// storage making progress.
+    fn can_degrade(&self) -> bool {
+        self.config.degrade_on_storage_error()
+            && matches!(
+                self.engine.state.server_state,
+                ServerState::Follower | ServerState::Learner
+            )
+    }
+
+    /// Participate read-only after a storage error, until the storage works again or shut down.
+    ///
+    /// No IO is submitted any more, and every request is answered at once: heartbeats and vote
+    /// requests from the durable state, client requests with a [`ForwardToLeader`] error. The
+    /// responder of any other request is dropped, and its caller receives `error`.
+    ///
+    /// A request that needs IO, such as appending entries, is a retry of the storage: if
+    /// [`Self::try_recover_storage()`] succeeds, the request is handled as usual and it returns
+    /// `Ok(())` to resume the main loop.
+    ///
+    /// See [`Config::degrade_on_storage_error`](crate::Config::degrade_on_storage_error).
+    #[tracing::instrument(level = "debug", skip_all, fields(id=display(&self.id)))]
+    async fn degraded_loop(
+        &mut self,
+        error: StorageError<C>,
+        rx_shutdown: &mut OneshotReceiverOf<C, ()>,
+    ) -> Result<(), Fatal<C>> {
+        tracing::error!("{}: storage error, keep running degraded: {}", self.id, error);
+
+        self.core_state.storage_error = Some(error.clone());
+        self.flush_metrics();
+
+        // The dropped IO never completes: the callers waiting for it receive the storage error,
+        // which is published in the metrics above.
+        self.engine.output.drop_log_io();
+        #[cfg(feature = "audit-log-chain")]
+        self.log_chaining.cancel();
+
+        // The state machine results of the commands submitted before the failure. It is bounded:
+        // no command is submitted while degraded.
+        let mut sm_results = Vec::new();
+
+        loop {
+            futures_util::select_biased! {
+                _ = (&mut *rx_shutdown).fuse() => {
+                    tracing::info!("recv from rx_shutdown, quit degraded mode");
+                    return Err(Fatal::StorageError(error));
+                }
+
+                notify_res = self.rx_notification.recv().fuse() => {
+                    match notify_res {
+                        Some(notify @ Notification::StateMachine { .. }) => sm_results.push(notify),
+                        // Other IO completions and timers no longer drive anything.
+                        Some(notify) => tracing::debug!("degraded: ignore notification: {}", notify),
+                        None => {
+                            tracing::error!("all rx_notify senders are dropped");
+                            return Err(Fatal::Stopped);
+                        }
+                    }
+                }
+
+                msg_res = self.rx_api.ensure_buffered().fuse() => {
+                    msg_res?;
+                }
+            };
+
+            while let Some(msg) = self.rx_api.try_recv().await? {
+                let Some(msg) = self.handle_degraded_msg(msg) else {
+                    continue;
+                };
+
+                if let Err(e) = self.try_recover_storage(&error).await {
+                    tracing::warn!("degraded: storage is not recovered: {}; drop: {}", e, msg);
+                    continue;
+                }
+
+                tracing::info!("storage is recovered, quit degraded mode");
+
+                self.core_state.storage_error = None;
+                for notify in sm_results {
+                    self.handle_notification(notify)?;
+                }
+                self.handle_api_msg(msg).await;
+                return Ok(());
+            }
+        }
+    }
+
+    /// Answer a request from the durable state while running degraded.
+    ///
+    /// Returns the request back if it needs IO. A request that can be answered neither way is
+    /// dropped.
+    fn handle_degraded_msg(&mut self, msg: RaftMsg<C>) -> Option<RaftMsg<C>> {
+        tracing::debug!("RAFT_event id={:<2} degraded input: {}", self.id, msg);
+
+        match msg {
+            RaftMsg::AppendEntries { rpc, txs } => {
+                let my_vote = self.engine.state.vote_ref().clone();
+
+                let res: StreamAppendResult<C> = if rpc.vote != my_vote {
+                    if rpc.vote.as_ref_vote() >= my_vote.as_ref_vote() {
+                        // Accepting a new leader requires persisting its vote.
+                        return Some(RaftMsg::AppendEntries { rpc, txs });
+                    }
+                    Err(StreamAppendError::HigherVote(my_vote))
+                } else if !rpc.entries.is_empty() {
+                    return Some(RaftMsg::AppendEntries { rpc, txs });
+                } else if let Some(prev) = &rpc.prev_log_id
+                    && !self.engine.state.has_log_id(prev)
+                {
+                    Err(StreamAppendError::Conflict(prev.clone()))
+                } else {
+                    let io_id = IOId::new_log_io(rpc.vote.to_committed(), rpc.prev_log_id.clone());
+                    let flushed = self.engine.state.log_progress().flushed();
+                    if !flushed.is_some_and(|f| f >= &io_id) {
+                        // `prev_log_id` was not flushed before the storage failed.
+                        return Some(RaftMsg::AppendEntries { rpc, txs });
+                    }
+                    Ok(rpc.prev_log_id.clone())
+                };
+
+                if let Some(sender) = AppendEntriesSender::new(res, txs) {
+                    sender.send();
+                }
+            }
+            RaftMsg::RequestVote { rpc, tx } => {
+                tracing::info!("degraded: reject vote request, it can not be persisted: {}", rpc);
+
+                let st = &self.engine.state;
+                tx.send(VoteResponse::new(st.vote_ref(), st.last_log_id().cloned(), false)).ok();
+            }
+            RaftMsg::RequestPreVote { rpc, tx } => {
+                tx.send(self.engine.handle_pre_vote_req(rpc)).ok();
+            }
+            RaftMsg::WithRaftState { req } => {
+                req(&self.engine.state);
+            }
+            msg @ (RaftMsg::InstallSnapshot { .. }
+            | RaftMsg::InstallSnapshotLocator { .. }
+            | RaftMsg::InstallSnapshotMeta { .. }) => {
+                return Some(msg);
+            }
+
+            // Only a follower or a learner runs degraded: forward client requests to the leader.
+            RaftMsg::ClientWrite { responders, .. } => {
+                let forward_err = self.engine.state.forward_to_leader();
+                for r in responders.into_iter().flatten() {
+                    r.on_complete(Err(ClientWriteError::ForwardToLeader(forward_err.clone())));
+                }
+            }
+            RaftMsg::ChangeMembership { tx, .. } => {
+                tx.on_complete(Err(ClientWriteError::ForwardToLeader(
+                    self.engine.state.forward_to_leader(),
+                )));
+            }
+            RaftMsg::GetLinearizer { tx, .. } | RaftMsg::FollowerReadIndex { tx, .. } => {
+                tx.send(Err(self.engine.state.forward_to_leader().into())).ok();
+            }
+
+            msg => {
+                tracing::info!("degraded: drop request, its caller receives the storage error: {}", msg);
+            }
+        }
+
+        None
+    }
+
+    /// Leave degraded mode if the log storage works again.
+    ///
+    /// It re-saves the vote to probe writing, then re-reads the durable log: the entries that
+    /// were not persisted before the failure are removed from the in-memory log, so that the
+    /// leader sends them again.
+    ///
+    /// A state machine error is not recovered: the state machine worker has quit.
+    async fn try_recover_storage(&mut self, error: &StorageError<C>) -> Result<(), StorageError<C>> {
+        if matches!(
+            error.subject(),
+            ErrorSubject::Apply(_) | ErrorSubject::StateMachine | ErrorSubject::Snapshot(_)
+        ) {
+            return Err(error.clone());
+        }
+
+        let vote = self.engine.state.vote_ref().clone();
+        self.log_store.save_vote(&vote).await.sto_write_vote()?;
+
+        let st = self.log_store.get_log_state().await.sto_read_logs()?;
+        let last = st.last_log_id;
+
+        let committed = self.engine.state.committed();
+        let matches = match &last {
+            None => committed.is_none(),
+            Some(last) => Some(last) >= committed && self.engine.state.has_log_id(last),
+        };
+        if !matches {
+            let err = C::err_from_string(format!(
+                "durable last log id {} does not match the in-memory log, committed: {}",
+                last.display(),
+                committed.display()
+            ));
+            return Err(StorageError::read_logs(err));
+        }
+
+        let since = last.next_index();
+        self.engine.state.log_ids.truncate(since);
+        if self.engine.state.membership_state.truncate(since).is_some() {
+            self.engine.server_state_handler().update_server_state_if_changed();
+        }
+
+        let io_id = if vote.is_committed() {
+            IOId::new_log_io(vote.to_committed(), last)
+        } else {
+            IOId::new(&vote)
+        };
+        self.engine.state.io_state_mut().reset_log_progress(self.id.to_string(), io_id);
+
+        Ok(())
+    }
+
     /// Process RaftMsg as many as possible.
     ///
     /// It returns the number of processed message.
@@ -1296,7 +2749,20 @@ where
         let run_command_threshold = 0;
         let mut last_log_index = 0;
 
-        for _i in 0..at_most {
+        for _i in 0..at_most {
+            if self.is_command_queue_full() {
+                if !self.core_state.input_stalled {
+                    tracing::warn!(
+                        "command queue is full: {} bytes queued, stop taking RaftMsg",
+                        self.engine.output.queued_bytes()
+                    );
+                    self.core_state.input_stalled = true;
+                    self.runtime_stats.input_stalls += 1;
+                }
+                break;
+            }
+            self.core_state.input_stalled = false;
+
             let res = self.rx_api.try_recv().await?;
             let Some(msg) = res else {
                 break;
@@ -1389,6 +2855,10 @@ where
     async fn spawn_parallel_vote_requests(&mut self, vote_req: &VoteRequest<C>, kind: VoteRequestKind) {
         let members = self.engine.state.membership_state.effective().voter_ids();
 
+        let vote_req = VoteRequest {
+            config_digest: Some(self.config_mismatches.local()),
+            ..vote_req.clone()
+        };
         let vote = vote_req.vote.clone();
 
         for target in members {
@@ -1402,6 +2872,15 @@ where
             let target_node = self.engine.state.membership_state.effective().get_node(&target).unwrap().clone();
             let mut client = self.network_factory.new_client(target.clone(), &target_node).await;
 
+            // A second connection to send the request again if the first one stalls.
+            let mut hedge_client = match self.config.vote_hedge_delay() {
+                Some(delay) => Some((
+                    self.network_factory.new_client(target.clone(), &target_node).await,
+                    delay,
+                )),
+                None => None,
+            };
+
             let tx = self.tx_notification.clone();
 
             let ttl = Duration::from_millis(self.config.election_timeout_min);
@@ -1425,10 +2904,17 @@ where
                 {
                     let target = target.clone();
                     async move {
-                        let tm_res = match kind {
-                            VoteRequestKind::Vote => C::timeout(ttl, client.vote(req, option)).await,
-                            VoteRequestKind::PreVote => C::timeout(ttl, client.pre_vote(req, option)).await,
+                        let send = async {
+                            match hedge_client.as_mut() {
+                                None => kind.send(&mut client, req, option).await,
+                                Some((hedge_client, delay)) => {
+                                    let primary = kind.send(&mut client, req.clone(), option.clone());
+                                    let secondary = kind.send(hedge_client, req, option);
+                                    hedge::<C, _, _>(*delay, primary, secondary).await
+                                }
+                            }
                         };
+                        let tm_res = C::timeout(ttl, send).await;
                         let res = match tm_res {
                             Ok(res) => res,
 
@@ -1539,7 +3025,10 @@ where
     pub(super) fn handle_vote_request(&mut self, req: VoteRequest<C>, tx: VoteTx<C>) {
         tracing::info!("{}: req: {}", func_name!(), req);
 
-        let resp = self.engine.handle_vote_req(req);
+        self.check_config_digest(req.vote.leader_node_id(), req.config_digest.as_ref());
+
+        let mut resp = self.engine.handle_vote_req(req);
+        resp.capabilities = Capabilities::supported();
 
         // Record vote to external metrics recorder
         if let Some(r) = &self.metrics_recorder {
@@ -1559,7 +3048,10 @@ where
     pub(super) fn handle_pre_vote_request(&mut self, req: VoteRequest<C>, tx: VoteTx<C>) {
         tracing::info!("{}: req: {}", func_name!(), req);
 
-        let resp = self.engine.handle_pre_vote_req(req);
+        self.check_config_digest(req.vote.leader_node_id(), req.config_digest.as_ref());
+
+        let mut resp = self.engine.handle_pre_vote_req(req);
+        resp.capabilities = Capabilities::supported();
 
         // A Pre-Vote persists nothing, so there is no vote IO to wait for: respond at once.
         self.engine.output.push_command(Command::Respond {
@@ -1569,11 +3061,139 @@ where
     }
 
     #[tracing::instrument(level = "debug", skip_all)]
-    pub(super) fn handle_append_entries_request(&mut self, req: AppendEntriesRequest<C>, tx: AppendEntriesTx<C>) {
-        tracing::debug!("{}: req: {}", func_name!(), req);
+    pub(super) fn handle_append_entries_request(
+        &mut self,
+        req: AppendEntriesRequest<C>,
+        txs: BatchOf<C, AppendEntriesTx<C>>,
+    ) {
+        tracing::debug!("{}: req: {}, merged: {}", func_name!(), req, txs.len());
+
+        self.check_config_digest(req.vote.leader_node_id(), req.config_digest.as_ref());
+
+        // A request from another leader can not follow the snapshot the held ones are waiting for.
+        if self.snapshot_tail.vote().is_some_and(|v| v != &req.vote) {
+            self.release_snapshot_tail();
+        }
+
+        if self.should_hold_snapshot_tail(&req) {
+            let entries = self.snapshot_tail.entries() + req.entries.len() as u64;
+            if entries <= self.config.max_append_entries() {
+                tracing::debug!("hold AppendEntries until the snapshot it follows is installed: {}", req);
+                self.snapshot_tail.push(req, txs);
+                return;
+            }
+
+            tracing::info!(
+                "too many log entries to hold until the snapshot is installed: {} > {}; reject them",
+                entries,
+                self.config.max_append_entries()
+            );
+            self.release_snapshot_tail();
+        }
+
+        self.append_entries(req, txs);
+    }
+
+    /// Record the protocol features advertised by `peer`, and log a change.
+    /// Record the time the entries this node appended as a leader took to be committed, up to
+    /// index `end`.
+    fn record_quorum_ack_latency(&mut self, end: u64) {
+        let recorder = self.metrics_recorder.as_deref();
+
+        let breaches = self.quorum_ack_latency.committed(end, C::now(), |latency, n| {
+            if let Some(r) = recorder {
+                r.record_quorum_ack_latency(latency, n);
+            }
+        });
+
+        if breaches > 0 {
+            tracing::info!(
+                "{} log entries exceeded quorum_ack_slo({:?}) before {}: {}",
+                breaches,
+                self.config.quorum_ack_slo().unwrap_or_default(),
+                end,
+                self.quorum_ack_latency.metrics()
+            );
+
+            if let Some(r) = recorder {
+                r.increment_quorum_ack_slo_breach(breaches);
+            }
+        }
+    }
+
+    fn update_peer_capabilities(&mut self, peer: &C::NodeId, capabilities: Capabilities) {
+        let Some(prev) = self.peer_capabilities.update(peer, capabilities) else {
+            return;
+        };
+
+        tracing::info!(
+            "capabilities of node {} changed: {} -> {}",
+            peer,
+            prev.display(),
+            capabilities
+        );
+    }
+
+    /// Warn if the config digest received from `peer` differs from this node's.
+    ///
+    /// A mismatch is reported once, until the peer's digest changes again.
+    fn check_config_digest(&mut self, peer: &C::NodeId, digest: Option<&ConfigDigest>) {
+        if peer == &self.id {
+            return;
+        }
+
+        let Some(fields) = self.config_mismatches.check(peer, digest) else {
+            return;
+        };
+
+        tracing::warn!(
+            "config of node {} differs from this node {} in: {}; theirs: {}, mine: {}; \
+             every node of a cluster must run with the same election timeout, durability mode and leader id type",
+            peer,
+            self.id,
+            fields.join(", "),
+            digest.display(),
+            self.config_mismatches.local()
+        );
+
+        if let Some(r) = &self.metrics_recorder {
+            r.increment_config_mismatch();
+        }
+    }
+
+    /// Whether the request follows a snapshot being sent to this node, which is not installed yet.
+    fn should_hold_snapshot_tail(&self, req: &AppendEntriesRequest<C>) -> bool {
+        let Some(snapshot_last) = &req.after_snapshot else {
+            return false;
+        };
+
+        let Some(prev) = &req.prev_log_id else {
+            return false;
+        };
+
+        let st = &self.engine.state;
+        &req.vote == st.vote_ref() && Some(snapshot_last) > st.last_log_id() && !st.has_log_id(prev)
+    }
+
+    /// Hand the held requests to the engine.
+    ///
+    /// Those that do not follow the local logs, e.g., when the snapshot is not installed, are
+    /// rejected, and the leader sends them again after the snapshot is installed.
+    fn release_snapshot_tail(&mut self) {
+        for (req, txs) in self.snapshot_tail.take() {
+            self.append_entries(req, txs);
+        }
+    }
+
+    fn append_entries(&mut self, req: AppendEntriesRequest<C>, txs: BatchOf<C, AppendEntriesTx<C>>) {
+        // A barrier from a stale leader does nothing: a snapshot is built only when the entry with
+        // exactly this log id is applied.
+        if req.backup_barrier > self.core_state.backup_barrier {
+            self.core_state.backup_barrier = req.backup_barrier.clone();
+        }
 
         let segment = LogSegment::new(req.prev_log_id, req.entries);
-        self.engine.handle_append_entries(&req.vote, segment, tx);
+        self.engine.handle_append_entries(&req.vote, segment, txs);
 
         // Record append entries to external metrics recorder
         if let Some(r) = &self.metrics_recorder {
@@ -1581,7 +3201,10 @@ where
         }
 
         let committed = LogIOId::new(req.vote.to_committed(), req.leader_commit);
-        self.engine.state.update_committed(committed);
+        if self.engine.state.update_committed(committed) {
+            let membership = self.engine.state.membership_state.committed().clone();
+            self.engine.output.push_event(RaftEvent::MembershipCommitted { membership });
+        }
     }
 
     // TODO: Make this method non-async. It does not need to run any async command in it.
@@ -1593,8 +3216,8 @@ where
         self.runtime_stats.record_raft_msg(msg.name());
 
         match msg {
-            RaftMsg::AppendEntries { rpc, tx } => {
-                self.handle_append_entries_request(rpc, tx);
+            RaftMsg::AppendEntries { rpc, txs } => {
+                self.handle_append_entries_request(rpc, txs);
             }
             RaftMsg::RequestVote { rpc, tx } => {
                 let now = C::now();
@@ -1617,10 +3240,22 @@ where
             }
             RaftMsg::InstallSnapshot { vote, snapshot, tx } => {
                 self.engine.handle_install_full_snapshot(vote, snapshot, tx);
+                self.release_snapshot_tail();
+            }
+            RaftMsg::InstallSnapshotLocator { vote, locator, tx } => {
+                self.engine.handle_install_snapshot_locator(vote, locator, tx);
+                self.release_snapshot_tail();
+            }
+            RaftMsg::InstallSnapshotMeta { vote, meta, tx } => {
+                self.engine.handle_install_snapshot_meta(vote, meta, tx);
+                self.release_snapshot_tail();
             }
             RaftMsg::GetLinearizer { read_policy, tx } => {
                 self.handle_ensure_linearizable_read(read_policy, tx).await;
             }
+            RaftMsg::FollowerReadIndex { read_policy, tx } => {
+                self.handle_follower_read_index(read_policy, tx).await;
+            }
             RaftMsg::ClientWrite {
                 payloads,
                 responders,
@@ -1645,27 +3280,47 @@ where
                     }
                 }
                 self.runtime_stats.write_batch.record(payloads.len() as u64);
-                self.write_entries(
+
+                let write = HeldWrite::new(
                     payloads,
                     responders,
                     #[cfg(feature = "runtime-stats")]
                     proposed_at,
+                    C::now(),
                 );
+                if let Err(write) = self.try_hold_write(write) {
+                    self.write_entries(
+                        write.payloads,
+                        write.responders,
+                        #[cfg(feature = "runtime-stats")]
+                        write.proposed_at,
+                    );
+                }
             }
-            RaftMsg::Initialize { members, tx } => {
-                tracing::info!("received RaftMsg::Initialize: {}, members: {:?}", func_name!(), members);
+            RaftMsg::Initialize { membership, tx } => {
+                tracing::info!(
+                    "received RaftMsg::Initialize: {}, membership: {}",
+                    func_name!(),
+                    membership
+                );
 
-                self.handle_initialize(members, tx);
+                self.handle_initialize(membership, tx);
             }
-            RaftMsg::ChangeMembership { changes, retain, tx } => {
+            RaftMsg::ChangeMembership {
+                changes,
+                retain,
+                correlation_id,
+                tx,
+            } => {
                 tracing::info!(
-                    "received RaftMsg::ChangeMembership: {}, members: {:?}, retain: {:?}",
+                    "received RaftMsg::ChangeMembership: {}, members: {:?}, retain: {:?}, correlation_id: {}",
                     func_name!(),
                     changes,
-                    retain
+                    retain,
+                    correlation_id.display()
                 );
 
-                self.change_membership(changes, retain, tx);
+                self.change_membership(changes, retain, correlation_id, tx);
             }
             RaftMsg::WithRaftState { req } => {
                 req(&self.engine.state);
@@ -1713,7 +3368,16 @@ where
                     ExternalCommand::Heartbeat => {
                         self.send_heartbeat("ExternalCommand");
                     }
-                    ExternalCommand::Snapshot => self.trigger_snapshot(),
+                    ExternalCommand::Snapshot { tx } => {
+                        self.trigger_snapshot();
+
+                        if self.engine.state.io_state().building_snapshot() {
+                            self.snapshot_waiters.push(tx);
+                        } else {
+                            // Nothing is being built, e.g., this node is log-only.
+                            tx.send(None).ok();
+                        }
+                    }
                     ExternalCommand::GetSnapshot { tx } => {
                         let cmd = sm::Command::get_snapshot(tx);
                         let res = self.sm_handle.send(cmd).await;
@@ -1745,6 +3409,31 @@ where
                         tracing::info!("setting metrics recorder");
                         self.metrics_recorder = recorder;
                     }
+                    ExternalCommand::SetStorageUsageProbe { probe } => {
+                        tracing::info!("setting storage usage probe");
+                        self.storage_usage_probe = probe;
+                    }
+                    ExternalCommand::SetSnapshotTrigger { trigger } => {
+                        tracing::info!("setting snapshot trigger");
+                        self.snapshot_trigger = trigger;
+                    }
+                    ExternalCommand::NotifyCompaction { expected } => {
+                        if expected.is_zero() {
+                            tracing::info!("state machine finished compacting");
+                            self.core_state.compacting_until = None;
+                        } else {
+                            tracing::info!("state machine is compacting, expect slow applies for {:?}", expected);
+                            self.core_state.compacting_until = Some(C::now() + expected);
+                        }
+                    }
+                    #[cfg(feature = "lease-check")]
+                    ExternalCommand::SetLeaseChecker { checker } => {
+                        tracing::info!("setting lease checker");
+                        self.lease_checker = checker;
+                    }
+                    ExternalCommand::GetPendingResponds { tx } => {
+                        tx.send(self.engine.output.pending_responds.infos(C::now())).ok();
+                    }
                     ExternalCommand::RefreshServerState {
                         vote,
                         membership_log_id,
@@ -1772,6 +3461,89 @@ where
                             );
                         }
                     }
+                    ExternalCommand::Backup { responder } => {
+                        let log_ids = self.write_entries(
+                            Batch::of([EntryPayload::Blank]),
+                            Batch::of([Some(responder)]),
+                            #[cfg(feature = "runtime-stats")]
+                            C::now(),
+                        );
+
+                        // The barrier is set before the entry is replicated, so that every
+                        // AppendEntries that may commit it carries it.
+                        if let Some(log_ids) = log_ids {
+                            let barrier = log_ids.last_log_id();
+                            tracing::info!("proposed backup barrier: {}", barrier);
+
+                            self.core_state.backup_barrier = Some(barrier.clone());
+                            self.backup_barrier_tx.send_if_greater(Some(barrier));
+                        }
+                    }
+                    ExternalCommand::SubscribeLog { from_index, tx } => {
+                        // Logs already purged, or scheduled to purge, can not be delivered.
+                        let next_index = std::cmp::max(from_index, self.engine.state.purge_upto().next_index());
+                        let hold_id = self.log_holds.add(next_index);
+                        self.refresh_purge_hold();
+                        tx.send((hold_id, next_index)).ok();
+                    }
+                    ExternalCommand::RefreshPurgeHold => {
+                        self.refresh_purge_hold();
+                        self.engine.log_handler().schedule_policy_based_purge();
+                        self.engine.try_purge_log();
+                    }
+                    ExternalCommand::WriteInScope {
+                        app_data,
+                        scope,
+                        responder,
+                    } => {
+                        self.write_entries_in_scope(
+                            Batch::of([EntryPayload::Normal(app_data)]),
+                            Batch::of([Some(responder)]),
+                            Some(scope),
+                            None,
+                            #[cfg(feature = "runtime-stats")]
+                            C::now(),
+                        );
+                    }
+                    ExternalCommand::ReserveLogIndexes { count, tx } => {
+                        let res = self
+                            .ensure_writable_leader_handler()
+                            .map(|mut lh| lh.reserve_log_indexes(count))
+                            .map_err(ClientWriteError::ForwardToLeader);
+                        tx.send(res).ok();
+                    }
+                    ExternalCommand::WriteReserved {
+                        index,
+                        app_data,
+                        responders,
+                    } => {
+                        self.write_entries_in_scope(
+                            Batch::of(app_data.into_iter().map(EntryPayload::Normal)),
+                            Batch::of(responders.into_iter().map(Some)),
+                            None,
+                            Some(index),
+                            #[cfg(feature = "runtime-stats")]
+                            C::now(),
+                        );
+                    }
+                    ExternalCommand::LocalRead { options, tx } => {
+                        let res = self.check_local_read(&options);
+                        tx.send(res).ok();
+                    }
+                    ExternalCommand::GetLogTail { n, tx } => {
+                        self.spawn_get_log_tail(n, tx).await;
+                    }
+                    #[cfg(feature = "audit-log-chain")]
+                    ExternalCommand::VerifyLogChain { range, tx } => {
+                        self.spawn_verify_log_chain(range, tx).await;
+                    }
+                    ExternalCommand::SetSnapshotSeed { target, seed, tx } => {
+                        let res = self.engine.snapshot_handler().set_snapshot_seed(target, seed);
+                        tx.send(res).ok();
+                    }
+                    ExternalCommand::FetchSeedSnapshot { req, tx } => {
+                        self.spawn_fetch_seed_snapshot(req, tx).await;
+                    }
                 }
             }
             #[cfg(feature = "runtime-stats")]
@@ -1809,6 +3581,8 @@ where
                     resp
                 );
 
+                self.update_peer_capabilities(&target, resp.capabilities);
+
                 #[allow(clippy::collapsible_if)]
                 if self.engine.candidate.is_some() {
                     if self.does_candidate_vote_match(&candidate_vote, "VoteResponse") {
@@ -1828,6 +3602,8 @@ where
                     resp
                 );
 
+                self.update_peer_capabilities(&target, resp.capabilities);
+
                 #[allow(clippy::collapsible_if)]
                 if self.engine.pre_candidate.is_some() {
                     if self.does_pre_candidate_vote_match(&candidate_vote, "PreVoteResponse") {
@@ -1879,6 +3655,11 @@ where
                         l.next_heartbeat = C::now() + Duration::from_millis(self.config.heartbeat_interval);
                     }
                 }
+
+                // Leader evicts the nodes that have been unreachable for too long
+                if let Ok(mut lh) = self.engine.try_leader_handler() {
+                    lh.try_evict_unreachable(now);
+                }
             }
 
             Notification::StorageError { error } => {
@@ -1902,6 +3683,10 @@ where
                         if self.engine.leader.is_some() {
                             if self.does_leader_vote_match(&log_io_id.committed_vote, "LocalIO Notification") {
                                 self.engine.replication_handler().update_local_progress(log_io_id.log_id);
+
+                                if let Ok(mut lh) = self.engine.try_leader_handler() {
+                                    lh.try_promote_caught_up();
+                                }
                             }
                         }
                     }
@@ -1917,6 +3702,10 @@ where
                 if let Some(mut rh) = self.engine.try_replication_handler() {
                     rh.update_progress(progress.target, progress.result, inflight_id);
                 }
+
+                if let Ok(mut lh) = self.engine.try_leader_handler() {
+                    lh.try_promote_caught_up();
+                }
             }
 
             Notification::HeartbeatProgress {
@@ -1929,6 +3718,29 @@ where
                 }
             }
 
+            Notification::SnapshotTransmitDone {
+                target,
+                inflight_id,
+                leader_vote,
+                installed,
+            } => {
+                if let Some(snapshot_id) = installed {
+                    self.engine.snapshot_handler().follower_installed_snapshot(
+                        &leader_vote,
+                        target.clone(),
+                        snapshot_id,
+                    );
+                }
+
+                self.snapshot_transfers.finish(&target, inflight_id);
+                self.start_queued_snapshot_transfers();
+            }
+
+            #[cfg(feature = "audit-log-chain")]
+            Notification::LogChained { chained } => {
+                self.log_chaining.finish(chained);
+            }
+
             Notification::StateMachine { command_result } => {
                 tracing::debug!("sm::StateMachine command result: {:?}", command_result);
 
@@ -1942,7 +3754,26 @@ where
                             func_name!()
                         );
 
-                        self.engine.on_building_snapshot_done(meta);
+                        if let Some(meta) = &meta
+                            && meta.last_log_id.is_some()
+                            && meta.last_log_id == self.core_state.backup_barrier
+                        {
+                            tracing::info!("backup snapshot is built: {}", meta);
+                            self.core_state.last_backup = meta.last_log_id.clone();
+                        }
+
+                        // A subscription may have been dropped since the last refresh.
+                        self.refresh_purge_hold();
+                        if meta.is_some() {
+                            self.core_state.snapshot_built_at = Some(C::now());
+                        }
+
+                        self.engine.on_building_snapshot_done(meta.clone());
+                        self.on_snapshot_updated();
+
+                        for tx in self.snapshot_waiters.drain(..) {
+                            tx.send(meta.clone()).ok();
+                        }
                     }
                     sm::Response::InstallSnapshot((log_io_id, meta)) => {
                         tracing::info!(
@@ -1960,11 +3791,36 @@ where
                                 st.apply_progress.try_flush(last.clone());
                                 st.snapshot.try_flush(last.clone());
                             }
+                            self.on_snapshot_updated();
+
+                            if self.config.warm_up_after_install() {
+                                tracing::info!("state machine is warming up after installing snapshot: {}", meta);
+                                self.core_state.warming_up = meta.last_log_id.clone();
+                                self.engine.output.push_event(RaftEvent::WarmUpStarted { meta });
+                            }
                         }
                     }
                     sm::Response::Apply(res) => {
                         self.runtime_stats.record_log_stage_now(Stage::Applied, res.last_applied.index() + 1);
                         self.engine.state.apply_progress_mut().try_flush(res.last_applied);
+
+                        if self.config.purge_policy() == PurgePolicy::DurableApplied
+                            && let Some(durable) = res.durable_applied
+                        {
+                            self.engine.update_durable_applied(durable);
+                        }
+
+                        if let (Some(lag), Some(r)) = (res.standby_lag, &self.metrics_recorder) {
+                            r.set_standby_lag(lag);
+                        }
+                    }
+                    sm::Response::WarmUpDone(meta) => {
+                        tracing::info!("state machine is warmed up after installing snapshot: {}", meta);
+
+                        if self.core_state.warming_up == meta.last_log_id {
+                            self.core_state.warming_up = None;
+                        }
+                        self.engine.output.push_event(RaftEvent::WarmUpFinished { meta });
                     }
                 }
             }
@@ -1972,15 +3828,41 @@ where
         Ok(())
     }
 
+    /// The election timeout of a voter in a 2-voter cluster, which breaks ties by node id.
+    ///
+    /// The voter with the smaller id times out first, and the other one does not compete with it.
+    /// See [`Config::two_voter_tie_breaker`](crate::Config::two_voter_tie_breaker).
+    fn tie_breaker_election_timeout(&self) -> Duration {
+        let smallest = self.engine.state.membership_state.effective().voter_ids().min();
+
+        let ms = if smallest.as_ref() == Some(&self.id) {
+            self.config.election_timeout_min
+        } else {
+            self.config.election_timeout_max
+        };
+        Duration::from_millis(ms)
+    }
+
     #[tracing::instrument(level = "debug", skip_all)]
     fn handle_tick_election(&mut self) {
         let now = C::now();
 
         tracing::debug!("try to trigger election, now: {}", now.display());
 
+        if self.config.adaptive_election_timeout() {
+            let election_timeout = self.engine.config.timer_config.election_timeout;
+            if self.core_state.election_tuner.observe(self.engine.state.vote_ref(), now, election_timeout) {
+                self.redraw_election_timeout();
+            }
+        }
+
         // TODO: leader lease should be extended. Or it has to examine if it is leader
         //       before electing.
         if self.engine.state.server_state == ServerState::Leader {
+            if self.config.check_quorum() && self.engine.check_quorum(now) {
+                return;
+            }
+
             tracing::debug!("skip election, already a leader");
             return;
         }
@@ -1995,12 +3877,28 @@ where
             return;
         }
 
-        let mut election_timeout = self.engine.config.timer_config.election_timeout;
+        // A committed vote means a leader has been established since the last election started by
+        // this node: it is not in an election storm.
+        if self.engine.state.vote_ref().is_committed() {
+            self.core_state.election_storm.reset();
+        }
+
+        let voter_count = self.engine.state.membership_state.effective().voter_ids().count();
+
+        let mut election_timeout = if voter_count == 2 && self.config.two_voter_tie_breaker() {
+            self.tie_breaker_election_timeout()
+        } else {
+            self.engine.config.timer_config.election_timeout
+        };
         if self.engine.is_there_greater_log() {
             election_timeout += self.engine.config.timer_config.smaller_log_timeout;
         }
 
-        let voter_count = self.engine.state.membership_state.effective().voter_ids().count();
+        if let Some(threshold) = self.config.election_storm_threshold {
+            let base = self.engine.config.timer_config.election_timeout;
+            let window = self.config.election_storm_window();
+            election_timeout += self.core_state.election_storm.backoff(now, window, threshold, base);
+        }
 
         if voter_count == 1 {
             // When a node restart, it may stay in any state but the in progress election(engine.candidate) is
@@ -2026,7 +3924,9 @@ where
 
         // Pre-Vote (multi-voter only): probe a quorum before incrementing the term.
         // A single voter always wins its own Pre-Vote, so it elects directly.
-        let pre_vote = self.runtime_config.enable_pre_vote.load(Ordering::Relaxed) && voter_count > 1;
+        let pre_vote = self.runtime_config.enable_pre_vote.load(Ordering::Relaxed)
+            && voter_count > 1
+            && self.all_voters_support(Capabilities::PRE_VOTE);
 
         if pre_vote {
             // A Pre-Vote does not advance `vote.last_update_time`, so without this guard a node
@@ -2040,9 +3940,20 @@ where
             }
         }
 
+        // An election started while the vote is not committed means the previous one did not
+        // establish a leader.
+        if self.config.adaptive_election_timeout()
+            && !self.engine.state.vote_ref().is_committed()
+            && self.core_state.election_tuner.on_failed_election()
+        {
+            self.redraw_election_timeout();
+        }
+
         // Every time elect, reset this flag.
         self.engine.reset_greater_log();
 
+        self.record_election(now);
+
         if pre_vote {
             tracing::info!("trigger pre-vote");
             self.engine.pre_elect();
@@ -2052,6 +3963,70 @@ where
         }
     }
 
+    /// Returns `true` if no other voter is known to lack `capability`.
+    ///
+    /// A protocol feature that needs the cooperation of the voters, such as Pre-Vote, is not used
+    /// until the voters seen without it are upgraded. See [`Capabilities`].
+    fn all_voters_support(&self, capability: Capabilities) -> bool {
+        let voters = self.engine.state.membership_state.effective().voter_ids().filter(|id| id != &self.id);
+        let lacking = self.peer_capabilities.lacking(voters, capability);
+
+        if lacking.is_empty() {
+            return true;
+        }
+
+        tracing::debug!(
+            "voters {} do not support {}, do not use it",
+            lacking.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(","),
+            capability
+        );
+        false
+    }
+
+    /// Draw a new election timeout from the range adapted by the election tuner.
+    ///
+    /// See [`Config::adaptive_election_timeout`](crate::Config::adaptive_election_timeout).
+    fn redraw_election_timeout(&mut self) {
+        let tuner = &self.core_state.election_tuner;
+        let (min, max) = tuner.range(self.config.election_timeout_min, self.config.election_timeout_max);
+        let ms = AsyncRuntimeOf::<C>::thread_rng().random_range(min..max);
+
+        tracing::info!(
+            "election timeout range adapted to [{}, {}) ms at level {}, election_timeout: {} ms",
+            min,
+            max,
+            tuner.level(),
+            ms
+        );
+
+        self.engine.config.timer_config.election_timeout = Duration::from_millis(ms);
+    }
+
+    /// Record an election started at `now` for the election storm circuit breaker, and report the
+    /// storm when the breaker trips.
+    fn record_election(&mut self, now: InstantOf<C>) {
+        let Some(threshold) = self.config.election_storm_threshold else {
+            return;
+        };
+
+        let window = self.config.election_storm_window();
+        let tripped = self.core_state.election_storm.record(now, window, threshold);
+        if !tripped {
+            return;
+        }
+
+        tracing::warn!(
+            "election storm detected: {} elections without a leader established within {:?}, backing off further elections; vote: {}",
+            self.core_state.election_storm.attempts(),
+            window,
+            self.engine.state.vote_ref()
+        );
+
+        if let Some(r) = &self.metrics_recorder {
+            r.increment_election_storm();
+        }
+    }
+
     /// If a message is sent by a previous Candidate but is received by current Candidate,
     /// it is a stale message and should be just ignored.
     fn does_candidate_vote_match(&self, candidate_vote: &UncommittedVoteOf<C>, msg: impl fmt::Display) -> bool {
@@ -2155,6 +4130,7 @@ where
         }
 
         let cluster_committed = lh.state.cluster_committed().cloned();
+        let backup_barrier = self.core_state.backup_barrier.clone();
         let now = C::now();
         let events =
             lh.leader
@@ -2166,6 +4142,7 @@ where
                         time: now,
                         matching: progress_entry.val.matching.clone(),
                         cluster_committed: cluster_committed.clone(),
+                        backup_barrier: backup_barrier.clone(),
                     })
                 });
 
@@ -2181,21 +4158,87 @@ where
         leader_vote: CommittedVoteOf<C>,
         stream_id: StreamId,
         target: C::NodeId,
+        witness: Arc<AtomicBool>,
     ) -> (ReplicationContext<C>, WatchSenderOf<C, ()>) {
         let (cancel_tx, cancel_rx) = C::watch_channel(());
         let ctx = ReplicationContext {
             id: self.id.clone(),
             target,
             leader_vote,
+            witness,
             stream_id,
             config: self.config.clone(),
             tx_notify: self.tx_notification.clone(),
             cancel_rx,
             replicate_batch: self.shared_replicate_batch.clone(),
+            throughput: self.replication_throughput.clone(),
+            commit_notified: Default::default(),
         };
         (ctx, cancel_tx)
     }
 
+    /// Spawn a task to send the snapshot to a target, whose slot is already taken in
+    /// `snapshot_transfers`.
+    async fn spawn_snapshot_transmitter(&mut self, req: QueuedSnapshot<C>) {
+        let QueuedSnapshot {
+            leader_vote,
+            target,
+            inflight_id,
+        } = req;
+
+        let Some(node) = self.replications.get(&target) else {
+            tracing::warn!("replication to {} is closed, skip sending snapshot", target);
+            self.snapshot_transfers.finish(&target, inflight_id);
+            return;
+        };
+
+        let snapshot_reader = self.sm_handle.new_snapshot_reader();
+        let base = self.engine.snapshot_handler().follower_snapshot(&target);
+        let seed = self.engine.snapshot_handler().snapshot_seed(&target);
+        let seed = seed.map(|(seed_id, seed_node)| SeedSnapshotRequest {
+            vote: leader_vote.clone().into_vote(),
+            seed_id,
+            seed_node,
+            min_last_log_id: self.engine.state.last_purged_log_id().cloned(),
+        });
+        let stream_id = node.stream_id;
+        let witness = node.witness.clone();
+        let (replication_task_context, cancel_tx) =
+            self.new_replication_task_context(leader_vote, stream_id, target.clone(), witness);
+
+        let target_node = self.engine.state.membership_state.effective().get_node(&target).unwrap();
+        let snapshot_network = self.network_factory.new_client(target.clone(), target_node).await;
+
+        let handle = SnapshotTransmitter::<C, NF, SM>::spawn(
+            replication_task_context,
+            snapshot_network,
+            snapshot_reader,
+            base,
+            seed,
+            self.engine.state.snapshot_meta.clone(),
+            inflight_id,
+            cancel_tx,
+        );
+
+        let node = self.replications.get_mut(&target).expect("replication to target node exists");
+        // TODO: it is not cleaned when snapshot transmission is done.
+        node.snapshot_transmit_handle = Some(handle);
+    }
+
+    /// Start the queued snapshot transfers for which there is a free slot.
+    ///
+    /// A slot is taken for each of them, and a command is queued to spawn the transfer.
+    fn start_queued_snapshot_transfers(&mut self) {
+        while let Some(req) = self.snapshot_transfers.pop_ready() {
+            tracing::info!("start queued snapshot transfer to {}", req.target);
+            self.engine.output.push_command(Command::ReplicateSnapshot {
+                leader_vote: req.leader_vote,
+                target: req.target,
+                inflight_id: req.inflight_id,
+            });
+        }
+    }
+
     async fn close_replication(target: &C::NodeId, mut s: ReplicationHandle<C>) {
         let Some(handle) = s.join_handle.take() else {
             return;
@@ -2209,6 +4252,57 @@ where
         let _x = handle.await;
         tracing::info!("done joining removed replication: {}", target);
     }
+
+    /// Submit `entries` to the log store, for the leader of `vote`.
+    async fn submit_append_entries(
+        &mut self,
+        vote: CommittedVoteOf<C>,
+        entries: BatchOf<C, C::Entry>,
+    ) -> Result<(), StorageError<C>> {
+        let last_log_id = entries.last().unwrap().log_id();
+        let last_log_index = last_log_id.index();
+        tracing::debug!("AppendEntries: {}", entries.as_ref().display_n(10));
+
+        let entry_count = entries.len() as u64;
+
+        // Record to internal histogram
+        self.runtime_stats.append_batch.record(entry_count);
+
+        // Record to external metrics recorder
+        if let Some(r) = &self.metrics_recorder {
+            r.record_append_batch(entry_count);
+        }
+
+        let io_id = IOId::new_log_io(vote, Some(last_log_id));
+        let callback = IOFlushed::new(io_id.clone(), self.tx_io_completed.clone());
+
+        // Notify that I/O is about to be submitted.
+        self.io_accepted_tx.send_if_greater(io_id.clone());
+
+        // Mark this IO request as submitted,
+        // other commands relying on it can then be processed.
+        // For example,
+        // `Replicate` command cannot run until this IO request is submitted(no need to be flushed),
+        // because it needs to read the log entry from the log store.
+        //
+        // The `submit` state must be updated before calling `append()`,
+        // because `append()` may call the callback before returning.
+        self.engine.state.log_progress_mut().submit(io_id.clone());
+
+        self.runtime_stats.record_log_stage_now(Stage::Submitted, last_log_index + 1);
+
+        if self.engine.leader.is_some() {
+            let end = last_log_index + 1;
+            self.quorum_ack_latency.appended(end - entry_count, end, C::now());
+        } else {
+            self.quorum_ack_latency.clear_pending();
+        }
+
+        // Submit IO request, do not wait for the response.
+        self.log_store.append(entries, callback).await.sto_write_logs()?;
+
+        Ok(())
+    }
 }
 
 impl<C, N, LS, SM> RaftRuntime<C, SM> for RaftCore<C, N, LS, SM>
@@ -2253,40 +4347,13 @@ where
                 committed_vote: vote,
                 entries,
             } => {
-                let last_log_id = entries.last().unwrap().log_id();
-                let last_log_index = last_log_id.index();
-                tracing::debug!("AppendEntries: {}", entries.as_ref().display_n(10));
-
-                let entry_count = entries.len() as u64;
-
-                // Record to internal histogram
-                self.runtime_stats.append_batch.record(entry_count);
-
-                // Record to external metrics recorder
-                if let Some(r) = &self.metrics_recorder {
-                    r.record_append_batch(entry_count);
+                #[cfg(feature = "audit-log-chain")]
+                if self.config.audit_log_chain() {
+                    self.spawn_chain_entries(vote, entries);
+                    return Ok(None);
                 }
 
-                let io_id = IOId::new_log_io(vote, Some(last_log_id));
-                let callback = IOFlushed::new(io_id.clone(), self.tx_io_completed.clone());
-
-                // Notify that I/O is about to be submitted.
-                self.io_accepted_tx.send_if_greater(io_id.clone());
-
-                // Mark this IO request as submitted,
-                // other commands relying on it can then be processed.
-                // For example,
-                // `Replicate` command cannot run until this IO request is submitted(no need to be flushed),
-                // because it needs to read the log entry from the log store.
-                //
-                // The `submit` state must be updated before calling `append()`,
-                // because `append()` may call the callback before returning.
-                self.engine.state.log_progress_mut().submit(io_id.clone());
-
-                self.runtime_stats.record_log_stage_now(Stage::Submitted, last_log_index + 1);
-
-                // Submit IO request, do not wait for the response.
-                self.log_store.append(entries, callback).await.sto_write_logs()?;
+                self.submit_append_entries(vote, entries).await?;
             }
             Command::SaveVote { vote } => {
                 let io_id = IOId::new(&vote);
@@ -2335,10 +4402,13 @@ where
                     tracing::debug!("sent ForwardToLeader for purged log_index: {}", log_index);
                 }
 
-                self.engine.state.io_state_mut().update_purged(Some(upto));
+                self.engine.state.io_state_mut().update_purged(Some(upto.clone()));
+                self.engine.output.push_event(RaftEvent::LogPurged { upto });
             }
             Command::TruncateLog { after } => {
                 self.log_store.truncate_after(after.clone()).await.sto_write_logs()?;
+                #[cfg(feature = "audit-log-chain")]
+                self.log_chain_hashes.truncate(after.next_index());
 
                 // Inform clients waiting for logs to be applied.
                 let leader_id = self.current_leader();
@@ -2365,18 +4435,29 @@ where
             Command::BroadcastHeartbeat { session_id } => {
                 self.broadcast_heartbeat(session_id);
             }
-            Command::SaveCommittedAndApply {
-                already_applied: already_committed,
-                upto,
-            } => {
+            Command::SaveCommittedAndApply { already_applied, upto } => {
                 self.runtime_stats.record_log_stage_now(Stage::Committed, upto.index() + 1);
+                self.record_quorum_ack_latency(upto.index() + 1);
+
+                // A log-only node has no state machine: the committed logs are done with once
+                // persisted, and its applied log id stays where its state machine is. If it is
+                // re-added as a voter, the logs committed meanwhile are applied then.
+                let log_only = self.is_log_only();
 
-                self.engine.state.apply_progress_mut().submit(upto.clone());
+                if log_only {
+                    self.engine.state.io_state_mut().log_only_committed = Some(upto.clone());
+                } else {
+                    self.engine.state.apply_progress_mut().submit(upto.clone());
+                }
 
                 self.log_store.save_committed(Some(upto.clone())).await.sto_write()?;
 
-                let first = self.engine.state.get_log_id(already_committed.next_index()).unwrap();
-                self.apply_to_state_machine(first, upto).await?;
+                // A witness has no state machine either, but applies nothing: its applied log id
+                // only advances when a snapshot meta is installed.
+                if !log_only && !self.is_witness() {
+                    let first = self.engine.state.get_log_id(already_applied.next_index()).unwrap();
+                    self.apply_to_state_machine(first, upto).await?;
+                }
             }
             Command::Replicate { req, target } => {
                 let node = self.replications.get(&target).expect("replication to target node exists");
@@ -2387,32 +4468,37 @@ where
                 target,
                 inflight_id,
             } => {
-                let node = self.replications.get(&target).expect("replication to target node exists");
-
-                let snapshot_reader = self.sm_handle.new_snapshot_reader();
-                let stream_id = node.stream_id;
-                let (replication_task_context, cancel_tx) =
-                    self.new_replication_task_context(leader_vote, stream_id, target.clone());
-
-                let target_node = self.engine.state.membership_state.effective().get_node(&target).unwrap();
-                let snapshot_network = self.network_factory.new_client(target.clone(), target_node).await;
+                if self.engine.state.snapshot_last_log_id() < self.engine.state.last_purged_log_id() {
+                    // With `PurgePolicy::DurableApplied`, the logs after the snapshot may be
+                    // purged: build a newer snapshot, which the target receives the next time,
+                    // after installing this one.
+                    tracing::info!(
+                        "snapshot does not include the last purged log {}, build a new one",
+                        self.engine.state.last_purged_log_id().display()
+                    );
+                    self.trigger_snapshot();
+                }
 
-                let handle = SnapshotTransmitter::<C, N, SM>::spawn(
-                    replication_task_context,
-                    snapshot_network,
-                    snapshot_reader,
+                let req = QueuedSnapshot {
+                    leader_vote,
+                    target,
                     inflight_id,
-                    cancel_tx,
-                );
+                };
 
-                let node = self.replications.get_mut(&target).expect("replication to target node exists");
-                // TODO: it is not cleaned when snapshot transmission is done.
-                node.snapshot_transmit_handle = Some(handle);
+                if self.snapshot_transfers.request(req.clone()) {
+                    self.spawn_snapshot_transmitter(req).await;
+                } else {
+                    tracing::info!(
+                        "snapshot transfer to {} is queued: max_inflight_snapshots reached",
+                        req.target
+                    );
+                }
             }
             Command::BroadcastTransferLeader { req } => self.broadcast_transfer_leader(req).await,
 
             Command::CloseReplicationStreams => {
                 self.heartbeat_handle.close_workers();
+                self.snapshot_transfers.clear();
 
                 let left = std::mem::take(&mut self.replications);
                 for (target, s) in left {
@@ -2451,6 +4537,10 @@ where
                     };
 
                     let handle = if let Some(handle) = handle {
+                        // The role of a kept target may be changed by the new membership.
+                        let witness =
+                            self.engine.state.membership_state.effective().membership().is_witness(&prog.target);
+                        handle.witness.store(witness, Ordering::Relaxed);
                         handle
                     } else {
                         self.spawn_replication_stream(leader_vote.clone(), prog).await
@@ -2466,6 +4556,15 @@ where
                 for (target, s) in left {
                     Self::close_replication(&target, s).await;
                 }
+
+                // The snapshot transfers of the closed replications are gone with them.
+                if close_old_streams {
+                    self.snapshot_transfers.clear();
+                } else {
+                    let replications = &self.replications;
+                    self.snapshot_transfers.retain_targets(|t| replications.contains_key(t));
+                }
+                self.start_queued_snapshot_transfers();
             }
             Command::StateMachine { command } => {
                 let io_id = command.get_log_progress();
@@ -1,17 +1,37 @@
 //! This mod defines external command sent by application to Raft.
 
 use std::fmt;
+use std::ops::Range;
 use std::sync::Arc;
+use std::time::Duration;
 
 use display_more::DisplayOptionExt;
 
 use crate::RaftTypeConfig;
+use crate::StorageError;
 use crate::core::raft_msg::ExternalCommandName;
 use crate::core::raft_msg::ResultSender;
+use crate::entry::ApplyScope;
+#[cfg(feature = "audit-log-chain")]
+use crate::entry::ChainHash;
 use crate::errors::AllowNextRevertError;
+use crate::errors::ClientWriteError;
+#[cfg(feature = "audit-log-chain")]
+use crate::errors::LogChainError;
+use crate::errors::SeedSnapshotError;
+use crate::errors::StaleRead;
 use crate::metrics::MetricsRecorder;
+use crate::raft::FetchSnapshotResponse;
+use crate::raft::PendingRespondInfo;
+use crate::raft::ReadOptions;
+use crate::raft::SeedSnapshotRequest;
+use crate::raft::responder::core_responder::CoreResponder;
+use crate::storage::LogEntryMeta;
+use crate::storage::SnapshotTrigger;
+use crate::storage::StorageUsageProbe;
 use crate::type_config::alias::LogIdOf;
 use crate::type_config::alias::OneshotSenderOf;
+use crate::type_config::alias::SnapshotMetaOf;
 use crate::type_config::alias::SnapshotOf;
 use crate::type_config::alias::VoteOf;
 
@@ -40,7 +60,11 @@ pub(crate) enum ExternalCommand<C: RaftTypeConfig> {
     Heartbeat,
 
     /// Initiate to build a snapshot on this node.
-    Snapshot,
+    ///
+    /// The meta of the built snapshot is sent back via `tx`, or `None` if none is built.
+    Snapshot {
+        tx: OneshotSenderOf<C, Option<SnapshotMetaOf<C>>>,
+    },
 
     /// Get a snapshot from the state machine, send back via a oneshot::Sender.
     GetSnapshot {
@@ -71,6 +95,30 @@ pub(crate) enum ExternalCommand<C: RaftTypeConfig> {
     /// Pass `None` to disable metrics recording.
     SetMetricsRecorder { recorder: Option<Arc<dyn MetricsRecorder>> },
 
+    /// Set or unset the probe that reports the storage usage, to enforce
+    /// [`Config::storage_quota`](crate::Config::storage_quota).
+    SetStorageUsageProbe { probe: Option<Arc<dyn StorageUsageProbe>> },
+
+    /// Set or unset the trigger deciding when to build a snapshot, in addition to the policy.
+    SetSnapshotTrigger {
+        trigger: Option<Arc<dyn SnapshotTrigger<C>>>,
+    },
+
+    /// The state machine is compacting and applies are expected to be slow for `expected`; a zero
+    /// duration means it has finished.
+    NotifyCompaction { expected: Duration },
+
+    /// Set or unset the checker that lease reads served by this node are reported to.
+    #[cfg(feature = "lease-check")]
+    SetLeaseChecker {
+        checker: Option<crate::testing::lease_check::LeaseChecker<C>>,
+    },
+
+    /// Get the responses waiting for an IO condition, send back via a oneshot::Sender.
+    GetPendingResponds {
+        tx: OneshotSenderOf<C, Vec<PendingRespondInfo<C>>>,
+    },
+
     /// Recalculate the internal server state based on the vote and the membership config.
     ///
     /// Most of the time the internal server state is recalculated automatically; the only
@@ -90,6 +138,78 @@ pub(crate) enum ExternalCommand<C: RaftTypeConfig> {
         vote: Option<VoteOf<C>>,
         membership_log_id: Option<LogIdOf<C>>,
     },
+
+    /// Propose a backup barrier entry, if the node is leader.
+    ///
+    /// Every node builds a snapshot right after applying it. The responder receives the result of
+    /// applying the barrier entry on the leader.
+    Backup { responder: CoreResponder<C> },
+
+    /// Hold logs from `from_index` for a new log subscription.
+    ///
+    /// The id of the hold and the first index the subscription reads are sent back: it is after
+    /// `from_index` if the logs are already purged.
+    SubscribeLog {
+        from_index: u64,
+        tx: OneshotSenderOf<C, (u64, u64)>,
+    },
+
+    /// Reload the log holds after a subscription advanced, and purge the logs no longer held.
+    RefreshPurgeHold,
+
+    /// Write application data that is applied only by the nodes in `scope`, if the node is leader.
+    WriteInScope {
+        app_data: C::D,
+        scope: ApplyScope<C::NodeId>,
+        responder: CoreResponder<C>,
+    },
+
+    /// Reserve `count` log indexes to fill with [`Self::WriteReserved`], if the node is leader.
+    ReserveLogIndexes {
+        count: u64,
+        tx: OneshotSenderOf<C, Result<Range<u64>, ClientWriteError<C>>>,
+    },
+
+    /// Write application data to the reserved log indexes starting at `index`, if the node is
+    /// leader.
+    WriteReserved {
+        index: u64,
+        app_data: Vec<C::D>,
+        responders: Vec<CoreResponder<C>>,
+    },
+
+    /// Check if this node meets `options` to serve a local read, and send back the applied log id
+    /// or how stale this node is.
+    LocalRead {
+        options: ReadOptions,
+        tx: OneshotSenderOf<C, Result<Option<LogIdOf<C>>, StaleRead<C>>>,
+    },
+
+    /// Read what the last `n` log entries are, without their payload.
+    GetLogTail {
+        n: u64,
+        tx: OneshotSenderOf<C, Result<Vec<LogEntryMeta<C>>, StorageError<C>>>,
+    },
+
+    /// Verify the chain hash of the log entries in `range`.
+    #[cfg(feature = "audit-log-chain")]
+    VerifyLogChain {
+        range: Range<u64>,
+        tx: OneshotSenderOf<C, Result<Option<ChainHash>, LogChainError<C>>>,
+    },
+
+    /// Set or unset the node the leader asks `target` to fetch snapshots from.
+    SetSnapshotSeed {
+        target: C::NodeId,
+        seed: Option<C::NodeId>,
+        tx: ResultSender<C, (), SeedSnapshotError<C>>,
+    },
+
+    /// Fetch a snapshot from the seed node in `req`, send back via a oneshot::Sender.
+    FetchSeedSnapshot {
+        req: SeedSnapshotRequest<C>,
+        tx: OneshotSenderOf<C, FetchSnapshotResponse<C>>,
+    },
 }
 
 impl<C: RaftTypeConfig> ExternalCommand<C> {
@@ -98,13 +218,31 @@ impl<C: RaftTypeConfig> ExternalCommand<C> {
         match self {
             ExternalCommand::Elect { .. } => ExternalCommandName::Elect,
             ExternalCommand::Heartbeat => ExternalCommandName::Heartbeat,
-            ExternalCommand::Snapshot => ExternalCommandName::Snapshot,
+            ExternalCommand::Snapshot { .. } => ExternalCommandName::Snapshot,
             ExternalCommand::GetSnapshot { .. } => ExternalCommandName::GetSnapshot,
             ExternalCommand::PurgeLog { .. } => ExternalCommandName::PurgeLog,
             ExternalCommand::TriggerTransferLeader { .. } => ExternalCommandName::TriggerTransferLeader,
             ExternalCommand::AllowNextRevert { .. } => ExternalCommandName::AllowNextRevert,
             ExternalCommand::SetMetricsRecorder { .. } => ExternalCommandName::SetMetricsRecorder,
+            ExternalCommand::SetStorageUsageProbe { .. } => ExternalCommandName::SetStorageUsageProbe,
+            ExternalCommand::SetSnapshotTrigger { .. } => ExternalCommandName::SetSnapshotTrigger,
+            ExternalCommand::NotifyCompaction { .. } => ExternalCommandName::NotifyCompaction,
+            #[cfg(feature = "lease-check")]
+            ExternalCommand::SetLeaseChecker { .. } => ExternalCommandName::SetLeaseChecker,
+            ExternalCommand::GetPendingResponds { .. } => ExternalCommandName::GetPendingResponds,
             ExternalCommand::RefreshServerState { .. } => ExternalCommandName::RefreshServerState,
+            ExternalCommand::Backup { .. } => ExternalCommandName::Backup,
+            ExternalCommand::WriteInScope { .. } => ExternalCommandName::WriteInScope,
+            ExternalCommand::SubscribeLog { .. } => ExternalCommandName::SubscribeLog,
+            ExternalCommand::RefreshPurgeHold => ExternalCommandName::RefreshPurgeHold,
+            ExternalCommand::ReserveLogIndexes { .. } => ExternalCommandName::ReserveLogIndexes,
+            ExternalCommand::WriteReserved { .. } => ExternalCommandName::WriteReserved,
+            ExternalCommand::LocalRead { .. } => ExternalCommandName::LocalRead,
+            ExternalCommand::GetLogTail { .. } => ExternalCommandName::GetLogTail,
+            #[cfg(feature = "audit-log-chain")]
+            ExternalCommand::VerifyLogChain { .. } => ExternalCommandName::VerifyLogChain,
+            ExternalCommand::SetSnapshotSeed { .. } => ExternalCommandName::SetSnapshotSeed,
+            ExternalCommand::FetchSeedSnapshot { .. } => ExternalCommandName::FetchSeedSnapshot,
         }
     }
 }
@@ -128,7 +266,7 @@ where C: RaftTypeConfig
             ExternalCommand::Heartbeat => {
                 write!(f, "Heartbeat")
             }
-            ExternalCommand::Snapshot => {
+            ExternalCommand::Snapshot { .. } => {
                 write!(f, "Snapshot")
             }
             ExternalCommand::GetSnapshot { .. } => {
@@ -151,6 +289,22 @@ where C: RaftTypeConfig
             ExternalCommand::SetMetricsRecorder { .. } => {
                 write!(f, "SetMetricsRecorder")
             }
+            ExternalCommand::SetStorageUsageProbe { .. } => {
+                write!(f, "SetStorageUsageProbe")
+            }
+            ExternalCommand::SetSnapshotTrigger { .. } => {
+                write!(f, "SetSnapshotTrigger")
+            }
+            ExternalCommand::NotifyCompaction { expected } => {
+                write!(f, "NotifyCompaction: expected: {:?}", expected)
+            }
+            #[cfg(feature = "lease-check")]
+            ExternalCommand::SetLeaseChecker { .. } => {
+                write!(f, "SetLeaseChecker")
+            }
+            ExternalCommand::GetPendingResponds { .. } => {
+                write!(f, "GetPendingResponds")
+            }
             ExternalCommand::RefreshServerState {
                 vote,
                 membership_log_id,
@@ -162,6 +316,40 @@ where C: RaftTypeConfig
                     membership_log_id.display()
                 )
             }
+            ExternalCommand::Backup { .. } => {
+                write!(f, "Backup")
+            }
+            ExternalCommand::WriteInScope { scope, .. } => {
+                write!(f, "WriteInScope: scope: {}", scope)
+            }
+            ExternalCommand::SubscribeLog { from_index, .. } => {
+                write!(f, "SubscribeLog: from_index: {}", from_index)
+            }
+            ExternalCommand::RefreshPurgeHold => {
+                write!(f, "RefreshPurgeHold")
+            }
+            ExternalCommand::ReserveLogIndexes { count, .. } => {
+                write!(f, "ReserveLogIndexes: count: {}", count)
+            }
+            ExternalCommand::WriteReserved { index, app_data, .. } => {
+                write!(f, "WriteReserved: index: {}, count: {}", index, app_data.len())
+            }
+            ExternalCommand::LocalRead { options, .. } => {
+                write!(f, "LocalRead: {:?}", options)
+            }
+            ExternalCommand::GetLogTail { n, .. } => {
+                write!(f, "GetLogTail: n: {}", n)
+            }
+            #[cfg(feature = "audit-log-chain")]
+            ExternalCommand::VerifyLogChain { range, .. } => {
+                write!(f, "VerifyLogChain: range: {:?}", range)
+            }
+            ExternalCommand::SetSnapshotSeed { target, seed, .. } => {
+                write!(f, "SetSnapshotSeed: target: {}, seed: {}", target, seed.display())
+            }
+            ExternalCommand::FetchSeedSnapshot { req, .. } => {
+                write!(f, "FetchSeedSnapshot: {}", req)
+            }
         }
     }
 }
@@ -1,22 +1,22 @@
-use std::collections::BTreeMap;
 use std::fmt;
 
 use display_more::DisplayOptionExt;
 
 use crate::ChangeMembers;
+use crate::Membership;
 use crate::RaftState;
 use crate::RaftTypeConfig;
 use crate::base::BoxOnce;
 use crate::core::raft_msg::external_command::ExternalCommand;
 #[cfg(feature = "runtime-stats")]
 use crate::core::runtime_stats::RuntimeStats;
-use crate::display_ext::DisplayBTreeMapDebugValueExt;
 use crate::errors::Infallible;
 use crate::errors::InitializeError;
 use crate::errors::LinearizableReadError;
 use crate::impls::ProgressResponder;
 use crate::raft::AppendEntriesRequest;
 use crate::raft::ClientWriteResult;
+use crate::raft::CorrelationId;
 use crate::raft::ReadPolicy;
 use crate::raft::SnapshotResponse;
 use crate::raft::VoteRequest;
@@ -24,6 +24,7 @@ use crate::raft::VoteResponse;
 use crate::raft::linearizable_read::Linearizer;
 use crate::raft::responder::core_responder::CoreResponder;
 use crate::raft::stream_append::StreamAppendResult;
+use crate::storage::SnapshotLocator;
 use crate::type_config::alias::BatchOf;
 use crate::type_config::alias::CommittedLeaderIdOf;
 use crate::type_config::alias::EntryPayloadOf;
@@ -32,6 +33,7 @@ use crate::type_config::alias::InstantOf;
 use crate::type_config::alias::LogIdOf;
 use crate::type_config::alias::OneshotSenderOf;
 use crate::type_config::alias::SnapshotDataOf;
+use crate::type_config::alias::SnapshotMetaOf;
 use crate::type_config::alias::SnapshotOf;
 use crate::type_config::alias::VoteOf;
 
@@ -48,7 +50,10 @@ pub(crate) type ResultSender<C, T, E = Infallible> = OneshotSenderOf<C, Result<T
 pub(crate) type VoteTx<C> = OneshotSenderOf<C, VoteResponse<C>>;
 
 /// TX for Append Entries Response
-pub(crate) type AppendEntriesTx<C> = OneshotSenderOf<C, StreamAppendResult<C>>;
+///
+/// `None` is sent if the request is merged into a later one and has been appended successfully:
+/// the response to the last merged request answers it.
+pub(crate) type AppendEntriesTx<C> = OneshotSenderOf<C, Option<StreamAppendResult<C>>>;
 
 /// TX for Linearizable Read Response
 pub(crate) type ClientReadTx<C> = ResultSender<C, Linearizer<C>, LinearizableReadError<C>>;
@@ -59,9 +64,14 @@ pub(crate) type ClientReadTx<C> = ResultSender<C, Linearizer<C>, LinearizableRea
 pub(crate) enum RaftMsg<C>
 where C: RaftTypeConfig
 {
+    /// AppendEntries from a leader.
+    ///
+    /// Consecutive requests from the same leader may be merged into one `rpc`. `txs` keeps the
+    /// sender of every merged request, in order. Only the last one is responded with the result of
+    /// the merged segment.
     AppendEntries {
         rpc: AppendEntriesRequest<C>,
-        tx: AppendEntriesTx<C>,
+        txs: BatchOf<C, AppendEntriesTx<C>>,
     },
 
     RequestVote {
@@ -81,6 +91,20 @@ where C: RaftTypeConfig
         tx: OneshotSenderOf<C, SnapshotResponse<C>>,
     },
 
+    /// Install a snapshot stored out of band, which the state machine fetches by itself.
+    InstallSnapshotLocator {
+        vote: VoteOf<C>,
+        locator: SnapshotLocator<C>,
+        tx: OneshotSenderOf<C, SnapshotResponse<C>>,
+    },
+
+    /// Install only the metadata of a snapshot on a witness, which keeps no state machine data.
+    InstallSnapshotMeta {
+        vote: VoteOf<C>,
+        meta: SnapshotMetaOf<C>,
+        tx: OneshotSenderOf<C, SnapshotResponse<C>>,
+    },
+
     /// Begin receiving a snapshot from the leader.
     ///
     /// Returns a snapshot data handle for receiving data.
@@ -104,8 +128,14 @@ where C: RaftTypeConfig
         tx: ClientReadTx<C>,
     },
 
+    /// Get a [`Linearizer`] from the leader, to serve a linearizable read on this node.
+    FollowerReadIndex {
+        read_policy: ReadPolicy,
+        tx: ClientReadTx<C>,
+    },
+
     Initialize {
-        members: BTreeMap<C::NodeId, C::Node>,
+        membership: Membership<C::NodeId, C::Node>,
         tx: ResultSender<C, (), InitializeError<C>>,
     },
 
@@ -116,6 +146,9 @@ where C: RaftTypeConfig
         /// config will be converted into learners, otherwise they will be removed.
         retain: bool,
 
+        /// The application-assigned id to trace this membership change.
+        correlation_id: Option<CorrelationId>,
+
         tx: ProgressResponder<C, ClientWriteResult<C>>,
     },
 
@@ -157,9 +190,12 @@ impl<C: RaftTypeConfig> RaftMsg<C> {
             RaftMsg::RequestVote { .. } => RaftMsgName::RequestVote,
             RaftMsg::RequestPreVote { .. } => RaftMsgName::RequestPreVote,
             RaftMsg::InstallSnapshot { .. } => RaftMsgName::InstallSnapshot,
+            RaftMsg::InstallSnapshotLocator { .. } => RaftMsgName::InstallSnapshotLocator,
+            RaftMsg::InstallSnapshotMeta { .. } => RaftMsgName::InstallSnapshotMeta,
             RaftMsg::GetSnapshotReceiver { .. } => RaftMsgName::GetSnapshotReceiver,
             RaftMsg::ClientWrite { .. } => RaftMsgName::ClientWrite,
             RaftMsg::GetLinearizer { .. } => RaftMsgName::GetLinearizer,
+            RaftMsg::FollowerReadIndex { .. } => RaftMsgName::FollowerReadIndex,
             RaftMsg::Initialize { .. } => RaftMsgName::Initialize,
             RaftMsg::ChangeMembership { .. } => RaftMsgName::ChangeMembership,
             RaftMsg::HandleTransferLeader { .. } => RaftMsgName::HandleTransferLeader,
@@ -191,12 +227,21 @@ where C: RaftTypeConfig
             RaftMsg::InstallSnapshot { vote, snapshot, .. } => {
                 write!(f, "InstallSnapshot: vote: {}, snapshot: {}", vote, snapshot)
             }
+            RaftMsg::InstallSnapshotLocator { vote, locator, .. } => {
+                write!(f, "InstallSnapshotLocator: vote: {}, locator: {}", vote, locator)
+            }
+            RaftMsg::InstallSnapshotMeta { vote, meta, .. } => {
+                write!(f, "InstallSnapshotMeta: vote: {}, meta: {}", vote, meta)
+            }
             RaftMsg::ClientWrite { .. } => write!(f, "ClientWrite"),
             RaftMsg::GetLinearizer { read_policy, .. } => {
                 write!(f, "GetLinearizer: {}", read_policy)
             }
-            RaftMsg::Initialize { members, .. } => {
-                write!(f, "Initialize: {}", members.display())
+            RaftMsg::FollowerReadIndex { read_policy, .. } => {
+                write!(f, "FollowerReadIndex: {}", read_policy)
+            }
+            RaftMsg::Initialize { membership, .. } => {
+                write!(f, "Initialize: {}", membership)
             }
             RaftMsg::ChangeMembership { changes, retain, .. } => {
                 write!(f, "ChangeMembership: {}, retain: {}", changes, retain)
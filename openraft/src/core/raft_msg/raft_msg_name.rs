@@ -10,13 +10,29 @@ pub enum ExternalCommandName {
     TriggerTransferLeader,
     AllowNextRevert,
     SetMetricsRecorder,
+    SetStorageUsageProbe,
     RefreshServerState,
+    Backup,
+    WriteInScope,
+    SubscribeLog,
+    RefreshPurgeHold,
+    SetLeaseChecker,
+    GetPendingResponds,
+    ReserveLogIndexes,
+    WriteReserved,
+    NotifyCompaction,
+    LocalRead,
+    GetLogTail,
+    SetSnapshotSeed,
+    FetchSeedSnapshot,
+    SetSnapshotTrigger,
+    VerifyLogChain,
 }
 
 impl ExternalCommandName {
     /// Total number of variants.
     #[allow(dead_code)]
-    pub const COUNT: usize = 9;
+    pub const COUNT: usize = 25;
 
     /// All variants in canonical order.
     #[allow(dead_code)]
@@ -29,7 +45,23 @@ impl ExternalCommandName {
         ExternalCommandName::TriggerTransferLeader,
         ExternalCommandName::AllowNextRevert,
         ExternalCommandName::SetMetricsRecorder,
+        ExternalCommandName::SetStorageUsageProbe,
         ExternalCommandName::RefreshServerState,
+        ExternalCommandName::Backup,
+        ExternalCommandName::WriteInScope,
+        ExternalCommandName::SubscribeLog,
+        ExternalCommandName::RefreshPurgeHold,
+        ExternalCommandName::SetLeaseChecker,
+        ExternalCommandName::GetPendingResponds,
+        ExternalCommandName::ReserveLogIndexes,
+        ExternalCommandName::WriteReserved,
+        ExternalCommandName::NotifyCompaction,
+        ExternalCommandName::LocalRead,
+        ExternalCommandName::GetLogTail,
+        ExternalCommandName::SetSnapshotSeed,
+        ExternalCommandName::FetchSeedSnapshot,
+        ExternalCommandName::SetSnapshotTrigger,
+        ExternalCommandName::VerifyLogChain,
     ];
 
     /// Returns the index of this variant for array-based storage.
@@ -44,7 +76,23 @@ impl ExternalCommandName {
             ExternalCommandName::TriggerTransferLeader => 5,
             ExternalCommandName::AllowNextRevert => 6,
             ExternalCommandName::SetMetricsRecorder => 7,
-            ExternalCommandName::RefreshServerState => 8,
+            ExternalCommandName::SetStorageUsageProbe => 8,
+            ExternalCommandName::RefreshServerState => 9,
+            ExternalCommandName::Backup => 10,
+            ExternalCommandName::WriteInScope => 11,
+            ExternalCommandName::SubscribeLog => 12,
+            ExternalCommandName::RefreshPurgeHold => 13,
+            ExternalCommandName::SetLeaseChecker => 14,
+            ExternalCommandName::GetPendingResponds => 15,
+            ExternalCommandName::ReserveLogIndexes => 16,
+            ExternalCommandName::WriteReserved => 17,
+            ExternalCommandName::NotifyCompaction => 18,
+            ExternalCommandName::LocalRead => 19,
+            ExternalCommandName::GetLogTail => 20,
+            ExternalCommandName::SetSnapshotSeed => 21,
+            ExternalCommandName::FetchSeedSnapshot => 22,
+            ExternalCommandName::SetSnapshotTrigger => 23,
+            ExternalCommandName::VerifyLogChain => 24,
         }
     }
 
@@ -59,7 +107,23 @@ impl ExternalCommandName {
             ExternalCommandName::TriggerTransferLeader => "Ext::TriggerTransferLeader",
             ExternalCommandName::AllowNextRevert => "Ext::AllowNextRevert",
             ExternalCommandName::SetMetricsRecorder => "Ext::SetMetricsRecorder",
+            ExternalCommandName::SetStorageUsageProbe => "Ext::SetStorageUsageProbe",
             ExternalCommandName::RefreshServerState => "Ext::RefreshServerState",
+            ExternalCommandName::Backup => "Ext::Backup",
+            ExternalCommandName::WriteInScope => "Ext::WriteInScope",
+            ExternalCommandName::SubscribeLog => "Ext::SubscribeLog",
+            ExternalCommandName::RefreshPurgeHold => "Ext::RefreshPurgeHold",
+            ExternalCommandName::SetLeaseChecker => "Ext::SetLeaseChecker",
+            ExternalCommandName::GetPendingResponds => "Ext::GetPendingResponds",
+            ExternalCommandName::ReserveLogIndexes => "Ext::ReserveLogIndexes",
+            ExternalCommandName::WriteReserved => "Ext::WriteReserved",
+            ExternalCommandName::NotifyCompaction => "Ext::NotifyCompaction",
+            ExternalCommandName::LocalRead => "Ext::LocalRead",
+            ExternalCommandName::GetLogTail => "Ext::GetLogTail",
+            ExternalCommandName::SetSnapshotSeed => "Ext::SetSnapshotSeed",
+            ExternalCommandName::FetchSeedSnapshot => "Ext::FetchSeedSnapshot",
+            ExternalCommandName::SetSnapshotTrigger => "Ext::SetSnapshotTrigger",
+            ExternalCommandName::VerifyLogChain => "Ext::VerifyLogChain",
         }
     }
 }
@@ -84,17 +148,20 @@ pub enum RaftMsgName {
     GetSnapshotReceiver,
     ClientWrite,
     GetLinearizer,
+    FollowerReadIndex,
     Initialize,
     ChangeMembership,
     HandleTransferLeader,
     WithRaftState,
     ExternalCommand(ExternalCommandName),
     GetRuntimeStats,
+    InstallSnapshotLocator,
+    InstallSnapshotMeta,
 }
 
 impl RaftMsgName {
     /// Total number of variants (including expanded ExternalCommand variants).
-    pub const COUNT: usize = 21;
+    pub const COUNT: usize = 40;
 
     /// All variants in canonical order.
     ///
@@ -108,6 +175,7 @@ impl RaftMsgName {
         RaftMsgName::GetSnapshotReceiver,
         RaftMsgName::ClientWrite,
         RaftMsgName::GetLinearizer,
+        RaftMsgName::FollowerReadIndex,
         RaftMsgName::Initialize,
         RaftMsgName::ChangeMembership,
         RaftMsgName::HandleTransferLeader,
@@ -120,8 +188,26 @@ impl RaftMsgName {
         RaftMsgName::ExternalCommand(ExternalCommandName::TriggerTransferLeader),
         RaftMsgName::ExternalCommand(ExternalCommandName::AllowNextRevert),
         RaftMsgName::ExternalCommand(ExternalCommandName::SetMetricsRecorder),
+        RaftMsgName::ExternalCommand(ExternalCommandName::SetStorageUsageProbe),
         RaftMsgName::ExternalCommand(ExternalCommandName::RefreshServerState),
+        RaftMsgName::ExternalCommand(ExternalCommandName::Backup),
+        RaftMsgName::ExternalCommand(ExternalCommandName::WriteInScope),
+        RaftMsgName::ExternalCommand(ExternalCommandName::SubscribeLog),
+        RaftMsgName::ExternalCommand(ExternalCommandName::RefreshPurgeHold),
+        RaftMsgName::ExternalCommand(ExternalCommandName::SetLeaseChecker),
+        RaftMsgName::ExternalCommand(ExternalCommandName::GetPendingResponds),
+        RaftMsgName::ExternalCommand(ExternalCommandName::ReserveLogIndexes),
+        RaftMsgName::ExternalCommand(ExternalCommandName::WriteReserved),
+        RaftMsgName::ExternalCommand(ExternalCommandName::NotifyCompaction),
+        RaftMsgName::ExternalCommand(ExternalCommandName::LocalRead),
+        RaftMsgName::ExternalCommand(ExternalCommandName::GetLogTail),
+        RaftMsgName::ExternalCommand(ExternalCommandName::SetSnapshotSeed),
+        RaftMsgName::ExternalCommand(ExternalCommandName::FetchSeedSnapshot),
+        RaftMsgName::ExternalCommand(ExternalCommandName::SetSnapshotTrigger),
+        RaftMsgName::ExternalCommand(ExternalCommandName::VerifyLogChain),
         RaftMsgName::GetRuntimeStats,
+        RaftMsgName::InstallSnapshotLocator,
+        RaftMsgName::InstallSnapshotMeta,
     ];
 
     /// Returns the index of this variant for array-based storage.
@@ -134,12 +220,15 @@ impl RaftMsgName {
             RaftMsgName::GetSnapshotReceiver => 4,
             RaftMsgName::ClientWrite => 5,
             RaftMsgName::GetLinearizer => 6,
-            RaftMsgName::Initialize => 7,
-            RaftMsgName::ChangeMembership => 8,
-            RaftMsgName::HandleTransferLeader => 9,
-            RaftMsgName::WithRaftState => 10,
-            RaftMsgName::ExternalCommand(ext) => 11 + ext.index(),
-            RaftMsgName::GetRuntimeStats => 11 + ExternalCommandName::COUNT,
+            RaftMsgName::FollowerReadIndex => 7,
+            RaftMsgName::Initialize => 8,
+            RaftMsgName::ChangeMembership => 9,
+            RaftMsgName::HandleTransferLeader => 10,
+            RaftMsgName::WithRaftState => 11,
+            RaftMsgName::ExternalCommand(ext) => 12 + ext.index(),
+            RaftMsgName::GetRuntimeStats => 12 + ExternalCommandName::COUNT,
+            RaftMsgName::InstallSnapshotLocator => 13 + ExternalCommandName::COUNT,
+            RaftMsgName::InstallSnapshotMeta => 14 + ExternalCommandName::COUNT,
         }
     }
 
@@ -154,12 +243,15 @@ impl RaftMsgName {
             RaftMsgName::GetSnapshotReceiver => "GetSnapshotReceiver",
             RaftMsgName::ClientWrite => "ClientWrite",
             RaftMsgName::GetLinearizer => "GetLinearizer",
+            RaftMsgName::FollowerReadIndex => "FollowerReadIndex",
             RaftMsgName::Initialize => "Initialize",
             RaftMsgName::ChangeMembership => "ChangeMembership",
             RaftMsgName::HandleTransferLeader => "HandleTransferLeader",
             RaftMsgName::WithRaftState => "WithRaftState",
             RaftMsgName::ExternalCommand(ext) => ext.as_str(),
             RaftMsgName::GetRuntimeStats => "GetRuntimeStats",
+            RaftMsgName::InstallSnapshotLocator => "InstallSnapshotLocator",
+            RaftMsgName::InstallSnapshotMeta => "InstallSnapshotMeta",
         }
     }
 }
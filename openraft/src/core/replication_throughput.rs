@@ -0,0 +1,215 @@
+//! Measured replication throughput, readable without calling `RaftCore`.
+
+use std::collections::BTreeMap;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::LogIdOptionExt;
+use crate::RaftTypeConfig;
+use crate::metrics::CatchUpEstimate;
+use crate::type_config::alias::LogIdOf;
+
+/// The number of recent acknowledgements kept for each target.
+const SAMPLES_PER_TARGET: usize = 64;
+
+/// Log entries acknowledged by a target in one response.
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    entries: u64,
+    bytes: u64,
+
+    /// How long the replication stream was busy delivering these entries: from when the first
+    /// of them was sent, or the previous response if that is later, to this response.
+    busy: Duration,
+}
+
+#[derive(Debug)]
+struct Inner<NID> {
+    logs: BTreeMap<NID, VecDeque<Sample>>,
+
+    /// How long the last snapshot sent to any target took to transfer.
+    snapshot_sent: Option<Duration>,
+}
+
+/// The log replication throughput to each target and the duration of the last snapshot transfer.
+///
+/// It is shared between the replication tasks, which record every acknowledgement, and
+/// [`Raft::estimate_catchup()`](crate::Raft::estimate_catchup).
+///
+/// Only the time a replication stream is busy is counted, so that a follower that is caught up
+/// and receives a few writes per second does not appear slow.
+pub(crate) struct ReplicationThroughput<C>
+where C: RaftTypeConfig
+{
+    inner: Arc<Mutex<Inner<C::NodeId>>>,
+}
+
+impl<C> Clone for ReplicationThroughput<C>
+where C: RaftTypeConfig
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<C> ReplicationThroughput<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(Inner {
+                logs: BTreeMap::new(),
+                snapshot_sent: None,
+            })),
+        }
+    }
+
+    /// Record that `target` acknowledged `entries` log entries of `bytes` in total, which the
+    /// replication stream was busy delivering for `busy`.
+    pub(crate) fn record_logs(&self, target: &C::NodeId, entries: u64, bytes: u64, busy: Duration) {
+        let mut inner = self.inner.lock().unwrap();
+        let samples = inner.logs.entry(target.clone()).or_default();
+
+        samples.push_back(Sample { entries, bytes, busy });
+        while samples.len() > SAMPLES_PER_TARGET {
+            samples.pop_front();
+        }
+    }
+
+    /// Record that a snapshot is sent to a target in `elapsed`.
+    pub(crate) fn record_snapshot(&self, elapsed: Duration) {
+        self.inner.lock().unwrap().snapshot_sent = Some(elapsed);
+    }
+
+    /// Estimate how long `target` takes to catch up from `matched` to `last_log_id`, by
+    /// replicating logs or by installing the snapshot that includes `snapshot` first.
+    ///
+    /// The throughput to `target` is used if it has been measured, otherwise the throughput to
+    /// all targets.
+    pub(crate) fn estimate(
+        &self,
+        target: &C::NodeId,
+        matched: Option<&LogIdOf<C>>,
+        last_log_id: Option<&LogIdOf<C>>,
+        purged: Option<&LogIdOf<C>>,
+        snapshot: Option<&LogIdOf<C>>,
+    ) -> CatchUpEstimate {
+        let inner = self.inner.lock().unwrap();
+
+        let samples = match inner.logs.get(target) {
+            Some(samples) => samples.iter().collect::<Vec<_>>(),
+            None => inner.logs.values().flatten().collect(),
+        };
+
+        let entries = samples.iter().map(|s| s.entries).sum::<u64>();
+        let bytes = samples.iter().map(|s| s.bytes).sum::<u64>();
+        let busy = samples.iter().map(|s| s.busy).sum::<Duration>();
+
+        let entries_per_sec = if entries > 0 && !busy.is_zero() {
+            let rate = entries as u128 * 1_000_000_000 / busy.as_nanos();
+            Some(std::cmp::max(rate as u64, 1))
+        } else {
+            None
+        };
+        let bytes_per_entry = if entries > 0 { Some(bytes / entries) } else { None };
+
+        let time_to_send = |n: u64| -> Option<Duration> {
+            if n == 0 {
+                return Some(Duration::ZERO);
+            }
+            entries_per_sec.map(|rate| Duration::from_nanos((n as u128 * 1_000_000_000 / rate as u128) as u64))
+        };
+
+        let last_next = last_log_id.next_index();
+        let lag_entries = last_next.saturating_sub(matched.next_index());
+        let snapshot_required = purged > matched;
+
+        let by_logs = if snapshot_required {
+            None
+        } else {
+            time_to_send(lag_entries)
+        };
+
+        let by_snapshot = match (snapshot, inner.snapshot_sent) {
+            (Some(snapshot), Some(sent)) => {
+                time_to_send(last_next.saturating_sub(snapshot.index() + 1)).map(|tail| sent + tail)
+            }
+            _ => None,
+        };
+
+        CatchUpEstimate {
+            lag_entries,
+            snapshot_required,
+            entries_per_sec,
+            bytes_per_entry,
+            by_logs,
+            by_snapshot,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::ReplicationThroughput;
+    use crate::engine::testing::UTConfig;
+    use crate::engine::testing::log_id;
+
+    #[test]
+    fn test_replication_throughput_estimate() {
+        let t = ReplicationThroughput::<UTConfig>::new();
+
+        let est = t.estimate(&2, Some(&log_id(1, 1, 9)), Some(&log_id(1, 1, 109)), None, None);
+        assert_eq!(100, est.lag_entries);
+        assert_eq!(None, est.entries_per_sec, "nothing is measured");
+        assert_eq!(None, est.by_logs);
+        assert_eq!(None, est.duration());
+
+        // 100 entries per second, 10 bytes each.
+        t.record_logs(&2, 50, 500, Duration::from_millis(500));
+        t.record_logs(&3, 50, 500, Duration::from_millis(500));
+
+        let est = t.estimate(&2, Some(&log_id(1, 1, 9)), Some(&log_id(1, 1, 109)), None, None);
+        assert_eq!(Some(100), est.entries_per_sec);
+        assert_eq!(Some(10), est.bytes_per_entry);
+        assert_eq!(Some(Duration::from_secs(1)), est.by_logs);
+        assert_eq!(None, est.by_snapshot, "no snapshot is sent");
+        assert!(!est.prefer_snapshot());
+
+        // Target 4 is not measured: use the throughput to all targets.
+        let est = t.estimate(&4, None, Some(&log_id(1, 1, 199)), None, None);
+        assert_eq!(200, est.lag_entries);
+        assert_eq!(Some(Duration::from_secs(2)), est.by_logs);
+
+        // A snapshot up to 99 takes 200ms, then 10 entries take 100ms.
+        t.record_snapshot(Duration::from_millis(200));
+
+        let est = t.estimate(
+            &2,
+            Some(&log_id(1, 1, 9)),
+            Some(&log_id(1, 1, 109)),
+            None,
+            Some(&log_id(1, 1, 99)),
+        );
+        assert_eq!(Some(Duration::from_millis(300)), est.by_snapshot);
+        assert!(est.prefer_snapshot());
+        assert_eq!(Some(Duration::from_millis(300)), est.duration());
+
+        // Logs are purged: a snapshot is required.
+        let est = t.estimate(
+            &2,
+            Some(&log_id(1, 1, 9)),
+            Some(&log_id(1, 1, 109)),
+            Some(&log_id(1, 1, 50)),
+            Some(&log_id(1, 1, 99)),
+        );
+        assert!(est.snapshot_required);
+        assert_eq!(None, est.by_logs);
+        assert!(est.prefer_snapshot());
+    }
+}
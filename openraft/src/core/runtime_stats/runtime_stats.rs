@@ -79,6 +79,16 @@ where C: RaftTypeConfig
     /// where 1000 means 100% utilization. Helps identify if the budget is well-tuned.
     pub notification_usage_permille: Histogram,
 
+    /// Histogram tracking the approximate size in bytes of the commands queued in `RaftCore`.
+    ///
+    /// This is recorded each time `run_engine_commands()` starts, showing how much work piles up
+    /// when storage or the state machine falls behind.
+    pub command_queue_bytes: Histogram,
+
+    /// Number of times `RaftCore` stopped taking new RaftMsg because the command queue exceeded
+    /// [`Config::max_command_queue_bytes`].
+    pub input_stalls: u64,
+
     /// Count of each command type executed.
     ///
     /// This tracks how many times each command type has been executed,
@@ -139,6 +149,8 @@ where C: RaftTypeConfig
             notification_budget: Histogram::<()>::new(),
             raft_msg_usage_permille: Histogram::<()>::new(),
             notification_usage_permille: Histogram::<()>::new(),
+            command_queue_bytes: Histogram::<()>::new(),
+            input_stalls: 0,
             command_counts: vec![0; CommandName::COUNT],
             raft_msg_counts: vec![0; RaftMsgName::COUNT],
             notification_counts: vec![0; NotificationName::COUNT],
@@ -211,6 +223,8 @@ where C: RaftTypeConfig
             notification_budget: self.notification_budget.percentile_stats(),
             raft_msg_usage_permille: self.raft_msg_usage_permille.percentile_stats(),
             notification_usage_permille: self.notification_usage_permille.percentile_stats(),
+            command_queue_bytes: self.command_queue_bytes.percentile_stats(),
+            input_stalls: self.input_stalls,
             command_counts: self.command_counts.clone(),
             raft_msg_counts: self.raft_msg_counts.clone(),
             notification_counts: self.notification_counts.clone(),
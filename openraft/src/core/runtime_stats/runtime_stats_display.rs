@@ -37,6 +37,8 @@ where C: RaftTypeConfig
     pub(crate) notification_budget: PercentileStats,
     pub(crate) raft_msg_usage_permille: PercentileStats,
     pub(crate) notification_usage_permille: PercentileStats,
+    pub(crate) command_queue_bytes: PercentileStats,
+    pub(crate) input_stalls: u64,
     pub(crate) command_counts: Vec<u64>,
     pub(crate) raft_msg_counts: Vec<u64>,
     pub(crate) notification_counts: Vec<u64>,
@@ -90,7 +92,7 @@ where C: RaftTypeConfig
     fn fmt_compact(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "RuntimeStats {{ apply_batch: {}, append_batch: {}, replicate_batch: {}, raft_msg_per_run: {}, write_batch: {}, raft_msg_budget: {}, notification_budget: {}, raft_msg_usage_permille: {}, notification_usage_permille: {}, commands: {{",
+            "RuntimeStats {{ apply_batch: {}, append_batch: {}, replicate_batch: {}, raft_msg_per_run: {}, write_batch: {}, raft_msg_budget: {}, notification_budget: {}, raft_msg_usage_permille: {}, notification_usage_permille: {}, command_queue_bytes: {}, input_stalls: {}, commands: {{",
             self.apply_batch,
             self.append_batch,
             self.replicate_batch,
@@ -99,7 +101,9 @@ where C: RaftTypeConfig
             self.raft_msg_budget,
             self.notification_budget,
             self.raft_msg_usage_permille,
-            self.notification_usage_permille
+            self.notification_usage_permille,
+            self.command_queue_bytes,
+            self.input_stalls
         )?;
 
         let mut first = true;
@@ -170,6 +174,8 @@ where C: RaftTypeConfig
         writeln!(f, "  notification_budget: {}", self.notification_budget)?;
         writeln!(f, "  raft_msg_usage_permille: {}", self.raft_msg_usage_permille)?;
         writeln!(f, "  notification_usage_permille: {}", self.notification_usage_permille)?;
+        writeln!(f, "  command_queue_bytes: {}", self.command_queue_bytes)?;
+        writeln!(f, "  input_stalls: {}", self.input_stalls)?;
 
         writeln!(f, "  commands:")?;
         for (i, name) in CommandName::ALL.iter().enumerate() {
@@ -284,12 +290,18 @@ where C: RaftTypeConfig
             &self.notification_usage_permille,
             "Notification budget utilization (‰)",
         ));
+        builder.push_record(Self::percentile_row(
+            "CmdQueueBytes",
+            &self.command_queue_bytes,
+            "Approximate bytes of queued commands",
+        ));
         let mut table = builder.build();
         table.with(Style::rounded());
         table.with(Alignment::right());
         table.modify(Columns::first(), Alignment::left());
         table.modify(Columns::last(), Alignment::left());
         writeln!(f, "{}", table)?;
+        writeln!(f, "Input stalls: {}", Self::format_count(self.input_stalls))?;
 
         // Commands table with right-aligned counts
         let mut builder = Builder::default();
@@ -468,6 +480,8 @@ mod tests {
             notification_budget: EMPTY_STATS,
             raft_msg_usage_permille: EMPTY_STATS,
             notification_usage_permille: EMPTY_STATS,
+            command_queue_bytes: EMPTY_STATS,
+            input_stalls: 0,
             command_counts,
             raft_msg_counts: vec![0; RaftMsgName::COUNT],
             notification_counts: vec![0; NotificationName::COUNT],
@@ -0,0 +1,102 @@
+//! Shares the maps in metrics between the metrics published, until they change.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use crate::RaftTypeConfig;
+use crate::config::ConfigDigest;
+use crate::metrics::HeartbeatMetrics;
+use crate::metrics::ReplicationMetrics;
+use crate::metrics::ReplicationTargetsMetrics;
+use crate::metrics::SnapshotTransferState;
+use crate::raft::Capabilities;
+
+/// The last published value, returned again instead of a new `Arc` while the value is unchanged.
+#[derive(Debug)]
+pub(crate) struct SharedArc<T> {
+    last: Option<Arc<T>>,
+}
+
+impl<T> Default for SharedArc<T> {
+    fn default() -> Self {
+        Self { last: None }
+    }
+}
+
+impl<T> SharedArc<T>
+where T: PartialEq
+{
+    /// Return the last published `Arc` if it holds a value equal to `value`, otherwise publish
+    /// `value` in a new one.
+    pub(crate) fn share(&mut self, value: T) -> Arc<T> {
+        if let Some(last) = &self.last
+            && **last == value
+        {
+            return last.clone();
+        }
+
+        let a = Arc::new(value);
+        self.last = Some(a.clone());
+        a
+    }
+
+    /// Same as [`Self::share()`], for a value that is only present on some nodes, e.g., a leader.
+    pub(crate) fn share_opt(&mut self, value: Option<T>) -> Option<Arc<T>> {
+        value.map(|v| self.share(v))
+    }
+}
+
+/// The maps in [`RaftMetrics`](crate::metrics::RaftMetrics) last published by `RaftCore`.
+///
+/// Metrics are published on every change of the Raft state, but most of the time the maps are
+/// unchanged: sharing them avoids cloning them for each metrics published, and for each clone
+/// made by a metrics subscriber.
+pub(crate) struct SharedMetrics<C>
+where C: RaftTypeConfig
+{
+    pub(crate) replication: SharedArc<ReplicationMetrics<C>>,
+    pub(crate) replication_targets: SharedArc<ReplicationTargetsMetrics<C>>,
+    pub(crate) heartbeat: SharedArc<HeartbeatMetrics<C>>,
+    pub(crate) snapshot_transfers: SharedArc<BTreeMap<C::NodeId, SnapshotTransferState>>,
+    pub(crate) config_mismatches: SharedArc<BTreeMap<C::NodeId, ConfigDigest>>,
+    pub(crate) peer_capabilities: SharedArc<BTreeMap<C::NodeId, Capabilities>>,
+}
+
+impl<C> Default for SharedMetrics<C>
+where C: RaftTypeConfig
+{
+    fn default() -> Self {
+        Self {
+            replication: SharedArc::default(),
+            replication_targets: SharedArc::default(),
+            heartbeat: SharedArc::default(),
+            snapshot_transfers: SharedArc::default(),
+            config_mismatches: SharedArc::default(),
+            peer_capabilities: SharedArc::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::SharedArc;
+
+    #[test]
+    fn test_shared_arc() {
+        let mut s = SharedArc::<Vec<u64>>::default();
+
+        let a = s.share(vec![1, 2]);
+        let b = s.share(vec![1, 2]);
+        assert!(Arc::ptr_eq(&a, &b), "unchanged value is shared");
+
+        let c = s.share(vec![1, 3]);
+        assert!(!Arc::ptr_eq(&a, &c));
+        assert_eq!(vec![1, 3], *c);
+
+        assert_eq!(None, s.share_opt(None));
+        let d = s.share_opt(Some(vec![1, 3])).unwrap();
+        assert!(Arc::ptr_eq(&c, &d), "shared after a None");
+    }
+}
@@ -2,15 +2,21 @@ use std::fmt;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 
+use display_more::DisplayOptionExt;
+
 use crate::RaftTypeConfig;
+use crate::SnapshotId;
 use crate::base::BoxAsyncOnceMut;
 use crate::engine::SMCommandName;
 use crate::raft::responder::core_responder::CoreResponder;
 use crate::raft_state::IOId;
 use crate::raft_state::io_state::log_io_id::LogIOId;
+use crate::storage::SnapshotLocator;
+use crate::type_config::alias::InstantOf;
 use crate::type_config::alias::LogIdOf;
 use crate::type_config::alias::OneshotSenderOf;
 use crate::type_config::alias::SnapshotDataOf;
+use crate::type_config::alias::SnapshotMetaOf;
 use crate::type_config::alias::SnapshotOf;
 
 /// The payload of a state machine command.
@@ -18,10 +24,20 @@ pub(crate) enum Command<C, SM = ()>
 where C: RaftTypeConfig
 {
     /// Instruct the state machine to create a snapshot based on its most recent view.
-    BuildSnapshot,
+    BuildSnapshot {
+        /// Build a backup snapshot: it can not be refused by the state machine, and no further
+        /// command is run until it is built, so that it reflects exactly the entries applied so
+        /// far.
+        ///
+        /// See [`Raft::backup()`](crate::Raft::backup).
+        backup: bool,
+    },
 
     /// Get the latest built snapshot.
     GetSnapshot {
+        /// If it is `Some`, get the snapshot as a delta relative to this base snapshot if the
+        /// state machine can build one.
+        base: Option<SnapshotId>,
         tx: OneshotSenderOf<C, Option<SnapshotOf<C>>>,
     },
 
@@ -38,6 +54,25 @@ where C: RaftTypeConfig
         snapshot: SnapshotOf<C>,
     },
 
+    /// Get the locator of the current snapshot if it is stored out of band.
+    GetSnapshotLocator {
+        tx: OneshotSenderOf<C, Option<SnapshotLocator<C>>>,
+    },
+
+    /// Fetch a snapshot stored out of band and install it.
+    InstallSnapshotLocator {
+        /// The Log IO id used to update IO progress, the same as `InstallFullSnapshot`.
+        log_io_id: LogIOId<C>,
+        locator: SnapshotLocator<C>,
+    },
+
+    /// Record only the metadata of a snapshot, on a witness.
+    InstallSnapshotMeta {
+        /// The Log IO id used to update IO progress, the same as `InstallFullSnapshot`.
+        log_io_id: LogIOId<C>,
+        meta: SnapshotMetaOf<C>,
+    },
+
     /// Apply the log entries to the state machine.
     Apply {
         /// The first log id to apply, inclusive.
@@ -49,6 +84,17 @@ where C: RaftTypeConfig
         /// Client responders as a vector of (log_index, responder) pairs.
         /// The vector is sorted by log_index in ascending order.
         client_resp_channels: Vec<(u64, CoreResponder<C>)>,
+
+        /// Do not apply before this time, for a time-delayed or throttled replica.
+        ///
+        /// See [`Config::apply_delay`](crate::Config::apply_delay) and
+        /// [`Config::max_apply_rate`](crate::Config::max_apply_rate).
+        not_before: Option<InstantOf<C>>,
+
+        /// The indexes of the first entries of the leaders after `first`, up to the committed
+        /// index, in ascending order. They decide if an entry with a deadline expires, see
+        /// [`RaftEntry::deadline()`](crate::entry::RaftEntry::deadline).
+        leader_starts: Vec<u64>,
     },
 
     /// Apply a typed function to the state machine.
@@ -57,6 +103,16 @@ where C: RaftTypeConfig
     ExternalFunc {
         func: BoxAsyncOnceMut<'static, SM>,
     },
+
+    /// Swap in the warm standby state machine, if there is one, as the primary.
+    PromoteStandby,
+
+    /// Let the state machine remove the snapshots older than `keep_from`.
+    ///
+    /// See [`Config::snapshot_keep_count`](crate::Config::snapshot_keep_count).
+    RemoveSnapshotsBefore {
+        keep_from: SnapshotMetaOf<C>,
+    },
 }
 
 impl<C, SM> Command<C, SM>
@@ -65,21 +121,38 @@ where C: RaftTypeConfig
     #[allow(dead_code)]
     pub(crate) fn name(&self) -> SMCommandName {
         match self {
-            Command::BuildSnapshot => SMCommandName::BuildSnapshot,
+            Command::BuildSnapshot { .. } => SMCommandName::BuildSnapshot,
             Command::GetSnapshot { .. } => SMCommandName::GetSnapshot,
             Command::BeginReceivingSnapshot { .. } => SMCommandName::BeginReceivingSnapshot,
             Command::InstallFullSnapshot { .. } => SMCommandName::InstallFullSnapshot,
             Command::Apply { .. } => SMCommandName::Apply,
             Command::ExternalFunc { .. } => SMCommandName::ExternalFunc,
+            Command::PromoteStandby => SMCommandName::PromoteStandby,
+            Command::GetSnapshotLocator { .. } => SMCommandName::GetSnapshotLocator,
+            Command::InstallSnapshotLocator { .. } => SMCommandName::InstallSnapshotLocator,
+            Command::RemoveSnapshotsBefore { .. } => SMCommandName::RemoveSnapshotsBefore,
+            Command::InstallSnapshotMeta { .. } => SMCommandName::InstallSnapshotMeta,
         }
     }
 
     pub(crate) fn build_snapshot() -> Self {
-        Command::BuildSnapshot
+        Command::BuildSnapshot { backup: false }
+    }
+
+    pub(crate) fn build_backup_snapshot() -> Self {
+        Command::BuildSnapshot { backup: true }
+    }
+
+    pub(crate) fn promote_standby() -> Self {
+        Command::PromoteStandby
     }
 
     pub(crate) fn get_snapshot(tx: OneshotSenderOf<C, Option<SnapshotOf<C>>>) -> Self {
-        Command::GetSnapshot { tx }
+        Command::GetSnapshot { base: None, tx }
+    }
+
+    pub(crate) fn get_delta_snapshot(base: Option<SnapshotId>, tx: OneshotSenderOf<C, Option<SnapshotOf<C>>>) -> Self {
+        Command::GetSnapshot { base, tx }
     }
 
     pub(crate) fn begin_receiving_snapshot(tx: OneshotSenderOf<C, SnapshotDataOf<C>>) -> Self {
@@ -90,6 +163,22 @@ where C: RaftTypeConfig
         Command::InstallFullSnapshot { log_io_id, snapshot }
     }
 
+    pub(crate) fn get_snapshot_locator(tx: OneshotSenderOf<C, Option<SnapshotLocator<C>>>) -> Self {
+        Command::GetSnapshotLocator { tx }
+    }
+
+    pub(crate) fn install_snapshot_locator(locator: SnapshotLocator<C>, log_io_id: LogIOId<C>) -> Self {
+        Command::InstallSnapshotLocator { log_io_id, locator }
+    }
+
+    pub(crate) fn install_snapshot_meta(meta: SnapshotMetaOf<C>, log_io_id: LogIOId<C>) -> Self {
+        Command::InstallSnapshotMeta { log_io_id, meta }
+    }
+
+    pub(crate) fn remove_snapshots_before(keep_from: SnapshotMetaOf<C>) -> Self {
+        Command::RemoveSnapshotsBefore { keep_from }
+    }
+
     /// Applies log ids within the inclusive range `[first, last]`.
     pub(crate) fn apply(
         first: LogIdOf<C>,
@@ -100,7 +189,26 @@ where C: RaftTypeConfig
             first,
             last,
             client_resp_channels,
+            not_before: None,
+            leader_starts: vec![],
+        }
+    }
+
+    /// Delay an `Apply` command until `at`. Other commands are returned unchanged.
+    pub(crate) fn with_not_before(mut self, at: Option<InstantOf<C>>) -> Self {
+        if let Command::Apply { not_before, .. } = &mut self {
+            *not_before = at;
         }
+        self
+    }
+
+    /// Set the first entries of the leaders an `Apply` command needs to decide if an entry
+    /// expires. Other commands are returned unchanged.
+    pub(crate) fn with_leader_starts(mut self, starts: Vec<u64>) -> Self {
+        if let Command::Apply { leader_starts, .. } = &mut self {
+            *leader_starts = starts;
+        }
+        self
     }
 
     /// Return the [`IOId`] of the log-related I/O progress to submit if this command submits any
@@ -109,12 +217,17 @@ where C: RaftTypeConfig
     /// Log-related I/O progress includes both Vote and AppendEntries operations.
     pub(crate) fn get_log_progress(&self) -> Option<IOId<C>> {
         match self {
-            Command::BuildSnapshot => None,
+            Command::BuildSnapshot { .. } => None,
             Command::GetSnapshot { .. } => None,
             Command::BeginReceivingSnapshot { .. } => None,
             Command::InstallFullSnapshot { log_io_id, .. } => Some(IOId::Log(log_io_id.clone())),
             Command::Apply { .. } => None,
             Command::ExternalFunc { .. } => None,
+            Command::PromoteStandby => None,
+            Command::GetSnapshotLocator { .. } => None,
+            Command::InstallSnapshotLocator { log_io_id, .. } => Some(IOId::Log(log_io_id.clone())),
+            Command::InstallSnapshotMeta { log_io_id, .. } => Some(IOId::Log(log_io_id.clone())),
+            Command::RemoveSnapshotsBefore { .. } => None,
         }
     }
 
@@ -125,12 +238,17 @@ where C: RaftTypeConfig
     /// which tracks the highest log id that has been submitted to be applied to the state machine.
     pub(crate) fn get_apply_progress(&self) -> Option<LogIdOf<C>> {
         match self {
-            Command::BuildSnapshot => None,
+            Command::BuildSnapshot { .. } => None,
             Command::GetSnapshot { .. } => None,
             Command::BeginReceivingSnapshot { .. } => None,
             Command::InstallFullSnapshot { log_io_id, .. } => log_io_id.last_log_id().cloned(),
             Command::Apply { last, .. } => Some(last.clone()),
             Command::ExternalFunc { .. } => None,
+            Command::PromoteStandby => None,
+            Command::GetSnapshotLocator { .. } => None,
+            Command::InstallSnapshotLocator { log_io_id, .. } => log_io_id.last_log_id().cloned(),
+            Command::InstallSnapshotMeta { log_io_id, .. } => log_io_id.last_log_id().cloned(),
+            Command::RemoveSnapshotsBefore { .. } => None,
         }
     }
 
@@ -139,16 +257,22 @@ where C: RaftTypeConfig
     /// The caller uses this to update `snapshot_progress.submitted()` in `IOState`,
     /// tracking the highest log id that has been submitted to be included in a persisted snapshot.
     ///
-    /// Only `InstallFullSnapshot` returns the snapshot's last_log_id, as it's the only command
-    /// that directly updates the persisted snapshot state.
+    /// Only `InstallFullSnapshot`, `InstallSnapshotLocator` and `InstallSnapshotMeta` return the
+    /// snapshot's last_log_id, as they are the only commands that directly update the persisted
+    /// snapshot state.
     pub(crate) fn get_snapshot_progress(&self) -> Option<LogIdOf<C>> {
         match self {
-            Command::BuildSnapshot => None,
+            Command::BuildSnapshot { .. } => None,
             Command::GetSnapshot { .. } => None,
             Command::BeginReceivingSnapshot { .. } => None,
             Command::InstallFullSnapshot { snapshot, .. } => snapshot.meta.last_log_id.clone(),
             Command::Apply { .. } => None,
             Command::ExternalFunc { .. } => None,
+            Command::PromoteStandby => None,
+            Command::GetSnapshotLocator { .. } => None,
+            Command::InstallSnapshotLocator { locator, .. } => locator.meta.last_log_id.clone(),
+            Command::InstallSnapshotMeta { meta, .. } => meta.last_log_id.clone(),
+            Command::RemoveSnapshotsBefore { .. } => None,
         }
     }
 }
@@ -158,8 +282,8 @@ where C: RaftTypeConfig
 {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
-            Command::BuildSnapshot => write!(f, "BuildSnapshot"),
-            Command::GetSnapshot { .. } => write!(f, "GetSnapshot"),
+            Command::BuildSnapshot { backup } => write!(f, "BuildSnapshot: backup: {}", backup),
+            Command::GetSnapshot { base, .. } => write!(f, "GetSnapshot: base: {}", base.display()),
             Command::InstallFullSnapshot {
                 log_io_id: io_id,
                 snapshot,
@@ -171,6 +295,21 @@ where C: RaftTypeConfig
             }
             Command::Apply { first, last, .. } => write!(f, "Apply: [{},{}]", first, last),
             Command::ExternalFunc { .. } => write!(f, "ExternalFunc"),
+            Command::PromoteStandby => write!(f, "PromoteStandby"),
+            Command::GetSnapshotLocator { .. } => write!(f, "GetSnapshotLocator"),
+            Command::InstallSnapshotLocator { log_io_id, locator } => {
+                write!(
+                    f,
+                    "InstallSnapshotLocator: locator: {:?}, io_id: {:?}",
+                    locator, log_io_id
+                )
+            }
+            Command::InstallSnapshotMeta { log_io_id, meta } => {
+                write!(f, "InstallSnapshotMeta: meta: {:?}, io_id: {:?}", meta, log_io_id)
+            }
+            Command::RemoveSnapshotsBefore { keep_from } => {
+                write!(f, "RemoveSnapshotsBefore: keep_from: {:?}", keep_from)
+            }
         }
     }
 }
@@ -180,8 +319,8 @@ where C: RaftTypeConfig
 {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
-            Command::BuildSnapshot => write!(f, "BuildSnapshot"),
-            Command::GetSnapshot { .. } => write!(f, "GetSnapshot"),
+            Command::BuildSnapshot { backup } => write!(f, "BuildSnapshot: backup: {}", backup),
+            Command::GetSnapshot { base, .. } => write!(f, "GetSnapshot: base: {}", base.display()),
             Command::InstallFullSnapshot {
                 log_io_id: io_id,
                 snapshot,
@@ -193,6 +332,17 @@ where C: RaftTypeConfig
             }
             Command::Apply { first, last, .. } => write!(f, "Apply: [{},{}]", first, last),
             Command::ExternalFunc { .. } => write!(f, "ExternalFunc"),
+            Command::PromoteStandby => write!(f, "PromoteStandby"),
+            Command::GetSnapshotLocator { .. } => write!(f, "GetSnapshotLocator"),
+            Command::InstallSnapshotLocator { log_io_id, locator } => {
+                write!(f, "InstallSnapshotLocator: locator: {}, io_id: {}", locator, log_io_id)
+            }
+            Command::InstallSnapshotMeta { log_io_id, meta } => {
+                write!(f, "InstallSnapshotMeta: meta: {}, io_id: {}", meta, log_io_id)
+            }
+            Command::RemoveSnapshotsBefore { keep_from } => {
+                write!(f, "RemoveSnapshotsBefore: keep_from: {}", keep_from)
+            }
         }
     }
 }
@@ -203,7 +353,7 @@ where C: RaftTypeConfig
 {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
-            (Command::BuildSnapshot, Command::BuildSnapshot) => true,
+            (Command::BuildSnapshot { backup: b1 }, Command::BuildSnapshot { backup: b2 }) => b1 == b2,
             (Command::GetSnapshot { .. }, Command::GetSnapshot { .. }) => true,
             (Command::BeginReceivingSnapshot { .. }, Command::BeginReceivingSnapshot { .. }) => true,
             (
@@ -225,6 +375,31 @@ where C: RaftTypeConfig
                 },
             ) => first == first2 && last == last2,
             (Command::ExternalFunc { .. }, Command::ExternalFunc { .. }) => false,
+            (Command::PromoteStandby, Command::PromoteStandby) => true,
+            (Command::GetSnapshotLocator { .. }, Command::GetSnapshotLocator { .. }) => true,
+            (
+                Command::InstallSnapshotLocator {
+                    log_io_id: io1,
+                    locator: l1,
+                },
+                Command::InstallSnapshotLocator {
+                    log_io_id: io2,
+                    locator: l2,
+                },
+            ) => l1 == l2 && io1 == io2,
+            (
+                Command::InstallSnapshotMeta {
+                    log_io_id: io1,
+                    meta: m1,
+                },
+                Command::InstallSnapshotMeta {
+                    log_io_id: io2,
+                    meta: m2,
+                },
+            ) => m1 == m2 && io1 == io2,
+            (Command::RemoveSnapshotsBefore { keep_from: k1 }, Command::RemoveSnapshotsBefore { keep_from: k2 }) => {
+                k1 == k2
+            }
             _ => false,
         }
     }
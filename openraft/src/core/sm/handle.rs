@@ -1,10 +1,13 @@
 //! State machine control handle
 
 use crate::RaftTypeConfig;
+use crate::SnapshotId;
 use crate::async_runtime::MpscSender;
 use crate::async_runtime::MpscWeakSender;
 use crate::async_runtime::SendError;
+use crate::base::BoxFuture;
 use crate::core::sm;
+use crate::storage::SnapshotLocator;
 use crate::type_config::TypeConfigExt;
 use crate::type_config::alias::JoinHandleOf;
 use crate::type_config::alias::MpscSenderOf;
@@ -17,8 +20,14 @@ where C: RaftTypeConfig
 {
     pub(in crate::core::sm) cmd_tx: MpscSenderOf<C, sm::Command<C, SM>>,
 
+    /// The worker task that is not spawned yet.
+    ///
+    /// A log-only node has no state machine to run, its worker is spawned when the first command
+    /// is sent to it, e.g., after the node is re-added as a voter with a state machine.
+    pub(in crate::core::sm) pending: Option<BoxFuture<'static, ()>>,
+
     #[allow(dead_code)]
-    pub(in crate::core::sm) join_handle: JoinHandleOf<C, ()>,
+    pub(in crate::core::sm) join_handle: Option<JoinHandleOf<C, ()>>,
 }
 
 impl<C, SM> Handle<C, SM>
@@ -26,9 +35,18 @@ where C: RaftTypeConfig
 {
     pub(crate) async fn send(&mut self, cmd: sm::Command<C, SM>) -> Result<(), SendError<sm::Command<C, SM>>> {
         tracing::debug!("sending command to state machine worker: {:?}", cmd);
+        self.start();
         self.cmd_tx.send(cmd).await
     }
 
+    /// Spawn the worker task if it is not spawned yet.
+    pub(crate) fn start(&mut self) {
+        if let Some(fu) = self.pending.take() {
+            tracing::info!("spawn state machine worker");
+            self.join_handle = Some(C::spawn_io(fu));
+        }
+    }
+
     /// Create a weak sender for direct access to the SM command channel.
     ///
     /// It is weak because the [`Worker`] watches the close event of this channel for shutdown.
@@ -63,12 +81,15 @@ where C: RaftTypeConfig
 {
     /// Get a snapshot from the state machine.
     ///
+    /// If `base` is `Some`, a delta snapshot relative to it is returned if the state machine can
+    /// build one, otherwise the full snapshot.
+    ///
     /// If the state machine worker has shutdown, it will return an error.
     /// If there is no snapshot available, it will return `Ok(None)`.
-    pub(crate) async fn get_snapshot(&self) -> Result<Option<SnapshotOf<C>>, &'static str> {
+    pub(crate) async fn get_snapshot(&self, base: Option<SnapshotId>) -> Result<Option<SnapshotOf<C>>, &'static str> {
         let (tx, rx) = C::oneshot();
 
-        let cmd = sm::Command::get_snapshot(tx);
+        let cmd = sm::Command::get_delta_snapshot(base, tx);
         tracing::debug!("SnapshotReader sending command to sm::Worker: {:?}", cmd);
 
         let Some(cmd_tx) = self.cmd_tx.upgrade() else {
@@ -89,4 +110,28 @@ where C: RaftTypeConfig
 
         Ok(snapshot)
     }
+
+    /// Get the locator of the current snapshot from the state machine, if it is stored out of
+    /// band.
+    ///
+    /// If the state machine worker has shutdown, it will return an error.
+    pub(crate) async fn get_snapshot_locator(&self) -> Result<Option<SnapshotLocator<C>>, &'static str> {
+        let (tx, rx) = C::oneshot();
+
+        let cmd = sm::Command::get_snapshot_locator(tx);
+        tracing::debug!("SnapshotReader sending command to sm::Worker: {:?}", cmd);
+
+        let Some(cmd_tx) = self.cmd_tx.upgrade() else {
+            tracing::info!("failed to upgrade cmd_tx, sm::Worker may have shutdown");
+            return Err("failed to upgrade cmd_tx, sm::Worker may have shutdown");
+        };
+
+        // If fail to send command, cmd is dropped and tx will be dropped.
+        cmd_tx.send(cmd).await.ok();
+
+        rx.await.map_err(|_e| {
+            tracing::error!("failed to receive snapshot locator, sm::Worker may have shutdown");
+            "failed to receive snapshot locator, sm::Worker may have shutdown"
+        })
+    }
 }
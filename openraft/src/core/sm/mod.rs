@@ -7,6 +7,7 @@
 pub(crate) mod command;
 pub(crate) mod handle;
 pub(crate) mod response;
+pub(crate) mod standby;
 pub(crate) mod worker;
 
 pub(crate) use command::Command;
@@ -28,6 +28,9 @@ where C: RaftTypeConfig
 
     /// Send back applied result to RaftCore.
     Apply(ApplyResult<C>),
+
+    /// When finishing warming up the state machine after installing a snapshot.
+    WarmUpDone(SnapshotMetaOf<C>),
 }
 
 impl<C> fmt::Display for Response<C>
@@ -44,6 +47,9 @@ where C: RaftTypeConfig
             Self::Apply(result) => {
                 write!(f, "{}", result)
             }
+            Self::WarmUpDone(meta) => {
+                write!(f, "WarmUpDone({})", meta)
+            }
         }
     }
 }
@@ -0,0 +1,302 @@
+//! Warm standby of the state machine.
+//!
+//! A standby is a second instance of the application state machine, created by
+//! [`RaftStateMachine::create_standby`]. It runs in its own task and is fed the same log entries
+//! as the primary, after the primary has applied them. So that when the leader changes, the
+//! [`Worker`](super::worker::Worker) swaps it in as the primary without rebuilding it from a
+//! snapshot.
+
+use std::io;
+
+use display_more::DisplayOptionExt;
+use futures_util::TryStreamExt;
+use tracing::Instrument;
+
+use crate::LogIdOptionExt;
+use crate::RaftLogReader;
+use crate::RaftTypeConfig;
+use crate::async_runtime::MpscReceiver;
+use crate::async_runtime::MpscSender;
+use crate::async_runtime::OneshotSender;
+use crate::async_runtime::watch::WatchReceiver;
+use crate::async_runtime::watch::WatchSender;
+use crate::entry::RaftEntry;
+use crate::entry::expiry::Expiry;
+use crate::entry::raft_entry_ext::RaftEntryExt;
+use crate::storage::RaftStateMachine;
+use crate::type_config::TypeConfigExt;
+use crate::type_config::alias::JoinHandleOf;
+use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::MpscReceiverOf;
+use crate::type_config::alias::MpscSenderOf;
+use crate::type_config::alias::OneshotSenderOf;
+use crate::type_config::alias::WatchReceiverOf;
+use crate::type_config::alias::WatchSenderOf;
+
+/// A command sent by the primary state machine worker to the standby task.
+pub(crate) enum StandbyCommand<C, SM>
+where
+    C: RaftTypeConfig,
+    SM: RaftStateMachine<C>,
+{
+    /// Apply the log entries within the inclusive range `[first, last]`, applying those in
+    /// `large_payloads` from a payload stream if the log storage provides one.
+    ///
+    /// `expiry` decides if an entry expires, as it does for the primary.
+    Apply {
+        first: LogIdOf<C>,
+        last: LogIdOf<C>,
+        large_payloads: Vec<LogIdOf<C>>,
+        expiry: Expiry,
+    },
+
+    /// Hand over the standby state machine, once all previous commands are done.
+    Take { tx: OneshotSenderOf<C, Option<SM>> },
+
+    /// Replace the standby state machine, e.g., with the former primary after a swap.
+    Reset { state_machine: Option<SM> },
+}
+
+/// Handle to the task running the standby state machine.
+pub(crate) struct Standby<C, SM>
+where
+    C: RaftTypeConfig,
+    SM: RaftStateMachine<C>,
+{
+    cmd_tx: MpscSenderOf<C, StandbyCommand<C, SM>>,
+
+    /// The last log id applied to the standby, `None` if nothing is applied or it is dropped.
+    rx_applied: WatchReceiverOf<C, Option<LogIdOf<C>>>,
+
+    #[allow(dead_code)]
+    join_handle: JoinHandleOf<C, ()>,
+}
+
+impl<C, SM> Standby<C, SM>
+where
+    C: RaftTypeConfig,
+    SM: RaftStateMachine<C>,
+{
+    /// Spawn a task to run the standby `state_machine`, reading logs with `log_reader`.
+    pub(crate) fn spawn<LR>(
+        id: C::NodeId,
+        state_machine: SM,
+        log_reader: LR,
+        channel_size: usize,
+        span: tracing::Span,
+    ) -> Self
+    where
+        LR: RaftLogReader<C>,
+    {
+        let (cmd_tx, cmd_rx) = C::mpsc(channel_size);
+        let (tx_applied, rx_applied) = C::watch_channel(None);
+
+        let task = StandbyTask {
+            id,
+            state_machine: None,
+            log_reader,
+            cmd_rx,
+            tx_applied,
+        };
+
+        let join_handle = C::spawn_io(task.run(state_machine).instrument(span));
+
+        Self {
+            cmd_tx,
+            rx_applied,
+            join_handle,
+        }
+    }
+
+    /// Feed the standby with the log entries the primary has applied.
+    pub(crate) async fn apply(
+        &self,
+        first: LogIdOf<C>,
+        last: LogIdOf<C>,
+        large_payloads: Vec<LogIdOf<C>>,
+        expiry: Expiry,
+    ) {
+        let cmd = StandbyCommand::Apply {
+            first,
+            last,
+            large_payloads,
+            expiry,
+        };
+        self.cmd_tx.send(cmd).await.ok();
+    }
+
+    /// Take the standby state machine, after it has caught up with all the entries fed to it.
+    ///
+    /// Returns `None` if there is no usable standby, e.g., it failed to apply entries.
+    pub(crate) async fn take(&self) -> Option<SM> {
+        let (tx, rx) = C::oneshot();
+        self.cmd_tx.send(StandbyCommand::Take { tx }).await.ok()?;
+        rx.await.ok().flatten()
+    }
+
+    /// Replace the standby state machine.
+    pub(crate) async fn reset(&self, state_machine: Option<SM>) {
+        self.cmd_tx.send(StandbyCommand::Reset { state_machine }).await.ok();
+    }
+
+    /// The number of log entries the standby lags behind the primary, which has applied up to
+    /// `primary_applied`.
+    pub(crate) fn lag(&self, primary_applied: &LogIdOf<C>) -> u64 {
+        let standby_applied = self.rx_applied.borrow_watched().clone();
+        (primary_applied.index() + 1).saturating_sub(standby_applied.next_index())
+    }
+}
+
+struct StandbyTask<C, SM, LR>
+where
+    C: RaftTypeConfig,
+    SM: RaftStateMachine<C>,
+    LR: RaftLogReader<C>,
+{
+    /// The id of this node, to skip the entries out of their apply scope.
+    id: C::NodeId,
+
+    /// The standby state machine; `None` if it is taken or has failed.
+    state_machine: Option<SM>,
+    log_reader: LR,
+    cmd_rx: MpscReceiverOf<C, StandbyCommand<C, SM>>,
+    tx_applied: WatchSenderOf<C, Option<LogIdOf<C>>>,
+}
+
+impl<C, SM, LR> StandbyTask<C, SM, LR>
+where
+    C: RaftTypeConfig,
+    SM: RaftStateMachine<C>,
+    LR: RaftLogReader<C>,
+{
+    async fn run(mut self, state_machine: SM) {
+        self.reset(Some(state_machine)).await;
+
+        while let Some(cmd) = self.cmd_rx.recv().await {
+            match cmd {
+                StandbyCommand::Apply {
+                    first,
+                    last,
+                    large_payloads,
+                    expiry,
+                } => {
+                    self.apply(first, last, large_payloads, expiry).await;
+                }
+                StandbyCommand::Take { tx } => {
+                    self.tx_applied.send(None).ok();
+                    tx.send(self.state_machine.take()).ok();
+                }
+                StandbyCommand::Reset { state_machine } => {
+                    self.reset(state_machine).await;
+                }
+            }
+        }
+
+        tracing::info!("{}: rx closed, standby state machine task quit", func_name!());
+    }
+
+    async fn reset(&mut self, state_machine: Option<SM>) {
+        self.state_machine = state_machine;
+
+        let applied = match &mut self.state_machine {
+            None => None,
+            Some(sm) => match sm.applied_state().await {
+                Ok((applied, _membership)) => applied,
+                Err(e) => {
+                    tracing::error!("standby state machine failed to read applied state: {}, drop it", e);
+                    self.state_machine = None;
+                    None
+                }
+            },
+        };
+
+        tracing::info!("standby state machine is reset, applied: {}", applied.display());
+        self.tx_applied.send(applied).ok();
+    }
+
+    async fn apply(&mut self, first: LogIdOf<C>, last: LogIdOf<C>, large_payloads: Vec<LogIdOf<C>>, expiry: Expiry) {
+        let Some(sm) = &mut self.state_machine else {
+            return;
+        };
+
+        let res = Self::apply_range(
+            sm,
+            &mut self.log_reader,
+            &self.id,
+            first.index(),
+            last.index() + 1,
+            large_payloads,
+            &expiry,
+        )
+        .await;
+
+        if let Err(e) = res {
+            // The primary is not affected: the standby is only an optimization for failover.
+            tracing::error!(
+                "standby state machine failed to apply [{}, {}]: {}, drop it",
+                first,
+                last,
+                e
+            );
+            self.state_machine = None;
+            self.tx_applied.send(None).ok();
+            return;
+        }
+
+        self.tx_applied.send(Some(last)).ok();
+    }
+
+    /// Apply the entries in `[since, end)` to `sm`, in the same way as the primary does.
+    async fn apply_range(
+        sm: &mut SM,
+        log_reader: &mut LR,
+        id: &C::NodeId,
+        since: u64,
+        end: u64,
+        large_payloads: Vec<LogIdOf<C>>,
+        expiry: &Expiry,
+    ) -> Result<(), io::Error> {
+        let mut next = since;
+
+        for log_id in large_payloads {
+            let Some(payload) = log_reader.payload_stream(&log_id).await? else {
+                continue;
+            };
+
+            let index = log_id.index();
+            if next < index {
+                Self::apply_entries(sm, log_reader, id, next, index, expiry).await?;
+            }
+
+            sm.apply_payload_stream(log_id, payload, None).await?;
+            next = index + 1;
+        }
+
+        if next < end {
+            Self::apply_entries(sm, log_reader, id, next, end, expiry).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn apply_entries(
+        sm: &mut SM,
+        log_reader: &mut LR,
+        id: &C::NodeId,
+        since: u64,
+        end: u64,
+        expiry: &Expiry,
+    ) -> Result<(), io::Error> {
+        let strm = log_reader.entries_stream(since..end).await;
+        let id = id.clone();
+        let expiry = expiry.clone();
+        let strm = strm.map_ok(move |entry| {
+            if expiry.expires(&entry) {
+                return (C::Entry::new_blank(entry.log_id()), None);
+            }
+            (entry.scoped_for(&id), None)
+        });
+
+        sm.apply(Box::pin(strm)).await
+    }
+}
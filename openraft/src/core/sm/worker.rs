@@ -1,32 +1,62 @@
+use std::collections::VecDeque;
+#[cfg(feature = "audit-log-chain")]
+use std::io;
+#[cfg(feature = "audit-log-chain")]
+use std::sync::Arc;
+#[cfg(feature = "audit-log-chain")]
+use std::sync::Mutex;
+
 use display_more::DisplayOptionExt;
 use futures_util::TryStreamExt;
+#[cfg(feature = "audit-log-chain")]
+use futures_util::future;
+use itertools::Itertools;
 use tracing::Instrument;
+use tracing::Level;
 
 use crate::RaftLogReader;
 use crate::RaftSnapshotBuilder;
 use crate::RaftTypeConfig;
+use crate::SnapshotId;
 use crate::StorageError;
 use crate::async_runtime::MpscReceiver;
 use crate::async_runtime::OneshotSender;
+use crate::base::BoxFuture;
 use crate::core::ApplyResult;
 use crate::core::notification::Notification;
 use crate::core::sm::Command;
 use crate::core::sm::CommandResult;
 use crate::core::sm::Response;
 use crate::core::sm::handle::Handle;
+use crate::core::sm::standby::Standby;
+#[cfg(feature = "audit-log-chain")]
+use crate::entry::ChainHash;
+use crate::entry::EntryKind;
 use crate::entry::RaftEntry;
+use crate::entry::expiry::Expiry;
+#[cfg(feature = "audit-log-chain")]
+use crate::entry::log_chain::follow_log_chain;
+use crate::entry::raft_entry_ext::RaftEntryExt;
+use crate::errors::ClientWriteError;
 use crate::errors::StorageIOResult;
+use crate::errors::WriteExpired;
+use crate::raft::responder::Responder;
 use crate::raft::responder::core_responder::CoreResponder;
+use crate::storage::ApplyResponder;
+use crate::storage::PayloadStream;
 #[cfg(doc)]
 use crate::storage::RaftLogStorage;
 use crate::storage::RaftStateMachine;
+use crate::storage::SnapshotFetched;
+use crate::storage::v2::applied_result_cache::AppliedResultCache;
+use crate::storage::v2::apply_responder_inner::ApplyResponderInner;
 use crate::storage::v2::entry_responder::EntryResponderBuilder;
 use crate::type_config::TypeConfigExt;
-use crate::type_config::alias::JoinHandleOf;
 use crate::type_config::alias::LogIdOf;
 use crate::type_config::alias::MpscReceiverOf;
 use crate::type_config::alias::MpscSenderOf;
 use crate::type_config::alias::OneshotSenderOf;
+use crate::type_config::alias::SnapshotMetaOf;
 use crate::type_config::alias::SnapshotOf;
 use crate::type_config::async_runtime::mpsc::MpscSender;
 
@@ -36,6 +66,9 @@ where
     SM: RaftStateMachine<C>,
     LR: RaftLogReader<C>,
 {
+    /// The id of this node, to skip the entries out of their apply scope.
+    id: C::NodeId,
+
     /// The application state machine implementation.
     state_machine: SM,
 
@@ -47,6 +80,38 @@ where
 
     /// Send back the result of the command to RaftCore.
     resp_tx: MpscSenderOf<C, Notification<C>>,
+
+    /// The log reader for the standby, until the standby is created.
+    standby_log_reader: Option<LR>,
+
+    /// The warm standby state machine, if the state machine provides one.
+    standby: Option<Standby<C, SM>>,
+
+    state_machine_channel_size: usize,
+
+    /// Whether to warm up the state machine after installing a snapshot.
+    warm_up_after_install: bool,
+
+    /// Retains the responses the state machine sends with `send_and_cache()`.
+    applied_result_cache: AppliedResultCache<C>,
+
+    /// Whether to verify the chain hash of each entry before applying it.
+    audit_log_chain: bool,
+
+    /// The index and chain hash of the last applied entry, to verify the next entries with.
+    #[cfg(feature = "audit-log-chain")]
+    log_chain_tail: Option<(u64, Option<ChainHash>)>,
+
+    /// The size of an application entry above which it is applied from a payload stream.
+    stream_payload_threshold: Option<u64>,
+
+    /// The commands received after an `Apply` command that is not due yet, see
+    /// [`Self::recv_command()`].
+    delayed: VecDeque<Command<C, SM>>,
+
+    /// The number of commands at the front of `delayed` to run without waiting: those before a
+    /// snapshot to install.
+    released: usize,
 }
 
 impl<C, SM, LR> Worker<C, SM, LR>
@@ -56,28 +121,58 @@ where
     LR: RaftLogReader<C>,
 {
     /// Spawn a new state machine worker, return a controlling handle.
+    ///
+    /// If `start` is false, the worker task is not spawned until the first command is sent to it
+    /// through the handle: a log-only node does not run a state machine.
     pub(crate) fn spawn(
+        id: C::NodeId,
         state_machine: SM,
         log_reader: LR,
+        standby_log_reader: LR,
         resp_tx: MpscSenderOf<C, Notification<C>>,
         state_machine_channel_size: usize,
+        warm_up_after_install: bool,
+        audit_log_chain: bool,
+        stream_payload_threshold: Option<u64>,
+        applied_result_cache: AppliedResultCache<C>,
+        start: bool,
         span: tracing::Span,
     ) -> Handle<C, SM> {
         let (cmd_tx, cmd_rx) = C::mpsc(state_machine_channel_size);
 
         let worker = Worker {
+            id,
             state_machine,
             log_reader,
             cmd_rx,
             resp_tx,
+            standby_log_reader: Some(standby_log_reader),
+            standby: None,
+            state_machine_channel_size,
+            warm_up_after_install,
+            applied_result_cache,
+            audit_log_chain,
+            #[cfg(feature = "audit-log-chain")]
+            log_chain_tail: None,
+            stream_payload_threshold,
+            delayed: VecDeque::new(),
+            released: 0,
+        };
+
+        let mut handle = Handle {
+            cmd_tx,
+            pending: Some(worker.into_task(span)),
+            join_handle: None,
         };
 
-        let join_handle = worker.do_spawn(span);
+        if start {
+            handle.start();
+        }
 
-        Handle { cmd_tx, join_handle }
+        handle
     }
 
-    fn do_spawn(mut self, span: tracing::Span) -> JoinHandleOf<C, ()> {
+    fn into_task(mut self, span: tracing::Span) -> BoxFuture<'static, ()> {
         let fu = async move {
             let res = self.worker_loop().await;
 
@@ -92,13 +187,15 @@ where
                     .ok();
             }
         };
-        C::spawn(fu.instrument(span))
+        Box::pin(fu.instrument(span))
     }
 
     #[tracing::instrument(level = "debug", skip_all)]
     async fn worker_loop(&mut self) -> Result<(), StorageError<C>> {
+        self.init_standby().await;
+
         loop {
-            let cmd = self.cmd_rx.recv().await;
+            let cmd = self.recv_command().await;
             let cmd = match cmd {
                 None => {
                     tracing::info!("{}: rx closed, state machine worker quit", func_name!());
@@ -110,16 +207,20 @@ where
             tracing::debug!("{}: received command: {:?}", func_name!(), cmd);
 
             match cmd {
-                Command::BuildSnapshot => {
-                    tracing::info!("{}: build snapshot", func_name!());
-
-                    // It is a read operation and is spawned, and it responds in another task
-                    self.build_snapshot(self.resp_tx.clone()).await;
+                Command::BuildSnapshot { backup } => {
+                    tracing::info!("{}: build snapshot, backup: {}", func_name!(), backup);
+
+                    if backup {
+                        self.build_backup_snapshot().await;
+                    } else {
+                        // It is a read operation and is spawned, and it responds in another task
+                        self.build_snapshot(self.resp_tx.clone()).await;
+                    }
                 }
-                Command::GetSnapshot { tx } => {
-                    tracing::info!("{}: get snapshot", func_name!());
+                Command::GetSnapshot { base, tx } => {
+                    tracing::info!("{}: get snapshot, base: {}", func_name!(), base.display());
 
-                    self.get_snapshot(tx).await?;
+                    self.get_snapshot(base, tx).await?;
                     // GetSnapshot does not respond to RaftCore
                 }
                 Command::InstallFullSnapshot {
@@ -136,7 +237,62 @@ where
 
                     tracing::info!("Done install complete snapshot, meta: {}", meta);
 
-                    let res = CommandResult::new(Ok(Response::InstallSnapshot((io_id, Some(meta)))));
+                    self.reset_standby().await;
+                    #[cfg(feature = "audit-log-chain")]
+                    self.log_chain_tail = None;
+
+                    let res = CommandResult::new(Ok(Response::InstallSnapshot((io_id, Some(meta.clone())))));
+                    self.resp_tx.send(Notification::sm(res)).await.ok();
+
+                    self.warm_up(meta).await;
+                }
+                Command::GetSnapshotLocator { tx } => {
+                    tracing::info!("{}: get snapshot locator", func_name!());
+
+                    let locator = self.state_machine.get_snapshot_locator().await.sto_read_snapshot(None)?;
+                    tx.send(locator).ok();
+                    // GetSnapshotLocator does not respond to RaftCore
+                }
+                Command::InstallSnapshotLocator { log_io_id, locator } => {
+                    tracing::info!("{}: install snapshot from locator: {}", func_name!(), locator);
+
+                    let meta = locator.meta.clone();
+                    let (tx, rx) = C::oneshot();
+
+                    self.state_machine
+                        .install_snapshot_locator(&locator, SnapshotFetched::new(meta.clone(), tx))
+                        .await
+                        .sto_write_snapshot(Some(meta.signature()))?;
+
+                    // The state machine may fetch the snapshot in the background; wait for it.
+                    let fetched = match rx.await {
+                        Ok(x) => x,
+                        Err(_e) => Err(std::io::Error::other("SnapshotFetched is dropped without completion")),
+                    };
+                    fetched.sto_write_snapshot(Some(meta.signature()))?;
+
+                    tracing::info!("Done install snapshot from locator, meta: {}", meta);
+
+                    self.reset_standby().await;
+                    #[cfg(feature = "audit-log-chain")]
+                    self.log_chain_tail = None;
+
+                    let res = CommandResult::new(Ok(Response::InstallSnapshot((log_io_id, Some(meta.clone())))));
+                    self.resp_tx.send(Notification::sm(res)).await.ok();
+
+                    self.warm_up(meta).await;
+                }
+                Command::InstallSnapshotMeta { log_io_id, meta } => {
+                    tracing::info!("{}: install snapshot meta: {}", func_name!(), meta);
+
+                    self.state_machine.install_snapshot_meta(&meta).await.sto_write_snapshot(Some(meta.signature()))?;
+
+                    tracing::info!("Done install snapshot meta: {}", meta);
+
+                    #[cfg(feature = "audit-log-chain")]
+                    self.log_chain_tail = None;
+
+                    let res = CommandResult::new(Ok(Response::InstallSnapshot((log_io_id, Some(meta)))));
                     self.resp_tx.send(Notification::sm(res)).await.ok();
                 }
                 Command::BeginReceivingSnapshot { tx } => {
@@ -151,8 +307,30 @@ where
                     first,
                     last,
                     client_resp_channels,
+                    not_before: _,
+                    leader_starts,
                 } => {
-                    let resp = self.apply(first, last, client_resp_channels).await?;
+                    let expiry = Expiry::read(&mut self.log_reader, &leader_starts).await?;
+
+                    // An entry that may expire is applied from the log entry, not a payload stream.
+                    let mut large_payloads = self.large_payloads(first.index(), last.index() + 1).await?;
+                    large_payloads.retain(|log_id| !expiry.may_expire(log_id.index()));
+
+                    let mut resp = self
+                        .apply(
+                            first.clone(),
+                            last.clone(),
+                            client_resp_channels,
+                            large_payloads.clone(),
+                            &expiry,
+                        )
+                        .await?;
+
+                    if let Some(standby) = &self.standby {
+                        standby.apply(first, last, large_payloads, expiry).await;
+                        resp.standby_lag = Some(standby.lag(&resp.last_applied));
+                    }
+
                     let res = CommandResult::new(Ok(Response::Apply(resp)));
                     self.resp_tx.send(Notification::sm(res)).await.ok();
                 }
@@ -160,19 +338,180 @@ where
                     tracing::debug!("{}: run user defined ExternalFunc", func_name!());
                     func(&mut self.state_machine).await;
                 }
+                Command::PromoteStandby => {
+                    self.promote_standby().await;
+                }
+                Command::RemoveSnapshotsBefore { keep_from } => {
+                    tracing::info!("{}: remove snapshots before: {}", func_name!(), keep_from);
+
+                    // Removing an old snapshot is best effort: a failure only wastes space.
+                    if let Err(e) = self.state_machine.remove_snapshots_before(&keep_from).await {
+                        tracing::warn!("failed to remove snapshots before {}: {}", keep_from, e);
+                    }
+                    // RemoveSnapshotsBefore does not respond to RaftCore
+                }
             };
         }
     }
+
+    /// Receive the next command to run, or `None` if `RaftCore` closed the channel.
+    ///
+    /// An `Apply` command with a `not_before` in the future is held in `delayed` until then, along
+    /// with every command after it, so that they stay in order. The commands that do not depend on
+    /// the applied state, i.e., getting a snapshot or receiving one, are run at once. A snapshot
+    /// to install does not wait either: the commands before it are released at once, since the
+    /// snapshot replaces the state they build anyway.
+    async fn recv_command(&mut self) -> Option<Command<C, SM>> {
+        loop {
+            if self.released > 0 {
+                self.released -= 1;
+                return self.delayed.pop_front();
+            }
+
+            let not_before = match self.delayed.front() {
+                None => None,
+                Some(Command::Apply {
+                    not_before: Some(at), ..
+                }) if *at > C::now() => Some(*at),
+                Some(_) => return self.delayed.pop_front(),
+            };
+
+            let cmd = match not_before {
+                None => self.cmd_rx.recv().await?,
+                Some(at) => match C::timeout_at(at, self.cmd_rx.recv()).await {
+                    Ok(cmd) => cmd?,
+                    // The first delayed command is due.
+                    Err(_) => continue,
+                },
+            };
+
+            match cmd {
+                Command::GetSnapshot { .. }
+                | Command::GetSnapshotLocator { .. }
+                | Command::BeginReceivingSnapshot { .. } => {
+                    return Some(cmd);
+                }
+                Command::InstallFullSnapshot { .. }
+                | Command::InstallSnapshotLocator { .. }
+                | Command::InstallSnapshotMeta { .. } => {
+                    if self.delayed.is_empty() {
+                        return Some(cmd);
+                    }
+                    tracing::info!(
+                        "{}: install a snapshot, release {} delayed commands before it",
+                        func_name!(),
+                        self.delayed.len()
+                    );
+                    self.delayed.push_back(cmd);
+                    self.released = self.delayed.len();
+                }
+                Command::Apply {
+                    not_before: Some(ref at),
+                    ..
+                } if *at > C::now() => {
+                    self.delayed.push_back(cmd);
+                }
+                _ => {
+                    if self.delayed.is_empty() {
+                        return Some(cmd);
+                    }
+                    self.delayed.push_back(cmd);
+                }
+            }
+        }
+    }
+
+    /// Returns the log ids of the application entries in `[since, end)` large enough to be applied
+    /// from a payload stream, see [`Config::stream_payload_threshold`].
+    ///
+    /// [`Config::stream_payload_threshold`]: crate::Config::stream_payload_threshold
+    async fn large_payloads(&mut self, since: u64, end: u64) -> Result<Vec<LogIdOf<C>>, StorageError<C>> {
+        // Verifying the chain hash of an entry requires the decoded entry.
+        let Some(threshold) = self.stream_payload_threshold.filter(|_| !self.audit_log_chain) else {
+            return Ok(vec![]);
+        };
+
+        let metas = self.log_reader.get_log_meta(since..=end - 1).await.sto_read_logs()?;
+
+        let log_ids = metas
+            .into_iter()
+            .filter(|m| m.kind == EntryKind::Normal && m.size.is_some_and(|size| size >= threshold))
+            .map(|m| m.log_id)
+            .collect();
+
+        Ok(log_ids)
+    }
+
+    /// Apply the entries in `[first, last]`; those in `large_payloads` are applied from a payload
+    /// stream if the log storage provides one.
+    ///
+    /// An entry that `expiry` decides expires is applied as a blank entry, see
+    /// [`RaftEntry::deadline()`].
     #[tracing::instrument(level = "debug", skip_all)]
     async fn apply(
         &mut self,
         first: LogIdOf<C>,
         last: LogIdOf<C>,
         client_resp_channels: Vec<(u64, CoreResponder<C>)>,
+        large_payloads: Vec<LogIdOf<C>>,
+        expiry: &Expiry,
     ) -> Result<ApplyResult<C>, StorageError<C>> {
         let since = first.index();
         let end = last.index() + 1;
 
+        let mut responders = client_resp_channels.into_iter().peekable();
+        let mut next = since;
+
+        for log_id in large_payloads {
+            let Some(payload) = self.log_reader.payload_stream(&log_id).await.sto_read_log_entry(log_id.clone())?
+            else {
+                continue;
+            };
+
+            let index = log_id.index();
+            if next < index {
+                let before = responders.peeking_take_while(|(idx, _)| *idx < index).collect();
+                self.apply_entries(next, index, &last, before, expiry).await?;
+            }
+
+            let responder = responders.next_if(|(idx, _)| *idx == index).map(|(_, r)| r);
+            self.apply_payload_stream(log_id, payload, responder).await?;
+            next = index + 1;
+        }
+
+        if next < end {
+            self.apply_entries(next, end, &last, responders.collect(), expiry).await?;
+        }
+
+        let durable_applied = self.state_machine.durable_applied().await.sto_read_sm()?;
+        // A state machine must not report a log id it has not applied.
+        let durable_applied = durable_applied.filter(|x| x <= &last);
+
+        let resp = ApplyResult {
+            since,
+            end,
+            last_applied: last,
+            durable_applied,
+            standby_lag: None,
+        };
+
+        Ok(resp)
+    }
+
+    /// Apply the entries in `[since, end)` read with [`RaftLogReader::entries_stream`].
+    ///
+    /// An entry that `expiry` decides expires is applied as a blank entry, and its responder is
+    /// completed with [`ClientWriteError::WriteExpired`].
+    ///
+    /// `last` is the last log id of the whole apply command, to report an error with.
+    async fn apply_entries(
+        &mut self,
+        since: u64,
+        end: u64,
+        last: &LogIdOf<C>,
+        client_resp_channels: Vec<(u64, CoreResponder<C>)>,
+        expiry: &Expiry,
+    ) -> Result<(), StorageError<C>> {
         #[cfg(debug_assertions)]
         let (got_last_index, last_apply) = {
             let l = std::sync::Arc::new(std::sync::atomic::AtomicU64::new(0));
@@ -181,8 +520,46 @@ where
 
         let strm = self.log_reader.entries_stream(since..end).await;
 
+        // The chain hash of the last verified entry. The first entry is not verified if the entry
+        // before it is not applied by this worker, e.g., it is in a snapshot.
+        #[cfg(feature = "audit-log-chain")]
+        let chain_tail = self.audit_log_chain.then(|| {
+            let prev = match self.log_chain_tail {
+                Some((index, hash)) if index + 1 == since => hash,
+                _ => None,
+            };
+            Arc::new(Mutex::new(prev))
+        });
+
+        #[cfg(feature = "audit-log-chain")]
+        let strm = {
+            let chain_tail = chain_tail.clone();
+            strm.and_then(move |entry| {
+                let res = match &chain_tail {
+                    None => Ok(entry),
+                    Some(tail) => {
+                        let mut prev = tail.lock().unwrap();
+                        match follow_log_chain::<C>(*prev, &entry) {
+                            Ok(hash) => {
+                                *prev = hash;
+                                Ok(entry)
+                            }
+                            Err(e) => {
+                                tracing::error!("{}", e);
+                                Err(io::Error::new(io::ErrorKind::InvalidData, e.to_string()))
+                            }
+                        }
+                    }
+                };
+                future::ready(res)
+            })
+        };
+
         // Convert Vec to an iterator for efficient matching
         let mut responder_iter = client_resp_channels.into_iter().peekable();
+        let applied_result_cache = self.applied_result_cache.clone();
+        let id = self.id.clone();
+        let expiry = expiry.clone();
 
         // Prepare entries with responders upfront.
         let strm = strm.map_ok(move |entry| {
@@ -195,7 +572,31 @@ where
                 None
             };
 
-            let item = EntryResponderBuilder { entry, responder };
+            if expiry.expires(&entry) {
+                let log_id = entry.log_id();
+                tracing::info!(
+                    "{} is not committed before its deadline, apply it as a blank entry",
+                    log_id
+                );
+
+                if let Some(responder) = responder {
+                    responder.on_complete(Err(ClientWriteError::WriteExpired(WriteExpired {
+                        log_id: log_id.clone(),
+                    })));
+                }
+
+                #[cfg(debug_assertions)]
+                last_apply.store(log_index, std::sync::atomic::Ordering::Relaxed);
+
+                return (C::Entry::new_blank(log_id), None);
+            }
+
+            let item = EntryResponderBuilder {
+                // An entry out of its apply scope is applied as a blank entry.
+                entry: entry.scoped_for(&id),
+                responder,
+                applied_result_cache: applied_result_cache.clone(),
+            };
 
             #[cfg(debug_assertions)]
             last_apply.store(log_index, std::sync::atomic::Ordering::Relaxed);
@@ -209,18 +610,115 @@ where
 
         self.state_machine.apply(Box::pin(strm)).await.sto_apply(last.clone())?;
 
+        #[cfg(feature = "audit-log-chain")]
+        if let Some(tail) = chain_tail {
+            self.log_chain_tail = Some((end - 1, *tail.lock().unwrap()));
+        }
+
         #[cfg(debug_assertions)]
         {
             assert_eq!(end - 1, got_last_index.load(std::sync::atomic::Ordering::Relaxed));
         }
 
-        let resp = ApplyResult {
-            since,
-            end,
-            last_applied: last,
+        Ok(())
+    }
+
+    /// Apply the entry `log_id` from the stream of its payload.
+    async fn apply_payload_stream(
+        &mut self,
+        log_id: LogIdOf<C>,
+        payload: PayloadStream,
+        responder: Option<CoreResponder<C>>,
+    ) -> Result<(), StorageError<C>> {
+        tracing::debug!("Applying entry to state machine from a payload stream: {}", log_id);
+
+        let responder = responder.map(|responder| ApplyResponder {
+            inner: ApplyResponderInner::Normal {
+                log_id: log_id.clone(),
+                responder,
+            },
+            applied_result_cache: self.applied_result_cache.clone(),
+        });
+
+        self.state_machine
+            .apply_payload_stream(log_id.clone(), payload, responder)
+            .await
+            .sto_apply(log_id)?;
+        Ok(())
+    }
+
+    /// Create the standby state machine and start feeding it, if the state machine provides one.
+    async fn init_standby(&mut self) {
+        let Some(log_reader) = self.standby_log_reader.take() else {
+            return;
         };
 
-        Ok(resp)
+        let Some(standby) = self.state_machine.create_standby().await else {
+            return;
+        };
+
+        tracing::info!("{}: state machine standby is created", func_name!());
+
+        let span = tracing::span!(parent: tracing::Span::current(), Level::DEBUG, "sm_standby");
+        self.standby = Some(Standby::spawn(
+            self.id.clone(),
+            standby,
+            log_reader,
+            self.state_machine_channel_size,
+            span,
+        ));
+    }
+
+    /// Re-create the standby after the primary is replaced by a snapshot, which the standby has not
+    /// seen.
+    async fn reset_standby(&mut self) {
+        let Some(standby) = &self.standby else {
+            return;
+        };
+
+        let state_machine = self.state_machine.create_standby().await;
+        standby.reset(state_machine).await;
+    }
+
+    /// Swap the standby in as the primary state machine; the former primary becomes the standby.
+    ///
+    /// The standby is taken only after it has applied every entry fed to it, i.e., it is at the
+    /// same state as the primary.
+    #[tracing::instrument(level = "info", skip_all)]
+    async fn promote_standby(&mut self) {
+        let Some(standby) = &self.standby else {
+            return;
+        };
+
+        let Some(mut state_machine) = standby.take().await else {
+            tracing::warn!("{}: no usable standby state machine to promote", func_name!());
+            return;
+        };
+
+        std::mem::swap(&mut self.state_machine, &mut state_machine);
+        standby.reset(Some(state_machine)).await;
+
+        tracing::info!("{}: standby state machine is promoted to primary", func_name!());
+    }
+
+    /// Warm up the state machine after installing the snapshot `meta`, if it is enabled.
+    async fn warm_up(&mut self, meta: SnapshotMetaOf<C>) {
+        if !self.warm_up_after_install {
+            return;
+        }
+
+        tracing::info!(
+            "{}: warm up state machine after installing snapshot: {}",
+            func_name!(),
+            meta
+        );
+
+        if let Err(e) = self.state_machine.warm_up(&meta).await {
+            tracing::warn!("failed to warm up state machine, ignored: {}", e);
+        }
+
+        let res = CommandResult::new(Ok(Response::WarmUpDone(meta)));
+        self.resp_tx.send(Notification::sm(res)).await.ok();
     }
 
     /// Build a snapshot by requesting a builder from the state machine.
@@ -250,7 +748,7 @@ where
             return;
         };
 
-        let _handle = C::spawn(async move {
+        let _handle = C::spawn_io(async move {
             let res = builder.build_snapshot().await.sto_write_snapshot(None);
             let res = res.map(|snap| Response::BuildSnapshotDone(Some(snap.meta)));
             let cmd_res = CommandResult::new(res);
@@ -259,10 +757,60 @@ where
         tracing::info!("{}: returning; spawned building snapshot task", func_name!());
     }
 
+    /// Build a backup snapshot in place.
+    ///
+    /// Unlike [`Self::build_snapshot()`], the builder is created with
+    /// [`try_create_snapshot_builder(true)`](`RaftStateMachine::try_create_snapshot_builder`) and
+    /// the snapshot is built before the next command is handled. Thus the snapshot reflects
+    /// exactly the entries applied so far, i.e., up to the backup barrier, no matter whether
+    /// the builder holds a consistent view of the state machine.
     #[tracing::instrument(level = "info", skip_all)]
-    async fn get_snapshot(&mut self, tx: OneshotSenderOf<C, Option<SnapshotOf<C>>>) -> Result<(), StorageError<C>> {
+    async fn build_backup_snapshot(&mut self) {
+        let builder = self.state_machine.try_create_snapshot_builder(true).await;
+
+        let res = match builder {
+            Some(mut builder) => {
+                let res = builder.build_snapshot().await.sto_write_snapshot(None);
+                res.map(|snap| Response::BuildSnapshotDone(Some(snap.meta)))
+            }
+            None => {
+                tracing::warn!("{}: state machine returned no builder for backup", func_name!());
+                Ok(Response::BuildSnapshotDone(None))
+            }
+        };
+
+        let cmd_res = CommandResult::new(res);
+        self.resp_tx.send(Notification::sm(cmd_res)).await.ok();
+    }
+
+    #[tracing::instrument(level = "info", skip_all)]
+    async fn get_snapshot(
+        &mut self,
+        base: Option<SnapshotId>,
+        tx: OneshotSenderOf<C, Option<SnapshotOf<C>>>,
+    ) -> Result<(), StorageError<C>> {
         tracing::info!("{}", func_name!());
 
+        if let Some(base) = base {
+            let delta = self.state_machine.get_delta_snapshot(&base).await.sto_read_snapshot(None)?;
+
+            match delta {
+                Some(delta) if delta.meta.base_snapshot_id.as_ref() == Some(&base) => {
+                    tracing::info!("sending back delta snapshot: meta: {}", delta.meta);
+                    tx.send(Some(delta)).ok();
+                    return Ok(());
+                }
+                Some(delta) => {
+                    tracing::warn!(
+                        "delta snapshot is not relative to base: {}, meta: {}; send the full snapshot",
+                        base,
+                        delta.meta
+                    );
+                }
+                None => {}
+            }
+        }
+
         let snapshot = self.state_machine.get_current_snapshot().await.sto_read_snapshot(None)?;
 
         tracing::info!(
@@ -0,0 +1,142 @@
+//! Defers policy-triggered snapshot builds while the node is busy.
+
+use std::time::Duration;
+
+use crate::RaftTypeConfig;
+use crate::type_config::alias::InstantOf;
+
+/// The interval over which the write rate is measured.
+const RATE_SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Tracks the write load of this node, and for how long a snapshot build has been deferred
+/// because of it.
+///
+/// Building a snapshot competes with writes and applying for disk and CPU. So when the
+/// [`SnapshotPolicy`](crate::SnapshotPolicy) asks for a snapshot while the node is busy, the build
+/// is postponed to a quieter moment, but no longer than a maximum deferral, so that the log does
+/// not grow without bound under a sustained load.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct SnapshotDeferral<C>
+where C: RaftTypeConfig
+{
+    /// When the current write rate sample started, and the last log index at that time.
+    sample_start: Option<(InstantOf<C>, u64)>,
+
+    /// The number of log entries appended per second, measured over the last complete sample.
+    write_rate: u64,
+
+    /// Since when a snapshot build has been deferred, `None` if it is not deferred.
+    deferred_since: Option<InstantOf<C>>,
+}
+
+impl<C> SnapshotDeferral<C>
+where C: RaftTypeConfig
+{
+    /// Record that the log has `next_index` entries at `now`, to measure the write rate.
+    pub(crate) fn sample(&mut self, now: InstantOf<C>, next_index: u64) {
+        let Some((start, start_index)) = self.sample_start else {
+            self.sample_start = Some((now, next_index));
+            return;
+        };
+
+        let elapsed = now - start;
+        if elapsed < RATE_SAMPLE_INTERVAL {
+            return;
+        }
+
+        let appended = next_index.saturating_sub(start_index);
+        self.write_rate = (appended as u128 * 1000 / elapsed.as_millis()) as u64;
+        self.sample_start = Some((now, next_index));
+    }
+
+    /// The number of log entries appended per second.
+    pub(crate) fn write_rate(&self) -> u64 {
+        self.write_rate
+    }
+
+    /// Decide whether a snapshot build that is due at `now` should be deferred.
+    ///
+    /// It is deferred while `busy`, until it has been deferred for `max_defer`.
+    pub(crate) fn should_defer(&mut self, now: InstantOf<C>, busy: bool, max_defer: Duration) -> bool {
+        if !busy {
+            self.deferred_since = None;
+            return false;
+        }
+
+        let since = *self.deferred_since.get_or_insert(now);
+        if now >= since + max_defer {
+            self.deferred_since = None;
+            return false;
+        }
+        true
+    }
+
+    /// Forget the deferral, because no snapshot build is due anymore.
+    pub(crate) fn cancel(&mut self) {
+        self.deferred_since = None;
+    }
+
+    /// Since when a snapshot build has been deferred, `None` if it is not deferred.
+    pub(crate) fn deferred_since(&self) -> Option<InstantOf<C>> {
+        self.deferred_since
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::engine::testing::UTConfig;
+    use crate::type_config::TypeConfigExt;
+
+    type SnapshotDeferral = super::SnapshotDeferral<UTConfig>;
+
+    #[test]
+    fn test_snapshot_deferral_write_rate() {
+        let now = UTConfig::<()>::now();
+
+        let mut d = SnapshotDeferral::default();
+        d.sample(now, 10);
+        assert_eq!(0, d.write_rate());
+
+        d.sample(now + Duration::from_millis(500), 100);
+        assert_eq!(0, d.write_rate(), "sample is not complete");
+
+        d.sample(now + Duration::from_secs(2), 1010);
+        assert_eq!(500, d.write_rate());
+
+        d.sample(now + Duration::from_secs(3), 1010);
+        assert_eq!(0, d.write_rate());
+    }
+
+    #[test]
+    fn test_snapshot_deferral_max_defer() {
+        let max = Duration::from_secs(10);
+        let now = UTConfig::<()>::now();
+
+        let mut d = SnapshotDeferral::default();
+        assert!(!d.should_defer(now, false, max));
+        assert_eq!(None, d.deferred_since());
+
+        assert!(d.should_defer(now, true, max));
+        assert!(d.should_defer(now + Duration::from_secs(5), true, max));
+        assert_eq!(Some(now), d.deferred_since(), "deferred since the first busy check");
+
+        assert!(
+            !d.should_defer(now + Duration::from_secs(10), true, max),
+            "max deferral reached"
+        );
+        assert_eq!(None, d.deferred_since());
+
+        assert!(d.should_defer(now + Duration::from_secs(11), true, max));
+        assert!(
+            !d.should_defer(now + Duration::from_secs(12), false, max),
+            "no longer busy"
+        );
+        assert_eq!(None, d.deferred_since());
+
+        d.should_defer(now, true, max);
+        d.cancel();
+        assert_eq!(None, d.deferred_since());
+    }
+}
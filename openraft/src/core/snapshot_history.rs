@@ -0,0 +1,131 @@
+//! The meta of the most recent snapshots, readable without calling `RaftCore`.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use crate::RaftTypeConfig;
+use crate::type_config::alias::SnapshotMetaOf;
+
+/// The meta of the snapshots this node built or installed, oldest first.
+///
+/// It is shared between `RaftCore`, which records every new snapshot, and
+/// [`Raft::snapshot_history()`](crate::Raft::snapshot_history).
+///
+/// With a `keep_count`, at most that many are tracked and recording a new one drops the oldest.
+/// Without it, only the current snapshot is tracked and nothing is reported as dropped.
+pub(crate) struct SnapshotHistory<C>
+where C: RaftTypeConfig
+{
+    keep_count: Option<usize>,
+    inner: Arc<Mutex<VecDeque<SnapshotMetaOf<C>>>>,
+}
+
+impl<C> Clone for SnapshotHistory<C>
+where C: RaftTypeConfig
+{
+    fn clone(&self) -> Self {
+        Self {
+            keep_count: self.keep_count,
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<C> SnapshotHistory<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new(keep_count: Option<usize>, meta: &SnapshotMetaOf<C>) -> Self {
+        let s = Self {
+            keep_count,
+            inner: Arc::new(Mutex::new(VecDeque::new())),
+        };
+        s.push(meta);
+        s
+    }
+
+    /// Record a snapshot, if it is newer than the last recorded one.
+    ///
+    /// Returns the oldest snapshot still kept if any older one is dropped, so that the caller can
+    /// tell the state machine to remove the snapshots before it.
+    pub(crate) fn push(&self, meta: &SnapshotMetaOf<C>) -> Option<SnapshotMetaOf<C>> {
+        if meta.last_log_id.is_none() {
+            return None;
+        }
+
+        let mut history = self.inner.lock().unwrap();
+
+        if let Some(last) = history.back() {
+            if last.snapshot_id == meta.snapshot_id || last.last_log_id >= meta.last_log_id {
+                return None;
+            }
+        }
+
+        history.push_back(meta.clone());
+
+        let Some(keep_count) = self.keep_count else {
+            // Without a retention limit, only the current snapshot is tracked.
+            while history.len() > 1 {
+                history.pop_front();
+            }
+            return None;
+        };
+
+        let mut dropped = false;
+        while history.len() > keep_count {
+            history.pop_front();
+            dropped = true;
+        }
+
+        if dropped { history.front().cloned() } else { None }
+    }
+
+    /// Returns the meta of the tracked snapshots, oldest first.
+    pub(crate) fn get(&self) -> Vec<SnapshotMetaOf<C>> {
+        self.inner.lock().unwrap().iter().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SnapshotHistory;
+    use crate::engine::testing::UTConfig;
+    use crate::engine::testing::log_id;
+    use crate::storage::SnapshotMeta;
+
+    fn meta(index: u64) -> SnapshotMeta<UTConfig> {
+        SnapshotMeta {
+            last_log_id: Some(log_id(1, 1, index)),
+            snapshot_id: format!("s{}", index),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_snapshot_history_keep_count() {
+        let history = SnapshotHistory::<UTConfig>::new(Some(2), &SnapshotMeta::default());
+        assert!(history.get().is_empty(), "no snapshot");
+
+        let reader = history.clone();
+
+        assert_eq!(None, history.push(&meta(5)));
+        assert_eq!(None, history.push(&meta(5)), "same snapshot is ignored");
+        assert_eq!(None, history.push(&meta(10)));
+        assert_eq!(vec![meta(5), meta(10)], reader.get());
+
+        assert_eq!(Some(meta(10)), history.push(&meta(15)));
+        assert_eq!(vec![meta(10), meta(15)], reader.get());
+
+        assert_eq!(None, history.push(&meta(12)), "older snapshot is ignored");
+        assert_eq!(vec![meta(10), meta(15)], reader.get());
+    }
+
+    #[test]
+    fn test_snapshot_history_no_keep_count() {
+        let history = SnapshotHistory::<UTConfig>::new(None, &meta(5));
+        assert_eq!(vec![meta(5)], history.get());
+
+        assert_eq!(None, history.push(&meta(10)));
+        assert_eq!(vec![meta(10)], history.get(), "only the current snapshot is tracked");
+    }
+}
@@ -0,0 +1,87 @@
+//! The meta of the current snapshot, readable without calling `RaftCore`.
+
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use crate::RaftTypeConfig;
+use crate::type_config::alias::SnapshotMetaOf;
+
+/// The meta of the snapshot this node currently has.
+///
+/// It is shared between `RaftCore`, which replaces it when a snapshot is built or installed, and
+/// [`Raft::current_snapshot_meta()`](crate::Raft::current_snapshot_meta), which reads it without
+/// opening the snapshot data.
+pub(crate) struct SnapshotMetaCache<C>
+where C: RaftTypeConfig
+{
+    inner: Arc<Mutex<Option<SnapshotMetaOf<C>>>>,
+}
+
+impl<C> Clone for SnapshotMetaCache<C>
+where C: RaftTypeConfig
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<C> SnapshotMetaCache<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new(meta: &SnapshotMetaOf<C>) -> Self {
+        let s = Self {
+            inner: Arc::new(Mutex::new(None)),
+        };
+        s.set(meta);
+        s
+    }
+
+    /// Replace the cached meta, if it changed.
+    ///
+    /// A meta without `last_log_id` means there is no snapshot.
+    pub(crate) fn set(&self, meta: &SnapshotMetaOf<C>) {
+        let mut cached = self.inner.lock().unwrap();
+
+        if meta.last_log_id.is_none() {
+            *cached = None;
+            return;
+        }
+
+        if cached.as_ref() != Some(meta) {
+            *cached = Some(meta.clone());
+        }
+    }
+
+    pub(crate) fn get(&self) -> Option<SnapshotMetaOf<C>> {
+        self.inner.lock().unwrap().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SnapshotMetaCache;
+    use crate::engine::testing::UTConfig;
+    use crate::engine::testing::log_id;
+    use crate::storage::SnapshotMeta;
+
+    #[test]
+    fn test_snapshot_meta_cache() {
+        let cache = SnapshotMetaCache::<UTConfig>::new(&SnapshotMeta::default());
+        assert_eq!(None, cache.get(), "no snapshot");
+
+        let meta = SnapshotMeta {
+            last_log_id: Some(log_id(1, 1, 5)),
+            snapshot_id: "s1".to_string(),
+            ..Default::default()
+        };
+
+        let reader = cache.clone();
+        cache.set(&meta);
+        assert_eq!(Some(meta), reader.get());
+
+        cache.set(&SnapshotMeta::default());
+        assert_eq!(None, reader.get());
+    }
+}
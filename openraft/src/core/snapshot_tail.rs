@@ -0,0 +1,55 @@
+use crate::RaftTypeConfig;
+use crate::core::raft_msg::AppendEntriesTx;
+use crate::raft::AppendEntriesRequest;
+use crate::type_config::alias::BatchOf;
+
+/// An `AppendEntries` request along with the senders to respond to.
+type HeldRequest<C> = (AppendEntriesRequest<C>, BatchOf<C, AppendEntriesTx<C>>);
+
+/// `AppendEntries` requests a follower holds until the snapshot they follow is installed.
+///
+/// See [`Config::pipeline_snapshot_tail`](crate::Config::pipeline_snapshot_tail).
+pub(crate) struct SnapshotTail<C>
+where C: RaftTypeConfig
+{
+    requests: Vec<HeldRequest<C>>,
+
+    /// The total number of log entries in `requests`.
+    entries: u64,
+}
+
+impl<C> Default for SnapshotTail<C>
+where C: RaftTypeConfig
+{
+    fn default() -> Self {
+        Self {
+            requests: Vec::new(),
+            entries: 0,
+        }
+    }
+}
+
+impl<C> SnapshotTail<C>
+where C: RaftTypeConfig
+{
+    /// The vote of the leader that sent the held requests, if any.
+    pub(crate) fn vote(&self) -> Option<&C::Vote> {
+        self.requests.first().map(|(req, _)| &req.vote)
+    }
+
+    /// The total number of log entries held.
+    pub(crate) fn entries(&self) -> u64 {
+        self.entries
+    }
+
+    pub(crate) fn push(&mut self, req: AppendEntriesRequest<C>, txs: BatchOf<C, AppendEntriesTx<C>>) {
+        self.entries += req.entries.len() as u64;
+        self.requests.push((req, txs));
+    }
+
+    /// Remove and return all held requests, in the order they were received.
+    pub(crate) fn take(&mut self) -> Vec<HeldRequest<C>> {
+        self.entries = 0;
+        std::mem::take(&mut self.requests)
+    }
+}
@@ -0,0 +1,170 @@
+//! Limits the number of snapshot transfers a leader runs at the same time.
+
+use std::collections::BTreeMap;
+use std::collections::VecDeque;
+
+use crate::RaftTypeConfig;
+use crate::metrics::SnapshotTransferState;
+use crate::progress::inflight_id::InflightId;
+use crate::type_config::alias::CommittedVoteOf;
+
+/// A snapshot transfer waiting for a free slot.
+#[derive(Debug, Clone)]
+pub(crate) struct QueuedSnapshot<C>
+where C: RaftTypeConfig
+{
+    pub(crate) leader_vote: CommittedVoteOf<C>,
+    pub(crate) target: C::NodeId,
+    pub(crate) inflight_id: InflightId,
+}
+
+/// Tracks the running and the queued snapshot transfers of a leader.
+///
+/// At most `max` transfers run at the same time; the others are queued in the order they are
+/// requested. A target has at most one transfer: a new request for it replaces the previous one.
+#[derive(Debug, Clone)]
+pub(crate) struct SnapshotTransfers<C>
+where C: RaftTypeConfig
+{
+    /// The maximum number of running transfers, `None` for no limit.
+    max: Option<usize>,
+
+    /// The `InflightId` of the running transfer to every target.
+    sending: BTreeMap<C::NodeId, InflightId>,
+
+    queued: VecDeque<QueuedSnapshot<C>>,
+}
+
+impl<C> SnapshotTransfers<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new(max: Option<usize>) -> Self {
+        Self {
+            max,
+            sending: BTreeMap::new(),
+            queued: VecDeque::new(),
+        }
+    }
+
+    /// Request a snapshot transfer; returns `true` if it can start at once, or `false` if it is
+    /// queued.
+    pub(crate) fn request(&mut self, req: QueuedSnapshot<C>) -> bool {
+        self.queued.retain(|q| q.target != req.target);
+
+        let has_slot = match self.max {
+            None => true,
+            Some(max) => self.sending.contains_key(&req.target) || self.sending.len() < max,
+        };
+
+        if has_slot {
+            self.sending.insert(req.target, req.inflight_id);
+        } else {
+            self.queued.push_back(req);
+        }
+        has_slot
+    }
+
+    /// Release the slot of a transfer that has finished.
+    ///
+    /// It is ignored if the transfer to `target` has been replaced by another one.
+    pub(crate) fn finish(&mut self, target: &C::NodeId, inflight_id: InflightId) {
+        if self.sending.get(target) == Some(&inflight_id) {
+            self.sending.remove(target);
+        }
+    }
+
+    /// Forget all the transfers, e.g., when this node is no longer leader.
+    pub(crate) fn clear(&mut self) {
+        self.sending.clear();
+        self.queued.clear();
+    }
+
+    /// Take the next queued transfer if there is a free slot, and mark it as running.
+    pub(crate) fn pop_ready(&mut self) -> Option<QueuedSnapshot<C>> {
+        if let Some(max) = self.max
+            && self.sending.len() >= max
+        {
+            return None;
+        }
+
+        let req = self.queued.pop_front()?;
+        self.sending.insert(req.target.clone(), req.inflight_id);
+        Some(req)
+    }
+
+    /// Keep only the transfers to the targets for which `f` returns `true`.
+    pub(crate) fn retain_targets(&mut self, f: impl Fn(&C::NodeId) -> bool) {
+        self.sending.retain(|t, _| f(t));
+        self.queued.retain(|q| f(&q.target));
+    }
+
+    /// The state of the transfer to every target, for metrics.
+    pub(crate) fn states(&self) -> BTreeMap<C::NodeId, SnapshotTransferState> {
+        let sending = self.sending.keys().map(|t| (t.clone(), SnapshotTransferState::Sending));
+        let queued = self.queued.iter().map(|q| (q.target.clone(), SnapshotTransferState::Queued));
+        sending.chain(queued).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use maplit::btreemap;
+
+    use crate::engine::testing::UTConfig;
+    use crate::metrics::SnapshotTransferState::Queued;
+    use crate::metrics::SnapshotTransferState::Sending;
+    use crate::progress::inflight_id::InflightId;
+    use crate::vote::raft_vote::RaftVoteExt;
+
+    type SnapshotTransfers = super::SnapshotTransfers<UTConfig>;
+    type QueuedSnapshot = super::QueuedSnapshot<UTConfig>;
+
+    fn req(target: u64, inflight_id: u64) -> QueuedSnapshot {
+        QueuedSnapshot {
+            leader_vote: crate::Vote::new_committed(1, 1).into_committed(),
+            target,
+            inflight_id: InflightId::new(inflight_id),
+        }
+    }
+
+    #[test]
+    fn test_snapshot_transfers_unlimited() {
+        let mut t = SnapshotTransfers::new(None);
+
+        assert!(t.request(req(2, 1)));
+        assert!(t.request(req(3, 2)));
+        assert_eq!(btreemap! {2=>Sending, 3=>Sending}, t.states());
+        assert!(t.pop_ready().is_none());
+    }
+
+    #[test]
+    fn test_snapshot_transfers_queue() {
+        let mut t = SnapshotTransfers::new(Some(1));
+
+        assert!(t.request(req(2, 1)));
+        assert!(!t.request(req(3, 2)));
+        assert!(!t.request(req(4, 3)));
+        assert_eq!(btreemap! {2=>Sending, 3=>Queued, 4=>Queued}, t.states());
+
+        // A new request for a running target takes the same slot.
+        assert!(t.request(req(2, 4)));
+        assert!(t.pop_ready().is_none());
+
+        // A stale transfer does not release the slot.
+        t.finish(&2, InflightId::new(1));
+        assert!(t.pop_ready().is_none());
+
+        t.finish(&2, InflightId::new(4));
+        let next = t.pop_ready().unwrap();
+        assert_eq!(3, next.target);
+        assert_eq!(InflightId::new(2), next.inflight_id);
+        assert!(t.pop_ready().is_none());
+        assert_eq!(btreemap! {3=>Sending, 4=>Queued}, t.states());
+
+        t.retain_targets(|t| *t != 3);
+        assert_eq!(4, t.pop_ready().unwrap().target);
+
+        t.clear();
+        assert!(t.states().is_empty());
+    }
+}
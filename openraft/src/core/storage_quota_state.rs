@@ -0,0 +1,15 @@
+use crate::errors::StorageFull;
+
+/// Where this node is in enforcing [`Config::storage_quota`](crate::Config::storage_quota).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) enum StorageQuotaState {
+    /// The storage usage is within the quota, or no quota is enforced.
+    #[default]
+    Normal,
+
+    /// The usage exceeds the quota; a snapshot is being built and logs purged to reclaim space.
+    Reclaiming,
+
+    /// The usage still exceeds the quota after reclaiming; client writes are rejected.
+    Full(StorageFull),
+}
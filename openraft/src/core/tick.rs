@@ -125,6 +125,10 @@ where C: RaftTypeConfig
         self.enabled.store(enabled, Ordering::Relaxed);
     }
 
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
     /// Signal the tick loop to stop. And return a JoinHandle to wait for the loop to stop.
     ///
     /// If it is called twice, the second call will return None.
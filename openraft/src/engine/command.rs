@@ -8,6 +8,8 @@ use display_more::DisplaySliceExt;
 use crate::OptionalSend;
 use crate::RaftTypeConfig;
 use crate::async_runtime::OneshotSender;
+use crate::batch::Batch;
+use crate::core::raft_msg::AppendEntriesTx;
 use crate::core::sm;
 use crate::engine::CommandKind;
 use crate::engine::CommandName;
@@ -362,6 +364,19 @@ where C: RaftTypeConfig
             Command::StateMachine { .. }              => None,
         }
     }
+
+    /// The approximate in-memory size of the command in bytes.
+    ///
+    /// It counts the command itself and the entries it carries, but not the heap data the entries
+    /// own.
+    pub(crate) fn approx_size(&self) -> u64 {
+        let size = size_of::<Self>();
+        let entries = match self {
+            Command::AppendEntries { entries, .. } => entries.len() * size_of::<C::Entry>(),
+            _ => 0,
+        };
+        (size + entries) as u64
+    }
 }
 
 /// A condition to wait for before executing a command or sending a respond.
@@ -446,7 +461,7 @@ pub(crate) enum Respond<C>
 where C: RaftTypeConfig
 {
     Vote(ValueSender<C, VoteResponse<C>>),
-    AppendEntries(ValueSender<C, StreamAppendResult<C>>),
+    AppendEntries(AppendEntriesSender<C>),
     ReceiveSnapshotChunk(ValueSender<C, Result<(), InstallSnapshotError>>),
     InstallSnapshot(ValueSender<C, Result<InstallSnapshotResponse<C>, InstallSnapshotError>>),
     InstallFullSnapshot(ValueSender<C, SnapshotResponse<C>>),
@@ -459,10 +474,16 @@ where C: RaftTypeConfig
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Respond::Vote(vs) => write!(f, "Vote {}", vs.value()),
-            Respond::AppendEntries(vs) => match vs.value() {
-                Ok(log_id) => write!(f, "AppendEntries Ok({})", log_id.display()),
-                Err(e) => write!(f, "AppendEntries Err({})", e),
-            },
+            Respond::AppendEntries(s) => {
+                match s.value() {
+                    Ok(log_id) => write!(f, "AppendEntries Ok({})", log_id.display())?,
+                    Err(e) => write!(f, "AppendEntries Err({})", e)?,
+                }
+                if !s.merged.is_empty() {
+                    write!(f, ", merged: {}", s.merged.len())?;
+                }
+                Ok(())
+            }
             Respond::ReceiveSnapshotChunk(vs) => {
                 write!(
                     f,
@@ -553,3 +574,62 @@ where
         self.tx.send(self.value).ok();
     }
 }
+
+/// Sends the result of a merged AppendEntries segment to the last merged request.
+///
+/// The earlier merged requests receive `None` if the segment is appended: the response to the
+/// last one answers them. Otherwise they receive the same error.
+pub(crate) struct AppendEntriesSender<C>
+where C: RaftTypeConfig
+{
+    value: StreamAppendResult<C>,
+    tx: AppendEntriesTx<C>,
+    merged: Vec<AppendEntriesTx<C>>,
+}
+
+impl<C> Debug for AppendEntriesSender<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AppendEntriesSender")
+            .field("value", &self.value)
+            .field("merged", &self.merged.len())
+            .finish()
+    }
+}
+
+impl<C> PartialEq for AppendEntriesSender<C>
+where C: RaftTypeConfig
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value && self.merged.len() == other.merged.len()
+    }
+}
+
+impl<C> Eq for AppendEntriesSender<C> where C: RaftTypeConfig {}
+
+impl<C> AppendEntriesSender<C>
+where C: RaftTypeConfig
+{
+    /// Create a sender for the senders of the merged requests, in order.
+    ///
+    /// Returns `None` if `txs` is empty.
+    pub(crate) fn new(res: StreamAppendResult<C>, txs: impl IntoIterator<Item = AppendEntriesTx<C>>) -> Option<Self> {
+        let mut merged: Vec<_> = txs.into_iter().collect();
+        let tx = merged.pop()?;
+
+        Some(Self { value: res, tx, merged })
+    }
+
+    pub(crate) fn value(&self) -> &StreamAppendResult<C> {
+        &self.value
+    }
+
+    pub(crate) fn send(self) {
+        for tx in self.merged {
+            let res = self.value.as_ref().err().map(|e| Err(e.clone()));
+            tx.send(res).ok();
+        }
+        self.tx.send(Some(self.value)).ok();
+    }
+}
@@ -9,12 +9,17 @@ pub enum SMCommandName {
     InstallFullSnapshot = 3,
     Apply = 4,
     ExternalFunc = 5,
+    PromoteStandby = 6,
+    GetSnapshotLocator = 7,
+    InstallSnapshotLocator = 8,
+    RemoveSnapshotsBefore = 9,
+    InstallSnapshotMeta = 10,
 }
 
 impl SMCommandName {
     /// Total number of variants.
     #[allow(dead_code)]
-    pub const COUNT: usize = 6;
+    pub const COUNT: usize = 11;
 
     /// All variants in canonical order.
     #[allow(dead_code)]
@@ -25,6 +30,11 @@ impl SMCommandName {
         SMCommandName::InstallFullSnapshot,
         SMCommandName::Apply,
         SMCommandName::ExternalFunc,
+        SMCommandName::PromoteStandby,
+        SMCommandName::GetSnapshotLocator,
+        SMCommandName::InstallSnapshotLocator,
+        SMCommandName::RemoveSnapshotsBefore,
+        SMCommandName::InstallSnapshotMeta,
     ];
 
     /// Returns the index of this variant for array-based storage.
@@ -42,6 +52,11 @@ impl SMCommandName {
             SMCommandName::InstallFullSnapshot => "SM::InstallFullSnapshot",
             SMCommandName::Apply => "SM::Apply",
             SMCommandName::ExternalFunc => "SM::ExternalFunc",
+            SMCommandName::PromoteStandby => "SM::PromoteStandby",
+            SMCommandName::GetSnapshotLocator => "SM::GetSnapshotLocator",
+            SMCommandName::InstallSnapshotLocator => "SM::InstallSnapshotLocator",
+            SMCommandName::RemoveSnapshotsBefore => "SM::RemoveSnapshotsBefore",
+            SMCommandName::InstallSnapshotMeta => "SM::InstallSnapshotMeta",
         }
     }
 }
@@ -80,7 +95,7 @@ pub enum CommandName {
 
 impl CommandName {
     /// Total number of variants (including expanded StateMachine variants).
-    pub const COUNT: usize = 22;
+    pub const COUNT: usize = 27;
 
     /// All variants in canonical order.
     ///
@@ -108,6 +123,11 @@ impl CommandName {
         CommandName::StateMachine(SMCommandName::InstallFullSnapshot),
         CommandName::StateMachine(SMCommandName::Apply),
         CommandName::StateMachine(SMCommandName::ExternalFunc),
+        CommandName::StateMachine(SMCommandName::PromoteStandby),
+        CommandName::StateMachine(SMCommandName::GetSnapshotLocator),
+        CommandName::StateMachine(SMCommandName::InstallSnapshotLocator),
+        CommandName::StateMachine(SMCommandName::RemoveSnapshotsBefore),
+        CommandName::StateMachine(SMCommandName::InstallSnapshotMeta),
         CommandName::Respond,
     ];
 
@@ -302,6 +322,10 @@ mod tests {
         let cmd: sm::Command<C> = sm::Command::apply(log_id(1, 0, 1), log_id(1, 0, 2), vec![]);
         assert_eq!(cmd.name(), SMCommandName::Apply);
 
+        // PromoteStandby
+        let cmd: sm::Command<C> = sm::Command::promote_standby();
+        assert_eq!(cmd.name(), SMCommandName::PromoteStandby);
+
         // GetSnapshot, BeginReceivingSnapshot, InstallFullSnapshot require channels/data
         // Test via StateMachine command wrapper
         let cmd: Command<C> = Command::StateMachine {
@@ -355,6 +379,16 @@ mod tests {
         assert_eq!(SMCommandName::InstallFullSnapshot.as_str(), "SM::InstallFullSnapshot");
         assert_eq!(SMCommandName::Apply.as_str(), "SM::Apply");
         assert_eq!(SMCommandName::ExternalFunc.as_str(), "SM::ExternalFunc");
+        assert_eq!(SMCommandName::GetSnapshotLocator.as_str(), "SM::GetSnapshotLocator");
+        assert_eq!(
+            SMCommandName::InstallSnapshotLocator.as_str(),
+            "SM::InstallSnapshotLocator"
+        );
+        assert_eq!(
+            SMCommandName::RemoveSnapshotsBefore.as_str(),
+            "SM::RemoveSnapshotsBefore"
+        );
+        assert_eq!(SMCommandName::InstallSnapshotMeta.as_str(), "SM::InstallSnapshotMeta");
     }
 
     #[test]
@@ -58,6 +58,8 @@ where C: RaftTypeConfig
                     committed_vote: next_vote,
                     entries: next_entries,
                 } if next_vote == committed_vote && entries.len() as u64 + next_entries.len() as u64 <= max_entries => {
+                    // The merged entries are still queued, only the command itself is gone.
+                    self.output.queued_bytes -= size_of::<Command<C, SM>>() as u64;
                     n += 1;
                     entries.extend(next_entries);
                 }
@@ -169,6 +171,13 @@ mod tests {
         } else {
             panic!("Expected AppendEntries");
         }
+
+        let want = output.commands[0].approx_size();
+        assert_eq!(
+            want,
+            output.queued_bytes(),
+            "merging keeps the byte accounting accurate"
+        );
     }
 
     #[test]
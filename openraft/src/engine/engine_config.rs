@@ -1,13 +1,14 @@
+use std::sync::Arc;
 use std::time::Duration;
 
 use crate::Config;
 use crate::RaftTypeConfig;
 use crate::engine::time_state;
+use crate::membership::QuorumPolicy;
 use crate::type_config::alias::AsyncRuntimeOf;
 
 /// Config for Engine
 #[derive(Clone, Debug)]
-#[derive(PartialEq, Eq)]
 pub(crate) struct EngineConfig<C: RaftTypeConfig> {
     /// The id of this node.
     pub(crate) id: C::NodeId,
@@ -26,6 +27,30 @@ pub(crate) struct EngineConfig<C: RaftTypeConfig> {
     pub(crate) timer_config: time_state::Config,
 
     pub(crate) enable_leader_restore: bool,
+
+    /// Whether to acknowledge AppendEntries before the entries are flushed.
+    pub(crate) relaxed_durability: bool,
+
+    /// Whether to stream the log entries following a snapshot while sending the snapshot.
+    pub(crate) pipeline_snapshot_tail: bool,
+
+    /// Whether to stamp log entries with the leader's wall-clock time when they are proposed.
+    pub(crate) entry_timestamp: bool,
+
+    /// The maximum lag of a learner to be promoted to a voter automatically.
+    pub(crate) promote_lag_threshold: u64,
+
+    /// Whether to reject an initial membership in which two nodes have equal node info.
+    pub(crate) reject_duplicate_nodes: bool,
+
+    /// How long a node may be unreachable before the leader evicts it, if eviction is enabled.
+    pub(crate) evict_unreachable_after: Option<Duration>,
+
+    /// Whether to demote an unreachable voter to a learner instead of removing it.
+    pub(crate) evict_demote_voters: bool,
+
+    /// Decides the election and commit quorums in place of a majority, if set.
+    pub(crate) quorum_policy: Option<Arc<dyn QuorumPolicy<C>>>,
 }
 
 impl<C> EngineConfig<C>
@@ -47,6 +72,14 @@ where C: RaftTypeConfig
             },
 
             enable_leader_restore: config.enable_leader_restore(),
+            relaxed_durability: config.relaxed_durability(),
+            pipeline_snapshot_tail: config.pipeline_snapshot_tail(),
+            entry_timestamp: config.entry_timestamp(),
+            promote_lag_threshold: config.promote_lag_threshold(),
+            reject_duplicate_nodes: config.reject_duplicate_nodes(),
+            evict_unreachable_after: config.evict_unreachable_after(),
+            evict_demote_voters: config.evict_demote_voters(),
+            quorum_policy: None,
         }
     }
 
@@ -60,6 +93,14 @@ where C: RaftTypeConfig
             allow_log_reversion: false,
             timer_config: time_state::Config::default(),
             enable_leader_restore: true,
+            relaxed_durability: false,
+            pipeline_snapshot_tail: false,
+            entry_timestamp: false,
+            promote_lag_threshold: 5000,
+            reject_duplicate_nodes: false,
+            evict_unreachable_after: None,
+            evict_demote_voters: false,
+            quorum_policy: None,
         }
     }
 }
@@ -10,6 +10,8 @@ use crate::RaftTypeConfig;
 use crate::core::ServerState;
 use crate::core::raft_msg::AppendEntriesTx;
 use crate::core::sm;
+use crate::display_ext::DisplayInstantExt;
+use crate::engine::AppendEntriesSender;
 use crate::engine::Command;
 use crate::engine::Condition;
 use crate::engine::EngineOutput;
@@ -30,20 +32,26 @@ use crate::errors::InitializeError;
 use crate::errors::NotAllowed;
 use crate::errors::NotInMembers;
 use crate::errors::RejectAppendEntries;
+use crate::membership::QuorumKind;
 use crate::proposer::Candidate;
 use crate::proposer::Leader;
 use crate::proposer::LeaderQuorumSet;
 use crate::proposer::LeaderState;
 use crate::proposer::leader_state::CandidateState;
 use crate::raft::LogSegment;
+use crate::raft::RaftEvent;
 use crate::raft::SnapshotResponse;
 use crate::raft::VoteRequest;
 use crate::raft::VoteResponse;
+use crate::raft::message::validate_snapshot_meta;
 use crate::raft::stream_append::StreamAppendResult;
 use crate::raft_state::IOId;
 use crate::raft_state::LogStateReader;
 use crate::raft_state::RaftState;
+use crate::storage::SnapshotLocator;
 use crate::type_config::TypeConfigExt;
+use crate::type_config::alias::CommittedLeaderIdOf;
+use crate::type_config::alias::InstantOf;
 use crate::type_config::alias::LeaderIdOf;
 use crate::type_config::alias::LogIdOf;
 use crate::type_config::alias::OneshotSenderOf;
@@ -100,6 +108,19 @@ where C: RaftTypeConfig
     /// [`Config::enable_pre_vote`](crate::Config::enable_pre_vote) is set.
     pub(crate) pre_candidate: CandidateState<C>,
 
+    /// The Leader this node granted a vote to in the relaxed durability mode.
+    ///
+    /// With [`Config::relaxed_durability`](crate::Config::relaxed_durability), AppendEntries from
+    /// this Leader is acknowledged before it is flushed. It is not persisted: after a restart, a
+    /// follower acknowledges after the flush until it votes again.
+    pub(crate) relaxed_leader: Option<LeaderIdOf<C>>,
+
+    /// The leader id of the term this node abandoned as a Leader because a write passed its
+    /// deadline uncommitted, see [`Self::abandon_expired_writes()`].
+    ///
+    /// It is not persisted: after a restart, such a write is committed by the next Leader.
+    pub(crate) abandoned: Option<CommittedLeaderIdOf<C>>,
+
     /// Output entry for the runtime.
     pub(crate) output: EngineOutput<C, SM>,
 }
@@ -115,6 +136,8 @@ where C: RaftTypeConfig
             leader: None,
             candidate: None,
             pre_candidate: None,
+            relaxed_leader: None,
+            abandoned: None,
             output: EngineOutput::new(4096),
         }
     }
@@ -127,13 +150,15 @@ where C: RaftTypeConfig
         let now = C::now();
         let last_log_id = self.state.last_log_id().cloned();
 
-        let membership = self.state.membership_state.effective().membership();
+        let membership = Arc::new((*self.state.membership_state.effective().membership()).clone());
+        let policy = self.config.quorum_policy.as_ref();
 
         self.candidate = Some(Candidate::new(
             now,
             vote,
             last_log_id,
-            Arc::new((*membership).clone()),
+            LeaderQuorumSet::new(membership.clone(), policy, QuorumKind::Election),
+            LeaderQuorumSet::new(membership.clone(), policy, QuorumKind::Commit),
             membership.learner_ids(),
             self.state.progress_id_gen.clone(),
         ));
@@ -150,13 +175,15 @@ where C: RaftTypeConfig
         let now = C::now();
         let last_log_id = self.state.last_log_id().cloned();
 
-        let membership = self.state.membership_state.effective().membership();
+        let membership = Arc::new((*self.state.membership_state.effective().membership()).clone());
+        let policy = self.config.quorum_policy.as_ref();
 
         self.pre_candidate = Some(Candidate::new(
             now,
             vote,
             last_log_id,
-            Arc::new((*membership).clone()),
+            LeaderQuorumSet::new(membership.clone(), policy, QuorumKind::Election),
+            LeaderQuorumSet::new(membership.clone(), policy, QuorumKind::Commit),
             membership.learner_ids(),
             self.state.progress_id_gen.clone(),
         ));
@@ -231,6 +258,10 @@ where C: RaftTypeConfig
 
         self.check_members_contain_me(&membership)?;
 
+        if self.config.reject_duplicate_nodes {
+            membership.find_duplicate_node()?;
+        }
+
         // FollowingHandler requires vote to be committed.
         let leader_id = LeaderIdOf::<C>::new(TermOf::<C>::default(), self.config.id.clone());
         let vote = <VoteOf<C> as RaftVote>::from_leader_id(leader_id.clone(), true);
@@ -259,11 +290,53 @@ where C: RaftTypeConfig
         self.do_elect(true);
     }
 
+    /// Re-elect this Leader if a write it proposed passes its deadline before it is committed.
+    ///
+    /// Such a write is no longer committed in this term. When this node is elected again, its
+    /// first entry records the first index this term did not commit, so that every node applies
+    /// the writes after it that passed their deadline as blank entries, see
+    /// [`RaftEntry::deadline()`]. If another node is elected instead, the write is committed by
+    /// it as usual.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub(crate) fn abandon_expired_writes(&mut self) {
+        if !self.state.is_leader(&self.config.id) {
+            return;
+        }
+
+        let Some(leader) = self.leader.as_ref() else {
+            return;
+        };
+
+        let Some(index) = leader.first_expired_write(LeaderHandler::<C>::wall_clock_ms()) else {
+            return;
+        };
+
+        tracing::info!(
+            "{}: the write at {} passed its deadline uncommitted, committed: {}; re-elect this leader",
+            func_name!(),
+            index,
+            self.state.committed().display()
+        );
+
+        self.abandoned = Some(leader.committed_vote_ref().committed_leader_id());
+        self.elect_by_leadership_transfer();
+    }
+
     fn do_elect(&mut self, leadership_transfer: bool) {
+        if !self.is_leader_eligible() {
+            tracing::debug!(
+                "{}: skip election, a witness or log-only node never becomes leader",
+                func_name!()
+            );
+            return;
+        }
+
         // A real election supersedes any in-flight Pre-Vote round.
         self.pre_candidate = None;
 
-        let new_term = self.state.vote.term().next();
+        let Some(new_term) = self.next_term() else {
+            return;
+        };
         let leader_id = LeaderIdOf::<C>::new(new_term, self.config.id.clone());
         let new_vote = VoteOf::<C>::from_leader_id(leader_id, false);
 
@@ -279,15 +352,69 @@ where C: RaftTypeConfig
 
         self.output.push_command(Command::SendVote {
             vote_req: VoteRequest {
-                vote: new_vote,
-                last_log_id,
                 leadership_transfer,
+                relaxed_durability: self.config.relaxed_durability,
+                ..VoteRequest::new(new_vote, last_log_id)
             },
         });
 
         self.server_state_handler().update_server_state_if_changed();
     }
 
+    /// Step down if this node is a Leader that has not heard from a quorum of voters for an
+    /// election timeout.
+    ///
+    /// Such a Leader may be partitioned away from the quorum, which may have elected another
+    /// Leader. It stops leading at once and becomes a Follower, without increasing the term: an
+    /// isolated ex-Leader that raises its term at once would disrupt the quorum once reachable
+    /// again. It waits for the election timer, then elects through a Pre-Vote if it is enabled,
+    /// like any other Follower. A newly established Leader is given an election timeout to hear
+    /// from the quorum.
+    ///
+    /// Returns `true` if it stepped down.
+    ///
+    /// See: [`Config::check_quorum`](crate::Config::check_quorum).
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub(crate) fn check_quorum(&mut self, now: InstantOf<C>) -> bool {
+        let timeout = self.config.timer_config.election_timeout;
+
+        let Some(leader) = self.leader.as_mut() else {
+            return false;
+        };
+
+        let since = *leader.leader_since.instant;
+        let quorum_acked = leader.last_quorum_acked_time();
+        let last_heard = quorum_acked.map_or(since, |t| std::cmp::max(t, since));
+
+        if last_heard + timeout > now {
+            return false;
+        }
+
+        tracing::warn!(
+            "{}: Leader has not heard from a quorum since {}, step down; now: {}",
+            func_name!(),
+            last_heard.display(),
+            now.display()
+        );
+
+        self.leader = None;
+        self.output.push_command(Command::CloseReplicationStreams);
+
+        // A Follower can not keep its own committed vote. Demote the vote in memory only, as
+        // `startup()` does, and restart the election timer from now.
+        let stepped_down = self.state.vote_ref().clone();
+        let uncommitted: VoteOf<C> = self.state.vote.to_non_committed().into_vote();
+        self.state.vote.update(now, Duration::default(), uncommitted);
+
+        self.output.push_event(RaftEvent::SteppedDown { vote: stepped_down });
+        self.state.server_state = if self.state.membership_state.effective().is_voter(&self.config.id) {
+            ServerState::Follower
+        } else {
+            ServerState::Learner
+        };
+        true
+    }
+
     /// Start a Pre-Vote round.
     ///
     /// Probe whether a quorum *would* grant a vote at `term + 1` without changing any local state:
@@ -296,7 +423,17 @@ where C: RaftTypeConfig
     /// follows.
     #[tracing::instrument(level = "debug", skip(self))]
     pub(crate) fn pre_elect(&mut self) {
-        let new_term = self.state.vote.term().next();
+        if !self.is_leader_eligible() {
+            tracing::debug!(
+                "{}: skip pre-vote, a witness or log-only node never becomes leader",
+                func_name!()
+            );
+            return;
+        }
+
+        let Some(new_term) = self.next_term() else {
+            return;
+        };
         let leader_id = LeaderIdOf::<C>::new(new_term, self.config.id.clone());
         let pre_vote = VoteOf::<C>::from_leader_id(leader_id, false);
 
@@ -317,10 +454,37 @@ where C: RaftTypeConfig
         // Unlike `elect`, this neither updates `vote` (no term bump, no SaveVote) nor changes the
         // server state: the node stays a Follower until a quorum would grant the vote.
         self.output.push_command(Command::SendPreVote {
-            vote_req: VoteRequest::new(pre_vote, last_log_id),
+            vote_req: VoteRequest {
+                relaxed_durability: self.config.relaxed_durability,
+                ..VoteRequest::new(pre_vote, last_log_id)
+            },
         });
     }
 
+    /// Whether this node may become leader, i.e., it is not a witness or a log-only node, which
+    /// have no state machine.
+    fn is_leader_eligible(&self) -> bool {
+        self.state.membership_state.effective().membership().is_leader_eligible(&self.config.id)
+    }
+
+    /// Returns the term for the next election, or `None` if the term is exhausted.
+    ///
+    /// A term never wraps around, because a wrapped vote would compare less than every vote seen
+    /// before. See [`RaftTerm::checked_next`]. The exhaustion is reported in
+    /// [`RaftMetrics::term_exhausted`](crate::metrics::RaftMetrics::term_exhausted).
+    fn next_term(&self) -> Option<C::Term> {
+        let term = self.state.vote.term();
+        let next = term.checked_next();
+        if next.is_none() {
+            tracing::error!(
+                "{}: term {} is exhausted, refuse to start an election",
+                func_name!(),
+                term
+            );
+        }
+        next
+    }
+
     pub(crate) fn leader_ref(&self) -> Option<&Leader<C, LeaderQuorumSet<C>>> {
         self.leader.as_deref()
     }
@@ -359,6 +523,20 @@ where C: RaftTypeConfig
             local_leased_vote.display_lease_info(now)
         );
 
+        if req.relaxed_durability != self.config.relaxed_durability {
+            tracing::error!(
+                "reject vote-request: durability mode mismatch: candidate relaxed_durability: {}, mine: {}",
+                req.relaxed_durability,
+                self.config.relaxed_durability
+            );
+            return VoteResponse::new(self.state.vote_ref(), self.state.last_log_id().cloned(), false);
+        }
+
+        if let Err(e) = req.validate() {
+            tracing::error!("reject vote-request: malformed request: {}", e);
+            return VoteResponse::new(self.state.vote_ref(), self.state.last_log_id().cloned(), false);
+        }
+
         // A leadership-transfer election is authorized by the current Leader, thus it proceeds
         // even when the leader lease has not expired.
         // See: Raft dissertation, section 4.2.3.
@@ -398,6 +576,10 @@ where C: RaftTypeConfig
 
         tracing::info!("handle vote request result: req: {}, result: {:?}", req, res);
 
+        if res.is_ok() && self.config.relaxed_durability {
+            self.relaxed_leader = Some(req.vote.leader_id().clone());
+        }
+
         // Return the updated vote, this way the candidate knows which vote is granted, in case
         // the candidate's vote is changed after sending the vote request.
         VoteResponse::new(self.state.vote_ref(), self.state.last_log_id().cloned(), res.is_ok())
@@ -423,6 +605,20 @@ where C: RaftTypeConfig
             local_leased_vote.display_lease_info(now)
         );
 
+        if req.relaxed_durability != self.config.relaxed_durability {
+            tracing::error!(
+                "reject pre-vote-request: durability mode mismatch: candidate relaxed_durability: {}, mine: {}",
+                req.relaxed_durability,
+                self.config.relaxed_durability
+            );
+            return VoteResponse::new(self.state.vote_ref(), self.state.last_log_id().cloned(), false);
+        }
+
+        if let Err(e) = req.validate() {
+            tracing::error!("reject pre-vote-request: malformed request: {}", e);
+            return VoteResponse::new(self.state.vote_ref(), self.state.last_log_id().cloned(), false);
+        }
+
         // Respect the leader lease: while an established Leader's lease has not expired, this node
         // would not grant a vote, so it would not grant a Pre-Vote either.
         if local_leased_vote.is_committed() && !local_leased_vote.is_expired(now, Duration::from_millis(0)) {
@@ -568,11 +764,21 @@ where C: RaftTypeConfig
         }
     }
 
-    /// Append entries to follower/learner.
+    /// Append a segment of log entries sent by the leader to follower/learner.
     ///
     /// Also clean conflicting entries and update membership state.
+    ///
+    /// `txs` contains more than one sender if several consecutive AppendEntries requests are
+    /// merged into `segment`. The segment is checked and appended at once, and a single response
+    /// with the last log id of the segment is sent once the whole segment is flushed. See
+    /// [`AppendEntriesSender`].
     #[tracing::instrument(level = "debug", skip_all)]
-    pub(crate) fn handle_append_entries(&mut self, vote: &VoteOf<C>, segment: LogSegment<C>, tx: AppendEntriesTx<C>) {
+    pub(crate) fn handle_append_entries(
+        &mut self,
+        vote: &VoteOf<C>,
+        segment: LogSegment<C>,
+        txs: impl IntoIterator<Item = AppendEntriesTx<C>>,
+    ) {
         tracing::debug!(
             "{}: vote: {}, segment: {}, my_vote: {}, my_last_log_id: {}",
             func_name!(),
@@ -584,7 +790,11 @@ where C: RaftTypeConfig
 
         let stream_result: StreamAppendResult<C> = self.append_entries(vote, segment).map_err(Into::into);
 
-        let condition = if stream_result.is_ok() {
+        // In the relaxed durability mode, respond once the entries are submitted to the storage,
+        // but only to a Leader known to run in the same mode.
+        let relaxed = self.relaxed_leader.as_ref() == Some(vote.leader_id());
+
+        let condition = if stream_result.is_ok() && !relaxed {
             Some(Condition::IOFlushed {
                 io_id: self.state.accepted_log_io().unwrap().clone(),
             })
@@ -592,9 +802,13 @@ where C: RaftTypeConfig
             None
         };
 
+        let Some(sender) = AppendEntriesSender::new(stream_result, txs) else {
+            return;
+        };
+
         self.output.push_command(Command::Respond {
             when: condition,
-            resp: Respond::new(stream_result, tx),
+            resp: Respond::from(sender),
         });
     }
 
@@ -626,6 +840,59 @@ where C: RaftTypeConfig
     ) {
         tracing::info!("{}: vote: {}, snapshot: {}", func_name!(), vote, snapshot);
 
+        let meta = snapshot.meta.clone();
+        self.install_snapshot(vote, &meta, tx, |fh| fh.install_full_snapshot(snapshot));
+    }
+
+    /// Install a snapshot stored out of band on a follower, which the state machine fetches by
+    /// itself.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) fn handle_install_snapshot_locator(
+        &mut self,
+        vote: VoteOf<C>,
+        locator: SnapshotLocator<C>,
+        tx: OneshotSenderOf<C, SnapshotResponse<C>>,
+    ) {
+        tracing::info!("{}: vote: {}, locator: {}", func_name!(), vote, locator);
+
+        let meta = locator.meta.clone();
+        self.install_snapshot(vote, &meta, tx, |fh| fh.install_snapshot_locator(locator));
+    }
+
+    /// Install only the metadata of a snapshot on a witness, which keeps no state machine data.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) fn handle_install_snapshot_meta(
+        &mut self,
+        vote: VoteOf<C>,
+        meta: SnapshotMetaOf<C>,
+        tx: OneshotSenderOf<C, SnapshotResponse<C>>,
+    ) {
+        tracing::info!("{}: vote: {}, meta: {}", func_name!(), vote, meta);
+
+        self.install_snapshot(vote, &meta, tx, |fh| fh.install_snapshot_meta(meta.clone()));
+    }
+
+    /// Check the snapshot `meta` and the `vote` from the leader, then install the snapshot with
+    /// `install` and respond once it is installed.
+    fn install_snapshot(
+        &mut self,
+        vote: VoteOf<C>,
+        meta: &SnapshotMetaOf<C>,
+        tx: OneshotSenderOf<C, SnapshotResponse<C>>,
+        install: impl FnOnce(&mut FollowingHandler<'_, C, SM>) -> Option<Condition<C>>,
+    ) {
+        // A malformed snapshot is not installed, and the vote it carries is not accepted either.
+        if let Err(e) = validate_snapshot_meta::<C>(&vote, meta) {
+            tracing::error!("{}: reject malformed snapshot: {}", func_name!(), e);
+
+            let res = SnapshotResponse::new(self.state.vote_ref().clone());
+            self.output.push_command(Command::Respond {
+                when: None,
+                resp: Respond::new(res, tx),
+            });
+            return;
+        }
+
         let vote_res = self.vote_handler().accept_vote(&vote, tx, |state, _rejected| {
             SnapshotResponse::new(state.vote_ref().clone())
         });
@@ -638,7 +905,7 @@ where C: RaftTypeConfig
 
         // The condition to satisfy before running other command that depends on the snapshot.
         // In this case, the response can only be sent when the snapshot is installed.
-        let cond = fh.install_full_snapshot(snapshot);
+        let cond = install(&mut fh);
         let res = SnapshotResponse {
             vote: self.state.vote_ref().clone(),
         };
@@ -722,11 +989,13 @@ where C: RaftTypeConfig
 
         let mut h = self.snapshot_handler();
 
-        let updated = h.update_snapshot(meta);
+        let updated = h.update_snapshot(meta.clone());
         if !updated {
             return;
         }
 
+        self.output.push_event(RaftEvent::SnapshotBuilt { meta });
+
         self.log_handler().schedule_policy_based_purge();
         self.try_purge_log();
     }
@@ -745,22 +1014,40 @@ where C: RaftTypeConfig
         if self.leader.is_some() {
             // If it is leading, it must not delete a log that is in use by a replication task.
             self.replication_handler().try_purge_log();
-        } else {
+        } else if !self.log_handler().is_purge_held() {
             // For follower/learner, no other tasks are using logs, just purge.
             self.log_handler().purge_log();
         }
     }
 
+    /// Update the last applied log id the state machine has persisted, and purge the logs up to
+    /// it according to the configured policy.
+    ///
+    /// It is called only with `PurgePolicy::DurableApplied`.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) fn update_durable_applied(&mut self, log_id: LogIdOf<C>) {
+        if self.state.durable_applied.as_ref() >= Some(&log_id) {
+            return;
+        }
+
+        tracing::debug!("{}: durable_applied: {}", func_name!(), log_id);
+
+        self.state.durable_applied = Some(log_id);
+
+        self.log_handler().schedule_policy_based_purge();
+        self.try_purge_log();
+    }
+
     /// This is a to user API that triggers log purging up to `index`, inclusive.
     #[tracing::instrument(level = "debug", skip_all)]
     pub(crate) fn trigger_purge_log(&mut self, mut index: u64) {
         tracing::info!("{}: index: {}", func_name!(), index);
 
-        let snapshot_last_log_id = self.state.snapshot_last_log_id();
-        let snapshot_last_log_id = if let Some(log_id) = snapshot_last_log_id {
+        let purgeable = self.state.purgeable_log_id();
+        let purgeable = if let Some(log_id) = purgeable {
             log_id.clone()
         } else {
-            tracing::info!("no snapshot, cannot purge");
+            tracing::info!("no snapshot or persisted applied log, cannot purge");
             return;
         };
 
@@ -775,13 +1062,30 @@ where C: RaftTypeConfig
             return;
         }
 
-        if index > snapshot_last_log_id.index() {
+        if index > purgeable.index() {
             tracing::info!(
-                "cannot purge logs not in a snapshot; index: {}, last in snapshot log id: {}",
+                "cannot purge logs not in a snapshot or not persisted by state machine; index: {}, last purgeable log id: {}",
                 index,
-                snapshot_last_log_id
+                purgeable
             );
-            index = snapshot_last_log_id.index();
+            index = purgeable.index();
+        }
+
+        if let Some(hold) = self.state.log_only_hold
+            && index >= hold
+        {
+            tracing::info!(
+                "cannot purge logs a log-only voter still needs; index: {}, log_only_hold: {}",
+                index,
+                hold
+            );
+            let Some(before_hold) = hold.checked_sub(1) else {
+                return;
+            };
+            if before_hold < scheduled.next_index() {
+                return;
+            }
+            index = before_hold;
         }
 
         // Safe unwrap: `index` is ensured to be present in the above code.
@@ -805,6 +1109,15 @@ where C: RaftTypeConfig
             return;
         };
 
+        if !lh.state.membership_state.effective().membership().is_leader_eligible(&to) {
+            tracing::info!(
+                "{}: a witness or log-only node can not be Leader, ignore transfer Leader: to: {}",
+                func_name!(),
+                to
+            );
+            return;
+        }
+
         lh.transfer_leader(to);
     }
 
@@ -815,7 +1128,9 @@ where C: RaftTypeConfig
     /// executed immediately without queuing.
     ///
     /// Currently, generates [`Command::SaveCommittedAndApply`] when committed log entries
-    /// haven't been applied: `(apply_progress.submitted()..apply_progress.accepted()]`.
+    /// haven't been applied: `(apply_progress.submitted()..apply_progress.accepted()]`, or, on a
+    /// log-only node, haven't been saved:
+    /// `(io_state.log_only_committed..apply_progress.accepted()]`.
     ///
     /// Requirements:
     /// - Commands must update their corresponding progress when executed to prevent duplicates
@@ -847,7 +1162,31 @@ where C: RaftTypeConfig
 
             let applicable_upto = log_submitted.min(apply_accepted);
 
-            if apply_submitted.next_index() < applicable_upto.next_index() {
+            // A log-only node only saves the committed log id, it applies nothing.
+            let log_only = self.state.membership_state.effective().membership().is_log_only(&self.config.id);
+            let done = if log_only {
+                self.state.io_state.log_only_committed.as_ref()
+            } else {
+                apply_submitted
+            };
+
+            // An entry with a deadline may expire, which is decided by the first entry of the next
+            // leader, see `RaftEntry::deadline()`. Do not apply until such an entry that is
+            // committed is submitted, so that the state machine can read it.
+            if self.config.entry_timestamp
+                && !log_only
+                && let (Some(upto), Some(committed)) = (applicable_upto, self.state.committed())
+                && self.state.log_ids.leader_starts(upto.index(), committed.index()).next().is_some()
+            {
+                tracing::debug!(
+                    "hold applying up to {}: the next leader's first entry before {} is not submitted",
+                    upto,
+                    committed
+                );
+                return None;
+            }
+
+            if done.next_index() < applicable_upto.next_index() {
                 let apply_upto = applicable_upto.cloned().unwrap();
 
                 return Some(Command::SaveCommittedAndApply {
@@ -871,11 +1210,23 @@ where C: RaftTypeConfig
         tracing::info!("{}", func_name!());
 
         let candidate = self.candidate.take().unwrap();
+
+        // Re-established right after the term it abandoned, this leader records what that term did
+        // not commit.
+        let last_leader_id = self.state.last_log_id().map(|log_id| log_id.committed_leader_id().clone());
+        let expire_from = self
+            .abandoned
+            .take()
+            .filter(|leader_id| last_leader_id.as_ref() == Some(leader_id))
+            .map(|_| self.state.committed().next_index());
+
         let leader = self.establish_handler().establish(candidate);
 
         // There may already be a Leader with higher vote
         let Some(leader) = leader else { return };
 
+        leader.expire_from = expire_from;
+
         let vote = leader.committed_vote_ref().clone();
         let last_log_id = leader.last_log_id().cloned();
 
@@ -892,7 +1243,7 @@ where C: RaftTypeConfig
         // No need to submit UpdateIOProgress command,
         // IO progress is updated by the new blank log
 
-        self.try_leader_handler().unwrap().leader_append_entries([EntryPayload::Blank]);
+        self.try_leader_handler().unwrap().leader_append_internal(EntryPayload::Blank);
     }
 
     /// Check if a raft node is in a state that allows to initialize.
@@ -975,6 +1326,7 @@ where C: RaftTypeConfig
 
     pub(crate) fn snapshot_handler(&mut self) -> SnapshotHandler<'_, '_, C, SM> {
         SnapshotHandler {
+            leader: self.leader.as_deref_mut(),
             state: &mut self.state,
             output: &mut self.output,
         }
@@ -1052,10 +1404,11 @@ where C: RaftTypeConfig
         }
     }
 
-    pub(crate) fn server_state_handler(&mut self) -> ServerStateHandler<'_, C> {
+    pub(crate) fn server_state_handler(&mut self) -> ServerStateHandler<'_, C, SM> {
         ServerStateHandler {
             config: &self.config,
             state: &mut self.state,
+            output: &mut self.output,
         }
     }
     pub(crate) fn establish_handler(&mut self) -> EstablishHandler<'_, C> {
@@ -1082,7 +1435,7 @@ mod engine_testing {
         /// without initializing related resource,
         /// such as setting up replication, propose blank log.
         pub(crate) fn testing_new_leader(&mut self) -> &mut crate::proposer::Leader<C, LeaderQuorumSet<C>> {
-            let leader = self.state.new_leader();
+            let leader = self.state.new_leader(self.config.quorum_policy.as_ref());
             self.leader = Some(Box::new(leader));
             self.leader.as_mut().unwrap()
         }
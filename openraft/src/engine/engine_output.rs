@@ -3,10 +3,12 @@ use std::collections::VecDeque;
 use crate::RaftTypeConfig;
 use crate::config::Config;
 use crate::engine::Command;
+use crate::engine::CommandKind;
 use crate::engine::Condition;
 use crate::engine::command_scheduler::CommandScheduler;
 use crate::engine::pending_responds::PendingResponds;
 use crate::engine::respond_command::PendingRespond;
+use crate::raft::RaftEvent;
 
 /// The entry of output from Engine to the runtime.
 #[derive(Debug)]
@@ -16,8 +18,14 @@ where C: RaftTypeConfig
     /// Command queue that needs to be executed by `RaftRuntime`.
     pub(crate) commands: VecDeque<Command<C, SM>>,
 
+    /// The approximate size in bytes of the commands in [`commands`](Self::commands).
+    pub(crate) queued_bytes: u64,
+
     /// Pending responds waiting for IO conditions to be met before sending.
     pub(crate) pending_responds: PendingResponds<C>,
+
+    /// Events to publish to the event subscribers, in the order they are emitted.
+    pub(crate) events: Vec<RaftEvent<C>>,
 }
 
 impl<C, SM> Default for EngineOutput<C, SM>
@@ -26,7 +34,9 @@ where C: RaftTypeConfig
     fn default() -> Self {
         Self {
             commands: VecDeque::new(),
+            queued_bytes: 0,
             pending_responds: PendingResponds::default(),
+            events: Vec::new(),
         }
     }
 }
@@ -38,7 +48,9 @@ where C: RaftTypeConfig
         let pending_capacity = 1024;
         Self {
             commands: VecDeque::with_capacity(command_buffer_size),
+            queued_bytes: 0,
             pending_responds: PendingResponds::new(pending_capacity),
+            events: Vec::new(),
         }
     }
 
@@ -46,12 +58,49 @@ where C: RaftTypeConfig
         self.commands.len()
     }
 
+    /// The approximate size in bytes of the queued commands, including the responds waiting for
+    /// an IO condition.
+    pub(crate) fn queued_bytes(&self) -> u64 {
+        let pending = self.pending_responds.len() * size_of::<Command<C, SM>>();
+        self.queued_bytes + pending as u64
+    }
+
     /// Push a command to the queue.
     pub(crate) fn push_command(&mut self, cmd: Command<C, SM>) {
         tracing::debug!("push command: {:?}", cmd);
+        self.queued_bytes += cmd.approx_size();
         self.commands.push_back(cmd)
     }
 
+    /// Emit an event to the event subscribers.
+    pub(crate) fn push_event(&mut self, event: RaftEvent<C>) {
+        tracing::debug!("push event: {}", event);
+        self.events.push(event);
+    }
+
+    /// Take all emitted events that are not yet published.
+    pub(crate) fn take_events(&mut self) -> Vec<RaftEvent<C>> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Drop the queued log IO commands, and the responds waiting for log IO.
+    ///
+    /// Used when the log storage fails: the dropped IO never completes, and the callers of the
+    /// dropped responds receive the storage error instead.
+    pub(crate) fn drop_log_io(&mut self) {
+        self.commands.retain(|cmd| match cmd {
+            Command::Respond {
+                when: Some(Condition::IOFlushed { .. } | Condition::LogFlushed { .. }),
+                ..
+            } => false,
+            cmd => cmd.kind() != CommandKind::Log,
+        });
+        self.queued_bytes = self.commands.iter().map(|cmd| cmd.approx_size()).sum();
+
+        self.pending_responds.on_log_io.clear();
+        self.pending_responds.on_log_flush.clear();
+    }
+
     /// Put the command to the head of the queue or to a separate pending queue.
     ///
     /// This will be used when the command is not ready to be executed.
@@ -87,6 +136,7 @@ where C: RaftTypeConfig
             }
 
             _ => {
+                self.queued_bytes += cmd.approx_size();
                 self.commands.push_front(cmd);
                 Err("Put back to the front of command queue")
             }
@@ -95,7 +145,9 @@ where C: RaftTypeConfig
 
     /// Pop the first command to run from the queue.
     pub(crate) fn pop_command(&mut self) -> Option<Command<C, SM>> {
-        self.commands.pop_front()
+        let cmd = self.commands.pop_front()?;
+        self.queued_bytes -= cmd.approx_size();
+        Some(cmd)
     }
 
     /// Iterate all queued commands.
@@ -106,12 +158,14 @@ where C: RaftTypeConfig
     /// Take all queued commands and clear the queue.
     #[cfg(test)]
     pub(crate) fn take_commands(&mut self) -> Vec<Command<C, SM>> {
+        self.queued_bytes = 0;
         self.commands.drain(..).collect()
     }
 
     /// Clear all queued commands.
     #[cfg(test)]
     pub(crate) fn clear_commands(&mut self) {
+        self.queued_bytes = 0;
         self.commands.clear()
     }
 
@@ -127,3 +181,42 @@ where C: RaftTypeConfig
         scheduler.merge_front_append_entries();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::EngineOutput;
+    use crate::batch::Batch;
+    use crate::engine::Command;
+    use crate::engine::testing::UTConfig;
+    use crate::impls::Vote;
+    use crate::testing::blank_ent;
+    use crate::vote::raft_vote::RaftVoteExt;
+
+    #[test]
+    fn test_engine_output_queued_bytes() {
+        let mut output: EngineOutput<UTConfig> = EngineOutput::new(8);
+        assert_eq!(0, output.queued_bytes());
+
+        let save_vote = Command::SaveVote { vote: Vote::new(1, 0) };
+        let append = Command::AppendEntries {
+            committed_vote: Vote::new(1, 0).into_committed(),
+            entries: Batch::of([blank_ent::<UTConfig>(1, 0, 1), blank_ent::<UTConfig>(1, 0, 2)]),
+        };
+        let (vote_size, append_size) = (save_vote.approx_size(), append.approx_size());
+        assert!(append_size > vote_size, "entries are counted");
+
+        output.push_command(save_vote);
+        output.push_command(append);
+        assert_eq!(vote_size + append_size, output.queued_bytes());
+
+        let cmd = output.pop_command().unwrap();
+        assert_eq!(append_size, output.queued_bytes());
+
+        let _ = output.postpone_command(cmd);
+        assert_eq!(vote_size + append_size, output.queued_bytes(), "put back to the queue");
+
+        output.pop_command();
+        output.pop_command();
+        assert_eq!(0, output.queued_bytes());
+    }
+}
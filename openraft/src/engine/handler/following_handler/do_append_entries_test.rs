@@ -110,6 +110,11 @@ fn test_follower_do_append_entries_one_membership_entry() -> anyhow::Result<()>
     eng.following_handler().do_append_entries(vec![blank_ent::<UTConfig>(3, 1, 4), EntryOf::<UTConfig> {
         log_id: log_id(3, 1, 5),
         payload: EntryPayload::<u64, u64, ()>::Membership(m34()),
+        apply_scope: None,
+        timestamp: None,
+        deadline: None,
+        expire_from: None,
+        chain_hash: None,
     }]);
 
     assert_eq!(None, eng.state.log_ids.purged());
@@ -144,6 +149,11 @@ fn test_follower_do_append_entries_one_membership_entry() -> anyhow::Result<()>
                 EntryOf::<UTConfig> {
                     log_id: log_id(3, 1, 5),
                     payload: EntryPayload::<u64, u64, ()>::Membership(m34()),
+                    apply_scope: None,
+                    timestamp: None,
+                    deadline: None,
+                    expire_from: None,
+                    chain_hash: None,
                 },
             ])
         },],
@@ -16,6 +16,7 @@ use crate::engine::testing::UTConfig;
 use crate::engine::testing::log_id;
 use crate::raft_state::IOId;
 use crate::raft_state::io_state::log_io_id::LogIOId;
+use crate::storage::SnapshotLocator;
 use crate::type_config::TypeConfigExt;
 use crate::type_config::alias::SnapshotMetaOf;
 use crate::type_config::alias::SnapshotOf;
@@ -50,6 +51,7 @@ fn eng() -> Engine<UTConfig> {
         last_log_id: Some(log_id(2, 1, 2)),
         last_membership: StoredMembershipOf::<UTConfig>::new(Some(log_id(1, 1, 1)), m12()),
         snapshot_id: "1-2-3-4".to_string(),
+        base_snapshot_id: None,
     };
     eng.state.server_state = eng.calc_server_state();
 
@@ -67,6 +69,7 @@ fn test_install_snapshot_lt_last_snapshot() -> anyhow::Result<()> {
             last_log_id: Some(log_id(2, 1, 2)),
             last_membership: StoredMembershipOf::<UTConfig>::new(Some(log_id(1, 1, 1)), m1234()),
             snapshot_id: "1-2-3-4".to_string(),
+            base_snapshot_id: None,
         },
         snapshot: Cursor::new(vec![0u8]),
     });
@@ -78,6 +81,7 @@ fn test_install_snapshot_lt_last_snapshot() -> anyhow::Result<()> {
             last_log_id: Some(log_id(2, 1, 2)),
             last_membership: StoredMembershipOf::<UTConfig>::new(Some(log_id(1, 1, 1)), m12()),
             snapshot_id: "1-2-3-4".to_string(),
+            base_snapshot_id: None,
         },
         eng.state.snapshot_meta
     );
@@ -99,6 +103,7 @@ fn test_install_snapshot_lt_committed() -> anyhow::Result<()> {
             last_log_id: Some(log_id(4, 1, 5)),
             last_membership: StoredMembershipOf::<UTConfig>::new(Some(log_id(1, 1, 1)), m1234()),
             snapshot_id: "1-2-3-4".to_string(),
+            base_snapshot_id: None,
         },
         snapshot: Cursor::new(vec![0u8]),
     });
@@ -110,6 +115,7 @@ fn test_install_snapshot_lt_committed() -> anyhow::Result<()> {
             last_log_id: Some(log_id(2, 1, 2)),
             last_membership: StoredMembershipOf::<UTConfig>::new(Some(log_id(1, 1, 1)), m12()),
             snapshot_id: "1-2-3-4".to_string(),
+            base_snapshot_id: None,
         },
         eng.state.snapshot_meta
     );
@@ -128,6 +134,7 @@ fn test_install_snapshot_not_conflict() -> anyhow::Result<()> {
             last_log_id: Some(log_id(4, 1, 6)),
             last_membership: StoredMembershipOf::<UTConfig>::new(Some(log_id(1, 1, 1)), m1234()),
             snapshot_id: "1-2-3-4".to_string(),
+            base_snapshot_id: None,
         },
         snapshot: Cursor::new(vec![0u8]),
     });
@@ -144,6 +151,7 @@ fn test_install_snapshot_not_conflict() -> anyhow::Result<()> {
             last_log_id: Some(log_id(4, 1, 6)),
             last_membership: StoredMembershipOf::<UTConfig>::new(Some(log_id(1, 1, 1)), m1234()),
             snapshot_id: "1-2-3-4".to_string(),
+            base_snapshot_id: None,
         },
         eng.state.snapshot_meta
     );
@@ -163,6 +171,7 @@ fn test_install_snapshot_not_conflict() -> anyhow::Result<()> {
                         last_log_id: Some(log_id(4, 1, 6)),
                         last_membership: StoredMembershipOf::<UTConfig>::new(Some(log_id(1, 1, 1)), m1234()),
                         snapshot_id: "1-2-3-4".to_string(),
+                        base_snapshot_id: None,
                     },
                     snapshot: Cursor::new(vec![0u8]),
                 },
@@ -201,6 +210,7 @@ fn test_install_snapshot_conflict() -> anyhow::Result<()> {
             last_log_id: Some(log_id(2, 1, 2)),
             last_membership: StoredMembershipOf::<UTConfig>::new(Some(log_id(1, 1, 1)), m12()),
             snapshot_id: "1-2-3-4".to_string(),
+            base_snapshot_id: None,
         };
 
         eng.state.server_state = eng.calc_server_state();
@@ -213,6 +223,7 @@ fn test_install_snapshot_conflict() -> anyhow::Result<()> {
             last_log_id: Some(log_id(5, 1, 6)),
             last_membership: StoredMembershipOf::<UTConfig>::new(Some(log_id(1, 1, 1)), m1234()),
             snapshot_id: "1-2-3-4".to_string(),
+            base_snapshot_id: None,
         },
         snapshot: Cursor::new(vec![0u8]),
     });
@@ -229,6 +240,7 @@ fn test_install_snapshot_conflict() -> anyhow::Result<()> {
             last_log_id: Some(log_id(5, 1, 6)),
             last_membership: StoredMembershipOf::<UTConfig>::new(Some(log_id(1, 1, 1)), m1234()),
             snapshot_id: "1-2-3-4".to_string(),
+            base_snapshot_id: None,
         },
         eng.state.snapshot_meta
     );
@@ -252,6 +264,7 @@ fn test_install_snapshot_conflict() -> anyhow::Result<()> {
                         last_log_id: Some(log_id(5, 1, 6)),
                         last_membership: StoredMembershipOf::<UTConfig>::new(Some(log_id(1, 1, 1)), m1234()),
                         snapshot_id: "1-2-3-4".to_string(),
+                        base_snapshot_id: None,
                     },
                     snapshot: Cursor::new(vec![0u8]),
                 },
@@ -275,6 +288,7 @@ fn test_install_snapshot_advance_last_log_id() -> anyhow::Result<()> {
             last_log_id: Some(log_id(100, 1, 100)),
             last_membership: StoredMembershipOf::<UTConfig>::new(Some(log_id(1, 1, 1)), m1234()),
             snapshot_id: "1-2-3-4".to_string(),
+            base_snapshot_id: None,
         },
         snapshot: Cursor::new(vec![0u8]),
     });
@@ -291,6 +305,7 @@ fn test_install_snapshot_advance_last_log_id() -> anyhow::Result<()> {
             last_log_id: Some(log_id(100, 1, 100)),
             last_membership: StoredMembershipOf::<UTConfig>::new(Some(log_id(1, 1, 1)), m1234()),
             snapshot_id: "1-2-3-4".to_string(),
+            base_snapshot_id: None,
         },
         eng.state.snapshot_meta
     );
@@ -314,6 +329,7 @@ fn test_install_snapshot_advance_last_log_id() -> anyhow::Result<()> {
                         last_log_id: Some(log_id(100, 1, 100)),
                         last_membership: StoredMembershipOf::<UTConfig>::new(Some(log_id(1, 1, 1)), m1234()),
                         snapshot_id: "1-2-3-4".to_string(),
+                        base_snapshot_id: None,
                     },
                     snapshot: Cursor::new(vec![0u8]),
                 },
@@ -339,6 +355,7 @@ fn test_install_snapshot_update_accepted() -> anyhow::Result<()> {
             last_log_id: Some(log_id(100, 1, 100)),
             last_membership: StoredMembershipOf::<UTConfig>::new(Some(log_id(1, 1, 1)), m1234()),
             snapshot_id: "1-2-3-4".to_string(),
+            base_snapshot_id: None,
         },
         snapshot: Cursor::new(vec![0u8]),
     });
@@ -360,3 +377,45 @@ fn test_install_snapshot_update_accepted() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_install_snapshot_locator() -> anyhow::Result<()> {
+    // A snapshot stored out of band updates the state the same way, but the state machine is
+    // asked to fetch it with the locator.
+    let mut eng = eng();
+
+    let locator = SnapshotLocator::<UTConfig> {
+        meta: SnapshotMetaOf::<UTConfig> {
+            last_log_id: Some(log_id(4, 1, 6)),
+            last_membership: StoredMembershipOf::<UTConfig>::new(Some(log_id(1, 1, 1)), m1234()),
+            snapshot_id: "1-2-3-4".to_string(),
+            base_snapshot_id: None,
+        },
+        locator: "s3://bucket/1-2-3-4".to_string(),
+    };
+
+    let cond = eng.following_handler().install_snapshot_locator(locator.clone());
+
+    assert_eq!(
+        Some(Condition::Snapshot::<UTConfig> {
+            log_id: log_id(4, 1, 6)
+        }),
+        cond
+    );
+
+    assert_eq!(locator.meta, eng.state.snapshot_meta);
+    assert_eq!(Some(&log_id(4, 1, 6)), eng.state.local_committed());
+    assert_eq!(
+        vec![
+            //
+            Command::from(sm::Command::install_snapshot_locator(
+                locator,
+                LogIOId::new(Vote::new(2, 1).into_committed(), Some(log_id(4, 1, 6))),
+            )),
+            Command::PurgeLog { upto: log_id(4, 1, 6) },
+        ],
+        eng.output.take_commands()
+    );
+
+    Ok(())
+}
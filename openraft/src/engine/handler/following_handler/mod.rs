@@ -20,11 +20,14 @@ use crate::entry::raft_entry_ext::RaftEntryExt;
 use crate::errors::ConflictingLogId;
 use crate::errors::RejectAppendEntries;
 use crate::log_id::option_raft_log_id_ext::OptionRaftLogIdExt;
+use crate::raft::RaftEvent;
 use crate::raft_state::IOId;
 use crate::raft_state::LogStateReader;
 use crate::raft_state::io_state::log_io_id::LogIOId;
+use crate::storage::SnapshotLocator;
 use crate::type_config::alias::CommittedVoteOf;
 use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::SnapshotMetaOf;
 use crate::type_config::alias::SnapshotOf;
 use crate::type_config::alias::StoredMembershipOf;
 use crate::vote::raft_vote::RaftVoteExt;
@@ -212,8 +215,16 @@ where C: RaftTypeConfig
 
         let m = Arc::new(membership);
 
+        let prev_committed = self.state.membership_state.committed().log_id().clone();
         self.state.membership_state.install_membership_snapshot(m);
 
+        let committed = self.state.membership_state.committed();
+        if committed.log_id() > &prev_committed {
+            self.output.push_event(RaftEvent::MembershipCommitted {
+                membership: committed.clone(),
+            });
+        }
+
         self.server_state_handler().update_server_state_if_changed();
     }
 
@@ -237,9 +248,47 @@ where C: RaftTypeConfig
     ///   current state).
     #[tracing::instrument(level = "debug", skip_all)]
     pub(crate) fn install_full_snapshot(&mut self, snapshot: SnapshotOf<C>) -> Option<Condition<C>> {
-        let meta = &snapshot.meta;
-        tracing::info!("install full snapshot, meta: {:?}", meta);
+        tracing::info!("install full snapshot, meta: {:?}", snapshot.meta);
+
+        let meta = snapshot.meta.clone();
+        self.install_snapshot(meta, |log_io_id| {
+            sm::Command::install_full_snapshot(snapshot, log_io_id)
+        })
+    }
+
+    /// Install a snapshot stored out of band, which the state machine fetches by itself.
+    ///
+    /// It updates the raft state the same way as [`Self::install_full_snapshot`] does.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) fn install_snapshot_locator(&mut self, locator: SnapshotLocator<C>) -> Option<Condition<C>> {
+        tracing::info!("install snapshot locator: {}", locator);
+
+        let meta = locator.meta.clone();
+        self.install_snapshot(meta, |log_io_id| {
+            sm::Command::install_snapshot_locator(locator, log_io_id)
+        })
+    }
+
+    /// Install only the metadata of a snapshot, on a witness.
+    ///
+    /// It updates the raft state the same way as [`Self::install_full_snapshot`] does, while the
+    /// state machine records only the last applied log id and the membership config.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) fn install_snapshot_meta(&mut self, meta: SnapshotMetaOf<C>) -> Option<Condition<C>> {
+        tracing::info!("install snapshot meta: {}", meta);
 
+        self.install_snapshot(meta.clone(), |log_io_id| {
+            sm::Command::install_snapshot_meta(meta, log_io_id)
+        })
+    }
+
+    /// Update the raft state for installing a snapshot with `meta`, and push the state machine
+    /// command built by `sm_cmd` to install the snapshot data.
+    fn install_snapshot(
+        &mut self,
+        meta: SnapshotMetaOf<C>,
+        sm_cmd: impl FnOnce(LogIOId<C>) -> sm::Command<C, SM>,
+    ) -> Option<Condition<C>> {
         let snap_last_log_id = meta.last_log_id.clone();
 
         if snap_last_log_id.as_ref() <= self.state.local_committed() {
@@ -280,7 +329,7 @@ where C: RaftTypeConfig
 
         self.membership_install_snapshot(meta.last_membership.clone());
 
-        self.output.push_command(Command::from(sm::Command::install_full_snapshot(snapshot, log_io_id)));
+        self.output.push_command(Command::from(sm_cmd(log_io_id)));
 
         self.state.purge_upto = Some(snap_last_log_id.clone());
         self.log_handler().purge_log();
@@ -321,15 +370,17 @@ where C: RaftTypeConfig
 
     fn snapshot_handler(&mut self) -> SnapshotHandler<'_, '_, C, SM> {
         SnapshotHandler {
+            leader: None,
             state: self.state,
             output: self.output,
         }
     }
 
-    fn server_state_handler(&mut self) -> ServerStateHandler<'_, C> {
+    fn server_state_handler(&mut self) -> ServerStateHandler<'_, C, SM> {
         ServerStateHandler {
             config: self.config,
             state: self.state,
+            output: self.output,
         }
     }
 }
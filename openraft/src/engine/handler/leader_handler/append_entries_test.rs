@@ -1,6 +1,9 @@
 use std::sync::Arc;
 use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
 
+use maplit::btreemap;
 use maplit::btreeset;
 #[allow(unused_imports)]
 use pretty_assertions::assert_eq;
@@ -21,6 +24,7 @@ use crate::engine::testing::UTConfig;
 use crate::engine::testing::log_id;
 use crate::entry::RaftEntry;
 use crate::entry::payload::EntryPayload;
+use crate::errors::ReservedIndexMismatch;
 use crate::log_id_range::LogIdRange;
 use crate::progress::entry::ProgressEntry;
 use crate::progress::inflight_id::InflightId;
@@ -113,6 +117,66 @@ fn test_leader_append_entries_empty() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_leader_append_reserved_entries() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.output.take_commands();
+
+    let reserved = eng.try_leader_handler()?.reserve_log_indexes(3);
+    assert_eq!(4..7, reserved);
+    assert_eq!(eng.output.take_commands(), vec![], "reserving writes nothing");
+
+    tracing::info!("--- reject entries that do not start at the first reserved index");
+    {
+        let got = eng.try_leader_handler()?.leader_append_reserved_entries(5, [EntryPayload::Blank], None, &[]);
+
+        assert_eq!(
+            Err(ReservedIndexMismatch {
+                index: 5,
+                count: 1,
+                reserved: Some(4..7),
+            }),
+            got
+        );
+        assert_eq!(Some(&log_id(2, 1, 3)), eng.state.last_log_id());
+        assert_eq!(eng.output.take_commands(), vec![]);
+    }
+
+    tracing::info!("--- append entries at the reserved indexes");
+    {
+        let got = eng.try_leader_handler()?.leader_append_reserved_entries(
+            4,
+            [EntryPayload::Blank, EntryPayload::Blank],
+            None,
+            &[],
+        );
+
+        assert_eq!(Ok(Some(LeaderLogIds::new(committed_leader_id(3, 1), 4, 5))), got);
+        assert_eq!(Some(&log_id(3, 1, 5)), eng.state.last_log_id());
+        assert!(eng.output.take_commands().iter().any(|c| matches!(c, Command::AppendEntries { .. })));
+    }
+
+    tracing::info!("--- other entries drop the reservation");
+    {
+        eng.try_leader_handler()?.leader_append_entries([EntryPayload::Blank]);
+        eng.output.take_commands();
+
+        let got = eng.try_leader_handler()?.leader_append_reserved_entries(6, [EntryPayload::Blank], None, &[]);
+
+        assert_eq!(
+            Err(ReservedIndexMismatch {
+                index: 6,
+                count: 1,
+                reserved: None,
+            }),
+            got
+        );
+        assert_eq!(Some(&log_id(3, 1, 6)), eng.state.last_log_id());
+    }
+
+    Ok(())
+}
+
 #[test]
 fn test_leader_append_entries_normal() -> anyhow::Result<()> {
     let mut eng = eng();
@@ -178,6 +242,97 @@ fn test_leader_append_entries_normal() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_leader_append_internal() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.output.take_commands();
+
+    let got = eng.try_leader_handler()?.leader_append_internal(EntryPayload::Blank);
+
+    assert_eq!(log_id(3, 1, 4), got);
+    assert_eq!(Some(&log_id(3, 1, 4)), eng.state.last_log_id());
+
+    Ok(())
+}
+
+#[test]
+fn test_leader_append_entries_with_timestamp() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.config.entry_timestamp = true;
+    eng.output.take_commands();
+
+    let now_ms = || SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as u64;
+
+    let before = now_ms();
+    eng.try_leader_handler()?.leader_append_entries([EntryPayload::Blank, EntryPayload::Blank]);
+    let after = now_ms();
+
+    let Some(Command::AppendEntries { entries, .. }) = eng.output.take_commands().into_iter().next() else {
+        panic!("expect AppendEntries command");
+    };
+
+    let timestamps = entries.into_iter().map(|e| e.timestamp()).collect::<Vec<_>>();
+    let ts = timestamps[0].expect("entries are stamped");
+    assert!(before <= ts && ts <= after);
+    assert_eq!(
+        vec![Some(ts), Some(ts)],
+        timestamps,
+        "entries in a batch share the timestamp"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_leader_append_entries_with_deadline() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.output.take_commands();
+
+    let deadlines = [Some(Duration::from_millis(500)), None];
+
+    tracing::info!("--- without timestamps, a deadline is not set");
+    {
+        eng.try_leader_handler()?.leader_append_entries_in_scope(
+            [EntryPayload::Blank, EntryPayload::Blank],
+            None,
+            &deadlines,
+        );
+
+        let Some(Command::AppendEntries { entries, .. }) = eng.output.take_commands().into_iter().next() else {
+            panic!("expect AppendEntries command");
+        };
+        let got = entries.into_iter().map(|e| e.deadline()).collect::<Vec<_>>();
+        assert_eq!(vec![None, None], got);
+    }
+
+    tracing::info!("--- the deadline is the timestamp plus the time left");
+    {
+        eng.config.entry_timestamp = true;
+
+        eng.try_leader_handler()?.leader_append_entries_in_scope(
+            [EntryPayload::Blank, EntryPayload::Blank],
+            None,
+            &deadlines,
+        );
+
+        let Some(Command::AppendEntries { entries, .. }) = eng.output.take_commands().into_iter().next() else {
+            panic!("expect AppendEntries command");
+        };
+        let got = entries.into_iter().map(|e| (e.index(), e.timestamp(), e.deadline())).collect::<Vec<_>>();
+        let ts = got[0].1.expect("entries are stamped");
+        assert_eq!(vec![(6, Some(ts), Some(ts + 500)), (7, Some(ts), None)], got);
+
+        let leader = eng.leader.as_ref().unwrap();
+        assert_eq!(
+            btreemap! {6 => ts + 500},
+            leader.write_deadlines,
+            "the leader tracks the deadline until the write is committed"
+        );
+    }
+
+    Ok(())
+}
+
 #[test]
 fn test_leader_append_entries_single_node_leader() -> anyhow::Result<()> {
     let mut eng = eng();
@@ -1,20 +1,35 @@
+use std::collections::BTreeSet;
+use std::ops::Range;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use crate::ChangeMembers;
+use crate::LogIdOptionExt;
 use crate::RaftState;
 use crate::RaftTypeConfig;
+use crate::core::replication_lag;
 use crate::engine::Command;
 use crate::engine::EngineConfig;
 use crate::engine::EngineOutput;
 use crate::engine::handler::replication_handler::ReplicationHandler;
 use crate::engine::leader_log_ids::LeaderLogIds;
+use crate::entry::ApplyScope;
 use crate::entry::RaftEntry;
 use crate::entry::RaftPayload;
+use crate::entry::payload::EntryPayload;
+use crate::errors::ReservedIndexMismatch;
+use crate::progress::Progress;
 use crate::proposer::Leader;
 use crate::proposer::LeaderQuorumSet;
+use crate::raft::RaftEvent;
 use crate::raft::message::TransferLeaderRequest;
 use crate::raft_state::IOId;
 use crate::replication::ReplicationSessionId;
 use crate::type_config::alias::BatchOf;
 use crate::type_config::alias::CommittedLeaderIdOf;
 use crate::type_config::alias::EntryPayloadOf;
+use crate::type_config::alias::InstantOf;
 use crate::type_config::alias::LogIdOf;
 
 #[cfg(test)]
@@ -25,6 +40,10 @@ mod get_read_log_id_test;
 mod send_heartbeat_test;
 #[cfg(test)]
 mod transfer_leader_test;
+#[cfg(test)]
+mod try_evict_unreachable_test;
+#[cfg(test)]
+mod try_promote_caught_up_test;
 
 /// Handle leader operations.
 ///
@@ -52,20 +71,115 @@ where C: RaftTypeConfig
     /// committed.
     ///
     /// TODO(xp): if vote indicates this node is not the leader, refuse append
-    #[tracing::instrument(level = "debug", skip(self, payloads))]
     pub(crate) fn leader_append_entries<I>(&mut self, payloads: I) -> Option<LeaderLogIds<CommittedLeaderIdOf<C>>>
     where I: IntoIterator<Item = EntryPayloadOf<C>> + AsRef<[EntryPayloadOf<C>]> {
+        self.leader_append_entries_in_scope(payloads, None, &[])
+    }
+
+    /// Append new log entries by a leader, each applied only by the nodes in `scope`.
+    ///
+    /// `deadlines` is the time left to the deadline of each entry by its position, see
+    /// [`RaftEntry::deadline()`]; an entry without one has no deadline.
+    ///
+    /// The caller has to guarantee the log entry type stores the scope, see
+    /// [`Self::supports_apply_scope()`].
+    #[tracing::instrument(level = "debug", skip(self, payloads))]
+    pub(crate) fn leader_append_entries_in_scope<I>(
+        &mut self,
+        payloads: I,
+        scope: Option<ApplyScope<C::NodeId>>,
+        deadlines: &[Option<Duration>],
+    ) -> Option<LeaderLogIds<CommittedLeaderIdOf<C>>>
+    where
+        I: IntoIterator<Item = EntryPayloadOf<C>> + AsRef<[EntryPayloadOf<C>]>,
+    {
         let log_ids = self.leader.assign_log_ids(payloads.as_ref().len())?;
+        self.append_entries_with_log_ids(log_ids.clone(), payloads, scope, deadlines);
+        Some(log_ids)
+    }
+
+    /// Reserve `count` log indexes, to be filled later with
+    /// [`leader_append_reserved_entries()`](Self::leader_append_reserved_entries).
+    ///
+    /// The reservation is dropped when any other entry is appended, or when this leader is gone.
+    pub(crate) fn reserve_log_indexes(&mut self, count: u64) -> Range<u64> {
+        let reserved = self.leader.reserve_log_indexes(count);
+        tracing::debug!("reserved log indexes: {:?}", reserved);
+        reserved
+    }
+
+    /// Append new log entries to the reserved log indexes starting at `index`.
+    ///
+    /// It returns [`ReservedIndexMismatch`] without appending anything if the entries do not
+    /// continue the reservation, i.e., `index` is not the first unfilled reserved index, or the
+    /// entries do not fit in the reservation.
+    #[tracing::instrument(level = "debug", skip(self, payloads))]
+    pub(crate) fn leader_append_reserved_entries<I>(
+        &mut self,
+        index: u64,
+        payloads: I,
+        scope: Option<ApplyScope<C::NodeId>>,
+        deadlines: &[Option<Duration>],
+    ) -> Result<Option<LeaderLogIds<CommittedLeaderIdOf<C>>>, ReservedIndexMismatch>
+    where
+        I: IntoIterator<Item = EntryPayloadOf<C>> + AsRef<[EntryPayloadOf<C>]>,
+    {
+        let Some(log_ids) = self.leader.assign_reserved_log_ids(index, payloads.as_ref().len())? else {
+            return Ok(None);
+        };
+        self.append_entries_with_log_ids(log_ids.clone(), payloads, scope, deadlines);
+        Ok(Some(log_ids))
+    }
 
+    fn append_entries_with_log_ids<I>(
+        &mut self,
+        log_ids: LeaderLogIds<CommittedLeaderIdOf<C>>,
+        payloads: I,
+        scope: Option<ApplyScope<C::NodeId>>,
+        deadlines: &[Option<Duration>],
+    ) where
+        I: IntoIterator<Item = EntryPayloadOf<C>>,
+    {
         self.state.extend_log_ids_from_same_leader(log_ids.clone());
 
+        // All the entries in a batch share the propose time.
+        let timestamp = if self.config.entry_timestamp {
+            Some(Self::wall_clock_ms())
+        } else {
+            None
+        };
+
+        // The first entry of a leader that re-establishes itself records what its previous term
+        // did not commit.
+        let mut expire_from = self.leader.expire_from.take();
+
         let mut membership_entry = None;
+        let mut write_deadlines = vec![];
         let entries: BatchOf<C, _> = payloads
             .into_iter()
-            .zip(log_ids.clone())
-            .map(|(payload, log_id)| {
+            .zip(log_ids)
+            .enumerate()
+            .map(|(i, (payload, log_id))| {
                 tracing::debug!("assign log id: {}", log_id);
-                let entry = C::Entry::new(log_id, payload);
+                let mut entry = C::Entry::new(log_id, payload);
+                if scope.is_some() {
+                    entry.set_apply_scope(scope.clone());
+                }
+                if timestamp.is_some() {
+                    entry.set_timestamp(timestamp);
+                }
+                if expire_from.is_some() {
+                    entry.set_expire_from(expire_from.take());
+                }
+                // Whether a write expires is decided by the timestamp of the next leader's first
+                // entry, a deadline is useless without timestamps.
+                let deadline = timestamp.zip(deadlines.get(i).copied().flatten());
+                if let Some((ts, left)) = deadline {
+                    let deadline = ts + left.as_millis() as u64;
+                    if entry.set_deadline(Some(deadline)) {
+                        write_deadlines.push((entry.index(), deadline));
+                    }
+                }
                 if let Some(m) = entry.get_membership() {
                     debug_assert!(
                         membership_entry.is_none(),
@@ -77,6 +191,8 @@ where C: RaftTypeConfig
             })
             .collect();
 
+        self.leader.write_deadlines.extend(write_deadlines);
+
         self.state.accept_log_io(IOId::new_log_io(
             self.leader.committed_vote.clone(),
             self.leader.last_log_id().cloned(),
@@ -99,8 +215,216 @@ where C: RaftTypeConfig
         }
 
         rh.initiate_replication();
+    }
 
-        Some(log_ids)
+    /// Append a single entry proposed by Openraft itself, such as a blank entry or a membership
+    /// entry, rather than by a client write.
+    ///
+    /// Unlike [`leader_append_entries()`](Self::leader_append_entries), this can not fail: a log id
+    /// is always assigned to a single entry. Thus there is no error to report to a caller.
+    pub(crate) fn leader_append_internal(&mut self, payload: EntryPayloadOf<C>) -> LogIdOf<C> {
+        let log_ids = self.leader_append_entries([payload]);
+        log_ids.expect("a log id is always assigned to a single entry").last_log_id()
+    }
+
+    /// Propose a membership change to promote the learners that have caught up to voters.
+    ///
+    /// The learners are registered by [`ChangeMembers::AddVotersWhenCaughtUp`]. A learner is
+    /// caught up when its matching log is within `promote_lag_threshold` of the leader's last log.
+    /// Promoting learners enters a joint config, which is flattened to a uniform config once it
+    /// is committed.
+    ///
+    /// Nothing is proposed while the last membership is not committed; it is called again when
+    /// the replication progress or the committed log id changes.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) fn try_promote_caught_up(&mut self) {
+        if self.leader.promote_when_caught_up.is_empty() && self.leader.promoting.is_none() {
+            return;
+        }
+
+        if self.leader.get_transfer_to().is_some() {
+            return;
+        }
+
+        if self.state.membership_state.change_handler().ensure_committed().is_err() {
+            return;
+        }
+
+        let effective = self.state.membership_state.effective().clone();
+
+        if let Some(promoting) = self.leader.promoting.take()
+            && effective.log_id().as_ref() == Some(&promoting)
+            && effective.membership().get_joint_config().len() > 1
+        {
+            tracing::info!("flatten the joint membership that promotes learners: {}", promoting);
+
+            self.append_membership_change(ChangeMembers::AddVoterIds(Default::default()), true);
+            return;
+        }
+
+        // A node removed from the membership or already a voter is no longer to promote.
+        let membership = effective.membership();
+        self.leader.promote_when_caught_up.retain(|id| membership.contains(id) && !membership.is_voter(id));
+
+        let last_log_index = self.leader.last_log_id().index();
+        let threshold = self.config.promote_lag_threshold;
+
+        let caught_up = self
+            .leader
+            .promote_when_caught_up
+            .iter()
+            .filter(|id| {
+                let Some(p) = self.leader.progress.try_get(id) else {
+                    return false;
+                };
+                replication_lag(&p.matching().index(), &last_log_index) <= threshold
+            })
+            .cloned()
+            .collect::<BTreeSet<_>>();
+
+        if caught_up.is_empty() {
+            return;
+        }
+
+        tracing::info!("promote caught up learners to voters: {:?}", caught_up);
+
+        for id in caught_up.iter() {
+            self.leader.promote_when_caught_up.remove(id);
+        }
+
+        if let Some((log_id, true)) = self.append_membership_change(ChangeMembers::AddVoterIds(caught_up), true) {
+            self.leader.promoting = Some(log_id);
+        }
+    }
+
+    /// Propose a membership change to evict the nodes that have not acknowledged this leader for
+    /// longer than `evict_unreachable_after`, and emit a [`RaftEvent::NodeEvicted`] for each.
+    ///
+    /// An unreachable voter is removed, or demoted to a learner if `evict_demote_voters` is
+    /// enabled, in which case unreachable learners are left in place. Evicting voters enters a
+    /// joint config, which is flattened to a uniform config once it is committed.
+    ///
+    /// Nothing is proposed while the last membership is not committed or is a joint config; it is
+    /// called again at the next tick.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) fn try_evict_unreachable(&mut self, now: InstantOf<C>) {
+        let Some(grace) = self.config.evict_unreachable_after else {
+            return;
+        };
+        let demote = self.config.evict_demote_voters;
+
+        if self.leader.get_transfer_to().is_some() {
+            return;
+        }
+
+        if self.state.membership_state.change_handler().ensure_committed().is_err() {
+            return;
+        }
+
+        let effective = self.state.membership_state.effective().clone();
+
+        if let Some(evicting) = self.leader.evicting.take()
+            && effective.log_id().as_ref() == Some(&evicting)
+            && effective.membership().get_joint_config().len() > 1
+        {
+            tracing::info!(
+                "flatten the joint membership that evicts unreachable voters: {}",
+                evicting
+            );
+
+            self.append_membership_change(ChangeMembers::AddVoterIds(Default::default()), demote);
+            return;
+        }
+
+        let membership = effective.membership();
+        if membership.get_joint_config().len() > 1 {
+            return;
+        }
+
+        let unreachable = self
+            .leader
+            .unreachable_nodes(now, grace)
+            .into_iter()
+            .filter(|(id, _)| membership.contains(id))
+            .filter(|(id, _)| !demote || membership.is_voter(id))
+            .collect::<Vec<_>>();
+
+        if unreachable.is_empty() {
+            return;
+        }
+
+        let (voters, learners): (BTreeSet<_>, BTreeSet<_>) =
+            unreachable.iter().map(|(id, _)| id.clone()).partition(|id| membership.is_voter(id));
+
+        tracing::warn!(
+            "evict unreachable nodes: voters: {:?}, learners: {:?}, demote voters: {}",
+            voters,
+            learners,
+            demote
+        );
+
+        let mut changes = vec![];
+        if !voters.is_empty() {
+            changes.push(ChangeMembers::RemoveVoters(voters));
+        }
+        if !learners.is_empty() {
+            changes.push(ChangeMembers::RemoveNodes(learners));
+        }
+
+        let Some((log_id, is_joint)) = self.append_membership_change(ChangeMembers::Batch(changes), demote) else {
+            return;
+        };
+
+        if is_joint {
+            self.leader.evicting = Some(log_id);
+        }
+
+        for (node_id, unreachable_for) in unreachable {
+            self.output.push_event(RaftEvent::NodeEvicted {
+                demoted: demote && membership.is_voter(&node_id),
+                node_id,
+                unreachable_for,
+            });
+        }
+    }
+
+    /// Append a membership entry built from the committed membership and `change`.
+    ///
+    /// Returns the log id of the entry and whether it is a joint membership, or `None` if the
+    /// change can not be applied.
+    fn append_membership_change(
+        &mut self,
+        change: ChangeMembers<C::NodeId, C::Node>,
+        retain: bool,
+    ) -> Option<(LogIdOf<C>, bool)> {
+        let membership = match self.state.membership_state.change_handler().apply(change, retain) {
+            Ok(m) => m,
+            Err(e) => {
+                tracing::warn!("failed to build the membership change: {}", e);
+                return None;
+            }
+        };
+
+        let is_joint = membership.get_joint_config().len() > 1;
+        let log_id = self.leader_append_internal(EntryPayload::Membership(membership));
+
+        Some((log_id, is_joint))
+    }
+
+    /// Whether the log entry type stores an [`ApplyScope`], which is required to append entries
+    /// with [`Self::leader_append_entries_in_scope()`].
+    pub(crate) fn supports_apply_scope(&self) -> bool {
+        let log_id = LogIdOf::<C>::new(
+            self.leader.committed_vote.committed_leader_id(),
+            self.leader.last_log_id().next_index(),
+        );
+        let mut probe = C::Entry::new_blank(log_id);
+        probe.set_apply_scope(Some(ApplyScope::Except(Default::default())))
+    }
+
+    /// The wall-clock time in milliseconds since the UNIX epoch, to stamp proposed entries.
+    pub(crate) fn wall_clock_ms() -> u64 {
+        SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or_default()
     }
 
     #[tracing::instrument(level = "debug", skip_all)]
@@ -0,0 +1,143 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+#[allow(unused_imports)]
+use pretty_assertions::assert_eq;
+#[allow(unused_imports)]
+use pretty_assertions::assert_ne;
+#[allow(unused_imports)]
+use pretty_assertions::assert_str_eq;
+
+use crate::Membership;
+use crate::MembershipState;
+use crate::Vote;
+use crate::engine::Engine;
+use crate::engine::testing::UTConfig;
+use crate::engine::testing::log_id;
+use crate::progress::Progress;
+use crate::raft::RaftEvent;
+use crate::raft_state::LogStateReader;
+use crate::type_config::TypeConfigExt;
+use crate::type_config::alias::InstantOf;
+use crate::type_config::alias::StoredMembershipOf;
+use crate::utime::Leased;
+
+/// members: {1,2,3}, learners: {4}
+fn m123_4() -> Membership<u64, ()> {
+    Membership::<u64, ()>::new_with_defaults(vec![btreeset! {1,2,3}], btreeset! {4})
+}
+
+/// Node 1 is the leader; node 2 acked recently, node 3 and 4 never acked.
+fn eng(demote: bool) -> (Engine<UTConfig>, InstantOf<UTConfig>) {
+    let mut eng = Engine::testing_default(0);
+    eng.state.enable_validation(false); // Disable validation for incomplete state
+
+    eng.config.id = 1;
+    eng.config.evict_unreachable_after = Some(Duration::from_millis(100));
+    eng.config.evict_demote_voters = demote;
+    eng.state.vote = Leased::new(
+        UTConfig::<()>::now(),
+        Duration::from_millis(500),
+        Vote::new_committed(3, 1),
+    );
+    eng.state.log_ids.append(log_id(1, 1, 1));
+    eng.state.log_ids.append(log_id(2, 1, 3));
+    eng.state.membership_state = MembershipState::new(
+        Arc::new(StoredMembershipOf::<UTConfig>::new(Some(log_id(1, 1, 1)), m123_4())),
+        Arc::new(StoredMembershipOf::<UTConfig>::new(Some(log_id(1, 1, 1)), m123_4())),
+    );
+
+    let leader = eng.testing_new_leader();
+    let since = *leader.leader_since.instant;
+    leader.clock_progress.increase_to(&2, Some(since + Duration::from_millis(150))).ok();
+
+    eng.state.server_state = eng.calc_server_state();
+
+    (eng, since)
+}
+
+#[test]
+fn test_try_evict_unreachable_remove() -> anyhow::Result<()> {
+    let (mut eng, since) = eng(false);
+
+    // Within the grace period: nothing to do.
+    eng.try_leader_handler()?.try_evict_unreachable(since + Duration::from_millis(100));
+    assert_eq!(Some(&log_id(2, 1, 3)), eng.state.last_log_id());
+
+    // Voter 3 and learner 4 are unreachable: enter a joint config without them.
+    let now = since + Duration::from_millis(200);
+    eng.try_leader_handler()?.try_evict_unreachable(now);
+
+    assert_eq!(Some(&log_id(3, 1, 4)), eng.state.last_log_id());
+    assert_eq!(Some(log_id(3, 1, 4)), eng.leader.as_ref().unwrap().evicting);
+    assert_eq!(
+        &vec![btreeset! {1,2,3}, btreeset! {1,2}],
+        eng.state.membership_state.effective().membership().get_joint_config()
+    );
+    assert_eq!(
+        vec![
+            RaftEvent::NodeEvicted {
+                node_id: 3,
+                unreachable_for: Duration::from_millis(200),
+                demoted: false,
+            },
+            RaftEvent::NodeEvicted {
+                node_id: 4,
+                unreachable_for: Duration::from_millis(200),
+                demoted: false,
+            },
+        ],
+        eng.output.take_events()
+    );
+
+    // The joint config is not committed: nothing to do.
+    eng.try_leader_handler()?.try_evict_unreachable(now);
+    assert_eq!(Some(&log_id(3, 1, 4)), eng.state.last_log_id());
+
+    // The joint config is committed: flatten it.
+    eng.state.membership_state.commit(&Some(log_id(3, 1, 4)));
+    eng.try_leader_handler()?.try_evict_unreachable(now);
+
+    let effective = eng.state.membership_state.effective().clone();
+    assert_eq!(Some(&log_id(3, 1, 5)), eng.state.last_log_id());
+    assert_eq!(None, eng.leader.as_ref().unwrap().evicting);
+    assert_eq!(&vec![btreeset! {1,2}], effective.membership().get_joint_config());
+    assert_eq!(0, effective.membership().learner_ids().count());
+
+    Ok(())
+}
+
+#[test]
+fn test_try_evict_unreachable_demote() -> anyhow::Result<()> {
+    let (mut eng, since) = eng(true);
+
+    // Voter 3 is demoted; learner 4 is left in place.
+    let now = since + Duration::from_millis(200);
+    eng.try_leader_handler()?.try_evict_unreachable(now);
+
+    assert_eq!(Some(&log_id(3, 1, 4)), eng.state.last_log_id());
+    assert_eq!(
+        vec![RaftEvent::NodeEvicted {
+            node_id: 3,
+            unreachable_for: Duration::from_millis(200),
+            demoted: true,
+        }],
+        eng.output.take_events()
+    );
+
+    eng.state.membership_state.commit(&Some(log_id(3, 1, 4)));
+    eng.try_leader_handler()?.try_evict_unreachable(now);
+
+    let effective = eng.state.membership_state.effective().clone();
+    assert_eq!(Some(&log_id(3, 1, 5)), eng.state.last_log_id());
+    assert_eq!(&vec![btreeset! {1,2}], effective.membership().get_joint_config());
+    assert_eq!(vec![3, 4], effective.membership().learner_ids().collect::<Vec<_>>());
+
+    // Demoted voter 3 and learner 4 are not evicted again.
+    eng.state.membership_state.commit(&Some(log_id(3, 1, 5)));
+    eng.try_leader_handler()?.try_evict_unreachable(now);
+    assert_eq!(Some(&log_id(3, 1, 5)), eng.state.last_log_id());
+
+    Ok(())
+}
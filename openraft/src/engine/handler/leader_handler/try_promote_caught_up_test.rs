@@ -0,0 +1,105 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+#[allow(unused_imports)]
+use pretty_assertions::assert_eq;
+#[allow(unused_imports)]
+use pretty_assertions::assert_ne;
+#[allow(unused_imports)]
+use pretty_assertions::assert_str_eq;
+
+use crate::Membership;
+use crate::MembershipState;
+use crate::Vote;
+use crate::engine::Engine;
+use crate::engine::testing::UTConfig;
+use crate::engine::testing::log_id;
+use crate::progress::Progress;
+use crate::raft_state::LogStateReader;
+use crate::type_config::TypeConfigExt;
+use crate::type_config::alias::StoredMembershipOf;
+use crate::utime::Leased;
+
+/// members: {1}, learners: {2}
+fn m1_2() -> Membership<u64, ()> {
+    Membership::<u64, ()>::new_with_defaults(vec![btreeset! {1}], btreeset! {2})
+}
+
+fn eng() -> Engine<UTConfig> {
+    let mut eng = Engine::testing_default(0);
+    eng.state.enable_validation(false); // Disable validation for incomplete state
+
+    eng.config.id = 1;
+    eng.config.promote_lag_threshold = 1;
+    eng.state.vote = Leased::new(
+        UTConfig::<()>::now(),
+        Duration::from_millis(500),
+        Vote::new_committed(3, 1),
+    );
+    eng.state.log_ids.append(log_id(1, 1, 1));
+    eng.state.log_ids.append(log_id(2, 1, 3));
+    eng.state.membership_state = MembershipState::new(
+        Arc::new(StoredMembershipOf::<UTConfig>::new(Some(log_id(1, 1, 1)), m1_2())),
+        Arc::new(StoredMembershipOf::<UTConfig>::new(Some(log_id(1, 1, 1)), m1_2())),
+    );
+    eng.testing_new_leader();
+    eng.state.server_state = eng.calc_server_state();
+
+    eng
+}
+
+#[test]
+fn test_try_promote_caught_up() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.leader.as_mut().unwrap().promote_when_caught_up.insert(2);
+
+    // Learner 2 lags behind more than the threshold.
+    eng.try_leader_handler()?.try_promote_caught_up();
+
+    assert_eq!(Some(&log_id(2, 1, 3)), eng.state.last_log_id());
+    assert_eq!(btreeset! {2}, eng.leader.as_ref().unwrap().promote_when_caught_up);
+
+    // Learner 2 is caught up: enter a joint config.
+    eng.leader.as_mut().unwrap().progress.get_mut(&2).unwrap().matching = Some(log_id(2, 1, 2));
+    eng.try_leader_handler()?.try_promote_caught_up();
+
+    let leader = eng.leader.as_ref().unwrap();
+    assert_eq!(Some(&log_id(3, 1, 4)), eng.state.last_log_id());
+    assert_eq!(Some(log_id(3, 1, 4)), leader.promoting);
+    assert!(leader.promote_when_caught_up.is_empty());
+    assert_eq!(
+        &vec![btreeset! {1}, btreeset! {1, 2}],
+        eng.state.membership_state.effective().membership().get_joint_config()
+    );
+
+    // The joint config is not committed: nothing to do.
+    eng.try_leader_handler()?.try_promote_caught_up();
+    assert_eq!(Some(&log_id(3, 1, 4)), eng.state.last_log_id());
+
+    // The joint config is committed: flatten it.
+    eng.state.membership_state.commit(&Some(log_id(3, 1, 4)));
+    eng.try_leader_handler()?.try_promote_caught_up();
+
+    assert_eq!(Some(&log_id(3, 1, 5)), eng.state.last_log_id());
+    assert_eq!(None, eng.leader.as_ref().unwrap().promoting);
+    assert_eq!(
+        &vec![btreeset! {1, 2}],
+        eng.state.membership_state.effective().membership().get_joint_config()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_try_promote_caught_up_drop_removed() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.leader.as_mut().unwrap().promote_when_caught_up.insert(5);
+
+    eng.try_leader_handler()?.try_promote_caught_up();
+
+    assert_eq!(Some(&log_id(2, 1, 3)), eng.state.last_log_id());
+    assert!(eng.leader.as_ref().unwrap().promote_when_caught_up.is_empty());
+
+    Ok(())
+}
@@ -78,3 +78,89 @@ fn test_calc_purge_upto() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_calc_purge_upto_with_purge_hold() -> anyhow::Result<()> {
+    // purge_hold, want
+    let cases = vec![
+        (None, Some(log_id(3, 4))),
+        (Some(5), Some(log_id(3, 4))),
+        (Some(4), Some(log_id(3, 3))),
+        (Some(2), Some(log_id(1, 1))),
+        (Some(0), None),
+    ];
+
+    for (purge_hold, want) in cases {
+        let mut eng = eng();
+        eng.config.max_in_snapshot_log_to_keep = 0;
+        eng.config.purge_batch_size = 1;
+
+        eng.state.snapshot_meta.last_log_id = Some(log_id(3, 4));
+        eng.state.purge_hold = purge_hold;
+        let got = eng.log_handler().calc_purge_upto();
+
+        assert_eq!(want, got, "case: purge_hold: {:?}", purge_hold);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_calc_purge_upto_with_log_only_hold() -> anyhow::Result<()> {
+    // purge_hold, log_only_hold, want
+    let cases = vec![
+        (None, None, Some(log_id(3, 4))),
+        (None, Some(4), Some(log_id(3, 3))),
+        (Some(4), Some(2), Some(log_id(1, 1))),
+        (Some(2), Some(4), Some(log_id(1, 1))),
+        (None, Some(0), None),
+    ];
+
+    for (purge_hold, log_only_hold, want) in cases {
+        let mut eng = eng();
+        eng.config.max_in_snapshot_log_to_keep = 0;
+        eng.config.purge_batch_size = 1;
+
+        eng.state.snapshot_meta.last_log_id = Some(log_id(3, 4));
+        eng.state.purge_hold = purge_hold;
+        eng.state.log_only_hold = log_only_hold;
+        let got = eng.log_handler().calc_purge_upto();
+
+        assert_eq!(
+            want, got,
+            "case: purge_hold: {:?}, log_only_hold: {:?}",
+            purge_hold, log_only_hold
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_calc_purge_upto_with_durable_applied() -> anyhow::Result<()> {
+    // snapshot_last_log_id, durable_applied, want
+    let cases = vec![
+        (None, None, None),
+        (None, Some(log_id(3, 3)), Some(log_id(3, 3))),
+        (Some(log_id(1, 2)), Some(log_id(3, 4)), Some(log_id(3, 4))),
+        (Some(log_id(3, 4)), Some(log_id(1, 2)), Some(log_id(3, 4))),
+    ];
+
+    for (snapshot_last_log_id, durable_applied, want) in cases {
+        let mut eng = eng();
+        eng.config.max_in_snapshot_log_to_keep = 0;
+        eng.config.purge_batch_size = 1;
+
+        eng.state.snapshot_meta.last_log_id = snapshot_last_log_id;
+        eng.state.durable_applied = durable_applied;
+        let got = eng.log_handler().calc_purge_upto();
+
+        assert_eq!(
+            want, got,
+            "case: snapshot_last_log_id: {:?}, durable_applied: {:?}",
+            snapshot_last_log_id, durable_applied
+        );
+    }
+
+    Ok(())
+}
@@ -49,6 +49,25 @@ where C: RaftTypeConfig
         self.output.push_command(Command::PurgeLog { upto });
     }
 
+    /// Whether the scheduled purge removes logs a log-only voter may still need.
+    ///
+    /// Such a purge is scheduled before the log-only voter held the logs and is postponed.
+    pub(crate) fn is_purge_held(&self) -> bool {
+        let Some(hold) = self.state.log_only_hold else {
+            return false;
+        };
+
+        let held = self.state.purge_upto().index() >= Some(hold);
+        if held {
+            tracing::debug!(
+                "cannot purge: {} is needed by a log-only voter, log_only_hold: {}",
+                self.state.purge_upto().display(),
+                hold
+            );
+        }
+        held
+    }
+
     /// Update the next log id to purge up to, if more logs can be purged, according to the
     /// configured policy.
     ///
@@ -57,7 +76,10 @@ where C: RaftTypeConfig
     #[tracing::instrument(level = "debug", skip_all)]
     pub(crate) fn schedule_policy_based_purge(&mut self) {
         if let Some(purge_upto) = self.calc_purge_upto() {
-            self.update_purge_upto(purge_upto);
+            // A purge scheduled before a log subscriber held the logs is not taken back.
+            if self.state.purge_upto() < Some(&purge_upto) {
+                self.update_purge_upto(purge_upto);
+            }
         }
     }
 
@@ -70,7 +92,9 @@ where C: RaftTypeConfig
 
     /// Calculate the log id up to which to purge, inclusive.
     ///
-    /// Only logs included in the snapshot will be purged.
+    /// Only logs included in the snapshot, or persisted by the state machine with
+    /// `PurgePolicy::DurableApplied`, will be purged, and none at or after the index held by a
+    /// log subscriber or a log-only voter.
     /// It may return None if there is no log to purge.
     ///
     /// `max_keep` specifies the number of applied logs to keep.
@@ -81,19 +105,24 @@ where C: RaftTypeConfig
         let max_keep = self.config.max_in_snapshot_log_to_keep;
         let batch_size = self.config.purge_batch_size;
 
-        let purge_end = self.state.snapshot_meta.last_log_id.next_index().saturating_sub(max_keep);
+        let mut purge_end = self.state.purgeable_log_id().next_index().saturating_sub(max_keep);
+        for hold in [st.purge_hold, st.log_only_hold].into_iter().flatten() {
+            purge_end = std::cmp::min(purge_end, hold);
+        }
 
         tracing::debug!(
-            "calculate purge range: up to index {}, snapshot_last_log_id: {:?}, max_keep: {}",
+            "calculate purge range: up to index {}, purgeable: {:?}, max_keep: {}, purge_hold: {:?}, log_only_hold: {:?}",
             purge_end,
-            self.state.snapshot_meta.last_log_id,
-            max_keep
+            self.state.purgeable_log_id(),
+            max_keep,
+            st.purge_hold,
+            st.log_only_hold
         );
 
         if st.last_purged_log_id().next_index() + batch_size > purge_end {
             tracing::debug!(
-                "skip purge: batch not full, snapshot_last_log_id: {:?}, max_keep: {}, last_purged: {}, batch_size: {}, purge_end: {}",
-                self.state.snapshot_meta.last_log_id,
+                "skip purge: batch not full, purgeable: {:?}, max_keep: {}, last_purged: {}, batch_size: {}, purge_end: {}",
+                self.state.purgeable_log_id(),
                 max_keep,
                 st.last_purged_log_id().display(),
                 batch_size,
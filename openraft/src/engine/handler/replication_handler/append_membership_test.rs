@@ -109,14 +109,20 @@ fn test_leader_append_membership_for_leader() -> anyhow::Result<()> {
                 target: 3,
                 req: Replicate {
                     inflight_id: InflightId::new(1),
-                    payload: Payload::LogsSince { prev: None },
+                    payload: Payload::LogsSince {
+                        prev: None,
+                        after_snapshot: None,
+                    },
                 },
             },
             Command::Replicate {
                 target: 4,
                 req: Replicate {
                     inflight_id: InflightId::new(2),
-                    payload: Payload::LogsSince { prev: None },
+                    payload: Payload::LogsSince {
+                        prev: None,
+                        after_snapshot: None,
+                    },
                 },
             },
         ],
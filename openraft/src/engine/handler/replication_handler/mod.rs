@@ -3,6 +3,7 @@ use std::sync::Arc;
 use display_more::DisplayOptionExt;
 use display_more::DisplayResultExt;
 
+use crate::Instant;
 use crate::LogIdOptionExt;
 use crate::Membership;
 use crate::RaftState;
@@ -13,9 +14,11 @@ use crate::engine::Command;
 use crate::engine::EngineConfig;
 use crate::engine::EngineOutput;
 use crate::engine::TargetProgress;
+use crate::engine::handler::leader_handler::LeaderHandler;
 use crate::engine::handler::log_handler::LogHandler;
 use crate::errors::NodeNotFound;
 use crate::errors::Operation;
+use crate::membership::QuorumKind;
 use crate::progress;
 use crate::progress::Inflight;
 use crate::progress::Progress;
@@ -24,10 +27,12 @@ use crate::progress::inflight_id::InflightId;
 use crate::progress::stream_id::StreamId;
 use crate::proposer::Leader;
 use crate::proposer::LeaderQuorumSet;
+use crate::raft::RaftEvent;
 use crate::raft_state::LogStateReader;
 use crate::raft_state::io_state::log_io_id::LogIOId;
 use crate::replication::replicate::Replicate;
 use crate::replication::response::ReplicationResult;
+use crate::type_config::TypeConfigExt;
 use crate::type_config::alias::CommittedVoteOf;
 use crate::type_config::alias::InstantOf;
 use crate::type_config::alias::LogIdOf;
@@ -101,6 +106,11 @@ where C: RaftTypeConfig
         let em = self.state.membership_state.effective();
 
         let learner_ids = em.learner_ids().collect::<Vec<_>>();
+        let quorum_set = LeaderQuorumSet::new(
+            Arc::new((*em.membership()).clone()),
+            self.config.quorum_policy.as_ref(),
+            QuorumKind::Commit,
+        );
 
         {
             let end = self.state.last_log_id().next_index();
@@ -113,15 +123,13 @@ where C: RaftTypeConfig
                 ProgressEntry::empty(progress_id, end)
             };
 
-            self.leader.progress =
-                old_progress.upgrade_quorum_set(Arc::new((*em.membership()).clone()), learner_ids.clone(), default_v);
+            self.leader.progress = old_progress.upgrade_quorum_set(quorum_set.clone(), learner_ids.clone(), default_v);
         }
 
         {
             let old_progress = self.leader.clock_progress.clone();
 
-            self.leader.clock_progress =
-                old_progress.upgrade_quorum_set(Arc::new((*em.membership()).clone()), learner_ids, || None);
+            self.leader.clock_progress = old_progress.upgrade_quorum_set(quorum_set, learner_ids, || None);
         }
     }
 
@@ -142,6 +150,9 @@ where C: RaftTypeConfig
             return;
         }
 
+        let rtt = C::now().saturating_duration_since(sending_time);
+        self.leader.round_trips.insert(target.clone(), rtt);
+
         let granted = *self
             .leader
             .clock_progress
@@ -203,8 +214,25 @@ where C: RaftTypeConfig
     /// Commit the log id that is granted(accepted) by a quorum of voters.
     ///
     /// In raft a log that is granted and in the leader term is committed.
+    ///
+    /// A write that passes its deadline before it is committed is not committed by this leader,
+    /// nor is any log after it, see [`Engine::abandon_expired_writes()`].
+    ///
+    /// [`Engine::abandon_expired_writes()`]: crate::engine::Engine::abandon_expired_writes
     #[tracing::instrument(level = "debug", skip_all)]
     pub(crate) fn try_commit_quorum_accepted(&mut self, granted: Option<LogIdOf<C>>) {
+        let granted = match self.leader.first_expired_write(LeaderHandler::<C>::wall_clock_ms()) {
+            Some(index) if granted.next_index() > index => {
+                tracing::debug!(
+                    "granted: {}, do not commit the write at {} that passed its deadline",
+                    granted.display(),
+                    index
+                );
+                index.checked_sub(1).and_then(|prev| self.state.get_log_id(prev))
+            }
+            _ => granted,
+        };
+
         // Only when the log id is proposed by the current leader, it is committed.
         if let Some(ref c) = granted
             && !self.state.vote_ref().is_same_leader(c.committed_leader_id())
@@ -218,7 +246,13 @@ where C: RaftTypeConfig
         self.state.io_state_mut().cluster_committed.try_update(committed).ok();
 
         // Advance the local apply ceiling regardless; it is a no-op when nothing changed.
-        self.state.update_local_committed(&granted);
+        self.leader.forget_write_deadlines(granted.next_index());
+
+        if self.state.update_local_committed(&granted) {
+            self.output.push_event(RaftEvent::MembershipCommitted {
+                membership: self.state.membership_state.committed().clone(),
+            });
+        }
 
         // Notify replication only when the cluster-committed log id advanced. The vote component of
         // `cluster_committed` can bump on its own at leadership start with no new committed log id,
@@ -312,7 +346,7 @@ where C: RaftTypeConfig
 
                 // Reset inflight state and it will retry.
                 if let Some(p) = self.leader.progress.get_mut(&target) {
-                    p.inflight = Inflight::None;
+                    p.inflight.fail(inflight_id);
                 };
             }
         };
@@ -365,13 +399,28 @@ where C: RaftTypeConfig
                 continue;
             }
 
+            let log_only = self.state.membership_state.effective().membership().is_log_only(&item.id);
+
             let t = item.val.next_send(self.state, self.config.max_payload_entries);
             tracing::debug!("next send: target: {}, send: {:?}", item.id, t);
 
             match t {
-                Ok(inflight) => {
+                Ok(inflight) if log_only && inflight.is_sending_snapshot() => {
+                    // Every purge is held for log-only voters, the logs are purged only if the
+                    // node was added as a log-only voter after they were purged.
+                    tracing::warn!(
+                        "node-{} is log-only and does not install snapshot, but the logs it needs are purged",
+                        item.id
+                    );
+                    item.val.inflight = Inflight::None;
+                }
+                Ok(_) => {
+                    if self.config.pipeline_snapshot_tail {
+                        item.val.pipeline_snapshot_tail(self.state);
+                    }
+
                     let leader_vote = self.leader.committed_vote.clone();
-                    Self::send_to_target(self.output, leader_vote, &item.id, inflight);
+                    Self::send_to_target(self.output, leader_vote, &item.id, &item.val.inflight);
                 }
                 Err(e) => {
                     tracing::debug!("no data to replicate for node-{}: current inflight: {:?}", item.id, e,);
@@ -413,6 +462,23 @@ where C: RaftTypeConfig
                     req,
                 });
             }
+            Inflight::SnapshotWithTail {
+                snapshot_id,
+                prev,
+                tail_id,
+            } => {
+                output.push_command(Command::ReplicateSnapshot {
+                    leader_vote,
+                    target: target.clone(),
+                    inflight_id: *snapshot_id,
+                });
+
+                let req = Replicate::new_snapshot_tail(prev.clone(), *tail_id);
+                output.push_command(Command::Replicate {
+                    target: target.clone(),
+                    req,
+                });
+            }
         };
     }
 
@@ -436,6 +502,10 @@ where C: RaftTypeConfig
             return;
         }
 
+        if self.log_handler().is_purge_held() {
+            return;
+        }
+
         // Safe unwrap(): it greater than an Option thus it must be a Some()
         let purge_upto = self.state.purge_upto().unwrap().clone();
 
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 use std::time::Duration;
 
+use maplit::btreemap;
 use maplit::btreeset;
 use pretty_assertions::assert_eq;
 
@@ -9,6 +10,7 @@ use crate::MembershipState;
 use crate::Vote;
 use crate::engine::Command;
 use crate::engine::Engine;
+use crate::engine::LogIdList;
 use crate::engine::testing::UTConfig;
 use crate::engine::testing::log_id;
 use crate::progress::Inflight;
@@ -125,3 +127,32 @@ fn test_update_matching() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_update_matching_write_passed_deadline() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.state.log_ids = LogIdList::new(None, [log_id(1, 1, 2), log_id(2, 1, 4)]);
+    eng.testing_new_leader();
+    eng.output.take_commands();
+
+    let mut rh = eng.replication_handler();
+    for id in [1, 3] {
+        let prog_entry = rh.leader.progress.get_mut(&id).unwrap();
+        prog_entry.inflight = Inflight::logs(Some(log_id(1, 1, 2)), Some(log_id(2, 1, 4)), InflightId::new(1));
+    }
+
+    // The write at 4 passed its deadline, the one at 3 did not.
+    rh.leader.write_deadlines = btreemap! {3 => u64::MAX, 4 => 1};
+
+    rh.update_matching(1, Some(log_id(2, 1, 4)), None);
+    rh.update_matching(3, Some(log_id(2, 1, 4)), None);
+
+    assert_eq!(
+        Some(&log_id(2, 1, 3)),
+        rh.state.local_committed(),
+        "the write that passed its deadline is not committed"
+    );
+    assert_eq!(btreemap! {4 => 1}, rh.leader.write_deadlines);
+
+    Ok(())
+}
@@ -2,23 +2,26 @@ use crate::RaftState;
 use crate::RaftTypeConfig;
 use crate::ServerState;
 use crate::engine::EngineConfig;
+use crate::engine::EngineOutput;
+use crate::raft::RaftEvent;
 
 #[cfg(test)]
 mod update_server_state_test;
 
 /// Handle raft server-state related operations
-pub(crate) struct ServerStateHandler<'st, C>
+pub(crate) struct ServerStateHandler<'st, C, SM = ()>
 where C: RaftTypeConfig
 {
     pub(crate) config: &'st EngineConfig<C>,
     pub(crate) state: &'st mut RaftState<C>,
+    pub(crate) output: &'st mut EngineOutput<C, SM>,
 }
 
-impl<C> ServerStateHandler<'_, C>
+impl<C, SM> ServerStateHandler<'_, C, SM>
 where C: RaftTypeConfig
 {
-    /// Re-calculate the server-state if it changed, update the `server_state` field and dispatch
-    /// commands to inform a runtime.
+    /// Re-calculate the server-state if it changed, update the `server_state` field and emit an
+    /// event if this node becomes the leader or steps down.
     pub(crate) fn update_server_state_if_changed(&mut self) {
         let server_state = self.state.calc_server_state(&self.config.id);
 
@@ -38,8 +41,14 @@ where C: RaftTypeConfig
 
         if !was_leader && is_leader {
             tracing::info!("id={} becomes leader", &self.config.id);
+            self.output.push_event(RaftEvent::LeaderElected {
+                vote: self.state.vote_ref().clone(),
+            });
         } else if was_leader && !is_leader {
             tracing::info!("id={} steps down from leader", &self.config.id);
+            self.output.push_event(RaftEvent::SteppedDown {
+                vote: self.state.vote_ref().clone(),
+            });
         } else {
             // nothing to do
         }
@@ -11,6 +11,7 @@ use crate::Vote;
 use crate::engine::Engine;
 use crate::engine::testing::UTConfig;
 use crate::engine::testing::log_id;
+use crate::raft::RaftEvent;
 use crate::type_config::TypeConfigExt;
 use crate::type_config::alias::StoredMembershipOf;
 use crate::utime::Leased;
@@ -55,6 +56,31 @@ fn test_update_server_state_if_changed() -> anyhow::Result<()> {
         ssh.update_server_state_if_changed();
 
         assert_eq!(ServerState::Follower, ssh.state.server_state);
+        assert_eq!(
+            vec![RaftEvent::SteppedDown { vote: Vote::new(2, 100) }],
+            ssh.output.take_events()
+        );
+    }
+
+    // Follower become leader
+    {
+        ssh.state.vote = Leased::new(
+            UTConfig::<()>::now(),
+            Duration::from_millis(500),
+            Vote::new_committed(3, 2),
+        );
+        ssh.update_server_state_if_changed();
+
+        assert_eq!(ServerState::Leader, ssh.state.server_state);
+        assert_eq!(
+            vec![RaftEvent::LeaderElected {
+                vote: Vote::new_committed(3, 2)
+            }],
+            ssh.output.take_events()
+        );
+
+        ssh.update_server_state_if_changed();
+        assert_eq!(Vec::<RaftEvent<UTConfig>>::new(), ssh.output.take_events());
     }
 
     // TODO(3): add more test,
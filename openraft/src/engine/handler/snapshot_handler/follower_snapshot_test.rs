@@ -0,0 +1,73 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use pretty_assertions::assert_eq;
+
+use crate::Membership;
+use crate::MembershipState;
+use crate::Vote;
+use crate::engine::Engine;
+use crate::engine::testing::UTConfig;
+use crate::engine::testing::log_id;
+use crate::type_config::TypeConfigExt;
+use crate::type_config::alias::StoredMembershipOf;
+use crate::utime::Leased;
+use crate::vote::raft_vote::RaftVoteExt;
+
+fn m012() -> Membership<u64, ()> {
+    Membership::<u64, ()>::new_with_defaults(vec![btreeset! {0,1,2}], [])
+}
+
+fn eng() -> Engine<UTConfig> {
+    let mut eng = Engine::testing_default(0);
+    eng.state.enable_validation(false); // Disable validation for incomplete state
+
+    eng.state.vote = Leased::new(
+        UTConfig::<()>::now(),
+        Duration::from_millis(500),
+        Vote::new_committed(2, 0),
+    );
+    eng.state.membership_state = MembershipState::new(
+        Arc::new(StoredMembershipOf::<UTConfig>::new(Some(log_id(1, 0, 1)), m012())),
+        Arc::new(StoredMembershipOf::<UTConfig>::new(Some(log_id(1, 0, 1)), m012())),
+    );
+
+    eng
+}
+
+#[test]
+fn test_follower_snapshot_not_leader() -> anyhow::Result<()> {
+    let mut eng = eng();
+
+    let vote = Vote::new_committed(2, 0).into_committed();
+    eng.snapshot_handler().follower_installed_snapshot(&vote, 1, "s1".to_string());
+
+    assert_eq!(None, eng.snapshot_handler().follower_snapshot(&1));
+
+    Ok(())
+}
+
+#[test]
+fn test_follower_snapshot() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.testing_new_leader();
+
+    let vote = Vote::new_committed(2, 0).into_committed();
+
+    assert_eq!(None, eng.snapshot_handler().follower_snapshot(&1));
+
+    eng.snapshot_handler().follower_installed_snapshot(&vote, 1, "s1".to_string());
+    eng.snapshot_handler().follower_installed_snapshot(&vote, 2, "s1".to_string());
+    eng.snapshot_handler().follower_installed_snapshot(&vote, 1, "s2".to_string());
+
+    assert_eq!(Some("s2".to_string()), eng.snapshot_handler().follower_snapshot(&1));
+    assert_eq!(Some("s1".to_string()), eng.snapshot_handler().follower_snapshot(&2));
+
+    // A transfer by an earlier leader is ignored.
+    let stale = Vote::new_committed(1, 0).into_committed();
+    eng.snapshot_handler().follower_installed_snapshot(&stale, 2, "s0".to_string());
+    assert_eq!(Some("s1".to_string()), eng.snapshot_handler().follower_snapshot(&2));
+
+    Ok(())
+}
@@ -4,12 +4,24 @@ use display_more::DisplayOptionExt;
 
 use crate::RaftState;
 use crate::RaftTypeConfig;
+use crate::SnapshotId;
 use crate::core::sm;
 use crate::engine::Command;
 use crate::engine::EngineOutput;
+use crate::errors::NodeNotFound;
+use crate::errors::Operation;
+use crate::errors::SeedSnapshotError;
+use crate::progress::Progress;
+use crate::proposer::Leader;
+use crate::proposer::LeaderQuorumSet;
 use crate::raft_state::LogStateReader;
+use crate::type_config::alias::CommittedVoteOf;
 use crate::type_config::alias::SnapshotMetaOf;
 
+#[cfg(test)]
+mod follower_snapshot_test;
+#[cfg(test)]
+mod snapshot_seed_test;
 #[cfg(test)]
 mod trigger_snapshot_test;
 #[cfg(test)]
@@ -19,6 +31,8 @@ mod update_snapshot_test;
 pub(crate) struct SnapshotHandler<'st, 'out, C, SM = ()>
 where C: RaftTypeConfig
 {
+    /// The leader state if this node is a leader, which tracks the snapshots followers hold.
+    pub(crate) leader: Option<&'st mut Leader<C, LeaderQuorumSet<C>>>,
     pub(crate) state: &'st mut RaftState<C>,
     pub(crate) output: &'out mut EngineOutput<C, SM>,
 }
@@ -51,7 +65,7 @@ where C: RaftTypeConfig
     ///
     /// [`RaftStateMachine`]: crate::storage::RaftStateMachine
     #[tracing::instrument(level = "debug", skip_all)]
-    pub(crate) fn update_snapshot(&mut self, meta: SnapshotMetaOf<C>) -> bool {
+    pub(crate) fn update_snapshot(&mut self, mut meta: SnapshotMetaOf<C>) -> bool {
         tracing::info!("update_snapshot: {:?}", meta);
 
         if meta.last_log_id.as_ref() <= self.state.snapshot_last_log_id() {
@@ -63,8 +77,85 @@ where C: RaftTypeConfig
             return false;
         }
 
+        // An installed delta snapshot is merged into a full one by the state machine.
+        meta.base_snapshot_id = None;
         self.state.snapshot_meta = meta;
 
         true
     }
+
+    /// Record that `target` has installed the snapshot `snapshot_id` sent by the leader of
+    /// `leader_vote`.
+    ///
+    /// It is ignored if this node is no longer that leader.
+    pub(crate) fn follower_installed_snapshot(
+        &mut self,
+        leader_vote: &CommittedVoteOf<C>,
+        target: C::NodeId,
+        snapshot_id: SnapshotId,
+    ) {
+        let Some(leader) = self.leader.as_deref_mut() else {
+            return;
+        };
+
+        if leader.committed_vote_ref() != leader_vote {
+            tracing::debug!(
+                "ignore snapshot installed by {} from another leader: {}",
+                target,
+                leader_vote
+            );
+            return;
+        }
+
+        tracing::info!("follower {} installed snapshot: {}", target, snapshot_id);
+        leader.follower_snapshots.insert(target, snapshot_id);
+    }
+
+    /// Returns the snapshot `target` last installed from this leader, which a snapshot sent to it
+    /// can be a delta relative to.
+    pub(crate) fn follower_snapshot(&self, target: &C::NodeId) -> Option<SnapshotId> {
+        self.leader.as_deref()?.follower_snapshots.get(target).cloned()
+    }
+
+    /// Set the node `target` is asked to fetch snapshots from, or unset it with `None` so that
+    /// this leader sends them.
+    pub(crate) fn set_snapshot_seed(
+        &mut self,
+        target: C::NodeId,
+        seed: Option<C::NodeId>,
+    ) -> Result<(), SeedSnapshotError<C>> {
+        let Some(leader) = self.leader.as_deref_mut() else {
+            return Err(self.state.forward_to_leader().into());
+        };
+
+        if leader.progress.try_get(&target).is_none() {
+            tracing::warn!(
+                "target node {} not found in progress tracker, when {}",
+                target,
+                func_name!()
+            );
+            return Err(NodeNotFound::new(target, Operation::SeedSnapshot).into());
+        }
+
+        let Some(seed) = seed else {
+            leader.snapshot_seeds.remove(&target);
+            return Ok(());
+        };
+
+        if seed == target || self.state.membership_state.effective().get_node(&seed).is_none() {
+            tracing::warn!("seed node {} not found in membership, when {}", seed, func_name!());
+            return Err(NodeNotFound::new(seed, Operation::SeedSnapshot).into());
+        }
+
+        tracing::info!("follower {} fetches snapshot from seed {}", target, seed);
+        leader.snapshot_seeds.insert(target, seed);
+        Ok(())
+    }
+
+    /// Returns the node `target` is asked to fetch snapshots from, if it is still a member.
+    pub(crate) fn snapshot_seed(&self, target: &C::NodeId) -> Option<(C::NodeId, C::Node)> {
+        let seed = self.leader.as_deref()?.snapshot_seeds.get(target)?;
+        let node = self.state.membership_state.effective().get_node(seed)?;
+        Some((seed.clone(), node.clone()))
+    }
 }
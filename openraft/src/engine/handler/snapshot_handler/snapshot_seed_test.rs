@@ -0,0 +1,107 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use pretty_assertions::assert_eq;
+
+use crate::Membership;
+use crate::MembershipState;
+use crate::Vote;
+use crate::engine::Engine;
+use crate::engine::testing::UTConfig;
+use crate::engine::testing::log_id;
+use crate::errors::ForwardToLeader;
+use crate::errors::NodeNotFound;
+use crate::errors::Operation;
+use crate::errors::SeedSnapshotError;
+use crate::type_config::TypeConfigExt;
+use crate::type_config::alias::StoredMembershipOf;
+use crate::utime::Leased;
+
+fn m012() -> Membership<u64, ()> {
+    Membership::<u64, ()>::new_with_defaults(vec![btreeset! {0,1,2}], [])
+}
+
+fn eng() -> Engine<UTConfig> {
+    let mut eng = Engine::testing_default(0);
+    eng.state.enable_validation(false); // Disable validation for incomplete state
+
+    eng.state.vote = Leased::new(
+        UTConfig::<()>::now(),
+        Duration::from_millis(500),
+        Vote::new_committed(2, 0),
+    );
+    eng.state.membership_state = MembershipState::new(
+        Arc::new(StoredMembershipOf::<UTConfig>::new(Some(log_id(1, 0, 1)), m012())),
+        Arc::new(StoredMembershipOf::<UTConfig>::new(Some(log_id(1, 0, 1)), m012())),
+    );
+
+    eng
+}
+
+#[test]
+fn test_set_snapshot_seed_not_leader() -> anyhow::Result<()> {
+    let mut eng = eng();
+
+    let res = eng.snapshot_handler().set_snapshot_seed(1, Some(2));
+    assert_eq!(
+        Err(SeedSnapshotError::ForwardToLeader(ForwardToLeader::new(0, ()))),
+        res
+    );
+    assert_eq!(None, eng.snapshot_handler().snapshot_seed(&1));
+
+    Ok(())
+}
+
+#[test]
+fn test_set_snapshot_seed() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.testing_new_leader();
+
+    assert_eq!(None, eng.snapshot_handler().snapshot_seed(&1));
+
+    eng.snapshot_handler().set_snapshot_seed(1, Some(2))?;
+    assert_eq!(Some((2, ())), eng.snapshot_handler().snapshot_seed(&1));
+
+    eng.snapshot_handler().set_snapshot_seed(1, None)?;
+    assert_eq!(None, eng.snapshot_handler().snapshot_seed(&1));
+
+    Ok(())
+}
+
+#[test]
+fn test_set_snapshot_seed_node_not_found() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.testing_new_leader();
+
+    let res = eng.snapshot_handler().set_snapshot_seed(5, Some(2));
+    assert_eq!(
+        Err(SeedSnapshotError::NodeNotFound(NodeNotFound::new(
+            5,
+            Operation::SeedSnapshot
+        ))),
+        res
+    );
+
+    let res = eng.snapshot_handler().set_snapshot_seed(1, Some(5));
+    assert_eq!(
+        Err(SeedSnapshotError::NodeNotFound(NodeNotFound::new(
+            5,
+            Operation::SeedSnapshot
+        ))),
+        res
+    );
+
+    let res = eng.snapshot_handler().set_snapshot_seed(1, Some(1));
+    assert_eq!(
+        Err(SeedSnapshotError::NodeNotFound(NodeNotFound::new(
+            1,
+            Operation::SeedSnapshot
+        ))),
+        res
+    );
+
+    assert_eq!(None, eng.snapshot_handler().snapshot_seed(&1));
+
+    Ok(())
+}
@@ -85,7 +85,10 @@ fn test_become_leader() -> anyhow::Result<()> {
             target: 0,
             req: Replicate {
                 inflight_id: InflightId::new(1),
-                payload: Payload::LogsSince { prev: None },
+                payload: Payload::LogsSince {
+                    prev: None,
+                    after_snapshot: None,
+                },
             }
         }
     ]);
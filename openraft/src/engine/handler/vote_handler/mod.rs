@@ -198,7 +198,7 @@ where C: RaftTypeConfig
         // It's a different leader that creates this vote.
         // Re-create a new Leader instance.
 
-        let leader = self.state.new_leader();
+        let leader = self.state.new_leader(self.config.quorum_policy.as_ref());
         let leader_vote = leader.committed_vote_ref().clone();
         *self.leader = Some(Box::new(leader));
 
@@ -222,7 +222,7 @@ where C: RaftTypeConfig
         // If the leader has not yet proposed any log, propose a blank log and initiate replication;
         // Otherwise, just initiate replication.
         if last_log_id.as_ref() < Some(&noop_log_id) {
-            self.leader_handler().leader_append_entries([EntryPayload::Blank]);
+            self.leader_handler().leader_append_internal(EntryPayload::Blank);
         } else {
             self.replication_handler().initiate_replication();
         }
@@ -248,10 +248,11 @@ where C: RaftTypeConfig
         self.server_state_handler().update_server_state_if_changed();
     }
 
-    pub(crate) fn server_state_handler(&mut self) -> ServerStateHandler<'_, C> {
+    pub(crate) fn server_state_handler(&mut self) -> ServerStateHandler<'_, C, SM> {
         ServerStateHandler {
             config: self.config,
             state: self.state,
+            output: self.output,
         }
     }
 
@@ -392,6 +392,15 @@ where CLID: RaftCommittedLeaderId
         &self.key_log_ids
     }
 
+    /// Returns the indexes of the first log ids of the leaders in the index range `(after, upto]`,
+    /// in ascending order.
+    pub(crate) fn leader_starts(&self, after: u64, upto: u64) -> impl Iterator<Item = u64> + '_ {
+        self.key_log_ids
+            .iter()
+            .map(|log_id| log_id.index() + 1)
+            .filter(move |index| *index > after && *index <= upto)
+    }
+
     /// Returns key log ids appended by the last leader.
     ///
     /// With last-per-leader storage, the first index of the last leader is:
@@ -255,6 +255,20 @@ fn test_log_id_list_truncate() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_log_id_list_leader_starts() -> anyhow::Result<()> {
+    // Leader 2: index 0-2, leader 3: 3-5, leader 6: 6-8
+    let ids = LogIdList::<UtClid>::new(None, vec![log_id(2, 1, 2), log_id(3, 1, 5), log_id(6, 1, 8)]);
+
+    assert_eq!(vec![3, 6], ids.leader_starts(0, 8).collect::<Vec<_>>());
+    assert_eq!(vec![3, 6], ids.leader_starts(2, 6).collect::<Vec<_>>());
+    assert_eq!(vec![6], ids.leader_starts(3, 8).collect::<Vec<_>>());
+    assert_eq!(Vec::<u64>::new(), ids.leader_starts(0, 2).collect::<Vec<_>>());
+    assert_eq!(Vec::<u64>::new(), ids.leader_starts(6, 8).collect::<Vec<_>>());
+
+    Ok(())
+}
+
 #[test]
 fn test_log_id_list_purge() -> anyhow::Result<()> {
     // Purge on an empty log id list:
@@ -47,6 +47,7 @@ pub(crate) mod time_state;
 #[cfg(test)]
 mod tests {
     mod append_entries_test;
+    mod check_quorum_test;
     mod elect_test;
     mod handle_pre_vote_req_test;
     mod handle_pre_vote_resp_test;
@@ -64,6 +65,7 @@ mod log_id_list_test;
 #[cfg(test)]
 pub(crate) mod testing;
 
+pub(crate) use command::AppendEntriesSender;
 pub(crate) use command::Command;
 pub(crate) use command::Condition;
 pub(crate) use command::Respond;
@@ -1,11 +1,14 @@
 use std::collections::VecDeque;
 use std::fmt;
 
+use crate::Instant;
 use crate::RaftTypeConfig;
 use crate::engine::Respond;
 use crate::engine::respond_command::PendingRespond;
+use crate::raft::PendingRespondInfo;
 use crate::raft_state::IOId;
 use crate::raft_state::io_state::IOState;
+use crate::type_config::alias::InstantOf;
 use crate::type_config::alias::LogIdOf;
 
 /// Queues of pending responds waiting for IO conditions to be satisfied.
@@ -38,6 +41,37 @@ where C: RaftTypeConfig
         }
     }
 
+    /// The number of responds waiting in all the queues.
+    pub(crate) fn len(&self) -> usize {
+        self.on_log_io.len() + self.on_log_flush.len() + self.on_apply.len() + self.on_snapshot.len()
+    }
+
+    /// Describe every waiting respond, in the order of [`DrainPhase`], as of `now`.
+    pub(crate) fn infos(&self, now: InstantOf<C>) -> Vec<PendingRespondInfo<C>> {
+        fn info<C, V>(
+            phase: DrainPhase,
+            p: &PendingRespond<C, V>,
+            log_id: Option<&LogIdOf<C>>,
+            now: InstantOf<C>,
+        ) -> PendingRespondInfo<C>
+        where
+            C: RaftTypeConfig,
+        {
+            PendingRespondInfo {
+                waiting_for: phase.as_str(),
+                log_id: log_id.cloned(),
+                age: now.saturating_duration_since(p.since()),
+            }
+        }
+
+        let mut infos = Vec::with_capacity(self.len());
+        infos.extend(self.on_log_io.iter().map(|p| info(DrainPhase::LogIO, p, p.wait_for().last_log_id(), now)));
+        infos.extend(self.on_log_flush.iter().map(|p| info(DrainPhase::LogFlush, p, Some(p.wait_for()), now)));
+        infos.extend(self.on_apply.iter().map(|p| info(DrainPhase::Apply, p, Some(p.wait_for()), now)));
+        infos.extend(self.on_snapshot.iter().map(|p| info(DrainPhase::Snapshot, p, Some(p.wait_for()), now)));
+        infos
+    }
+
     /// Drain all satisfied responds based on the current IO state.
     ///
     /// Returns an iterator that yields all responds whose conditions are met by the provided
@@ -92,18 +126,24 @@ pub(crate) enum DrainPhase {
     Done,
 }
 
-impl fmt::Display for DrainPhase {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl DrainPhase {
+    pub(crate) const fn as_str(&self) -> &'static str {
         match self {
-            DrainPhase::LogIO => write!(f, "log_io"),
-            DrainPhase::LogFlush => write!(f, "log_flush"),
-            DrainPhase::Apply => write!(f, "apply"),
-            DrainPhase::Snapshot => write!(f, "snapshot"),
-            DrainPhase::Done => write!(f, "done"),
+            DrainPhase::LogIO => "log_io",
+            DrainPhase::LogFlush => "log_flush",
+            DrainPhase::Apply => "apply",
+            DrainPhase::Snapshot => "snapshot",
+            DrainPhase::Done => "done",
         }
     }
 }
 
+impl fmt::Display for DrainPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 impl<'a, C> Iterator for DrainSatisfied<'a, C>
 where C: RaftTypeConfig
 {
@@ -155,6 +195,8 @@ where C: RaftTypeConfig
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use openraft_rt_tokio::TokioRuntime;
     use validit::Valid;
 
@@ -169,6 +211,7 @@ mod tests {
     use crate::raft_state::IOId;
     use crate::raft_state::io_state::IOState;
     use crate::raft_state::io_state::io_progress::IOProgress;
+    use crate::type_config::TypeConfigExt;
     use crate::type_config::async_runtime::oneshot::Oneshot;
 
     type TestIOId = IOId<UTConfig>;
@@ -367,6 +410,32 @@ mod tests {
         assert_eq!(pending.on_snapshot.len(), 1);
     }
 
+    #[test]
+    fn test_infos() {
+        let mut pending = PendingResponds::<UTConfig>::new(10);
+        let io_id = TestIOId::new_log_io(Default::default(), Some(log_id(1, 1, 2)));
+        pending.on_log_io.push_back(PendingRespond::new(io_id, new_respond()));
+        pending.on_apply.push_back(PendingRespond::new(log_id(1, 1, 3), new_respond()));
+        pending.on_apply.push_back(PendingRespond::new(log_id(1, 1, 4), new_respond()));
+        pending.on_snapshot.push_back(PendingRespond::new(log_id(1, 1, 5), new_respond()));
+
+        let now = <UTConfig as TypeConfigExt>::now() + Duration::from_secs(2);
+        let infos = pending.infos(now);
+
+        let got = infos.iter().map(|i| (i.waiting_for, i.log_id)).collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                ("log_io", Some(log_id(1, 1, 2))),
+                ("apply", Some(log_id(1, 1, 3))),
+                ("apply", Some(log_id(1, 1, 4))),
+                ("snapshot", Some(log_id(1, 1, 5))),
+            ],
+            got
+        );
+        assert!(infos.iter().all(|i| i.age >= Duration::from_secs(2)));
+        assert_eq!(4, pending.len(), "listing does not remove responds");
+    }
+
     #[test]
     fn test_drain_phase_display() {
         assert_eq!(format!("{}", DrainPhase::LogIO), "log_io");
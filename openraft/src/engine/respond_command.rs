@@ -1,5 +1,7 @@
 use crate::RaftTypeConfig;
 use crate::engine::Respond;
+use crate::type_config::TypeConfigExt;
+use crate::type_config::alias::InstantOf;
 
 /// A respond waiting for an IO condition to be satisfied.
 ///
@@ -11,19 +13,30 @@ where C: RaftTypeConfig
     /// The expected progress value that must be reached before sending the respond.
     wait_for: V,
     respond: Respond<C>,
+
+    /// When the respond started waiting.
+    since: InstantOf<C>,
 }
 
 impl<C, V> PendingRespond<C, V>
 where C: RaftTypeConfig
 {
     pub(crate) fn new(wait_for: V, respond: Respond<C>) -> Self {
-        Self { wait_for, respond }
+        Self {
+            wait_for,
+            respond,
+            since: C::now(),
+        }
     }
 
     pub(crate) fn wait_for(&self) -> &V {
         &self.wait_for
     }
 
+    pub(crate) fn since(&self) -> InstantOf<C> {
+        self.since
+    }
+
     pub(crate) fn into_respond(self) -> Respond<C> {
         self.respond
     }
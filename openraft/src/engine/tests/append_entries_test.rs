@@ -12,6 +12,7 @@ use crate::core::ServerState;
 use crate::engine::Command;
 use crate::engine::Condition;
 use crate::engine::Engine;
+use crate::engine::Respond;
 use crate::engine::testing::UTConfig;
 use crate::engine::testing::log_id;
 use crate::entry::RaftEntry;
@@ -350,3 +351,64 @@ fn test_append_entries_conflict() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_handle_append_entries_relaxed_durability() -> anyhow::Result<()> {
+    // With relaxed durability, only the Leader this node voted for in relaxed mode is responded
+    // to without waiting for the flush.
+    let relaxed_leader = *Vote::new_committed(2, 1).leader_id();
+
+    for (recorded, want_flushed) in [(None, true), (Some(relaxed_leader), false)] {
+        let mut eng = eng();
+        eng.config.relaxed_durability = true;
+        eng.relaxed_leader = recorded;
+
+        let (tx, _rx) = UTConfig::<()>::oneshot();
+        eng.handle_append_entries(
+            &Vote::new_committed(2, 1),
+            LogSegment::new(Some(log_id(2, 1, 3)), vec![blank_ent::<UTConfig>(2, 1, 4)]),
+            [tx],
+        );
+
+        let respond = eng.output.take_commands().into_iter().find(|c| matches!(c, Command::Respond { .. }));
+        let Some(Command::Respond { when, .. }) = respond else {
+            panic!("expect a Respond command");
+        };
+        assert_eq!(want_flushed, when.is_some());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_handle_append_entries_merged_responds_once() -> anyhow::Result<()> {
+    // Merged requests are answered by a single response with the last log id of the segment.
+    let mut eng = eng();
+
+    let (tx1, _rx1) = UTConfig::<()>::oneshot();
+    let (tx2, _rx2) = UTConfig::<()>::oneshot();
+    eng.handle_append_entries(
+        &Vote::new_committed(2, 1),
+        LogSegment::new(Some(log_id(2, 1, 3)), vec![
+            blank_ent::<UTConfig>(2, 1, 4),
+            blank_ent::<UTConfig>(2, 1, 5),
+        ]),
+        [tx1, tx2],
+    );
+
+    let responds = eng
+        .output
+        .take_commands()
+        .into_iter()
+        .filter_map(|c| match c {
+            Command::Respond {
+                resp: Respond::AppendEntries(s),
+                ..
+            } => Some(s.value().clone()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(vec![Ok(Some(log_id(2, 1, 5)))], responds);
+
+    Ok(())
+}
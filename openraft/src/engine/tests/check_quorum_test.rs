@@ -0,0 +1,106 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use pretty_assertions::assert_eq;
+
+use crate::Membership;
+use crate::MembershipState;
+use crate::Vote;
+use crate::core::ServerState;
+use crate::engine::Command;
+use crate::engine::Engine;
+use crate::engine::testing::UTConfig;
+use crate::engine::testing::log_id;
+use crate::progress::Progress;
+use crate::type_config::TypeConfigExt;
+use crate::type_config::alias::InstantOf;
+use crate::type_config::alias::StoredMembershipOf;
+use crate::utime::Leased;
+
+fn m123() -> Membership<u64, ()> {
+    Membership::new_with_defaults(vec![btreeset! {1,2,3}], [])
+}
+
+/// Build a Leader engine of node 1 with voters 1,2,3; returns it with the time the Leader is
+/// established.
+fn eng_leader() -> (Engine<UTConfig>, InstantOf<UTConfig>) {
+    let mut eng = Engine::testing_default(0);
+    eng.state.enable_validation(false); // Disable validation for incomplete state
+
+    eng.config.id = 1;
+    eng.state.vote = Leased::new(
+        UTConfig::<()>::now(),
+        Duration::from_millis(500),
+        Vote::new_committed(3, 1),
+    );
+    eng.state.log_ids.append(log_id(1, 1, 1));
+    eng.state.membership_state = MembershipState::new(
+        Arc::new(StoredMembershipOf::<UTConfig>::new(Some(log_id(1, 1, 1)), m123())),
+        Arc::new(StoredMembershipOf::<UTConfig>::new(Some(log_id(1, 1, 1)), m123())),
+    );
+    let since = *eng.testing_new_leader().leader_since.instant;
+    eng.state.server_state = eng.calc_server_state();
+    eng.output.clear_commands();
+
+    (eng, since)
+}
+
+#[test]
+fn test_check_quorum_new_leader() -> anyhow::Result<()> {
+    // A newly established Leader is given an election timeout to hear from a quorum.
+    let (mut eng, since) = eng_leader();
+    let timeout = eng.config.timer_config.election_timeout;
+
+    assert!(!eng.check_quorum(since + timeout - Duration::from_millis(1)));
+
+    assert!(eng.leader.is_some());
+    assert_eq!(ServerState::Leader, eng.state.server_state);
+    assert!(eng.output.take_commands().is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_check_quorum_acked() -> anyhow::Result<()> {
+    // The Leader is established long ago, but node 2 acked recently: together with the Leader it
+    // is a quorum.
+    let (mut eng, now) = eng_leader();
+    let timeout = eng.config.timer_config.election_timeout;
+
+    let leader = eng.leader.as_mut().unwrap();
+    leader.leader_since.instant = (now - timeout * 2).into();
+    leader.clock_progress.increase_to(&2, Some(now)).ok();
+
+    assert!(!eng.check_quorum(now + timeout - Duration::from_millis(1)));
+
+    assert!(eng.leader.is_some());
+    assert_eq!(ServerState::Leader, eng.state.server_state);
+
+    Ok(())
+}
+
+#[test]
+fn test_check_quorum_step_down() -> anyhow::Result<()> {
+    // No quorum is heard from for an election timeout: stop leading, without increasing the term.
+    let (mut eng, now) = eng_leader();
+    let timeout = eng.config.timer_config.election_timeout;
+
+    eng.leader.as_mut().unwrap().leader_since.instant = (now - timeout * 2).into();
+
+    assert!(eng.check_quorum(now));
+
+    assert!(eng.leader.is_none());
+    assert!(eng.candidate_ref().is_none());
+    assert!(eng.pre_candidate_ref().is_none());
+    assert_eq!(Vote::new(3, 1), *eng.state.vote_ref());
+    assert_eq!(ServerState::Follower, eng.state.server_state);
+
+    // The election timer restarts from the step-down.
+    assert!(!eng.state.vote.is_expired(now, timeout));
+    assert!(eng.state.vote.is_expired(now + timeout + Duration::from_millis(1), timeout));
+
+    assert_eq!(vec![Command::CloseReplicationStreams], eng.output.take_commands());
+
+    Ok(())
+}
@@ -61,6 +61,8 @@ fn test_elect_single_node() -> anyhow::Result<()> {
                         vote: Vote::new(1, 1),
                         last_log_id: Some(log_id(0, 0, 0)),
                         leadership_transfer: false,
+                        relaxed_durability: false,
+                        config_digest: None,
                     },
                 },
             ],
@@ -97,6 +99,8 @@ fn test_elect_by_leadership_transfer_sets_flag() -> anyhow::Result<()> {
                     vote: Vote::new(1, 1),
                     last_log_id: Some(log_id(0, 0, 0)),
                     leadership_transfer: true,
+                    relaxed_durability: false,
+                    config_digest: None,
                 },
             },
         ],
@@ -144,6 +148,8 @@ fn test_elect_single_node_elect_again() -> anyhow::Result<()> {
                         vote: Vote::new(2, 1),
                         last_log_id: Some(log_id(0, 0, 0)),
                         leadership_transfer: false,
+                        relaxed_durability: false,
+                        config_digest: None,
                     },
                 },
             ],
@@ -153,6 +159,37 @@ fn test_elect_single_node_elect_again() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_elect_term_exhausted() -> anyhow::Result<()> {
+    // The term must not wrap around: the node refuses to start an election instead.
+    let mut eng = eng();
+    eng.config.id = 1;
+    eng.state.membership_state.set_effective(Arc::new(StoredMembershipOf::<UTConfig>::new(
+        Some(log_id(0, 1, 1)),
+        m12(),
+    )));
+    eng.state.vote = Leased::new(
+        UTConfig::<()>::now(),
+        Duration::from_millis(500),
+        Vote::new(u64::MAX, 2),
+    );
+    eng.state.server_state = ServerState::Follower;
+
+    eng.elect();
+
+    assert_eq!(Vote::new(u64::MAX, 2), *eng.state.vote_ref());
+    assert!(eng.candidate_ref().is_none());
+    assert_eq!(ServerState::Follower, eng.state.server_state);
+    assert_eq!(0, eng.output.take_commands().len());
+
+    eng.pre_elect();
+
+    assert!(eng.pre_candidate.is_none());
+    assert_eq!(0, eng.output.take_commands().len());
+
+    Ok(())
+}
+
 #[test]
 fn test_elect_multi_node_enter_candidate() -> anyhow::Result<()> {
     tracing::info!("--- multi nodes: enter candidate state");
@@ -191,3 +228,47 @@ fn test_elect_multi_node_enter_candidate() -> anyhow::Result<()> {
     }
     Ok(())
 }
+
+#[test]
+fn test_elect_witness() -> anyhow::Result<()> {
+    // A witness does not have the data to be leader: it never starts an election.
+    let mut eng = eng();
+    eng.config.id = 2;
+    eng.state.membership_state.set_effective(Arc::new(StoredMembershipOf::<UTConfig>::new(
+        Some(log_id(0, 1, 1)),
+        Membership::new_with_witnesses(vec![btreeset! {1,2}], btreeset! {1,2}, btreeset! {2})?,
+    )));
+    let vote = *eng.state.vote_ref();
+
+    eng.elect();
+    eng.pre_elect();
+
+    assert_eq!(vote, *eng.state.vote_ref());
+    assert!(eng.candidate_ref().is_none());
+    assert!(eng.pre_candidate.is_none());
+    assert_eq!(0, eng.output.take_commands().len());
+
+    Ok(())
+}
+
+#[test]
+fn test_elect_log_only() -> anyhow::Result<()> {
+    // A log-only node has no state machine to be leader: it never starts an election.
+    let mut eng = eng();
+    eng.config.id = 2;
+    eng.state.membership_state.set_effective(Arc::new(StoredMembershipOf::<UTConfig>::new(
+        Some(log_id(0, 1, 1)),
+        Membership::new_with_log_only(vec![btreeset! {1,2}], btreeset! {1,2}, btreeset! {2})?,
+    )));
+    let vote = *eng.state.vote_ref();
+
+    eng.elect();
+    eng.pre_elect();
+
+    assert_eq!(vote, *eng.state.vote_ref());
+    assert!(eng.candidate_ref().is_none());
+    assert!(eng.pre_candidate.is_none());
+    assert_eq!(0, eng.output.take_commands().len());
+
+    Ok(())
+}
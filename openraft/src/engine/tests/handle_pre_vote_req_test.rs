@@ -54,6 +54,8 @@ fn test_handle_pre_vote_req_rejected_by_leader_lease() -> anyhow::Result<()> {
         vote: Vote::new(3, 2),
         last_log_id: Some(log_id(2, 1, 3)),
         leadership_transfer: false,
+        relaxed_durability: false,
+        config_digest: None,
     });
 
     assert_eq!(
@@ -78,6 +80,8 @@ fn test_handle_pre_vote_req_reject_smaller_last_log_id() -> anyhow::Result<()> {
         vote: Vote::new(3, 0),
         last_log_id: None,
         leadership_transfer: false,
+        relaxed_durability: false,
+        config_digest: None,
     });
 
     assert_eq!(VoteResponse::new(Vote::new(2, 1), Some(log_id(1, 1, 1)), false), resp);
@@ -97,6 +101,8 @@ fn test_handle_pre_vote_req_granted_without_mutation() -> anyhow::Result<()> {
         vote: Vote::new(3, 0),
         last_log_id: Some(log_id(1, 1, 1)),
         leadership_transfer: false,
+        relaxed_durability: false,
+        config_digest: None,
     });
 
     assert_eq!(VoteResponse::new(Vote::new(2, 1), Some(log_id(1, 1, 1)), true), resp);
@@ -53,6 +53,8 @@ fn test_handle_vote_req_rejected_by_leader_lease() -> anyhow::Result<()> {
         vote: Vote::new(3, 2),
         last_log_id: Some(log_id(2, 1, 3)),
         leadership_transfer: false,
+        relaxed_durability: false,
+        config_digest: None,
     });
 
     assert_eq!(VoteResponse::new(Vote::new_committed(2, 1), None, false), resp);
@@ -83,6 +85,8 @@ fn test_handle_vote_req_leadership_transfer_overrides_leader_lease() -> anyhow::
         vote: Vote::new(3, 2),
         last_log_id: Some(log_id(2, 1, 3)),
         leadership_transfer: true,
+        relaxed_durability: false,
+        config_digest: None,
     });
 
     assert_eq!(VoteResponse::new(Vote::new(3, 2), None, true), resp);
@@ -111,6 +115,8 @@ fn test_handle_vote_req_reject_smaller_vote() -> anyhow::Result<()> {
         vote: Vote::new(1, 2),
         last_log_id: None,
         leadership_transfer: false,
+        relaxed_durability: false,
+        config_digest: None,
     });
 
     assert_eq!(VoteResponse::new(Vote::new(2, 1), None, false), resp);
@@ -134,6 +140,8 @@ fn test_handle_vote_req_reject_smaller_last_log_id() -> anyhow::Result<()> {
         vote: Vote::new(3, 2),
         last_log_id: Some(log_id(1, 1, 3)),
         leadership_transfer: false,
+        relaxed_durability: false,
+        config_digest: None,
     });
 
     assert_eq!(VoteResponse::new(Vote::new(2, 1), Some(log_id(2, 1, 3)), false), resp);
@@ -162,6 +170,8 @@ fn test_handle_vote_req_granted_equal_vote_and_last_log_id() -> anyhow::Result<(
         vote: Vote::new(2, 1),
         last_log_id: Some(log_id(2, 1, 3)),
         leadership_transfer: false,
+        relaxed_durability: false,
+        config_digest: None,
     });
 
     assert_eq!(VoteResponse::new(Vote::new(2, 1), Some(log_id(2, 1, 3)), true), resp);
@@ -189,6 +199,8 @@ fn test_handle_vote_req_granted_greater_vote() -> anyhow::Result<()> {
         vote: Vote::new(3, 1),
         last_log_id: Some(log_id(2, 1, 3)),
         leadership_transfer: false,
+        relaxed_durability: false,
+        config_digest: None,
     });
 
     // respond the updated vote.
@@ -226,6 +238,8 @@ fn test_handle_vote_req_granted_follower_learner_does_not_emit_update_server_sta
             vote: Vote::new(3, 1),
             last_log_id: Some(log_id(2, 1, 3)),
             leadership_transfer: false,
+            relaxed_durability: false,
+            config_digest: None,
         });
 
         assert_eq!(st, eng.state.server_state);
@@ -251,6 +265,8 @@ fn test_handle_vote_req_granted_follower_learner_does_not_emit_update_server_sta
             vote: Vote::new(3, 1),
             last_log_id: Some(log_id(2, 1, 3)),
             leadership_transfer: false,
+            relaxed_durability: false,
+            config_digest: None,
         });
 
         assert_eq!(st, eng.state.server_state);
@@ -264,3 +280,77 @@ fn test_handle_vote_req_granted_follower_learner_does_not_emit_update_server_sta
     }
     Ok(())
 }
+
+#[test]
+fn test_handle_vote_req_reject_durability_mode_mismatch() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.config.id = 0;
+    eng.vote_handler().update_internal_server_state();
+    eng.state.log_ids = LogIdList::new(None, vec![log_id(2, 1, 3)]);
+
+    eng.output.clear_commands();
+
+    let resp = eng.handle_vote_req(VoteRequest {
+        vote: Vote::new(3, 1),
+        last_log_id: Some(log_id(2, 1, 3)),
+        leadership_transfer: false,
+        relaxed_durability: true,
+        config_digest: None,
+    });
+
+    assert_eq!(VoteResponse::new(Vote::new(2, 1), Some(log_id(2, 1, 3)), false), resp);
+    assert_eq!(Vote::new(2, 1), *eng.state.vote_ref());
+    assert_eq!(None, eng.relaxed_leader);
+    assert_eq!(0, eng.output.take_commands().len());
+
+    Ok(())
+}
+
+#[test]
+fn test_handle_vote_req_reject_malformed() -> anyhow::Result<()> {
+    // The candidate's last log id is proposed by a leader greater than the vote it asks for.
+    let mut eng = eng();
+    eng.config.id = 0;
+    eng.vote_handler().update_internal_server_state();
+    eng.state.log_ids = LogIdList::new(None, vec![log_id(2, 1, 3)]);
+
+    eng.output.clear_commands();
+
+    let resp = eng.handle_vote_req(VoteRequest {
+        vote: Vote::new(3, 1),
+        last_log_id: Some(log_id(4, 1, 3)),
+        leadership_transfer: false,
+        relaxed_durability: false,
+        config_digest: None,
+    });
+
+    assert_eq!(VoteResponse::new(Vote::new(2, 1), Some(log_id(2, 1, 3)), false), resp);
+    assert_eq!(Vote::new(2, 1), *eng.state.vote_ref());
+    assert_eq!(0, eng.output.take_commands().len());
+
+    Ok(())
+}
+
+#[test]
+fn test_handle_vote_req_granted_records_relaxed_leader() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.config.id = 0;
+    eng.config.relaxed_durability = true;
+    eng.vote_handler().update_internal_server_state();
+    eng.state.log_ids = LogIdList::new(None, vec![log_id(2, 1, 3)]);
+
+    eng.output.clear_commands();
+
+    let resp = eng.handle_vote_req(VoteRequest {
+        vote: Vote::new(3, 1),
+        last_log_id: Some(log_id(2, 1, 3)),
+        leadership_transfer: false,
+        relaxed_durability: true,
+        config_digest: None,
+    });
+
+    assert_eq!(VoteResponse::new(Vote::new(3, 1), Some(log_id(2, 1, 3)), true), resp);
+    assert_eq!(Some(*Vote::new(3, 1).leader_id()), eng.relaxed_leader);
+
+    Ok(())
+}
@@ -13,6 +13,7 @@ use crate::engine::LogIdList;
 use crate::engine::testing::UTConfig;
 use crate::engine::testing::log_id;
 use crate::entry::RaftEntry;
+use crate::errors::DuplicateNode;
 use crate::errors::InitializeError;
 use crate::errors::NotAllowed;
 use crate::errors::NotInMembers;
@@ -152,5 +153,17 @@ fn test_initialize() -> anyhow::Result<()> {
         );
     }
 
+    tracing::info!("--- node 1 and node 2 have the same node info");
+    {
+        let mut eng = eng();
+        eng.config.id = 1;
+        eng.config.reject_duplicate_nodes = true;
+
+        assert_eq!(
+            Err(InitializeError::DuplicateNode(DuplicateNode { node_id: 2, other: 1 })),
+            eng.initialize(m12())
+        );
+    }
+
     Ok(())
 }
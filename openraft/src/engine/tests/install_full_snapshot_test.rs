@@ -50,6 +50,7 @@ fn eng() -> Engine<UTConfig> {
         last_log_id: Some(log_id(2, 1, 2)),
         last_membership: StoredMembershipOf::<UTConfig>::new(Some(log_id(1, 1, 1)), m12()),
         snapshot_id: "1-2-3-4".to_string(),
+        base_snapshot_id: None,
     };
     eng.state.server_state = eng.calc_server_state();
 
@@ -76,6 +77,7 @@ fn test_handle_install_full_snapshot_lt_last_snapshot() -> anyhow::Result<()> {
                 last_log_id: Some(log_id(1, 1, 2)),
                 last_membership: StoredMembershipOf::<UTConfig>::new(Some(log_id(1, 1, 1)), m1234()),
                 snapshot_id: "1-2-3-4".to_string(),
+                base_snapshot_id: None,
             },
             snapshot: Cursor::new(vec![0u8]),
         },
@@ -87,6 +89,7 @@ fn test_handle_install_full_snapshot_lt_last_snapshot() -> anyhow::Result<()> {
             last_log_id: Some(log_id(2, 1, 2)),
             last_membership: StoredMembershipOf::<UTConfig>::new(Some(log_id(1, 1, 1)), m12()),
             snapshot_id: "1-2-3-4".to_string(),
+            base_snapshot_id: None,
         },
         eng.state.snapshot_meta
     );
@@ -103,6 +106,57 @@ fn test_handle_install_full_snapshot_lt_last_snapshot() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_handle_install_full_snapshot_reject_malformed() -> anyhow::Result<()> {
+    // A malformed snapshot is rejected before the vote is accepted.
+    // It should respond at once with the current vote.
+
+    let curr_vote = Vote::new_committed(2, 1);
+
+    let malformed = [
+        // Uncommitted vote
+        (Vote::new(3, 1), Some(log_id(2, 1, 6)), Some(log_id(1, 1, 1))),
+        // No last log id
+        (Vote::new_committed(3, 1), None, None),
+        // Membership after the last log id
+        (Vote::new_committed(3, 1), Some(log_id(2, 1, 6)), Some(log_id(2, 1, 7))),
+    ];
+
+    for (vote, last_log_id, membership_log_id) in malformed {
+        let mut eng = eng();
+
+        let (tx, _rx) = UTConfig::<()>::oneshot();
+
+        eng.handle_install_full_snapshot(
+            vote,
+            SnapshotOf::<UTConfig> {
+                meta: SnapshotMetaOf::<UTConfig> {
+                    last_log_id,
+                    last_membership: StoredMembershipOf::<UTConfig>::new(membership_log_id, m1234()),
+                    snapshot_id: "1-2-3-4".to_string(),
+                    base_snapshot_id: None,
+                },
+                snapshot: Cursor::new(vec![0u8]),
+            },
+            tx,
+        );
+
+        assert_eq!(curr_vote, *eng.state.vote_ref());
+        assert_eq!(Some(&log_id(2, 1, 2)), eng.state.snapshot_meta.last_log_id.as_ref());
+
+        let (dummy_tx, _rx) = UTConfig::<()>::oneshot();
+        assert_eq!(
+            vec![Command::Respond {
+                when: None,
+                resp: Respond::new(SnapshotResponse::new(curr_vote), dummy_tx),
+            }],
+            eng.output.take_commands()
+        );
+    }
+
+    Ok(())
+}
+
 #[test]
 fn test_handle_install_full_snapshot_no_conflict() -> anyhow::Result<()> {
     // Snapshot will be installed and there are no conflicting logs.
@@ -121,6 +175,7 @@ fn test_handle_install_full_snapshot_no_conflict() -> anyhow::Result<()> {
                 last_log_id: Some(log_id(4, 1, 6)),
                 last_membership: StoredMembershipOf::<UTConfig>::new(Some(log_id(1, 1, 1)), m1234()),
                 snapshot_id: "1-2-3-4".to_string(),
+                base_snapshot_id: None,
             },
             snapshot: Cursor::new(vec![0u8]),
         },
@@ -132,6 +187,7 @@ fn test_handle_install_full_snapshot_no_conflict() -> anyhow::Result<()> {
             last_log_id: Some(log_id(4, 1, 6)),
             last_membership: StoredMembershipOf::<UTConfig>::new(Some(log_id(1, 1, 1)), m1234()),
             snapshot_id: "1-2-3-4".to_string(),
+            base_snapshot_id: None,
         },
         eng.state.snapshot_meta
     );
@@ -146,6 +202,7 @@ fn test_handle_install_full_snapshot_no_conflict() -> anyhow::Result<()> {
                         last_log_id: Some(log_id(4, 1, 6)),
                         last_membership: StoredMembershipOf::<UTConfig>::new(Some(log_id(1, 1, 1)), m1234()),
                         snapshot_id: "1-2-3-4".to_string(),
+                        base_snapshot_id: None,
                     },
                     snapshot: Cursor::new(vec![0u8]),
                 },
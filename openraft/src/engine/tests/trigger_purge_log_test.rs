@@ -54,6 +54,7 @@ fn test_trigger_purge_log_already_scheduled() -> anyhow::Result<()> {
         last_log_id: Some(log_id(1, 0, 3)),
         last_membership: StoredMembershipOf::<UTConfig>::new(Some(log_id(1, 0, 1)), m12()),
         snapshot_id: "1".to_string(),
+        base_snapshot_id: None,
     };
     eng.state.purge_upto = Some(log_id(1, 0, 2));
     eng.state.io_state.purged = Some(log_id(1, 0, 2));
@@ -74,6 +75,7 @@ fn test_trigger_purge_log_delete_only_in_snapshot_logs() -> anyhow::Result<()> {
         last_log_id: Some(log_id(1, 0, 3)),
         last_membership: StoredMembershipOf::<UTConfig>::new(Some(log_id(1, 0, 1)), m12()),
         snapshot_id: "1".to_string(),
+        base_snapshot_id: None,
     };
     eng.state.purge_upto = Some(log_id(1, 0, 2));
     eng.state.io_state.purged = Some(log_id(1, 0, 2));
@@ -95,6 +97,94 @@ fn test_trigger_purge_log_delete_only_in_snapshot_logs() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_trigger_purge_log_durable_applied() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.state.snapshot_meta = SnapshotMeta {
+        last_log_id: Some(log_id(1, 0, 3)),
+        last_membership: StoredMembershipOf::<UTConfig>::new(Some(log_id(1, 0, 1)), m12()),
+        snapshot_id: "1".to_string(),
+        base_snapshot_id: None,
+    };
+    eng.state.purge_upto = Some(log_id(1, 0, 2));
+    eng.state.io_state.purged = Some(log_id(1, 0, 2));
+    eng.state.log_ids = LogIdList::new(Some(log_id(1, 0, 2)), [log_id(1, 0, 10)]);
+    eng.state.durable_applied = Some(log_id(1, 0, 7));
+
+    eng.trigger_purge_log(9);
+
+    assert_eq!(
+        Some(log_id(1, 0, 7)),
+        eng.state.purge_upto,
+        "delete persisted applied logs beyond the snapshot"
+    );
+
+    assert_eq!(
+        vec![Command::PurgeLog { upto: log_id(1, 0, 7) },],
+        eng.output.take_commands()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_trigger_purge_log_log_only_hold() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.state.snapshot_meta = SnapshotMeta {
+        last_log_id: Some(log_id(1, 0, 3)),
+        last_membership: StoredMembershipOf::<UTConfig>::new(Some(log_id(1, 0, 1)), m12()),
+        snapshot_id: "1".to_string(),
+        base_snapshot_id: None,
+    };
+    eng.state.purge_upto = Some(log_id(1, 0, 2));
+    eng.state.io_state.purged = Some(log_id(1, 0, 2));
+    eng.state.log_ids = LogIdList::new(Some(log_id(1, 0, 2)), [log_id(1, 0, 10)]);
+    eng.state.durable_applied = Some(log_id(1, 0, 7));
+    eng.state.log_only_hold = Some(5);
+
+    eng.trigger_purge_log(9);
+
+    assert_eq!(
+        Some(log_id(1, 0, 4)),
+        eng.state.purge_upto,
+        "keep logs a log-only voter needs"
+    );
+
+    assert_eq!(
+        vec![Command::PurgeLog { upto: log_id(1, 0, 4) },],
+        eng.output.take_commands()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_trigger_purge_log_postponed_by_log_only_hold() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.state.snapshot_meta = SnapshotMeta {
+        last_log_id: Some(log_id(1, 0, 3)),
+        last_membership: StoredMembershipOf::<UTConfig>::new(Some(log_id(1, 0, 1)), m12()),
+        snapshot_id: "1".to_string(),
+        base_snapshot_id: None,
+    };
+    eng.state.purge_upto = Some(log_id(1, 0, 3));
+    eng.state.io_state.purged = Some(log_id(1, 0, 1));
+    eng.state.log_ids = LogIdList::new(Some(log_id(1, 0, 1)), [log_id(1, 0, 10)]);
+    eng.state.log_only_hold = Some(2);
+
+    eng.try_purge_log();
+
+    assert_eq!(Some(log_id(1, 0, 3)), eng.state.purge_upto, "scheduled before the hold");
+
+    assert_eq!(
+        0,
+        eng.output.take_commands().len(),
+        "logs a log-only voter needs won't be deleted"
+    );
+
+    Ok(())
+}
+
 #[test]
 fn test_trigger_purge_log_in_used_wont_be_delete() -> anyhow::Result<()> {
     let mut eng = eng();
@@ -102,6 +192,7 @@ fn test_trigger_purge_log_in_used_wont_be_delete() -> anyhow::Result<()> {
         last_log_id: Some(log_id(1, 0, 3)),
         last_membership: StoredMembershipOf::<UTConfig>::new(Some(log_id(1, 0, 1)), m12()),
         snapshot_id: "1".to_string(),
+        base_snapshot_id: None,
     };
     eng.state.purge_upto = Some(log_id(1, 0, 2));
     eng.state.io_state.purged = Some(log_id(1, 0, 2));
@@ -0,0 +1,93 @@
+//! Restricts which nodes apply a log entry.
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+use display_more::DisplayBTreeSetExt;
+use openraft_macros::since;
+
+use crate::entry::EncodeContent;
+use crate::node::NodeId;
+
+/// The set of nodes that apply a log entry to their state machine.
+///
+/// An entry with a scope is replicated and committed as usual, but a node out of the scope
+/// applies it as a blank entry. This makes a canary rollout possible: an entry is first applied
+/// only by a few learners, see [`Raft::client_write_canary()`], and once it is proven safe, it is
+/// proposed again to be applied by the other nodes, see [`Raft::confirm_canary()`].
+///
+/// [`Raft::client_write_canary()`]: crate::Raft::client_write_canary
+/// [`Raft::confirm_canary()`]: crate::Raft::confirm_canary
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub enum ApplyScope<NID>
+where NID: NodeId
+{
+    /// Only these nodes apply the entry.
+    Only(BTreeSet<NID>),
+
+    /// Every node except these applies the entry.
+    Except(BTreeSet<NID>),
+}
+
+impl<NID> ApplyScope<NID>
+where NID: NodeId
+{
+    /// Returns `true` if the node `id` applies an entry with this scope.
+    #[since(version = "0.10.0")]
+    pub fn includes(&self, id: &NID) -> bool {
+        match self {
+            ApplyScope::Only(ids) => ids.contains(id),
+            ApplyScope::Except(ids) => !ids.contains(id),
+        }
+    }
+}
+
+impl<NID> fmt::Display for ApplyScope<NID>
+where NID: NodeId
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApplyScope::Only(ids) => write!(f, "only:{}", ids.display()),
+            ApplyScope::Except(ids) => write!(f, "except:{}", ids.display()),
+        }
+    }
+}
+
+impl<NID> EncodeContent for ApplyScope<NID>
+where NID: NodeId + EncodeContent
+{
+    fn encode_content(&self, buf: &mut Vec<u8>) {
+        match self {
+            ApplyScope::Only(ids) => {
+                buf.push(0);
+                ids.encode_content(buf);
+            }
+            ApplyScope::Except(ids) => {
+                buf.push(1);
+                ids.encode_content(buf);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use maplit::btreeset;
+
+    use super::ApplyScope;
+
+    #[test]
+    fn test_apply_scope_includes() {
+        let only = ApplyScope::Only(btreeset! {3u64});
+        assert!(only.includes(&3));
+        assert!(!only.includes(&1));
+        assert_eq!("only:[3]", only.to_string());
+
+        let except = ApplyScope::Except(btreeset! {3u64});
+        assert!(!except.includes(&3));
+        assert!(except.includes(&1));
+        assert_eq!("except:[3]", except.to_string());
+    }
+}
@@ -0,0 +1,172 @@
+//! Canonical encoding of the content of log entries, to chain them with a hash.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use openraft_macros::since;
+
+/// A canonical byte encoding of a value, which the hash chain of the log is computed over.
+///
+/// Followers recompute the chain hash of every entry they receive and refuse an entry whose hash
+/// does not match, thus equal values must be encoded into the same bytes on every node and by
+/// every version. E.g., encode a map in its key order, rather than in the iteration order of a
+/// `HashMap`, and do not rely on a serializer whose output may change between releases.
+///
+/// The built-in [`Entry`](crate::Entry) encodes its content with it, if the `audit-log-chain`
+/// feature is enabled. See [`Config::audit_log_chain`](crate::Config::audit_log_chain).
+#[since(version = "0.10.0")]
+pub trait EncodeContent {
+    /// Append the encoding of `self` to `buf`.
+    fn encode_content(&self, buf: &mut Vec<u8>);
+}
+
+macro_rules! impl_encode_content_for_int {
+    ($($t:ty),*) => {
+        $(
+            impl EncodeContent for $t {
+                fn encode_content(&self, buf: &mut Vec<u8>) {
+                    buf.extend_from_slice(&self.to_be_bytes());
+                }
+            }
+        )*
+    };
+}
+
+impl_encode_content_for_int!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+/// Encoded as a `u64`, so that it does not depend on the platform.
+impl EncodeContent for usize {
+    fn encode_content(&self, buf: &mut Vec<u8>) {
+        (*self as u64).encode_content(buf);
+    }
+}
+
+impl EncodeContent for bool {
+    fn encode_content(&self, buf: &mut Vec<u8>) {
+        buf.push(*self as u8);
+    }
+}
+
+impl EncodeContent for () {
+    fn encode_content(&self, _buf: &mut Vec<u8>) {}
+}
+
+impl EncodeContent for str {
+    fn encode_content(&self, buf: &mut Vec<u8>) {
+        self.len().encode_content(buf);
+        buf.extend_from_slice(self.as_bytes());
+    }
+}
+
+impl EncodeContent for String {
+    fn encode_content(&self, buf: &mut Vec<u8>) {
+        self.as_str().encode_content(buf);
+    }
+}
+
+impl<T> EncodeContent for &T
+where T: EncodeContent + ?Sized
+{
+    fn encode_content(&self, buf: &mut Vec<u8>) {
+        (**self).encode_content(buf);
+    }
+}
+
+impl<T> EncodeContent for Box<T>
+where T: EncodeContent + ?Sized
+{
+    fn encode_content(&self, buf: &mut Vec<u8>) {
+        (**self).encode_content(buf);
+    }
+}
+
+impl<T> EncodeContent for Option<T>
+where T: EncodeContent
+{
+    fn encode_content(&self, buf: &mut Vec<u8>) {
+        match self {
+            None => buf.push(0),
+            Some(v) => {
+                buf.push(1);
+                v.encode_content(buf);
+            }
+        }
+    }
+}
+
+impl<T> EncodeContent for [T]
+where T: EncodeContent
+{
+    fn encode_content(&self, buf: &mut Vec<u8>) {
+        self.len().encode_content(buf);
+        for v in self {
+            v.encode_content(buf);
+        }
+    }
+}
+
+impl<T> EncodeContent for Vec<T>
+where T: EncodeContent
+{
+    fn encode_content(&self, buf: &mut Vec<u8>) {
+        self.as_slice().encode_content(buf);
+    }
+}
+
+impl<T> EncodeContent for BTreeSet<T>
+where T: EncodeContent
+{
+    fn encode_content(&self, buf: &mut Vec<u8>) {
+        self.len().encode_content(buf);
+        for v in self {
+            v.encode_content(buf);
+        }
+    }
+}
+
+impl<K, V> EncodeContent for BTreeMap<K, V>
+where
+    K: EncodeContent,
+    V: EncodeContent,
+{
+    fn encode_content(&self, buf: &mut Vec<u8>) {
+        self.len().encode_content(buf);
+        for (k, v) in self {
+            k.encode_content(buf);
+            v.encode_content(buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::EncodeContent;
+
+    fn encode<T: EncodeContent + ?Sized>(v: &T) -> Vec<u8> {
+        let mut buf = Vec::new();
+        v.encode_content(&mut buf);
+        buf
+    }
+
+    #[test]
+    fn test_encode_content() {
+        assert_eq!(vec![0, 0, 0, 0, 0, 0, 1, 2], encode(&0x0102u64));
+        assert_eq!(encode(&3u64), encode(&3usize));
+        assert_eq!(vec![0, 0, 0, 0, 0, 0, 0, 2, b'a', b'b'], encode("ab"));
+        assert_eq!(vec![0], encode(&None::<u64>));
+        assert_eq!(vec![1, 7], encode(&Some(7u8)));
+
+        // Length prefixes keep adjacent fields apart.
+        assert_ne!(
+            [encode("a"), encode("bc")].concat(),
+            [encode("ab"), encode("c")].concat()
+        );
+
+        // A map is encoded in key order, whatever order it is built in.
+        let a = BTreeMap::from([(1u64, "x"), (2, "y")]);
+        let b = BTreeMap::from([(2u64, "y"), (1, "x")]);
+        assert_eq!(encode(&a), encode(&b));
+    }
+}
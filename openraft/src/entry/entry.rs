@@ -5,6 +5,10 @@ use openraft_macros::since;
 use crate::AppData;
 use crate::EntryPayload;
 use crate::Membership;
+use crate::OptionalEncodeContent;
+use crate::entry::ApplyScope;
+use crate::entry::ChainHash;
+use crate::entry::EntryKind;
 use crate::entry::RaftEntry;
 use crate::entry::RaftPayload;
 use crate::log_id::LogId;
@@ -13,7 +17,10 @@ use crate::node::NodeId;
 use crate::vote::RaftCommittedLeaderId;
 
 /// A Raft log entry.
-#[since(version = "0.10.0", change = "from `Entry<C>` to `Entry<CLID, D, NID, N>`")]
+#[since(
+    version = "0.10.0",
+    change = "from `Entry<C>` to `Entry<CLID, D, NID, N>`, add `apply_scope`, `timestamp`, `deadline`, `expire_from` and `chain_hash`"
+)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
 pub struct Entry<CLID, D, NID, N>
 where
@@ -27,6 +34,39 @@ where
 
     /// This entry's payload.
     pub payload: EntryPayload<D, NID, N>,
+
+    /// The nodes that apply this entry, `None` if every node applies it.
+    ///
+    /// See [`ApplyScope`].
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub apply_scope: Option<ApplyScope<NID>>,
+
+    /// The leader's wall-clock time when this entry is proposed, in milliseconds since the UNIX
+    /// epoch, `None` if it is not stamped.
+    ///
+    /// See [`Config::entry_timestamp`](crate::Config::entry_timestamp).
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub timestamp: Option<u64>,
+
+    /// The wall-clock time by which this entry must be committed, in milliseconds since the UNIX
+    /// epoch, `None` if it has no deadline.
+    ///
+    /// See [`RaftEntry::deadline()`].
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub deadline: Option<u64>,
+
+    /// On the first entry of a leader that re-establishes itself, the index of the first entry not
+    /// committed in its previous term, `None` if it is not recorded.
+    ///
+    /// See [`RaftEntry::expire_from()`].
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub expire_from: Option<u64>,
+
+    /// The hash that chains this entry to the entry before it, `None` if it is not chained.
+    ///
+    /// See [`Config::audit_log_chain`](crate::Config::audit_log_chain).
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub chain_hash: Option<ChainHash>,
 }
 
 impl<CLID, D, NID, N> Clone for Entry<CLID, D, NID, N>
@@ -40,6 +80,11 @@ where
         Self {
             log_id: self.log_id.clone(),
             payload: self.payload.clone(),
+            apply_scope: self.apply_scope.clone(),
+            timestamp: self.timestamp,
+            deadline: self.deadline,
+            expire_from: self.expire_from,
+            chain_hash: self.chain_hash,
         }
     }
 }
@@ -52,7 +97,15 @@ where
     N: Node,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("Entry").field("log_id", &self.log_id).field("payload", &self.payload).finish()
+        f.debug_struct("Entry")
+            .field("log_id", &self.log_id)
+            .field("payload", &self.payload)
+            .field("apply_scope", &self.apply_scope)
+            .field("timestamp", &self.timestamp)
+            .field("deadline", &self.deadline)
+            .field("expire_from", &self.expire_from)
+            .field("chain_hash", &self.chain_hash)
+            .finish()
     }
 }
 
@@ -64,7 +117,13 @@ where
     N: Node,
 {
     fn eq(&self, other: &Self) -> bool {
-        self.log_id == other.log_id && self.payload == other.payload
+        self.log_id == other.log_id
+            && self.payload == other.payload
+            && self.apply_scope == other.apply_scope
+            && self.timestamp == other.timestamp
+            && self.deadline == other.deadline
+            && self.expire_from == other.expire_from
+            && self.chain_hash == other.chain_hash
     }
 }
 
@@ -88,7 +147,14 @@ where
     N: Node,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}:{}", self.log_id, self.payload)
+        write!(f, "{}:{}", self.log_id, self.payload)?;
+        if let Some(scope) = &self.apply_scope {
+            write!(f, "({})", scope)?;
+        }
+        if let Some(ts) = self.timestamp {
+            write!(f, "@{}ms", ts)?;
+        }
+        Ok(())
     }
 }
 
@@ -102,14 +168,20 @@ where
     fn get_membership(&self) -> Option<Membership<NID, N>> {
         self.payload.get_membership()
     }
+
+    fn kind(&self) -> EntryKind {
+        self.payload.kind()
+    }
 }
 
+/// With the `audit-log-chain` feature, the leader id, application data, node id and node types
+/// are required to implement [`EncodeContent`](crate::entry::EncodeContent), to chain the entries.
 impl<CLID, D, NID, N> RaftEntry for Entry<CLID, D, NID, N>
 where
-    CLID: RaftCommittedLeaderId,
-    D: AppData,
-    NID: NodeId,
-    N: Node,
+    CLID: RaftCommittedLeaderId + OptionalEncodeContent,
+    D: AppData + OptionalEncodeContent,
+    NID: NodeId + OptionalEncodeContent,
+    N: Node + OptionalEncodeContent,
 {
     type CommittedLeaderId = CLID;
     type D = D;
@@ -117,7 +189,15 @@ where
     type Node = N;
 
     fn new(log_id: LogId<CLID>, payload: EntryPayload<D, NID, N>) -> Self {
-        Self { log_id, payload }
+        Self {
+            log_id,
+            payload,
+            apply_scope: None,
+            timestamp: None,
+            deadline: None,
+            expire_from: None,
+            chain_hash: None,
+        }
     }
 
     fn log_id_parts(&self) -> (&CLID, u64) {
@@ -127,4 +207,65 @@ where
     fn set_log_id(&mut self, new: LogId<CLID>) {
         self.log_id = new;
     }
+
+    fn apply_scope(&self) -> Option<&ApplyScope<NID>> {
+        self.apply_scope.as_ref()
+    }
+
+    fn set_apply_scope(&mut self, scope: Option<ApplyScope<NID>>) -> bool {
+        self.apply_scope = scope;
+        true
+    }
+
+    fn timestamp(&self) -> Option<u64> {
+        self.timestamp
+    }
+
+    fn set_timestamp(&mut self, timestamp: Option<u64>) -> bool {
+        self.timestamp = timestamp;
+        true
+    }
+
+    fn deadline(&self) -> Option<u64> {
+        self.deadline
+    }
+
+    fn set_deadline(&mut self, deadline: Option<u64>) -> bool {
+        self.deadline = deadline;
+        true
+    }
+
+    fn expire_from(&self) -> Option<u64> {
+        self.expire_from
+    }
+
+    fn set_expire_from(&mut self, index: Option<u64>) -> bool {
+        self.expire_from = index;
+        true
+    }
+
+    fn chain_hash(&self) -> Option<ChainHash> {
+        self.chain_hash
+    }
+
+    fn set_chain_hash(&mut self, hash: Option<ChainHash>) -> bool {
+        self.chain_hash = hash;
+        true
+    }
+
+    /// Encodes every field except the chain hash with [`EncodeContent`].
+    ///
+    /// [`EncodeContent`]: crate::entry::EncodeContent
+    #[cfg(feature = "audit-log-chain")]
+    fn encode_content(&self, buf: &mut Vec<u8>) -> bool {
+        use crate::entry::EncodeContent;
+
+        self.log_id.encode_content(buf);
+        self.payload.encode_content(buf);
+        self.apply_scope.encode_content(buf);
+        self.timestamp.encode_content(buf);
+        self.deadline.encode_content(buf);
+        self.expire_from.encode_content(buf);
+        true
+    }
 }
@@ -0,0 +1,30 @@
+use std::fmt;
+
+use openraft_macros::since;
+
+/// The kind of payload a log entry carries, without the payload itself.
+///
+/// See [`LogEntryMeta`](crate::storage::LogEntryMeta).
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum EntryKind {
+    /// An empty payload, e.g., proposed by a new leader.
+    Blank,
+
+    /// Application data.
+    Normal,
+
+    /// A membership config.
+    Membership,
+}
+
+impl fmt::Display for EntryKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EntryKind::Blank => write!(f, "Blank"),
+            EntryKind::Normal => write!(f, "Normal"),
+            EntryKind::Membership => write!(f, "Membership"),
+        }
+    }
+}
@@ -0,0 +1,66 @@
+//! Expiry of the log entries with a deadline, see [`RaftEntry::deadline()`].
+
+use crate::RaftLogReader;
+use crate::RaftTypeConfig;
+use crate::StorageError;
+use crate::entry::RaftEntry;
+use crate::storage::RaftLogReaderExt;
+use crate::type_config::TypeConfigExt;
+
+/// The first entries of the leaders in a range of log entries to apply, which decide if the
+/// entries with a deadline before them expire.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Expiry {
+    /// The index, [`RaftEntry::expire_from()`] and [`RaftEntry::timestamp()`] of the first entry
+    /// of each leader, in index order.
+    leader_starts: Vec<(u64, Option<u64>, Option<u64>)>,
+}
+
+impl Expiry {
+    /// Read the first entries of the leaders at `indexes`, which are in ascending order.
+    pub(crate) async fn read<C, LR>(log_reader: &mut LR, indexes: &[u64]) -> Result<Self, StorageError<C>>
+    where
+        C: RaftTypeConfig,
+        LR: RaftLogReader<C>,
+    {
+        let mut leader_starts = Vec::with_capacity(indexes.len());
+
+        for index in indexes.iter().copied() {
+            let Some(entry) = log_reader.try_get_log_entry(index).await? else {
+                return Err(StorageError::read_log_at_index(
+                    index,
+                    C::err_from_string("log entry not found"),
+                ));
+            };
+            leader_starts.push((index, entry.expire_from(), entry.timestamp()));
+        }
+
+        Ok(Self { leader_starts })
+    }
+
+    /// Whether `entry` expires: it has a deadline, and the first entry of the next leader records
+    /// that it is not committed and is stamped later than the deadline. An expired entry is
+    /// applied as a blank entry.
+    ///
+    /// An entry followed by no other leader in the range is committed by its own leader.
+    pub(crate) fn expires<E>(&self, entry: &E) -> bool
+    where E: RaftEntry {
+        let Some(deadline) = entry.deadline() else {
+            return false;
+        };
+
+        let index = entry.index();
+        matches!(self.next_leader(index), Some((_, Some(from), Some(ts))) if index >= *from && *ts > deadline)
+    }
+
+    /// Whether the entry at `index` expires if it has a deadline that is passed, i.e., the next
+    /// leader records that it is not committed.
+    pub(crate) fn may_expire(&self, index: u64) -> bool {
+        matches!(self.next_leader(index), Some((_, Some(from), _)) if index >= *from)
+    }
+
+    /// The first entry of the leader after the entry at `index`.
+    fn next_leader(&self, index: u64) -> Option<&(u64, Option<u64>, Option<u64>)> {
+        self.leader_starts.iter().find(|(start, _, _)| *start > index)
+    }
+}
@@ -0,0 +1,249 @@
+//! Hash chain of log entries, see [`Config::audit_log_chain`](crate::Config::audit_log_chain).
+
+use std::fmt;
+
+use openraft_macros::since;
+#[cfg(feature = "audit-log-chain")]
+use sha2::Digest;
+#[cfg(feature = "audit-log-chain")]
+use sha2::Sha256;
+
+#[cfg(feature = "audit-log-chain")]
+use crate::RaftTypeConfig;
+#[cfg(feature = "audit-log-chain")]
+use crate::entry::RaftEntry;
+#[cfg(feature = "audit-log-chain")]
+use crate::entry::raft_entry_ext::RaftEntryExt;
+#[cfg(feature = "audit-log-chain")]
+use crate::errors::LogChainError;
+
+/// A SHA-256 hash that chains a log entry to the entry before it.
+///
+/// See [`Config::audit_log_chain`](crate::Config::audit_log_chain).
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ChainHash(pub [u8; 32]);
+
+#[cfg(feature = "audit-log-chain")]
+impl ChainHash {
+    /// Compute the SHA-256 hash of `bytes`.
+    pub fn digest(bytes: &[u8]) -> Self {
+        Self(Sha256::digest(bytes).into())
+    }
+}
+
+impl fmt::Display for ChainHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for b in &self.0 {
+            write!(f, "{:02x}", b)?;
+        }
+        Ok(())
+    }
+}
+
+/// Compute the chain hash of an entry, from the chain hash of the entry before it and the hash
+/// of the content of the entry, encoded with [`RaftEntry::encode_content()`].
+///
+/// `prev` is `None` for the first entry of a chain.
+#[since(version = "0.10.0")]
+#[cfg(feature = "audit-log-chain")]
+pub fn chain_hash(prev: Option<ChainHash>, content: ChainHash) -> ChainHash {
+    let mut h = Sha256::new();
+    h.update([prev.is_some() as u8]);
+    h.update(prev.unwrap_or_default().0);
+    h.update(content.0);
+    ChainHash(h.finalize().into())
+}
+
+/// Verify that each of `entries` carries the chain hash computed from the entry before it.
+///
+/// `prev` is the chain hash of the entry before the first one. If it is `None`, the chain hash
+/// of the first entry is trusted as it is, since what it is chained to is unknown.
+///
+/// Returns the chain hash of the last entry, or `prev` if `entries` is empty. It is meant for an
+/// auditor to verify a log exported from a node, or read with
+/// [`Raft::verify_log_chain()`](crate::Raft::verify_log_chain).
+#[since(version = "0.10.0")]
+#[cfg(feature = "audit-log-chain")]
+pub fn verify_log_chain<'a, C>(
+    prev: Option<ChainHash>,
+    entries: impl IntoIterator<Item = &'a C::Entry>,
+) -> Result<Option<ChainHash>, LogChainError<C>>
+where
+    C: RaftTypeConfig,
+    C::Entry: 'a,
+{
+    let mut prev = prev;
+
+    for entry in entries {
+        let expected = match (prev, entry.content_hash()) {
+            (Some(p), Some(content)) => Some(chain_hash(Some(p), content)),
+            _ => None,
+        };
+
+        let actual = entry.chain_hash();
+
+        let linked = match (expected, actual) {
+            (Some(e), Some(a)) => e == a,
+            // Nothing to verify the first entry against: trust it as the anchor of the chain.
+            (None, Some(_)) => prev.is_none() && entry.content_hash().is_some(),
+            (_, None) => false,
+        };
+
+        if !linked {
+            return Err(LogChainError::Broken {
+                log_id: entry.log_id(),
+                expected,
+                actual,
+            });
+        }
+
+        prev = actual;
+    }
+
+    Ok(prev)
+}
+
+/// Verify that `entry` follows the chain hash `prev`, and return the chain hash to verify the
+/// next entry with.
+///
+/// Unlike [`verify_log_chain()`], an entry that is not chained is accepted if no chained entry is
+/// before it, e.g., it is appended before [`Config::audit_log_chain`] is enabled; and an entry
+/// type that can not be chained restarts the chain.
+///
+/// [`Config::audit_log_chain`]: crate::Config::audit_log_chain
+#[cfg(feature = "audit-log-chain")]
+pub(crate) fn follow_log_chain<C>(
+    prev: Option<ChainHash>,
+    entry: &C::Entry,
+) -> Result<Option<ChainHash>, LogChainError<C>>
+where
+    C: RaftTypeConfig,
+{
+    match (entry.content_hash(), entry.chain_hash(), prev) {
+        (None, _, _) => Ok(None),
+        (Some(_), None, None) => Ok(None),
+        _ => verify_log_chain::<C>(prev, [entry]),
+    }
+}
+
+#[cfg(feature = "audit-log-chain")]
+#[cfg(test)]
+mod tests {
+    use super::ChainHash;
+    use super::chain_hash;
+    use crate::RaftEntry;
+    use crate::engine::testing::UTConfig;
+    use crate::engine::testing::log_id;
+    use crate::entry::raft_entry_ext::RaftEntryExt;
+    use crate::type_config::alias::EntryOf;
+
+    /// Chain `n` blank entries.
+    fn chained(prev: Option<ChainHash>, n: u64) -> Vec<EntryOf<UTConfig>> {
+        let mut prev = prev;
+        (0..n)
+            .map(|i| {
+                let mut ent = EntryOf::<UTConfig>::new_blank(log_id(1, 1, i));
+                let h = chain_hash(prev, ent.content_hash().unwrap());
+                ent.set_chain_hash(Some(h));
+                prev = Some(h);
+                ent
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_chain_hash() {
+        let (h0, h1, h2) = (ChainHash([0; 32]), ChainHash([1; 32]), ChainHash([2; 32]));
+
+        assert_ne!(chain_hash(None, h0), chain_hash(Some(h0), h0));
+        assert_ne!(chain_hash(Some(h1), h2), chain_hash(Some(h2), h1));
+        assert_eq!(ChainHash::digest(b"abc"), ChainHash::digest(b"abc"));
+        assert_ne!(ChainHash::digest(b"abc"), ChainHash::digest(b"abd"));
+    }
+
+    #[test]
+    fn test_content_hash() {
+        let ent = EntryOf::<UTConfig>::new_blank(log_id(1, 1, 1));
+
+        let mut chained = ent.clone();
+        chained.set_chain_hash(Some(ChainHash([1; 32])));
+        assert!(ent.content_hash().is_some());
+        assert_eq!(
+            ent.content_hash(),
+            chained.content_hash(),
+            "the chain hash is not hashed"
+        );
+
+        let other = EntryOf::<UTConfig>::new_blank(log_id(1, 1, 2));
+        assert_ne!(ent.content_hash(), other.content_hash());
+
+        let mut stamped = ent.clone();
+        stamped.timestamp = Some(1);
+        assert_ne!(ent.content_hash(), stamped.content_hash());
+    }
+
+    #[test]
+    fn test_verify_log_chain() {
+        use super::verify_log_chain;
+        use crate::errors::LogChainError;
+
+        let entries = chained(None, 5);
+        let last = entries[4].chain_hash();
+
+        assert_eq!(Ok(None), verify_log_chain::<UTConfig>(None, []));
+        assert_eq!(Ok(last), verify_log_chain::<UTConfig>(None, &entries));
+        assert_eq!(
+            Ok(last),
+            verify_log_chain::<UTConfig>(entries[1].chain_hash(), &entries[2..])
+        );
+
+        // The first entry does not follow the given previous hash.
+        let res = verify_log_chain::<UTConfig>(Some(ChainHash([0; 32])), &entries);
+        assert!(matches!(res, Err(LogChainError::Broken { .. })));
+
+        // An entry is replaced.
+        let mut tampered = entries.clone();
+        tampered[3] = EntryOf::<UTConfig>::new_normal(log_id(1, 1, 3), 3);
+        tampered[3].set_chain_hash(entries[3].chain_hash());
+
+        let res = verify_log_chain::<UTConfig>(None, &tampered);
+        assert_eq!(
+            Err(LogChainError::Broken {
+                log_id: log_id(1, 1, 3),
+                expected: Some(chain_hash(entries[2].chain_hash(), tampered[3].content_hash().unwrap())),
+                actual: entries[3].chain_hash(),
+            }),
+            res
+        );
+
+        // An entry is not chained.
+        let mut unchained = entries.clone();
+        unchained[2].set_chain_hash(None);
+
+        let res = verify_log_chain::<UTConfig>(None, &unchained);
+        assert!(matches!(res, Err(LogChainError::Broken { actual: None, .. })));
+    }
+
+    #[test]
+    fn test_follow_log_chain() {
+        use super::follow_log_chain;
+        use crate::errors::LogChainError;
+        let entries = chained(None, 3);
+
+        let mut prev = None;
+        for ent in &entries {
+            prev = follow_log_chain::<UTConfig>(prev, ent).unwrap();
+        }
+        assert_eq!(entries[2].chain_hash(), prev);
+
+        // Not chained, and nothing chained before it.
+        let unchained = EntryOf::<UTConfig>::new_blank(log_id(1, 1, 3));
+        assert_eq!(Ok(None), follow_log_chain::<UTConfig>(None, &unchained));
+
+        // Not chained after a chained entry.
+        let res = follow_log_chain::<UTConfig>(prev, &unchained);
+        assert!(matches!(res, Err(LogChainError::Broken { actual: None, .. })));
+    }
+}
@@ -9,12 +9,20 @@
 //! - [`EntryPayload`] - Payload types: application data, membership config, or blank
 //! - [`RaftEntry`] - Trait that log entries must implement
 //! - [`RaftPayload`] - Trait for entry payload types
+//! - [`ApplyScope`] - Restricts which nodes apply an entry
+//! - [`EncodeContent`] - Canonical encoding of the content of an entry, to chain it with a hash
 //!
 //! ## Overview
 //!
 //! Each log entry contains:
 //! - **Log ID**: `(term, node_id, index)` uniquely identifying the entry
 //! - **Payload**: Either application data, membership change, or blank (noop)
+//! - **Timestamp** (optional): The leader's wall-clock time when the entry is proposed, see
+//!   [`Config::entry_timestamp`](crate::Config::entry_timestamp)
+//! - **Deadline** (optional): The time by which the entry must be committed, or it may be applied
+//!   as a blank entry, see [`RaftEntry::deadline`]
+//! - **Chain hash** (optional): A hash chaining the entry to the one before it, see
+//!   [`Config::audit_log_chain`](crate::Config::audit_log_chain)
 //!
 //! ## Entry Types
 //!
@@ -28,14 +36,27 @@
 #[cfg(doc)]
 use crate::RaftTypeConfig;
 
+mod apply_scope;
+mod encode_content;
 #[allow(clippy::module_inception)]
 mod entry;
+mod entry_kind;
+pub(crate) mod expiry;
+pub(crate) mod log_chain;
 pub mod payload;
 mod raft_entry;
 pub(crate) mod raft_entry_ext;
 mod raft_payload;
 
+pub use apply_scope::ApplyScope;
+pub use encode_content::EncodeContent;
 pub use entry::Entry;
+pub use entry_kind::EntryKind;
+pub use log_chain::ChainHash;
+#[cfg(feature = "audit-log-chain")]
+pub use log_chain::chain_hash;
+#[cfg(feature = "audit-log-chain")]
+pub use log_chain::verify_log_chain;
 pub use payload::EntryPayload;
 pub use raft_entry::RaftEntry;
 pub use raft_payload::RaftPayload;
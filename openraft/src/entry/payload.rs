@@ -7,6 +7,8 @@ use openraft_macros::since;
 
 use crate::AppData;
 use crate::Membership;
+use crate::entry::EncodeContent;
+use crate::entry::EntryKind;
 use crate::node::Node;
 use crate::node::NodeId;
 
@@ -86,6 +88,27 @@ where
     }
 }
 
+impl<D, NID, N> EncodeContent for EntryPayload<D, NID, N>
+where
+    D: AppData + EncodeContent,
+    NID: NodeId + EncodeContent,
+    N: Node + EncodeContent,
+{
+    fn encode_content(&self, buf: &mut Vec<u8>) {
+        match self {
+            EntryPayload::Blank => buf.push(0),
+            EntryPayload::Normal(app_data) => {
+                buf.push(1);
+                app_data.encode_content(buf);
+            }
+            EntryPayload::Membership(m) => {
+                buf.push(2);
+                m.encode_content(buf);
+            }
+        }
+    }
+}
+
 impl<D, NID, N> EntryPayload<D, NID, N>
 where
     D: AppData,
@@ -114,6 +137,14 @@ where
             None
         }
     }
+
+    fn kind(&self) -> EntryKind {
+        match self {
+            EntryPayload::Blank => EntryKind::Blank,
+            EntryPayload::Normal(_) => EntryKind::Normal,
+            EntryPayload::Membership(_) => EntryKind::Membership,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -136,7 +167,7 @@ mod tests {
         ));
         assert_eq!(
             format!("{:?}", membership),
-            "membership:Membership { configs: [{1, 2}], nodes: {1: (), 2: ()} }"
+            "membership:Membership { configs: [{1, 2}], nodes: {1: (), 2: ()}, witnesses: {}, log_only: {}, failure_domains: {} }"
         );
     }
 
@@ -8,6 +8,8 @@ use crate::EntryPayload;
 use crate::Membership;
 use crate::base::OptionalFeatures;
 use crate::base::finalized::Final;
+use crate::entry::ApplyScope;
+use crate::entry::ChainHash;
 use crate::entry::RaftPayload;
 use crate::log_id::LogId;
 use crate::node::Node;
@@ -61,6 +63,142 @@ where
     #[since(version = "0.10.0", change = "use owned argument log id")]
     fn set_log_id(&mut self, new: LogId<Self::CommittedLeaderId>);
 
+    /// Returns the nodes that apply this entry, `None` if every node applies it.
+    ///
+    /// The default implementation does not store an [`ApplyScope`] and returns `None`.
+    #[since(version = "0.10.0")]
+    fn apply_scope(&self) -> Option<&ApplyScope<Self::NodeId>> {
+        None
+    }
+
+    /// Set the nodes that apply this entry.
+    ///
+    /// Returns `false` if this entry type does not store an [`ApplyScope`], which is the default
+    /// implementation: then a write with a scope, such as
+    /// [`Raft::client_write_canary()`](crate::Raft::client_write_canary), is refused.
+    #[since(version = "0.10.0")]
+    fn set_apply_scope(&mut self, scope: Option<ApplyScope<Self::NodeId>>) -> bool {
+        let _ = scope;
+        false
+    }
+
+    /// Returns the leader's wall-clock time when this entry is proposed, in milliseconds since the
+    /// UNIX epoch, or `None` if the entry is not stamped.
+    ///
+    /// The default implementation does not store a timestamp and returns `None`.
+    /// See [`Config::entry_timestamp`](crate::Config::entry_timestamp).
+    #[since(version = "0.10.0")]
+    fn timestamp(&self) -> Option<u64> {
+        None
+    }
+
+    /// Set the propose-time timestamp of this entry.
+    ///
+    /// Returns `false` if this entry type does not store a timestamp, which is the default
+    /// implementation: then the entry is appended without a timestamp.
+    #[since(version = "0.10.0")]
+    fn set_timestamp(&mut self, timestamp: Option<u64>) -> bool {
+        let _ = timestamp;
+        false
+    }
+
+    /// Returns the leader's wall-clock time by which this entry must be committed, in milliseconds
+    /// since the UNIX epoch, or `None` if the entry has no deadline.
+    ///
+    /// An entry committed by the leader that proposed it is applied as usual. Otherwise it expires
+    /// if that leader re-establishes itself with a first entry that records it is not committed,
+    /// see [`RaftEntry::expire_from()`], and that is stamped later than the deadline, see
+    /// [`RaftEntry::timestamp()`]: then every node applies it as a blank entry. The default
+    /// implementation does not store a deadline and returns `None`.
+    /// See [`WriteRequest::deadline()`](crate::raft::WriteRequest::deadline).
+    #[since(version = "0.10.0")]
+    fn deadline(&self) -> Option<u64> {
+        None
+    }
+
+    /// Set the deadline of this entry.
+    ///
+    /// Returns `false` if this entry type does not store a deadline, which is the default
+    /// implementation: then the entry is appended without a deadline and never expires.
+    #[since(version = "0.10.0")]
+    fn set_deadline(&mut self, deadline: Option<u64>) -> bool {
+        let _ = deadline;
+        false
+    }
+
+    /// Returns the index of the first entry not committed in the previous term of the same leader,
+    /// on the first entry of a leader that re-establishes itself, or `None` if it is not recorded.
+    ///
+    /// The entries of that term from this index on that have a deadline earlier than the timestamp
+    /// of this entry expire, see [`RaftEntry::deadline()`]. The default implementation does not
+    /// store it and returns `None`.
+    #[since(version = "0.10.0")]
+    fn expire_from(&self) -> Option<u64> {
+        None
+    }
+
+    /// Set the index of the first entry not committed in the previous term of the same leader.
+    ///
+    /// Returns `false` if this entry type does not store it, which is the default implementation:
+    /// then no entry expires.
+    #[since(version = "0.10.0")]
+    fn set_expire_from(&mut self, index: Option<u64>) -> bool {
+        let _ = index;
+        false
+    }
+
+    /// Returns the hash that chains this entry to the entry before it, or `None` if the entry is
+    /// not chained.
+    ///
+    /// The default implementation does not store a chain hash and returns `None`.
+    /// See [`Config::audit_log_chain`](crate::Config::audit_log_chain).
+    #[since(version = "0.10.0")]
+    fn chain_hash(&self) -> Option<ChainHash> {
+        None
+    }
+
+    /// Set the hash that chains this entry to the entry before it.
+    ///
+    /// Returns `false` if this entry type does not store a chain hash, which is the default
+    /// implementation: then the entry is appended without it.
+    #[since(version = "0.10.0")]
+    fn set_chain_hash(&mut self, hash: Option<ChainHash>) -> bool {
+        let _ = hash;
+        false
+    }
+
+    /// Append a canonical encoding of everything in this entry except the chain hash to `buf`.
+    ///
+    /// Returns `false` if this entry type can not be chained, which is the default
+    /// implementation. An entry type that can be chained must encode the same entry into the same
+    /// bytes on every node and by every version, e.g., with [`EncodeContent`]: a follower
+    /// recomputes the SHA-256 hash of the encoding to verify the chain hash it receives, and stops
+    /// with a [`StorageError`] if it differs.
+    ///
+    /// It is called only with the `audit-log-chain` feature enabled.
+    /// See [`Config::audit_log_chain`](crate::Config::audit_log_chain).
+    ///
+    /// [`EncodeContent`]: crate::entry::EncodeContent
+    /// [`StorageError`]: crate::StorageError
+    #[since(version = "0.10.0")]
+    fn encode_content(&self, buf: &mut Vec<u8>) -> bool {
+        let _ = buf;
+        false
+    }
+
+    /// Returns the approximate size in bytes of this entry when it is sent over the network.
+    ///
+    /// It is used to limit the size of an `AppendEntries` request to
+    /// [`Config::max_payload_bytes`](crate::Config::max_payload_bytes).
+    ///
+    /// The default implementation returns the in-memory size of the entry type, which does not
+    /// count the heap data the payload owns. Implement it, e.g., with the encoded length of the
+    /// entry, for `max_payload_bytes` to bound the request size.
+    #[since(version = "0.10.0")]
+    fn approx_size(&self) -> u64 {
+        size_of_val(self) as u64
+    }
+
     /// Create a new blank log entry.
     #[since(version = "0.10.0", change = "become a default method")]
     fn new_blank(log_id: LogId<Self::CommittedLeaderId>) -> Self
@@ -1,3 +1,6 @@
+use crate::base::finalized::Final;
+#[cfg(feature = "audit-log-chain")]
+use crate::entry::ChainHash;
 use crate::entry::RaftEntry;
 use crate::log_id::ref_log_id::RefLogId;
 
@@ -7,6 +10,24 @@ pub(crate) trait RaftEntryExt: RaftEntry {
         let (leader_id, index) = self.log_id_parts();
         RefLogId::new(leader_id, index)
     }
+
+    /// Returns the entry to apply on node `id`: this entry, or a blank entry with the same log id
+    /// if `id` is out of its [`ApplyScope`](crate::entry::ApplyScope).
+    fn scoped_for(self, id: &Self::NodeId) -> Self
+    where Self: Final + Sized {
+        match self.apply_scope() {
+            Some(scope) if !scope.includes(id) => Self::new_blank(self.log_id()),
+            _ => self,
+        }
+    }
+
+    /// Returns the SHA-256 hash of [`RaftEntry::encode_content()`], or `None` if this entry can
+    /// not be chained.
+    #[cfg(feature = "audit-log-chain")]
+    fn content_hash(&self) -> Option<ChainHash> {
+        let mut buf = Vec::new();
+        self.encode_content(&mut buf).then(|| ChainHash::digest(&buf))
+    }
 }
 
 impl<T> RaftEntryExt for T where T: RaftEntry {}
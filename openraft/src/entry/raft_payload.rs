@@ -1,6 +1,7 @@
 use openraft_macros::since;
 
 use crate::Membership;
+use crate::entry::EntryKind;
 use crate::node::Node;
 use crate::node::NodeId;
 
@@ -16,4 +17,17 @@ where
 {
     /// Return `Some(Membership)` if the entry payload contains a membership payload.
     fn get_membership(&self) -> Option<Membership<NID, N>>;
+
+    /// Return the kind of the payload.
+    ///
+    /// The default implementation tells a membership payload from the others, which are all
+    /// reported as [`EntryKind::Normal`]. Implement it to report [`EntryKind::Blank`] too.
+    #[since(version = "0.10.0")]
+    fn kind(&self) -> EntryKind {
+        if self.get_membership().is_some() {
+            EntryKind::Membership
+        } else {
+            EntryKind::Normal
+        }
+    }
 }
@@ -0,0 +1,14 @@
+use openraft_macros::since;
+
+/// Error indicating a write with an [`ApplyScope`](crate::entry::ApplyScope) is rejected because
+/// the log entry type does not store it.
+///
+/// The default [`Entry`](crate::Entry) type stores it. A custom
+/// [`RaftEntry`](crate::entry::RaftEntry) has to implement
+/// [`set_apply_scope()`](crate::entry::RaftEntry::set_apply_scope) and
+/// [`apply_scope()`](crate::entry::RaftEntry::apply_scope) to support it.
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[error("the log entry type does not support ApplyScope")]
+pub struct ApplyScopeUnsupported {}
@@ -0,0 +1,201 @@
+use openraft_macros::since;
+
+/// A stable, machine-readable classification of an error.
+///
+/// An RPC gateway maps Openraft errors to transport status codes with it, without matching on the
+/// `Display` output, which is meant for humans and may change between versions.
+///
+/// The numeric [`code()`](Self::code) and the string [`code_name()`](Self::code_name) of a variant
+/// never change once assigned, and are unique across all error types:
+///
+/// | code | name                     | error                                      | retryable |
+/// |------|--------------------------|--------------------------------------------|-----------|
+/// | 1001 | `FATAL_STORAGE`          | [`Fatal::StorageError`]                    | no        |
+/// | 1002 | `FATAL_PANICKED`         | [`Fatal::Panicked`]                        | no        |
+/// | 1003 | `FATAL_STOPPED`          | [`Fatal::Stopped`]                         | no        |
+/// | 2001 | `FORWARD_TO_LEADER`      | [`ForwardToLeader`]                        | yes       |
+/// | 3001 | `MEMBERSHIP_IN_PROGRESS` | [`ChangeMembershipError::InProgress`]      | yes       |
+/// | 3002 | `MEMBERSHIP_EMPTY`       | [`ChangeMembershipError::EmptyMembership`] | no        |
+/// | 3003 | `LEARNER_NOT_FOUND`      | [`ChangeMembershipError::LearnerNotFound`] | no        |
+/// | 3004 | `MEMBERSHIP_DUPLICATE_NODE` | [`ChangeMembershipError::DuplicateNode`] | no        |
+/// | 3005 | `MEMBERSHIP_INVALID_FAILURE_DOMAIN` | [`ChangeMembershipError::FailureDomain`] | no |
+/// | 3006 | `MEMBERSHIP_INVALID_QUORUM` | [`ChangeMembershipError::QuorumPolicy`] | no |
+/// | 3007 | `LOG_ONLY_LOGS_PURGED` | [`ChangeMembershipError::LogOnlyLogsPurged`] | no |
+/// | 3008 | `LEARNER_NOT_CAUGHT_UP` | [`ChangeMembershipError::LearnerNotCaughtUp`] | yes |
+/// | 4001 | `WRITE_EXPIRED`          | [`WriteExpired`]                           | yes       |
+/// | 4002 | `STORAGE_FULL`           | [`StorageFull`]                            | yes       |
+/// | 4003 | `APPLY_SCOPE_UNSUPPORTED`| [`ApplyScopeUnsupported`]                  | no        |
+/// | 4004 | `RESERVED_INDEX_MISMATCH`| [`ReservedIndexMismatch`]                  | no        |
+/// | 4005 | `STALE_READ`             | [`StaleRead`]                              | yes       |
+///
+/// Wrapper errors such as [`ClientWriteError`], [`WriteError`] and [`RaftError`] report the code
+/// of the error they wrap.
+///
+/// [`Fatal::StorageError`]: crate::errors::Fatal::StorageError
+/// [`Fatal::Panicked`]: crate::errors::Fatal::Panicked
+/// [`Fatal::Stopped`]: crate::errors::Fatal::Stopped
+/// [`ForwardToLeader`]: crate::errors::ForwardToLeader
+/// [`ChangeMembershipError::InProgress`]: crate::errors::ChangeMembershipError::InProgress
+/// [`ChangeMembershipError::EmptyMembership`]: crate::errors::ChangeMembershipError::EmptyMembership
+/// [`ChangeMembershipError::LearnerNotFound`]: crate::errors::ChangeMembershipError::LearnerNotFound
+/// [`ChangeMembershipError::DuplicateNode`]: crate::errors::ChangeMembershipError::DuplicateNode
+/// [`ChangeMembershipError::FailureDomain`]: crate::errors::ChangeMembershipError::FailureDomain
+/// [`ChangeMembershipError::QuorumPolicy`]: crate::errors::ChangeMembershipError::QuorumPolicy
+/// [`ChangeMembershipError::LogOnlyLogsPurged`]: crate::errors::ChangeMembershipError::LogOnlyLogsPurged
+/// [`ChangeMembershipError::LearnerNotCaughtUp`]: crate::errors::ChangeMembershipError::LearnerNotCaughtUp
+/// [`WriteExpired`]: crate::errors::WriteExpired
+/// [`StorageFull`]: crate::errors::StorageFull
+/// [`ApplyScopeUnsupported`]: crate::errors::ApplyScopeUnsupported
+/// [`ReservedIndexMismatch`]: crate::errors::ReservedIndexMismatch
+/// [`StaleRead`]: crate::errors::StaleRead
+/// [`ClientWriteError`]: crate::errors::ClientWriteError
+/// [`WriteError`]: crate::errors::WriteError
+/// [`RaftError`]: crate::errors::RaftError
+#[since(version = "0.10.0")]
+pub trait ErrorCode {
+    /// The stable numeric code of this error.
+    fn code(&self) -> u32;
+
+    /// The stable string code of this error, in `UPPER_SNAKE_CASE`.
+    fn code_name(&self) -> &'static str;
+
+    /// Whether the same request may succeed if it is retried, possibly on another node.
+    ///
+    /// For example, a write rejected by a follower can be retried on the leader, while a write to
+    /// a stopped node will never succeed on that node.
+    fn retryable(&self) -> bool;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use crate::StorageError;
+    use crate::engine::testing::UTConfig;
+    use crate::errors::ApplyScopeUnsupported;
+    use crate::errors::ChangeMembershipError;
+    use crate::errors::ClientWriteError;
+    use crate::errors::DuplicateNode;
+    use crate::errors::EmptyMembership;
+    use crate::errors::ErrorCode;
+    use crate::errors::FailureDomainError;
+    use crate::errors::Fatal;
+    use crate::errors::ForwardToLeader;
+    use crate::errors::InProgress;
+    use crate::errors::LearnerNotCaughtUp;
+    use crate::errors::LearnerNotFound;
+    use crate::errors::LogOnlyLogsPurged;
+    use crate::errors::QuorumPolicyError;
+    use crate::errors::RaftError;
+    use crate::errors::ReservedIndexMismatch;
+    use crate::errors::StaleRead;
+    use crate::errors::StorageFull;
+    use crate::errors::WriteExpired;
+    use crate::testing::log_id;
+    use crate::type_config::TypeConfigExt;
+    use crate::type_config::alias::CommittedLeaderIdOf;
+
+    type C = UTConfig;
+
+    fn all() -> Vec<(u32, &'static str, bool)> {
+        let fatal: Vec<Fatal<C>> = vec![
+            Fatal::StorageError(StorageError::read(C::err_from_string("x"))),
+            Fatal::Panicked,
+            Fatal::Stopped,
+        ];
+
+        let cm: Vec<ChangeMembershipError<CommittedLeaderIdOf<C>, u64>> = vec![
+            ChangeMembershipError::InProgress(InProgress {
+                committed: None,
+                membership_log_id: Some(log_id::<C>(1, 1, 1)),
+            }),
+            ChangeMembershipError::EmptyMembership(EmptyMembership {}),
+            ChangeMembershipError::LearnerNotFound(LearnerNotFound { node_id: 1 }),
+            ChangeMembershipError::DuplicateNode(DuplicateNode { node_id: 2, other: 1 }),
+            ChangeMembershipError::FailureDomain(FailureDomainError::Missing { node_id: 1 }),
+            ChangeMembershipError::QuorumPolicy(QuorumPolicyError::TooManyVoters { voters: 17, max: 16 }),
+            ChangeMembershipError::LogOnlyLogsPurged(LogOnlyLogsPurged {
+                node_id: 3,
+                last_purged_index: 5,
+            }),
+            ChangeMembershipError::LearnerNotCaughtUp(LearnerNotCaughtUp { node_id: 3 }),
+        ];
+
+        let mut res = vec![];
+        for e in fatal {
+            res.push((e.code(), e.code_name(), e.retryable()));
+        }
+        let e = ForwardToLeader::<C>::empty();
+        res.push((e.code(), e.code_name(), e.retryable()));
+        for e in cm {
+            res.push((e.code(), e.code_name(), e.retryable()));
+        }
+        let e = WriteExpired::<C> {
+            log_id: log_id::<C>(1, 1, 1),
+        };
+        res.push((e.code(), e.code_name(), e.retryable()));
+        let e = StorageFull { usage: 2, quota: 1 };
+        res.push((e.code(), e.code_name(), e.retryable()));
+        let e = ApplyScopeUnsupported {};
+        res.push((e.code(), e.code_name(), e.retryable()));
+        let e = ReservedIndexMismatch {
+            index: 1,
+            count: 1,
+            reserved: None,
+        };
+        res.push((e.code(), e.code_name(), e.retryable()));
+        let e = StaleRead::<C> {
+            applied: None,
+            min_applied_index: Some(1),
+            since_leader_contact: None,
+            max_staleness: None,
+            witness: false,
+        };
+        res.push((e.code(), e.code_name(), e.retryable()));
+        res
+    }
+
+    #[test]
+    fn test_error_code_stable() {
+        assert_eq!(
+            vec![
+                (1001, "FATAL_STORAGE", false),
+                (1002, "FATAL_PANICKED", false),
+                (1003, "FATAL_STOPPED", false),
+                (2001, "FORWARD_TO_LEADER", true),
+                (3001, "MEMBERSHIP_IN_PROGRESS", true),
+                (3002, "MEMBERSHIP_EMPTY", false),
+                (3003, "LEARNER_NOT_FOUND", false),
+                (3004, "MEMBERSHIP_DUPLICATE_NODE", false),
+                (3005, "MEMBERSHIP_INVALID_FAILURE_DOMAIN", false),
+                (3006, "MEMBERSHIP_INVALID_QUORUM", false),
+                (3007, "LOG_ONLY_LOGS_PURGED", false),
+                (3008, "LEARNER_NOT_CAUGHT_UP", true),
+                (4001, "WRITE_EXPIRED", true),
+                (4002, "STORAGE_FULL", true),
+                (4003, "APPLY_SCOPE_UNSUPPORTED", false),
+                (4004, "RESERVED_INDEX_MISMATCH", false),
+                (4005, "STALE_READ", true),
+            ],
+            all()
+        );
+
+        let codes = all().iter().map(|x| x.0).collect::<BTreeSet<_>>();
+        let names = all().iter().map(|x| x.1).collect::<BTreeSet<_>>();
+        assert_eq!(all().len(), codes.len(), "codes are unique");
+        assert_eq!(all().len(), names.len(), "names are unique");
+    }
+
+    #[test]
+    fn test_error_code_wrapper() {
+        let e: RaftError<C, ClientWriteError<C>> =
+            RaftError::APIError(ClientWriteError::ForwardToLeader(ForwardToLeader::empty()));
+        assert_eq!(
+            (2001, "FORWARD_TO_LEADER", true),
+            (e.code(), e.code_name(), e.retryable())
+        );
+
+        let e: RaftError<C, ClientWriteError<C>> = RaftError::Fatal(Fatal::Stopped);
+        assert_eq!((1003, "FATAL_STOPPED", false), (e.code(), e.code_name(), e.retryable()));
+    }
+}
@@ -0,0 +1,16 @@
+use openraft_macros::since;
+
+/// Error indicating an event subscriber falls behind and misses events.
+///
+/// A [`Raft`](crate::Raft) keeps only the most recent events for
+/// [`Raft::subscribe_events()`](crate::Raft::subscribe_events). A subscriber that does not consume
+/// them in time misses the older ones; it then continues with the oldest event kept. It should
+/// re-read the current state, e.g., from [`RaftMetrics`](crate::RaftMetrics), to catch up.
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[error("event subscriber lagged behind and missed {missed} events")]
+pub struct EventsLagged {
+    /// The number of missed events.
+    pub missed: u64,
+}
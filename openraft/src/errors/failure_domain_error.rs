@@ -0,0 +1,39 @@
+use openraft_macros::since;
+
+use crate::node::NodeId;
+
+/// Error indicating the failure domains of a domain-aware membership are not a sane topology.
+///
+/// A domain-aware membership is built with [`Membership::new_domain_aware()`]: a quorum of it is
+/// a majority of the voters in a majority of the failure domains.
+///
+/// [`Membership::new_domain_aware()`]: crate::Membership::new_domain_aware
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub enum FailureDomainError<NID>
+where NID: NodeId
+{
+    /// A voter has no failure domain, or an empty one.
+    #[error("voter {node_id} has no failure domain")]
+    Missing {
+        /// The voter without a failure domain.
+        node_id: NID,
+    },
+
+    /// The voters of a config span too few failure domains to survive losing one of them.
+    #[error("voters span {domains} failure domains, at least {min} are required")]
+    TooFewDomains {
+        /// The number of failure domains the voters span.
+        domains: usize,
+        /// The minimum number of failure domains.
+        min: usize,
+    },
+
+    /// Failure domains are set on a membership whose quorum is a majority of the voters.
+    ///
+    /// Switching the quorum rule in a single membership change may let two leaders be elected,
+    /// thus a cluster has to be initialized as domain-aware.
+    #[error("membership is not domain-aware, failure domains can not be set")]
+    NotDomainAware,
+}
@@ -1,5 +1,6 @@
 use crate::RaftTypeConfig;
 use crate::StorageError;
+use crate::errors::ErrorCode;
 
 /// Unrecoverable error that causes Raft to shut down.
 ///
@@ -30,3 +31,28 @@ where C: RaftTypeConfig
     #[error("raft stopped")]
     Stopped,
 }
+
+impl<C> ErrorCode for Fatal<C>
+where C: RaftTypeConfig
+{
+    fn code(&self) -> u32 {
+        match self {
+            Fatal::StorageError(_) => 1001,
+            Fatal::Panicked => 1002,
+            Fatal::Stopped => 1003,
+        }
+    }
+
+    fn code_name(&self) -> &'static str {
+        match self {
+            Fatal::StorageError(_) => "FATAL_STORAGE",
+            Fatal::Panicked => "FATAL_PANICKED",
+            Fatal::Stopped => "FATAL_STOPPED",
+        }
+    }
+
+    /// A fatal error is never retryable on the same node: the node has stopped.
+    fn retryable(&self) -> bool {
+        false
+    }
+}
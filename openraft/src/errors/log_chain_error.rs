@@ -0,0 +1,37 @@
+use display_more::DisplayOptionExt;
+use openraft_macros::since;
+
+use crate::RaftTypeConfig;
+use crate::StorageError;
+use crate::entry::ChainHash;
+use crate::type_config::alias::LogIdOf;
+
+/// Error returned when verifying the hash chain of log entries, see
+/// [`Config::audit_log_chain`](crate::Config::audit_log_chain).
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub enum LogChainError<C>
+where C: RaftTypeConfig
+{
+    /// An entry does not carry the chain hash computed from the entry before it: the entry, or
+    /// one before it, is modified, removed or reordered, or it is not chained at all.
+    #[error("log chain is broken at {log_id}: expect hash: {}, got: {}", expected.display(), actual.display())]
+    Broken {
+        /// The log id of the first entry that does not follow the chain.
+        log_id: LogIdOf<C>,
+
+        /// The chain hash computed for the entry, `None` if it can not be computed, e.g., the
+        /// entry type does not implement [`RaftEntry::encode_content()`].
+        ///
+        /// [`RaftEntry::encode_content()`]: crate::entry::RaftEntry::encode_content
+        expected: Option<ChainHash>,
+
+        /// The chain hash the entry carries, `None` if it is not chained.
+        actual: Option<ChainHash>,
+    },
+
+    /// Failed to read the log entries to verify.
+    #[error(transparent)]
+    StorageError(#[from] StorageError<C>),
+}
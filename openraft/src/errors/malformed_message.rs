@@ -0,0 +1,63 @@
+use display_more::DisplayOptionExt;
+use openraft_macros::since;
+
+use crate::RaftTypeConfig;
+use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::VoteOf;
+
+/// Error indicating an incoming RPC message breaks an invariant every well-formed message holds.
+///
+/// Such a message is sent by a buggy or corrupted peer, or damaged in transit. It is rejected
+/// before any of it is applied to the local state.
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub enum MalformedMessage<C>
+where C: RaftTypeConfig
+{
+    /// A message that must be sent by a leader carries a vote that is not committed.
+    #[error("the vote of a leader must be committed: {vote}")]
+    UncommittedLeaderVote {
+        /// The vote in the message.
+        vote: VoteOf<C>,
+    },
+
+    /// A log id in the message is proposed by a leader greater than the vote in the message.
+    #[error("log id {log_id} is proposed by a leader greater than the vote {vote}")]
+    LogNewerThanVote {
+        /// The log id.
+        log_id: LogIdOf<C>,
+        /// The vote in the message.
+        vote: VoteOf<C>,
+    },
+
+    /// The log entries do not follow `prev_log_id` or each other.
+    #[error("log id {next} does not follow {}", prev.display())]
+    NonConsecutiveEntries {
+        /// The log id of the entry before `next`, or `prev_log_id`.
+        prev: Option<LogIdOf<C>>,
+        /// The log id that does not follow `prev`.
+        next: LogIdOf<C>,
+    },
+
+    /// A chained log entry does not carry the chain hash computed from the entry before it in
+    /// the message. See [`Config::audit_log_chain`](crate::Config::audit_log_chain).
+    #[error("log id {log_id} does not follow the log chain")]
+    BrokenLogChain {
+        /// The log id of the entry that does not follow the chain.
+        log_id: LogIdOf<C>,
+    },
+
+    /// A snapshot to install has no last log id.
+    #[error("snapshot has no last log id")]
+    SnapshotWithoutLastLogId,
+
+    /// The membership config of a snapshot is after the last log id of the snapshot.
+    #[error("snapshot membership log id {} is after its last log id {}", membership_log_id.display(), last_log_id.display())]
+    MembershipAfterSnapshot {
+        /// The log id of the membership config in the snapshot.
+        membership_log_id: Option<LogIdOf<C>>,
+        /// The last log id of the snapshot.
+        last_log_id: Option<LogIdOf<C>>,
+    },
+}
@@ -2,6 +2,7 @@ use openraft_macros::since;
 
 use crate::errors::ChangeMembershipError;
 use crate::errors::EmptyMembership;
+use crate::errors::FailureDomainError;
 use crate::errors::LearnerNotFound;
 use crate::errors::NodeNotFound;
 use crate::node::NodeId;
@@ -23,6 +24,11 @@ where NID: NodeId
     /// A required node was not found.
     #[error(transparent)]
     NodeNotFound(#[from] NodeNotFound<NID>),
+
+    /// The failure domains of a domain-aware membership are not a sane topology.
+    #[since(version = "0.10.0")]
+    #[error(transparent)]
+    FailureDomain(#[from] FailureDomainError<NID>),
 }
 
 impl<CLID, NID> From<MembershipError<NID>> for ChangeMembershipError<CLID, NID>
@@ -36,6 +42,7 @@ where
             MembershipError::NodeNotFound(e) => {
                 ChangeMembershipError::LearnerNotFound(LearnerNotFound { node_id: e.node_id })
             }
+            MembershipError::FailureDomain(e) => ChangeMembershipError::FailureDomain(e),
         }
     }
 }
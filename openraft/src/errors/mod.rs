@@ -1,26 +1,39 @@
 //! Error types exposed by this crate.
 
 mod allow_next_revert_error;
+mod apply_scope_unsupported;
 mod conflicting_log_id;
 pub mod decompose;
+mod error_code;
 mod error_source;
+mod events_lagged;
+mod failure_domain_error;
 mod fatal;
 pub(crate) mod higher_vote;
 pub mod into_ok;
 pub(crate) mod into_raft_result;
 mod leader_changed;
 mod linearizable_read_error;
+mod log_chain_error;
+mod malformed_message;
 mod membership_error;
 mod node_not_found;
 mod operation;
+mod placement_error;
+mod quorum_policy_error;
 mod raft_error;
 mod reject_append_entries;
 mod reject_vote;
 mod replication_closed;
 pub(crate) mod replication_error;
+mod reserved_index_mismatch;
+mod seed_snapshot_error;
+mod stale_read;
 pub(crate) mod storage_error;
+mod storage_full;
 mod storage_io_result;
 mod streaming_error;
+mod write_expired;
 
 use std::collections::BTreeSet;
 use std::error::Error;
@@ -29,23 +42,36 @@ use std::time::Duration;
 use openraft_macros::since;
 
 pub use self::allow_next_revert_error::AllowNextRevertError;
+pub use self::apply_scope_unsupported::ApplyScopeUnsupported;
 pub use self::conflicting_log_id::ConflictingLogId;
+pub use self::error_code::ErrorCode;
 pub use self::error_source::BacktraceDisplay;
 pub use self::error_source::ErrorSource;
+pub use self::events_lagged::EventsLagged;
+pub use self::failure_domain_error::FailureDomainError;
 pub use self::fatal::Fatal;
 pub(crate) use self::higher_vote::HigherVote;
 pub use self::leader_changed::LeaderChanged;
 pub use self::linearizable_read_error::LinearizableReadError;
+pub use self::log_chain_error::LogChainError;
+pub use self::malformed_message::MalformedMessage;
 pub use self::membership_error::MembershipError;
 pub use self::node_not_found::NodeNotFound;
 pub use self::operation::Operation;
+pub use self::placement_error::PlacementError;
+pub use self::quorum_policy_error::QuorumPolicyError;
 pub use self::raft_error::RaftError;
 pub(crate) use self::reject_append_entries::RejectAppendEntries;
 pub use self::reject_vote::RejectVote;
 pub use self::replication_closed::ReplicationClosed;
 pub(crate) use self::replication_error::ReplicationError;
+pub use self::reserved_index_mismatch::ReservedIndexMismatch;
+pub use self::seed_snapshot_error::SeedSnapshotError;
+pub use self::stale_read::StaleRead;
+pub use self::storage_full::StorageFull;
 pub(crate) use self::storage_io_result::StorageIOResult;
 pub use self::streaming_error::StreamingError;
+pub use self::write_expired::WriteExpired;
 use crate::LogId;
 use crate::Membership;
 use crate::RaftTypeConfig;
@@ -87,6 +113,24 @@ where C: RaftTypeConfig
     /// When writing a change-membership entry.
     #[error(transparent)]
     ChangeMembershipError(#[from] ChangeMembershipError<CommittedLeaderIdOf<C>, C::NodeId>),
+
+    /// The write is not committed before the deadline set with
+    /// [`WriteRequest::deadline()`](crate::raft::WriteRequest::deadline), and takes no effect.
+    #[error(transparent)]
+    WriteExpired(#[from] WriteExpired<C>),
+
+    /// The storage usage exceeds [`Config::storage_quota`](crate::Config::storage_quota).
+    #[error(transparent)]
+    StorageFull(#[from] StorageFull),
+
+    /// The write has an [`ApplyScope`](crate::entry::ApplyScope) but the log entry type does not
+    /// store it.
+    #[error(transparent)]
+    ApplyScopeUnsupported(#[from] ApplyScopeUnsupported),
+
+    /// A write to reserved log indexes does not continue the reservation.
+    #[error(transparent)]
+    ReservedIndexMismatch(#[from] ReservedIndexMismatch),
 }
 
 impl<C> TryAsRef<ForwardToLeader<C>> for ClientWriteError<C>
@@ -100,6 +144,89 @@ where C: RaftTypeConfig
     }
 }
 
+impl<C> ErrorCode for ClientWriteError<C>
+where C: RaftTypeConfig
+{
+    fn code(&self) -> u32 {
+        match self {
+            Self::ForwardToLeader(e) => e.code(),
+            Self::ChangeMembershipError(e) => e.code(),
+            Self::WriteExpired(e) => e.code(),
+            Self::StorageFull(e) => e.code(),
+            Self::ApplyScopeUnsupported(e) => e.code(),
+            Self::ReservedIndexMismatch(e) => e.code(),
+        }
+    }
+
+    fn code_name(&self) -> &'static str {
+        match self {
+            Self::ForwardToLeader(e) => e.code_name(),
+            Self::ChangeMembershipError(e) => e.code_name(),
+            Self::WriteExpired(e) => e.code_name(),
+            Self::StorageFull(e) => e.code_name(),
+            Self::ApplyScopeUnsupported(e) => e.code_name(),
+            Self::ReservedIndexMismatch(e) => e.code_name(),
+        }
+    }
+
+    fn retryable(&self) -> bool {
+        match self {
+            Self::ForwardToLeader(e) => e.retryable(),
+            Self::ChangeMembershipError(e) => e.retryable(),
+            Self::WriteExpired(e) => e.retryable(),
+            Self::StorageFull(e) => e.retryable(),
+            Self::ApplyScopeUnsupported(e) => e.retryable(),
+            Self::ReservedIndexMismatch(e) => e.retryable(),
+        }
+    }
+}
+
+/// An error returned by [`Raft::client_write_many()`](crate::Raft::client_write_many) for a
+/// single write.
+///
+/// A subset of [`ClientWriteError`]: a batch of application writes does not change the
+/// membership and has no deadline.
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone, thiserror::Error)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub enum WriteError<C>
+where C: RaftTypeConfig
+{
+    /// This node is not the leader; request should be forwarded to the leader.
+    #[error(transparent)]
+    ForwardToLeader(#[from] ForwardToLeader<C>),
+
+    /// The storage usage exceeds [`Config::storage_quota`](crate::Config::storage_quota).
+    #[error(transparent)]
+    StorageFull(#[from] StorageFull),
+}
+
+impl<C> ErrorCode for WriteError<C>
+where C: RaftTypeConfig
+{
+    fn code(&self) -> u32 {
+        match self {
+            Self::ForwardToLeader(e) => e.code(),
+            Self::StorageFull(e) => e.code(),
+        }
+    }
+
+    fn code_name(&self) -> &'static str {
+        match self {
+            Self::ForwardToLeader(e) => e.code_name(),
+            Self::StorageFull(e) => e.code_name(),
+        }
+    }
+
+    fn retryable(&self) -> bool {
+        match self {
+            Self::ForwardToLeader(e) => e.retryable(),
+            Self::StorageFull(e) => e.retryable(),
+        }
+    }
+}
+
 /// The set of errors which may take place when requesting to propose a config change.
 #[since(
     version = "0.10.0",
@@ -123,6 +250,68 @@ where
     /// A learner that should be in the cluster was not found.
     #[error(transparent)]
     LearnerNotFound(#[from] LearnerNotFound<NID>),
+
+    /// Two nodes in the new membership have the same node info.
+    #[error(transparent)]
+    DuplicateNode(#[from] DuplicateNode<NID>),
+
+    /// The failure domains of the new domain-aware membership are not a sane topology.
+    #[since(version = "0.10.0")]
+    #[error(transparent)]
+    FailureDomain(#[from] FailureDomainError<NID>),
+
+    /// The quorums of the new membership, decided by the quorum policy, do not intersect.
+    #[since(version = "0.10.0")]
+    #[error(transparent)]
+    QuorumPolicy(#[from] QuorumPolicyError<NID>),
+
+    /// A log-only node is added after the leader has purged logs it would need.
+    #[since(version = "0.10.0")]
+    #[error(transparent)]
+    LogOnlyLogsPurged(#[from] LogOnlyLogsPurged<NID>),
+
+    /// The new node of a replacement does not catch up with the leader in time.
+    #[since(version = "0.10.0")]
+    #[error(transparent)]
+    LearnerNotCaughtUp(#[from] LearnerNotCaughtUp<NID>),
+}
+
+impl<CLID, NID> ErrorCode for ChangeMembershipError<CLID, NID>
+where
+    CLID: RaftCommittedLeaderId,
+    NID: NodeId,
+{
+    fn code(&self) -> u32 {
+        match self {
+            Self::InProgress(_) => 3001,
+            Self::EmptyMembership(_) => 3002,
+            Self::LearnerNotFound(_) => 3003,
+            Self::DuplicateNode(_) => 3004,
+            Self::FailureDomain(_) => 3005,
+            Self::QuorumPolicy(_) => 3006,
+            Self::LogOnlyLogsPurged(_) => 3007,
+            Self::LearnerNotCaughtUp(_) => 3008,
+        }
+    }
+
+    fn code_name(&self) -> &'static str {
+        match self {
+            Self::InProgress(_) => "MEMBERSHIP_IN_PROGRESS",
+            Self::EmptyMembership(_) => "MEMBERSHIP_EMPTY",
+            Self::LearnerNotFound(_) => "LEARNER_NOT_FOUND",
+            Self::DuplicateNode(_) => "MEMBERSHIP_DUPLICATE_NODE",
+            Self::FailureDomain(_) => "MEMBERSHIP_INVALID_FAILURE_DOMAIN",
+            Self::QuorumPolicy(_) => "MEMBERSHIP_INVALID_QUORUM",
+            Self::LogOnlyLogsPurged(_) => "LOG_ONLY_LOGS_PURGED",
+            Self::LearnerNotCaughtUp(_) => "LEARNER_NOT_CAUGHT_UP",
+        }
+    }
+
+    fn retryable(&self) -> bool {
+        // Another change in progress will complete, after which the change can be retried. A
+        // lagging learner may catch up on the next attempt.
+        matches!(self, Self::InProgress(_) | Self::LearnerNotCaughtUp(_))
+    }
 }
 
 /// The set of errors which may take place when initializing a pristine Raft node.
@@ -138,6 +327,15 @@ where C: RaftTypeConfig
     /// This node is not included in the initial membership configuration.
     #[error(transparent)]
     NotInMembers(#[from] NotInMembers<C>),
+
+    /// Two nodes in the initial membership have the same node info.
+    #[error(transparent)]
+    DuplicateNode(#[from] DuplicateNode<C::NodeId>),
+
+    /// The quorums of the initial membership, decided by the quorum policy, do not intersect.
+    #[since(version = "0.10.0")]
+    #[error(transparent)]
+    QuorumPolicy(#[from] QuorumPolicyError<C::NodeId>),
 }
 
 /// Error occurs when invoking a remote raft API.
@@ -377,6 +575,100 @@ where C: RaftTypeConfig
     }
 }
 
+impl<C> ErrorCode for WriteExpired<C>
+where C: RaftTypeConfig
+{
+    fn code(&self) -> u32 {
+        4001
+    }
+
+    fn code_name(&self) -> &'static str {
+        "WRITE_EXPIRED"
+    }
+
+    /// The expired entry takes no effect on any node, so the write can be retried.
+    fn retryable(&self) -> bool {
+        true
+    }
+}
+
+impl ErrorCode for StorageFull {
+    fn code(&self) -> u32 {
+        4002
+    }
+
+    fn code_name(&self) -> &'static str {
+        "STORAGE_FULL"
+    }
+
+    /// The write can be retried once space is reclaimed, e.g., by the application or an operator.
+    fn retryable(&self) -> bool {
+        true
+    }
+}
+
+impl ErrorCode for ApplyScopeUnsupported {
+    fn code(&self) -> u32 {
+        4003
+    }
+
+    fn code_name(&self) -> &'static str {
+        "APPLY_SCOPE_UNSUPPORTED"
+    }
+
+    fn retryable(&self) -> bool {
+        false
+    }
+}
+
+impl ErrorCode for ReservedIndexMismatch {
+    fn code(&self) -> u32 {
+        4004
+    }
+
+    fn code_name(&self) -> &'static str {
+        "RESERVED_INDEX_MISMATCH"
+    }
+
+    /// The reserved indexes may already be taken by other entries; the write has to reserve again.
+    fn retryable(&self) -> bool {
+        false
+    }
+}
+
+impl<C> ErrorCode for StaleRead<C>
+where C: RaftTypeConfig
+{
+    fn code(&self) -> u32 {
+        4005
+    }
+
+    fn code_name(&self) -> &'static str {
+        "STALE_READ"
+    }
+
+    /// The read can be retried on a less stale replica, or on this node once it catches up.
+    fn retryable(&self) -> bool {
+        true
+    }
+}
+
+impl<C> ErrorCode for ForwardToLeader<C>
+where C: RaftTypeConfig
+{
+    fn code(&self) -> u32 {
+        2001
+    }
+
+    fn code_name(&self) -> &'static str {
+        "FORWARD_TO_LEADER"
+    }
+
+    fn retryable(&self) -> bool {
+        true
+    }
+}
+
 /// Error indicating a snapshot segment ID mismatch.
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -427,6 +719,58 @@ where NID: NodeId
     pub node_id: NID,
 }
 
+/// Error indicating the new node of a [`Raft::replace_node()`](crate::Raft::replace_node) does not
+/// catch up with the leader in time.
+///
+/// The new node is removed again, unless it was a member before. See
+/// [`Config::replace_node_timeout`](crate::Config::replace_node_timeout).
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("learner {node_id} did not catch up with the leader in time")]
+pub struct LearnerNotCaughtUp<NID>
+where NID: NodeId
+{
+    /// The node ID of the learner that did not catch up.
+    pub node_id: NID,
+}
+
+/// Error indicating a log-only node cannot be added because the leader has purged logs.
+///
+/// A log-only node does not install snapshot, it has to receive every log from the leader. See
+/// [`NodeRole::LogOnly`](crate::NodeRole::LogOnly).
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("log-only node {node_id} can not be added: logs up to index {last_purged_index} are purged")]
+pub struct LogOnlyLogsPurged<NID>
+where NID: NodeId
+{
+    /// The node ID of the log-only node.
+    pub node_id: NID,
+
+    /// The index of the last log the leader has purged.
+    pub last_purged_index: u64,
+}
+
+/// Error indicating two nodes in a membership have the same node info, e.g., the same address.
+///
+/// Returned only if [`Config::reject_duplicate_nodes`](crate::Config::reject_duplicate_nodes) is
+/// enabled.
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("node {node_id} has the same node info as node {other}")]
+pub struct DuplicateNode<NID>
+where NID: NodeId
+{
+    /// The node ID with the duplicated node info.
+    pub node_id: NID,
+
+    /// The node ID that already has the same node info.
+    pub other: NID,
+}
+
 /// Error indicating an operation is not allowed in the current state.
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
@@ -463,6 +807,20 @@ pub struct EmptyMembership {}
 #[error("infallible")]
 pub enum Infallible {}
 
+impl ErrorCode for Infallible {
+    fn code(&self) -> u32 {
+        match *self {}
+    }
+
+    fn code_name(&self) -> &'static str {
+        match *self {}
+    }
+
+    fn retryable(&self) -> bool {
+        match *self {}
+    }
+}
+
 /// A placeholder to mark RaftError won't have a ForwardToLeader variant.
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -0,0 +1,43 @@
+use openraft_macros::since;
+
+use crate::errors::MembershipError;
+use crate::node::NodeId;
+
+/// Errors occur when building a [`Membership`] of placed nodes with
+/// [`Membership::new_placed()`].
+///
+/// [`Membership`]: crate::membership::Membership
+/// [`Membership::new_placed()`]: crate::membership::Membership::new_placed
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub enum PlacementError<NID>
+where NID: NodeId
+{
+    /// The membership config itself is invalid.
+    #[error(transparent)]
+    Membership(#[from] MembershipError<NID>),
+
+    /// A node has an empty region.
+    #[error("node {node_id} has an empty region")]
+    EmptyRegion {
+        /// The node with an empty region.
+        node_id: NID,
+    },
+
+    /// A node has an empty zone.
+    #[error("node {node_id} has an empty zone")]
+    EmptyZone {
+        /// The node with an empty zone.
+        node_id: NID,
+    },
+
+    /// A node has a tag that is empty or contains whitespace.
+    #[error("node {node_id} has an invalid tag: {tag:?}")]
+    InvalidTag {
+        /// The node with the invalid tag.
+        node_id: NID,
+        /// The invalid tag.
+        tag: String,
+    },
+}
@@ -0,0 +1,46 @@
+use std::collections::BTreeSet;
+
+use openraft_macros::since;
+
+use crate::node::NodeId;
+
+/// Error indicating the quorums decided by a [`QuorumPolicy`] do not intersect as required.
+///
+/// The quorums of every config are checked when a node is created with a policy, when it
+/// initializes a cluster and when it changes membership. See
+/// [`check_quorum_policy()`](crate::membership::check_quorum_policy).
+///
+/// [`QuorumPolicy`]: crate::membership::QuorumPolicy
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub enum QuorumPolicyError<NID>
+where NID: NodeId
+{
+    /// An election quorum and a commit quorum are disjoint: a new leader may miss committed logs.
+    #[error("election quorum {election:?} and commit quorum {commit:?} do not intersect")]
+    ElectionCommitDisjoint {
+        /// The election quorum.
+        election: BTreeSet<NID>,
+        /// The commit quorum.
+        commit: BTreeSet<NID>,
+    },
+
+    /// Two election quorums are disjoint: two leaders may be elected in a term.
+    #[error("election quorums {a:?} and {b:?} do not intersect")]
+    ElectionsDisjoint {
+        /// One election quorum.
+        a: BTreeSet<NID>,
+        /// Another election quorum.
+        b: BTreeSet<NID>,
+    },
+
+    /// A config has too many voters for its quorums to be checked.
+    #[error("config has {voters} voters, at most {max} can be checked")]
+    TooManyVoters {
+        /// The number of voters of the config.
+        voters: usize,
+        /// The maximum number of voters in a config.
+        max: usize,
+    },
+}
@@ -5,6 +5,7 @@ use peel_off::Peel;
 
 use crate::RaftTypeConfig;
 use crate::StorageError;
+use crate::errors::ErrorCode;
 use crate::errors::Fatal;
 use crate::errors::ForwardToLeader;
 use crate::errors::Infallible;
@@ -127,6 +128,33 @@ where
     }
 }
 
+impl<C, E> ErrorCode for RaftError<C, E>
+where
+    C: RaftTypeConfig,
+    E: ErrorCode,
+{
+    fn code(&self) -> u32 {
+        match self {
+            RaftError::APIError(e) => e.code(),
+            RaftError::Fatal(f) => f.code(),
+        }
+    }
+
+    fn code_name(&self) -> &'static str {
+        match self {
+            RaftError::APIError(e) => e.code_name(),
+            RaftError::Fatal(f) => f.code_name(),
+        }
+    }
+
+    fn retryable(&self) -> bool {
+        match self {
+            RaftError::APIError(e) => e.retryable(),
+            RaftError::Fatal(f) => f.retryable(),
+        }
+    }
+}
+
 impl<C, E> From<StorageError<C>> for RaftError<C, E>
 where C: RaftTypeConfig
 {
@@ -0,0 +1,25 @@
+use std::ops::Range;
+
+use openraft_macros::since;
+
+/// Error indicating a write to reserved log indexes is rejected because it does not continue the
+/// reservation.
+///
+/// Log indexes reserved with [`Raft::reserve_log_indexes()`](crate::Raft::reserve_log_indexes)
+/// have to be filled in order: a write has to start at the first unfilled reserved index and must
+/// not go beyond the end of the reservation. A reservation is dropped when the leader writes any
+/// other entry or when it loses leadership.
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[error("write to log indexes {}..{} does not continue the reserved log indexes: {reserved:?}", index, index + count)]
+pub struct ReservedIndexMismatch {
+    /// The first log index of the rejected write.
+    pub index: u64,
+
+    /// The number of entries in the rejected write.
+    pub count: u64,
+
+    /// The unfilled reserved log indexes, or `None` if there is no reservation.
+    pub reserved: Option<Range<u64>>,
+}
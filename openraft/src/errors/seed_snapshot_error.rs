@@ -0,0 +1,18 @@
+use openraft_macros::since;
+
+use crate::RaftTypeConfig;
+use crate::errors::ForwardToLeader;
+use crate::errors::NodeNotFound;
+
+/// Error related to setting the seed node a follower fetches snapshots from.
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub enum SeedSnapshotError<C: RaftTypeConfig> {
+    /// The target or the seed node was not found.
+    #[error("cannot set snapshot seed; error: {0}")]
+    NodeNotFound(#[from] NodeNotFound<C::NodeId>),
+    /// Request must be forwarded to the leader.
+    #[error("cannot set snapshot seed; error: {0}")]
+    ForwardToLeader(#[from] ForwardToLeader<C>),
+}
@@ -0,0 +1,44 @@
+use std::time::Duration;
+
+use display_more::DisplayOptionExt;
+use openraft_macros::since;
+
+use crate::RaftTypeConfig;
+use crate::type_config::alias::LogIdOf;
+
+/// Error indicating a local read is rejected because this node is more stale than the
+/// [`ReadOptions`](crate::raft::ReadOptions) allow.
+///
+/// It tells how stale this node is, so that the caller can choose another replica, or wait and
+/// retry on this one. A witness or a log-only node rejects every local read, since it has no
+/// state machine.
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error(
+    "stale read: applied: {}, required applied index: {}, since leader contact: {}, max staleness: {}, witness: {}",
+    applied.display(),
+    min_applied_index.display(),
+    since_leader_contact.map(|d| format!("{:?}", d)).display(),
+    max_staleness.map(|d| format!("{:?}", d)).display(),
+    witness
+)]
+pub struct StaleRead<C>
+where C: RaftTypeConfig
+{
+    /// The last log id applied to the state machine of this node.
+    pub applied: Option<LogIdOf<C>>,
+
+    /// The required applied log index, if any.
+    pub min_applied_index: Option<u64>,
+
+    /// The time since this node last heard from the leader, or `None` if no leader is known.
+    pub since_leader_contact: Option<Duration>,
+
+    /// The allowed time since the last contact with the leader, if any.
+    pub max_staleness: Option<Duration>,
+
+    /// Whether this node is a [`NodeRole::Witness`](crate::NodeRole::Witness), which never serves
+    /// a read.
+    pub witness: bool,
+}
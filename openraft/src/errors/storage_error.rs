@@ -146,6 +146,11 @@ where C: RaftTypeConfig
         Self { subject, verb, source }
     }
 
+    /// Returns the subject of the failed storage operation.
+    pub(crate) fn subject(&self) -> &ErrorSubject<C> {
+        &self.subject
+    }
+
     /// Create an error for writing a log entry.
     pub fn write_log_entry(log_id: LogIdOf<C>, source: C::ErrorSource) -> Self {
         Self::new(ErrorSubject::Log(log_id), ErrorVerb::Write, source)
@@ -0,0 +1,19 @@
+use openraft_macros::since;
+
+/// Error indicating a write is rejected because the storage usage exceeds
+/// [`Config::storage_quota`](crate::Config::storage_quota).
+///
+/// Openraft has already built a snapshot and purged the logs it includes, but the usage is still
+/// above the quota. Writes are accepted again once the usage reported by the
+/// [`StorageUsageProbe`](crate::storage::StorageUsageProbe) is back under the quota.
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[error("storage usage {usage} bytes exceeds quota {quota} bytes")]
+pub struct StorageFull {
+    /// The storage usage in bytes, when it was last checked.
+    pub usage: u64,
+
+    /// The configured quota in bytes.
+    pub quota: u64,
+}
@@ -0,0 +1,19 @@
+use openraft_macros::since;
+
+use crate::RaftTypeConfig;
+use crate::type_config::alias::LogIdOf;
+
+/// Error indicating a write with a deadline was not committed before the deadline.
+///
+/// The entry is committed, but every node applies it as a blank entry: the write takes no effect.
+/// See [`WriteRequest::deadline()`](crate::raft::WriteRequest::deadline).
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("write at {log_id} is not committed before its deadline, it is applied as a blank entry")]
+pub struct WriteExpired<C>
+where C: RaftTypeConfig
+{
+    /// The log id of the entry written.
+    pub log_id: LogIdOf<C>,
+}
@@ -12,6 +12,7 @@
 //! - [`NodeInfo`] - Simple node information with Raft address and user-defined data
 //! - [`EmptyNode`] - Minimal node representation (no metadata)
 //! - [`OneshotResponder`] - Single-use response channel
+//! - [`StreamingResponder`] - Delivers accepted, committed and completed events to a sink
 //! - [`BoxedErrorSource`] - Boxed error wrapper for smaller error types
 //!
 //! ## Runtime
@@ -38,6 +39,8 @@ pub use crate::node::EmptyNode;
 pub use crate::node::NodeInfo;
 pub use crate::raft::responder::impls::OneshotResponder;
 pub use crate::raft::responder::impls::ProgressResponder;
+pub use crate::raft::responder::impls::StreamingResponder;
+pub use crate::raft::responder::impls::WriteEvent;
 
 /// LeaderId implementation for advanced mode, allowing multiple leaders per term.
 pub mod leader_id_adv {
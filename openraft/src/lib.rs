@@ -112,13 +112,19 @@ pub use self::storage::RaftSnapshotBuilder;
 pub use self::storage::Snapshot;
 pub use self::storage::SnapshotMeta;
 pub use self::storage::StorageHelper;
+pub use crate::base::OptionalEncodeContent;
 use crate::base::OptionalFeatures;
 pub use crate::base::OptionalSend;
 pub use crate::base::OptionalSerde;
 pub use crate::base::OptionalSync;
 pub use crate::change_members::ChangeMembers;
 pub use crate::config::Config;
+pub use crate::config::ConfigDigest;
 pub use crate::config::ConfigError;
+pub use crate::config::ConfigSource;
+pub use crate::config::EffectiveConfig;
+pub use crate::config::Profile;
+pub use crate::config::PurgePolicy;
 pub use crate::config::SnapshotPolicy;
 pub use crate::config::StepDownPolicy;
 pub use crate::core::ServerState;
@@ -129,6 +135,7 @@ pub use crate::log_id::LogId;
 pub use crate::log_id::LogIdOptionExt;
 pub use crate::log_id::LogIndexOptionExt;
 pub use crate::membership::Membership;
+pub use crate::membership::NodeRole;
 pub use crate::membership::StoredMembership;
 pub use crate::metrics::RaftMetrics;
 pub use crate::network::RPCTypes;
@@ -141,6 +148,8 @@ pub use crate::node::EmptyNode;
 pub use crate::node::Node;
 pub use crate::node::NodeId;
 pub use crate::node::NodeInfo;
+pub use crate::node::NodePlacement;
+pub use crate::node::PlacedNode;
 pub use crate::raft::Raft;
 pub use crate::raft::ReadPolicy;
 pub use crate::raft::WatchChangeHandle;
@@ -0,0 +1,196 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::Range;
+
+use openraft_macros::since;
+
+use crate::LogIdOptionExt;
+use crate::engine::LogIdList;
+use crate::log_id::LogId;
+use crate::vote::RaftCommittedLeaderId;
+
+/// Where the logs of two nodes diverge, computed from their [`LogIdList`] summaries.
+///
+/// Admin tooling uses it to decide which replica should win before an unsafe recovery, such as
+/// forcing a membership change onto a minority. A node's summary is read with
+/// `raft.with_raft_state(|st| st.log_ids.clone())`.
+///
+/// By the log matching property, the two logs are identical up to [`common`](Self::common) and
+/// differ after it: entries in [`left_only()`](Self::left_only) are truncated from the left log if
+/// the right one wins, and are what the right log needs if the left one wins, and vice versa.
+#[since(version = "0.10.0")]
+#[derive(Clone, PartialEq, Eq)]
+pub struct LogDivergence<CLID>
+where CLID: RaftCommittedLeaderId
+{
+    /// The last log id both logs have, `None` if they share no entry.
+    pub common: Option<LogId<CLID>>,
+
+    /// The last log id of the left log.
+    pub left_last: Option<LogId<CLID>>,
+
+    /// The last log id of the right log.
+    pub right_last: Option<LogId<CLID>>,
+}
+
+impl<CLID> LogDivergence<CLID>
+where CLID: RaftCommittedLeaderId
+{
+    /// Compare two log summaries.
+    ///
+    /// Returns `None` if the divergence point falls in a range purged from either log, where the
+    /// log ids are no longer known. This happens when one log is purged beyond where the other
+    /// one ends or starts to differ.
+    #[since(version = "0.10.0")]
+    pub fn compare(left: &LogIdList<CLID>, right: &LogIdList<CLID>) -> Option<Self> {
+        let left_last = left.last().cloned();
+        let right_last = right.last().cloned();
+
+        let common = Self::find_common(left, right)?;
+
+        Some(Self {
+            common,
+            left_last,
+            right_last,
+        })
+    }
+
+    /// Binary search for the last index at which both logs have the same log id.
+    ///
+    /// The outer `Option` is `None` if it can not be determined.
+    fn find_common(left: &LogIdList<CLID>, right: &LogIdList<CLID>) -> Option<Option<LogId<CLID>>> {
+        let (Some(left_last), Some(right_last)) = (left.last(), right.last()) else {
+            return Some(None);
+        };
+
+        // The smallest index whose log id is known on both sides.
+        let first_known = |l: &LogIdList<CLID>| l.purged().map(|p| p.index).unwrap_or_default();
+        let lo = std::cmp::max(first_known(left), first_known(right));
+        let hi = std::cmp::min(left_last.index, right_last.index);
+
+        if lo > hi {
+            return None;
+        }
+
+        let same_at = |index: u64| left.get(index) == right.get(index);
+
+        if !same_at(lo) {
+            // Diverged at or before `lo`: known only if neither log has purged anything.
+            let nothing_purged = left.purged().is_none() && right.purged().is_none();
+            return if nothing_purged { Some(None) } else { None };
+        }
+
+        // Invariant: same at `lo`, different after `hi`.
+        let (mut lo, mut hi) = (lo, hi);
+        while lo < hi {
+            let mid = lo + (hi - lo).div_ceil(2);
+            if same_at(mid) {
+                lo = mid;
+            } else {
+                hi = mid - 1;
+            }
+        }
+
+        Some(left.get(lo))
+    }
+
+    /// Indexes of the entries in the left log after [`common`](Self::common).
+    #[since(version = "0.10.0")]
+    pub fn left_only(&self) -> Range<u64> {
+        self.common.next_index()..self.left_last.next_index()
+    }
+
+    /// Indexes of the entries in the right log after [`common`](Self::common).
+    #[since(version = "0.10.0")]
+    pub fn right_only(&self) -> Range<u64> {
+        self.common.next_index()..self.right_last.next_index()
+    }
+
+    /// Compare the logs the way a Raft election does: the log with the greater last log id is
+    /// more up-to-date, and a replica holding it is the one that should win.
+    ///
+    /// Returns `None` if the last log ids are not comparable.
+    #[since(version = "0.10.0")]
+    pub fn up_to_date_order(&self) -> Option<Ordering> {
+        self.left_last.partial_cmp(&self.right_last)
+    }
+}
+
+impl<CLID> fmt::Debug for LogDivergence<CLID>
+where CLID: RaftCommittedLeaderId
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LogDivergence")
+            .field("common", &self.common)
+            .field("left_last", &self.left_last)
+            .field("right_last", &self.right_last)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use super::LogDivergence;
+    use crate::engine::LogIdList;
+    use crate::engine::testing::log_id;
+
+    #[test]
+    fn test_log_divergence() -> anyhow::Result<()> {
+        // left:  1-1 1-2 2-3 2-4 2-5
+        // right: 1-1 1-2 3-3
+        let left = LogIdList::new(None, vec![log_id(1, 1, 2), log_id(2, 1, 5)]);
+        let right = LogIdList::new(None, vec![log_id(1, 1, 2), log_id(3, 1, 3)]);
+
+        let d = LogDivergence::compare(&left, &right).unwrap();
+        assert_eq!(Some(log_id(1, 1, 2)), d.common);
+        assert_eq!(3..6, d.left_only());
+        assert_eq!(3..4, d.right_only());
+        assert_eq!(Some(Ordering::Less), d.up_to_date_order());
+
+        // Identical prefix: the shorter log only needs entries.
+        let short = LogIdList::new(Some(log_id(1, 1, 2)), vec![log_id(2, 1, 4)]);
+        let d = LogDivergence::compare(&left, &short).unwrap();
+        assert_eq!(Some(log_id(2, 1, 4)), d.common);
+        assert_eq!(5..6, d.left_only());
+        assert_eq!(5..5, d.right_only());
+        assert_eq!(Some(Ordering::Greater), d.up_to_date_order());
+
+        // Diverged from the first entry.
+        let other = LogIdList::new(None, vec![log_id(3, 1, 1)]);
+        let d = LogDivergence::compare(&left, &other).unwrap();
+        assert_eq!(None, d.common);
+        assert_eq!(0..6, d.left_only());
+        assert_eq!(0..2, d.right_only());
+
+        // Empty log.
+        let d = LogDivergence::compare(&left, &LogIdList::default()).unwrap();
+        assert_eq!(None, d.common);
+        assert_eq!(0..0, d.right_only());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_log_divergence_in_purged_range() -> anyhow::Result<()> {
+        let left = LogIdList::new(Some(log_id(2, 1, 5)), vec![log_id(2, 1, 7)]);
+
+        // Ends before the left log is purged.
+        let right = LogIdList::new(None, vec![log_id(1, 1, 3)]);
+        assert_eq!(None, LogDivergence::compare(&left, &right));
+
+        // Differs at the purged log id of the left log.
+        let right = LogIdList::new(None, vec![log_id(1, 1, 5)]);
+        assert_eq!(None, LogDivergence::compare(&left, &right));
+
+        // Both purged, same after it.
+        let right = LogIdList::new(Some(log_id(2, 1, 6)), vec![log_id(2, 1, 7), log_id(3, 1, 8)]);
+        let d = LogDivergence::compare(&left, &right).unwrap();
+        assert_eq!(Some(log_id(2, 1, 7)), d.common);
+        assert_eq!(8..8, d.left_only());
+        assert_eq!(8..9, d.right_only());
+
+        Ok(())
+    }
+}
@@ -8,6 +8,8 @@
 //! - [`LogId`] - Unique identifier for a log entry `(leader_id, index)`
 //! - [`LogIdOptionExt`] - Extension trait for `Option<LogId>` comparisons
 //! - [`LogIndexOptionExt`] - Extension trait for `Option<u64>` index comparisons
+//! - [`LogIdList`] - Compact list of the log ids in a log, the last of each leader
+//! - [`LogDivergence`] - Where the logs of two nodes diverge
 //!
 //! ## Overview
 //!
@@ -24,6 +26,7 @@
 //! This ordering ensures that logs from higher terms always supersede logs from lower terms,
 //! which is fundamental to Raft's consistency guarantees.
 
+mod log_divergence;
 mod log_id_option_ext;
 mod log_index_option_ext;
 pub(crate) mod option_raft_log_id_ext;
@@ -36,11 +39,14 @@ mod std_log_id;
 use std::fmt::Display;
 use std::fmt::Formatter;
 
+pub use log_divergence::LogDivergence;
 pub use log_id_option_ext::LogIdOptionExt;
 pub use log_index_option_ext::LogIndexOptionExt;
 use openraft_macros::since;
 
 pub use self::raft_log_id::RaftLogId;
+pub use crate::engine::LogIdList;
+use crate::entry::EncodeContent;
 use crate::vote::RaftCommittedLeaderId;
 use crate::vote::RaftTerm;
 use crate::vote::leader_id_std;
@@ -94,6 +100,15 @@ where CLID: RaftCommittedLeaderId
     }
 }
 
+impl<CLID> EncodeContent for LogId<CLID>
+where CLID: RaftCommittedLeaderId + EncodeContent
+{
+    fn encode_content(&self, buf: &mut Vec<u8>) {
+        self.leader_id.encode_content(buf);
+        self.index.encode_content(buf);
+    }
+}
+
 impl<CLID> LogId<CLID>
 where CLID: RaftCommittedLeaderId
 {
@@ -57,7 +57,6 @@ where C: RaftTypeConfig
         Self { prev, last }
     }
 
-    #[allow(dead_code)]
     pub(crate) fn len(&self) -> u64 {
         self.last.next_index() - self.prev.next_index()
     }
@@ -2,18 +2,29 @@ use core::fmt;
 use std::collections::BTreeMap;
 use std::collections::BTreeSet;
 
+use display_more::DisplayBTreeSetExt;
 use openraft_macros::since;
 
 use crate::ChangeMembers;
+use crate::display_ext::DisplayBTreeMapExt;
+use crate::entry::EncodeContent;
+use crate::errors::DuplicateNode;
 use crate::errors::EmptyMembership;
+use crate::errors::FailureDomainError;
 use crate::errors::MembershipError;
 use crate::errors::NodeNotFound;
 use crate::errors::Operation;
 use crate::membership::IntoNodes;
+use crate::membership::NodeRole;
 use crate::node::Node;
 use crate::node::NodeId;
 use crate::quorum::FindCoherent;
 
+/// The minimum number of failure domains the voters of a domain-aware config have to span.
+///
+/// With two domains, a majority of them is both, and losing either loses the quorum.
+pub(crate) const MIN_FAILURE_DOMAINS: usize = 3;
+
 /// The membership configuration of the cluster.
 ///
 /// It could be a joint of one, two or more configs, i.e., a quorum is a node set that is superset
@@ -38,6 +49,28 @@ where
     ///
     /// A node-id key that is in `nodes` but is not in `configs` is a **learner**.
     pub(crate) nodes: BTreeMap<NID, N>,
+
+    /// The ids of the nodes that only store log metadata. See [`NodeRole::Witness`].
+    ///
+    /// Every one of them is in `nodes`.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "BTreeSet::is_empty"))]
+    pub(crate) witnesses: BTreeSet<NID>,
+
+    /// The ids of the nodes that store the log but have no state machine. See
+    /// [`NodeRole::LogOnly`].
+    ///
+    /// Every one of them is in `nodes`.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "BTreeSet::is_empty"))]
+    pub(crate) log_only: BTreeSet<NID>,
+
+    /// The failure domain of every node, e.g., its zone or rack, if the membership is
+    /// domain-aware.
+    ///
+    /// If it is not empty, a quorum of a config is a majority of the voters in a majority of the
+    /// failure domains, instead of a majority of the voters. See
+    /// [`Membership::new_domain_aware()`].
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "BTreeMap::is_empty"))]
+    pub(crate) failure_domains: BTreeMap<NID, String>,
 }
 
 impl<NID, N> Default for Membership<NID, N>
@@ -49,6 +82,9 @@ where
         Membership {
             configs: vec![],
             nodes: BTreeMap::new(),
+            witnesses: BTreeSet::new(),
+            log_only: BTreeSet::new(),
+            failure_domains: BTreeMap::new(),
         }
     }
 }
@@ -111,11 +147,39 @@ where
                 write!(f, "None")?;
             }
         }
-        write!(f, "]}}")?;
+        write!(f, "]")?;
+
+        if !self.witnesses.is_empty() {
+            write!(f, ", witnesses:{}", self.witnesses.display())?;
+        }
+
+        if !self.log_only.is_empty() {
+            write!(f, ", log_only:{}", self.log_only.display())?;
+        }
+
+        if !self.failure_domains.is_empty() {
+            write!(f, ", failure_domains:{}", self.failure_domains.display())?;
+        }
+
+        write!(f, "}}")?;
         Ok(())
     }
 }
 
+impl<NID, N> EncodeContent for Membership<NID, N>
+where
+    NID: NodeId + EncodeContent,
+    N: Node + EncodeContent,
+{
+    fn encode_content(&self, buf: &mut Vec<u8>) {
+        self.configs.encode_content(buf);
+        self.nodes.encode_content(buf);
+        self.witnesses.encode_content(buf);
+        self.log_only.encode_content(buf);
+        self.failure_domains.encode_content(buf);
+    }
+}
+
 // Public APIs
 impl<NID, N> Membership<NID, N>
 where
@@ -136,6 +200,9 @@ where
         let m = Membership {
             configs: config,
             nodes: nodes.into_nodes(),
+            witnesses: BTreeSet::new(),
+            log_only: BTreeSet::new(),
+            failure_domains: BTreeMap::new(),
         };
 
         m.ensure_valid()?;
@@ -160,7 +227,59 @@ where
 
         let nodes = Self::extend_nodes(nodes.into_iter().map(|x| (x, N::default())).collect(), &voter_nodes);
 
-        Membership { configs: config, nodes }
+        Membership {
+            configs: config,
+            nodes,
+            witnesses: BTreeSet::new(),
+            log_only: BTreeSet::new(),
+            failure_domains: BTreeMap::new(),
+        }
+    }
+
+    /// Create a new Membership the same as [`Self::new()`], with the nodes in `witnesses` as
+    /// witnesses. See [`NodeRole::Witness`].
+    ///
+    /// A witness id that is not in `nodes` results in an error return.
+    #[since(version = "0.10.0")]
+    pub fn new_with_witnesses<T>(
+        config: Vec<BTreeSet<NID>>,
+        nodes: T,
+        witnesses: BTreeSet<NID>,
+    ) -> Result<Self, MembershipError<NID>>
+    where
+        T: IntoNodes<NID, N>,
+    {
+        let mut m = Self::new(config, nodes)?;
+
+        if let Some(id) = witnesses.iter().find(|id| !m.contains(id)) {
+            return Err(NodeNotFound::new(id.clone(), Operation::None).into());
+        }
+
+        m.witnesses = witnesses;
+        Ok(m)
+    }
+
+    /// Create a new Membership the same as [`Self::new()`], with the nodes in `log_only` as
+    /// log-only nodes. See [`NodeRole::LogOnly`].
+    ///
+    /// A log-only id that is not in `nodes` results in an error return.
+    #[since(version = "0.10.0")]
+    pub fn new_with_log_only<T>(
+        config: Vec<BTreeSet<NID>>,
+        nodes: T,
+        log_only: BTreeSet<NID>,
+    ) -> Result<Self, MembershipError<NID>>
+    where
+        T: IntoNodes<NID, N>,
+    {
+        let mut m = Self::new(config, nodes)?;
+
+        if let Some(id) = log_only.iter().find(|id| !m.contains(id)) {
+            return Err(NodeNotFound::new(id.clone(), Operation::None).into());
+        }
+
+        m.log_only = log_only;
+        Ok(m)
     }
 
     /// Returns reference to the joint config.
@@ -184,6 +303,21 @@ where
         self.nodes.get(node_id)
     }
 
+    /// Find two nodes that have equal node info, e.g., the same address.
+    ///
+    /// The node with the greater id is reported as the duplicate.
+    pub(crate) fn find_duplicate_node(&self) -> Result<(), DuplicateNode<NID>> {
+        for (i, (node_id, node)) in self.nodes.iter().enumerate() {
+            if let Some((other, _)) = self.nodes.iter().take(i).find(|(_, n)| *n == node) {
+                return Err(DuplicateNode {
+                    node_id: node_id.clone(),
+                    other: other.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+
     /// Returns an Iterator of all voter node ids. Learners are not included.
     pub fn voter_ids(&self) -> impl Iterator<Item = NID> {
         self.configs.iter().flatten().cloned().collect::<BTreeSet<_>>().into_iter()
@@ -193,6 +327,56 @@ where
     pub fn learner_ids(&self) -> impl Iterator<Item = NID> + '_ {
         self.nodes.keys().filter(|x| !self.is_voter(x)).cloned()
     }
+
+    /// Returns an Iterator of the ids of all nodes that only store log metadata.
+    ///
+    /// A witness is usually a voter, see [`NodeRole::Witness`]. A witness removed from the voters
+    /// and retained as a learner still only receives log metadata.
+    #[since(version = "0.10.0")]
+    pub fn witness_ids(&self) -> impl Iterator<Item = NID> + '_ {
+        self.witnesses.iter().cloned()
+    }
+
+    /// Returns an Iterator of the ids of all nodes that store the log but have no state machine.
+    ///
+    /// See [`NodeRole::LogOnly`].
+    #[since(version = "0.10.0")]
+    pub fn log_only_ids(&self) -> impl Iterator<Item = NID> + '_ {
+        self.log_only.iter().cloned()
+    }
+
+    /// Returns the role of a node, or `None` if it is not in this membership.
+    #[since(version = "0.10.0")]
+    pub fn node_role(&self, node_id: &NID) -> Option<NodeRole> {
+        if !self.contains(node_id) {
+            return None;
+        }
+
+        let role = if !self.is_voter(node_id) {
+            NodeRole::Learner
+        } else if self.is_witness(node_id) {
+            NodeRole::Witness
+        } else if self.is_log_only(node_id) {
+            NodeRole::LogOnly
+        } else {
+            NodeRole::Voter
+        };
+        Some(role)
+    }
+
+    /// Returns `true` if a quorum of this membership is a majority of the voters in a majority of
+    /// the failure domains. See [`Membership::new_domain_aware()`].
+    #[since(version = "0.10.0")]
+    pub fn is_domain_aware(&self) -> bool {
+        !self.failure_domains.is_empty()
+    }
+
+    /// Returns the failure domain of a node, or `None` if the membership is not domain-aware or
+    /// the node is not in it.
+    #[since(version = "0.10.0")]
+    pub fn failure_domain(&self, node_id: &NID) -> Option<&str> {
+        self.failure_domains.get(node_id).map(|d| d.as_str())
+    }
 }
 
 impl<NID, N> Membership<NID, N>
@@ -215,13 +399,36 @@ where
         false
     }
 
+    /// Check if the given `NodeId` only stores log metadata.
+    pub(crate) fn is_witness(&self, node_id: &NID) -> bool {
+        self.witnesses.contains(node_id)
+    }
+
+    /// Check if the given `NodeId` stores the log but has no state machine.
+    pub(crate) fn is_log_only(&self, node_id: &NID) -> bool {
+        self.log_only.contains(node_id)
+    }
+
+    /// Check if the given `NodeId` may become leader.
+    ///
+    /// A witness or a log-only node has no state machine to serve clients with.
+    pub(crate) fn is_leader_eligible(&self, node_id: &NID) -> bool {
+        !self.is_witness(node_id) && !self.is_log_only(node_id)
+    }
+
     /// Create a new Membership the same as [`Self::new()`], but does not add the default
     /// value `Node::default()` if a voter id is not in `nodes`. Thus, it may create an invalid
     /// instance.
     pub(crate) fn new_unchecked<T>(configs: Vec<BTreeSet<NID>>, nodes: T) -> Self
     where T: IntoNodes<NID, N> {
         let nodes = nodes.into_nodes();
-        Membership { configs, nodes }
+        Membership {
+            configs,
+            nodes,
+            witnesses: BTreeSet::new(),
+            log_only: BTreeSet::new(),
+            failure_domains: BTreeMap::new(),
+        }
     }
 
     /// Extends nodes btreemap with another.
@@ -261,6 +468,43 @@ where
         Ok(())
     }
 
+    /// Ensures that the failure domains of a domain-aware membership are a sane topology:
+    /// every voter has a failure domain, and the voters of every config span at least
+    /// [`MIN_FAILURE_DOMAINS`] of them, so that losing one does not lose a quorum.
+    ///
+    /// A membership that is not domain-aware is always valid.
+    pub(crate) fn ensure_failure_domains(&self) -> Result<(), FailureDomainError<NID>> {
+        if !self.is_domain_aware() {
+            return Ok(());
+        }
+
+        for config in self.configs.iter() {
+            let mut domains = BTreeSet::new();
+
+            for voter_id in config.iter() {
+                match self.failure_domains.get(voter_id) {
+                    Some(d) if !d.is_empty() => {
+                        domains.insert(d.as_str());
+                    }
+                    _ => {
+                        return Err(FailureDomainError::Missing {
+                            node_id: voter_id.clone(),
+                        });
+                    }
+                }
+            }
+
+            if domains.len() < MIN_FAILURE_DOMAINS {
+                return Err(FailureDomainError::TooFewDomains {
+                    domains: domains.len(),
+                    min: MIN_FAILURE_DOMAINS,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
     /// Ensures that every vote has a corresponding Node.
     ///
     /// If a voter is found not having a Node, it returns the voter node id in an `Err()`
@@ -312,7 +556,22 @@ where
             }
         };
 
-        Membership::new_unchecked(config, nodes)
+        let witnesses = self.witnesses.iter().filter(|id| nodes.contains_key(id)).cloned().collect();
+        let log_only = self.log_only.iter().filter(|id| nodes.contains_key(id)).cloned().collect();
+        let failure_domains = self
+            .failure_domains
+            .iter()
+            .filter(|(id, _)| nodes.contains_key(id))
+            .map(|(id, d)| (id.clone(), d.clone()))
+            .collect();
+
+        Membership {
+            configs: config,
+            nodes,
+            witnesses,
+            log_only,
+            failure_domains,
+        }
     }
 
     /// Apply a change-membership request and return a new instance.
@@ -324,21 +583,62 @@ where
     pub(crate) fn change(mut self, change: ChangeMembers<NID, N>, retain: bool) -> Result<Self, MembershipError<NID>> {
         tracing::debug!("{}: change: {:?}", func_name!(), change);
 
-        let Membership { mut configs, nodes } = self.clone().compute_target_membership(change);
+        let Membership {
+            mut configs,
+            nodes,
+            witnesses,
+            log_only,
+            failure_domains,
+        } = self.clone().compute_target_membership(change);
+
+        // The quorum rule can not be switched without a joint config of both rules.
+        if !self.is_domain_aware() && !failure_domains.is_empty() {
+            return Err(FailureDomainError::NotDomainAware.into());
+        }
 
         // Safe unwrap(): `calculate_goal()` yields a uniform config.
         let target_voter_ids = configs.pop().unwrap();
 
         self.nodes = nodes;
+        self.witnesses = witnesses;
+        self.log_only = log_only;
+        self.failure_domains = failure_domains;
         let new_membership = self.next_coherent(target_voter_ids, retain);
 
         tracing::debug!("new membership: {}", new_membership);
 
         new_membership.ensure_valid()?;
+        new_membership.ensure_failure_domains()?;
 
         Ok(new_membership)
     }
 
+    /// Remove learners and return a new instance, keeping the voter configs as they are, even a
+    /// joint one.
+    ///
+    /// It returns an error if one of `learner_ids` is a voter.
+    pub(crate) fn remove_learners(mut self, learner_ids: &BTreeSet<NID>) -> Result<Self, MembershipError<NID>> {
+        for node_id in learner_ids.iter() {
+            self.nodes.remove(node_id);
+        }
+        self.witnesses.retain(|id| self.nodes.contains_key(id));
+        self.log_only.retain(|id| self.nodes.contains_key(id));
+        self.failure_domains.retain(|id, _| self.nodes.contains_key(id));
+
+        self.ensure_valid()?;
+
+        Ok(self)
+    }
+
+    /// Whether `self` could be built from `prev` by only removing learners, as
+    /// [`ChangeMembers::RemoveLearners`] does: the quorums are the same and no node is added.
+    pub(crate) fn is_learner_removal_of(&self, prev: &Self) -> bool {
+        self.configs == prev.configs
+            && self.is_domain_aware() == prev.is_domain_aware()
+            && self.voter_ids().all(|id| self.failure_domain(&id) == prev.failure_domain(&id))
+            && self.nodes.keys().all(|id| prev.nodes.contains_key(id))
+    }
+
     /// Compute the target membership configuration by applying a membership change.
     ///
     /// This method:
@@ -377,7 +677,7 @@ where
                 self.configs = vec![all_voter_ids];
                 self
             }
-            ChangeMembers::AddNodes(add_nodes) => {
+            ChangeMembers::AddNodes(add_nodes) | ChangeMembers::AddVotersWhenCaughtUp(add_nodes) => {
                 // When adding nodes, do not override existing node
                 for (node_id, node) in add_nodes.into_iter() {
                     self.nodes.entry(node_id).or_insert(node);
@@ -390,14 +690,54 @@ where
                 }
                 self
             }
-            ChangeMembers::RemoveNodes(remove_node_ids) => {
+            ChangeMembers::RemoveNodes(remove_node_ids) | ChangeMembers::RemoveLearners(remove_node_ids) => {
                 for node_id in remove_node_ids.iter() {
                     self.nodes.remove(node_id);
                 }
+                self.witnesses.retain(|id| self.nodes.contains_key(id));
+                self.log_only.retain(|id| self.nodes.contains_key(id));
+                self.failure_domains.retain(|id, _| self.nodes.contains_key(id));
                 self
             }
             ChangeMembers::ReplaceAllNodes(all_nodes) => {
                 self.nodes = all_nodes;
+                self.witnesses.retain(|id| self.nodes.contains_key(id));
+                self.log_only.retain(|id| self.nodes.contains_key(id));
+                self.failure_domains.retain(|id, _| self.nodes.contains_key(id));
+                self
+            }
+            ChangeMembers::SetFailureDomains(failure_domains) => {
+                // The failure domain of an existing node is not changed: it would change the
+                // quorums of the current config without a joint config.
+                for (node_id, domain) in failure_domains.into_iter() {
+                    self.failure_domains.entry(node_id).or_insert(domain);
+                }
+                self
+            }
+            ChangeMembers::AddWitnesses(add_witnesses) => {
+                // An existing node is not turned into a witness: it may already have data that a
+                // witness does not keep up to date.
+                let new_witness_ids = add_witnesses.keys().filter(|id| !self.nodes.contains_key(id)).cloned();
+                self.witnesses.extend(new_witness_ids);
+
+                self.nodes = Self::extend_nodes(self.nodes, &add_witnesses);
+
+                let add_voter_ids = add_witnesses.keys().cloned().collect::<BTreeSet<_>>();
+                let new_voter_ids = last.union(&add_voter_ids).cloned().collect::<BTreeSet<_>>();
+                self.configs = vec![new_voter_ids];
+                self
+            }
+            ChangeMembers::AddLogOnly(add_log_only) => {
+                // An existing node is not turned into a log-only node: it may be serving as a
+                // leader with its state machine.
+                let new_log_only_ids = add_log_only.keys().filter(|id| !self.nodes.contains_key(id)).cloned();
+                self.log_only.extend(new_log_only_ids);
+
+                self.nodes = Self::extend_nodes(self.nodes, &add_log_only);
+
+                let add_voter_ids = add_log_only.keys().cloned().collect::<BTreeSet<_>>();
+                let new_voter_ids = last.union(&add_voter_ids).cloned().collect::<BTreeSet<_>>();
+                self.configs = vec![new_voter_ids];
                 self
             }
             ChangeMembers::Batch(batch) => {
@@ -418,6 +758,7 @@ mod tests {
     use crate::ChangeMembers;
     use crate::Membership;
     use crate::errors::ChangeMembershipError;
+    use crate::errors::DuplicateNode;
     use crate::errors::EmptyMembership;
     use crate::errors::LearnerNotFound;
     use crate::errors::MembershipError;
@@ -428,16 +769,44 @@ mod tests {
         let m = Membership::<u64, ()> {
             configs: vec![btreeset! {1,2}],
             nodes: btreemap! {1=>()},
+            witnesses: Default::default(),
+            log_only: Default::default(),
+            failure_domains: Default::default(),
         };
         assert_eq!(Err(2), m.ensure_voter_nodes());
         Ok(())
     }
 
+    #[test]
+    fn test_membership_find_duplicate_node() -> anyhow::Result<()> {
+        let m = Membership::<u64, u64> {
+            configs: vec![btreeset! {1,2,3}],
+            nodes: btreemap! {1=>10,2=>20,3=>30},
+            witnesses: Default::default(),
+            log_only: Default::default(),
+            failure_domains: Default::default(),
+        };
+        assert_eq!(Ok(()), m.find_duplicate_node());
+
+        let m = Membership::<u64, u64> {
+            configs: vec![btreeset! {1,2,3}],
+            nodes: btreemap! {1=>10,2=>20,3=>10},
+            witnesses: Default::default(),
+            log_only: Default::default(),
+            failure_domains: Default::default(),
+        };
+        assert_eq!(Err(DuplicateNode { node_id: 3, other: 1 }), m.find_duplicate_node());
+        Ok(())
+    }
+
     #[test]
     fn test_membership_change() -> anyhow::Result<()> {
         let m = || Membership::<u64, ()> {
             configs: vec![btreeset! {1,2}],
             nodes: btreemap! {1=>(),2=>(),3=>()},
+            witnesses: Default::default(),
+            log_only: Default::default(),
+            failure_domains: Default::default(),
         };
 
         // Add: no such learner
@@ -456,7 +825,10 @@ mod tests {
             assert_eq!(
                 Ok(Membership::<u64, ()> {
                     configs: vec![btreeset! {1,2}, btreeset! {1,2,3}],
-                    nodes: btreemap! {1=>(),2=>(),3=>()}
+                    nodes: btreemap! {1=>(),2=>(),3=>()},
+                    witnesses: Default::default(),
+                    log_only: Default::default(),
+                    failure_domains: Default::default(),
                 }),
                 res
             );
@@ -468,7 +840,10 @@ mod tests {
             assert_eq!(
                 Ok(Membership::<u64, ()> {
                     configs: vec![btreeset! {1,2}, btreeset! {1,2,5}],
-                    nodes: btreemap! {1=>(),2=>(),3=>(),5=>()}
+                    nodes: btreemap! {1=>(),2=>(),3=>(),5=>()},
+                    witnesses: Default::default(),
+                    log_only: Default::default(),
+                    failure_domains: Default::default(),
                 }),
                 res
             );
@@ -480,7 +855,10 @@ mod tests {
             assert_eq!(
                 Ok(Membership::<u64, ()> {
                     configs: vec![btreeset! {1,2}],
-                    nodes: btreemap! {1=>(),2=>(),3=>()}
+                    nodes: btreemap! {1=>(),2=>(),3=>()},
+                    witnesses: Default::default(),
+                    log_only: Default::default(),
+                    failure_domains: Default::default(),
                 }),
                 res
             );
@@ -498,7 +876,10 @@ mod tests {
             assert_eq!(
                 Ok(Membership::<u64, ()> {
                     configs: vec![btreeset! {1,2}, btreeset! {2}],
-                    nodes: btreemap! {1=>(),2=>(),3=>()}
+                    nodes: btreemap! {1=>(),2=>(),3=>()},
+                    witnesses: Default::default(),
+                    log_only: Default::default(),
+                    failure_domains: Default::default(),
                 }),
                 res
             );
@@ -510,7 +891,10 @@ mod tests {
             assert_eq!(
                 Ok(Membership::<u64, ()> {
                     configs: vec![btreeset! {1,2}, btreeset! {2}],
-                    nodes: btreemap! {1=>(),2=>(),3=>()}
+                    nodes: btreemap! {1=>(),2=>(),3=>()},
+                    witnesses: Default::default(),
+                    log_only: Default::default(),
+                    failure_domains: Default::default(),
                 }),
                 res
             );
@@ -521,12 +905,18 @@ mod tests {
             let mem = Membership::<u64, ()> {
                 configs: vec![btreeset! {1,2}, btreeset! {2}],
                 nodes: btreemap! {1=>(),2=>(),3=>()},
+                witnesses: Default::default(),
+                log_only: Default::default(),
+                failure_domains: Default::default(),
             };
             let res = mem.change(ChangeMembers::RemoveVoters(btreeset! {1}), false);
             assert_eq!(
                 Ok(Membership::<u64, ()> {
                     configs: vec![btreeset! {2}],
-                    nodes: btreemap! {2=>(),3=>()}
+                    nodes: btreemap! {2=>(),3=>()},
+                    witnesses: Default::default(),
+                    log_only: Default::default(),
+                    failure_domains: Default::default(),
                 }),
                 res
             );
@@ -538,7 +928,10 @@ mod tests {
             assert_eq!(
                 Ok(Membership::<u64, ()> {
                     configs: vec![btreeset! {1,2}, btreeset! {2}],
-                    nodes: btreemap! {1=>(),2=>(),3=>()}
+                    nodes: btreemap! {1=>(),2=>(),3=>()},
+                    witnesses: Default::default(),
+                    log_only: Default::default(),
+                    failure_domains: Default::default(),
                 }),
                 res
             );
@@ -550,7 +943,10 @@ mod tests {
             assert_eq!(
                 Ok(Membership::<u64, ()> {
                     configs: vec![btreeset! {1,2}],
-                    nodes: btreemap! {1=>(),2=>(),3=>()}
+                    nodes: btreemap! {1=>(),2=>(),3=>()},
+                    witnesses: Default::default(),
+                    log_only: Default::default(),
+                    failure_domains: Default::default(),
                 }),
                 res
             );
@@ -562,7 +958,10 @@ mod tests {
             assert_eq!(
                 Ok(Membership::<u64, ()> {
                     configs: vec![btreeset! {1,2}],
-                    nodes: btreemap! {1=>(),2=>(),3=>()}
+                    nodes: btreemap! {1=>(),2=>(),3=>()},
+                    witnesses: Default::default(),
+                    log_only: Default::default(),
+                    failure_domains: Default::default(),
                 }),
                 res
             );
@@ -574,7 +973,10 @@ mod tests {
             assert_eq!(
                 Ok(Membership::<u64, ()> {
                     configs: vec![btreeset! {1,2}],
-                    nodes: btreemap! {1=>(),2=>(),3=>(), 4=>()}
+                    nodes: btreemap! {1=>(),2=>(),3=>(), 4=>()},
+                    witnesses: Default::default(),
+                    log_only: Default::default(),
+                    failure_domains: Default::default(),
                 }),
                 res
             );
@@ -585,13 +987,19 @@ mod tests {
             let m = || Membership::<u64, u64> {
                 configs: vec![btreeset! {1,2}],
                 nodes: btreemap! {1=>1,2=>2,3=>3},
+                witnesses: Default::default(),
+                log_only: Default::default(),
+                failure_domains: Default::default(),
             };
 
             let res = m().change(ChangeMembers::SetNodes(btreemap! {3=>30, 4=>40}), false);
             assert_eq!(
                 Ok(Membership::<u64, u64> {
                     configs: vec![btreeset! {1,2}],
-                    nodes: btreemap! {1=>1,2=>2,3=>30, 4=>40}
+                    nodes: btreemap! {1=>1,2=>2,3=>30, 4=>40},
+                    witnesses: Default::default(),
+                    log_only: Default::default(),
+                    failure_domains: Default::default(),
                 }),
                 res
             );
@@ -613,7 +1021,10 @@ mod tests {
             assert_eq!(
                 Ok(Membership::<u64, ()> {
                     configs: vec![btreeset! {1,2}],
-                    nodes: btreemap! {1=>(),2=>()}
+                    nodes: btreemap! {1=>(),2=>()},
+                    witnesses: Default::default(),
+                    log_only: Default::default(),
+                    failure_domains: Default::default(),
                 }),
                 res
             );
@@ -625,7 +1036,10 @@ mod tests {
             assert_eq!(
                 Ok(Membership::<u64, ()> {
                     configs: vec![btreeset! {1,2}],
-                    nodes: btreemap! {1=>(),2=>(),4=>()}
+                    nodes: btreemap! {1=>(),2=>(),4=>()},
+                    witnesses: Default::default(),
+                    log_only: Default::default(),
+                    failure_domains: Default::default(),
                 }),
                 res
             );
@@ -634,6 +1048,39 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_membership_remove_learners() -> anyhow::Result<()> {
+        let joint = Membership::<u64, ()> {
+            configs: vec![btreeset! {1,2}, btreeset! {2,3}],
+            nodes: btreemap! {1=>(),2=>(),3=>(),4=>(),5=>()},
+            witnesses: Default::default(),
+            log_only: Default::default(),
+            failure_domains: Default::default(),
+        };
+
+        // The joint config is kept as it is
+        let res = joint.clone().remove_learners(&btreeset! {4})?;
+        assert_eq!(vec![btreeset! {1,2}, btreeset! {2,3}], *res.get_joint_config());
+        assert_eq!(btreeset! {5}, res.learner_ids().collect());
+        assert!(res.is_learner_removal_of(&joint));
+        assert!(!joint.is_learner_removal_of(&res), "adding a learner");
+
+        // A voter can not be removed
+        let res = joint.clone().remove_learners(&btreeset! {1, 4});
+        let err: ChangeMembershipErrorOf<crate::engine::testing::UTConfig> = res.unwrap_err().into();
+        assert_eq!(
+            ChangeMembershipError::LearnerNotFound(LearnerNotFound { node_id: 1 }),
+            err
+        );
+
+        // As part of a change, it flattens the joint config like `RemoveNodes`
+        let res = joint.clone().change(ChangeMembers::RemoveLearners(btreeset! {4}), false)?;
+        assert_eq!(vec![btreeset! {2,3}], *res.get_joint_config());
+        assert!(!res.is_learner_removal_of(&joint));
+
+        Ok(())
+    }
+
     /// Test membership change described by a batch operation.
     ///
     /// The batch operations add one voter and remove another.
@@ -643,6 +1090,9 @@ mod tests {
         let m = || Membership::<u64, ()> {
             configs: vec![btreeset! {1,2}],
             nodes: btreemap! {1=>(),2=>(),3=>()},
+            witnesses: Default::default(),
+            log_only: Default::default(),
+            failure_domains: Default::default(),
         };
 
         let rm_2_add_5 = || {
@@ -656,14 +1106,20 @@ mod tests {
 
         assert_eq!(step1, Membership::<u64, ()> {
             configs: vec![btreeset! {1,2}, btreeset! {1,5}],
-            nodes: btreemap! {1=>(),2=>(),3=>(),5=>()}
+            nodes: btreemap! {1=>(),2=>(),3=>(),5=>()},
+            witnesses: Default::default(),
+            log_only: Default::default(),
+            failure_domains: Default::default(),
         });
 
         let step2 = step1.change(rm_2_add_5(), false)?;
 
         assert_eq!(step2, Membership::<u64, ()> {
             configs: vec![btreeset! {1,5}],
-            nodes: btreemap! {1=>(),3=>(), 5=>()}
+            nodes: btreemap! {1=>(),3=>(), 5=>()},
+            witnesses: Default::default(),
+            log_only: Default::default(),
+            failure_domains: Default::default(),
         });
 
         Ok(())
@@ -4,6 +4,62 @@ use crate::Membership;
 use crate::node::Node;
 use crate::node::NodeId;
 use crate::quorum::QuorumSet;
+use crate::quorum::domain_majority::DomainMajority;
+
+/// The quorum set of one config in a [`Membership`], selected by [`Membership::to_quorum_set()`].
+#[derive(Debug, Clone)]
+pub(crate) enum ConfigQuorumSet<'a, NID> {
+    /// A majority of the voters.
+    Majority(&'a BTreeSet<NID>),
+
+    /// A majority of the voters in a majority of the failure domains.
+    DomainMajority(DomainMajority<'a, NID>),
+}
+
+impl<NID> QuorumSet for ConfigQuorumSet<'_, NID>
+where NID: NodeId
+{
+    type Id = NID;
+    type Iter = std::collections::btree_set::IntoIter<NID>;
+
+    fn is_quorum<'a, I>(&self, ids: I) -> bool
+    where I: Iterator<Item = &'a NID> + Clone {
+        match self {
+            ConfigQuorumSet::Majority(config) => config.is_quorum(ids),
+            ConfigQuorumSet::DomainMajority(qs) => qs.is_quorum(ids),
+        }
+    }
+
+    fn ids(&self) -> Self::Iter {
+        match self {
+            ConfigQuorumSet::Majority(config) => config.ids(),
+            ConfigQuorumSet::DomainMajority(qs) => qs.ids(),
+        }
+    }
+}
+
+impl<NID, N> Membership<NID, N>
+where
+    NID: NodeId,
+    N: Node,
+{
+    /// Returns the quorum set of every config in the joint config.
+    ///
+    /// A node set is a quorum of the membership iff it is a quorum of every one of them. The
+    /// quorum set of a config is a majority of its voters, or, if the membership is domain-aware,
+    /// a majority of its voters in a majority of their failure domains.
+    pub(crate) fn to_quorum_set(&self) -> impl Iterator<Item = ConfigQuorumSet<'_, NID>> + Clone {
+        let domain_aware = self.is_domain_aware();
+
+        self.configs.iter().map(move |config| {
+            if domain_aware {
+                ConfigQuorumSet::DomainMajority(DomainMajority::new(config, &self.failure_domains))
+            } else {
+                ConfigQuorumSet::Majority(config)
+            }
+        })
+    }
+}
 
 impl<NID, N> QuorumSet for Membership<NID, N>
 where
@@ -15,8 +71,8 @@ where
 
     fn is_quorum<'a, I>(&self, ids: I) -> bool
     where I: Iterator<Item = &'a NID> + Clone {
-        for config in &self.configs {
-            if !config.is_quorum(ids.clone()) {
+        for qs in self.to_quorum_set() {
+            if !qs.is_quorum(ids.clone()) {
                 return false;
             }
         }
@@ -50,6 +106,9 @@ mod tests {
             let m12345 = Membership::<u64, ()> {
                 configs: vec![btreeset! {1,2,3,4,5}],
                 nodes: btreemap! {},
+                witnesses: Default::default(),
+                log_only: Default::default(),
+                failure_domains: Default::default(),
             };
 
             assert!(!m12345.is_quorum([0].iter()));
@@ -65,6 +124,9 @@ mod tests {
             let m12345_678 = Membership::<u64, ()> {
                 configs: vec![btreeset! {1,2,3,4,5}, btreeset! {6,7,8}],
                 nodes: btreemap! {},
+                witnesses: Default::default(),
+                log_only: Default::default(),
+                failure_domains: Default::default(),
             };
 
             assert!(!m12345_678.is_quorum([0].iter()));
@@ -78,11 +140,44 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_membership_is_quorum_domain_aware() -> anyhow::Result<()> {
+        let m = Membership::<u64, ()> {
+            configs: vec![btreeset! {1,2,3,4,5}, btreeset! {1,2,3,4,6}],
+            nodes: btreemap! {1=>(),2=>(),3=>(),4=>(),5=>(),6=>()},
+            witnesses: Default::default(),
+            log_only: Default::default(),
+            failure_domains: btreemap! {
+                1 => s("a"), 2 => s("a"),
+                3 => s("b"), 4 => s("b"),
+                5 => s("c"), 6 => s("d"),
+            },
+        };
+
+        // A majority of the nodes of both configs, but of only domain `a`.
+        assert!(!m.is_quorum([1, 2, 3].iter()));
+
+        // A majority of domain `a` and `c` in the first config, but only `a` in the second.
+        assert!(!m.is_quorum([1, 2, 5].iter()));
+
+        assert!(m.is_quorum([1, 2, 5, 6].iter()));
+        assert!(m.is_quorum([1, 2, 3, 4].iter()));
+
+        Ok(())
+    }
+
+    fn s(x: &str) -> String {
+        x.to_string()
+    }
+
     #[test]
     fn test_membership_ids() -> anyhow::Result<()> {
         let m12345_678 = Membership::<u64, ()> {
             configs: vec![btreeset! {1,2,3,4,5}, btreeset! {4,5,6,7,8}],
             nodes: btreemap! {},
+            witnesses: Default::default(),
+            log_only: Default::default(),
+            failure_domains: Default::default(),
         };
 
         assert_eq!(btreeset! {1,2,3,4,5,6,7,8}, m12345_678.ids().collect());
@@ -0,0 +1,104 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use openraft_macros::since;
+
+use crate::Membership;
+use crate::errors::MembershipError;
+use crate::errors::PlacementError;
+use crate::membership::IntoNodes;
+use crate::node::Node;
+use crate::node::NodeId;
+use crate::node::NodePlacement;
+
+/// Construction and queries of a membership whose nodes implement [`NodePlacement`].
+impl<NID, N> Membership<NID, N>
+where
+    NID: NodeId,
+    N: Node + NodePlacement,
+{
+    /// Create a new Membership the same as [`Self::new()`], and validate the placement of every
+    /// node.
+    ///
+    /// A node must have a non-empty region and zone, and its tags must be non-empty and contain
+    /// no whitespace.
+    #[since(version = "0.10.0")]
+    pub fn new_placed<T>(config: Vec<BTreeSet<NID>>, nodes: T) -> Result<Self, PlacementError<NID>>
+    where T: IntoNodes<NID, N> {
+        let m = Self::new(config, nodes)?;
+        m.ensure_placement()?;
+        Ok(m)
+    }
+
+    /// Create a new domain-aware Membership the same as [`Self::new_placed()`]: a quorum of it is
+    /// a majority of the voters in a majority of the failure domains, instead of a majority of the
+    /// voters.
+    ///
+    /// The failure domain of a node is [`NodePlacement::failure_domain()`], the zone by default.
+    /// Thus a cluster that spans three zones keeps a quorum if a whole zone fails, even if it
+    /// holds more nodes than the other two.
+    ///
+    /// The voters must span at least three failure domains. A cluster initialized with it by
+    /// [`Raft::initialize_with_membership()`] stays domain-aware: the failure domains of new nodes
+    /// are set by [`ChangeMembers::SetFailureDomains`].
+    ///
+    /// [`Raft::initialize_with_membership()`]: crate::Raft::initialize_with_membership
+    /// [`ChangeMembers::SetFailureDomains`]: crate::ChangeMembers::SetFailureDomains
+    #[since(version = "0.10.0")]
+    pub fn new_domain_aware<T>(config: Vec<BTreeSet<NID>>, nodes: T) -> Result<Self, PlacementError<NID>>
+    where T: IntoNodes<NID, N> {
+        let mut m = Self::new_placed(config, nodes)?;
+
+        m.failure_domains = m.nodes.iter().map(|(id, n)| (id.clone(), n.failure_domain().to_string())).collect();
+        m.ensure_failure_domains().map_err(MembershipError::from)?;
+
+        Ok(m)
+    }
+
+    /// Returns an Iterator of the ids of all nodes(voters and learners) in the region `region`.
+    #[since(version = "0.10.0")]
+    pub fn node_ids_in_region<'a>(&'a self, region: &'a str) -> impl Iterator<Item = NID> + 'a {
+        self.nodes.iter().filter(move |(_, n)| n.region() == region).map(|(id, _)| id.clone())
+    }
+
+    /// Returns the ids of all nodes(voters and learners) that have the tag `tag`.
+    #[since(version = "0.10.0")]
+    pub fn node_ids_with_tag<'a>(&'a self, tag: &'a str) -> impl Iterator<Item = NID> + 'a {
+        self.nodes.iter().filter(move |(_, n)| n.has_tag(tag)).map(|(id, _)| id.clone())
+    }
+
+    /// Returns the voter ids grouped by region.
+    #[since(version = "0.10.0")]
+    pub fn voter_ids_by_region(&self) -> BTreeMap<String, BTreeSet<NID>> {
+        let mut res: BTreeMap<String, BTreeSet<NID>> = BTreeMap::new();
+
+        for id in self.voter_ids() {
+            if let Some(n) = self.nodes.get(&id) {
+                res.entry(n.region().to_string()).or_default().insert(id);
+            }
+        }
+
+        res
+    }
+
+    fn ensure_placement(&self) -> Result<(), PlacementError<NID>> {
+        for (id, n) in self.nodes.iter() {
+            if n.region().is_empty() {
+                return Err(PlacementError::EmptyRegion { node_id: id.clone() });
+            }
+
+            if n.zone().is_empty() {
+                return Err(PlacementError::EmptyZone { node_id: id.clone() });
+            }
+
+            if let Some(tag) = n.tags().find(|t| t.is_empty() || t.contains(char::is_whitespace)) {
+                return Err(PlacementError::InvalidTag {
+                    node_id: id.clone(),
+                    tag: tag.to_string(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -7,9 +7,14 @@ use maplit::btreeset;
 
 use crate::ChangeMembers;
 use crate::Membership;
+use crate::NodeRole;
+use crate::PlacedNode;
+use crate::errors::FailureDomainError;
 use crate::errors::MembershipError;
 use crate::errors::NodeNotFound;
 use crate::errors::Operation;
+use crate::errors::PlacementError;
+use crate::quorum::QuorumSet;
 
 #[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -352,3 +357,210 @@ fn test_membership_next_coherent_retain_false_keeps_existing_learners() -> anyho
 
     Ok(())
 }
+
+#[test]
+fn test_membership_new_placed() -> anyhow::Result<()> {
+    let node = |region: &str, zone: &str| PlacedNode::new("addr", region, zone);
+
+    let m = Membership::<u64, PlacedNode>::new_placed(vec![btreeset! {1,2,3}], btreemap! {
+        1 => node("r1", "z1").with_tag("never-lead"),
+        2 => node("r1", "z2"),
+        3 => node("r2", "z1"),
+        4 => node("r2", "z2").with_tag("never-lead"),
+    })?;
+
+    assert_eq!(vec![1, 2], m.node_ids_in_region("r1").collect::<Vec<_>>());
+    assert_eq!(vec![3, 4], m.node_ids_in_region("r2").collect::<Vec<_>>());
+    assert_eq!(vec![1, 4], m.node_ids_with_tag("never-lead").collect::<Vec<_>>());
+    assert_eq!(
+        btreemap! {"r1".to_string() => btreeset! {1,2}, "r2".to_string() => btreeset! {3}},
+        m.voter_ids_by_region()
+    );
+
+    let res = Membership::<u64, PlacedNode>::new_placed(vec![btreeset! {1,2}], btreemap! {1 => node("r1", "z1")});
+    assert_eq!(
+        Err(PlacementError::Membership(MembershipError::NodeNotFound(
+            NodeNotFound::new(2, Operation::None)
+        ))),
+        res
+    );
+
+    let res = Membership::<u64, PlacedNode>::new_placed(vec![btreeset! {1}], btreemap! {1 => node("", "z1")});
+    assert_eq!(Err(PlacementError::EmptyRegion { node_id: 1 }), res);
+
+    let res = Membership::<u64, PlacedNode>::new_placed(vec![btreeset! {1}], btreemap! {1 => node("r1", "")});
+    assert_eq!(Err(PlacementError::EmptyZone { node_id: 1 }), res);
+
+    let res = Membership::<u64, PlacedNode>::new_placed(vec![btreeset! {1}], btreemap! {
+        1 => node("r1", "z1").with_tag("a b"),
+    });
+    assert_eq!(
+        Err(PlacementError::InvalidTag {
+            node_id: 1,
+            tag: "a b".to_string()
+        }),
+        res
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_membership_witnesses() -> anyhow::Result<()> {
+    let m = Membership::<u64, ()>::new_with_witnesses(
+        vec![btreeset! {1,2,3}],
+        btreemap! {1=>(),2=>(),3=>(),4=>()},
+        btreeset! {3},
+    )?;
+
+    assert_eq!(vec![3], m.witness_ids().collect::<Vec<_>>());
+    assert_eq!(Some(NodeRole::Voter), m.node_role(&1));
+    assert_eq!(Some(NodeRole::Witness), m.node_role(&3));
+    assert_eq!(Some(NodeRole::Learner), m.node_role(&4));
+    assert_eq!(None, m.node_role(&5));
+    assert_eq!(
+        "{voters:[{1:(),2:(),3:()}], learners:[4:()], witnesses:[3]}",
+        m.to_string()
+    );
+
+    let res = Membership::<u64, ()>::new_with_witnesses(vec![btreeset! {1}], btreemap! {1=>()}, btreeset! {2});
+    assert_eq!(
+        Err(MembershipError::NodeNotFound(NodeNotFound::new(2, Operation::None))),
+        res
+    );
+
+    // AddWitnesses adds new nodes as witness voters, but does not turn an existing node into a
+    // witness.
+    let m2 = m.clone().change(ChangeMembers::AddWitnesses(btreemap! {4=>(), 5=>()}), true)?;
+    assert_eq!(vec![3, 5], m2.witness_ids().collect::<Vec<_>>());
+    assert_eq!(&vec![btreeset! {1,2,3}, btreeset! {1,2,3,4,5}], m2.get_joint_config());
+
+    // A removed witness is forgotten.
+    let m3 = m.clone().change(ChangeMembers::RemoveVoters(btreeset! {3}), false)?;
+    let m3 = m3.change(ChangeMembers::AddVoterIds(btreeset! {}), false)?;
+    assert_eq!(Vec::<u64>::new(), m3.witness_ids().collect::<Vec<_>>());
+
+    // A witness retained as a learner is still a witness.
+    let m4 = m.clone().change(ChangeMembers::RemoveVoters(btreeset! {3}), true)?;
+    let m4 = m4.change(ChangeMembers::AddVoterIds(btreeset! {}), true)?;
+    assert_eq!(vec![3], m4.witness_ids().collect::<Vec<_>>());
+    assert_eq!(Some(NodeRole::Learner), m4.node_role(&3));
+
+    let m5 = m4.change(ChangeMembers::RemoveNodes(btreeset! {3}), true)?;
+    assert_eq!(Vec::<u64>::new(), m5.witness_ids().collect::<Vec<_>>());
+
+    Ok(())
+}
+
+#[test]
+fn test_membership_log_only() -> anyhow::Result<()> {
+    let m = Membership::<u64, ()>::new_with_log_only(
+        vec![btreeset! {1,2,3}],
+        btreemap! {1=>(),2=>(),3=>(),4=>()},
+        btreeset! {3},
+    )?;
+
+    assert_eq!(vec![3], m.log_only_ids().collect::<Vec<_>>());
+    assert_eq!(Some(NodeRole::Voter), m.node_role(&1));
+    assert_eq!(Some(NodeRole::LogOnly), m.node_role(&3));
+    assert_eq!(Some(NodeRole::Learner), m.node_role(&4));
+    assert!(m.is_leader_eligible(&1));
+    assert!(!m.is_leader_eligible(&3));
+    assert_eq!(
+        "{voters:[{1:(),2:(),3:()}], learners:[4:()], log_only:[3]}",
+        m.to_string()
+    );
+
+    let res = Membership::<u64, ()>::new_with_log_only(vec![btreeset! {1}], btreemap! {1=>()}, btreeset! {2});
+    assert_eq!(
+        Err(MembershipError::NodeNotFound(NodeNotFound::new(2, Operation::None))),
+        res
+    );
+
+    // AddLogOnly adds new nodes as log-only voters, but does not turn an existing node into a
+    // log-only node.
+    let m2 = m.clone().change(ChangeMembers::AddLogOnly(btreemap! {4=>(), 5=>()}), true)?;
+    assert_eq!(vec![3, 5], m2.log_only_ids().collect::<Vec<_>>());
+    assert_eq!(&vec![btreeset! {1,2,3}, btreeset! {1,2,3,4,5}], m2.get_joint_config());
+
+    // A removed log-only node is forgotten.
+    let m3 = m.clone().change(ChangeMembers::RemoveVoters(btreeset! {3}), false)?;
+    let m3 = m3.change(ChangeMembers::AddVoterIds(btreeset! {}), false)?;
+    assert_eq!(Vec::<u64>::new(), m3.log_only_ids().collect::<Vec<_>>());
+
+    Ok(())
+}
+
+#[test]
+fn test_membership_new_domain_aware() -> anyhow::Result<()> {
+    let node = |zone: &str| PlacedNode::new("addr", "r1", zone);
+
+    let m = Membership::<u64, PlacedNode>::new_domain_aware(vec![btreeset! {1,2,3,4}], btreemap! {
+        1 => node("z1"),
+        2 => node("z1"),
+        3 => node("z2"),
+        4 => node("z3"),
+    })?;
+
+    assert!(m.is_domain_aware());
+    assert_eq!(Some("z1"), m.failure_domain(&1));
+    assert_eq!(None, m.failure_domain(&5));
+
+    // Zone z1 is a majority of the nodes, but not of the zones.
+    assert!(!m.is_quorum([1, 2].iter()));
+    assert!(m.is_quorum([1, 2, 3].iter()));
+    assert!(m.is_quorum([3, 4].iter()));
+
+    let res = Membership::<u64, PlacedNode>::new_domain_aware(vec![btreeset! {1,2,3}], btreemap! {
+        1 => node("z1"),
+        2 => node("z1"),
+        3 => node("z2"),
+    });
+    assert_eq!(
+        Err(PlacementError::Membership(MembershipError::FailureDomain(
+            FailureDomainError::TooFewDomains { domains: 2, min: 3 }
+        ))),
+        res
+    );
+
+    // A new voter without a failure domain is rejected.
+    let res = m.clone().change(ChangeMembers::AddVoters(btreemap! {5 => node("z3")}), true);
+    assert_eq!(
+        Err(MembershipError::FailureDomain(FailureDomainError::Missing {
+            node_id: 5
+        })),
+        res
+    );
+
+    // The failure domain of an existing node is not changed.
+    let m2 = m.clone().change(
+        ChangeMembers::Batch(vec![
+            ChangeMembers::AddVoters(btreemap! {5 => node("z3")}),
+            ChangeMembers::SetFailureDomains(btreemap! {1 => "z9".to_string(), 5 => "z3".to_string()}),
+        ]),
+        true,
+    )?;
+    assert_eq!(Some("z1"), m2.failure_domain(&1));
+    assert_eq!(Some("z3"), m2.failure_domain(&5));
+    assert_eq!(&vec![btreeset! {1,2,3,4}, btreeset! {1,2,3,4,5}], m2.get_joint_config());
+
+    // A removed node is forgotten.
+    let m3 = m2.change(ChangeMembers::AddVoterIds(btreeset! {}), true)?;
+    let m3 = m3.change(ChangeMembers::RemoveVoters(btreeset! {5}), false)?;
+    let m3 = m3.change(ChangeMembers::AddVoterIds(btreeset! {}), false)?;
+    assert_eq!(None, m3.failure_domain(&5));
+
+    // A membership that is not domain-aware can not become domain-aware.
+    let m4 = Membership::<u64, PlacedNode>::new_placed(vec![btreeset! {1}], btreemap! {1 => node("z1")})?;
+    assert!(!m4.is_domain_aware());
+    let res = m4.change(
+        ChangeMembers::SetFailureDomains(btreemap! {1 => "z1".to_string()}),
+        true,
+    );
+    assert_eq!(
+        Err(MembershipError::FailureDomain(FailureDomainError::NotDomainAware)),
+        res
+    );
+
+    Ok(())
+}
@@ -7,6 +7,7 @@
 //! - [`Membership`] - Cluster membership configuration (voters and learners)
 //! - [`StoredMembership`] - Membership state stored in state machine
 //! - [`IntoNodes`] - Trait for converting node sets with metadata
+//! - [`QuorumPolicy`] - Trait for replacing majority quorums with flexible quorums
 //!
 //! ## Overview
 //!
@@ -24,6 +25,9 @@ mod into_nodes;
 #[allow(clippy::module_inception)]
 mod membership;
 mod membership_impl_quorum_set;
+mod membership_placement;
+mod node_role;
+mod quorum_policy;
 mod stored_membership;
 
 #[cfg(feature = "bench")]
@@ -37,4 +41,9 @@ mod stored_membership_test;
 
 pub use into_nodes::IntoNodes;
 pub use membership::Membership;
+pub use node_role::NodeRole;
+pub use quorum_policy::MAX_CHECKED_VOTERS;
+pub use quorum_policy::QuorumKind;
+pub use quorum_policy::QuorumPolicy;
+pub use quorum_policy::check_quorum_policy;
 pub use stored_membership::StoredMembership;
@@ -0,0 +1,60 @@
+use std::fmt;
+
+use openraft_macros::since;
+
+/// The role of a node in a [`Membership`](crate::Membership).
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum NodeRole {
+    /// A voter that stores the full log and state machine.
+    Voter,
+
+    /// A node that receives logs but does not vote.
+    Learner,
+
+    /// A voter that only stores log metadata.
+    ///
+    /// The leader replicates a membership entry to a witness as is, and every other entry as a
+    /// blank entry with the same log id. A witness grants votes and acknowledges logs as any
+    /// other voter, thus it counts in election and commit quorums, but never becomes a
+    /// candidate, since it does not have the data to serve as leader.
+    ///
+    /// A witness never applies entries, never builds a snapshot, and rejects local reads. A
+    /// witness that falls behind the leader's purged logs receives only the snapshot metadata, via
+    /// [`RaftNetworkV2::snapshot_meta`], which its state machine records with
+    /// [`RaftStateMachine::install_snapshot_meta`].
+    ///
+    /// A witness trusts the leader to send it only metadata: a former witness must be wiped
+    /// before it rejoins the cluster in another role.
+    ///
+    /// [`RaftNetworkV2::snapshot_meta`]: crate::network::RaftNetworkV2::snapshot_meta
+    /// [`RaftStateMachine::install_snapshot_meta`]: crate::storage::RaftStateMachine::install_snapshot_meta
+    Witness,
+
+    /// A voter that stores the full log but has no state machine.
+    ///
+    /// The leader replicates every entry to a log-only node as is. A log-only node persists and
+    /// acknowledges logs as any other voter, thus it counts in election and commit quorums, but
+    /// never applies entries, never builds a snapshot and never becomes a candidate. It is
+    /// cheaper than a full replica and, unlike a witness, keeps a durable copy of every committed
+    /// entry. It does not run its state machine worker, and its
+    /// [`RaftMetrics::last_applied`](crate::RaftMetrics::last_applied) stays where its state
+    /// machine is: the logs committed meanwhile are applied if it is re-added as a voter.
+    ///
+    /// A leader never sends a snapshot to a log-only node: the leader does not purge the logs a
+    /// log-only voter has not replicated yet, and adding a log-only node fails with
+    /// [`LogOnlyLogsPurged`](crate::errors::LogOnlyLogsPurged) once the leader has purged logs.
+    LogOnly,
+}
+
+impl fmt::Display for NodeRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NodeRole::Voter => write!(f, "voter"),
+            NodeRole::Learner => write!(f, "learner"),
+            NodeRole::Witness => write!(f, "witness"),
+            NodeRole::LogOnly => write!(f, "log-only"),
+        }
+    }
+}
@@ -0,0 +1,249 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
+use openraft_macros::since;
+
+use crate::Membership;
+use crate::RaftTypeConfig;
+use crate::errors::QuorumPolicyError;
+
+/// The maximum number of voters in a config whose quorums [`check_quorum_policy()`] checks.
+///
+/// Every subset of the voters is checked, thus the cost doubles with each voter.
+pub const MAX_CHECKED_VOTERS: usize = 16;
+
+/// What a quorum is checked for by a [`QuorumPolicy`].
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum QuorumKind {
+    /// The nodes that must grant a vote, or a pre-vote, to elect a leader.
+    Election,
+
+    /// The nodes that must accept a log entry to commit it. It is also the quorum a leader has to
+    /// reach to confirm its leadership for a read, and to extend its lease.
+    Commit,
+}
+
+impl fmt::Display for QuorumKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QuorumKind::Election => write!(f, "Election"),
+            QuorumKind::Commit => write!(f, "Commit"),
+        }
+    }
+}
+
+/// Decides which sets of voters are a quorum, in place of a majority of the voters.
+///
+/// Create a node with [`Raft::new_with_quorum_policy()`] to use flexible quorums, e.g., a grid in
+/// which an election quorum is a full row and a commit quorum is a full column, or all the voters
+/// in the primary data center plus one remote voter to commit, so that a commit does not wait for
+/// more than one cross-region round trip.
+///
+/// Without a policy, a quorum is a majority of the voters, or, if the membership is domain-aware,
+/// a majority of the voters in a majority of the failure domains.
+///
+/// # Safety
+///
+/// The policy is only safe if, for every config:
+/// - every election quorum intersects every commit quorum, so that a new leader sees every
+///   committed log;
+/// - every two election quorums intersect, so that at most one leader is elected in a term.
+///
+/// Openraft checks it with [`check_quorum_policy()`] for the stored membership when the node is
+/// created, and for every membership it initializes the cluster with or proposes, and rejects a
+/// policy or a membership that breaks it. The check assumes a set of voters that contains a quorum
+/// is also a quorum, which every policy must ensure.
+///
+/// The policy must be the same on every node. It can not be replaced on a running node: to change
+/// it, restart every node with the new policy, which is only safe if the quorums of the old and
+/// the new policy also intersect as above.
+///
+/// During a joint membership change, a set of voters is a quorum if it is a quorum of every
+/// config, as with majorities.
+///
+/// [`Raft::durability_report()`] still assumes majority quorums.
+///
+/// # Examples
+///
+/// ```ignore
+/// use openraft::membership::QuorumKind;
+/// use openraft::membership::QuorumPolicy;
+///
+/// /// Commit on all the voters in the primary data center and one more; elect with a majority
+/// /// of the remote voters and one voter in the primary data center.
+/// #[derive(Debug)]
+/// struct PrimaryDc {
+///     primary: BTreeSet<NodeId>,
+/// }
+///
+/// impl QuorumPolicy<TypeConfig> for PrimaryDc {
+///     fn is_quorum(
+///         &self,
+///         kind: QuorumKind,
+///         _membership: &Membership<NodeId, BasicNode>,
+///         voters: &BTreeSet<NodeId>,
+///         granted: &BTreeSet<NodeId>,
+///     ) -> bool {
+///         let (local, remote): (BTreeSet<_>, BTreeSet<_>) =
+///             voters.iter().partition(|id| self.primary.contains(id));
+///         let local_granted = local.iter().filter(|id| granted.contains(id)).count();
+///         let remote_granted = remote.iter().filter(|id| granted.contains(id)).count();
+///
+///         match kind {
+///             QuorumKind::Commit => local_granted == local.len() && remote_granted >= 1,
+///             QuorumKind::Election => local_granted >= 1 && remote_granted * 2 > remote.len(),
+///         }
+///     }
+/// }
+///
+/// let policy = Arc::new(PrimaryDc { primary });
+/// let raft = Raft::new_with_quorum_policy(id, config, network, log_store, sm, policy).await?;
+/// ```
+///
+/// [`Raft::new_with_quorum_policy()`]: crate::Raft::new_with_quorum_policy
+/// [`Raft::durability_report()`]: crate::Raft::durability_report
+#[since(version = "0.10.0")]
+pub trait QuorumPolicy<C>: Send + Sync + fmt::Debug
+where C: RaftTypeConfig
+{
+    /// Whether the `granted` nodes are a quorum of `voters` for `kind`.
+    ///
+    /// `voters` is one of the configs of `membership`, and `granted` may contain nodes that are
+    /// not in it. It is called from the RaftCore task, every time a node grants a vote or
+    /// acknowledges a log, and should return quickly.
+    fn is_quorum(
+        &self,
+        kind: QuorumKind,
+        membership: &Membership<C::NodeId, C::Node>,
+        voters: &BTreeSet<C::NodeId>,
+        granted: &BTreeSet<C::NodeId>,
+    ) -> bool;
+}
+
+/// Check that the quorums `policy` decides for every config of `membership` intersect as required
+/// by [`QuorumPolicy`].
+///
+/// For every split of the voters of a config into a set and its complement, it fails if the set is
+/// a commit quorum and the complement an election quorum, or if both are election quorums. A
+/// config with more than [`MAX_CHECKED_VOTERS`] voters is rejected.
+#[since(version = "0.10.0")]
+pub fn check_quorum_policy<C>(
+    policy: &dyn QuorumPolicy<C>,
+    membership: &Membership<C::NodeId, C::Node>,
+) -> Result<(), QuorumPolicyError<C::NodeId>>
+where
+    C: RaftTypeConfig,
+{
+    for voters in membership.get_joint_config() {
+        let ids = voters.iter().cloned().collect::<Vec<_>>();
+        if ids.len() > MAX_CHECKED_VOTERS {
+            return Err(QuorumPolicyError::TooManyVoters {
+                voters: ids.len(),
+                max: MAX_CHECKED_VOTERS,
+            });
+        }
+
+        let is_quorum = |kind, granted: &BTreeSet<C::NodeId>| policy.is_quorum(kind, membership, voters, granted);
+
+        for mask in 0..(1u32 << ids.len()) {
+            let mut set = BTreeSet::new();
+            let mut complement = BTreeSet::new();
+            for (i, id) in ids.iter().enumerate() {
+                if mask & (1 << i) != 0 {
+                    set.insert(id.clone());
+                } else {
+                    complement.insert(id.clone());
+                }
+            }
+
+            if !is_quorum(QuorumKind::Election, &complement) {
+                continue;
+            }
+
+            if is_quorum(QuorumKind::Commit, &set) {
+                return Err(QuorumPolicyError::ElectionCommitDisjoint {
+                    election: complement,
+                    commit: set,
+                });
+            }
+
+            if is_quorum(QuorumKind::Election, &set) {
+                return Err(QuorumPolicyError::ElectionsDisjoint { a: set, b: complement });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use maplit::btreeset;
+
+    use super::QuorumKind;
+    use super::QuorumPolicy;
+    use super::check_quorum_policy;
+    use crate::Membership;
+    use crate::engine::testing::UTConfig;
+    use crate::errors::QuorumPolicyError;
+
+    /// Commit on node 1; elect with `election_voters` of the voters.
+    #[derive(Debug)]
+    struct Primary {
+        election_voters: usize,
+    }
+
+    impl QuorumPolicy<UTConfig> for Primary {
+        fn is_quorum(
+            &self,
+            kind: QuorumKind,
+            _membership: &Membership<u64, ()>,
+            voters: &BTreeSet<u64>,
+            granted: &BTreeSet<u64>,
+        ) -> bool {
+            match kind {
+                QuorumKind::Commit => voters.contains(&1) && granted.contains(&1),
+                QuorumKind::Election => voters.intersection(granted).count() >= self.election_voters,
+            }
+        }
+    }
+
+    #[test]
+    fn test_check_quorum_policy() {
+        let m = Membership::<u64, ()>::new_with_defaults(vec![btreeset! {1,2,3}], []);
+
+        let p = Primary { election_voters: 3 };
+        assert_eq!(Ok(()), check_quorum_policy(&p, &m));
+
+        let p = Primary { election_voters: 2 };
+        assert_eq!(
+            Err(QuorumPolicyError::ElectionCommitDisjoint {
+                election: btreeset! {2,3},
+                commit: btreeset! {1},
+            }),
+            check_quorum_policy(&p, &m)
+        );
+
+        let p = Primary { election_voters: 1 };
+        assert!(check_quorum_policy(&p, &m).is_err());
+
+        // Every config of a joint membership is checked.
+        let m = Membership::<u64, ()>::new_with_defaults(vec![btreeset! {1}, btreeset! {1,2,3}], []);
+        let p = Primary { election_voters: 1 };
+        assert!(check_quorum_policy(&p, &m).is_err());
+    }
+
+    #[test]
+    fn test_check_quorum_policy_too_many_voters() {
+        let m = Membership::<u64, ()>::new_with_defaults(vec![(0..17).collect()], []);
+        let p = Primary { election_voters: 17 };
+        assert_eq!(
+            Err(QuorumPolicyError::TooManyVoters { voters: 17, max: 16 }),
+            check_quorum_policy(&p, &m)
+        );
+    }
+}
@@ -0,0 +1,47 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
+use display_more::DisplaySliceExt;
+use openraft_macros::since;
+
+use crate::Instant;
+use crate::RaftTypeConfig;
+use crate::type_config::alias::SerdeInstantOf;
+
+/// The election this node is running, if it is a candidate or a pre-candidate.
+///
+/// An election that stays here across many election timeouts, with the same voters granting,
+/// indicates that this node cannot reach or convince a quorum.
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct CandidateMetrics<C: RaftTypeConfig> {
+    /// The term being campaigned for.
+    pub term: C::Term,
+
+    /// Whether it is a Pre-Vote round, which does not change the term of any node, rather than a
+    /// real election.
+    pub pre_vote: bool,
+
+    /// The voters that have granted the vote so far, including this node.
+    pub granted: BTreeSet<C::NodeId>,
+
+    /// When this round of election started.
+    pub started_at: SerdeInstantOf<C>,
+}
+
+impl<C> fmt::Display for CandidateMetrics<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{term:{}, pre_vote:{}, granted:{}, started_at:{}({:?} ago)}}",
+            self.term,
+            self.pre_vote,
+            self.granted.iter().collect::<Vec<_>>().display(),
+            self.started_at,
+            self.started_at.elapsed()
+        )
+    }
+}
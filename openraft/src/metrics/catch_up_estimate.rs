@@ -0,0 +1,79 @@
+use std::fmt;
+use std::time::Duration;
+
+use display_more::DisplayOptionExt;
+use openraft_macros::since;
+
+/// An estimate of how long a node takes to catch up with the leader, returned by
+/// [`Raft::estimate_catchup()`](crate::Raft::estimate_catchup).
+///
+/// It is computed from the replication throughput the leader measured recently, and the duration
+/// of the last snapshot it sent. It is meant for planning, e.g., to schedule a maintenance
+/// window, and is only as accurate as the recent load is representative.
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct CatchUpEstimate {
+    /// The number of log entries the node lacks.
+    pub lag_entries: u64,
+
+    /// Whether some of the logs the node lacks are purged, so that it has to install a snapshot.
+    pub snapshot_required: bool,
+
+    /// The measured replication throughput, in log entries per second, or `None` if no log has
+    /// been replicated by this leader.
+    pub entries_per_sec: Option<u64>,
+
+    /// The measured average size of a replicated log entry in bytes, as returned by
+    /// [`RaftEntry::approx_size()`](crate::entry::RaftEntry::approx_size).
+    pub bytes_per_entry: Option<u64>,
+
+    /// The estimated time to catch up by replicating logs, or `None` if a snapshot is required or
+    /// the throughput is unknown.
+    pub by_logs: Option<Duration>,
+
+    /// The estimated time to catch up by installing the current snapshot and replicating the logs
+    /// after it, or `None` if there is no snapshot or no snapshot has been sent by this leader.
+    ///
+    /// The snapshot is assumed to take as long to send as the last one did.
+    pub by_snapshot: Option<Duration>,
+}
+
+impl CatchUpEstimate {
+    /// Whether installing a snapshot is expected to be faster than replicating logs, or is
+    /// required.
+    pub fn prefer_snapshot(&self) -> bool {
+        if self.snapshot_required {
+            return true;
+        }
+
+        match (self.by_logs, self.by_snapshot) {
+            (Some(logs), Some(snapshot)) => snapshot < logs,
+            _ => false,
+        }
+    }
+
+    /// The estimated time to catch up the faster way, or `None` if it is unknown.
+    pub fn duration(&self) -> Option<Duration> {
+        if self.prefer_snapshot() {
+            self.by_snapshot
+        } else {
+            self.by_logs
+        }
+    }
+}
+
+impl fmt::Display for CatchUpEstimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "CatchUpEstimate{{lag_entries:{}, snapshot_required:{}, entries_per_sec:{}, bytes_per_entry:{}, by_logs:{}, by_snapshot:{}}}",
+            self.lag_entries,
+            self.snapshot_required,
+            self.entries_per_sec.display(),
+            self.bytes_per_entry.display(),
+            self.by_logs.map(|d| format!("{:?}", d)).display(),
+            self.by_snapshot.map(|d| format!("{:?}", d)).display(),
+        )
+    }
+}
@@ -0,0 +1,52 @@
+use std::fmt;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use openraft_macros::since;
+
+use crate::Instant;
+use crate::RaftTypeConfig;
+use crate::type_config::TypeConfigExt;
+use crate::type_config::alias::SerdeInstantOf;
+
+/// When this node established its current leadership.
+///
+/// It is recorded once the vote of this node is granted by a quorum, and is reset when this node
+/// becomes leader again, e.g., with a higher term.
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct LeaderSince<C: RaftTypeConfig> {
+    /// The monotonic time when leadership was established.
+    pub instant: SerdeInstantOf<C>,
+
+    /// The wall-clock time when leadership was established, for display on dashboards.
+    ///
+    /// It is not affected by [`instant`](Self::instant) and may jump if the system clock is
+    /// adjusted.
+    pub system_time: SystemTime,
+}
+
+impl<C> LeaderSince<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn now() -> Self {
+        Self {
+            instant: C::now().into(),
+            system_time: SystemTime::now(),
+        }
+    }
+
+    /// How long this node has been leader, measured with the monotonic clock.
+    pub fn elapsed(&self) -> Duration {
+        self.instant.elapsed()
+    }
+}
+
+impl<C> fmt::Display for LeaderSince<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}({:?} ago)", self.instant, self.elapsed())
+    }
+}
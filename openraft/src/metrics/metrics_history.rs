@@ -0,0 +1,91 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use crate::RaftMetrics;
+use crate::RaftTypeConfig;
+
+/// A ring buffer of the most recent [`RaftMetrics`] snapshots.
+///
+/// It is shared between `RaftCore`, which records every reported metrics, and the `Raft` handle,
+/// which serves [`Raft::metrics_history()`](crate::Raft::metrics_history).
+#[derive(Debug, Clone)]
+pub(crate) struct MetricsHistory<C>
+where C: RaftTypeConfig
+{
+    /// The max number of snapshots to retain. `0` disables recording.
+    capacity: usize,
+    inner: Arc<Mutex<VecDeque<RaftMetrics<C>>>>,
+}
+
+impl<C> MetricsHistory<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            inner: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+        }
+    }
+
+    /// Append a snapshot, evicting the oldest one if the buffer is full.
+    ///
+    /// A snapshot identical to the last recorded one is ignored, so that an idle node does not
+    /// flush out the history with duplicates.
+    pub(crate) fn record(&self, metrics: &RaftMetrics<C>) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut history = self.inner.lock().unwrap();
+        if history.back() == Some(metrics) {
+            return;
+        }
+
+        if history.len() >= self.capacity {
+            history.pop_front();
+        }
+        history.push_back(metrics.clone());
+    }
+
+    /// Returns a copy of the retained snapshots, oldest first.
+    pub(crate) fn snapshot(&self) -> Vec<RaftMetrics<C>> {
+        let history = self.inner.lock().unwrap();
+        history.iter().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RaftMetrics;
+    use crate::engine::testing::UTConfig;
+
+    type MetricsHistory = super::MetricsHistory<UTConfig>;
+
+    fn metrics(term: u64) -> RaftMetrics<UTConfig> {
+        let mut m = RaftMetrics::new_initial(1);
+        m.current_term = term;
+        m
+    }
+
+    #[test]
+    fn test_metrics_history_ring_buffer() {
+        let h = MetricsHistory::new(2);
+        let shared = h.clone();
+
+        h.record(&metrics(1));
+        h.record(&metrics(1));
+        assert_eq!(vec![metrics(1)], shared.snapshot(), "duplicate is ignored");
+
+        h.record(&metrics(2));
+        h.record(&metrics(3));
+        assert_eq!(vec![metrics(2), metrics(3)], shared.snapshot(), "oldest is evicted");
+    }
+
+    #[test]
+    fn test_metrics_history_disabled() {
+        let h = MetricsHistory::new(0);
+        h.record(&metrics(1));
+        assert!(h.snapshot().is_empty());
+    }
+}
@@ -27,26 +27,40 @@
 //! not every change of the state.
 //! Because internally, `watch::channel()` only stores one last state.
 
+mod candidate_metrics;
+mod catch_up_estimate;
+mod leader_since;
 mod metric;
+mod metrics_history;
+mod quorum_ack_latency_metrics;
 mod raft_metrics;
+mod replication_target_metrics;
 mod wait;
 
 mod metric_display;
 pub mod recorder;
 mod serde_instant;
+mod snapshot_transfer_state;
 mod wait_condition;
 #[cfg(test)]
 mod wait_test;
 
 use std::collections::BTreeMap;
 
+pub use candidate_metrics::CandidateMetrics;
+pub use catch_up_estimate::CatchUpEstimate;
+pub use leader_since::LeaderSince;
 pub use metric::Metric;
+pub(crate) use metrics_history::MetricsHistory;
+pub use quorum_ack_latency_metrics::QuorumAckLatencyMetrics;
 pub use raft_metrics::RaftDataMetrics;
 pub use raft_metrics::RaftMetrics;
 pub use raft_metrics::RaftServerMetrics;
 pub use recorder::MetricsRecorder;
 pub use recorder::forward_metrics;
+pub use replication_target_metrics::ReplicationTargetMetrics;
 pub use serde_instant::SerdeInstant;
+pub use snapshot_transfer_state::SnapshotTransferState;
 pub use wait::Wait;
 pub use wait::WaitError;
 pub(crate) use wait_condition::Condition;
@@ -56,6 +70,8 @@ use crate::type_config::alias::NodeIdOf;
 use crate::type_config::alias::SerdeInstantOf;
 
 pub(crate) type ReplicationMetrics<C> = BTreeMap<NodeIdOf<C>, Option<LogIdOf<C>>>;
+/// The replication state of every target of the leader.
+pub(crate) type ReplicationTargetsMetrics<C> = BTreeMap<NodeIdOf<C>, ReplicationTargetMetrics<C>>;
 /// Heartbeat metrics, a mapping between a node's ID and the time of the last
 /// acknowledged heartbeat or replication to this node.
 pub(crate) type HeartbeatMetrics<C> = BTreeMap<NodeIdOf<C>, Option<SerdeInstantOf<C>>>;
@@ -0,0 +1,39 @@
+use std::fmt;
+use std::time::Duration;
+
+use openraft_macros::since;
+
+/// The time log entries take from being appended by the leader to being acknowledged by a
+/// quorum, i.e., committed.
+///
+/// It covers local storage and replication but not applying to the state machine, so a
+/// regression here points at the replication path rather than the apply path.
+///
+/// The percentiles are over the most recent log entries proposed by this node as a leader, see
+/// [`RaftMetrics::quorum_ack_latency`](crate::metrics::RaftMetrics::quorum_ack_latency).
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct QuorumAckLatencyMetrics {
+    /// The number of log entries the percentiles are computed over.
+    pub samples: u64,
+
+    pub p50: Duration,
+    pub p90: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+
+    /// The number of log entries, since this node started, that took longer than
+    /// [`Config::quorum_ack_slo`](crate::Config::quorum_ack_slo) to be acknowledged by a quorum.
+    pub slo_breaches: u64,
+}
+
+impl fmt::Display for QuorumAckLatencyMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{n:{}, p50:{:?}, p90:{:?}, p99:{:?}, max:{:?}, slo_breaches:{}}}",
+            self.samples, self.p50, self.p90, self.p99, self.max, self.slo_breaches
+        )
+    }
+}
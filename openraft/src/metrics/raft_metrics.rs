@@ -1,17 +1,27 @@
+use std::collections::BTreeMap;
 use std::fmt;
 use std::sync::Arc;
 
 use display_more::DisplayOptionExt;
 use openraft_macros::since;
 
+use crate::ConfigDigest;
 use crate::Instant;
 use crate::RaftTypeConfig;
+use crate::StorageError;
 use crate::core::ServerState;
+use crate::display_ext::DisplayBTreeMap;
 use crate::display_ext::DisplayBTreeMapOptValue;
 use crate::errors::Fatal;
+use crate::metrics::CandidateMetrics;
 use crate::metrics::HeartbeatMetrics;
+use crate::metrics::LeaderSince;
+use crate::metrics::QuorumAckLatencyMetrics;
 use crate::metrics::ReplicationMetrics;
+use crate::metrics::ReplicationTargetsMetrics;
 use crate::metrics::SerdeInstant;
+use crate::metrics::SnapshotTransferState;
+use crate::raft::Capabilities;
 use crate::type_config::alias::InstantOf;
 #[cfg(feature = "metrics-logids")]
 use crate::type_config::alias::LogIdListOf;
@@ -60,6 +70,7 @@ use crate::vote::raft_vote::RaftVoteExt;
 ///
 /// - `heartbeat`: Last acknowledged time for each node (for detecting offline nodes)
 /// - `replication`: Replication state including `matched` log index for each node
+/// - `snapshot_transfers`: Whether the snapshot to a node is being sent or is queued
 ///
 /// These fields are `None` when the node is a follower or candidate.
 ///
@@ -78,6 +89,12 @@ pub struct RaftMetrics<C: RaftTypeConfig> {
     /// The running state of the Raft node, or a fatal error if the node has stopped.
     pub running_state: Result<(), Fatal<C>>,
 
+    /// The storage error this node keeps running degraded with, `None` if the storage is healthy.
+    ///
+    /// See [`Config::degrade_on_storage_error`](crate::Config::degrade_on_storage_error).
+    #[since(version = "0.10.0")]
+    pub storage_error: Option<StorageError<C>>,
+
     /// The ID of the Raft node.
     pub id: C::NodeId,
 
@@ -136,6 +153,44 @@ pub struct RaftMetrics<C: RaftTypeConfig> {
     /// already been deleted.
     pub purged: Option<LogIdOf<C>>,
 
+    /// Since when a snapshot build triggered by the
+    /// [`SnapshotPolicy`](crate::SnapshotPolicy) is deferred because this node is busy.
+    ///
+    /// It is `None` if no snapshot build is deferred. See
+    /// [`Config::snapshot_defer_write_rate`](crate::Config::snapshot_defer_write_rate).
+    #[since(version = "0.10.0")]
+    pub snapshot_deferred_since: Option<SerdeInstantOf<C>>,
+
+    /// The barrier log id of the last backup snapshot built on this node.
+    ///
+    /// A backup started with [`Raft::backup()`](crate::Raft::backup) is complete on this node
+    /// once this is the barrier log id it returned: the snapshot of this node is then at exactly
+    /// that log id. See [`Raft::backup()`](crate::Raft::backup).
+    #[since(version = "0.10.0")]
+    pub last_backup: Option<LogIdOf<C>>,
+
+    /// Until when applying log entries on this node is delayed to stay under
+    /// [`Config::max_apply_rate`](crate::Config::max_apply_rate).
+    ///
+    /// It is `None` if applying is not throttled.
+    #[since(version = "0.10.0")]
+    pub apply_throttled_until: Option<SerdeInstantOf<C>>,
+
+    /// Until when the state machine of this node reported it is compacting, with
+    /// [`Raft::notify_compaction()`](crate::Raft::notify_compaction).
+    ///
+    /// Applying log entries is expected to be slow until then, so an alert on the apply lag of
+    /// this node can be relaxed. It is `None` if the state machine is not compacting.
+    #[since(version = "0.10.0")]
+    pub compacting_until: Option<SerdeInstantOf<C>>,
+
+    /// The last log id of the snapshot the state machine is warming up after installing it.
+    ///
+    /// This node should not serve reads until it is `None`. It is always `None` unless
+    /// [`Config::warm_up_after_install`](crate::Config::warm_up_after_install) is enabled.
+    #[since(version = "0.10.0")]
+    pub warming_up: Option<LogIdOf<C>>,
+
     /// The list of log IDs, one per leader, tracking the last log entry from each leader.
     ///
     /// Only available when the `metrics-logids` feature is enabled.
@@ -188,6 +243,35 @@ pub struct RaftMetrics<C: RaftTypeConfig> {
     #[since(version = "0.10.0")]
     pub last_quorum_acked: Option<SerdeInstantOf<C>>,
 
+    /// The time log entries proposed by this node as a leader took to be acknowledged by a
+    /// quorum.
+    ///
+    /// It is kept when this node is no longer the leader, until it leads again.
+    #[since(version = "0.10.0")]
+    pub quorum_ack_latency: QuorumAckLatencyMetrics,
+
+    /// For a leader, when it established its leadership, i.e., its vote was granted by a quorum.
+    ///
+    /// It is `None` if this node is not leader.
+    /// See [`Raft::leader_since()`](crate::Raft::leader_since).
+    #[since(version = "0.10.0")]
+    pub leader_since: Option<LeaderSince<C>>,
+
+    /// The election this node is running: the term it campaigns for, the voters that granted it
+    /// and when it started.
+    ///
+    /// It is `None` if this node is not a candidate or pre-candidate.
+    #[since(version = "0.10.0")]
+    pub candidate: Option<CandidateMetrics<C>>,
+
+    /// Whether the term of this node can not be increased any more.
+    ///
+    /// Such a node refuses to start an election, including one triggered by
+    /// [`Trigger::elect()`](crate::raft::trigger::Trigger::elect), but still follows a leader. See
+    /// [`RaftTerm`](crate::vote::RaftTerm#term-exhaustion).
+    #[since(version = "0.10.0")]
+    pub term_exhausted: bool,
+
     /// The current membership config of the cluster.
     pub membership_config: Arc<StoredMembershipOf<C>>,
 
@@ -207,14 +291,43 @@ pub struct RaftMetrics<C: RaftTypeConfig> {
     /// This duration since the recorded time can be used by applications to
     /// guess if a follower/learner node is offline, longer duration suggests
     /// a higher possibility of that.
-    #[since(version = "0.10.0")]
-    pub heartbeat: Option<HeartbeatMetrics<C>>,
+    #[since(version = "0.10.0", change = "wrapped in `Arc`")]
+    pub heartbeat: Option<Arc<HeartbeatMetrics<C>>>,
 
     // ---
     // --- replication ---
     // ---
     /// The replication states. It is Some() only when this node is leader.
-    pub replication: Option<ReplicationMetrics<C>>,
+    ///
+    /// The map is shared by the metrics published until it changes, so that cloning the metrics
+    /// does not clone it.
+    #[since(version = "0.10.0", change = "wrapped in `Arc`")]
+    pub replication: Option<Arc<ReplicationMetrics<C>>>,
+
+    /// The replication state of every target other than this node: what is in flight, when it
+    /// last acknowledged, the round-trip time and the lag. It is Some() only when this node is
+    /// leader.
+    #[since(version = "0.10.0")]
+    pub replication_targets: Option<Arc<ReplicationTargetsMetrics<C>>>,
+
+    /// The state of every snapshot transfer to a target node. It is empty if this node is not
+    /// leader or no snapshot is being sent.
+    ///
+    /// See [`Config::max_inflight_snapshots`](crate::Config::max_inflight_snapshots).
+    #[since(version = "0.10.0")]
+    pub snapshot_transfers: Arc<BTreeMap<C::NodeId, SnapshotTransferState>>,
+
+    /// The peers whose safety-relevant config differs from this node's, with the last
+    /// [`ConfigDigest`] received from each of them. It is empty if every peer agrees.
+    #[since(version = "0.10.0")]
+    pub config_mismatches: Arc<BTreeMap<C::NodeId, ConfigDigest>>,
+
+    /// The protocol features every peer advertised in its last response to a (Pre-)Vote request
+    /// of this node. A peer this node has not run an election against is absent.
+    ///
+    /// See [`Capabilities`].
+    #[since(version = "0.10.0")]
+    pub peer_capabilities: Arc<BTreeMap<C::NodeId, Capabilities>>,
 }
 
 impl<C> fmt::Display for RaftMetrics<C>
@@ -248,6 +361,26 @@ where C: RaftTypeConfig
             write!(f, "(quorum_acked_time:None)")?;
         }
 
+        if self.quorum_ack_latency.samples > 0 {
+            write!(f, ", quorum_ack_latency:{}", self.quorum_ack_latency)?;
+        }
+
+        if let Some(leader_since) = &self.leader_since {
+            write!(f, ", leader_since:{}", leader_since)?;
+        }
+
+        if let Some(candidate) = &self.candidate {
+            write!(f, ", candidate:{}", candidate)?;
+        }
+
+        if self.term_exhausted {
+            write!(f, ", term_exhausted")?;
+        }
+
+        if let Some(e) = &self.storage_error {
+            write!(f, ", storage_error:{}", e)?;
+        }
+
         write!(f, ", ")?;
         write!(
             f,
@@ -256,10 +389,26 @@ where C: RaftTypeConfig
             self.committed_membership_config,
             self.snapshot.display(),
             self.purged.display(),
-            self.replication.as_ref().map(DisplayBTreeMapOptValue).display(),
-            self.heartbeat.as_ref().map(DisplayBTreeMapOptValue).display(),
+            self.replication.as_deref().map(DisplayBTreeMapOptValue).display(),
+            self.heartbeat.as_deref().map(DisplayBTreeMapOptValue).display(),
         )?;
 
+        if !self.snapshot_transfers.is_empty() {
+            write!(
+                f,
+                ", snapshot_transfers:{{{}}}",
+                DisplayBTreeMap(self.snapshot_transfers.as_ref())
+            )?;
+        }
+
+        if !self.config_mismatches.is_empty() {
+            write!(
+                f,
+                ", config_mismatches:{{{}}}",
+                DisplayBTreeMap(self.config_mismatches.as_ref())
+            )?;
+        }
+
         write!(f, "}}")?;
         Ok(())
     }
@@ -274,6 +423,7 @@ where C: RaftTypeConfig
         #[allow(deprecated)]
         Self {
             running_state: Ok(()),
+            storage_error: None,
             id,
 
             current_term: Default::default(),
@@ -285,6 +435,11 @@ where C: RaftTypeConfig
             last_applied: None,
             snapshot: None,
             purged: None,
+            snapshot_deferred_since: None,
+            last_backup: None,
+            apply_throttled_until: None,
+            compacting_until: None,
+            warming_up: None,
 
             #[cfg(feature = "metrics-logids")]
             log_id_list: Default::default(),
@@ -293,12 +448,40 @@ where C: RaftTypeConfig
             current_leader: None,
             millis_since_quorum_ack: None,
             last_quorum_acked: None,
+            quorum_ack_latency: QuorumAckLatencyMetrics::default(),
+            leader_since: None,
+            candidate: None,
+            term_exhausted: false,
             membership_config: Arc::new(StoredMembershipOf::<C>::default()),
             committed_membership_config: Arc::new(StoredMembershipOf::<C>::default()),
             replication: None,
+            replication_targets: None,
             heartbeat: None,
+            snapshot_transfers: Default::default(),
+            config_mismatches: Default::default(),
+            peer_capabilities: Default::default(),
         }
     }
+
+    /// The replication states, borrowed from the shared map. It is Some() only when this node is
+    /// leader.
+    #[since(version = "0.10.0")]
+    pub fn replication(&self) -> Option<&ReplicationMetrics<C>> {
+        self.replication.as_deref()
+    }
+
+    /// The heartbeat metrics, borrowed from the shared map. It is Some() only when this node is
+    /// leader.
+    #[since(version = "0.10.0")]
+    pub fn heartbeat(&self) -> Option<&HeartbeatMetrics<C>> {
+        self.heartbeat.as_deref()
+    }
+
+    /// The effective membership config, borrowed from the shared one.
+    #[since(version = "0.10.0")]
+    pub fn membership(&self) -> &StoredMembershipOf<C> {
+        &self.membership_config
+    }
 }
 
 /// Subset of RaftMetrics, only include data-related metrics
@@ -342,6 +525,44 @@ pub struct RaftDataMetrics<C: RaftTypeConfig> {
     /// The last purged log id.
     pub purged: Option<LogIdOf<C>>,
 
+    /// Since when a snapshot build triggered by the
+    /// [`SnapshotPolicy`](crate::SnapshotPolicy) is deferred because this node is busy.
+    ///
+    /// It is `None` if no snapshot build is deferred. See
+    /// [`Config::snapshot_defer_write_rate`](crate::Config::snapshot_defer_write_rate).
+    #[since(version = "0.10.0")]
+    pub snapshot_deferred_since: Option<SerdeInstantOf<C>>,
+
+    /// The barrier log id of the last backup snapshot built on this node.
+    ///
+    /// A backup started with [`Raft::backup()`](crate::Raft::backup) is complete on this node
+    /// once this is the barrier log id it returned: the snapshot of this node is then at exactly
+    /// that log id. See [`Raft::backup()`](crate::Raft::backup).
+    #[since(version = "0.10.0")]
+    pub last_backup: Option<LogIdOf<C>>,
+
+    /// Until when applying log entries on this node is delayed to stay under
+    /// [`Config::max_apply_rate`](crate::Config::max_apply_rate).
+    ///
+    /// It is `None` if applying is not throttled.
+    #[since(version = "0.10.0")]
+    pub apply_throttled_until: Option<SerdeInstantOf<C>>,
+
+    /// Until when the state machine of this node reported it is compacting, with
+    /// [`Raft::notify_compaction()`](crate::Raft::notify_compaction).
+    ///
+    /// Applying log entries is expected to be slow until then, so an alert on the apply lag of
+    /// this node can be relaxed. It is `None` if the state machine is not compacting.
+    #[since(version = "0.10.0")]
+    pub compacting_until: Option<SerdeInstantOf<C>>,
+
+    /// The last log id of the snapshot the state machine is warming up after installing it.
+    ///
+    /// This node should not serve reads until it is `None`. It is always `None` unless
+    /// [`Config::warm_up_after_install`](crate::Config::warm_up_after_install) is enabled.
+    #[since(version = "0.10.0")]
+    pub warming_up: Option<LogIdOf<C>>,
+
     /// The list of log IDs, one per leader, tracking the last log entry from each leader.
     ///
     /// Only available when the `metrics-logids` feature is enabled.
@@ -382,7 +603,8 @@ pub struct RaftDataMetrics<C: RaftTypeConfig> {
     pub last_quorum_acked: Option<SerdeInstant<InstantOf<C>>>,
 
     /// Replication metrics for each node, available only on the leader.
-    pub replication: Option<ReplicationMetrics<C>>,
+    #[since(version = "0.10.0", change = "wrapped in `Arc`")]
+    pub replication: Option<Arc<ReplicationMetrics<C>>>,
 
     /// Heartbeat metrics. It is Some() only when this node is leader.
     ///
@@ -392,8 +614,26 @@ pub struct RaftDataMetrics<C: RaftTypeConfig> {
     /// This duration since the recorded time can be used by applications to
     /// guess if a follower/learner node is offline, longer duration suggests
     /// a higher possibility of that.
+    #[since(version = "0.10.0", change = "wrapped in `Arc`")]
+    pub heartbeat: Option<Arc<HeartbeatMetrics<C>>>,
+}
+
+impl<C> RaftDataMetrics<C>
+where C: RaftTypeConfig
+{
+    /// The replication states, borrowed from the shared map. It is Some() only when this node is
+    /// leader.
+    #[since(version = "0.10.0")]
+    pub fn replication(&self) -> Option<&ReplicationMetrics<C>> {
+        self.replication.as_deref()
+    }
+
+    /// The heartbeat metrics, borrowed from the shared map. It is Some() only when this node is
+    /// leader.
     #[since(version = "0.10.0")]
-    pub heartbeat: Option<HeartbeatMetrics<C>>,
+    pub fn heartbeat(&self) -> Option<&HeartbeatMetrics<C>> {
+        self.heartbeat.as_deref()
+    }
 }
 
 impl<C> fmt::Display for RaftDataMetrics<C>
@@ -427,8 +667,8 @@ where C: RaftTypeConfig
         write!(
             f,
             ", replication:{{{}}}, heartbeat:{{{}}}",
-            self.replication.as_ref().map(DisplayBTreeMapOptValue).display(),
-            self.heartbeat.as_ref().map(DisplayBTreeMapOptValue).display(),
+            self.replication.as_deref().map(DisplayBTreeMapOptValue).display(),
+            self.heartbeat.as_deref().map(DisplayBTreeMapOptValue).display(),
         )?;
 
         write!(f, "}}")?;
@@ -33,6 +33,10 @@
 //! raft.set_metrics_recorder(Some(Arc::new(MyRecorder)));
 //! ```
 
+use std::time::Duration;
+
+use openraft_macros::since;
+
 use crate::RaftTypeConfig;
 use crate::core::ServerState;
 use crate::metrics::RaftMetrics;
@@ -92,6 +96,73 @@ pub trait MetricsRecorder: Send + Sync + std::fmt::Debug {
 
     /// Increment the append entries operation counter.
     fn increment_append(&self);
+
+    /// Increment the election storm counter.
+    ///
+    /// Called when this node trips the election storm circuit breaker, i.e., it has started
+    /// [`Config::election_storm_threshold`] elections in a row without a leader being
+    /// established, and starts to back off further elections.
+    ///
+    /// [`Config::election_storm_threshold`]: crate::Config::election_storm_threshold
+    #[since(version = "0.10.0")]
+    fn increment_election_storm(&self) {}
+
+    /// Set the number of log entries the state machine standby lags behind the primary.
+    ///
+    /// Called after every apply if the state machine provides a standby with
+    /// [`RaftStateMachine::create_standby`].
+    ///
+    /// [`RaftStateMachine::create_standby`]: crate::storage::RaftStateMachine::create_standby
+    #[since(version = "0.10.0")]
+    fn set_standby_lag(&self, lag: u64) {
+        let _ = lag;
+    }
+
+    /// Set the storage usage in bytes, as reported by the
+    /// [`StorageUsageProbe`](crate::storage::StorageUsageProbe).
+    #[since(version = "0.10.0")]
+    fn set_storage_usage(&self, bytes: u64) {
+        let _ = bytes;
+    }
+
+    /// Increment the storage quota exceeded counter.
+    ///
+    /// Called when the storage usage rises above [`Config::storage_quota`], before a snapshot is
+    /// built and logs are purged to reclaim space.
+    ///
+    /// [`Config::storage_quota`]: crate::Config::storage_quota
+    #[since(version = "0.10.0")]
+    fn increment_storage_quota_exceeded(&self) {}
+
+    /// Increment the config mismatch counter.
+    ///
+    /// Called when a request from a peer carries a [`ConfigDigest`] that differs from this node's,
+    /// once per mismatching peer until its config changes again.
+    ///
+    /// [`ConfigDigest`]: crate::ConfigDigest
+    #[since(version = "0.10.0")]
+    fn increment_config_mismatch(&self) {}
+
+    /// Record the time log entries took from being appended by the leader to being acknowledged
+    /// by a quorum.
+    ///
+    /// Called on the leader for every batch of entries it appended once the batch is committed,
+    /// with the number of entries in it.
+    #[since(version = "0.10.0")]
+    fn record_quorum_ack_latency(&self, latency: Duration, entry_count: u64) {
+        let _ = (latency, entry_count);
+    }
+
+    /// Increment the quorum acknowledgment SLO breach counter by `entry_count`.
+    ///
+    /// Called on the leader when log entries took longer than [`Config::quorum_ack_slo`] to be
+    /// acknowledged by a quorum.
+    ///
+    /// [`Config::quorum_ack_slo`]: crate::Config::quorum_ack_slo
+    #[since(version = "0.10.0")]
+    fn increment_quorum_ack_slo_breach(&self, entry_count: u64) {
+        let _ = entry_count;
+    }
 }
 
 /// Forward gauge metrics from `RaftMetrics` to a `MetricsRecorder`.
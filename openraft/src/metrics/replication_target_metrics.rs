@@ -0,0 +1,64 @@
+use std::fmt;
+use std::time::Duration;
+
+use display_more::DisplayOptionExt;
+use openraft_macros::since;
+
+use crate::RaftTypeConfig;
+use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::SerdeInstantOf;
+
+/// The replication state of one target of the leader, in
+/// [`RaftMetrics::replication_targets`](crate::metrics::RaftMetrics::replication_targets).
+///
+/// It is what the leader tracks to replicate to the target, so that a dashboard can tell a
+/// follower that is slow from one that is unreachable or installing a snapshot.
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct ReplicationTargetMetrics<C: RaftTypeConfig> {
+    /// The id of the last log known to match on the target.
+    pub matched: Option<LogIdOf<C>>,
+
+    /// The index of the next log to send to the target.
+    pub next_index: u64,
+
+    /// The number of log entries sent to the target but not yet acknowledged.
+    pub inflight_entries: u64,
+
+    /// The sending time of the last request the target acknowledged, heartbeat or replication.
+    pub last_ack: Option<SerdeInstantOf<C>>,
+
+    /// The round-trip time of the last request the target acknowledged, including the time the
+    /// response waits to be handled by `RaftCore`.
+    pub rtt: Option<Duration>,
+
+    /// Whether a snapshot is being sent to the target.
+    pub sending_snapshot: bool,
+
+    /// The number of log entries the target lacks.
+    pub lag_entries: u64,
+
+    /// The estimated size in bytes of the log entries the target lacks, from the average size of
+    /// the replicated entries, or `None` if none has been replicated by this leader.
+    pub lag_bytes: Option<u64>,
+}
+
+impl<C> fmt::Display for ReplicationTargetMetrics<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{matched:{}, next_index:{}, inflight_entries:{}, last_ack:{}, rtt:{}, sending_snapshot:{}, lag_entries:{}, lag_bytes:{}}}",
+            self.matched.display(),
+            self.next_index,
+            self.inflight_entries,
+            self.last_ack.display(),
+            self.rtt.map(|d| format!("{:?}", d)).display(),
+            self.sending_snapshot,
+            self.lag_entries,
+            self.lag_bytes.display(),
+        )
+    }
+}
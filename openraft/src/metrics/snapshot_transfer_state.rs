@@ -0,0 +1,28 @@
+use std::fmt;
+
+use openraft_macros::since;
+
+/// The state of a snapshot transfer from the leader to a target node.
+///
+/// A leader runs at most [`Config::max_inflight_snapshots`](crate::Config::max_inflight_snapshots)
+/// snapshot transfers at a time. A transfer requested while all of them are in use is queued
+/// until one finishes.
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum SnapshotTransferState {
+    /// The transfer is waiting for another transfer to finish.
+    Queued,
+
+    /// The snapshot is being sent to the target.
+    Sending,
+}
+
+impl fmt::Display for SnapshotTransferState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotTransferState::Queued => write!(f, "queued"),
+            SnapshotTransferState::Sending => write!(f, "sending"),
+        }
+    }
+}
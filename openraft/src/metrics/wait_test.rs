@@ -293,6 +293,7 @@ where C: RaftTypeConfig<NodeId = u64> {
     #[allow(deprecated)]
     let init = RaftMetrics {
         running_state: Ok(()),
+        storage_error: None,
         id: NodeIdOf::<C>::default(),
         state: ServerState::Learner,
         current_term: Default::default(),
@@ -303,6 +304,11 @@ where C: RaftTypeConfig<NodeId = u64> {
         cluster_committed: None,
         last_applied: None,
         purged: None,
+        snapshot_deferred_since: None,
+        last_backup: None,
+        apply_throttled_until: None,
+        compacting_until: None,
+        warming_up: None,
 
         #[cfg(feature = "metrics-logids")]
         log_id_list: Default::default(),
@@ -310,12 +316,20 @@ where C: RaftTypeConfig<NodeId = u64> {
         current_leader: None,
         millis_since_quorum_ack: None,
         last_quorum_acked: None,
+        quorum_ack_latency: Default::default(),
+        leader_since: None,
+        candidate: None,
+        term_exhausted: false,
         membership_config: Arc::new(StoredMembershipOf::<C>::new(None, Membership::default())),
         committed_membership_config: Arc::new(StoredMembershipOf::<C>::new(None, Membership::default())),
         heartbeat: None,
 
         snapshot: None,
         replication: None,
+        replication_targets: None,
+        snapshot_transfers: Default::default(),
+        config_mismatches: Default::default(),
+        peer_capabilities: Default::default(),
     };
     let (tx, rx) = C::watch_channel(init.clone());
     let w = Wait {
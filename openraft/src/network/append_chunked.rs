@@ -0,0 +1,175 @@
+//! Sends an AppendEntries RPC in chunks with [`append_entries_chunked`].
+
+use openraft_macros::since;
+
+use crate::RaftTypeConfig;
+use crate::errors::RPCError;
+use crate::network::NetAppend;
+use crate::network::RPCOption;
+use crate::raft::AppendEntriesRequest;
+use crate::raft::AppendEntriesResponse;
+
+/// Send an AppendEntries RPC as consecutive requests of at most `max_entries` entries each.
+///
+/// It is an adapter for a network whose transport limits the size of a message: an
+/// application's [`RaftNetworkV2::append_entries()`] calls it with a connection that implements
+/// [`NetAppend`], instead of sending the whole request at once. The chunks are sent one after
+/// another, each after the previous one succeeds, so that at most one chunk is in flight.
+///
+/// The responses are merged into one for the whole request:
+/// - If every chunk succeeds, it returns [`AppendEntriesResponse::Success`].
+/// - If a chunk after the first one fails, or an error is returned for it, it returns
+///   [`AppendEntriesResponse::PartialSuccess`] with the last log id the previous chunks reached.
+/// - Otherwise the response or the error of the first chunk is returned.
+///
+/// [`RaftNetworkV2::append_entries()`]: crate::network::RaftNetworkV2::append_entries
+#[since(version = "0.10.0")]
+pub async fn append_entries_chunked<C, N>(
+    network: &mut N,
+    rpc: AppendEntriesRequest<C>,
+    option: RPCOption,
+    max_entries: usize,
+) -> Result<AppendEntriesResponse<C>, RPCError<C>>
+where
+    C: RaftTypeConfig,
+    N: NetAppend<C> + ?Sized,
+{
+    let mut first = true;
+    let mut reached = None;
+
+    for req in rpc.into_chunks(max_entries) {
+        let last = req.log_id_range().last;
+
+        let res = network.append_entries(req, option.clone()).await;
+
+        if first {
+            first = false;
+            match res? {
+                AppendEntriesResponse::Success => {
+                    reached = last;
+                    continue;
+                }
+                resp => return Ok(resp),
+            }
+        }
+
+        match res {
+            Ok(AppendEntriesResponse::Success) => reached = last,
+            Ok(AppendEntriesResponse::Conflict) | Err(_) => return Ok(AppendEntriesResponse::PartialSuccess(reached)),
+            Ok(resp) => return Ok(resp),
+        }
+    }
+
+    Ok(AppendEntriesResponse::Success)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use anyerror::AnyError;
+
+    use super::append_entries_chunked;
+    use crate::Vote;
+    use crate::engine::testing::UTConfig;
+    use crate::engine::testing::log_id;
+    use crate::errors::RPCError;
+    use crate::errors::Unreachable;
+    use crate::network::NetAppend;
+    use crate::network::RPCOption;
+    use crate::raft::AppendEntriesRequest;
+    use crate::raft::AppendEntriesResponse;
+    use crate::testing::blank_ent;
+    use crate::type_config::TypeConfigExt;
+
+    type C = UTConfig;
+
+    /// Replies to each request with the next scripted result, `Success` once the script runs out.
+    struct Scripted {
+        replies: Vec<Result<AppendEntriesResponse<C>, RPCError<C>>>,
+        received: Vec<(Option<u64>, usize)>,
+    }
+
+    impl NetAppend<C> for Scripted {
+        async fn append_entries(
+            &mut self,
+            rpc: AppendEntriesRequest<C>,
+            _option: RPCOption,
+        ) -> Result<AppendEntriesResponse<C>, RPCError<C>> {
+            self.received.push((rpc.prev_log_id.map(|x| x.index), rpc.entries.len()));
+            if self.replies.is_empty() {
+                Ok(AppendEntriesResponse::Success)
+            } else {
+                self.replies.remove(0)
+            }
+        }
+    }
+
+    fn rpc() -> AppendEntriesRequest<C> {
+        AppendEntriesRequest {
+            vote: Vote::new_committed(1, 1),
+            prev_log_id: Some(log_id(1, 1, 2)),
+            entries: (3..10).map(|i| blank_ent::<C>(1, 1, i)).collect(),
+            leader_commit: None,
+            backup_barrier: None,
+            after_snapshot: None,
+            config_digest: None,
+        }
+    }
+
+    async fn send(
+        replies: Vec<Result<AppendEntriesResponse<C>, RPCError<C>>>,
+    ) -> (Result<AppendEntriesResponse<C>, RPCError<C>>, Vec<(Option<u64>, usize)>) {
+        let mut net = Scripted {
+            replies,
+            received: vec![],
+        };
+        let res = append_entries_chunked(&mut net, rpc(), RPCOption::new(Duration::from_secs(1)), 3).await;
+        (res, net.received)
+    }
+
+    fn unreachable() -> RPCError<C> {
+        RPCError::Unreachable(Unreachable::new(&AnyError::error("down")))
+    }
+
+    #[test]
+    fn test_append_entries_chunked() {
+        C::run(async {
+            let (res, received) = send(vec![]).await;
+            assert_eq!(AppendEntriesResponse::Success, res.unwrap());
+            assert_eq!(vec![(Some(2), 3), (Some(5), 3), (Some(8), 1)], received);
+
+            let (res, received) = send(vec![Ok(AppendEntriesResponse::Conflict)]).await;
+            assert_eq!(AppendEntriesResponse::Conflict, res.unwrap());
+            assert_eq!(1, received.len(), "stop at the first failed chunk");
+
+            let (res, _) = send(vec![Err(unreachable())]).await;
+            assert!(res.is_err(), "the first chunk reaches nothing");
+
+            let (res, received) = send(vec![Ok(AppendEntriesResponse::Success), Err(unreachable())]).await;
+            assert_eq!(
+                AppendEntriesResponse::PartialSuccess(Some(log_id(1, 1, 5))),
+                res.unwrap()
+            );
+            assert_eq!(2, received.len());
+
+            let (res, _) = send(vec![
+                Ok(AppendEntriesResponse::Success),
+                Ok(AppendEntriesResponse::Success),
+                Ok(AppendEntriesResponse::Conflict),
+            ])
+            .await;
+            assert_eq!(
+                AppendEntriesResponse::PartialSuccess(Some(log_id(1, 1, 8))),
+                res.unwrap()
+            );
+
+            let (res, _) = send(vec![
+                Ok(AppendEntriesResponse::Success),
+                Ok(AppendEntriesResponse::HigherVote(Vote::new(2, 2))),
+            ])
+            .await;
+            assert_eq!(AppendEntriesResponse::HigherVote(Vote::new(2, 2)), res.unwrap());
+        });
+    }
+}
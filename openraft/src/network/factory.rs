@@ -4,6 +4,7 @@ use crate::OptionalSend;
 use crate::OptionalSync;
 use crate::RaftTypeConfig;
 use crate::network::NetBackoff;
+use crate::network::NetReadIndex;
 use crate::network::NetSnapshot;
 use crate::network::NetStreamAppend;
 use crate::network::NetTransferLeader;
@@ -28,7 +29,12 @@ pub trait RaftNetworkFactory<C>: OptionalSend + OptionalSync + 'static
 where C: RaftTypeConfig
 {
     /// Actual type of the network handling a single connection.
-    type Network: NetBackoff<C> + NetStreamAppend<C> + NetVote<C> + NetSnapshot<C> + NetTransferLeader<C>;
+    type Network: NetBackoff<C>
+        + NetStreamAppend<C>
+        + NetVote<C>
+        + NetSnapshot<C>
+        + NetTransferLeader<C>
+        + NetReadIndex<C>;
 
     /// Create a new network instance sending RPCs to the target node.
     ///
@@ -0,0 +1,39 @@
+//! Hedges an RPC by sending it again if it does not respond in time.
+
+use std::future::Future;
+use std::pin::pin;
+use std::time::Duration;
+
+use futures_util::future::Either;
+use futures_util::future::select;
+
+use crate::RaftTypeConfig;
+use crate::type_config::TypeConfigExt;
+
+/// Run `primary`, and start `secondary` as well if `primary` does not finish within `delay`.
+///
+/// Returns the first `Ok` of the two. If one of them fails, the other one is awaited, and if both
+/// fail, the error of the one that finishes last is returned.
+pub(crate) async fn hedge<C, T, E>(
+    delay: Duration,
+    primary: impl Future<Output = Result<T, E>>,
+    secondary: impl Future<Output = Result<T, E>>,
+) -> Result<T, E>
+where
+    C: RaftTypeConfig,
+{
+    let secondary = async move {
+        C::sleep(delay).await;
+        tracing::debug!("no response in {:?}, send the hedged request", delay);
+        secondary.await
+    };
+
+    let primary = pin!(primary);
+    let secondary = pin!(secondary);
+
+    match select(primary, secondary).await {
+        Either::Left((Ok(x), _)) | Either::Right((Ok(x), _)) => Ok(x),
+        Either::Left((Err(_), other)) => other.await,
+        Either::Right((Err(_), other)) => other.await,
+    }
+}
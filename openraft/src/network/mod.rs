@@ -22,12 +22,15 @@
 //! See the [Getting Started Guide](crate::docs::getting_started) for implementation
 //! details and examples.
 
+mod append_chunked;
 mod append_trait;
 mod backoff;
 mod backoff_trait;
 mod factory;
+mod hedge;
 mod raft_network_api;
 mod raft_network_v1;
+mod read_index_trait;
 mod rpc_option;
 mod rpc_type;
 mod snapshot_trait;
@@ -37,13 +40,16 @@ mod vote_trait;
 
 pub mod v2;
 
+pub use append_chunked::append_entries_chunked;
 pub use append_trait::NetAppend;
 pub use backoff::Backoff;
 pub use backoff_trait::NetBackoff;
 pub use factory::RaftNetworkFactory;
+pub(crate) use hedge::hedge;
 pub(crate) use raft_network_api::RaftNetworkApi;
 #[allow(deprecated)]
 pub use raft_network_v1::RaftNetwork;
+pub use read_index_trait::NetReadIndex;
 pub use rpc_option::RPCOption;
 pub use rpc_type::RPCTypes;
 pub use snapshot_trait::NetSnapshot;
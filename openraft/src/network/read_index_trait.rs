@@ -0,0 +1,37 @@
+//! Defines the [`NetReadIndex`] trait for follower reads.
+
+use openraft_macros::add_async_trait;
+use openraft_macros::since;
+
+use crate::OptionalSend;
+use crate::OptionalSync;
+use crate::RaftTypeConfig;
+use crate::errors::RPCError;
+use crate::network::RPCOption;
+use crate::raft::message::ReadIndexRequest;
+use crate::raft::message::ReadIndexResponse;
+
+/// Sends ReadIndex messages to the leader.
+///
+/// **For most applications, implement [`RaftNetworkV2`] instead.** This trait is
+/// automatically derived from `RaftNetworkV2` via blanket implementation.
+///
+/// Direct implementation is an advanced option for fine-grained control.
+///
+/// [`RaftNetworkV2`]: crate::network::RaftNetworkV2
+#[since(version = "0.10.0")]
+#[add_async_trait]
+pub trait NetReadIndex<C>: OptionalSend + OptionalSync + 'static
+where C: RaftTypeConfig
+{
+    /// Send ReadIndex message to the leader.
+    ///
+    /// The node received this message should pass it to [`Raft::handle_read_index()`].
+    ///
+    /// [`Raft::handle_read_index()`]: crate::raft::Raft::handle_read_index
+    async fn read_index(
+        &mut self,
+        req: ReadIndexRequest<C>,
+        option: RPCOption,
+    ) -> Result<ReadIndexResponse<C>, RPCError<C>>;
+}
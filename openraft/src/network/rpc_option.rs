@@ -1,5 +1,10 @@
 use std::time::Duration;
 
+use openraft_macros::since;
+
+use crate::RaftTypeConfig;
+use crate::raft::AppendEntriesRequest;
+
 /// An additional argument to the [`RaftNetworkV2`] methods to allow applications to customize
 /// networking behaviors.
 ///
@@ -11,8 +16,16 @@ pub struct RPCOption {
     /// The caller will cancel an RPC if it takes longer than this duration.
     hard_ttl: Duration,
 
+    /// The hard TTL for an `AppendEntries` RPC that carries no log entries, e.g., a heartbeat.
+    ///
+    /// If it is `None`, `hard_ttl` is used.
+    heartbeat_ttl: Option<Duration>,
+
     /// The size of the snapshot chunk.
     pub(crate) snapshot_chunk_size: Option<usize>,
+
+    /// The maximum number of bytes per second to send to the target.
+    pub(crate) max_bytes_rate: Option<u64>,
 }
 
 impl RPCOption {
@@ -20,7 +33,36 @@ impl RPCOption {
     pub fn new(hard_ttl: Duration) -> Self {
         Self {
             hard_ttl,
+            heartbeat_ttl: None,
             snapshot_chunk_size: None,
+            max_bytes_rate: None,
+        }
+    }
+
+    /// Set the hard TTL for an `AppendEntries` RPC that carries no log entries.
+    pub(crate) fn with_heartbeat_ttl(mut self, heartbeat_ttl: Duration) -> Self {
+        self.heartbeat_ttl = Some(heartbeat_ttl);
+        self
+    }
+
+    /// Return the option to send a single `AppendEntries` request with.
+    ///
+    /// The option passed to [`RaftNetworkV2::stream_append()`] covers both heartbeats and
+    /// requests carrying log entries, with the longer TTL of the latter. An implementation that
+    /// sends each request as a separate RPC should use this method to give a request without
+    /// entries the shorter [`heartbeat_ttl()`](Self::heartbeat_ttl), so that it fails fast.
+    ///
+    /// [`RaftNetworkV2::stream_append()`]: crate::network::RaftNetworkV2::stream_append
+    #[since(version = "0.10.0")]
+    pub fn for_append_entries<C>(&self, req: &AppendEntriesRequest<C>) -> Self
+    where C: RaftTypeConfig {
+        if req.entries.is_empty() {
+            Self {
+                hard_ttl: self.heartbeat_ttl(),
+                ..self.clone()
+            }
+        } else {
+            self.clone()
         }
     }
 
@@ -48,8 +90,66 @@ impl RPCOption {
         self.hard_ttl
     }
 
+    /// The hard limit of the interval an `AppendEntries` RPC without log entries should last for.
+    #[since(version = "0.10.0")]
+    pub fn heartbeat_ttl(&self) -> Duration {
+        self.heartbeat_ttl.unwrap_or(self.hard_ttl)
+    }
+
     /// Get the recommended size of the snapshot chunk for transport.
     pub fn snapshot_chunk_size(&self) -> Option<usize> {
         self.snapshot_chunk_size
     }
+
+    /// Get the maximum number of bytes per second a snapshot should be sent at, if limited.
+    ///
+    /// See [`Config::max_replication_rate`](crate::Config::max_replication_rate).
+    #[since(version = "0.10.0")]
+    pub fn max_bytes_rate(&self) -> Option<u64> {
+        self.max_bytes_rate
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::Vote;
+    use crate::engine::testing::UTConfig;
+    use crate::network::RPCOption;
+    use crate::raft::AppendEntriesRequest;
+    use crate::testing::blank_ent;
+
+    fn req(entries: Vec<u64>) -> AppendEntriesRequest<UTConfig> {
+        AppendEntriesRequest {
+            vote: Vote::new_committed(1, 1),
+            prev_log_id: None,
+            entries: entries.into_iter().map(|i| blank_ent::<UTConfig>(1, 1, i)).collect(),
+            leader_commit: None,
+            backup_barrier: None,
+            after_snapshot: None,
+            config_digest: None,
+        }
+    }
+
+    #[test]
+    fn test_for_append_entries() {
+        let option = RPCOption::new(Duration::from_millis(150)).with_heartbeat_ttl(Duration::from_millis(50));
+
+        assert_eq!(
+            Duration::from_millis(50),
+            option.for_append_entries(&req(vec![])).hard_ttl()
+        );
+        assert_eq!(
+            Duration::from_millis(150),
+            option.for_append_entries(&req(vec![1, 2])).hard_ttl()
+        );
+
+        // Without a heartbeat TTL, every request uses the hard TTL.
+        let option = RPCOption::new(Duration::from_millis(150));
+        assert_eq!(
+            Duration::from_millis(150),
+            option.for_append_entries(&req(vec![])).hard_ttl()
+        );
+    }
 }
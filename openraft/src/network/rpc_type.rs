@@ -18,6 +18,21 @@ pub enum RPCTypes {
     /// TransferLeader request RPC.
     #[since(version = "0.10.0")]
     TransferLeader,
+    /// ReadIndex request RPC.
+    #[since(version = "0.10.0")]
+    ReadIndex,
+    /// SeedSnapshot request RPC.
+    #[since(version = "0.10.0")]
+    SeedSnapshot,
+    /// FetchSnapshot request RPC.
+    #[since(version = "0.10.0")]
+    FetchSnapshot,
+    /// SnapshotLocator request RPC.
+    #[since(version = "0.10.0")]
+    SnapshotLocator,
+    /// SnapshotMeta request RPC.
+    #[since(version = "0.10.0")]
+    SnapshotMeta,
 }
 
 impl fmt::Display for RPCTypes {
@@ -2,15 +2,25 @@
 
 use std::future::Future;
 
+use anyerror::AnyError;
 use openraft_macros::add_async_trait;
+use openraft_macros::since;
 
 use crate::OptionalSend;
 use crate::OptionalSync;
 use crate::RaftTypeConfig;
+use crate::errors::RPCError;
 use crate::errors::ReplicationClosed;
 use crate::errors::StreamingError;
+use crate::errors::Unreachable;
 use crate::network::RPCOption;
+use crate::raft::FetchSnapshotRequest;
+use crate::raft::FetchSnapshotResponse;
+use crate::raft::SeedSnapshotRequest;
+use crate::raft::SeedSnapshotResponse;
 use crate::raft::SnapshotResponse;
+use crate::storage::SnapshotLocator;
+use crate::type_config::alias::SnapshotMetaOf;
 use crate::type_config::alias::SnapshotOf;
 use crate::type_config::alias::VoteOf;
 
@@ -48,4 +58,80 @@ where C: RaftTypeConfig
         cancel: impl Future<Output = ReplicationClosed> + OptionalSend + 'static,
         option: RPCOption,
     ) -> Result<SnapshotResponse<C>, StreamingError<C>>;
+
+    /// Ask the target to fetch its snapshot from a seed node instead of from the leader.
+    ///
+    /// The node received this message should pass it to [`Raft::handle_seed_snapshot()`].
+    /// The default implementation returns [`Unreachable`] and the leader sends the snapshot
+    /// itself. See
+    /// [`RaftNetworkV2::seed_snapshot`](crate::network::v2::RaftNetworkV2::seed_snapshot).
+    ///
+    /// [`Raft::handle_seed_snapshot()`]: crate::raft::Raft::handle_seed_snapshot
+    #[since(version = "0.10.0")]
+    async fn seed_snapshot(
+        &mut self,
+        _req: SeedSnapshotRequest<C>,
+        _option: RPCOption,
+    ) -> Result<SeedSnapshotResponse<C>, RPCError<C>> {
+        Err(RPCError::Unreachable(Unreachable::new(&AnyError::error(
+            "seed_snapshot not implemented",
+        ))))
+    }
+
+    /// Fetch the current snapshot from the target, which is a seed node.
+    ///
+    /// The node received this message should pass it to [`Raft::handle_fetch_snapshot()`].
+    /// The default implementation returns [`Unreachable`].
+    ///
+    /// [`Raft::handle_fetch_snapshot()`]: crate::raft::Raft::handle_fetch_snapshot
+    #[since(version = "0.10.0")]
+    async fn fetch_snapshot(
+        &mut self,
+        _req: FetchSnapshotRequest<C>,
+        _option: RPCOption,
+    ) -> Result<FetchSnapshotResponse<C>, RPCError<C>> {
+        Err(RPCError::Unreachable(Unreachable::new(&AnyError::error(
+            "fetch_snapshot not implemented",
+        ))))
+    }
+
+    /// Send the locator of a snapshot stored out of band to the target, instead of the snapshot
+    /// data.
+    ///
+    /// The node received this message should pass it to [`Raft::install_snapshot_locator()`].
+    /// The default implementation returns [`Unreachable`] and the leader sends the snapshot data.
+    /// See
+    /// [`RaftNetworkV2::snapshot_locator`](crate::network::v2::RaftNetworkV2::snapshot_locator).
+    ///
+    /// [`Raft::install_snapshot_locator()`]: crate::raft::Raft::install_snapshot_locator
+    #[since(version = "0.10.0")]
+    async fn snapshot_locator(
+        &mut self,
+        _vote: VoteOf<C>,
+        _locator: SnapshotLocator<C>,
+        _option: RPCOption,
+    ) -> Result<SnapshotResponse<C>, RPCError<C>> {
+        Err(RPCError::Unreachable(Unreachable::new(&AnyError::error(
+            "snapshot_locator not implemented",
+        ))))
+    }
+
+    /// Send only the metadata of a snapshot to the target, which is a witness.
+    ///
+    /// The node received this message should pass it to [`Raft::install_snapshot_meta()`].
+    /// The default implementation returns [`Unreachable`]. See
+    /// [`RaftNetworkV2::snapshot_meta`](crate::network::v2::RaftNetworkV2::snapshot_meta).
+    ///
+    /// [`Raft::install_snapshot_meta()`]: crate::raft::Raft::install_snapshot_meta
+    #[since(version = "0.10.0")]
+    async fn snapshot_meta(
+        &mut self,
+        _vote: VoteOf<C>,
+        _meta: SnapshotMetaOf<C>,
+        _option: RPCOption,
+    ) -> Result<SnapshotResponse<C>, RPCError<C>> {
+        Err(RPCError::Unreachable(Unreachable::new(&AnyError::error(
+            "snapshot_meta not implemented",
+        ))))
+    }
 }
@@ -83,6 +83,7 @@ where
                 let req = input.next().await?;
 
                 let range = req.log_id_range();
+                let option = option.for_append_entries(&req);
 
                 let result = network.append_entries(req, option).await;
 
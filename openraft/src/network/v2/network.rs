@@ -20,12 +20,20 @@ use crate::network::RPCOption;
 use crate::network::stream_append_sequential;
 use crate::raft::AppendEntriesRequest;
 use crate::raft::AppendEntriesResponse;
+use crate::raft::FetchSnapshotRequest;
+use crate::raft::FetchSnapshotResponse;
+use crate::raft::SeedSnapshotRequest;
+use crate::raft::SeedSnapshotResponse;
 use crate::raft::SnapshotResponse;
 use crate::raft::StreamAppendResult;
 use crate::raft::VoteRequest;
 use crate::raft::VoteResponse;
+use crate::raft::message::ReadIndexRequest;
+use crate::raft::message::ReadIndexResponse;
 use crate::raft::message::TransferLeaderRequest;
 use crate::raft::message::TransferLeaderResponse;
+use crate::storage::SnapshotLocator;
+use crate::type_config::alias::SnapshotMetaOf;
 use crate::type_config::alias::SnapshotOf;
 use crate::type_config::alias::VoteOf;
 
@@ -62,10 +70,11 @@ use crate::type_config::alias::VoteOf;
 ///
 /// - [`NetAppend`] — unary AppendEntries
 /// - [`NetVote`] — RequestVote
-/// - [`NetSnapshot`] — full snapshot transfer
+/// - [`NetSnapshot`] — full snapshot transfer and seeding a snapshot from a peer
 /// - [`NetStreamAppend`] — stream-oriented AppendEntries (implement directly for native gRPC bidi
 ///   streaming or pipelining)
 /// - [`NetTransferLeader`] — TransferLeader notification
+/// - [`NetReadIndex`] — ReadIndex request from a follower to the leader
 /// - [`NetBackoff`] — backoff strategy on `Unreachable`
 ///
 /// See the [network chapter of the guide](crate::docs::getting_started#4-implement-raftnetwork)
@@ -85,6 +94,7 @@ use crate::type_config::alias::VoteOf;
 /// [`NetSnapshot`]: crate::network::NetSnapshot
 /// [`NetStreamAppend`]: crate::network::NetStreamAppend
 /// [`NetTransferLeader`]: crate::network::NetTransferLeader
+/// [`NetReadIndex`]: crate::network::NetReadIndex
 /// [`NetBackoff`]: crate::network::NetBackoff
 /// [`RaftNetworkFactory::Network`]: crate::network::RaftNetworkFactory::Network
 /// [correct-node]: `crate::docs::cluster_control::dynamic_membership#ensure-connection-to-the-correct-node`
@@ -186,6 +196,95 @@ where C: RaftTypeConfig
         option: RPCOption,
     ) -> Result<SnapshotResponse<C>, StreamingError<C>>;
 
+    /// Ask the target node to fetch its snapshot from a seed node instead of from the leader.
+    ///
+    /// The node received this message should pass it to [`Raft::handle_seed_snapshot()`],
+    /// which fetches the snapshot with [`fetch_snapshot()`](Self::fetch_snapshot) and installs it.
+    ///
+    /// This method provides a default implementation that just returns [`Unreachable`] error. In
+    /// case the application did not implement it, the leader sends its own snapshot, as if no seed
+    /// were set with [`Raft::set_snapshot_seed()`].
+    ///
+    /// [`Raft::handle_seed_snapshot()`]: crate::raft::Raft::handle_seed_snapshot
+    /// [`Raft::set_snapshot_seed()`]: crate::raft::Raft::set_snapshot_seed
+    #[since(version = "0.10.0")]
+    async fn seed_snapshot(
+        &mut self,
+        _req: SeedSnapshotRequest<C>,
+        _option: RPCOption,
+    ) -> Result<SeedSnapshotResponse<C>, RPCError<C>> {
+        Err(RPCError::Unreachable(Unreachable::new(&AnyError::error(
+            "seed_snapshot not implemented",
+        ))))
+    }
+
+    /// Fetch the current snapshot from the target node, which is a seed node.
+    ///
+    /// The node received this message should pass it to [`Raft::handle_fetch_snapshot()`].
+    ///
+    /// This method provides a default implementation that just returns [`Unreachable`] error.
+    ///
+    /// [`Raft::handle_fetch_snapshot()`]: crate::raft::Raft::handle_fetch_snapshot
+    #[since(version = "0.10.0")]
+    async fn fetch_snapshot(
+        &mut self,
+        _req: FetchSnapshotRequest<C>,
+        _option: RPCOption,
+    ) -> Result<FetchSnapshotResponse<C>, RPCError<C>> {
+        Err(RPCError::Unreachable(Unreachable::new(&AnyError::error(
+            "fetch_snapshot not implemented",
+        ))))
+    }
+
+    /// Send the locator of a snapshot stored out of band to the target node, instead of the
+    /// snapshot data.
+    ///
+    /// It is used when the leader's state machine returns a locator from
+    /// [`RaftStateMachine::get_snapshot_locator()`]. The node received this message should pass it
+    /// to [`Raft::install_snapshot_locator()`], whose state machine fetches the data itself.
+    ///
+    /// This method provides a default implementation that just returns [`Unreachable`] error. In
+    /// case the application did not implement it, the leader sends the snapshot data with
+    /// [`full_snapshot()`](Self::full_snapshot).
+    ///
+    /// [`RaftStateMachine::get_snapshot_locator()`]: crate::storage::RaftStateMachine::get_snapshot_locator
+    /// [`Raft::install_snapshot_locator()`]: crate::raft::Raft::install_snapshot_locator
+    #[since(version = "0.10.0")]
+    async fn snapshot_locator(
+        &mut self,
+        _vote: VoteOf<C>,
+        _locator: SnapshotLocator<C>,
+        _option: RPCOption,
+    ) -> Result<SnapshotResponse<C>, RPCError<C>> {
+        Err(RPCError::Unreachable(Unreachable::new(&AnyError::error(
+            "snapshot_locator not implemented",
+        ))))
+    }
+
+    /// Send only the metadata of a snapshot to the target node, which is a witness.
+    ///
+    /// A [`NodeRole::Witness`] keeps no state machine data: when it falls behind the purged logs
+    /// of the leader, it is sent the metadata of the snapshot instead of the snapshot. The node
+    /// received this message should pass it to [`Raft::install_snapshot_meta()`].
+    ///
+    /// This method provides a default implementation that just returns [`Unreachable`] error. An
+    /// application with witnesses has to implement it, otherwise a witness that falls behind the
+    /// purged logs can not catch up.
+    ///
+    /// [`NodeRole::Witness`]: crate::NodeRole::Witness
+    /// [`Raft::install_snapshot_meta()`]: crate::raft::Raft::install_snapshot_meta
+    #[since(version = "0.10.0")]
+    async fn snapshot_meta(
+        &mut self,
+        _vote: VoteOf<C>,
+        _meta: SnapshotMetaOf<C>,
+        _option: RPCOption,
+    ) -> Result<SnapshotResponse<C>, RPCError<C>> {
+        Err(RPCError::Unreachable(Unreachable::new(&AnyError::error(
+            "snapshot_meta not implemented",
+        ))))
+    }
+
     /// Send TransferLeader message to the target node.
     ///
     /// The node received this message should pass it to [`Raft::handle_transfer_leader()`].
@@ -207,6 +306,27 @@ where C: RaftTypeConfig
         ))))
     }
 
+    /// Send ReadIndex message to the leader.
+    ///
+    /// The node received this message should pass it to [`Raft::handle_read_index()`].
+    ///
+    /// This method provides a default implementation that just returns [`Unreachable`] error. In
+    /// case the application did not implement it, [`Raft::follower_read_index()`] fails and the
+    /// read has to be forwarded to the leader.
+    ///
+    /// [`Raft::handle_read_index()`]: crate::raft::Raft::handle_read_index
+    /// [`Raft::follower_read_index()`]: crate::raft::Raft::follower_read_index
+    #[since(version = "0.10.0")]
+    async fn read_index(
+        &mut self,
+        _req: ReadIndexRequest<C>,
+        _option: RPCOption,
+    ) -> Result<ReadIndexResponse<C>, RPCError<C>> {
+        Err(RPCError::Unreachable(Unreachable::new(&AnyError::error(
+            "read_index not implemented",
+        ))))
+    }
+
     /// Build a backoff instance if the target node is temporarily(or permanently) unreachable.
     ///
     /// When a [`Unreachable`](`crate::error::Unreachable`) error is returned from the `Network`
@@ -237,6 +357,7 @@ where C: RaftTypeConfig
 // the corresponding RaftNetworkV2 methods.
 
 use crate::network::NetBackoff;
+use crate::network::NetReadIndex;
 use crate::network::NetSnapshot;
 use crate::network::NetStreamAppend;
 use crate::network::NetTransferLeader;
@@ -297,6 +418,40 @@ where
     ) -> Result<SnapshotResponse<C>, StreamingError<C>> {
         RaftNetworkV2::full_snapshot(self, vote, snapshot, cancel, option).await
     }
+
+    async fn seed_snapshot(
+        &mut self,
+        req: SeedSnapshotRequest<C>,
+        option: RPCOption,
+    ) -> Result<SeedSnapshotResponse<C>, RPCError<C>> {
+        RaftNetworkV2::seed_snapshot(self, req, option).await
+    }
+
+    async fn fetch_snapshot(
+        &mut self,
+        req: FetchSnapshotRequest<C>,
+        option: RPCOption,
+    ) -> Result<FetchSnapshotResponse<C>, RPCError<C>> {
+        RaftNetworkV2::fetch_snapshot(self, req, option).await
+    }
+
+    async fn snapshot_locator(
+        &mut self,
+        vote: VoteOf<C>,
+        locator: SnapshotLocator<C>,
+        option: RPCOption,
+    ) -> Result<SnapshotResponse<C>, RPCError<C>> {
+        RaftNetworkV2::snapshot_locator(self, vote, locator, option).await
+    }
+
+    async fn snapshot_meta(
+        &mut self,
+        vote: VoteOf<C>,
+        meta: SnapshotMetaOf<C>,
+        option: RPCOption,
+    ) -> Result<SnapshotResponse<C>, RPCError<C>> {
+        RaftNetworkV2::snapshot_meta(self, vote, meta, option).await
+    }
 }
 
 #[allow(clippy::manual_async_fn)]
@@ -314,6 +469,21 @@ where
     }
 }
 
+#[allow(clippy::manual_async_fn)]
+impl<C, T> NetReadIndex<C> for T
+where
+    C: RaftTypeConfig,
+    T: RaftNetworkV2<C> + ?Sized,
+{
+    async fn read_index(
+        &mut self,
+        req: ReadIndexRequest<C>,
+        option: RPCOption,
+    ) -> Result<ReadIndexResponse<C>, RPCError<C>> {
+        RaftNetworkV2::read_index(self, req, option).await
+    }
+}
+
 impl<C, T> NetStreamAppend<C> for T
 where
     C: RaftTypeConfig,
@@ -12,6 +12,7 @@
 //! - [`BasicNode`] - Node with network address string
 //! - [`NodeInfo`] - Node with a Raft address and user-defined data
 //! - [`EmptyNode`] - Minimal node with no metadata
+//! - [`PlacedNode`] - Node with network address, region, zone and tags
 //!
 //! ## Overview
 //!
@@ -22,12 +23,16 @@
 //! Applications can use built-in types or define custom [`Node`] implementations to store
 //! additional metadata like datacenter location, priority, or capabilities.
 
+use std::collections::BTreeSet;
 use std::fmt::Debug;
 use std::fmt::Display;
 use std::fmt::Formatter;
 use std::hash::Hash;
 
+use openraft_macros::since;
+
 use crate::base::OptionalFeatures;
+use crate::entry::EncodeContent;
 
 /// A Raft node's ID.
 ///
@@ -78,6 +83,10 @@ impl Display for EmptyNode {
     }
 }
 
+impl EncodeContent for EmptyNode {
+    fn encode_content(&self, _buf: &mut Vec<u8>) {}
+}
+
 /// An implementation of the [`Node`] trait that contains minimal node information.
 ///
 /// The most common usage is to store the connecting address of a node.
@@ -116,6 +125,12 @@ impl Display for BasicNode {
     }
 }
 
+impl EncodeContent for BasicNode {
+    fn encode_content(&self, buf: &mut Vec<u8>) {
+        self.addr.encode_content(buf);
+    }
+}
+
 /// An implementation of the [`Node`] trait that contains a Raft address and user-defined data.
 ///
 /// It is useful when an application wants OpenRaft to store one address for
@@ -156,6 +171,132 @@ impl Display for NodeInfo {
     }
 }
 
+impl EncodeContent for NodeInfo {
+    fn encode_content(&self, buf: &mut Vec<u8>) {
+        self.raft_addr.encode_content(buf);
+        self.data.encode_content(buf);
+    }
+}
+
+/// Where a [`Node`] is placed: its region, zone and tags.
+///
+/// A [`Membership`] of nodes implementing it can be built with validation by
+/// [`Membership::new_placed()`] and queried by region or tag, e.g., by placement logic that keeps
+/// a quorum within a region, or skips the nodes tagged as never to lead.
+///
+/// [`PlacedNode`] is a built-in implementation.
+///
+/// [`Membership`]: crate::Membership
+/// [`Membership::new_placed()`]: crate::Membership::new_placed
+#[since(version = "0.10.0")]
+pub trait NodePlacement {
+    /// The region the node is in, e.g., `"us-east-1"`.
+    fn region(&self) -> &str;
+
+    /// The zone within the region the node is in, e.g., `"us-east-1a"`.
+    fn zone(&self) -> &str;
+
+    /// Returns `true` if the node has the tag `tag`.
+    fn has_tag(&self, tag: &str) -> bool;
+
+    /// Returns an iterator of all tags of the node.
+    fn tags(&self) -> impl Iterator<Item = &str>;
+
+    /// The failure domain of the node, used by a domain-aware membership built with
+    /// [`Membership::new_domain_aware()`].
+    ///
+    /// It is the zone by default. Override it to use another failure domain, e.g., the rack.
+    ///
+    /// [`Membership::new_domain_aware()`]: crate::Membership::new_domain_aware
+    #[since(version = "0.10.0")]
+    fn failure_domain(&self) -> &str {
+        self.zone()
+    }
+}
+
+/// An implementation of the [`Node`] trait that contains a network address and where the node is
+/// placed.
+///
+/// ```
+/// use openraft::NodePlacement;
+/// use openraft::PlacedNode;
+///
+/// let node = PlacedNode::new("10.0.0.1:5001", "us-east-1", "us-east-1a").with_tag("never-lead");
+/// assert!(node.has_tag("never-lead"));
+/// ```
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct PlacedNode {
+    /// Address used by [`RaftNetworkV2`](crate::RaftNetworkV2) to contact the target node.
+    pub addr: String,
+
+    /// The region the node is in.
+    pub region: String,
+
+    /// The zone within the region the node is in.
+    pub zone: String,
+
+    /// User-defined tags, e.g., flags for placement logic.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tags: BTreeSet<String>,
+}
+
+impl PlacedNode {
+    /// Creates a [`PlacedNode`] without tags.
+    pub fn new(addr: impl ToString, region: impl ToString, zone: impl ToString) -> Self {
+        Self {
+            addr: addr.to_string(),
+            region: region.to_string(),
+            zone: zone.to_string(),
+            tags: BTreeSet::new(),
+        }
+    }
+
+    /// Add a tag.
+    pub fn with_tag(mut self, tag: impl ToString) -> Self {
+        self.tags.insert(tag.to_string());
+        self
+    }
+}
+
+impl NodePlacement for PlacedNode {
+    fn region(&self) -> &str {
+        &self.region
+    }
+
+    fn zone(&self) -> &str {
+        &self.zone
+    }
+
+    fn has_tag(&self, tag: &str) -> bool {
+        self.tags.contains(tag)
+    }
+
+    fn tags(&self) -> impl Iterator<Item = &str> {
+        self.tags.iter().map(|x| x.as_str())
+    }
+}
+
+impl Display for PlacedNode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}@{}/{}", self.addr, self.region, self.zone)?;
+        if !self.tags.is_empty() {
+            write!(f, "[{}]", self.tags.iter().cloned().collect::<Vec<_>>().join(","))?;
+        }
+        Ok(())
+    }
+}
+
+impl EncodeContent for PlacedNode {
+    fn encode_content(&self, buf: &mut Vec<u8>) {
+        self.addr.encode_content(buf);
+        self.region.encode_content(buf);
+        self.zone.encode_content(buf);
+        self.tags.encode_content(buf);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::fmt;
@@ -180,4 +321,22 @@ mod tests {
 
         assert_node_id(&AutoNodeId);
     }
+
+    #[test]
+    fn test_placed_node() {
+        use crate::NodePlacement;
+        use crate::PlacedNode;
+
+        let node = PlacedNode::new("a:1", "r1", "z1");
+        assert_eq!("a:1@r1/z1", node.to_string());
+
+        let node = node.with_tag("never-lead").with_tag("ssd");
+        assert_eq!("r1", node.region());
+        assert_eq!("z1", node.zone());
+        assert!(node.has_tag("ssd"));
+        assert!(!node.has_tag("hdd"));
+        assert_eq!(vec!["never-lead", "ssd"], node.tags().collect::<Vec<_>>());
+        assert_eq!("z1", node.failure_domain());
+        assert_eq!("a:1@r1/z1[never-lead,ssd]", node.to_string());
+    }
 }
@@ -103,7 +103,7 @@ where C: RaftTypeConfig
                 lid > log_id_range.prev.as_ref()
             }
             Inflight::Snapshot { inflight_id: _ } => false,
-            Inflight::LogsSince { prev, .. } => {
+            Inflight::LogsSince { prev, .. } | Inflight::SnapshotWithTail { prev, .. } => {
                 // All logs after prev are inflight in streaming mode
                 let lid = Some(upto);
                 lid > prev.as_ref()
@@ -184,10 +184,41 @@ where C: RaftTypeConfig
         Ok(&self.inflight)
     }
 
+    /// Stream the logs after the local snapshot along with an inflight snapshot.
+    ///
+    /// The logs after the snapshot are sent at once, instead of after the snapshot is installed.
+    /// It does nothing if no snapshot is inflight, or the snapshot does not cover the purged logs.
+    pub(crate) fn pipeline_snapshot_tail(&mut self, log_state: &mut RaftState<C>) {
+        let Inflight::Snapshot { inflight_id } = self.inflight else {
+            return;
+        };
+
+        let snapshot_last = log_state.snapshot_last_log_id().cloned();
+        if snapshot_last.as_ref() < log_state.purge_upto() {
+            return;
+        }
+
+        let tail_id = log_state.new_inflight_id();
+        self.inflight = Inflight::snapshot_with_tail(inflight_id, snapshot_last, tail_id);
+    }
+
+    /// Return the index of the next log to send and the number of logs in flight, for metrics.
+    ///
+    /// `last_next` is the index after the last log on the leader: in streaming mode, every log
+    /// after `prev` up to it is in flight.
+    pub(crate) fn sending_position(&self, last_next: u64) -> (u64, u64) {
+        match &self.inflight {
+            Inflight::None | Inflight::Snapshot { .. } => (self.sending_start().0, 0),
+            Inflight::Logs { log_id_range, .. } => (log_id_range.last.next_index(), log_id_range.len()),
+            Inflight::LogsSince { prev, .. } | Inflight::SnapshotWithTail { prev, .. } => {
+                (last_next, last_next.saturating_sub(prev.next_index()))
+            }
+        }
+    }
+
     /// Return the index range (`[start,end]`) of the first log in the next AppendEntries.
     ///
     /// The returned range is left close and right close.
-    #[allow(dead_code)]
     pub(crate) fn sending_start(&self) -> (u64, u64) {
         let mid = Self::calc_mid(self.matching().next_index(), self.searching_end);
         (mid, self.searching_end)
@@ -242,6 +273,7 @@ where C: RaftTypeConfig
             }
             Inflight::Snapshot { inflight_id: _ } => {}
             Inflight::LogsSince { .. } => {}
+            Inflight::SnapshotWithTail { .. } => {}
         }
         Ok(())
     }
@@ -48,6 +48,33 @@ fn test_is_log_range_inflight() -> anyhow::Result<()> {
     assert_eq!(true, pe.is_log_range_inflight(&log_id(3)));
     assert_eq!(true, pe.is_log_range_inflight(&log_id(100)));
 
+    // SnapshotWithTail: all logs after the snapshot are inflight
+    pe.inflight = Inflight::snapshot_with_tail(InflightId::new(0), Some(log_id(2)), InflightId::new(1));
+    assert_eq!(false, pe.is_log_range_inflight(&log_id(2)));
+    assert_eq!(true, pe.is_log_range_inflight(&log_id(3)));
+
+    Ok(())
+}
+
+#[test]
+fn test_sending_position() -> anyhow::Result<()> {
+    let mut pe = ProgressEntry::<UTConfig>::empty(StreamId::new(0), 20);
+    pe.matching = Some(log_id(19));
+    assert_eq!((20, 0), pe.sending_position(30));
+
+    pe.inflight = inflight_logs(19, 24);
+    assert_eq!((25, 5), pe.sending_position(30));
+
+    pe.inflight = Inflight::snapshot(InflightId::new(0));
+    assert_eq!((20, 0), pe.sending_position(30));
+
+    // Streaming: every log after prev up to the last log is inflight
+    pe.inflight = Inflight::logs_since(Some(log_id(19)), InflightId::new(0));
+    assert_eq!((30, 10), pe.sending_position(30));
+
+    pe.inflight = Inflight::snapshot_with_tail(InflightId::new(0), Some(log_id(24)), InflightId::new(1));
+    assert_eq!((30, 5), pe.sending_position(30));
+
     Ok(())
 }
 
@@ -109,6 +136,7 @@ fn new_raft_state(purge_upto: u64, snap_last: u64, last: u64) -> RaftState<UTCon
         last_log_id: Some(log_id(snap_last)),
         last_membership: StoredMembershipOf::<UTConfig>::default(),
         snapshot_id: "".to_string(),
+        base_snapshot_id: None,
     };
 
     st.log_ids = LogIdList::new(None, [log_id(purge_upto - 1), log_id(last)]);
@@ -358,3 +386,33 @@ fn test_next_send_pipeline_mode() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_pipeline_snapshot_tail() -> anyhow::Result<()> {
+    // Snapshot inflight: stream the logs after the snapshot along with it
+    {
+        let mut pe = ProgressEntry::<UTConfig>::empty(StreamId::new(0), 4);
+        pe.matching = Some(log_id(4));
+
+        let mut state = new_raft_state(6, 10, 20);
+        pe.next_send(&mut state, 100).ok();
+        pe.pipeline_snapshot_tail(&mut state);
+        assert_eq!(
+            Inflight::snapshot_with_tail(InflightId::new(1), Some(log_id(10)), InflightId::new(2)),
+            pe.inflight
+        );
+    }
+
+    // Logs inflight: nothing to do
+    {
+        let mut pe = ProgressEntry::<UTConfig>::empty(StreamId::new(0), 7);
+        pe.matching = Some(log_id(4));
+
+        let mut state = new_raft_state(6, 10, 20);
+        pe.next_send(&mut state, 100).ok();
+        pe.pipeline_snapshot_tail(&mut state);
+        assert_eq!(inflight_logs(6, 20).with_id(1), pe.inflight);
+    }
+
+    Ok(())
+}
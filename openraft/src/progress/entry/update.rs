@@ -121,6 +121,12 @@ where C: RaftTypeConfig
             return;
         }
 
+        // The log tail streamed along with a snapshot may be acknowledged after a greater
+        // snapshot is installed.
+        if matching < self.entry.matching {
+            return;
+        }
+
         debug_assert!(matching.as_ref() >= self.entry.matching());
         self.entry.matching = matching;
 
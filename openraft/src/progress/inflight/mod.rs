@@ -44,6 +44,17 @@ where C: RaftTypeConfig
     Snapshot {
         inflight_id: InflightId,
     },
+
+    /// Replicating a snapshot, while streaming the logs after it at the same time.
+    ///
+    /// `prev` is the last log id of the snapshot expected to be installed. The snapshot and the
+    /// log stream are two requests: they are responded to separately, with `snapshot_id` and
+    /// `tail_id`.
+    SnapshotWithTail {
+        snapshot_id: InflightId,
+        prev: Option<LogIdOf<C>>,
+        tail_id: InflightId,
+    },
 }
 
 impl<C> Copy for Inflight<C>
@@ -62,6 +73,7 @@ where C: RaftTypeConfig
             Inflight::Logs { log_id_range: r, .. } => r.validate(),
             Inflight::LogsSince { .. } => Ok(()),
             Inflight::Snapshot { .. } => Ok(()),
+            Inflight::SnapshotWithTail { .. } => Ok(()),
         }
     }
 }
@@ -80,6 +92,15 @@ where C: RaftTypeConfig
                 write!(f, "LogsSince:{:?}, inflight_id:{}", prev, inflight_id)
             }
             Inflight::Snapshot { inflight_id } => write!(f, "Snapshot, inflight_id:{}", inflight_id),
+            Inflight::SnapshotWithTail {
+                snapshot_id,
+                prev,
+                tail_id,
+            } => write!(
+                f,
+                "SnapshotWithTail:{:?}, snapshot_id:{}, tail_id:{}",
+                prev, snapshot_id, tail_id
+            ),
         }
     }
 }
@@ -105,6 +126,16 @@ where C: RaftTypeConfig
         Self::Snapshot { inflight_id }
     }
 
+    /// Create inflight state for sending a snapshot and streaming the logs after it at the same
+    /// time.
+    pub(crate) fn snapshot_with_tail(snapshot_id: InflightId, prev: Option<LogIdOf<C>>, tail_id: InflightId) -> Self {
+        Self::SnapshotWithTail {
+            snapshot_id,
+            prev,
+            tail_id,
+        }
+    }
+
     /// Create inflight state for streaming logs after a given log id.
     #[cfg_attr(not(test), allow(dead_code))]
     pub(crate) fn logs_since(prev: Option<LogIdOf<C>>, inflight_id: InflightId) -> Self {
@@ -129,6 +160,15 @@ where C: RaftTypeConfig
                 prev,
                 inflight_id: InflightId::new(id),
             },
+            Inflight::SnapshotWithTail {
+                snapshot_id: _,
+                prev,
+                tail_id: _,
+            } => Inflight::SnapshotWithTail {
+                snapshot_id: InflightId::new(id),
+                prev,
+                tail_id: InflightId::new(id + 1),
+            },
         }
     }
 
@@ -146,10 +186,8 @@ where C: RaftTypeConfig
         matches!(self, Inflight::LogsSince { .. })
     }
 
-    // test it if used
-    #[allow(dead_code)]
     pub(crate) fn is_sending_snapshot(&self) -> bool {
-        matches!(self, Inflight::Snapshot { .. })
+        matches!(self, Inflight::Snapshot { .. } | Inflight::SnapshotWithTail { .. })
     }
 
     /// Update inflight state when log up to `upto` is acknowledged by a follower/learner.
@@ -159,7 +197,7 @@ where C: RaftTypeConfig
     pub(crate) fn ack(&mut self, upto: Option<LogIdOf<C>>, from_inflight_id: InflightId) {
         match self {
             Inflight::None => {
-                unreachable!("no inflight data")
+                // The log tail of an abandoned `SnapshotWithTail` may still be acknowledged.
             }
             Inflight::Logs {
                 log_id_range,
@@ -191,6 +229,24 @@ where C: RaftTypeConfig
 
                 *prev = upto;
             }
+            Inflight::SnapshotWithTail {
+                snapshot_id,
+                prev,
+                tail_id,
+            } => {
+                if from_inflight_id == *tail_id {
+                    // The tail is accepted only after the snapshot is installed.
+                    *self = Inflight::logs_since(upto, *tail_id);
+                } else if from_inflight_id == *snapshot_id {
+                    if &upto == prev {
+                        *self = Inflight::logs_since(upto, *tail_id);
+                    } else {
+                        // A snapshot other than the expected one is installed: the tail does not
+                        // follow it. Abandon the tail and start over from the new matching.
+                        *self = Inflight::None;
+                    }
+                }
+            }
         }
     }
 
@@ -220,6 +276,18 @@ where C: RaftTypeConfig
 
                 unreachable!("sending snapshot should not conflict");
             }
+            Inflight::SnapshotWithTail {
+                snapshot_id, tail_id, ..
+            } => {
+                if from_inflight_id == *snapshot_id {
+                    unreachable!("sending snapshot should not conflict");
+                }
+
+                if from_inflight_id == *tail_id {
+                    // The follower did not hold the tail: keep waiting for the snapshot.
+                    *self = Inflight::snapshot(*snapshot_id);
+                }
+            }
             Inflight::LogsSince { prev: _, inflight_id } => {
                 if *inflight_id != from_inflight_id {
                     return;
@@ -229,4 +297,21 @@ where C: RaftTypeConfig
             }
         }
     }
+
+    /// Update inflight state when sending to the follower/learner failed.
+    ///
+    /// If only the log tail of a `SnapshotWithTail` failed, the snapshot is still inflight.
+    /// Otherwise, the inflight is reset and will be retried.
+    pub(crate) fn fail(&mut self, from_inflight_id: Option<InflightId>) {
+        match self {
+            Inflight::SnapshotWithTail {
+                snapshot_id, tail_id, ..
+            } if from_inflight_id == Some(*tail_id) => {
+                *self = Inflight::snapshot(*snapshot_id);
+            }
+            _ => {
+                *self = Inflight::None;
+            }
+        }
+    }
 }
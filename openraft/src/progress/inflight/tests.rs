@@ -250,3 +250,71 @@ fn test_inflight_logs_since_conflict() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_inflight_snapshot_with_tail_ack() -> anyhow::Result<()> {
+    let with_tail =
+        || Inflight::<UTConfig>::snapshot_with_tail(InflightId::new(1), Some(log_id(5)), InflightId::new(2));
+
+    // Snapshot installed as expected: keep streaming the tail
+    {
+        let mut f = with_tail();
+        f.ack(Some(log_id(5)), InflightId::new(1));
+        assert_eq!(Inflight::logs_since(Some(log_id(5)), InflightId::new(2)), f);
+    }
+
+    // Tail acked before the snapshot response
+    {
+        let mut f = with_tail();
+        f.ack(Some(log_id(7)), InflightId::new(2));
+        assert_eq!(Inflight::logs_since(Some(log_id(7)), InflightId::new(2)), f);
+
+        f.ack(Some(log_id(5)), InflightId::new(1));
+        assert_eq!(
+            Inflight::logs_since(Some(log_id(7)), InflightId::new(2)),
+            f,
+            "late snapshot ack is ignored"
+        );
+    }
+
+    // Another snapshot is installed: abandon the tail
+    {
+        let mut f = with_tail();
+        f.ack(Some(log_id(8)), InflightId::new(1));
+        assert_eq!(Inflight::None, f);
+
+        f.ack(Some(log_id(6)), InflightId::new(2));
+        assert_eq!(Inflight::None, f, "ack of the abandoned tail is ignored");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_inflight_snapshot_with_tail_conflict_and_fail() -> anyhow::Result<()> {
+    let with_tail =
+        || Inflight::<UTConfig>::snapshot_with_tail(InflightId::new(1), Some(log_id(5)), InflightId::new(2));
+
+    // The tail is rejected: keep waiting for the snapshot
+    {
+        let mut f = with_tail();
+        f.conflict(5, InflightId::new(2));
+        assert_eq!(Inflight::snapshot(InflightId::new(1)), f);
+    }
+
+    // Sending the tail failed: keep waiting for the snapshot
+    {
+        let mut f = with_tail();
+        f.fail(Some(InflightId::new(2)));
+        assert_eq!(Inflight::snapshot(InflightId::new(1)), f);
+    }
+
+    // Sending the snapshot failed: retry
+    {
+        let mut f = with_tail();
+        f.fail(Some(InflightId::new(1)));
+        assert_eq!(Inflight::None, f);
+    }
+
+    Ok(())
+}
@@ -34,7 +34,11 @@ where
     /// Which nodes have granted the vote at certain time point.
     progress: VecProgress<C::NodeId, bool, bool, QS>,
 
-    quorum_set: QS,
+    /// The quorum set the leader established by this vote commits with.
+    ///
+    /// It differs from the one the votes are counted with if the election and commit quorums are
+    /// different, see [`QuorumPolicy`](crate::membership::QuorumPolicy).
+    leader_quorum_set: QS,
 
     learner_ids: Vec<C::NodeId>,
 
@@ -68,6 +72,7 @@ where
         vote: VoteOf<C>,
         last_log_id: Option<LogIdOf<C>>,
         quorum_set: QS,
+        leader_quorum_set: QS,
         learner_ids: impl IntoIterator<Item = C::NodeId>,
         progress_id_gen: SharedIdGenerator,
     ) -> Self {
@@ -75,8 +80,8 @@ where
             starting_time,
             vote,
             last_log_id,
-            progress: VecProgress::new(quorum_set.clone(), [], || false),
-            quorum_set,
+            progress: VecProgress::new(quorum_set, [], || false),
+            leader_quorum_set,
             learner_ids: learner_ids.into_iter().collect::<Vec<_>>(),
             progress_id_gen,
         }
@@ -120,7 +125,6 @@ where
     }
 
     /// Return the node ids that have granted this vote.
-    #[allow(dead_code)]
     pub(crate) fn granters(&self) -> impl Iterator<Item = C::NodeId> + '_ {
         self.progress().iter().filter(|item| item.val).map(|item| item.id.clone())
     }
@@ -142,7 +146,7 @@ where
 
         Leader::new(
             vote,
-            self.quorum_set.clone(),
+            self.leader_quorum_set,
             self.learner_ids,
             last_leader_log_ids, // already Option<LeaderLogIds>
             self.progress_id_gen,
@@ -1,10 +1,18 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::fmt;
+use std::ops::Range;
+use std::time::Duration;
 
+use crate::Instant;
 use crate::LogIdOptionExt;
 use crate::RaftTypeConfig;
+use crate::SnapshotId;
 use crate::base::shared_id_generator::SharedIdGenerator;
 use crate::display_ext::DisplayInstantExt;
 use crate::engine::leader_log_ids::LeaderLogIds;
+use crate::errors::ReservedIndexMismatch;
+use crate::metrics::LeaderSince;
 use crate::progress::Progress;
 use crate::progress::VecProgress;
 use crate::progress::entry::ProgressEntry;
@@ -52,8 +60,17 @@ where C: RaftTypeConfig
     /// The time to send next heartbeat.
     pub(crate) next_heartbeat: InstantOf<C>,
 
+    /// When this leader is established.
+    pub(crate) leader_since: LeaderSince<C>,
+
     last_log_id: Option<LogIdOf<C>>,
 
+    /// The log indexes reserved by the application that are not yet filled.
+    ///
+    /// It always starts right after `last_log_id`: it is dropped when any other log id is
+    /// assigned.
+    reserved: Option<Range<u64>>,
+
     /// The log id of the first log entry proposed by this leader,
     /// i.e., the `noop` log(AKA blank log) after leader established.
     ///
@@ -77,6 +94,46 @@ where C: RaftTypeConfig
     ///
     /// [`docs::leader_lease`]: `crate::docs::protocol::replication::leader_lease`
     pub(crate) clock_progress: VecProgress<C::NodeId, Option<InstantOf<C>>, Option<InstantOf<C>>, QS>,
+
+    /// The round-trip time of the last request each node acknowledged, for metrics.
+    pub(crate) round_trips: BTreeMap<C::NodeId, Duration>,
+
+    /// The learners to promote to voters once they have caught up.
+    ///
+    /// They are added by [`ChangeMembers::AddVotersWhenCaughtUp`](crate::ChangeMembers).
+    pub(crate) promote_when_caught_up: BTreeSet<C::NodeId>,
+
+    /// The log id of the joint membership this leader proposed to promote learners.
+    ///
+    /// The leader flattens it to a uniform membership once it is committed.
+    pub(crate) promoting: Option<LogIdOf<C>>,
+
+    /// The log id of the joint membership this leader proposed to evict unreachable voters.
+    ///
+    /// The leader flattens it to a uniform membership once it is committed.
+    pub(crate) evicting: Option<LogIdOf<C>>,
+
+    /// The snapshot each follower last installed from this leader.
+    ///
+    /// A later snapshot is sent to such a follower as a delta relative to it, see
+    /// [`RaftStateMachine::get_delta_snapshot()`](crate::storage::RaftStateMachine::get_delta_snapshot).
+    pub(crate) follower_snapshots: BTreeMap<C::NodeId, SnapshotId>,
+
+    /// The node each follower is asked to fetch its snapshot from, instead of from this leader.
+    ///
+    /// Set by [`Raft::set_snapshot_seed()`](crate::Raft::set_snapshot_seed).
+    pub(crate) snapshot_seeds: BTreeMap<C::NodeId, C::NodeId>,
+
+    /// The deadlines of the writes this leader proposed that are not committed yet, by log index.
+    ///
+    /// A write is not committed by this leader once its deadline passes, see
+    /// [`RaftEntry::deadline()`](crate::entry::RaftEntry::deadline).
+    pub(crate) write_deadlines: BTreeMap<u64, u64>,
+
+    /// The index to record in the first entry this leader proposes, if this node re-establishes
+    /// itself after abandoning its previous term, see
+    /// [`RaftEntry::expire_from()`](crate::entry::RaftEntry::expire_from).
+    pub(crate) expire_from: Option<u64>,
 }
 
 impl<C, QS> Leader<C, QS>
@@ -137,13 +194,23 @@ where
             transfer_to: None,
             committed_vote: vote,
             next_heartbeat: C::now(),
+            leader_since: LeaderSince::now(),
             last_log_id: last_log_id.clone(),
+            reserved: None,
             noop_log_id,
             progress: VecProgress::new(quorum_set.clone(), learner_ids.iter().cloned(), || {
                 let stream_id = StreamId::new(id_gen.next_id());
                 ProgressEntry::empty(stream_id, last_log_id.next_index())
             }),
             clock_progress: VecProgress::new(quorum_set, learner_ids, || None),
+            round_trips: BTreeMap::new(),
+            promote_when_caught_up: BTreeSet::new(),
+            promoting: None,
+            evicting: None,
+            follower_snapshots: BTreeMap::new(),
+            snapshot_seeds: BTreeMap::new(),
+            write_deadlines: BTreeMap::new(),
+            expire_from: None,
         }
     }
 
@@ -163,6 +230,17 @@ where
         &self.committed_vote
     }
 
+    /// Returns the index of the first write that is not committed and whose deadline is earlier
+    /// than `now_ms`, the wall-clock time in milliseconds since the UNIX epoch.
+    pub(crate) fn first_expired_write(&self, now_ms: u64) -> Option<u64> {
+        self.write_deadlines.iter().find(|(_, deadline)| **deadline < now_ms).map(|(index, _)| *index)
+    }
+
+    /// Forget the deadlines of the writes before `index`, which are committed.
+    pub(crate) fn forget_write_deadlines(&mut self, index: u64) {
+        self.write_deadlines = self.write_deadlines.split_off(&index);
+    }
+
     pub(crate) fn mark_transfer(&mut self, to: C::NodeId) {
         self.transfer_to = Some(to);
     }
@@ -176,8 +254,69 @@ where
     /// Returns a [`LeaderLogIds`] containing the allocated log IDs, or `None` if count is 0.
     /// Updates `self.last_log_id` to the last allocated log ID.
     ///
+    /// Unfilled reserved log indexes are dropped, since the allocated log IDs take them.
+    ///
     /// The caller is responsible for assigning the log IDs to entries.
     pub(crate) fn assign_log_ids(&mut self, count: usize) -> Option<LeaderLogIds<CommittedLeaderIdOf<C>>> {
+        if count > 0
+            && let Some(reserved) = self.reserved.take()
+        {
+            tracing::info!("drop unfilled reserved log indexes: {:?}", reserved);
+        }
+
+        self.allocate_log_ids(count)
+    }
+
+    /// Reserve `count` log indexes, to be filled later with
+    /// [`assign_reserved_log_ids()`](Self::assign_reserved_log_ids).
+    ///
+    /// The returned indexes follow the unfilled reserved ones, or the last log id if there are
+    /// none.
+    pub(crate) fn reserve_log_indexes(&mut self, count: u64) -> Range<u64> {
+        let unfilled = self.reserved.get_or_insert_with(|| {
+            let next = self.last_log_id.next_index();
+            next..next
+        });
+
+        let start = unfilled.end;
+        unfilled.end += count;
+        let end = unfilled.end;
+
+        if unfilled.is_empty() {
+            self.reserved = None;
+        }
+
+        start..end
+    }
+
+    /// Allocate `count` reserved log IDs starting at `index`.
+    ///
+    /// It returns [`ReservedIndexMismatch`] if `index` is not the first unfilled reserved index,
+    /// or there are less than `count` unfilled reserved indexes.
+    pub(crate) fn assign_reserved_log_ids(
+        &mut self,
+        index: u64,
+        count: usize,
+    ) -> Result<Option<LeaderLogIds<CommittedLeaderIdOf<C>>>, ReservedIndexMismatch> {
+        let end = index + count as u64;
+
+        let Some(unfilled) = self.reserved.as_mut().filter(|r| r.start == index && end <= r.end) else {
+            return Err(ReservedIndexMismatch {
+                index,
+                count: count as u64,
+                reserved: self.reserved.clone(),
+            });
+        };
+
+        unfilled.start = end;
+        if unfilled.is_empty() {
+            self.reserved = None;
+        }
+
+        Ok(self.allocate_log_ids(count))
+    }
+
+    fn allocate_log_ids(&mut self, count: usize) -> Option<LeaderLogIds<CommittedLeaderIdOf<C>>> {
         debug_assert!(self.transfer_to.is_none(), "leader is disabled to propose new log");
 
         if count == 0 {
@@ -228,6 +367,30 @@ where
         }
     }
 
+    /// Return the nodes other than this leader that have not acknowledged it for longer than
+    /// `grace`, along with how long each has not.
+    ///
+    /// A node that has never acknowledged this leader is counted from when the leader is
+    /// established, so that a new leader gives every node the whole `grace` to respond.
+    pub(crate) fn unreachable_nodes(&self, now: InstantOf<C>, grace: Duration) -> Vec<(C::NodeId, Duration)> {
+        let leader_id = self.committed_vote.to_leader_node_id();
+        let since = *self.leader_since.instant;
+
+        self.clock_progress
+            .iter()
+            .filter(|item| item.id != leader_id)
+            .filter_map(|item| {
+                let last_heard = item.val.map_or(since, |t| std::cmp::max(t, since));
+                let elapsed = now.saturating_duration_since(last_heard);
+                if elapsed > grace {
+                    Some((item.id.clone(), elapsed))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
     pub(crate) fn is_replication_stream_valid(&self, target: &C::NodeId, stream_id: StreamId) -> bool {
         if let Some(prog_ent) = self.progress.try_get(target)
             && prog_ent.stream_id == stream_id
@@ -249,6 +412,7 @@ where
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeSet;
+    use std::time::Duration;
 
     use maplit::btreeset;
 
@@ -257,6 +421,7 @@ mod tests {
     use crate::engine::leader_log_ids::LeaderLogIds;
     use crate::engine::testing::UTConfig;
     use crate::engine::testing::log_id;
+    use crate::errors::ReservedIndexMismatch;
     use crate::progress::Progress;
     use crate::proposer::Leader;
     use crate::type_config::TypeConfigExt;
@@ -385,6 +550,85 @@ mod tests {
         assert_eq!(Some(log_id(2, 2, 11)), leading.last_log_id);
     }
 
+    #[test]
+    fn test_reserve_log_indexes() {
+        let vote = Vote::new(2, 2).into_committed();
+        let mut leading = Leader::<UTConfig, _>::new(
+            vote,
+            vec![btreeset! {1, 2, 3}],
+            [],
+            Some(LeaderLogIds::new_single(log_id(1, 1, 8))),
+            SharedIdGenerator::new(),
+        );
+
+        assert_eq!(9..12, leading.reserve_log_indexes(3));
+        assert_eq!(
+            12..14,
+            leading.reserve_log_indexes(2),
+            "follow the unfilled reserved indexes"
+        );
+        assert_eq!(
+            Some(log_id(1, 1, 8)),
+            leading.last_log_id,
+            "reserving assigns no log id"
+        );
+
+        tracing::info!("--- fill out of order or beyond the reservation");
+        {
+            let res = leading.assign_reserved_log_ids(10, 1);
+            assert_eq!(
+                Err(ReservedIndexMismatch {
+                    index: 10,
+                    count: 1,
+                    reserved: Some(9..14),
+                }),
+                res
+            );
+
+            let res = leading.assign_reserved_log_ids(9, 6);
+            assert_eq!(
+                Err(ReservedIndexMismatch {
+                    index: 9,
+                    count: 6,
+                    reserved: Some(9..14),
+                }),
+                res
+            );
+        }
+
+        tracing::info!("--- fill in order");
+        {
+            let log_ids: Vec<_> = leading.assign_reserved_log_ids(9, 2).unwrap().unwrap().into_iter().collect();
+            assert_eq!(log_ids, vec![log_id(2, 2, 9), log_id(2, 2, 10)]);
+            assert_eq!(Some(11..14), leading.reserved);
+        }
+
+        tracing::info!("--- other log ids drop the reservation");
+        {
+            let log_ids: Vec<_> = leading.assign_log_ids(1).unwrap().into_iter().collect();
+            assert_eq!(log_ids, vec![log_id(2, 2, 11)]);
+            assert_eq!(None, leading.reserved);
+
+            let res = leading.assign_reserved_log_ids(12, 1);
+            assert_eq!(
+                Err(ReservedIndexMismatch {
+                    index: 12,
+                    count: 1,
+                    reserved: None,
+                }),
+                res
+            );
+        }
+
+        tracing::info!("--- fill the whole reservation");
+        {
+            assert_eq!(12..14, leading.reserve_log_indexes(2));
+            leading.assign_reserved_log_ids(12, 2).unwrap();
+            assert_eq!(None, leading.reserved);
+            assert_eq!(Some(log_id(2, 2, 13)), leading.last_log_id);
+        }
+    }
+
     #[test]
     fn test_leading_last_quorum_acked_time_leader_is_voter() {
         let mut leading = Leader::<UTConfig, Vec<BTreeSet<u64>>>::new(
@@ -423,6 +667,35 @@ mod tests {
         assert_eq!(Some(t2), t, "n2 and n3 acked");
     }
 
+    #[test]
+    fn test_leading_unreachable_nodes() {
+        let mut leading = Leader::<UTConfig, Vec<BTreeSet<u64>>>::new(
+            Vote::new(2, 1).into_committed(),
+            vec![btreeset! {1, 2, 3}],
+            [4],
+            None,
+            SharedIdGenerator::new(),
+        );
+
+        let since = *leading.leader_since.instant;
+        let grace = Duration::from_millis(100);
+
+        assert!(
+            leading.unreachable_nodes(since + grace, grace).is_empty(),
+            "every node is given the grace period from when the leader is established"
+        );
+
+        leading.clock_progress.increase_to(&2, Some(since + Duration::from_millis(50))).ok();
+        leading.clock_progress.increase_to(&4, Some(since + Duration::from_millis(200))).ok();
+
+        let now = since + Duration::from_millis(250);
+        assert_eq!(
+            vec![(2, Duration::from_millis(200)), (3, Duration::from_millis(250))],
+            leading.unreachable_nodes(now, grace),
+            "n1 is the leader, n4 acked recently"
+        );
+    }
+
     #[test]
     fn test_leading_last_quorum_acked_time_leader_is_not_member() {
         let mut leading = Leader::<UTConfig, Vec<BTreeSet<u64>>>::new(
@@ -1,13 +1,156 @@
+use std::collections::BTreeSet;
+use std::fmt;
 use std::sync::Arc;
 
 use crate::Membership;
+use crate::RaftTypeConfig;
+use crate::membership::QuorumKind;
+use crate::membership::QuorumPolicy;
 use crate::proposer::Candidate;
 use crate::proposer::Leader;
+use crate::quorum::QuorumSet;
 use crate::type_config::alias::NodeIdOf;
 use crate::type_config::alias::NodeOf;
 
-/// The quorum set type used by `Leader`.
-pub(crate) type LeaderQuorumSet<C> = Arc<Membership<NodeIdOf<C>, NodeOf<C>>>;
+/// The quorum set type used by `Leader` and `Candidate`.
+///
+/// It is the quorum set of a membership, either built in, or decided by a [`QuorumPolicy`] for
+/// one [`QuorumKind`].
+pub(crate) struct LeaderQuorumSet<C>
+where C: RaftTypeConfig
+{
+    membership: Arc<Membership<NodeIdOf<C>, NodeOf<C>>>,
+    policy: Option<(QuorumKind, Arc<dyn QuorumPolicy<C>>)>,
+}
+
+impl<C> Clone for LeaderQuorumSet<C>
+where C: RaftTypeConfig
+{
+    fn clone(&self) -> Self {
+        Self {
+            membership: self.membership.clone(),
+            policy: self.policy.clone(),
+        }
+    }
+}
+
+impl<C> fmt::Debug for LeaderQuorumSet<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut d = f.debug_struct("LeaderQuorumSet");
+        d.field("membership", &self.membership);
+        if let Some((kind, policy)) = &self.policy {
+            d.field("kind", kind).field("policy", policy);
+        }
+        d.finish()
+    }
+}
+
+impl<C> LeaderQuorumSet<C>
+where C: RaftTypeConfig
+{
+    /// Create the quorum set of `membership` for `kind`, decided by `policy` if there is one.
+    pub(crate) fn new(
+        membership: Arc<Membership<NodeIdOf<C>, NodeOf<C>>>,
+        policy: Option<&Arc<dyn QuorumPolicy<C>>>,
+        kind: QuorumKind,
+    ) -> Self {
+        Self {
+            membership,
+            policy: policy.map(|p| (kind, p.clone())),
+        }
+    }
+}
+
+impl<C> QuorumSet for LeaderQuorumSet<C>
+where C: RaftTypeConfig
+{
+    type Id = C::NodeId;
+    type Iter = std::collections::btree_set::IntoIter<C::NodeId>;
+
+    fn is_quorum<'a, I: Iterator<Item = &'a C::NodeId> + Clone>(&self, ids: I) -> bool {
+        let Some((kind, policy)) = &self.policy else {
+            return self.membership.is_quorum(ids);
+        };
+
+        let granted = ids.cloned().collect::<BTreeSet<_>>();
+        let m = self.membership.as_ref();
+
+        m.get_joint_config().iter().all(|voters| policy.is_quorum(*kind, m, voters, &granted))
+    }
+
+    fn ids(&self) -> Self::Iter {
+        self.membership.ids()
+    }
+}
 
 pub(crate) type LeaderState<C> = Option<Box<Leader<C, LeaderQuorumSet<C>>>>;
 pub(crate) type CandidateState<C> = Option<Candidate<C, LeaderQuorumSet<C>>>;
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+    use std::sync::Arc;
+
+    use maplit::btreeset;
+
+    use super::LeaderQuorumSet;
+    use crate::Membership;
+    use crate::engine::testing::UTConfig;
+    use crate::membership::QuorumKind;
+    use crate::membership::QuorumPolicy;
+    use crate::quorum::QuorumSet;
+
+    /// Commit on any 2 voters, elect with all but one voter.
+    #[derive(Debug)]
+    struct Grid;
+
+    impl QuorumPolicy<UTConfig> for Grid {
+        fn is_quorum(
+            &self,
+            kind: QuorumKind,
+            _membership: &Membership<u64, ()>,
+            voters: &BTreeSet<u64>,
+            granted: &BTreeSet<u64>,
+        ) -> bool {
+            let n = voters.intersection(granted).count();
+            match kind {
+                QuorumKind::Commit => n >= 2,
+                QuorumKind::Election => n + 1 >= voters.len(),
+            }
+        }
+    }
+
+    #[test]
+    fn test_leader_quorum_set() {
+        let m = Arc::new(Membership::<u64, ()>::new_with_defaults(vec![btreeset! {1,2,3,4,5}], []));
+        let policy: Arc<dyn QuorumPolicy<UTConfig>> = Arc::new(Grid);
+
+        let majority = LeaderQuorumSet::<UTConfig>::new(m.clone(), None, QuorumKind::Commit);
+        assert!(!majority.is_quorum([1, 2].iter()));
+        assert!(majority.is_quorum([1, 2, 3].iter()));
+        assert_eq!(btreeset! {1,2,3,4,5}, majority.ids().collect());
+
+        let commit = LeaderQuorumSet::<UTConfig>::new(m.clone(), Some(&policy), QuorumKind::Commit);
+        assert!(commit.is_quorum([1, 2].iter()));
+        assert!(!commit.is_quorum([1, 6].iter()), "6 is not a voter");
+
+        let election = LeaderQuorumSet::<UTConfig>::new(m, Some(&policy), QuorumKind::Election);
+        assert!(!election.is_quorum([1, 2, 3].iter()));
+        assert!(election.is_quorum([1, 2, 3, 4].iter()));
+    }
+
+    #[test]
+    fn test_leader_quorum_set_joint() {
+        let m = Arc::new(Membership::<u64, ()>::new_with_defaults(
+            vec![btreeset! {1,2,3}, btreeset! {4,5,6}],
+            [],
+        ));
+        let policy: Arc<dyn QuorumPolicy<UTConfig>> = Arc::new(Grid);
+
+        let commit = LeaderQuorumSet::<UTConfig>::new(m, Some(&policy), QuorumKind::Commit);
+        assert!(!commit.is_quorum([1, 2].iter()), "a quorum of only the first config");
+        assert!(commit.is_quorum([1, 2, 4, 5].iter()));
+    }
+}
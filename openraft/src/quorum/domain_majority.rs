@@ -0,0 +1,61 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use crate::quorum::quorum_set::QuorumSet;
+
+/// A quorum set that requires a majority of the voters in a majority of the failure domains.
+///
+/// E.g., with voters `{a1,a2,b1,b2,c1}` in domains `a`, `b` and `c`, `{a1,a2,b1,b2}` is a quorum
+/// and so is `{a1,a2,c1}`, while `{a1,b1,c1}` is not: it is a majority of the nodes but has a
+/// majority of no domain except `c`.
+///
+/// Two such quorums share a domain in which each has a majority, thus they always intersect.
+/// Losing a whole domain loses no more than a minority of the domains.
+///
+/// A voter without a failure domain is counted in the unnamed domain `""`.
+#[derive(Debug, Clone)]
+pub(crate) struct DomainMajority<'a, ID> {
+    voters: &'a BTreeSet<ID>,
+    domains: &'a BTreeMap<ID, String>,
+}
+
+impl<'a, ID> DomainMajority<'a, ID>
+where ID: Ord
+{
+    pub(crate) fn new(voters: &'a BTreeSet<ID>, domains: &'a BTreeMap<ID, String>) -> Self {
+        Self { voters, domains }
+    }
+
+    fn domain_of(&self, id: &ID) -> &'a str {
+        self.domains.get(id).map(|d| d.as_str()).unwrap_or_default()
+    }
+}
+
+impl<ID> QuorumSet for DomainMajority<'_, ID>
+where ID: PartialOrd + Ord + Clone + 'static
+{
+    type Id = ID;
+    type Iter = std::collections::btree_set::IntoIter<ID>;
+
+    fn is_quorum<'a, I: Iterator<Item = &'a ID> + Clone>(&self, ids: I) -> bool {
+        // domain -> (number of voters, number of voters in `ids`)
+        let mut counts: BTreeMap<&str, (usize, usize)> = BTreeMap::new();
+
+        for id in self.voters.iter() {
+            counts.entry(self.domain_of(id)).or_default().0 += 1;
+        }
+
+        for id in ids {
+            if self.voters.contains(id) {
+                counts.entry(self.domain_of(id)).or_default().1 += 1;
+            }
+        }
+
+        let granted = counts.values().filter(|(total, acked)| acked * 2 > *total).count();
+        granted * 2 > counts.len()
+    }
+
+    fn ids(&self) -> Self::Iter {
+        self.voters.clone().into_iter()
+    }
+}
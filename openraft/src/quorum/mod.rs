@@ -2,9 +2,12 @@
 //! The most common quorum is **majority**.
 //! A quorum set is a collection of quorums, e.g., the quorum set of the majority of `{a,b,c}` is
 //! `{a,b}, {b,c}, {a,c}`.
+//! A quorum can also be defined over failure domains, e.g., a majority of the nodes in a majority
+//! of the zones.
 
 mod coherent;
 mod coherent_impl;
+pub(crate) mod domain_majority;
 mod quorum_set;
 mod quorum_set_impl;
 
@@ -1,6 +1,8 @@
+use maplit::btreemap;
 use maplit::btreeset;
 
 use crate::quorum::QuorumSet;
+use crate::quorum::domain_majority::DomainMajority;
 
 #[test]
 fn test_simple_quorum_set_impl() -> anyhow::Result<()> {
@@ -48,6 +50,44 @@ fn test_joint_quorum_set_impl() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_domain_majority_quorum_set_impl() -> anyhow::Result<()> {
+    let voters = btreeset! {1,2,3,4,5};
+    let domains = btreemap! {
+        1 => s("a"), 2 => s("a"),
+        3 => s("b"), 4 => s("b"),
+        5 => s("c"),
+    };
+    let qs = DomainMajority::new(&voters, &domains);
+
+    assert!(!qs.is_quorum([0].iter()));
+    assert!(!qs.is_quorum([1, 2].iter()), "one domain");
+    assert!(
+        !qs.is_quorum([1, 3, 5].iter()),
+        "a majority of nodes, but of only one domain"
+    );
+    assert!(!qs.is_quorum([1, 2, 6].iter()), "node 6 is not a voter");
+    assert!(qs.is_quorum([1, 2, 5].iter()));
+    assert!(qs.is_quorum([1, 2, 3, 4].iter()));
+    assert!(qs.is_quorum([3, 4, 5].iter()));
+
+    assert_eq!(btreeset! {1,2,3,4,5}, qs.ids().collect());
+
+    // A voter without a domain is in the unnamed domain.
+    let domains = btreemap! {1 => s("a"), 2 => s("b")};
+    let qs = DomainMajority::new(&voters, &domains);
+
+    assert!(!qs.is_quorum([1, 2].iter()));
+    assert!(!qs.is_quorum([1, 3, 4].iter()), "a minority of the unnamed domain");
+    assert!(qs.is_quorum([1, 3, 4, 5].iter()));
+
+    Ok(())
+}
+
+fn s(x: &str) -> String {
+    x.to_string()
+}
+
 #[test]
 fn test_ids() -> anyhow::Result<()> {
     {
@@ -1,18 +1,28 @@
+use std::ops::Range;
 use std::sync::Arc;
 
 use openraft_macros::since;
 
 use crate::RaftTypeConfig;
 use crate::ReadPolicy;
+use crate::async_runtime::OneshotSender;
 use crate::base::BoxStream;
 use crate::batch::Batch;
 use crate::core::raft_msg::RaftMsg;
+use crate::core::raft_msg::external_command::ExternalCommand;
+use crate::entry::ApplyScope;
 use crate::errors::ClientWriteError;
 use crate::errors::Fatal;
 use crate::errors::LinearizableReadError;
+use crate::errors::StaleRead;
 use crate::impls::ProgressResponder;
+use crate::raft::ClientWriteHandle;
 use crate::raft::ClientWriteResponse;
 use crate::raft::ClientWriteResult;
+use crate::raft::CorrelationId;
+use crate::raft::ReadOptions;
+use crate::raft::WriteOptions;
+use crate::raft::WriteWait;
 use crate::raft::linearizable_read::Linearizer;
 use crate::raft::message::WriteResult;
 use crate::raft::message::into_write_result;
@@ -23,6 +33,8 @@ use crate::type_config::alias::BatchOf;
 use crate::type_config::alias::EntryPayloadOf;
 #[cfg(feature = "runtime-stats")]
 use crate::type_config::alias::InstantOf;
+use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::OneshotReceiverOf;
 use crate::type_config::alias::WriteResponderOf;
 
 /// Provides application-facing APIs for interacting with the Raft system.
@@ -53,26 +65,116 @@ where C: RaftTypeConfig
         self.inner.call_core(RaftMsg::GetLinearizer { read_policy, tx }, rx).await
     }
 
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub(crate) async fn local_read(
+        &self,
+        options: ReadOptions,
+    ) -> Result<Result<Option<LogIdOf<C>>, StaleRead<C>>, Fatal<C>> {
+        let (tx, rx) = C::oneshot();
+        let cmd = ExternalCommand::LocalRead { options, tx };
+        self.inner.call_core(RaftMsg::ExternalCommand { cmd }, rx).await
+    }
+
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub(crate) async fn get_follower_read_linearizer(
+        &self,
+        read_policy: ReadPolicy,
+    ) -> Result<Result<Linearizer<C>, LinearizableReadError<C>>, Fatal<C>> {
+        let (tx, rx) = C::oneshot();
+        self.inner.call_core(RaftMsg::FollowerReadIndex { read_policy, tx }, rx).await
+    }
+
     #[since(version = "0.10.0")]
     #[tracing::instrument(level = "debug", skip(self, payload))]
     pub(crate) async fn client_write(
         &self,
         payload: EntryPayloadOf<C>,
+        correlation_id: Option<CorrelationId>,
         // TODO: ClientWriteError can only be ForwardToLeader Error
     ) -> Result<Result<ClientWriteResponse<C>, ClientWriteError<C>>, Fatal<C>> {
         let (responder, complete_rx) = ProgressResponder::complete_only();
+        let responder = CoreResponder::progress(responder).with_correlation_id(correlation_id);
 
-        self.do_client_write_ff(
-            Batch::of([payload]),
-            Batch::of([Some(CoreResponder::Progress(responder))]),
-        )
-        .await?;
+        self.do_client_write_ff(Batch::of([payload]), Batch::of([Some(responder)])).await?;
 
         let res: ClientWriteResult<C> = self.inner.recv_msg(complete_rx).await?;
 
         Ok(res)
     }
 
+    /// Write application data and return once the leader has appended it to its log.
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) async fn client_write_accepted(
+        &self,
+        payload: EntryPayloadOf<C>,
+    ) -> Result<Result<ClientWriteHandle<C>, ClientWriteError<C>>, Fatal<C>> {
+        let (responder, accept_rx, complete_rx) = ProgressResponder::accept_and_complete();
+        let responder = CoreResponder::progress(responder);
+
+        self.do_client_write_ff(Batch::of([payload]), Batch::of([Some(responder)])).await?;
+
+        if let Ok(log_id) = accept_rx.await {
+            return Ok(Ok(ClientWriteHandle::new(log_id, complete_rx, self.inner.clone())));
+        }
+
+        // Completed without being accepted, e.g., this node is not the leader.
+        self.completed_handle(complete_rx).await
+    }
+
+    /// Write application data and return once it reaches the stage `options.wait`.
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self, payload))]
+    pub(crate) async fn client_write_with_options(
+        &self,
+        payload: EntryPayloadOf<C>,
+        options: WriteOptions,
+    ) -> Result<Result<ClientWriteHandle<C>, ClientWriteError<C>>, Fatal<C>> {
+        match options.wait {
+            WriteWait::Accepted => self.client_write_accepted(payload).await,
+            WriteWait::Committed => {
+                let (responder, commit_rx, complete_rx) = ProgressResponder::new();
+                let responder = CoreResponder::progress(responder);
+
+                self.do_client_write_ff(Batch::of([payload]), Batch::of([Some(responder)])).await?;
+
+                if let Ok(log_id) = commit_rx.await {
+                    return Ok(Ok(ClientWriteHandle::new(log_id, complete_rx, self.inner.clone())));
+                }
+
+                // Completed without being committed, e.g., this node is not the leader.
+                self.completed_handle(complete_rx).await
+            }
+            WriteWait::Applied => {
+                let (responder, complete_rx) = ProgressResponder::complete_only();
+                let responder = CoreResponder::progress(responder);
+
+                self.do_client_write_ff(Batch::of([payload]), Batch::of([Some(responder)])).await?;
+
+                self.completed_handle(complete_rx).await
+            }
+        }
+    }
+
+    /// Wait for a write to complete, and return a handle that resolves at once with the result.
+    async fn completed_handle(
+        &self,
+        complete_rx: OneshotReceiverOf<C, ClientWriteResult<C>>,
+    ) -> Result<Result<ClientWriteHandle<C>, ClientWriteError<C>>, Fatal<C>> {
+        let res: ClientWriteResult<C> = self.inner.recv_msg(complete_rx).await?;
+        match res {
+            Err(e) => Ok(Err(e)),
+            Ok(resp) => {
+                let (tx, rx) = C::oneshot();
+                let log_id = resp.log_id.clone();
+                tx.send(Ok(resp)).ok();
+                Ok(Ok(ClientWriteHandle::new(log_id, rx, self.inner.clone())))
+            }
+        }
+    }
+
     #[since(version = "0.10.0")]
     #[tracing::instrument(level = "debug", skip_all)]
     pub(crate) async fn client_write_ff(
@@ -82,14 +184,101 @@ where C: RaftTypeConfig
     ) -> Result<(), Fatal<C>> {
         self.do_client_write_ff(
             Batch::of([payload]),
-            Batch::of([responder.map(|r| CoreResponder::UserDefined(r))]),
+            Batch::of([responder.map(CoreResponder::user_defined)]),
         )
         .await
     }
 
+    /// Propose a backup barrier and return its log id once it is applied on this leader.
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub(crate) async fn backup(&self) -> Result<Result<LogIdOf<C>, ClientWriteError<C>>, Fatal<C>> {
+        let (responder, complete_rx) = ProgressResponder::complete_only();
+        let responder = CoreResponder::progress(responder);
+
+        self.inner.send_external_command(ExternalCommand::Backup { responder }).await?;
+
+        let res: ClientWriteResult<C> = self.inner.recv_msg(complete_rx).await?;
+
+        Ok(res.map(|resp| resp.log_id))
+    }
+
+    /// Write application data that is applied only by the nodes in `scope`.
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self, app_data))]
+    pub(crate) async fn client_write_in_scope(
+        &self,
+        app_data: C::D,
+        scope: ApplyScope<C::NodeId>,
+    ) -> Result<ClientWriteResult<C>, Fatal<C>> {
+        let (responder, complete_rx) = ProgressResponder::complete_only();
+        let responder = CoreResponder::progress(responder);
+
+        self.inner
+            .send_external_command(ExternalCommand::WriteInScope {
+                app_data,
+                scope,
+                responder,
+            })
+            .await?;
+
+        self.inner.recv_msg(complete_rx).await
+    }
+
+    /// Reserve `count` log indexes on the leader, to fill with
+    /// [`client_write_reserved()`](Self::client_write_reserved).
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub(crate) async fn reserve_log_indexes(
+        &self,
+        count: u64,
+    ) -> Result<Result<Range<u64>, ClientWriteError<C>>, Fatal<C>> {
+        let (tx, rx) = C::oneshot();
+        let cmd = ExternalCommand::ReserveLogIndexes { count, tx };
+        self.inner.call_core(RaftMsg::ExternalCommand { cmd }, rx).await
+    }
+
+    /// Write application data to the reserved log indexes starting at `index`.
+    ///
+    /// The entries are either all written or all rejected.
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self, app_data))]
+    pub(crate) async fn client_write_reserved(
+        &self,
+        index: u64,
+        app_data: Vec<C::D>,
+    ) -> Result<Result<Vec<ClientWriteResponse<C>>, ClientWriteError<C>>, Fatal<C>> {
+        let mut responders = Vec::with_capacity(app_data.len());
+        let mut receivers = Vec::with_capacity(app_data.len());
+
+        for _ in 0..app_data.len() {
+            let (responder, complete_rx) = ProgressResponder::<C, ClientWriteResult<C>>::complete_only();
+            responders.push(CoreResponder::progress(responder));
+            receivers.push(complete_rx);
+        }
+
+        self.inner
+            .send_external_command(ExternalCommand::WriteReserved {
+                index,
+                app_data,
+                responders,
+            })
+            .await?;
+
+        let mut responses = Vec::with_capacity(receivers.len());
+        for rx in receivers {
+            match self.inner.recv_msg(rx).await? {
+                Ok(resp) => responses.push(resp),
+                Err(e) => return Ok(Err(e)),
+            }
+        }
+
+        Ok(Ok(responses))
+    }
+
     /// Fire-and-forget version of `client_write`, accept a generic responder.
     #[since(version = "0.10.0")]
-    async fn do_client_write_ff(
+    pub(in crate::raft) async fn do_client_write_ff(
         &self,
         payloads: BatchOf<C, EntryPayloadOf<C>>,
         responders: BatchOf<C, Option<CoreResponder<C>>>,
@@ -127,7 +316,7 @@ where C: RaftTypeConfig
 
         for _ in 0..payloads.len() {
             let (responder, complete_rx) = ProgressResponder::<C, ClientWriteResult<C>>::complete_only();
-            responders.push(Some(CoreResponder::Progress(responder)));
+            responders.push(Some(CoreResponder::progress(responder)));
             receivers.push(complete_rx);
         }
 
@@ -1,21 +1,32 @@
 use std::fmt::Debug;
+use std::time::Duration;
 
 use display_more::DisplayResultExt;
 use maplit::btreemap;
+use maplit::btreeset;
 use openraft_macros::since;
 
 use crate::ChangeMembers;
 use crate::LogIdOptionExt;
+use crate::Membership;
 use crate::OptionalSend;
 use crate::RaftMetrics;
 use crate::RaftTypeConfig;
+use crate::async_runtime::watch::WatchReceiver;
 use crate::core::raft_msg::RaftMsg;
+use crate::core::raft_msg::external_command::ExternalCommand;
 use crate::core::replication_lag;
+use crate::errors::ChangeMembershipError;
+use crate::errors::ClientWriteError;
 use crate::errors::Fatal;
 use crate::errors::InitializeError;
+use crate::errors::LearnerNotCaughtUp;
+use crate::errors::SeedSnapshotError;
 use crate::impls::ProgressResponder;
 use crate::membership::IntoNodes;
 use crate::raft::ClientWriteResult;
+use crate::raft::CorrelationId;
+use crate::raft::ReplaceNodeProgress;
 use crate::raft::raft_inner::RaftInner;
 use crate::type_config::TypeConfigExt;
 use crate::type_config::alias::LogIdOf;
@@ -43,16 +54,17 @@ where C: RaftTypeConfig
     #[tracing::instrument(level = "debug", skip(self))]
     pub(crate) async fn initialize<T>(&self, members: T) -> Result<Result<(), InitializeError<C>>, Fatal<C>>
     where T: IntoNodes<C::NodeId, C::Node> + Debug {
+        self.initialize_with_membership(Membership::from(members.into_nodes())).await
+    }
+
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub(crate) async fn initialize_with_membership(
+        &self,
+        membership: Membership<C::NodeId, C::Node>,
+    ) -> Result<Result<(), InitializeError<C>>, Fatal<C>> {
         let (tx, rx) = C::oneshot();
-        self.inner
-            .call_core(
-                RaftMsg::Initialize {
-                    members: members.into_nodes(),
-                    tx,
-                },
-                rx,
-            )
-            .await
+        self.inner.call_core(RaftMsg::Initialize { membership, tx }, rx).await
     }
 
     #[since(version = "0.10.0")]
@@ -61,6 +73,7 @@ where C: RaftTypeConfig
         &self,
         members: impl Into<ChangeMembers<C::NodeId, C::Node>>,
         retain: bool,
+        correlation_id: Option<CorrelationId>,
     ) -> Result<ClientWriteResult<C>, Fatal<C>> {
         let changes: ChangeMembers<C::NodeId, C::Node> = members.into();
 
@@ -82,6 +95,7 @@ where C: RaftTypeConfig
                 RaftMsg::ChangeMembership {
                     changes: changes.clone(),
                     retain,
+                    correlation_id,
                     tx,
                 },
                 rx,
@@ -109,6 +123,12 @@ where C: RaftTypeConfig
             return Ok(Ok(resp));
         }
 
+        // Removing learners keeps the joint config of a pending change: it is left to that change
+        // to flatten it.
+        if let ChangeMembers::RemoveLearners(_) = changes {
+            return Ok(Ok(resp));
+        }
+
         tracing::debug!("committed a joint config: {} {:?}", log_id, joint);
         tracing::debug!("the second step is to change to uniform config: {:?}", changes);
 
@@ -116,7 +136,13 @@ where C: RaftTypeConfig
 
         // The second step, send a NOOP change to flatten the joint config.
         let changes = ChangeMembers::AddVoterIds(Default::default());
-        let client_write_result = self.inner.call_core(RaftMsg::ChangeMembership { changes, retain, tx }, rx).await?;
+        let msg = RaftMsg::ChangeMembership {
+            changes,
+            retain,
+            correlation_id,
+            tx,
+        };
+        let client_write_result = self.inner.call_core(msg, rx).await?;
 
         tracing::info!(
             "result of second step of change_membership: {}",
@@ -130,6 +156,116 @@ where C: RaftTypeConfig
         Ok(client_write_result)
     }
 
+    /// Replace voter `old` with a new node `new_id`.
+    ///
+    /// The new node is added as a learner and is brought up to date first, by log replication or
+    /// by a snapshot if the logs are purged. Then `old` and `new_id` are swapped in a single joint
+    /// config change, and `old` is removed from the cluster.
+    ///
+    /// If `new_id` does not catch up within [`Config::replace_node_timeout`] or the swap fails,
+    /// and `new_id` was not a member before, it is removed again. Every stage is reported to
+    /// `progress`.
+    ///
+    /// [`Config::replace_node_timeout`]: crate::Config::replace_node_timeout
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "info", skip(self, new_node, progress))]
+    pub(crate) async fn replace_node<F>(
+        &self,
+        old: C::NodeId,
+        new_id: C::NodeId,
+        new_node: C::Node,
+        mut progress: F,
+    ) -> Result<ClientWriteResult<C>, Fatal<C>>
+    where
+        F: FnMut(ReplaceNodeProgress<C>) + OptionalSend,
+    {
+        let was_member = {
+            let metrics = self.inner.rx_metrics.borrow_watched();
+            metrics.membership_config.membership().get_node(&new_id).is_some()
+        };
+
+        let resp = match self.add_learner(new_id.clone(), new_node, false).await? {
+            Ok(resp) => resp,
+            Err(e) => {
+                tracing::error!("failed to add the replacement {} as learner: {}", new_id, e);
+                return Ok(Err(e));
+            }
+        };
+        progress(ReplaceNodeProgress::LearnerAdded {
+            log_id: resp.log_id.clone(),
+        });
+
+        let timeout = self.inner.config.replace_node_timeout();
+
+        let swap_err = if self.wait_learner_caught_up(&new_id, &resp.log_id, timeout).await {
+            progress(ReplaceNodeProgress::CaughtUp);
+
+            let changes = ChangeMembers::Batch(vec![
+                ChangeMembers::AddVoterIds(btreeset! {new_id.clone()}),
+                ChangeMembers::RemoveVoters(btreeset! {old}),
+            ]);
+
+            match self.change_membership(changes, false, None).await? {
+                Ok(resp) => {
+                    progress(ReplaceNodeProgress::Replaced {
+                        log_id: resp.log_id.clone(),
+                    });
+                    return Ok(Ok(resp));
+                }
+                Err(e) => e,
+            }
+        } else {
+            let e = ChangeMembershipError::from(LearnerNotCaughtUp {
+                node_id: new_id.clone(),
+            });
+            ClientWriteError::ChangeMembershipError(e)
+        };
+
+        tracing::error!("failed to swap in the replacement {}: {}", new_id, swap_err);
+
+        if was_member {
+            return Ok(Err(swap_err));
+        }
+
+        progress(ReplaceNodeProgress::RollingBack);
+
+        let rollback = ChangeMembers::RemoveNodes(btreeset! {new_id.clone()});
+        match self.change_membership(rollback, false, None).await? {
+            Ok(_) => progress(ReplaceNodeProgress::RolledBack),
+            Err(e) => {
+                tracing::error!("failed to roll back the replacement {}: {}", new_id, e);
+            }
+        }
+
+        Ok(Err(swap_err))
+    }
+
+    /// Wait until the replication to learner `id` is up to date, for at most `timeout`.
+    ///
+    /// Returns `false` if it times out, the learner is removed or this node is no longer the
+    /// leader.
+    async fn wait_learner_caught_up(&self, id: &C::NodeId, membership_log_id: &LogIdOf<C>, timeout: Duration) -> bool {
+        let wait_res = self
+            .inner
+            .wait(Some(timeout))
+            .metrics(
+                |metrics| self.check_replication_upto_date(metrics, id, Some(membership_log_id)).is_ok(),
+                "wait new learner to become line-rate",
+            )
+            .await;
+
+        let metrics = match wait_res {
+            Ok(metrics) => metrics,
+            Err(e) => {
+                tracing::warn!("learner {} did not catch up: {}", id, e);
+                return false;
+            }
+        };
+
+        let caught_up = self.check_replication_upto_date(&metrics, id, Some(membership_log_id));
+        matches!(caught_up, Ok(Some(_)))
+    }
+
     #[since(version = "0.10.0")]
     #[tracing::instrument(level = "debug", skip(self, id), fields(target=display(&id)))]
     pub(crate) async fn add_learner(
@@ -143,6 +279,7 @@ where C: RaftTypeConfig
         let msg = RaftMsg::ChangeMembership {
             changes: ChangeMembers::AddNodes(btreemap! {id.clone()=>node}),
             retain: true,
+            correlation_id: None,
             tx,
         };
 
@@ -187,6 +324,18 @@ where C: RaftTypeConfig
         Ok(Ok(resp))
     }
 
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub(crate) async fn set_snapshot_seed(
+        &self,
+        target: C::NodeId,
+        seed: Option<C::NodeId>,
+    ) -> Result<Result<(), SeedSnapshotError<C>>, Fatal<C>> {
+        let (tx, rx) = C::oneshot();
+        let cmd = ExternalCommand::SetSnapshotSeed { target, seed, tx };
+        self.inner.call_core(RaftMsg::ExternalCommand { cmd }, rx).await
+    }
+
     #[since(version = "0.10.0")]
     fn check_replication_upto_date(
         &self,
@@ -7,6 +7,8 @@ use openraft_macros::since;
 
 use crate::OptionalSend;
 use crate::RaftTypeConfig;
+use crate::async_runtime::watch::WatchReceiver;
+use crate::batch::Batch;
 use crate::core::io_flush_tracking::FlushPoint;
 use crate::core::raft_msg::RaftMsg;
 use crate::core::raft_msg::external_command::ExternalCommand;
@@ -15,6 +17,10 @@ use crate::errors::Fatal;
 use crate::errors::into_raft_result::IntoRaftResult;
 use crate::raft::AppendEntriesRequest;
 use crate::raft::AppendEntriesResponse;
+use crate::raft::FetchSnapshotRequest;
+use crate::raft::FetchSnapshotResponse;
+use crate::raft::SeedSnapshotRequest;
+use crate::raft::SeedSnapshotResponse;
 use crate::raft::SnapshotResponse;
 use crate::raft::TransferLeaderError;
 use crate::raft::TransferLeaderRequest;
@@ -24,8 +30,10 @@ use crate::raft::VoteResponse;
 use crate::raft::raft_inner::RaftInner;
 use crate::raft::stream_append;
 use crate::raft::stream_append::StreamAppendResult;
+use crate::storage::SnapshotLocator;
 use crate::type_config::TypeConfigExt;
 use crate::type_config::alias::SnapshotDataOf;
+use crate::type_config::alias::SnapshotMetaOf;
 use crate::type_config::alias::SnapshotOf;
 use crate::type_config::alias::VoteOf;
 use crate::vote::RaftVote;
@@ -101,9 +109,21 @@ where C: RaftTypeConfig
     ) -> Result<AppendEntriesResponse<C>, Fatal<C>> {
         tracing::debug!("Raft::append_entries: rpc: {}", rpc);
 
+        if let Err(e) = rpc.validate() {
+            tracing::error!("Raft::append_entries: reject malformed request: {}; rpc: {}", e, rpc);
+            return Ok(AppendEntriesResponse::Malformed(e));
+        }
+
         let (tx, rx) = C::oneshot();
-        let stream_result: StreamAppendResult<C> = self.inner.call_core(RaftMsg::AppendEntries { rpc, tx }, rx).await?;
-        Ok(AppendEntriesResponse::from(stream_result))
+        let msg = RaftMsg::AppendEntries {
+            rpc,
+            txs: Batch::of([tx]),
+        };
+        let stream_result: Option<StreamAppendResult<C>> = self.inner.call_core(msg, rx).await?;
+
+        // `None`: merged into a later request and appended, which is a success for this one.
+        let resp = stream_result.map_or(AppendEntriesResponse::Success, AppendEntriesResponse::from);
+        Ok(resp)
     }
 
     #[since(version = "0.10.0")]
@@ -149,6 +169,96 @@ where C: RaftTypeConfig
         self.inner.call_core(RaftMsg::InstallSnapshot { vote, snapshot, tx }, rx).await
     }
 
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) async fn install_snapshot_locator(
+        &self,
+        vote: VoteOf<C>,
+        locator: SnapshotLocator<C>,
+    ) -> Result<SnapshotResponse<C>, Fatal<C>> {
+        tracing::info!("Raft::install_snapshot_locator(): {}", locator);
+
+        let (tx, rx) = C::oneshot();
+        self.inner.call_core(RaftMsg::InstallSnapshotLocator { vote, locator, tx }, rx).await
+    }
+
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) async fn install_snapshot_meta(
+        &self,
+        vote: VoteOf<C>,
+        meta: SnapshotMetaOf<C>,
+    ) -> Result<SnapshotResponse<C>, Fatal<C>> {
+        tracing::info!("Raft::install_snapshot_meta(): {}", meta);
+
+        let (tx, rx) = C::oneshot();
+        self.inner.call_core(RaftMsg::InstallSnapshotMeta { vote, meta, tx }, rx).await
+    }
+
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) async fn handle_seed_snapshot(
+        &self,
+        req: SeedSnapshotRequest<C>,
+    ) -> Result<SeedSnapshotResponse<C>, Fatal<C>> {
+        tracing::info!("Raft::handle_seed_snapshot(): {}", req);
+
+        let vote = req.vote.clone();
+
+        let (tx, rx) = C::oneshot();
+        let cmd = ExternalCommand::FetchSeedSnapshot { req, tx };
+        let snapshot = self.inner.call_core(RaftMsg::ExternalCommand { cmd }, rx).await?;
+
+        let Some(snapshot) = snapshot else {
+            let vote = self.inner.rx_metrics.borrow_watched().vote.clone();
+            return Ok(SeedSnapshotResponse { vote, meta: None });
+        };
+
+        let meta = snapshot.meta.clone();
+        let resp = self.install_full_snapshot(vote, snapshot).await?;
+
+        Ok(SeedSnapshotResponse {
+            vote: resp.vote,
+            meta: Some(meta),
+        })
+    }
+
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) async fn handle_fetch_snapshot(
+        &self,
+        req: FetchSnapshotRequest<C>,
+    ) -> Result<FetchSnapshotResponse<C>, Fatal<C>> {
+        tracing::info!("Raft::handle_fetch_snapshot(): {}", req);
+
+        {
+            let metrics = self.inner.rx_metrics.borrow_watched();
+            if req.vote.as_ref_vote() < metrics.vote.as_ref_vote() {
+                tracing::info!(
+                    "reject fetching snapshot from a stale leader: {}, vote: {}",
+                    req.vote,
+                    metrics.vote
+                );
+                return Ok(None);
+            }
+        }
+
+        let Some(snapshot) = self.get_snapshot().await? else {
+            return Ok(None);
+        };
+
+        if snapshot.meta.last_log_id < req.min_last_log_id {
+            tracing::info!(
+                "snapshot {} does not include {}, can not seed",
+                snapshot.meta,
+                req.min_last_log_id.display()
+            );
+            return Ok(None);
+        }
+
+        Ok(Some(snapshot))
+    }
+
     #[since(version = "0.10.0")]
     pub(crate) async fn handle_transfer_leader(
         &self,
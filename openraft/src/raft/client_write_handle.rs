@@ -0,0 +1,67 @@
+use std::fmt;
+use std::sync::Arc;
+
+use openraft_macros::since;
+
+use crate::RaftTypeConfig;
+use crate::errors::ClientWriteError;
+use crate::errors::RaftError;
+use crate::errors::into_raft_result::IntoRaftResult;
+use crate::raft::ClientWriteResponse;
+use crate::raft::ClientWriteResult;
+use crate::raft::raft_inner::RaftInner;
+use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::OneshotReceiverOf;
+
+/// A write accepted into the leader's log, returned by
+/// [`Raft::client_write_accepted()`](crate::Raft::client_write_accepted) and
+/// [`Raft::client_write_with_options()`](crate::Raft::client_write_with_options).
+///
+/// The log id is assigned as soon as the leader appends the entry, before it is replicated. Await
+/// [`applied()`](Self::applied) for the result of applying the entry to the state machine. An
+/// accepted entry may still be lost, e.g., if the leader steps down before it is committed, in
+/// which case `applied()` returns an error.
+#[since(version = "0.10.0")]
+pub struct ClientWriteHandle<C>
+where C: RaftTypeConfig
+{
+    log_id: LogIdOf<C>,
+    complete_rx: OneshotReceiverOf<C, ClientWriteResult<C>>,
+    inner: Arc<RaftInner<C>>,
+}
+
+impl<C> fmt::Debug for ClientWriteHandle<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClientWriteHandle").field("log_id", &self.log_id).finish()
+    }
+}
+
+impl<C> ClientWriteHandle<C>
+where C: RaftTypeConfig
+{
+    pub(in crate::raft) fn new(
+        log_id: LogIdOf<C>,
+        complete_rx: OneshotReceiverOf<C, ClientWriteResult<C>>,
+        inner: Arc<RaftInner<C>>,
+    ) -> Self {
+        Self {
+            log_id,
+            complete_rx,
+            inner,
+        }
+    }
+
+    /// The log id the leader assigned to the entry.
+    #[since(version = "0.10.0")]
+    pub fn log_id(&self) -> &LogIdOf<C> {
+        &self.log_id
+    }
+
+    /// Wait until the entry is applied to the state machine, and return the result.
+    #[since(version = "0.10.0")]
+    pub async fn applied(self) -> Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>> {
+        self.inner.recv_msg(self.complete_rx).await.into_raft_result()
+    }
+}
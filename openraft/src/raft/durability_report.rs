@@ -0,0 +1,130 @@
+use std::collections::BTreeSet;
+
+use openraft_macros::since;
+
+use crate::Membership;
+use crate::RaftTypeConfig;
+use crate::quorum::QuorumSet;
+use crate::type_config::alias::LogIdOf;
+
+/// Which voters must keep their data for every committed log to stay recoverable, returned by
+/// [`Raft::durability_report()`](crate::Raft::durability_report).
+///
+/// A committed log is lost if a quorum of voters that do not have it can elect a leader: this
+/// happens when enough voters that have it lose their data, e.g., their disks are replaced. The
+/// report tells an operator which hardware can be decommissioned without risking that.
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct DurabilityReport<C>
+where C: RaftTypeConfig
+{
+    /// The last log id committed by the leader that produced this report.
+    pub committed: Option<LogIdOf<C>>,
+
+    /// The voters that have persisted every committed log.
+    pub holders: BTreeSet<C::NodeId>,
+
+    /// A minimal subset of [`holders`](Self::holders) that must survive.
+    ///
+    /// As long as these voters keep their data, every committed log is recoverable, even if all
+    /// the other voters lose theirs. The holders with the most logs are preferred. It is empty if
+    /// nothing is committed.
+    pub must_survive: BTreeSet<C::NodeId>,
+}
+
+impl<C> DurabilityReport<C>
+where C: RaftTypeConfig
+{
+    /// Build a report from the leader's `committed` log id, the `membership` and the last log id
+    /// every node has persisted.
+    pub(crate) fn new<'a>(
+        committed: Option<LogIdOf<C>>,
+        membership: &Membership<C::NodeId, C::Node>,
+        matching: impl IntoIterator<Item = (&'a C::NodeId, &'a Option<LogIdOf<C>>)>,
+    ) -> Self {
+        let voters = membership.voter_ids().collect::<BTreeSet<_>>();
+
+        let mut holders = matching
+            .into_iter()
+            .filter(|(id, matched)| voters.contains(*id) && **matched >= committed)
+            .collect::<Vec<_>>();
+
+        // Prefer the holders with the most logs, then the smallest id.
+        holders.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+
+        let mut must_survive = BTreeSet::new();
+        if committed.is_some() {
+            for (id, _) in holders.iter() {
+                // A committed log is lost only if the voters not surviving form a quorum.
+                let rest = voters.difference(&must_survive);
+                if !membership.is_quorum(rest) {
+                    break;
+                }
+                must_survive.insert((*id).clone());
+            }
+        }
+
+        Self {
+            committed,
+            holders: holders.into_iter().map(|(id, _)| id.clone()).collect(),
+            must_survive,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use maplit::btreeset;
+
+    use crate::Membership;
+    use crate::engine::testing::UTConfig;
+    use crate::testing::log_id;
+
+    type DurabilityReport = super::DurabilityReport<UTConfig>;
+
+    #[test]
+    fn test_durability_report() {
+        let m = Membership::<u64, ()>::new_with_defaults(vec![btreeset! {1,2,3,4,5}], []);
+
+        let matching = BTreeMap::from([
+            (1, Some(log_id::<UTConfig>(1, 1, 10))),
+            (2, Some(log_id::<UTConfig>(1, 1, 9))),
+            (3, Some(log_id::<UTConfig>(1, 1, 8))),
+            (4, Some(log_id::<UTConfig>(1, 1, 10))),
+            (5, None),
+        ]);
+
+        let r = DurabilityReport::new(Some(log_id::<UTConfig>(1, 1, 8)), &m, &matching);
+        assert_eq!(btreeset! {1,2,3,4}, r.holders);
+        assert_eq!(
+            btreeset! {1,4,2},
+            r.must_survive,
+            "3 of 5 must survive, the ones with most logs"
+        );
+
+        let r = DurabilityReport::new(None, &m, &matching);
+        assert_eq!(btreeset! {1,2,3,4,5}, r.holders);
+        assert_eq!(btreeset! {}, r.must_survive);
+    }
+
+    #[test]
+    fn test_durability_report_joint() {
+        let m = Membership::<u64, ()>::new_with_defaults(vec![btreeset! {1,2,3}, btreeset! {3,4,5}], [6]);
+
+        let matching = BTreeMap::from([
+            (1, Some(log_id::<UTConfig>(1, 1, 5))),
+            (2, Some(log_id::<UTConfig>(1, 1, 5))),
+            (3, Some(log_id::<UTConfig>(1, 1, 5))),
+            (4, Some(log_id::<UTConfig>(1, 1, 5))),
+            (5, Some(log_id::<UTConfig>(1, 1, 4))),
+            (6, Some(log_id::<UTConfig>(1, 1, 5))),
+        ]);
+
+        let r = DurabilityReport::new(Some(log_id::<UTConfig>(1, 1, 5)), &m, &matching);
+        assert_eq!(btreeset! {1,2,3,4}, r.holders, "learner 6 is not a holder");
+        assert_eq!(btreeset! {1,2}, r.must_survive, "3,4,5 do not form a joint quorum");
+    }
+}
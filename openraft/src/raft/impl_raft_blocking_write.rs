@@ -2,7 +2,10 @@
 //! Blocking-mode write API blocks until the write operation is completed,
 //! where [`RaftTypeConfig::Responder`] is a [`OneshotResponder`].
 
+use openraft_macros::since;
+
 use crate::ChangeMembers;
+use crate::OptionalSend;
 use crate::Raft;
 use crate::RaftTypeConfig;
 use crate::errors::ClientWriteError;
@@ -11,8 +14,10 @@ use crate::errors::into_raft_result::IntoRaftResult;
 #[cfg(doc)]
 use crate::impls::OneshotResponder;
 use crate::raft::ClientWriteResponse;
+use crate::raft::CorrelationId;
 #[cfg(doc)]
 use crate::raft::ManagementApi;
+use crate::raft::ReplaceNodeProgress;
 
 /// Implement blocking mode write operations those reply on oneshot channel for communication
 /// between Raft core and client.
@@ -68,7 +73,26 @@ where C: RaftTypeConfig
         members: impl Into<ChangeMembers<C::NodeId, C::Node>>,
         retain: bool,
     ) -> Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>> {
-        self.management_api().change_membership(members, retain).await.into_raft_result()
+        self.management_api().change_membership(members, retain, None).await.into_raft_result()
+    }
+
+    /// Propose a cluster configuration change, tagged with a [`CorrelationId`].
+    ///
+    /// It is the same as [`Self::change_membership`], except that `correlation_id` is carried
+    /// along with both the joint and the uniform membership log entries, the same way as
+    /// [`Self::client_write_with_correlation_id`] does for a normal entry.
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "info", skip_all, fields(correlation_id = display(correlation_id)))]
+    pub async fn change_membership_with_correlation_id(
+        &self,
+        members: impl Into<ChangeMembers<C::NodeId, C::Node>>,
+        retain: bool,
+        correlation_id: CorrelationId,
+    ) -> Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>> {
+        self.management_api()
+            .change_membership(members, retain, Some(correlation_id))
+            .await
+            .into_raft_result()
     }
 
     /// Add a new learner raft node, optionally, blocking until up-to-speed.
@@ -110,4 +134,61 @@ where C: RaftTypeConfig
     ) -> Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>> {
         self.management_api().add_learner(id, node, blocking).await.into_raft_result()
     }
+
+    /// Replace voter `old` with a new node, e.g., to migrate a member to another machine.
+    ///
+    /// - Add `new_id` as a learner and wait until it is up to date, for at most
+    ///   [`Config::replace_node_timeout`](crate::Config::replace_node_timeout). A new node is
+    ///   bootstrapped by log replication, or by a full snapshot if the logs it needs are purged.
+    /// - Then swap `new_id` in for `old` in one **joint** config change, so that the cluster does
+    ///   not spend any time with reduced redundancy: `old` keeps voting until the uniform config
+    ///   without it is committed.
+    /// - `old` is removed from the cluster.
+    ///
+    /// If `new_id` does not catch up in time, it fails with
+    /// [`LearnerNotCaughtUp`](crate::errors::LearnerNotCaughtUp). If it does not catch up or the
+    /// swap fails, e.g., this node is no longer the leader, the change is rolled back: `new_id` is
+    /// removed again unless it was already a member before this call.
+    ///
+    /// To follow the stages of the replacement, use
+    /// [`replace_node_with_progress()`](Self::replace_node_with_progress).
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use openraft::BasicNode;
+    ///
+    /// // Move voter 3 to a new machine as node 5
+    /// let node = BasicNode { addr: "127.0.0.1:8085".to_string() };
+    /// raft.replace_node(3, 5, node).await?;
+    /// ```
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "info", skip(self, new_node))]
+    pub async fn replace_node(
+        &self,
+        old: C::NodeId,
+        new_id: C::NodeId,
+        new_node: C::Node,
+    ) -> Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>> {
+        self.replace_node_with_progress(old, new_id, new_node, |_| {}).await
+    }
+
+    /// Replace voter `old` with a new node, and report every stage of it to `progress`.
+    ///
+    /// See [`replace_node()`](Self::replace_node) for the details of the replacement, and
+    /// [`ReplaceNodeProgress`] for the stages reported.
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "info", skip(self, new_node, progress))]
+    pub async fn replace_node_with_progress<F>(
+        &self,
+        old: C::NodeId,
+        new_id: C::NodeId,
+        new_node: C::Node,
+        progress: F,
+    ) -> Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>>
+    where
+        F: FnMut(ReplaceNodeProgress<C>) + OptionalSend,
+    {
+        self.management_api().replace_node(old, new_id, new_node, progress).await.into_raft_result()
+    }
 }
@@ -0,0 +1,74 @@
+//! Choose the node to hand leadership off to when a leader shuts down.
+
+use crate::RaftTypeConfig;
+use crate::metrics::RaftMetrics;
+
+/// Returns the voter, other than this node, that has replicated the most logs, if this node is
+/// the leader.
+///
+/// Among voters equally caught up, the one with the smallest id is chosen. A voter without any
+/// log replicated is not chosen.
+pub(crate) fn handoff_target<C>(metrics: &RaftMetrics<C>) -> Option<C::NodeId>
+where C: RaftTypeConfig {
+    if metrics.current_leader.as_ref() != Some(&metrics.id) {
+        return None;
+    }
+
+    let replication = metrics.replication.as_deref()?;
+
+    let mut best = None;
+    for voter in metrics.membership_config.voter_ids() {
+        if voter == metrics.id {
+            continue;
+        }
+
+        let Some(Some(matching)) = replication.get(&voter) else {
+            continue;
+        };
+
+        if best.as_ref().is_none_or(|(_, m)| matching > m) {
+            best = Some((voter, matching.clone()));
+        }
+    }
+
+    best.map(|(voter, _)| voter)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::sync::Arc;
+
+    use maplit::btreeset;
+
+    use super::handoff_target;
+    use crate::Membership;
+    use crate::StoredMembership;
+    use crate::engine::testing::UTConfig;
+    use crate::engine::testing::log_id;
+    use crate::metrics::RaftMetrics;
+
+    #[test]
+    fn test_handoff_target() {
+        let mut m = RaftMetrics::<UTConfig>::new_initial(1);
+        m.membership_config = Arc::new(StoredMembership::new(
+            None,
+            Membership::new_with_defaults(vec![btreeset! {1,2,3,4}], btreeset! {5}),
+        ));
+        m.replication = Some(Arc::new(BTreeMap::from([
+            (1, Some(log_id(1, 1, 9))),
+            (2, Some(log_id(1, 1, 7))),
+            (3, Some(log_id(1, 1, 8))),
+            (4, Some(log_id(1, 1, 8))),
+            (5, Some(log_id(1, 1, 9))),
+        ])));
+
+        assert_eq!(None, handoff_target(&m), "not a leader");
+
+        m.current_leader = Some(1);
+        assert_eq!(Some(3), handoff_target(&m), "most caught up voter, smallest id");
+
+        m.replication = Some(Arc::new(BTreeMap::from([(2, None)])));
+        assert_eq!(None, handoff_target(&m), "no voter has logs replicated");
+    }
+}
@@ -0,0 +1,117 @@
+use std::fmt;
+use std::sync::Arc;
+
+use openraft_macros::since;
+
+use crate::RaftTypeConfig;
+use crate::core::io_flush_tracking::LogProgress;
+use crate::core::log_holds::LogHolds;
+use crate::core::raft_msg::external_command::ExternalCommand;
+use crate::errors::Fatal;
+use crate::raft::raft_inner::RaftInner;
+use crate::type_config::alias::LogIdOf;
+
+/// Tails the raft log of this node as entries become durable in the local [`RaftLogStorage`],
+/// returned by [`Raft::subscribe_log()`](crate::Raft::subscribe_log).
+///
+/// A co-located component, such as a change-data-capture exporter or a secondary index, waits with
+/// [`wait_durable()`](Self::wait_durable) instead of polling the log store, reads the entries
+/// with its own log reader, then calls [`advance()`](Self::advance).
+///
+/// Entries not yet consumed are not purged by the policy-based purge after building a snapshot,
+/// so the subscriber never misses one. Dropping the subscription releases them. Two kinds of purge
+/// are not held back:
+/// - An explicit [`Trigger::purge_log()`](crate::raft::trigger::Trigger::purge_log).
+/// - Installing a snapshot from the leader, which replaces the whole log.
+///
+/// A subscriber detects a gap when [`next_index()`](Self::next_index) is not found in the log.
+///
+/// A durable entry is not necessarily committed: on a follower, an uncommitted entry may still be
+/// truncated and replaced by a new leader. A subscriber that needs committed entries only should
+/// read up to [`RaftMetrics::committed`](crate::RaftMetrics::committed) instead.
+///
+/// [`RaftLogStorage`]: crate::storage::RaftLogStorage
+#[since(version = "0.10.0")]
+pub struct LogSubscription<C>
+where C: RaftTypeConfig
+{
+    hold_id: u64,
+    next_index: u64,
+    log_progress: LogProgress<C>,
+    holds: LogHolds,
+    inner: Arc<RaftInner<C>>,
+}
+
+impl<C> LogSubscription<C>
+where C: RaftTypeConfig
+{
+    pub(in crate::raft) fn new(hold_id: u64, next_index: u64, inner: Arc<RaftInner<C>>, holds: LogHolds) -> Self {
+        Self {
+            hold_id,
+            next_index,
+            log_progress: inner.progress_watcher.log_progress(),
+            holds,
+            inner,
+        }
+    }
+
+    /// The index of the first entry not yet consumed by this subscriber.
+    #[since(version = "0.10.0")]
+    pub fn next_index(&self) -> u64 {
+        self.next_index
+    }
+
+    /// Wait until the entry at [`next_index()`](Self::next_index) is durable in the local log.
+    ///
+    /// Returns the last durable log id; every entry from `next_index()` up to it can be read.
+    #[since(version = "0.10.0")]
+    pub async fn wait_durable(&mut self) -> Result<LogIdOf<C>, Fatal<C>> {
+        let next_index = self.next_index;
+
+        let durable = self
+            .log_progress
+            .wait_until(move |p| {
+                let last = p.as_ref().and_then(|p| p.last_log_id.as_ref());
+                last.is_some_and(|log_id| log_id.index() >= next_index)
+            })
+            .await;
+
+        match durable {
+            Ok(Some(flush_point)) => Ok(flush_point.last_log_id.unwrap()),
+            Ok(None) => unreachable!("the condition requires a durable log id"),
+            Err(_) => Err(self.inner.get_core_stop_error().await),
+        }
+    }
+
+    /// Mark the entries up to `upto_index`, inclusive, as consumed, so that they can be purged.
+    #[since(version = "0.10.0")]
+    pub async fn advance(&mut self, upto_index: u64) -> Result<(), Fatal<C>> {
+        if upto_index < self.next_index {
+            return Ok(());
+        }
+
+        self.next_index = upto_index + 1;
+        self.holds.advance(self.hold_id, self.next_index);
+        self.inner.send_external_command(ExternalCommand::RefreshPurgeHold).await
+    }
+}
+
+impl<C> Drop for LogSubscription<C>
+where C: RaftTypeConfig
+{
+    fn drop(&mut self) {
+        // `RaftCore` picks up the released hold the next time it refreshes the holds.
+        self.holds.remove(self.hold_id);
+    }
+}
+
+impl<C> fmt::Debug for LogSubscription<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LogSubscription")
+            .field("hold_id", &self.hold_id)
+            .field("next_index", &self.next_index)
+            .finish()
+    }
+}
@@ -0,0 +1,161 @@
+use openraft_macros::since;
+
+use crate::ConfigDigest;
+use crate::RaftTypeConfig;
+use crate::entry::RaftEntry;
+use crate::raft::AppendEntriesRequest;
+use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::VoteOf;
+
+/// Splits the log entries of an AppendEntries RPC into consecutive [`AppendEntriesRequest`]s of
+/// at most `max_entries` entries each.
+///
+/// The entries are pulled from an iterator only when the next request is built, so that a very
+/// large batch is never materialized as a whole: e.g., a follower transport that receives the
+/// entries of a request in frames feeds them into [`Raft::stream_append()`] chunk by chunk:
+///
+/// ```ignore
+/// let header = AppendEntriesRequest { entries: vec![], ..header };
+/// let chunks = AppendEntriesChunks::new(header, received_entries, 256);
+///
+/// let mut output = pin!(raft.stream_append(futures_util::stream::iter(chunks)));
+/// ```
+///
+/// Every request carries the vote and the other fields of the first request, with `prev_log_id`
+/// set to the last log id of the request before it. If there are no entries, a single request
+/// without entries is yielded.
+///
+/// [`Raft::stream_append()`]: crate::Raft::stream_append
+#[since(version = "0.10.0")]
+pub struct AppendEntriesChunks<C, I>
+where
+    C: RaftTypeConfig,
+    I: Iterator<Item = C::Entry>,
+{
+    vote: VoteOf<C>,
+    prev_log_id: Option<LogIdOf<C>>,
+    leader_commit: Option<LogIdOf<C>>,
+    backup_barrier: Option<LogIdOf<C>>,
+    after_snapshot: Option<LogIdOf<C>>,
+    config_digest: Option<ConfigDigest>,
+
+    /// The entries in the first request, yielded before `tail`.
+    head: std::vec::IntoIter<C::Entry>,
+    tail: I,
+
+    max_entries: usize,
+
+    /// Whether a request has been yielded.
+    started: bool,
+}
+
+impl<C, I> AppendEntriesChunks<C, I>
+where
+    C: RaftTypeConfig,
+    I: Iterator<Item = C::Entry>,
+{
+    /// Create the chunks of `first`, with its entries followed by `entries`.
+    ///
+    /// `max_entries` of 0 is treated as 1.
+    pub fn new(first: AppendEntriesRequest<C>, entries: I, max_entries: usize) -> Self {
+        Self {
+            vote: first.vote,
+            prev_log_id: first.prev_log_id,
+            leader_commit: first.leader_commit,
+            backup_barrier: first.backup_barrier,
+            after_snapshot: first.after_snapshot,
+            config_digest: first.config_digest,
+            head: first.entries.into_iter(),
+            tail: entries,
+            max_entries: max_entries.max(1),
+            started: false,
+        }
+    }
+}
+
+impl<C, I> Iterator for AppendEntriesChunks<C, I>
+where
+    C: RaftTypeConfig,
+    I: Iterator<Item = C::Entry>,
+{
+    type Item = AppendEntriesRequest<C>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entries = self.head.by_ref().chain(self.tail.by_ref()).take(self.max_entries).collect::<Vec<_>>();
+
+        if entries.is_empty() && self.started {
+            return None;
+        }
+        self.started = true;
+
+        let prev_log_id = self.prev_log_id.clone();
+        if let Some(last) = entries.last() {
+            self.prev_log_id = Some(last.log_id());
+        }
+
+        Some(AppendEntriesRequest {
+            vote: self.vote.clone(),
+            prev_log_id,
+            entries,
+            leader_commit: self.leader_commit.clone(),
+            backup_barrier: self.backup_barrier.clone(),
+            after_snapshot: self.after_snapshot.clone(),
+            config_digest: self.config_digest,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AppendEntriesChunks;
+    use crate::Vote;
+    use crate::engine::testing::UTConfig;
+    use crate::engine::testing::log_id;
+    use crate::raft::AppendEntriesRequest;
+    use crate::testing::blank_ent;
+    use crate::type_config::alias::EntryOf;
+
+    fn first(prev: Option<u64>, entries: Vec<u64>) -> AppendEntriesRequest<UTConfig> {
+        AppendEntriesRequest {
+            vote: Vote::new_committed(1, 1),
+            prev_log_id: prev.map(|i| log_id(1, 1, i)),
+            entries: entries.into_iter().map(|i| blank_ent::<UTConfig>(1, 1, i)).collect(),
+            leader_commit: Some(log_id(1, 1, 3)),
+            backup_barrier: None,
+            after_snapshot: None,
+            config_digest: None,
+        }
+    }
+
+    /// Returns the `(prev_log_id, last_log_id)` index of each chunk.
+    fn ranges<I>(chunks: AppendEntriesChunks<UTConfig, I>) -> Vec<(Option<u64>, Option<u64>)>
+    where I: Iterator<Item = EntryOf<UTConfig>> {
+        chunks
+            .map(|req| {
+                assert_eq!(Some(log_id(1, 1, 3)), req.leader_commit);
+                let r = req.log_id_range();
+                (r.prev.map(|x| x.index), r.last.map(|x| x.index))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_append_entries_chunks() {
+        let tail = || (5..10).map(|i| blank_ent::<UTConfig>(1, 1, i));
+
+        let got = ranges(AppendEntriesChunks::new(first(Some(2), vec![3, 4]), tail(), 3));
+        assert_eq!(vec![(Some(2), Some(5)), (Some(5), Some(8)), (Some(8), Some(9))], got);
+
+        let got = ranges(AppendEntriesChunks::new(first(Some(4), vec![]), tail(), 5));
+        assert_eq!(vec![(Some(4), Some(9))], got);
+
+        let got = ranges(AppendEntriesChunks::new(first(None, vec![1, 2]), std::iter::empty(), 0));
+        assert_eq!(vec![(None, Some(1)), (Some(1), Some(2))], got);
+    }
+
+    #[test]
+    fn test_append_entries_chunks_no_entries() {
+        let got = ranges(AppendEntriesChunks::new(first(Some(2), vec![]), std::iter::empty(), 3));
+        assert_eq!(vec![(Some(2), Some(2))], got, "a single request without entries");
+    }
+}
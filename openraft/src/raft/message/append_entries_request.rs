@@ -2,12 +2,21 @@ use std::fmt;
 
 use display_more::DisplayOptionExt;
 use display_more::DisplaySliceExt;
+use openraft_macros::since;
 
+use crate::ConfigDigest;
 use crate::RaftTypeConfig;
 use crate::entry::RaftEntry;
+#[cfg(feature = "audit-log-chain")]
+use crate::entry::chain_hash;
+#[cfg(feature = "audit-log-chain")]
+use crate::entry::raft_entry_ext::RaftEntryExt;
+use crate::errors::MalformedMessage;
 use crate::log_id_range::LogIdRange;
+use crate::raft::AppendEntriesChunks;
 use crate::type_config::alias::LogIdOf;
 use crate::type_config::alias::VoteOf;
+use crate::vote::RaftVote;
 
 /// An RPC sent by a cluster leader to replicate log entries (§5.3), and as a heartbeat (§5.2).
 ///
@@ -16,6 +25,7 @@ use crate::type_config::alias::VoteOf;
 /// which is always valid. Because `prev_log_id` is used to assert `entries` to be consecutive with
 /// the previous log entries, and `prev_log_id=None` is the very beginning position and there are no
 /// previous log entries.
+#[since]
 #[derive(Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
 pub struct AppendEntriesRequest<C: RaftTypeConfig> {
@@ -36,6 +46,31 @@ pub struct AppendEntriesRequest<C: RaftTypeConfig> {
     /// The receiver records this as its own cluster-committed value and applies up to it (gated by
     /// the locally persisted logs).
     pub leader_commit: Option<LogIdOf<C>>,
+
+    /// The log id of the last backup barrier proposed by the leader with
+    /// [`Raft::backup()`](crate::Raft::backup).
+    ///
+    /// A receiver that applies the log entry with this log id builds a snapshot right after it, so
+    /// that every node has a snapshot at the same log id.
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub backup_barrier: Option<LogIdOf<C>>,
+
+    /// The last log id of a snapshot the leader is sending to the receiver at the same time.
+    ///
+    /// The entries in this request follow that snapshot. A receiver that does not have
+    /// `prev_log_id` holds the request until the snapshot is installed, instead of rejecting it.
+    /// See [`Config::pipeline_snapshot_tail`](crate::Config::pipeline_snapshot_tail).
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub after_snapshot: Option<LogIdOf<C>>,
+
+    /// The digest of the leader's safety-relevant config.
+    ///
+    /// The receiver warns if it differs from its own. See [`ConfigDigest`].
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub config_digest: Option<ConfigDigest>,
 }
 
 impl<C: RaftTypeConfig> fmt::Debug for AppendEntriesRequest<C> {
@@ -45,6 +80,9 @@ impl<C: RaftTypeConfig> fmt::Debug for AppendEntriesRequest<C> {
             .field("prev_log_id", &self.prev_log_id)
             .field("entries", &self.entries)
             .field("leader_commit", &self.leader_commit)
+            .field("backup_barrier", &self.backup_barrier)
+            .field("after_snapshot", &self.after_snapshot)
+            .field("config_digest", &self.config_digest)
             .finish()
     }
 }
@@ -60,13 +98,100 @@ where C: RaftTypeConfig
             self.prev_log_id.display(),
             self.leader_commit.display(),
             self.entries.display()
-        )
+        )?;
+
+        if let Some(barrier) = &self.backup_barrier {
+            write!(f, ", backup_barrier={}", barrier)?;
+        }
+
+        if let Some(snapshot) = &self.after_snapshot {
+            write!(f, ", after_snapshot={}", snapshot)?;
+        }
+
+        Ok(())
     }
 }
 
 impl<C> AppendEntriesRequest<C>
 where C: RaftTypeConfig
 {
+    /// Split this request into consecutive requests of at most `max_entries` entries each.
+    ///
+    /// See [`AppendEntriesChunks`].
+    #[since(version = "0.10.0")]
+    pub fn into_chunks(self, max_entries: usize) -> AppendEntriesChunks<C, std::iter::Empty<C::Entry>> {
+        AppendEntriesChunks::new(self, std::iter::empty(), max_entries)
+    }
+
+    /// Check the invariants every request sent by a leader holds.
+    ///
+    /// - `vote` is committed;
+    /// - `prev_log_id` and the entries are not proposed by a leader greater than `vote`;
+    /// - the entries are consecutive and follow `prev_log_id`, with non-decreasing leader ids;
+    /// - a chained entry follows the chain hash of the chained entry before it, if any.
+    ///
+    /// A follower rejects a request that breaks any of them with
+    /// [`AppendEntriesResponse::Malformed`](crate::raft::AppendEntriesResponse::Malformed), before
+    /// it touches the local state.
+    #[since(version = "0.10.0")]
+    pub fn validate(&self) -> Result<(), MalformedMessage<C>> {
+        if !self.vote.is_committed() {
+            return Err(MalformedMessage::UncommittedLeaderVote {
+                vote: self.vote.clone(),
+            });
+        }
+
+        let leader_id = self.vote.leader_id();
+        let newer_than_vote = |log_id: &LogIdOf<C>| leader_id < log_id.committed_leader_id();
+
+        if let Some(prev) = &self.prev_log_id
+            && newer_than_vote(prev)
+        {
+            return Err(MalformedMessage::LogNewerThanVote {
+                log_id: prev.clone(),
+                vote: self.vote.clone(),
+            });
+        }
+
+        let mut prev = self.prev_log_id.clone();
+        #[cfg(feature = "audit-log-chain")]
+        let mut prev_chain_hash = None;
+
+        for entry in &self.entries {
+            let log_id = entry.log_id();
+
+            let follows = match &prev {
+                None => log_id.index() == 0,
+                Some(p) => log_id.index() == p.index() + 1 && log_id.committed_leader_id() >= p.committed_leader_id(),
+            };
+
+            if !follows {
+                return Err(MalformedMessage::NonConsecutiveEntries { prev, next: log_id });
+            }
+
+            if newer_than_vote(&log_id) {
+                return Err(MalformedMessage::LogNewerThanVote {
+                    log_id,
+                    vote: self.vote.clone(),
+                });
+            }
+
+            #[cfg(feature = "audit-log-chain")]
+            {
+                if let (Some(p), Some(h), Some(content)) = (prev_chain_hash, entry.chain_hash(), entry.content_hash())
+                    && h != chain_hash(Some(p), content)
+                {
+                    return Err(MalformedMessage::BrokenLogChain { log_id });
+                }
+                prev_chain_hash = entry.chain_hash();
+            }
+
+            prev = Some(log_id);
+        }
+
+        Ok(())
+    }
+
     /// Returns the last log id in this request.
     ///
     /// This is the log id of the last entry, or `prev_log_id` if entries is empty.
@@ -85,9 +210,18 @@ where C: RaftTypeConfig
 
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "audit-log-chain")]
+    use crate::RaftEntry;
     use crate::Vote;
     use crate::engine::testing::UTConfig;
     use crate::engine::testing::log_id;
+    #[cfg(feature = "audit-log-chain")]
+    use crate::entry::ChainHash;
+    #[cfg(feature = "audit-log-chain")]
+    use crate::entry::chain_hash;
+    #[cfg(feature = "audit-log-chain")]
+    use crate::entry::raft_entry_ext::RaftEntryExt;
+    use crate::errors::MalformedMessage;
     use crate::raft::AppendEntriesRequest;
     use crate::testing::blank_ent;
 
@@ -97,6 +231,9 @@ mod tests {
             prev_log_id: prev.map(|i| log_id(1, 1, i)),
             entries: entries.into_iter().map(|i| blank_ent::<UTConfig>(1, 1, i)).collect(),
             leader_commit: None,
+            backup_barrier: None,
+            after_snapshot: None,
+            config_digest: None,
         }
     }
 
@@ -122,4 +259,105 @@ mod tests {
         let r = req(Some(5), vec![6, 7]).log_id_range();
         assert_eq!((r.prev, r.last), (Some(log_id(1, 1, 5)), Some(log_id(1, 1, 7))));
     }
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(Ok(()), req(None, vec![]).validate());
+        assert_eq!(Ok(()), req(None, vec![0, 1]).validate());
+        assert_eq!(Ok(()), req(Some(5), vec![6, 7]).validate());
+
+        let mut r = req(Some(5), vec![6]);
+        r.vote = Vote::new(1, 1);
+        assert_eq!(
+            Err(MalformedMessage::UncommittedLeaderVote { vote: Vote::new(1, 1) }),
+            r.validate()
+        );
+
+        let mut r = req(Some(5), vec![]);
+        r.prev_log_id = Some(log_id(2, 1, 5));
+        assert_eq!(
+            Err(MalformedMessage::LogNewerThanVote {
+                log_id: log_id(2, 1, 5),
+                vote: Vote::new_committed(1, 1),
+            }),
+            r.validate()
+        );
+
+        let mut r = req(Some(5), vec![6, 7]);
+        r.entries[1] = blank_ent::<UTConfig>(2, 1, 7);
+        assert_eq!(
+            Err(MalformedMessage::LogNewerThanVote {
+                log_id: log_id(2, 1, 7),
+                vote: Vote::new_committed(1, 1),
+            }),
+            r.validate()
+        );
+
+        assert_eq!(
+            Err(MalformedMessage::NonConsecutiveEntries {
+                prev: Some(log_id(1, 1, 5)),
+                next: log_id(1, 1, 7),
+            }),
+            req(Some(5), vec![7]).validate()
+        );
+
+        assert_eq!(
+            Err(MalformedMessage::NonConsecutiveEntries {
+                prev: None,
+                next: log_id(1, 1, 1),
+            }),
+            req(None, vec![1]).validate()
+        );
+
+        let mut r = req(Some(5), vec![6, 7]);
+        r.vote = Vote::new_committed(3, 1);
+        r.prev_log_id = Some(log_id(2, 1, 5));
+        assert_eq!(
+            Err(MalformedMessage::NonConsecutiveEntries {
+                prev: Some(log_id(2, 1, 5)),
+                next: log_id(1, 1, 6),
+            }),
+            r.validate(),
+            "leader id must not decrease"
+        );
+    }
+
+    #[cfg(feature = "audit-log-chain")]
+    #[test]
+    fn test_validate_log_chain() {
+        let mut r = req(Some(5), vec![6, 7, 8]);
+        let mut prev = None;
+        for ent in r.entries.iter_mut() {
+            let h = chain_hash(prev, ent.content_hash().unwrap());
+            ent.set_chain_hash(Some(h));
+            prev = Some(h);
+        }
+        assert_eq!(Ok(()), r.validate());
+
+        // The first entry is not verified, but the next one does not follow it.
+        let mut r2 = r.clone();
+        r2.entries[0].set_chain_hash(Some(ChainHash([0; 32])));
+        assert_eq!(
+            Err(MalformedMessage::BrokenLogChain {
+                log_id: log_id(1, 1, 7)
+            }),
+            r2.validate()
+        );
+
+        let mut r3 = r.clone();
+        r3.entries[2] = blank_ent::<UTConfig>(1, 1, 8);
+        r3.entries[2].set_timestamp(Some(1));
+        r3.entries[2].set_chain_hash(r.entries[2].chain_hash());
+        assert_eq!(
+            Err(MalformedMessage::BrokenLogChain {
+                log_id: log_id(1, 1, 8)
+            }),
+            r3.validate()
+        );
+
+        // Entries that are not chained are not verified.
+        let mut r4 = r.clone();
+        r4.entries[1].set_chain_hash(None);
+        assert_eq!(Ok(()), r4.validate());
+    }
 }
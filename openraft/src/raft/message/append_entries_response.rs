@@ -3,6 +3,7 @@ use std::fmt;
 use display_more::DisplayOptionExt;
 
 use crate::RaftTypeConfig;
+use crate::errors::MalformedMessage;
 use crate::raft::StreamAppendError;
 use crate::raft::stream_append::StreamAppendResult;
 use crate::type_config::alias::LogIdOf;
@@ -51,6 +52,13 @@ pub enum AppendEntriesResponse<C: RaftTypeConfig> {
     /// And a leader's vote(committed vote) must be total order with other votes.
     /// Therefore, it has to be a higher vote: `mine_vote < v`
     HigherVote(VoteOf<C>),
+
+    /// The request is malformed and is rejected by the remote target node without being applied.
+    ///
+    /// See [`AppendEntriesRequest::validate()`].
+    ///
+    /// [`AppendEntriesRequest::validate()`]: crate::raft::AppendEntriesRequest::validate
+    Malformed(MalformedMessage<C>),
 }
 
 impl<C> AppendEntriesResponse<C>
@@ -63,7 +71,7 @@ where C: RaftTypeConfig
 
     /// Returns the partial success log id if this is a `PartialSuccess` response.
     ///
-    /// Returns `None` for `Success`, `Conflict`, or `HigherVote` responses.
+    /// Returns `None` for any other response.
     pub(crate) fn get_partial_success(&self) -> Option<&Option<LogIdOf<C>>> {
         match self {
             AppendEntriesResponse::PartialSuccess(log_id) => Some(log_id),
@@ -91,6 +99,7 @@ where C: RaftTypeConfig
             AppendEntriesResponse::PartialSuccess(log_id) => Ok(log_id),
             AppendEntriesResponse::Conflict => Err(StreamAppendError::Conflict(prev_log_id.unwrap())),
             AppendEntriesResponse::HigherVote(vote) => Err(StreamAppendError::HigherVote(vote)),
+            AppendEntriesResponse::Malformed(e) => Err(StreamAppendError::Malformed(e)),
         }
     }
 }
@@ -101,6 +110,7 @@ impl<C: RaftTypeConfig> From<StreamAppendResult<C>> for AppendEntriesResponse<C>
             Ok(_) => AppendEntriesResponse::Success,
             Err(StreamAppendError::Conflict(_)) => AppendEntriesResponse::Conflict,
             Err(StreamAppendError::HigherVote(v)) => AppendEntriesResponse::HigherVote(v),
+            Err(StreamAppendError::Malformed(e)) => AppendEntriesResponse::Malformed(e),
         }
     }
 }
@@ -116,6 +126,7 @@ where C: RaftTypeConfig
             }
             AppendEntriesResponse::HigherVote(vote) => write!(f, "Higher vote, {}", vote),
             AppendEntriesResponse::Conflict => write!(f, "Conflict"),
+            AppendEntriesResponse::Malformed(e) => write!(f, "Malformed: {}", e),
         }
     }
 }
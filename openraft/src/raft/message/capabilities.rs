@@ -0,0 +1,95 @@
+use std::fmt;
+
+use openraft_macros::since;
+
+/// The protocol features a node supports, advertised to its peers in RPC responses.
+///
+/// A protocol feature that changes what a node sends is only used when every relevant peer is
+/// able to handle it; otherwise a cluster in the middle of a rolling upgrade, running old and new
+/// versions side by side, could stop making progress. A node advertises its capabilities in
+/// [`VoteResponse::capabilities`], and the receiver turns off a feature as soon as a peer it
+/// depends on is seen without it.
+///
+/// A response from a version that does not know about capabilities deserializes to
+/// [`Capabilities::empty()`], as does one that passed through a network implementation that does
+/// not transport the field: such a peer is treated as supporting none of the features.
+///
+/// The capabilities known for every peer are exposed in
+/// [`RaftMetrics::peer_capabilities`](crate::metrics::RaftMetrics::peer_capabilities).
+///
+/// [`VoteResponse::capabilities`]: crate::raft::VoteResponse::capabilities
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(transparent))]
+pub struct Capabilities(u64);
+
+impl Capabilities {
+    /// The node handles [`RaftNetworkV2::pre_vote`](crate::network::v2::RaftNetworkV2::pre_vote)
+    /// requests, see [`Config::enable_pre_vote`](crate::Config::enable_pre_vote).
+    pub const PRE_VOTE: Self = Self(1 << 0);
+
+    const NAMES: [(Self, &'static str); 1] = [(Self::PRE_VOTE, "pre_vote")];
+
+    /// No capability: a peer running an older version.
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// The capabilities of this version of Openraft.
+    pub const fn supported() -> Self {
+        Self::PRE_VOTE
+    }
+
+    /// Build from raw bits, e.g., as received in an RPC; unknown bits are kept.
+    pub const fn from_bits(bits: u64) -> Self {
+        Self(bits)
+    }
+
+    /// The raw bits, e.g., to send in an RPC.
+    pub const fn bits(&self) -> u64 {
+        self.0
+    }
+
+    /// Returns `true` if every capability in `other` is present.
+    pub const fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl fmt::Display for Capabilities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+
+        let mut first = true;
+        for (cap, name) in Self::NAMES {
+            if self.contains(cap) {
+                if !first {
+                    write!(f, ",")?;
+                }
+                write!(f, "{}", name)?;
+                first = false;
+            }
+        }
+
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Capabilities;
+
+    #[test]
+    fn test_capabilities() {
+        assert!(Capabilities::supported().contains(Capabilities::PRE_VOTE));
+        assert!(!Capabilities::empty().contains(Capabilities::PRE_VOTE));
+        assert!(Capabilities::empty().contains(Capabilities::empty()));
+
+        let future = Capabilities::from_bits(0b11);
+        assert!(future.contains(Capabilities::PRE_VOTE));
+        assert_eq!(0b11, future.bits());
+
+        assert_eq!("{pre_vote}", Capabilities::supported().to_string());
+        assert_eq!("{}", Capabilities::empty().to_string());
+    }
+}
@@ -0,0 +1,29 @@
+use openraft_macros::since;
+
+/// An application-assigned id that correlates a write request with everything Openraft does for
+/// it.
+///
+/// A correlation id is attached to a write with
+/// [`Raft::client_write_with_correlation_id()`](crate::Raft::client_write_with_correlation_id),
+/// [`Raft::change_membership_with_correlation_id()`](crate::Raft::change_membership_with_correlation_id)
+/// or [`WriteRequest::correlation_id()`](crate::raft::WriteRequest::correlation_id).
+/// It travels with the responder of the write: it is recorded in the `correlation_id` field of
+/// the tracing events emitted when the entry is proposed, committed and completed, and it is
+/// exposed to the state machine by
+/// [`ApplyResponder::correlation_id()`](crate::storage::ApplyResponder::correlation_id) when the
+/// entry is applied.
+///
+/// Openraft does not interpret the value, and it is not replicated: it is only visible on the
+/// Leader that accepted the write.
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(derive_more::Display)]
+#[display("{}", _0)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct CorrelationId(pub u64);
+
+impl From<u64> for CorrelationId {
+    fn from(v: u64) -> Self {
+        CorrelationId(v)
+    }
+}
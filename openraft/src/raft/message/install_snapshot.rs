@@ -1,8 +1,12 @@
 use std::fmt;
 
+use display_more::DisplayOptionExt;
+
 use crate::RaftTypeConfig;
+use crate::errors::MalformedMessage;
 use crate::type_config::alias::SnapshotMetaOf;
 use crate::type_config::alias::VoteOf;
+use crate::vote::RaftVote;
 
 /// An RPC sent by the Raft leader to send chunks of a snapshot to a follower (§7).
 #[derive(Clone, Debug)]
@@ -42,11 +46,19 @@ impl<C: RaftTypeConfig> fmt::Display for InstallSnapshotRequest<C> {
 #[derive(Debug)]
 #[derive(PartialEq, Eq)]
 #[derive(derive_more::Display)]
-#[display("{{vote:{}}}", vote)]
+#[display("{{vote:{}, received:{}}}", vote, received.display())]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
 pub struct InstallSnapshotResponse<C: RaftTypeConfig> {
     /// The responder's current vote.
     pub vote: VoteOf<C>,
+
+    /// The number of bytes of the snapshot the responder has received.
+    ///
+    /// A chunked transfer that is interrupted resumes from this offset instead of from the
+    /// beginning. `None` if the responder does not track it, in which case the transfer
+    /// restarts from offset 0.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub received: Option<u64>,
 }
 
 /// The response to `Raft::install_full_snapshot` API.
@@ -71,6 +83,35 @@ impl<C> From<SnapshotResponse<C>> for InstallSnapshotResponse<C>
 where C: RaftTypeConfig
 {
     fn from(snap_resp: SnapshotResponse<C>) -> Self {
-        Self { vote: snap_resp.vote }
+        Self {
+            vote: snap_resp.vote,
+            received: None,
+        }
+    }
+}
+
+/// Check the invariants every snapshot sent by a leader holds.
+///
+/// - `vote` is committed;
+/// - the snapshot has a last log id;
+/// - the membership config in the snapshot is not after the last log id.
+pub(crate) fn validate_snapshot_meta<C>(vote: &VoteOf<C>, meta: &SnapshotMetaOf<C>) -> Result<(), MalformedMessage<C>>
+where C: RaftTypeConfig {
+    if !vote.is_committed() {
+        return Err(MalformedMessage::UncommittedLeaderVote { vote: vote.clone() });
     }
+
+    let Some(last) = &meta.last_log_id else {
+        return Err(MalformedMessage::SnapshotWithoutLastLogId);
+    };
+
+    let membership_log_id = meta.last_membership.log_id();
+    if membership_log_id.as_ref() > Some(last) {
+        return Err(MalformedMessage::MembershipAfterSnapshot {
+            membership_log_id: membership_log_id.clone(),
+            last_log_id: meta.last_log_id.clone(),
+        });
+    }
+
+    Ok(())
 }
@@ -3,10 +3,15 @@
 //! Request and response types for an application to talk to the Raft,
 //! and are also used by network layer to talk to other Raft nodes.
 
+mod append_entries_chunks;
 mod append_entries_request;
 mod append_entries_response;
+mod capabilities;
+mod correlation_id;
 mod install_snapshot;
 mod log_segment;
+mod read_index;
+mod seed_snapshot;
 mod stream_append_error;
 mod transfer_leader;
 mod vote;
@@ -15,14 +20,24 @@ mod write;
 mod client_write;
 mod write_request;
 
+pub use append_entries_chunks::AppendEntriesChunks;
 pub use append_entries_request::AppendEntriesRequest;
 pub use append_entries_response::AppendEntriesResponse;
+pub use capabilities::Capabilities;
 pub use client_write::ClientWriteResponse;
 pub use client_write::ClientWriteResult;
+pub use correlation_id::CorrelationId;
 pub use install_snapshot::InstallSnapshotRequest;
 pub use install_snapshot::InstallSnapshotResponse;
 pub use install_snapshot::SnapshotResponse;
+pub(crate) use install_snapshot::validate_snapshot_meta;
 pub use log_segment::LogSegment;
+pub use read_index::ReadIndexRequest;
+pub use read_index::ReadIndexResponse;
+pub use seed_snapshot::FetchSnapshotRequest;
+pub use seed_snapshot::FetchSnapshotResponse;
+pub use seed_snapshot::SeedSnapshotRequest;
+pub use seed_snapshot::SeedSnapshotResponse;
 pub use stream_append_error::StreamAppendError;
 pub use transfer_leader::TransferLeaderError;
 pub use transfer_leader::TransferLeaderRequest;
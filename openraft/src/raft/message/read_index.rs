@@ -0,0 +1,58 @@
+use std::fmt;
+
+use openraft_macros::since;
+
+use crate::RaftTypeConfig;
+use crate::errors::LinearizableReadError;
+use crate::raft::ReadPolicy;
+use crate::type_config::alias::LogIdOf;
+
+/// A request from a follower or learner to the leader for the log id a linearizable read has to
+/// wait for.
+///
+/// See [`Raft::follower_read_index()`](crate::Raft::follower_read_index).
+#[since(version = "0.10.0")]
+#[derive(Clone, Debug)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct ReadIndexRequest<C>
+where C: RaftTypeConfig
+{
+    /// The node that serves the read.
+    pub(crate) from: C::NodeId,
+
+    /// How the leader confirms its leadership before returning the read log id.
+    pub(crate) read_policy: ReadPolicy,
+}
+
+impl<C> ReadIndexRequest<C>
+where C: RaftTypeConfig
+{
+    /// Create a new read index request.
+    pub fn new(from: C::NodeId, read_policy: ReadPolicy) -> Self {
+        Self { from, read_policy }
+    }
+
+    /// The node that serves the read.
+    pub fn from(&self) -> &C::NodeId {
+        &self.from
+    }
+
+    /// How the leader confirms its leadership before returning the read log id.
+    pub fn read_policy(&self) -> &ReadPolicy {
+        &self.read_policy
+    }
+}
+
+impl<C> fmt::Display for ReadIndexRequest<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(from={}, read_policy={})", self.from, self.read_policy)
+    }
+}
+
+/// Result of a delivered read index request: the log id the reading node has to apply before
+/// serving the read.
+#[since(version = "0.10.0")]
+pub type ReadIndexResponse<C> = Result<LogIdOf<C>, LinearizableReadError<C>>;
@@ -0,0 +1,116 @@
+use std::fmt;
+
+use display_more::DisplayOptionExt;
+use openraft_macros::since;
+
+use crate::RaftTypeConfig;
+use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::SnapshotMetaOf;
+use crate::type_config::alias::SnapshotOf;
+use crate::type_config::alias::VoteOf;
+
+/// A request from the leader to a follower or learner to fetch its snapshot from a seed node,
+/// instead of receiving it from the leader.
+///
+/// The node receiving it should pass it to
+/// [`Raft::handle_seed_snapshot()`](crate::Raft::handle_seed_snapshot).
+/// See [`Raft::set_snapshot_seed()`](crate::Raft::set_snapshot_seed).
+#[since(version = "0.10.0")]
+#[derive(Clone, Debug)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct SeedSnapshotRequest<C>
+where C: RaftTypeConfig
+{
+    /// The leader's current vote, which authorizes the fetch.
+    pub vote: VoteOf<C>,
+
+    /// The id of the node to fetch the snapshot from.
+    pub seed_id: C::NodeId,
+
+    /// The node to fetch the snapshot from.
+    pub seed_node: C::Node,
+
+    /// The snapshot must include at least this log id, i.e., the last log the leader purged, so
+    /// that the leader can replicate the logs after it.
+    pub min_last_log_id: Option<LogIdOf<C>>,
+}
+
+impl<C> fmt::Display for SeedSnapshotRequest<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "SeedSnapshotRequest {{ vote:{}, seed:{}, min_last_log_id:{} }}",
+            self.vote,
+            self.seed_id,
+            self.min_last_log_id.display()
+        )
+    }
+}
+
+/// The response to a [`SeedSnapshotRequest`].
+#[since(version = "0.10.0")]
+#[derive(Clone, Debug)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct SeedSnapshotResponse<C>
+where C: RaftTypeConfig
+{
+    /// The responder's current vote.
+    pub vote: VoteOf<C>,
+
+    /// The meta of the snapshot fetched from the seed and installed, or `None` if it could not be
+    /// fetched, in which case the leader sends its own snapshot.
+    pub meta: Option<SnapshotMetaOf<C>>,
+}
+
+impl<C> fmt::Display for SeedSnapshotResponse<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "SeedSnapshotResponse {{ vote:{}, meta:{} }}",
+            self.vote,
+            self.meta.display()
+        )
+    }
+}
+
+/// A request from a follower or learner to a seed node for its current snapshot.
+///
+/// The node receiving it should pass it to
+/// [`Raft::handle_fetch_snapshot()`](crate::Raft::handle_fetch_snapshot).
+#[since(version = "0.10.0")]
+#[derive(Clone, Debug)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct FetchSnapshotRequest<C>
+where C: RaftTypeConfig
+{
+    /// The vote of the leader that authorized the fetch.
+    pub vote: VoteOf<C>,
+
+    /// The snapshot must include at least this log id.
+    pub min_last_log_id: Option<LogIdOf<C>>,
+}
+
+impl<C> fmt::Display for FetchSnapshotRequest<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "FetchSnapshotRequest {{ vote:{}, min_last_log_id:{} }}",
+            self.vote,
+            self.min_last_log_id.display()
+        )
+    }
+}
+
+/// The response to a [`FetchSnapshotRequest`]: the current snapshot of the seed node, or `None`
+/// if it has none that includes `min_last_log_id`, or the fetch is not authorized.
+#[since(version = "0.10.0")]
+pub type FetchSnapshotResponse<C> = Option<SnapshotOf<C>>;
@@ -4,6 +4,7 @@ use peel_off::Peel;
 
 use crate::RaftTypeConfig;
 use crate::errors::ConflictingLogId;
+use crate::errors::MalformedMessage;
 use crate::errors::RejectVote;
 use crate::type_config::alias::LogIdOf;
 use crate::type_config::alias::VoteOf;
@@ -21,6 +22,9 @@ pub enum StreamAppendError<C: RaftTypeConfig> {
 
     /// The follower has a higher vote than the sender's.
     HigherVote(VoteOf<C>),
+
+    /// The request is malformed and is rejected without being applied.
+    Malformed(MalformedMessage<C>),
 }
 
 impl<C> fmt::Display for StreamAppendError<C>
@@ -34,22 +38,26 @@ where C: RaftTypeConfig
             StreamAppendError::HigherVote(vote) => {
                 write!(f, "HigherVote({})", vote)
             }
+            StreamAppendError::Malformed(e) => {
+                write!(f, "Malformed({})", e)
+            }
         }
     }
 }
 
-/// Peel off `RejectVote`, leaving `ConflictingLogId` as the residual.
+/// Peel off `RejectVote`, leaving `ConflictingLogId` or `MalformedMessage` as the residual.
 impl<C: RaftTypeConfig> Peel for StreamAppendError<C> {
     type Peeled = RejectVote<C>;
-    type Residual = ConflictingLogId<C>;
+    type Residual = Result<ConflictingLogId<C>, MalformedMessage<C>>;
 
-    fn peel(self) -> Result<ConflictingLogId<C>, RejectVote<C>> {
+    fn peel(self) -> Result<Result<ConflictingLogId<C>, MalformedMessage<C>>, RejectVote<C>> {
         match self {
             StreamAppendError::HigherVote(vote) => Err(RejectVote { higher: vote }),
-            StreamAppendError::Conflict(log_id) => Ok(ConflictingLogId {
+            StreamAppendError::Conflict(log_id) => Ok(Ok(ConflictingLogId {
                 expect: log_id,
                 local: None,
-            }),
+            })),
+            StreamAppendError::Malformed(e) => Ok(Err(e)),
         }
     }
 }
@@ -4,9 +4,13 @@ use std::fmt;
 use display_more::DisplayOptionExt;
 use openraft_macros::since;
 
+use crate::ConfigDigest;
 use crate::RaftTypeConfig;
+use crate::errors::MalformedMessage;
+use crate::raft::Capabilities;
 use crate::type_config::alias::LogIdOf;
 use crate::type_config::alias::VoteOf;
+use crate::vote::RaftVote;
 
 /// An RPC sent by candidates to gather votes (§5.2).
 #[since]
@@ -27,6 +31,21 @@ pub struct VoteRequest<C: RaftTypeConfig> {
     #[since(version = "0.10.0")]
     #[cfg_attr(feature = "serde", serde(default))]
     pub leadership_transfer: bool,
+
+    /// True if the candidate runs with
+    /// [`Config::relaxed_durability`](crate::Config::relaxed_durability).
+    ///
+    /// A voter does not grant its vote to a candidate whose durability mode differs from its own.
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub relaxed_durability: bool,
+
+    /// The digest of the candidate's safety-relevant config.
+    ///
+    /// The receiver warns if it differs from its own. See [`ConfigDigest`].
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub config_digest: Option<ConfigDigest>,
 }
 
 impl<C> fmt::Display for VoteRequest<C>
@@ -35,10 +54,11 @@ where C: RaftTypeConfig
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{{vote:{}, last_log:{}, leadership_transfer:{}}}",
+            "{{vote:{}, last_log:{}, leadership_transfer:{}, relaxed_durability:{}}}",
             self.vote,
             self.last_log_id.display(),
             self.leadership_transfer,
+            self.relaxed_durability,
         )
     }
 }
@@ -52,8 +72,28 @@ where C: RaftTypeConfig
             vote,
             last_log_id,
             leadership_transfer: false,
+            relaxed_durability: false,
+            config_digest: None,
         }
     }
+
+    /// Check the invariants every vote request holds.
+    ///
+    /// The candidate's last log id can not be proposed by a leader greater than the vote it asks
+    /// for. A voter does not grant a request that breaks it.
+    #[since(version = "0.10.0")]
+    pub fn validate(&self) -> Result<(), MalformedMessage<C>> {
+        if let Some(last) = &self.last_log_id
+            && self.vote.leader_id() < last.committed_leader_id()
+        {
+            return Err(MalformedMessage::LogNewerThanVote {
+                log_id: last.clone(),
+                vote: self.vote.clone(),
+            });
+        }
+
+        Ok(())
+    }
 }
 
 /// The response to a `VoteRequest`.
@@ -72,6 +112,13 @@ pub struct VoteResponse<C: RaftTypeConfig> {
 
     /// The last log id stored on the remote voter.
     pub last_log_id: Option<LogIdOf<C>>,
+
+    /// The protocol features the remote voter supports.
+    ///
+    /// A candidate does not run a Pre-Vote once a voter is seen without
+    /// [`Capabilities::PRE_VOTE`]. See [`Capabilities`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub capabilities: Capabilities,
 }
 
 impl<C> VoteResponse<C>
@@ -83,6 +130,7 @@ where C: RaftTypeConfig
             vote: vote.borrow().clone(),
             vote_granted: granted,
             last_log_id: last_log_id.map(|x| x.borrow().clone()),
+            capabilities: Capabilities::empty(),
         }
     }
 
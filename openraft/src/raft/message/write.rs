@@ -1,18 +1,17 @@
 use crate::RaftTypeConfig;
 use crate::errors::ClientWriteError;
-use crate::errors::ForwardToLeader;
+use crate::errors::WriteError;
 use crate::raft::ClientWriteResponse;
 use crate::raft::ClientWriteResult;
 use crate::type_config::alias::LogIdOf;
 
 /// The result of a write operation, returned by [`Raft::client_write_many()`].
 ///
-/// This is a simplified version of [`ClientWriteResult`] that only contains
-/// [`ForwardToLeader`] as the error type, since batch writes do not support
-/// membership changes.
+/// This is a simplified version of [`ClientWriteResult`] with [`WriteError`] as the error type,
+/// since batch writes do not support membership changes.
 ///
 /// [`Raft::client_write_many()`]: crate::Raft::client_write_many
-pub type WriteResult<C> = Result<WriteResponse<C>, ForwardToLeader<C>>;
+pub type WriteResult<C> = Result<WriteResponse<C>, WriteError<C>>;
 
 /// Response from a successful write operation.
 ///
@@ -47,9 +46,19 @@ impl<C: RaftTypeConfig> From<ClientWriteResponse<C>> for WriteResponse<C> {
 pub(crate) fn into_write_result<C: RaftTypeConfig>(result: ClientWriteResult<C>) -> WriteResult<C> {
     match result {
         Ok(resp) => Ok(resp.into()),
-        Err(ClientWriteError::ForwardToLeader(e)) => Err(e),
+        Err(ClientWriteError::ForwardToLeader(e)) => Err(WriteError::ForwardToLeader(e)),
+        Err(ClientWriteError::StorageFull(e)) => Err(WriteError::StorageFull(e)),
         Err(ClientWriteError::ChangeMembershipError(_)) => {
             unreachable!("ChangeMembershipError should not occur for normal writes")
         }
+        Err(ClientWriteError::WriteExpired(_)) => {
+            unreachable!("WriteExpired should not occur for writes without a deadline")
+        }
+        Err(ClientWriteError::ApplyScopeUnsupported(_)) => {
+            unreachable!("ApplyScopeUnsupported should not occur for writes without an apply scope")
+        }
+        Err(ClientWriteError::ReservedIndexMismatch(_)) => {
+            unreachable!("ReservedIndexMismatch should not occur for writes to unreserved log indexes")
+        }
     }
 }
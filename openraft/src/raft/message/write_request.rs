@@ -10,9 +10,11 @@ use crate::entry::EntryPayload;
 use crate::errors::Fatal;
 #[cfg(feature = "runtime-stats")]
 use crate::raft::api::app::propose_at_now;
+use crate::raft::message::CorrelationId;
 use crate::raft::raft_inner::RaftInner;
 use crate::raft::responder::core_responder::CoreResponder;
 use crate::type_config::alias::CommittedLeaderIdOf;
+use crate::type_config::alias::InstantOf;
 use crate::type_config::alias::WriteResponderOf;
 
 /// Builder for submitting write requests to Raft.
@@ -48,6 +50,8 @@ where C: RaftTypeConfig
     pub(in crate::raft) app_data: C::D,
     pub(in crate::raft) responder: Option<CoreResponder<C>>,
     pub(in crate::raft) expected_leader: Option<CommittedLeaderIdOf<C>>,
+    pub(in crate::raft) correlation_id: Option<CorrelationId>,
+    pub(in crate::raft) deadline: Option<InstantOf<C>>,
 }
 
 impl<'a, C> WriteRequest<'a, C>
@@ -69,7 +73,7 @@ where C: RaftTypeConfig
     /// ```
     #[since(version = "0.10.0")]
     pub fn responder(mut self, responder: WriteResponderOf<C>) -> Self {
-        self.responder = Some(CoreResponder::UserDefined(responder));
+        self.responder = Some(CoreResponder::user_defined(responder));
         self
     }
 
@@ -130,6 +134,65 @@ where C: RaftTypeConfig
     }
 }
 
+impl<'a, C> WriteRequest<'a, C>
+where C: RaftTypeConfig
+{
+    /// Attach a [`CorrelationId`] to this write, for application-level request tracing.
+    ///
+    /// The id is carried by the responder, thus it only takes effect along with
+    /// [`.responder()`](Self::responder): a fire-and-forget write has nothing to carry it.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let (responder, rx) = ProgressResponder::complete_only();
+    /// raft.write(my_data)
+    ///     .correlation_id(CorrelationId(request_id))
+    ///     .responder(responder)
+    ///     .await?;
+    /// ```
+    #[since(version = "0.10.0")]
+    pub fn correlation_id(mut self, correlation_id: impl Into<CorrelationId>) -> Self {
+        self.correlation_id = Some(correlation_id.into());
+        self
+    }
+
+    /// Abandon this write if it is not committed by `deadline`.
+    ///
+    /// The deadline is stored in the log entry, converted to the leader's wall clock, see
+    /// [`RaftEntry::deadline()`]. The leader does not commit the entry once the deadline passes;
+    /// instead it runs an election to start a new term. When it is elected again, its first entry
+    /// records that the entry is not committed, every node applies the entry as a blank entry,
+    /// and the responder is completed with [`ClientWriteError::WriteExpired`]. Expiry adds no
+    /// round trip to a write that is committed in time.
+    ///
+    /// If another node is elected instead, or the leader restarts, the entry is committed by the
+    /// next leader as usual, later than the deadline.
+    ///
+    /// It requires [`Config::entry_timestamp`](crate::Config::entry_timestamp) and a log entry
+    /// type that stores a deadline, or the deadline is ignored. Like
+    /// [`.correlation_id()`](Self::correlation_id), it only takes effect along with
+    /// [`.responder()`](Self::responder).
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let (responder, rx) = ProgressResponder::complete_only();
+    /// raft.write(my_data)
+    ///     .deadline(TypeConfig::now() + Duration::from_secs(1))
+    ///     .responder(responder)
+    ///     .await?;
+    /// ```
+    ///
+    /// [`ClientWriteError::WriteExpired`]: crate::errors::ClientWriteError::WriteExpired
+    /// [`RaftEntry::deadline()`]: crate::entry::RaftEntry::deadline
+    #[since(version = "0.10.0")]
+    pub fn deadline(mut self, deadline: InstantOf<C>) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+}
+
 impl<'a, C> IntoFuture for WriteRequest<'a, C>
 where C: RaftTypeConfig
 {
@@ -141,7 +204,9 @@ where C: RaftTypeConfig
             self.inner
                 .send_msg(RaftMsg::ClientWrite {
                     payloads: Batch::of([EntryPayload::Normal(self.app_data)]),
-                    responders: Batch::of([self.responder]),
+                    responders: Batch::of([self
+                        .responder
+                        .map(|r| r.with_correlation_id(self.correlation_id).with_deadline(self.deadline))]),
                     expected_leader: self.expected_leader,
                     #[cfg(feature = "runtime-stats")]
                     proposed_at: propose_at_now::<C>(),
@@ -9,17 +9,27 @@
 //! to efficiently share access.
 
 pub(crate) mod api;
+mod client_write_handle;
 #[cfg(test)]
 mod declare_raft_types_test;
+mod durability_report;
 mod impl_raft_blocking_write;
+mod leader_handoff;
 pub mod linearizable_read;
 pub(crate) mod message;
+mod pending_respond_info;
+mod raft_event;
 mod raft_inner;
+mod read_options;
+mod replace_node_progress;
 pub mod responder;
 mod runtime_config_handle;
+mod shutdown_report;
 pub(crate) mod stream_append;
 pub mod trigger;
 mod watch_handle;
+mod write_options;
+mod write_wait;
 
 pub(crate) use api::app::AppApi;
 pub(crate) use api::management::ManagementApi;
@@ -27,24 +37,39 @@ pub(crate) use api::protocol::ProtocolApi;
 
 pub(in crate::raft) mod core_state;
 mod leader;
+mod log_subscription;
+mod ordered_writer;
 
+use std::collections::BTreeSet;
+use std::collections::VecDeque;
 use std::fmt::Debug;
 use std::future::Future;
+use std::ops::Range;
 use std::sync::Arc;
 use std::sync::Mutex;
 use std::time::Duration;
 
 use core_state::CoreState;
 use derive_more::Display;
+use display_more::DisplayOptionExt;
 use futures_util::FutureExt;
 use linearizable_read::Linearizer;
+pub use message::AppendEntriesChunks;
 pub use message::AppendEntriesRequest;
 pub use message::AppendEntriesResponse;
+pub use message::Capabilities;
 pub use message::ClientWriteResponse;
 pub use message::ClientWriteResult;
+pub use message::CorrelationId;
+pub use message::FetchSnapshotRequest;
+pub use message::FetchSnapshotResponse;
 pub use message::InstallSnapshotRequest;
 pub use message::InstallSnapshotResponse;
 pub use message::LogSegment;
+pub use message::ReadIndexRequest;
+pub use message::ReadIndexResponse;
+pub use message::SeedSnapshotRequest;
+pub use message::SeedSnapshotResponse;
 pub use message::SnapshotResponse;
 pub use message::StreamAppendError;
 pub use message::TransferLeaderError;
@@ -61,9 +86,23 @@ use tracing::Instrument;
 use tracing::Level;
 use tracing::trace_span;
 
+pub use self::client_write_handle::ClientWriteHandle;
+pub use self::durability_report::DurabilityReport;
 pub use self::leader::Leader;
+pub use self::log_subscription::LogSubscription;
+pub use self::ordered_writer::OrderedWriter;
+pub use self::pending_respond_info::PendingRespondInfo;
+pub use self::raft_event::RaftEvent;
+pub use self::read_options::ReadOptions;
+pub use self::replace_node_progress::ReplaceNodeProgress;
+pub use self::shutdown_report::ShutdownReport;
 pub use self::watch_handle::WatchChangeHandle;
+pub use self::write_options::WriteOptions;
+pub use self::write_wait::WriteWait;
+use crate::ConfigDigest;
+use crate::EffectiveConfig;
 use crate::Extensions;
+use crate::Membership;
 use crate::OptionalSend;
 use crate::RaftNetworkFactory;
 use crate::RaftState;
@@ -84,7 +123,10 @@ use crate::core::RaftCore;
 use crate::core::SharedReplicateBatch;
 use crate::core::StepDownWatcher;
 use crate::core::Tick;
+use crate::core::config_mismatches::ConfigMismatches;
+use crate::core::event_log::EventLog;
 use crate::core::heartbeat::handle::HeartbeatWorkersHandle;
+use crate::core::held_writes::HeldWrites;
 use crate::core::io_flush_tracking::AppliedProgress;
 use crate::core::io_flush_tracking::CommitProgress;
 pub use crate::core::io_flush_tracking::FlushPoint;
@@ -92,24 +134,50 @@ use crate::core::io_flush_tracking::IoProgressWatcher;
 use crate::core::io_flush_tracking::LogProgress;
 use crate::core::io_flush_tracking::SnapshotProgress;
 use crate::core::io_flush_tracking::VoteProgress;
+#[cfg(feature = "audit-log-chain")]
+use crate::core::log_chain_hashes::LogChainHashes;
+use crate::core::log_holds::LogHolds;
 use crate::core::merged_raft_msg_receiver::BatchRaftMsgReceiver;
 use crate::core::notification::Notification;
+use crate::core::peer_capabilities::PeerCapabilities;
+use crate::core::quorum_ack_latency::QuorumAckLatency;
 use crate::core::raft_msg::RaftMsg;
 use crate::core::raft_msg::external_command::ExternalCommand;
+use crate::core::replication_throughput::ReplicationThroughput;
 use crate::core::runtime_stats::RuntimeStats;
+use crate::core::shared_metrics::SharedMetrics;
 use crate::core::sm;
 use crate::core::sm::worker;
+use crate::core::snapshot_history::SnapshotHistory;
+use crate::core::snapshot_meta_cache::SnapshotMetaCache;
+use crate::core::snapshot_tail::SnapshotTail;
+use crate::core::snapshot_transfers::SnapshotTransfers;
 use crate::engine::Engine;
 use crate::engine::EngineConfig;
+use crate::entry::ApplyScope;
+#[cfg(feature = "audit-log-chain")]
+use crate::entry::ChainHash;
 use crate::entry::EntryPayload;
 use crate::errors::ClientWriteError;
+use crate::errors::EventsLagged;
 use crate::errors::Fatal;
 use crate::errors::ForwardToLeader;
 use crate::errors::InitializeError;
 use crate::errors::LinearizableReadError;
+#[cfg(feature = "audit-log-chain")]
+use crate::errors::LogChainError;
+use crate::errors::QuorumPolicyError;
 use crate::errors::RaftError;
+use crate::errors::SeedSnapshotError;
+use crate::errors::StaleRead;
 use crate::errors::into_raft_result::IntoRaftResult;
+use crate::log_id::option_raft_log_id_ext::OptionRaftLogIdExt;
 use crate::membership::IntoNodes;
+use crate::membership::QuorumPolicy;
+use crate::membership::check_quorum_policy;
+use crate::metrics::CatchUpEstimate;
+use crate::metrics::LeaderSince;
+use crate::metrics::MetricsHistory;
 use crate::metrics::MetricsRecorder;
 use crate::metrics::RaftDataMetrics;
 use crate::metrics::RaftMetrics;
@@ -120,14 +188,22 @@ use crate::raft::raft_inner::RaftInner;
 pub use crate::raft::runtime_config_handle::RuntimeConfigHandle;
 use crate::raft::trigger::Trigger;
 use crate::raft_state::IOId;
+use crate::raft_state::LogStateReader;
+use crate::storage::LogEntryMeta;
 use crate::storage::RaftLogStorage;
 use crate::storage::RaftStateMachine;
+use crate::storage::SnapshotLocator;
+use crate::storage::SnapshotTrigger;
+use crate::storage::StorageUsageProbe;
+use crate::storage::v2::applied_result_cache::AppliedResultCache;
 use crate::type_config::TypeConfigExt;
+use crate::type_config::alias::InstantOf;
 use crate::type_config::alias::JoinErrorOf;
 use crate::type_config::alias::LogIdOf;
 use crate::type_config::alias::MpscWeakSenderOf;
 use crate::type_config::alias::NodeIdOf;
 use crate::type_config::alias::SnapshotDataOf;
+use crate::type_config::alias::SnapshotMetaOf;
 use crate::type_config::alias::SnapshotOf;
 use crate::type_config::alias::VoteOf;
 use crate::type_config::alias::WatchReceiverOf;
@@ -240,12 +316,14 @@ macro_rules! declare_raft_types {
 /// This enum defines strategies for ensuring linearizable reads in distributed systems
 /// while balancing between consistency guarantees and performance.
 #[derive(Clone, Debug, Display, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum ReadPolicy {
     /// Uses leader lease to avoid network round-trips for read operations.
     ///
     /// With `LeaseRead`, the leader can serve reads locally without contacting followers
     /// as long as it believes its leadership lease is still valid. This provides better
-    /// performance compared to `ReadIndex` but assumes clock drift between nodes is negligible.
+    /// performance compared to `ReadIndex` but assumes clock drift between nodes is bounded by
+    /// [`Config::lease_read_clock_drift`].
     ///
     /// Note: This offers slightly weaker consistency guarantees than `ReadIndex` in exchange
     /// for lower latency.
@@ -443,16 +521,87 @@ where
     /// ```
     #[tracing::instrument(level="debug", skip_all, fields(cluster=%config.cluster_name))]
     pub async fn new<LS, N>(
+        id: C::NodeId,
+        config: Arc<Config>,
+        network: N,
+        log_store: LS,
+        state_machine: SM,
+    ) -> Result<Self, Fatal<C>>
+    where
+        N: RaftNetworkFactory<C>,
+        LS: RaftLogStorage<C>,
+    {
+        match Self::spawn(id, config, network, log_store, state_machine, None).await? {
+            Ok(raft) => Ok(raft),
+            Err(e) => unreachable!("no quorum policy to check: {}", e),
+        }
+    }
+
+    /// Create and spawn a new Raft task that decides the election and commit quorums with
+    /// `quorum_policy`, in place of a majority of the voters.
+    ///
+    /// The arguments are the same as [`new()`](Self::new). The same policy must be used on every
+    /// node; see [`QuorumPolicy`] for the requirements on the quorums and an example.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QuorumPolicyError`] if the quorums the policy decides for the stored membership
+    /// do not intersect, or [`Fatal`] error if reading the storage fails.
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level="debug", skip_all, fields(cluster=%config.cluster_name))]
+    pub async fn new_with_quorum_policy<LS, N>(
+        id: C::NodeId,
+        config: Arc<Config>,
+        network: N,
+        log_store: LS,
+        state_machine: SM,
+        quorum_policy: Arc<dyn QuorumPolicy<C>>,
+    ) -> Result<Self, RaftError<C, QuorumPolicyError<C::NodeId>>>
+    where
+        N: RaftNetworkFactory<C>,
+        LS: RaftLogStorage<C>,
+    {
+        Self::spawn(id, config, network, log_store, state_machine, Some(quorum_policy))
+            .await
+            .into_raft_result()
+    }
+
+    async fn spawn<LS, N>(
         id: C::NodeId,
         config: Arc<Config>,
         network: N,
         mut log_store: LS,
         mut state_machine: SM,
-    ) -> Result<Self, Fatal<C>>
+        quorum_policy: Option<Arc<dyn QuorumPolicy<C>>>,
+    ) -> Result<Result<Self, QuorumPolicyError<C::NodeId>>, Fatal<C>>
     where
         N: RaftNetworkFactory<C>,
         LS: RaftLogStorage<C>,
     {
+        let state = {
+            let mut helper = StorageHelper::new(&mut log_store, &mut state_machine).with_id(id.clone());
+            helper.get_initial_state().await?
+        };
+
+        if let Some(policy) = &quorum_policy
+            && let Err(e) = check_quorum_policy(policy.as_ref(), state.membership_state.effective().membership())
+        {
+            return Ok(Err(e));
+        }
+
+        // The entries after the committed one may be truncated: read their chain hashes before
+        // `RaftCore` starts, so that it does not read the log to chain the next entries.
+        #[cfg(feature = "audit-log-chain")]
+        let log_chain_hashes = if config.audit_log_chain() {
+            let committed = state.committed().index().unwrap_or_default();
+            let start = std::cmp::max(committed, state.last_purged_log_id().next_index());
+            let end = state.last_log_id().next_index();
+            let mut log_reader = log_store.get_log_reader().await;
+            LogChainHashes::load(&mut log_reader, start, end).await?
+        } else {
+            LogChainHashes::default()
+        };
+
         let api_channel_size = config.api_channel_size();
         let notification_channel_size = config.notification_channel_size();
 
@@ -490,22 +639,33 @@ where
             cluster = display(&config.cluster_name)
         );
 
-        let eng_config = EngineConfig::new(id.clone(), config.as_ref());
-
-        let state = {
-            let mut helper = StorageHelper::new(&mut log_store, &mut state_machine).with_id(id.clone());
-            helper.get_initial_state().await?
-        };
+        let mut eng_config = EngineConfig::new(id.clone(), config.as_ref());
+        eng_config.quorum_policy = quorum_policy;
 
+        let snapshot_meta_cache = SnapshotMetaCache::new(&state.snapshot_meta);
+        let snapshot_history = SnapshotHistory::new(config.snapshot_keep_count(), &state.snapshot_meta);
+        let replication_throughput = ReplicationThroughput::new();
+        let shutdown_report = Arc::new(Mutex::new(None));
         let engine = Engine::new(state, eng_config);
 
         let sm_span = tracing::span!(parent: &core_span, Level::DEBUG, "sm_worker");
 
+        let applied_result_cache = AppliedResultCache::new(config.applied_result_cache_size());
+
+        let log_only = engine.state.membership_state.effective().membership().is_log_only(&id);
+
         let sm_handle = worker::Worker::spawn(
+            id.clone(),
             state_machine,
             log_store.get_log_reader().await,
+            log_store.get_log_reader().await,
             tx_notify.clone(),
             config.state_machine_channel_size(),
+            config.warm_up_after_install(),
+            config.audit_log_chain(),
+            config.stream_payload_threshold(),
+            applied_result_cache.clone(),
+            !log_only,
             sm_span,
         );
 
@@ -515,8 +675,12 @@ where
         let (io_accepted_tx, _io_accepted_rx) = C::watch_channel(default_io_id.clone());
         let (io_submitted_tx, _io_submitted_rx) = C::watch_channel(default_io_id);
         let (committed_tx, _committed_rx) = C::watch_channel(None);
+        let (backup_barrier_tx, _backup_barrier_rx) = C::watch_channel(None);
+        let (tx_events, rx_events) = C::watch_channel(EventLog::default());
 
         let shared_replicate_batch = SharedReplicateBatch::new();
+        let metrics_history = MetricsHistory::new(config.metrics_history_size());
+        let log_holds = LogHolds::default();
 
         let core: RaftCore<C, N, LS, SM> = RaftCore {
             id: id.clone(),
@@ -552,6 +716,8 @@ where
             io_submitted_tx,
 
             committed_tx,
+            backup_barrier_tx,
+            tx_events,
             tx_metrics,
             tx_data_metrics,
             tx_server_metrics,
@@ -561,6 +727,28 @@ where
             shared_replicate_batch,
 
             metrics_recorder: None,
+            storage_usage_probe: None,
+            snapshot_trigger: None,
+            #[cfg(feature = "lease-check")]
+            lease_checker: None,
+            metrics_history: metrics_history.clone(),
+            log_holds: log_holds.clone(),
+            snapshot_meta_cache: snapshot_meta_cache.clone(),
+            snapshot_history: snapshot_history.clone(),
+            replication_throughput: replication_throughput.clone(),
+            snapshot_tail: SnapshotTail::default(),
+            held_writes: HeldWrites::default(),
+            #[cfg(feature = "audit-log-chain")]
+            log_chain_hashes,
+            #[cfg(feature = "audit-log-chain")]
+            log_chaining: Default::default(),
+            snapshot_transfers: SnapshotTransfers::new(config.max_inflight_snapshots()),
+            snapshot_waiters: Vec::new(),
+            config_mismatches: ConfigMismatches::new(ConfigDigest::new::<C>(&config)),
+            peer_capabilities: PeerCapabilities::default(),
+            quorum_ack_latency: QuorumAckLatency::new(config.quorum_ack_slo()),
+            shared_metrics: SharedMetrics::default(),
+            shutdown_report: shutdown_report.clone(),
 
             span: core_span,
         };
@@ -586,16 +774,24 @@ where
             rx_metrics,
             rx_data_metrics,
             rx_server_metrics,
+            rx_events,
             progress_watcher,
             tx_shutdown: Mutex::new(Some(tx_shutdown)),
             core_state: Mutex::new(CoreState::Running(core_handle)),
+            metrics_history,
+            applied_result_cache,
+            log_holds,
+            snapshot_meta_cache,
+            snapshot_history,
+            replication_throughput,
+            shutdown_report,
             extensions: Extensions::default(),
         };
 
-        Ok(Self {
+        Ok(Ok(Self {
             inner: Arc::new(inner),
             sm_cmd_tx,
-        })
+        }))
     }
 }
 
@@ -622,6 +818,27 @@ where C: RaftTypeConfig
         &self.inner.config
     }
 
+    /// Return the config this Raft node is actually running, with the source of every field.
+    ///
+    /// Unlike [`config()`](Self::config), fields updated with
+    /// [`runtime_config()`](Self::runtime_config) hold their current value, and each field tells
+    /// whether it is the default, set at startup, or updated at runtime:
+    ///
+    /// ```ignore
+    /// let effective = raft.effective_config();
+    /// for (field, source) in effective.overridden() {
+    ///     println!("{}: {}", field, source);
+    /// }
+    /// ```
+    #[since(version = "0.10.0")]
+    pub fn effective_config(&self) -> EffectiveConfig {
+        EffectiveConfig::new(
+            &self.inner.config,
+            &self.inner.runtime_config,
+            self.inner.tick_handle.is_enabled(),
+        )
+    }
+
     /// Access the underlying extensions map.
     ///
     /// For most use cases, prefer [`extension()`](Self::extension) which provides
@@ -742,6 +959,27 @@ where C: RaftTypeConfig
         }
     }
 
+    /// Get when this node established its current leadership.
+    ///
+    /// Returns `None` if this node is not leader. The returned [`LeaderSince`] carries both the
+    /// monotonic time, to measure how long this node has been leader, and the wall-clock time, to
+    /// display on a dashboard.
+    ///
+    /// It is read from the latest metrics, without calling `RaftCore`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Do not transfer leadership right after an election.
+    /// if raft.leader_since().is_some_and(|s| s.elapsed() > Duration::from_secs(10)) {
+    ///     raft.trigger().transfer_leader(to).await?;
+    /// }
+    /// ```
+    #[since(version = "0.10.0")]
+    pub fn leader_since(&self) -> Option<LeaderSince<C>> {
+        self.inner.rx_metrics.borrow_watched().leader_since
+    }
+
     /// Get the ID of this Raft node.
     ///
     /// # Example
@@ -793,6 +1031,53 @@ where C: RaftTypeConfig
         membership.membership().learner_ids().collect::<Vec<_>>().into_iter()
     }
 
+    /// Report which voters must keep their data for every committed log to stay recoverable.
+    ///
+    /// It is computed from the replication progress and the membership in the latest metrics of
+    /// this node, so it is only available on the leader. Check it before decommissioning the
+    /// hardware of a voter: as long as the voters in
+    /// [`DurabilityReport::must_survive`] keep their data, no committed log is lost.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let report = raft.durability_report()?;
+    /// if !report.must_survive.contains(&node_id) {
+    ///     // The disk of `node_id` can be wiped, if the others in `must_survive` are kept.
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ForwardToLeader`] if this node is not the leader.
+    ///
+    /// [`ForwardToLeader`]: crate::errors::ForwardToLeader
+    #[since(version = "0.10.0")]
+    pub fn durability_report(&self) -> Result<DurabilityReport<C>, ForwardToLeader<C>> {
+        self.as_leader()?;
+
+        // borrow_watched() holds a lock that blocks RaftCore, clone and release it quickly.
+        let (committed, membership, replication) = {
+            let metrics = self.inner.rx_metrics.borrow_watched();
+            (
+                metrics.local_committed.clone(),
+                metrics.membership_config.clone(),
+                metrics.replication.clone(),
+            )
+        };
+
+        let Some(replication) = replication else {
+            // The replication progress of a new leader is not yet published.
+            return Err(ForwardToLeader::empty());
+        };
+
+        Ok(DurabilityReport::new(
+            committed,
+            membership.membership(),
+            replication.iter(),
+        ))
+    }
+
     /// Create a new [`ProtocolApi`] to handle Raft protocol RPCs received by this Raft node.
     ///
     /// [`ProtocolApi`] provides the following protocol APIs:
@@ -801,6 +1086,8 @@ where C: RaftTypeConfig
     /// - [`ProtocolApi::get_snapshot`]
     /// - [`ProtocolApi::begin_receiving_snapshot`]
     /// - [`ProtocolApi::install_full_snapshot`]
+    /// - [`ProtocolApi::install_snapshot_locator`]
+    /// - [`ProtocolApi::install_snapshot_meta`]
     /// - [`ProtocolApi::handle_transfer_leader`]
     pub(crate) fn protocol_api(&self) -> ProtocolApi<C> {
         ProtocolApi::new(self.inner.clone())
@@ -860,6 +1147,108 @@ where C: RaftTypeConfig
         self.inner.send_external_command(ExternalCommand::SetMetricsRecorder { recorder }).await
     }
 
+    /// Set or unset the probe that reports the storage usage of this node.
+    ///
+    /// With a probe installed and [`Config::storage_quota`] set, a node whose storage usage
+    /// exceeds the quota builds a snapshot and purges the logs included in it. If the usage is
+    /// still above the quota, client writes are rejected with [`StorageFull`] until it is back
+    /// under the quota. Pass `None` to stop enforcing the quota.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use openraft::storage::StorageUsageProbe;
+    ///
+    /// #[derive(Debug)]
+    /// struct DirSize(Arc<AtomicU64>);
+    /// impl StorageUsageProbe for DirSize {
+    ///     fn usage(&self) -> u64 { self.0.load(Ordering::Relaxed) }
+    /// }
+    ///
+    /// raft.set_storage_usage_probe(Some(Arc::new(DirSize(size)))).await?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Fatal`] error if RaftCore is shut down or has a storage error.
+    ///
+    /// [`Config::storage_quota`]: crate::Config::storage_quota
+    /// [`StorageFull`]: crate::errors::StorageFull
+    #[since(version = "0.10.0")]
+    pub async fn set_storage_usage_probe(&self, probe: Option<Arc<dyn StorageUsageProbe>>) -> Result<(), Fatal<C>> {
+        self.inner.send_external_command(ExternalCommand::SetStorageUsageProbe { probe }).await
+    }
+
+    /// Set or unset the trigger that decides when to build a snapshot.
+    ///
+    /// The trigger is evaluated in addition to [`Config::snapshot_policy`]: a snapshot is built
+    /// when either decides to. With [`SnapshotPolicy::Never`], only the trigger decides. Pass
+    /// `None` to remove it.
+    ///
+    /// See [`SnapshotTrigger`] for an example.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Fatal`] error if RaftCore is shut down or has a storage error.
+    ///
+    /// [`Config::snapshot_policy`]: crate::Config::snapshot_policy
+    /// [`SnapshotPolicy::Never`]: crate::SnapshotPolicy::Never
+    #[since(version = "0.10.0")]
+    pub async fn set_snapshot_trigger(&self, trigger: Option<Arc<dyn SnapshotTrigger<C>>>) -> Result<(), Fatal<C>> {
+        self.inner.send_external_command(ExternalCommand::SetSnapshotTrigger { trigger }).await
+    }
+
+    /// Tell Raft that the state machine is compacting and applies are expected to be slow for
+    /// `expected`.
+    ///
+    /// A storage engine that runs a scheduled compaction calls it before starting, so that this
+    /// node defers the snapshot builds triggered by [`SnapshotPolicy`] until the compaction is
+    /// over, instead of piling them up on a busy disk. The end of the compaction is reported in
+    /// [`RaftMetrics::compacting_until`], so that an alert on the apply lag of this node can be
+    /// relaxed meanwhile. Call it again to extend the compaction, or with [`Duration::ZERO`] once
+    /// it finished early.
+    ///
+    /// A snapshot triggered with [`Raft::trigger()`] is not deferred.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// raft.notify_compaction(Duration::from_secs(60)).await?;
+    /// db.compact_range(None, None);
+    /// raft.notify_compaction(Duration::ZERO).await?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Fatal`] error if RaftCore is shut down or has a storage error.
+    ///
+    /// [`SnapshotPolicy`]: crate::SnapshotPolicy
+    /// [`RaftMetrics::compacting_until`]: crate::RaftMetrics::compacting_until
+    #[since(version = "0.10.0")]
+    pub async fn notify_compaction(&self, expected: Duration) -> Result<(), Fatal<C>> {
+        self.inner.send_external_command(ExternalCommand::NotifyCompaction { expected }).await
+    }
+
+    /// Set or unset the checker that asserts the leader lease invariant in a simulation run.
+    ///
+    /// Install the same [`LeaseChecker`] on every node of the cluster: every
+    /// [`ReadPolicy::LeaseRead`] this node serves as a leader is reported to it, and it panics if
+    /// two leaders serve lease reads at overlapping times. Pass `None` to stop reporting.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Fatal`] error if RaftCore is shut down or has a storage error.
+    ///
+    /// [`LeaseChecker`]: crate::testing::lease_check::LeaseChecker
+    #[cfg(feature = "lease-check")]
+    #[since(version = "0.10.0")]
+    pub async fn set_lease_checker(
+        &self,
+        checker: Option<crate::testing::lease_check::LeaseChecker<C>>,
+    ) -> Result<(), Fatal<C>> {
+        self.inner.send_external_command(ExternalCommand::SetLeaseChecker { checker }).await
+    }
+
     /// Submit an AppendEntries RPC to this Raft node.
     ///
     /// These RPCs are sent by the cluster leader to replicate log entries (§5.3), and are also
@@ -972,6 +1361,65 @@ where C: RaftTypeConfig
         self.protocol_api().get_snapshot().await.into_raft_result()
     }
 
+    /// Get the meta of the latest snapshot this node has, without opening the snapshot data.
+    ///
+    /// Unlike [`get_snapshot()`](Self::get_snapshot), it does not call `RaftCore` or the state
+    /// machine: it reads a copy that `RaftCore` replaces once a snapshot is built or installed.
+    /// It is cheap enough for a health endpoint to show how recent the snapshot is.
+    ///
+    /// Returns `None` if this node has no snapshot.
+    #[since(version = "0.10.0")]
+    pub fn current_snapshot_meta(&self) -> Option<SnapshotMetaOf<C>> {
+        self.inner.snapshot_meta_cache.get()
+    }
+
+    /// Get the meta of the most recent snapshots this node built or installed, oldest first.
+    ///
+    /// With [`Config::snapshot_keep_count`] set, it lists up to that many snapshots, which the
+    /// state machine is expected to keep, e.g., for a point-in-time restore. Otherwise it lists
+    /// only the current snapshot. Like [`current_snapshot_meta()`](Self::current_snapshot_meta),
+    /// it does not call `RaftCore`.
+    ///
+    /// The history starts with the snapshot found at startup; older ones are not listed.
+    #[since(version = "0.10.0")]
+    pub fn snapshot_history(&self) -> Vec<SnapshotMetaOf<C>> {
+        self.inner.snapshot_history.get()
+    }
+
+    /// Estimate how long `target` takes to catch up with this leader, and whether installing a
+    /// snapshot would be faster than replicating logs.
+    ///
+    /// The estimate is computed from the replication throughput this leader measured recently,
+    /// preferring the throughput to `target` if it has been measured, and the duration of the
+    /// last snapshot it sent. `target` does not have to be a member yet: a node that is not
+    /// replicated to is estimated as if it had no log.
+    ///
+    /// It does not call `RaftCore`. Returns `None` if this node is not the leader.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// if let Some(est) = raft.estimate_catchup(&node_id) {
+    ///     println!("lag: {}, prefer snapshot: {}, ETA: {:?}", est.lag_entries, est.prefer_snapshot(), est.duration());
+    /// }
+    /// ```
+    #[since(version = "0.10.0")]
+    pub fn estimate_catchup(&self, target: &C::NodeId) -> Option<CatchUpEstimate> {
+        let metrics = self.inner.rx_metrics.borrow_watched();
+        let replication = metrics.replication.as_ref()?;
+
+        let matched = replication.get(target).cloned().flatten();
+        let last_log_id = metrics.log_id_list.last();
+
+        Some(self.inner.replication_throughput.estimate(
+            target,
+            matched.as_ref(),
+            last_log_id,
+            metrics.purged.as_ref(),
+            metrics.snapshot.as_ref(),
+        ))
+    }
+
     /// Get a snapshot data for receiving snapshot from the leader.
     #[since(version = "0.10.0", change = "SnapshotData without Box")]
     #[tracing::instrument(level = "debug", skip_all)]
@@ -994,6 +1442,46 @@ where C: RaftTypeConfig
         self.protocol_api().install_full_snapshot(vote, snapshot).await
     }
 
+    /// Install a snapshot stored out of band, which the state machine fetches by itself.
+    ///
+    /// The leader sends the locator via [`RaftNetworkV2::snapshot_locator`] instead of the
+    /// snapshot data, when its state machine returns one from
+    /// [`RaftStateMachine::get_snapshot_locator`]. The implementation on the remote node responds
+    /// to it by calling this method, which returns once
+    /// [`RaftStateMachine::install_snapshot_locator`] has fetched and installed the snapshot.
+    ///
+    /// [`RaftNetworkV2::snapshot_locator`]: crate::network::RaftNetworkV2::snapshot_locator
+    /// [`RaftStateMachine::get_snapshot_locator`]: crate::storage::RaftStateMachine::get_snapshot_locator
+    /// [`RaftStateMachine::install_snapshot_locator`]: crate::storage::RaftStateMachine::install_snapshot_locator
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn install_snapshot_locator(
+        &self,
+        vote: VoteOf<C>,
+        locator: SnapshotLocator<C>,
+    ) -> Result<SnapshotResponse<C>, Fatal<C>> {
+        self.protocol_api().install_snapshot_locator(vote, locator).await
+    }
+
+    /// Install only the metadata of a snapshot, on a witness.
+    ///
+    /// A witness keeps no state machine data: when it falls behind the purged logs of the leader,
+    /// the leader sends it the snapshot metadata via [`RaftNetworkV2::snapshot_meta`] instead of
+    /// a snapshot. The implementation on the remote node responds to it by calling this method,
+    /// which returns once [`RaftStateMachine::install_snapshot_meta`] has recorded it.
+    ///
+    /// [`RaftNetworkV2::snapshot_meta`]: crate::network::RaftNetworkV2::snapshot_meta
+    /// [`RaftStateMachine::install_snapshot_meta`]: crate::storage::RaftStateMachine::install_snapshot_meta
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn install_snapshot_meta(
+        &self,
+        vote: VoteOf<C>,
+        meta: SnapshotMetaOf<C>,
+    ) -> Result<SnapshotResponse<C>, Fatal<C>> {
+        self.protocol_api().install_snapshot_meta(vote, meta).await
+    }
+
     /// Get the ID of the current leader from this Raft node.
     ///
     /// This method is based on the Raft metrics system which does a good job at staying
@@ -1051,6 +1539,83 @@ where C: RaftTypeConfig
         Ok(Some(state.read_log_id().clone()))
     }
 
+    /// Serves a linearizable read with the leader lease, without any network round-trip.
+    ///
+    /// It is a shorthand of
+    /// [`ensure_linearizable(ReadPolicy::LeaseRead)`](Self::ensure_linearizable): if this node
+    /// is the leader and its lease, shortened by [`Config::lease_read_clock_drift`], is still
+    /// valid, it waits for the state machine to apply up to the read log id, and returns the
+    /// applied log id. Reads from the state machine after it returns are linearizable.
+    ///
+    /// Returns [`ForwardToLeader`](crate::errors::ForwardToLeader) if this node is not the leader
+    /// or the lease has expired. The application may then fall back to
+    /// [`ReadPolicy::ReadIndex`].
+    ///
+    /// ```ignore
+    /// my_raft.lease_read().await?;
+    /// let val = my_raft.with_state_machine(|sm| { sm.read("foo") }).await?;
+    /// ```
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn lease_read(&self) -> Result<Option<LogIdOf<C>>, RaftError<C, LinearizableReadError<C>>> {
+        let linearizer = self.app_api().get_read_linearizer(ReadPolicy::LeaseRead).await.into_raft_result()?;
+
+        let state = linearizer.await_ready(self).await?;
+        Ok(state.applied().cloned())
+    }
+
+    /// Actively confirms that this node is still the leader, by sending a round of heartbeats to
+    /// the voters and waiting for a quorum of them to acknowledge.
+    ///
+    /// It returns the time before the heartbeats are sent: this node was the leader at least until
+    /// then. It is an on-demand proof of leadership, stronger than checking
+    /// [`RaftMetrics::current_leader`], which may be stale, and cheaper than a write, since
+    /// nothing is appended to the log. Unlike
+    /// [`ensure_linearizable()`](Self::ensure_linearizable), it does not wait for the state
+    /// machine to apply.
+    ///
+    /// Returns [`ForwardToLeader`](crate::errors::ForwardToLeader) if this node is not the leader
+    /// or a higher vote is seen, and [`QuorumNotEnough`](crate::errors::QuorumNotEnough) if a
+    /// quorum does not respond in time.
+    ///
+    /// ```ignore
+    /// let confirmed_at = my_raft.verify_quorum().await?;
+    /// assert!(confirmed_at.elapsed() < max_clock_skew);
+    /// ```
+    ///
+    /// [`RaftMetrics::current_leader`]: crate::metrics::RaftMetrics::current_leader
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn verify_quorum(&self) -> Result<InstantOf<C>, RaftError<C, LinearizableReadError<C>>> {
+        let probe_time = C::now();
+        self.app_api().get_read_linearizer(ReadPolicy::ReadIndex).await.into_raft_result()?;
+        Ok(probe_time)
+    }
+
+    /// Checks if this node is fresh enough to serve a read from its local state machine.
+    ///
+    /// Unlike [`ensure_linearizable()`](Self::ensure_linearizable), it can be called on any
+    /// node, including followers and learners, and it never contacts other nodes or waits: the
+    /// read is served only if this node meets the [`ReadOptions`], e.g., the state machine has
+    /// applied the last write the client observed, and this node heard from the leader recently.
+    /// It returns the applied log id the read observes at least.
+    ///
+    /// Otherwise it returns a [`StaleRead`] telling how stale this node is, so that a gateway
+    /// can fail over to another replica, or retry later.
+    ///
+    /// ```ignore
+    /// let opts = ReadOptions::new().with_min_applied_index(5).with_max_staleness(Duration::from_secs(1));
+    /// my_raft.local_read(opts).await?;
+    /// let val = my_raft.with_state_machine(|sm| { sm.read("foo") }).await?;
+    /// ```
+    ///
+    /// [`StaleRead`]: crate::errors::StaleRead
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn local_read(&self, options: ReadOptions) -> Result<Option<LogIdOf<C>>, RaftError<C, StaleRead<C>>> {
+        self.app_api().local_read(options).await.into_raft_result()
+    }
+
     /// Legacy method that returns log IDs directly. Use
     /// [`Raft::get_read_linearizer`] instead.
     ///
@@ -1125,6 +1690,39 @@ where C: RaftTypeConfig
         self.app_api().get_read_linearizer(read_policy).await.into_raft_result()
     }
 
+    /// Ensures reads performed on this node after this method are linearizable, without
+    /// forwarding the read to the leader.
+    ///
+    /// If this node is not the leader, it sends a [`ReadIndexRequest`] to the current leader via
+    /// [`RaftNetworkV2::read_index`] to get the `read_log_id`, which the leader confirms with the
+    /// given `read_policy`, then waits for the local state machine to apply up to it. On the
+    /// leader it is the same as [`ensure_linearizable()`](Self::ensure_linearizable).
+    ///
+    /// Returns:
+    /// - `Ok(read_log_id)` once the local state machine has applied up to `read_log_id`.
+    /// - `Err(RaftError<LinearizableReadError>)` if the leader fails to ensure its leadership.
+    ///   [`ForwardToLeader`](crate::errors::ForwardToLeader) is returned if the leader is unknown
+    ///   or can not be reached, or this node is a witness, which has no state machine, so that the
+    ///   read can be forwarded to the leader instead.
+    ///
+    /// ```ignore
+    /// my_raft.follower_read_index(ReadPolicy::ReadIndex).await?;
+    /// let val = my_raft.with_state_machine(|sm| { sm.read("foo") }).await?;
+    /// ```
+    ///
+    /// [`RaftNetworkV2::read_index`]: crate::network::RaftNetworkV2::read_index
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn follower_read_index(
+        &self,
+        read_policy: ReadPolicy,
+    ) -> Result<Option<LogIdOf<C>>, RaftError<C, LinearizableReadError<C>>> {
+        let linearizer = self.app_api().get_follower_read_linearizer(read_policy).await.into_raft_result()?;
+
+        let state = linearizer.await_ready(self).await?;
+        Ok(Some(state.read_log_id().clone()))
+    }
+
     /// Submit a mutating client request to Raft to update the state of the system (§5.1).
     ///
     /// It will be appended to the log, committed to the cluster, and then applied to the
@@ -1157,7 +1755,32 @@ where C: RaftTypeConfig
         &self,
         app_data: C::D,
     ) -> Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>> {
-        self.app_api().client_write(EntryPayload::Normal(app_data)).await.into_raft_result()
+        self.app_api().client_write(EntryPayload::Normal(app_data), None).await.into_raft_result()
+    }
+
+    /// Submit a mutating client request to Raft, tagged with a [`CorrelationId`].
+    ///
+    /// It is the same as [`Self::client_write`], except that `correlation_id` is carried along
+    /// with the request through the write pipeline: it is recorded in the tracing events emitted
+    /// when the entry is proposed, committed and completed, and is passed to the state machine via
+    /// [`ApplyResponder::correlation_id()`](crate::storage::ApplyResponder::correlation_id).
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let response = raft.client_write_with_correlation_id(request, CorrelationId(req_id)).await?;
+    /// ```
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self, app_data), fields(correlation_id = display(correlation_id)))]
+    pub async fn client_write_with_correlation_id(
+        &self,
+        app_data: C::D,
+        correlation_id: CorrelationId,
+    ) -> Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>> {
+        self.app_api()
+            .client_write(EntryPayload::Normal(app_data), Some(correlation_id))
+            .await
+            .into_raft_result()
     }
 
     /// Write a blank log entry to the Raft log.
@@ -1171,7 +1794,139 @@ where C: RaftTypeConfig
     #[since(version = "0.10.0")]
     #[tracing::instrument(level = "debug", skip(self))]
     pub async fn write_blank(&self) -> Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>> {
-        self.app_api().client_write(EntryPayload::Blank).await.into_raft_result()
+        self.app_api().client_write(EntryPayload::Blank, None).await.into_raft_result()
+    }
+
+    /// Start a consistent backup of the cluster: every node builds a snapshot at the same log id.
+    ///
+    /// The leader proposes a blank backup barrier entry. Each node, upon applying it, builds a
+    /// snapshot of its state machine at exactly the barrier log id, before applying any later
+    /// entry. The snapshots of all the nodes are therefore mutually consistent, and any of them
+    /// can restore the cluster state at the barrier.
+    ///
+    /// It returns the barrier log id once the barrier entry is applied on the leader. The snapshot
+    /// on each node is built asynchronously: the backup is complete on a node once its
+    /// [`RaftMetrics::last_backup`] is the returned log id. The snapshot can then be read with
+    /// [`get_snapshot()`](Self::get_snapshot), until a later snapshot replaces it.
+    ///
+    /// A node that receives the barrier entry in a snapshot instead of applying it, or that does
+    /// not learn of the barrier before a leader change, does not build a backup snapshot; the
+    /// backup should then be started again.
+    ///
+    /// ```ignore
+    /// let barrier = raft.backup().await?;
+    /// for node in nodes {
+    ///     node.wait(timeout).metrics(|m| m.last_backup == Some(barrier), "backup built").await?;
+    ///     let snapshot = node.get_snapshot().await?;
+    /// }
+    /// ```
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn backup(&self) -> Result<LogIdOf<C>, RaftError<C, ClientWriteError<C>>> {
+        self.app_api().backup().await.into_raft_result()
+    }
+
+    /// Write application data that is applied only by the given learners, to canary a risky
+    /// state transition.
+    ///
+    /// The entry is replicated and committed as usual, but every node not in `learners`,
+    /// including this leader, applies it as a blank entry. It returns the log id of the entry once
+    /// it is applied, as a blank, on this leader.
+    ///
+    /// The application then checks the outcome on the canary learners, e.g., with their
+    /// [`RaftMetrics::last_applied`] and their own health checks. If the transition is safe, it
+    /// proposes the same data with [`confirm_canary()`](Self::confirm_canary) to apply it on the
+    /// other nodes. Otherwise it is never applied out of the canary learners, which may then be
+    /// rebuilt, e.g., from a snapshot of a voter.
+    ///
+    /// `learners` should not contain a voter: the state of the voters has to stay the same on
+    /// all of them.
+    ///
+    /// It returns [`ClientWriteError::ApplyScopeUnsupported`] if the log entry type does not store
+    /// an [`ApplyScope`].
+    ///
+    /// ```ignore
+    /// let canary = btreeset! {4};
+    /// let log_id = raft.client_write_canary(data.clone(), canary.clone()).await?;
+    /// // Check the outcome on node 4, then apply it on the other nodes:
+    /// let resp = raft.confirm_canary(data, canary).await?;
+    /// ```
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self, app_data))]
+    pub async fn client_write_canary(
+        &self,
+        app_data: C::D,
+        learners: BTreeSet<C::NodeId>,
+    ) -> Result<LogIdOf<C>, RaftError<C, ClientWriteError<C>>> {
+        let res = self.app_api().client_write_in_scope(app_data, ApplyScope::Only(learners)).await;
+        res.map(|r| r.map(|resp| resp.log_id)).into_raft_result()
+    }
+
+    /// Apply application data that has been canaried with
+    /// [`client_write_canary()`](Self::client_write_canary) on every node but the canary
+    /// learners, which have already applied it.
+    ///
+    /// It returns the response of applying it on this leader, like
+    /// [`client_write()`](Self::client_write).
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self, app_data))]
+    pub async fn confirm_canary(
+        &self,
+        app_data: C::D,
+        learners: BTreeSet<C::NodeId>,
+    ) -> Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>> {
+        self.app_api()
+            .client_write_in_scope(app_data, ApplyScope::Except(learners))
+            .await
+            .into_raft_result()
+    }
+
+    /// Reserve `count` contiguous log indexes on the leader, to fill later with
+    /// [`client_write_reserved()`](Self::client_write_reserved).
+    ///
+    /// It lets a system that sequences requests on top of Raft assign the log index of a request
+    /// before proposing it, e.g., to batch requests outside of Openraft and tag each with its
+    /// final position in the log.
+    ///
+    /// The returned indexes follow the indexes reserved before and not yet filled, if any.
+    /// Otherwise, they follow the last log entry of the leader. Reserving does not write any
+    /// entry.
+    ///
+    /// A reservation is dropped, and the unfilled indexes are taken by other entries, when the
+    /// leader writes any entry other than a reserved one, e.g., with
+    /// [`client_write()`](Self::client_write) or a membership change, or when it loses leadership.
+    /// Filling a dropped reservation returns [`ClientWriteError::ReservedIndexMismatch`].
+    ///
+    /// ```ignore
+    /// let reserved = raft.reserve_log_indexes(3).await?;
+    /// let resp = raft.client_write_reserved(reserved.start, [a, b, c]).await?;
+    /// assert_eq!(reserved.start, resp[0].log_id.index());
+    /// ```
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn reserve_log_indexes(&self, count: u64) -> Result<Range<u64>, RaftError<C, ClientWriteError<C>>> {
+        self.app_api().reserve_log_indexes(count).await.into_raft_result()
+    }
+
+    /// Write application data to the log indexes reserved with
+    /// [`reserve_log_indexes()`](Self::reserve_log_indexes), starting at `index`.
+    ///
+    /// The entries are assigned the log indexes `index..index + n` and are all written, or all
+    /// rejected with [`ClientWriteError::ReservedIndexMismatch`] if they do not continue the
+    /// reservation: `index` has to be the first unfilled reserved index, and the entries have
+    /// to fit in the reservation. Thus reserved indexes are filled in order, and a batch can be
+    /// filled with several writes.
+    ///
+    /// It returns the response of applying each entry on this leader, in order.
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self, app_data))]
+    pub async fn client_write_reserved(
+        &self,
+        index: u64,
+        app_data: impl IntoIterator<Item = C::D>,
+    ) -> Result<Vec<ClientWriteResponse<C>>, RaftError<C, ClientWriteError<C>>> {
+        let app_data = app_data.into_iter().collect();
+        self.app_api().client_write_reserved(index, app_data).await.into_raft_result()
     }
 
     /// Submit a mutating client request to Raft to update the state machine, returns an application
@@ -1190,6 +1945,76 @@ where C: RaftTypeConfig
         self.app_api().client_write_ff(EntryPayload::Normal(app_data), responder).await
     }
 
+    /// Submit a mutating client request and return once it reaches the stage given by
+    /// [`WriteOptions::wait`].
+    ///
+    /// - [`WriteWait::Accepted`]: once the leader appends it to its log, the same as
+    ///   [`client_write_accepted()`](Self::client_write_accepted).
+    /// - [`WriteWait::Committed`]: once it is replicated to a quorum, without waiting for the state
+    ///   machine to apply it. A latency-sensitive writer that does not need the response returns
+    ///   earlier.
+    /// - [`WriteWait::Applied`]: once it is applied, the same as
+    ///   [`client_write()`](Self::client_write).
+    ///
+    /// The returned [`ClientWriteHandle`] carries the log id of the entry, and can be awaited for
+    /// the result of applying it.
+    ///
+    /// ```ignore
+    /// let opts = WriteOptions::new().with_wait(WriteWait::Committed);
+    /// let handle = raft.client_write_with_options(request, opts).await?;
+    /// println!("committed at: {}", handle.log_id());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClientWriteError::ForwardToLeader`] if this node is not the leader, or [`Fatal`]
+    /// if RaftCore is shut down.
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self, app_data))]
+    pub async fn client_write_with_options(
+        &self,
+        app_data: C::D,
+        options: WriteOptions,
+    ) -> Result<ClientWriteHandle<C>, RaftError<C, ClientWriteError<C>>> {
+        self.app_api()
+            .client_write_with_options(EntryPayload::Normal(app_data), options)
+            .await
+            .into_raft_result()
+    }
+
+    /// Submit a mutating client request and return as soon as the leader has appended it to its
+    /// log, without waiting for it to be committed and applied.
+    ///
+    /// The returned [`ClientWriteHandle`] carries the log id the leader assigned to the entry, and
+    /// can be awaited later for the result of applying it, as returned by
+    /// [`client_write()`](Self::client_write). This lets a client pipeline writes and learn their
+    /// order in the log early, while still observing the applied results.
+    ///
+    /// An accepted entry is not yet replicated: it may still be lost if the leader steps down,
+    /// in which case [`ClientWriteHandle::applied()`] returns an error.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let handle = raft.client_write_accepted(request).await?;
+    /// println!("accepted at: {}", handle.log_id());
+    ///
+    /// let resp = handle.applied().await?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClientWriteError::ForwardToLeader`] if this node is not the leader, or [`Fatal`]
+    /// if RaftCore is shut down.
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn client_write_accepted(
+        &self,
+        app_data: C::D,
+    ) -> Result<ClientWriteHandle<C>, RaftError<C, ClientWriteError<C>>> {
+        self.app_api().client_write_accepted(EntryPayload::Normal(app_data)).await.into_raft_result()
+    }
+
     /// Write multiple application data payloads in a single batch.
     ///
     /// Returns a stream that yields each result in submission order.
@@ -1220,6 +2045,30 @@ where C: RaftTypeConfig
         self.app_api().client_write_many(app_data.into_iter().map(EntryPayload::Normal)).await
     }
 
+    /// Create a write handle that assigns log indexes in the order its writes are called.
+    ///
+    /// Futures returned by [`client_write()`](Self::client_write) may reach `RaftCore` in any
+    /// order when they are polled concurrently. A write submitted with
+    /// [`OrderedWriter::client_write()`] takes its place in the order when it is called, which
+    /// suits applications that need their log to follow the submission order, e.g., a replicated
+    /// queue.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let writer = raft.ordered_writer();
+    ///
+    /// let a = writer.client_write(data_a);
+    /// let b = writer.client_write(data_b);
+    ///
+    /// // `a` is assigned a smaller log index than `b`, whichever is awaited first.
+    /// let (b, a) = futures::join!(b, a);
+    /// ```
+    #[since(version = "0.10.0")]
+    pub fn ordered_writer(&self) -> OrderedWriter<C> {
+        OrderedWriter::new(self.inner.clone())
+    }
+
     /// Submit a write request to Raft.
     ///
     /// Returns a [`WriteRequest`] builder. Fire-and-forget by default;
@@ -1255,6 +2104,8 @@ where C: RaftTypeConfig
             app_data,
             responder: None,
             expected_leader: None,
+            correlation_id: None,
+            deadline: None,
         }
     }
 
@@ -1281,6 +2132,80 @@ where C: RaftTypeConfig
         self.protocol_api().handle_transfer_leader(req).await
     }
 
+    /// Handle a ReadIndex request from a follower or learner.
+    ///
+    /// The leader ensures its leadership with the `read_policy` in the request and returns the
+    /// `read_log_id` the requesting node has to apply before serving the read. A node that is not
+    /// the leader responds with a [`ForwardToLeader`](crate::errors::ForwardToLeader) error.
+    ///
+    /// The request is sent by [`Raft::follower_read_index()`] via [`RaftNetworkV2::read_index`] and
+    /// the implementation on the remote node responds to it by calling this method.
+    ///
+    /// [`RaftNetworkV2::read_index`]: crate::network::RaftNetworkV2::read_index
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn handle_read_index(&self, req: ReadIndexRequest<C>) -> Result<ReadIndexResponse<C>, Fatal<C>> {
+        tracing::debug!("{}: req: {}", func_name!(), req);
+
+        let res = self.app_api().get_read_linearizer(req.read_policy).await?;
+        Ok(res.map(|linearizer| linearizer.read_log_id().clone()))
+    }
+
+    /// Set the node `target` fetches snapshots from, instead of from this leader.
+    ///
+    /// When `target` needs a snapshot, e.g., a new learner joins, this leader asks it via
+    /// [`RaftNetworkV2::seed_snapshot`] to fetch the snapshot from `seed`, an existing follower,
+    /// to take the bulk transfer off this leader. If seeding fails, e.g., the network does not
+    /// implement it or `seed` has no snapshot that includes the logs this leader has purged, this
+    /// leader sends its own snapshot. Pass `None` to unset the seed.
+    ///
+    /// It returns [`SeedSnapshotError::ForwardToLeader`] if this node is not the leader and
+    /// [`SeedSnapshotError::NodeNotFound`] if `target` or `seed` is not in the membership.
+    ///
+    /// [`RaftNetworkV2::seed_snapshot`]: crate::network::RaftNetworkV2::seed_snapshot
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn set_snapshot_seed(
+        &self,
+        target: C::NodeId,
+        seed: Option<C::NodeId>,
+    ) -> Result<(), RaftError<C, SeedSnapshotError<C>>> {
+        self.management_api().set_snapshot_seed(target, seed).await.into_raft_result()
+    }
+
+    /// Handle a request from the leader to fetch a snapshot from a seed node and install it.
+    ///
+    /// The snapshot is fetched with [`RaftNetworkV2::fetch_snapshot`] and installed as if it
+    /// were sent by the leader. The response has no snapshot meta if it can not be fetched, and
+    /// the leader then sends its own snapshot.
+    ///
+    /// The request is sent by the leader via [`RaftNetworkV2::seed_snapshot`] and the
+    /// implementation on the remote node responds to it by calling this method.
+    ///
+    /// [`RaftNetworkV2::seed_snapshot`]: crate::network::RaftNetworkV2::seed_snapshot
+    /// [`RaftNetworkV2::fetch_snapshot`]: crate::network::RaftNetworkV2::fetch_snapshot
+    #[since(version = "0.10.0")]
+    pub async fn handle_seed_snapshot(&self, req: SeedSnapshotRequest<C>) -> Result<SeedSnapshotResponse<C>, Fatal<C>> {
+        self.protocol_api().handle_seed_snapshot(req).await
+    }
+
+    /// Handle a request from a node that is seeding its snapshot from this node.
+    ///
+    /// It returns the current snapshot of this node, or `None` if it has none that includes
+    /// `req.min_last_log_id`, or if `req.vote` is older than the vote of this node.
+    ///
+    /// The request is sent via [`RaftNetworkV2::fetch_snapshot`] and the implementation on the
+    /// remote node responds to it by calling this method.
+    ///
+    /// [`RaftNetworkV2::fetch_snapshot`]: crate::network::RaftNetworkV2::fetch_snapshot
+    #[since(version = "0.10.0")]
+    pub async fn handle_fetch_snapshot(
+        &self,
+        req: FetchSnapshotRequest<C>,
+    ) -> Result<FetchSnapshotResponse<C>, Fatal<C>> {
+        self.protocol_api().handle_fetch_snapshot(req).await
+    }
+
     /// Return `true` if this node is already initialized and cannot be initialized again with
     /// [`Raft::initialize`]
     #[since(version = "0.10.0")]
@@ -1290,6 +2215,66 @@ where C: RaftTypeConfig
         Ok(initialized)
     }
 
+    /// Return the [`LogId`](crate::LogId) of the log entry at `index` on this node.
+    ///
+    /// It recovers the full log id, i.e., the leader id and the index, from an index an application
+    /// has stored elsewhere, e.g., in a receipt given to a client, so that it can be checked
+    /// against the log ids seen in the applied stream.
+    ///
+    /// It returns `None` if there is no such log on this node: the index is greater than the last
+    /// log index, or it is before the last purged log. The last purged log id is still returned.
+    ///
+    /// The log id at a non-committed index may change if the log is truncated by a new leader.
+    #[since(version = "0.10.0")]
+    pub async fn get_log_id_by_index(&self, index: u64) -> Result<Option<LogIdOf<C>>, Fatal<C>> {
+        let log_id = self.with_raft_state(move |st| st.get_log_id(index)).await?;
+
+        Ok(log_id)
+    }
+
+    /// Get what the last `n` log entries on this node are: their log ids, payload kinds and sizes,
+    /// without the payloads.
+    ///
+    /// It is meant for a debug or admin endpoint to show which log entries a node has, without
+    /// reading the payloads. The entries are returned in index order; fewer than `n` are returned
+    /// if there are not that many since the last purged log.
+    ///
+    /// The entries are read with [`RaftLogReader::get_log_meta()`], off the `RaftCore` task. The
+    /// size is only known if the storage implements it.
+    ///
+    /// [`RaftLogReader::get_log_meta()`]: crate::storage::RaftLogReader::get_log_meta
+    #[since(version = "0.10.0")]
+    pub async fn local_log_tail(&self, n: u64) -> Result<Vec<LogEntryMeta<C>>, RaftError<C, StorageError<C>>> {
+        let (tx, rx) = C::oneshot();
+        let cmd = ExternalCommand::GetLogTail { n, tx };
+        self.inner.call_core(RaftMsg::ExternalCommand { cmd }, rx).await.into_raft_result()
+    }
+
+    /// Verify the chain hash of the log entries in `range` on this node, and return the chain hash
+    /// of the last of them.
+    ///
+    /// The entries must have been appended with [`Config::audit_log_chain`] enabled. The range is
+    /// limited to the entries this node has; `Ok(None)` is returned if there is none. The first
+    /// entry is verified against the entry before it, unless that entry is purged, in which case it
+    /// is trusted as the anchor of the chain.
+    ///
+    /// The returned hash can be compared with the one returned by another node for the same range,
+    /// or with a hash recorded earlier, to detect a log that is modified after it is written. The
+    /// entries are read off the `RaftCore` task, with [`verify_log_chain()`].
+    ///
+    /// [`Config::audit_log_chain`]: crate::Config::audit_log_chain
+    /// [`verify_log_chain()`]: crate::entry::verify_log_chain
+    #[since(version = "0.10.0")]
+    #[cfg(feature = "audit-log-chain")]
+    pub async fn verify_log_chain(
+        &self,
+        range: Range<u64>,
+    ) -> Result<Option<ChainHash>, RaftError<C, LogChainError<C>>> {
+        let (tx, rx) = C::oneshot();
+        let cmd = ExternalCommand::VerifyLogChain { range, tx };
+        self.inner.call_core(RaftMsg::ExternalCommand { cmd }, rx).await.into_raft_result()
+    }
+
     /// Initialize a pristine Raft node with the given config.
     ///
     /// This command should be called on pristine nodes — where the log index is 0 and the node is
@@ -1343,6 +2328,35 @@ where C: RaftTypeConfig
         self.management_api().initialize(members).await.into_raft_result()
     }
 
+    /// Initialize a pristine Raft node with a membership built by the application, the same as
+    /// [`initialize()`](Self::initialize) otherwise.
+    ///
+    /// It is required to initialize a cluster whose quorum is not a plain majority of the voters,
+    /// e.g., a domain-aware membership built with
+    /// [`Membership::new_domain_aware()`](crate::Membership::new_domain_aware).
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use openraft::Membership;
+    /// use openraft::PlacedNode;
+    ///
+    /// let membership = Membership::new_domain_aware(vec![btreeset! {1,2,3}], btreemap! {
+    ///     1 => PlacedNode::new("10.0.0.1:5001", "us-east-1", "us-east-1a"),
+    ///     2 => PlacedNode::new("10.0.0.2:5001", "us-east-1", "us-east-1b"),
+    ///     3 => PlacedNode::new("10.0.0.3:5001", "us-east-1", "us-east-1c"),
+    /// })?;
+    /// raft.initialize_with_membership(membership).await?;
+    /// ```
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn initialize_with_membership(
+        &self,
+        membership: Membership<C::NodeId, C::Node>,
+    ) -> Result<(), RaftError<C, InitializeError<C>>> {
+        self.management_api().initialize_with_membership(membership).await.into_raft_result()
+    }
+
     /// Provides read-only access to [`RaftState`] through a user-provided function.
     ///
     /// The function `func` is applied to the current [`RaftState`]. The result of this function,
@@ -1421,6 +2435,51 @@ where C: RaftTypeConfig
         self.inner.rx_metrics.clone()
     }
 
+    /// Get the most recent metrics snapshots retained by this node, oldest first.
+    ///
+    /// At most [`Config::metrics_history_size`] snapshots are retained; consecutive identical
+    /// snapshots are recorded only once. It returns an empty `Vec` if the history is disabled.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// for m in raft.metrics_history() {
+    ///     println!("term: {}, state: {:?}, leader: {:?}", m.current_term, m.state, m.current_leader);
+    /// }
+    /// ```
+    #[since(version = "0.10.0")]
+    pub fn metrics_history(&self) -> Vec<RaftMetrics<C>> {
+        self.inner.metrics_history.snapshot()
+    }
+
+    /// Get the response of the applied log entry at `log_id`, if it is still retained.
+    ///
+    /// A client that lost the response to a write, e.g., to a network error, can retrieve it
+    /// instead of proposing the write again. At most [`Config::applied_result_cache_size`]
+    /// responses are retained, and only those the state machine sends with
+    /// [`ApplyResponder::send_and_cache()`](crate::storage::ApplyResponder::send_and_cache).
+    ///
+    /// A response is retained only on the node that proposed the entry: after a leader change,
+    /// the new leader does not have it, and `None` is returned. `None` therefore does not mean
+    /// the entry is not applied.
+    #[since(version = "0.10.0")]
+    pub fn applied_result(&self, log_id: &LogIdOf<C>) -> Option<C::R>
+    where C::R: Clone {
+        self.inner.applied_result_cache.get(log_id)
+    }
+
+    /// Get the log id and the response of the applied write that carried `correlation_id`, if it
+    /// is still retained.
+    ///
+    /// It is the same as [`applied_result()`](Self::applied_result), for a client that knows only
+    /// the [`CorrelationId`] it wrote with, e.g., by
+    /// [`client_write_with_correlation_id()`](Self::client_write_with_correlation_id).
+    #[since(version = "0.10.0")]
+    pub fn applied_result_by_correlation_id(&self, correlation_id: CorrelationId) -> Option<(LogIdOf<C>, C::R)>
+    where C::R: Clone {
+        self.inner.applied_result_cache.get_by_correlation_id(correlation_id)
+    }
+
     /// Get a handle to the data metrics channel.
     pub fn data_metrics(&self) -> WatchReceiverOf<C, RaftDataMetrics<C>> {
         self.inner.rx_data_metrics.clone()
@@ -1454,6 +2513,111 @@ where C: RaftTypeConfig
         self.inner.progress_watcher.log_progress()
     }
 
+    /// Subscribe to the log of this node from `from_index`, to tail entries as they become durable
+    /// in the local log store, without polling it.
+    ///
+    /// Entries are not purged by the snapshot policy until the subscription consumes them, see
+    /// [`LogSubscription`]. If the entries before `from_index` are already purged, the
+    /// subscription starts at the first entry that is not.
+    ///
+    /// ```ignore
+    /// let mut sub = raft.subscribe_log(0).await?;
+    /// loop {
+    ///     let last = sub.wait_durable().await?;
+    ///     let entries = log_reader.try_get_log_entries(sub.next_index()..=last.index()).await?;
+    ///     export(entries).await;
+    ///     sub.advance(last.index()).await?;
+    /// }
+    /// ```
+    #[since(version = "0.10.0")]
+    pub async fn subscribe_log(&self, from_index: u64) -> Result<LogSubscription<C>, Fatal<C>> {
+        let (tx, rx) = C::oneshot();
+        let cmd = ExternalCommand::SubscribeLog { from_index, tx };
+        let (hold_id, next_index) = self.inner.call_core(RaftMsg::ExternalCommand { cmd }, rx).await?;
+
+        Ok(LogSubscription::new(
+            hold_id,
+            next_index,
+            self.inner.clone(),
+            self.inner.log_holds.clone(),
+        ))
+    }
+
+    /// Subscribe to the events of this node, such as becoming the leader or committing a
+    /// membership config, see [`RaftEvent`].
+    ///
+    /// The returned stream yields every event emitted after subscribing, in order, and ends when
+    /// the node shuts down. Any number of subscribers can be active: each reads at its own pace.
+    ///
+    /// Only the most recent events are kept for subscribers: a subscriber that falls too far
+    /// behind receives an [`EventsLagged`] error telling how many events it missed, then continues
+    /// with the oldest event kept.
+    ///
+    /// ```ignore
+    /// let mut events = raft.subscribe_events();
+    /// while let Some(ev) = events.next().await {
+    ///     match ev {
+    ///         Ok(RaftEvent::LeaderElected { vote }) => println!("became leader: {}", vote),
+    ///         Ok(ev) => println!("event: {}", ev),
+    ///         Err(lagged) => println!("missed {} events", lagged.missed),
+    ///     }
+    /// }
+    /// ```
+    #[since(version = "0.10.0")]
+    pub fn subscribe_events(&self) -> BoxStream<'static, Result<RaftEvent<C>, EventsLagged>> {
+        let rx = self.inner.rx_events.clone();
+        let next_seq = rx.borrow_watched().next_seq();
+
+        let stream = futures_util::stream::unfold(
+            (rx, next_seq, VecDeque::new()),
+            |(mut rx, mut next_seq, mut pending)| async move {
+                loop {
+                    if let Some(event) = pending.pop_front() {
+                        return Some((Ok(event), (rx, next_seq, pending)));
+                    }
+
+                    let read = rx.borrow_watched().read_from(next_seq);
+                    match read {
+                        Ok(events) => {
+                            next_seq += events.len() as u64;
+                            pending.extend(events);
+                        }
+                        Err((lagged, first_seq)) => {
+                            next_seq = first_seq;
+                            return Some((Err(lagged), (rx, next_seq, pending)));
+                        }
+                    }
+
+                    if pending.is_empty() && rx.changed().await.is_err() {
+                        return None;
+                    }
+                }
+            },
+        );
+
+        Box::pin(stream)
+    }
+
+    /// List the responses held until an IO condition is satisfied, to diagnose stuck clients.
+    ///
+    /// A client write, for example, is not responded until its log entry is applied: such a
+    /// response is held by `RaftCore` and listed here along with what it waits for and how long
+    /// it has waited. Responses sent right away are not listed.
+    ///
+    /// ```ignore
+    /// for p in raft.pending_responds().await? {
+    ///     if p.age > Duration::from_secs(10) {
+    ///         tracing::warn!("response stuck: {}", p);
+    ///     }
+    /// }
+    /// ```
+    #[since(version = "0.10.0")]
+    pub async fn pending_responds(&self) -> Result<Vec<PendingRespondInfo<C>>, Fatal<C>> {
+        let (tx, rx) = C::oneshot();
+        let cmd = ExternalCommand::GetPendingResponds { tx };
+        self.inner.call_core(RaftMsg::ExternalCommand { cmd }, rx).await
+    }
+
     /// Get a handle to watch vote I/O flush progress.
     ///
     /// Tracks when votes (leadership changes) are durably written to storage.
@@ -1798,6 +2962,9 @@ where C: RaftTypeConfig
     /// leader, lost quorum, or this node is isolated), the method returns [`WaitError::Timeout`].
     /// If `timeout` is `None` it waits forever (see [`wait`](Self::wait)).
     ///
+    /// A [`NodeRole::Witness`](crate::NodeRole::Witness) applies nothing: on a witness it returns
+    /// once the cluster commit is re-established.
+    ///
     /// # Why this works
     ///
     /// Perceiving a non-null `cluster_committed` means the current leader has re-established the
@@ -1830,6 +2997,10 @@ where C: RaftTypeConfig
             )
             .await?;
 
+        if metrics.membership_config.membership().is_witness(&self.inner.id) {
+            return Ok(metrics);
+        }
+
         let target = metrics.cluster_committed.as_ref().map(|x| x.index());
 
         // Phase 2: wait until the state machine has applied up to that cluster commit, within the
@@ -1844,13 +3015,21 @@ where C: RaftTypeConfig
     ///
     /// It sends a shutdown signal and waits until `RaftCore` returns.
     ///
+    /// Returns the [`ShutdownReport`] of the final state, which is also logged when `RaftCore`
+    /// quits. If `RaftCore` has already quit on a fatal error, the report of that quit is
+    /// returned. It is `None` only if `RaftCore` panicked.
+    ///
+    /// A leader stopped this way leaves the cluster without a leader until an election timeout;
+    /// use [`shutdown_graceful()`](Self::shutdown_graceful) to hand leadership off first.
+    ///
     /// # Examples
     ///
     /// ```ignore
     /// // Gracefully shutdown the Raft node
-    /// raft.shutdown().await?;
+    /// let report = raft.shutdown().await?;
     /// ```
-    pub async fn shutdown(&self) -> Result<(), JoinErrorOf<C>> {
+    #[since(version = "0.10.0", change = "return ShutdownReport")]
+    pub async fn shutdown(&self) -> Result<Option<ShutdownReport<C>>, JoinErrorOf<C>> {
         if let Some(tx) = self.inner.tx_shutdown.lock().unwrap().take() {
             // A failure to send means the RaftCore is already shutdown. Continue to check the task
             // return value.
@@ -1864,7 +3043,61 @@ where C: RaftTypeConfig
 
         // TODO(xp): API change: replace `JoinError` with `Fatal`,
         //           to let the caller know the return value of RaftCore task.
-        Ok(())
+        Ok(self.inner.shutdown_report.lock().unwrap().clone())
+    }
+
+    /// Hand leadership off to another voter, then shutdown this Raft node.
+    ///
+    /// If this node is the leader, leadership is transferred to the voter that has replicated
+    /// the most logs, and this waits up to `timeout` for another node to become the leader, so
+    /// that the cluster does not have to wait for an election timeout after this node is gone.
+    /// This node is then shut down as by [`shutdown()`](Self::shutdown), whether or not the
+    /// handoff succeeded, e.g., because no voter is reachable.
+    ///
+    /// On a node that is not the leader, it is the same as [`shutdown()`](Self::shutdown).
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// // Stop a node during a rolling deploy, without leaving the cluster leaderless
+    /// let report = raft.shutdown_graceful(Duration::from_secs(5)).await?;
+    /// ```
+    #[since(version = "0.10.0")]
+    pub async fn shutdown_graceful(&self, timeout: Duration) -> Result<Option<ShutdownReport<C>>, JoinErrorOf<C>> {
+        self.hand_off_leadership(timeout).await;
+        self.shutdown().await
+    }
+
+    /// Transfer leadership to the most caught up voter, and wait up to `timeout` for another node
+    /// to become the leader.
+    async fn hand_off_leadership(&self, timeout: Duration) {
+        let metrics = self.metrics().borrow_watched().clone();
+
+        let Some(to) = leader_handoff::handoff_target(&metrics) else {
+            tracing::info!("shutdown_graceful: no leadership to hand off");
+            return;
+        };
+
+        tracing::info!("shutdown_graceful: transfer leadership to {}", to);
+
+        if let Err(e) = self.trigger().transfer_leader(to.clone()).await {
+            tracing::warn!("shutdown_graceful: failed to transfer leadership to {}: {}", to, e);
+            return;
+        }
+
+        let id = self.inner.id.clone();
+        let res = self
+            .wait(Some(timeout))
+            .metrics(
+                |m| m.current_leader.as_ref().is_some_and(|leader| leader != &id),
+                "shutdown_graceful: another node becomes leader",
+            )
+            .await;
+
+        match res {
+            Ok(m) => tracing::info!("shutdown_graceful: {} is the new leader", m.current_leader.display()),
+            Err(e) => tracing::warn!("shutdown_graceful: leadership not handed off: {}", e),
+        }
     }
 
     /// Provides mutable access to [`RaftStateMachine`] through a user-provided function.
@@ -1925,6 +3158,10 @@ where C: RaftTypeConfig
     /// The request functor will be called with a mutable reference to the state machine.
     /// The functor returns a [`Future`] because state machine methods are `async`.
     ///
+    /// A [`NodeRole::LogOnly`](crate::NodeRole::LogOnly) node does not run its state machine
+    /// worker: the request is executed once the worker is started, e.g., after the node is
+    /// re-added as a voter.
+    ///
     /// Returns a `Fatal` error if:
     /// - Raft core task is stopped normally.
     /// - Raft core task is panicked due to programming error.
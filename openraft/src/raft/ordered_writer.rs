@@ -0,0 +1,172 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use openraft_macros::since;
+
+use crate::RaftTypeConfig;
+use crate::async_runtime::Mutex as AsyncMutex;
+use crate::batch::Batch;
+use crate::entry::EntryPayload;
+use crate::errors::ClientWriteError;
+use crate::errors::Fatal;
+use crate::errors::RaftError;
+use crate::errors::into_raft_result::IntoRaftResult;
+use crate::impls::ProgressResponder;
+use crate::raft::AppApi;
+use crate::raft::ClientWriteResponse;
+use crate::raft::ClientWriteResult;
+use crate::raft::raft_inner::RaftInner;
+use crate::raft::responder::core_responder::CoreResponder;
+use crate::type_config::TypeConfigExt;
+use crate::type_config::alias::EntryPayloadOf;
+use crate::type_config::alias::MutexOf;
+
+/// A write handle that assigns log indexes in the order [`client_write()`](Self::client_write)
+/// is called, returned by [`Raft::ordered_writer()`](crate::Raft::ordered_writer).
+///
+/// Writes submitted concurrently with [`Raft::client_write()`](crate::Raft::client_write) reach
+/// `RaftCore` in the order their futures happen to be polled, which depends on the scheduler. An
+/// `OrderedWriter` instead queues a write when `client_write()` is called, before the returned
+/// future is polled, and forwards the queue to `RaftCore` in order. Thus if `client_write(a)` is
+/// called before `client_write(b)` on the same writer, `a` is assigned a smaller log index than
+/// `b`, if both are accepted by the same leader.
+///
+/// Clones of an `OrderedWriter` share the same queue. Writes through different writers, or
+/// through [`Raft`](crate::Raft) directly, are not ordered with respect to each other.
+///
+/// A write is sent when its future, or the future of a later write, is polled. If a future is
+/// dropped before its write is sent, the write is removed from the queue and never proposed.
+#[since(version = "0.10.0")]
+pub struct OrderedWriter<C>
+where C: RaftTypeConfig
+{
+    inner: Arc<RaftInner<C>>,
+
+    /// Writes queued in call order but not yet sent to `RaftCore`.
+    queue: Arc<Mutex<WriteQueue<C>>>,
+
+    /// Held while forwarding the queue, so that batches are sent in order.
+    forwarding: Arc<MutexOf<C, ()>>,
+}
+
+impl<C> Clone for OrderedWriter<C>
+where C: RaftTypeConfig
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            queue: self.queue.clone(),
+            forwarding: self.forwarding.clone(),
+        }
+    }
+}
+
+impl<C> fmt::Debug for OrderedWriter<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let queued = self.queue.lock().unwrap().writes.len();
+        f.debug_struct("OrderedWriter").field("queued", &queued).finish()
+    }
+}
+
+impl<C> OrderedWriter<C>
+where C: RaftTypeConfig
+{
+    pub(in crate::raft) fn new(inner: Arc<RaftInner<C>>) -> Self {
+        Self {
+            inner,
+            queue: Arc::new(Mutex::new(WriteQueue {
+                next_id: 0,
+                writes: VecDeque::new(),
+            })),
+            forwarding: Arc::new(C::mutex(())),
+        }
+    }
+
+    /// Queue `app_data` to write, and return a future that resolves once it is applied.
+    ///
+    /// The write takes its place in the order when this method is called, not when the returned
+    /// future is polled. Otherwise it behaves the same as
+    /// [`Raft::client_write()`](crate::Raft::client_write).
+    ///
+    /// If the returned future is dropped before the write is sent to `RaftCore`, the write is
+    /// removed from the queue and never proposed.
+    #[since(version = "0.10.0")]
+    pub fn client_write(
+        &self,
+        app_data: C::D,
+    ) -> impl Future<Output = Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>>> + 'static {
+        let (responder, complete_rx) = ProgressResponder::complete_only();
+        let responder = CoreResponder::progress(responder);
+
+        let id = {
+            let mut queue = self.queue.lock().unwrap();
+            let id = queue.next_id;
+            queue.next_id += 1;
+            queue.writes.push_back((id, EntryPayload::Normal(app_data), responder));
+            id
+        };
+
+        let dequeue = Dequeue {
+            queue: self.queue.clone(),
+            id,
+        };
+
+        let this = self.clone();
+        async move {
+            let _dequeue = dequeue;
+
+            this.forward().await.map_err(RaftError::Fatal)?;
+
+            let res: Result<ClientWriteResult<C>, Fatal<C>> = this.inner.recv_msg(complete_rx).await;
+            res.into_raft_result()
+        }
+    }
+
+    /// Send all queued writes to `RaftCore`, in one message.
+    ///
+    /// Does nothing if another call already sent them.
+    async fn forward(&self) -> Result<(), Fatal<C>> {
+        let _guard = self.forwarding.lock().await;
+
+        let queued = std::mem::take(&mut self.queue.lock().unwrap().writes);
+        if queued.is_empty() {
+            return Ok(());
+        }
+
+        let (payloads, responders): (Vec<_>, Vec<_>) = queued.into_iter().map(|(_id, p, r)| (p, Some(r))).unzip();
+
+        AppApi::new(&self.inner).do_client_write_ff(Batch::of(payloads), Batch::of(responders)).await
+    }
+}
+
+/// Writes queued by an [`OrderedWriter`], each with an id increasing in call order.
+struct WriteQueue<C>
+where C: RaftTypeConfig
+{
+    next_id: u64,
+    writes: VecDeque<(u64, EntryPayloadOf<C>, CoreResponder<C>)>,
+}
+
+/// Removes a queued write when its future is dropped before the write is sent to `RaftCore`.
+struct Dequeue<C>
+where C: RaftTypeConfig
+{
+    queue: Arc<Mutex<WriteQueue<C>>>,
+    id: u64,
+}
+
+impl<C> Drop for Dequeue<C>
+where C: RaftTypeConfig
+{
+    fn drop(&mut self) {
+        let mut queue = self.queue.lock().unwrap();
+        if let Ok(i) = queue.writes.binary_search_by_key(&self.id, |(id, _, _)| *id) {
+            queue.writes.remove(i);
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use std::fmt;
+use std::time::Duration;
+
+use display_more::DisplayOptionExt;
+use openraft_macros::since;
+
+use crate::RaftTypeConfig;
+use crate::type_config::alias::LogIdOf;
+
+/// A response held by `RaftCore` until an IO condition is satisfied, returned by
+/// [`Raft::pending_responds()`](crate::Raft::pending_responds).
+///
+/// A response that stays here for long tells what a stuck client is waiting on, e.g., a log
+/// flush the storage never completes.
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingRespondInfo<C>
+where C: RaftTypeConfig
+{
+    /// The IO event the response waits for.
+    ///
+    /// It is one of `"log_io"`, `"log_flush"`, `"apply"` and `"snapshot"`: a log IO, a log
+    /// entry being flushed, a log entry being applied, and a snapshot being built.
+    pub waiting_for: &'static str,
+
+    /// The log id the IO event must reach.
+    pub log_id: Option<LogIdOf<C>>,
+
+    /// How long the response has been waiting.
+    pub age: Duration,
+}
+
+impl<C> fmt::Display for PendingRespondInfo<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}({}), age: {:?}",
+            self.waiting_for,
+            self.log_id.display(),
+            self.age
+        )
+    }
+}
@@ -0,0 +1,87 @@
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use openraft_macros::since;
+
+use crate::RaftTypeConfig;
+use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::SnapshotMetaOf;
+use crate::type_config::alias::StoredMembershipOf;
+use crate::type_config::alias::VoteOf;
+
+/// A change of the state of a Raft node, delivered by
+/// [`Raft::subscribe_events()`](crate::Raft::subscribe_events).
+///
+/// Events are emitted in the order they happen on this node. Unlike polling
+/// [`RaftMetrics`](crate::RaftMetrics), which only shows the latest state, no event is
+/// coalesced: for example, a node that becomes leader and steps down in a short time emits both
+/// events.
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RaftEvent<C>
+where C: RaftTypeConfig
+{
+    /// This node becomes the leader with `vote`.
+    LeaderElected { vote: VoteOf<C> },
+
+    /// This node is no longer the leader, and its vote is now `vote`.
+    SteppedDown { vote: VoteOf<C> },
+
+    /// A membership config is committed, by replication or by installing a snapshot.
+    MembershipCommitted { membership: Arc<StoredMembershipOf<C>> },
+
+    /// This node builds a snapshot of its state machine.
+    SnapshotBuilt { meta: SnapshotMetaOf<C> },
+
+    /// The logs up to `upto`, inclusive, are purged from the local log store.
+    LogPurged { upto: LogIdOf<C> },
+
+    /// The state machine starts warming up after installing the snapshot `meta`, and this node
+    /// should not serve reads until [`WarmUpFinished`](Self::WarmUpFinished).
+    ///
+    /// See [`Config::warm_up_after_install`](crate::Config::warm_up_after_install).
+    WarmUpStarted { meta: SnapshotMetaOf<C> },
+
+    /// The state machine finishes warming up after installing the snapshot `meta`.
+    WarmUpFinished { meta: SnapshotMetaOf<C> },
+
+    /// This leader proposes to evict `node_id`, which has not acknowledged it for
+    /// `unreachable_for`.
+    ///
+    /// If `demoted` is `true`, the voter is demoted to a learner instead of being removed.
+    ///
+    /// See [`Config::evict_unreachable_after`](crate::Config::evict_unreachable_after).
+    NodeEvicted {
+        node_id: C::NodeId,
+        unreachable_for: Duration,
+        demoted: bool,
+    },
+}
+
+impl<C> fmt::Display for RaftEvent<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RaftEvent::LeaderElected { vote } => write!(f, "LeaderElected: vote: {}", vote),
+            RaftEvent::SteppedDown { vote } => write!(f, "SteppedDown: vote: {}", vote),
+            RaftEvent::MembershipCommitted { membership } => {
+                write!(f, "MembershipCommitted: {}", membership)
+            }
+            RaftEvent::SnapshotBuilt { meta } => write!(f, "SnapshotBuilt: {}", meta),
+            RaftEvent::LogPurged { upto } => write!(f, "LogPurged: upto: {}", upto),
+            RaftEvent::WarmUpStarted { meta } => write!(f, "WarmUpStarted: {}", meta),
+            RaftEvent::WarmUpFinished { meta } => write!(f, "WarmUpFinished: {}", meta),
+            RaftEvent::NodeEvicted {
+                node_id,
+                unreachable_for,
+                demoted,
+            } => write!(
+                f,
+                "NodeEvicted: node: {}, unreachable_for: {:?}, demoted: {}",
+                node_id, unreachable_for, demoted
+            ),
+        }
+    }
+}
@@ -16,14 +16,22 @@ use crate::async_runtime::watch::WatchReceiver;
 use crate::async_runtime::watch::WatchSender;
 use crate::config::RuntimeConfig;
 use crate::core::TickHandle;
+use crate::core::event_log::EventLog;
 use crate::core::io_flush_tracking::IoProgressWatcher;
+use crate::core::log_holds::LogHolds;
 use crate::core::raft_msg::RaftMsg;
 use crate::core::raft_msg::external_command::ExternalCommand;
+use crate::core::replication_throughput::ReplicationThroughput;
+use crate::core::snapshot_history::SnapshotHistory;
+use crate::core::snapshot_meta_cache::SnapshotMetaCache;
 use crate::errors::Fatal;
+use crate::metrics::MetricsHistory;
 use crate::metrics::RaftDataMetrics;
 use crate::metrics::RaftServerMetrics;
 use crate::metrics::Wait;
+use crate::raft::ShutdownReport;
 use crate::raft::core_state::CoreState;
+use crate::storage::v2::applied_result_cache::AppliedResultCache;
 use crate::type_config::AsyncRuntime;
 use crate::type_config::TypeConfigExt;
 use crate::type_config::alias::AsyncRuntimeOf;
@@ -45,11 +53,36 @@ where C: RaftTypeConfig
     pub(in crate::raft) rx_metrics: WatchReceiverOf<C, RaftMetrics<C>>,
     pub(in crate::raft) rx_data_metrics: WatchReceiverOf<C, RaftDataMetrics<C>>,
     pub(in crate::raft) rx_server_metrics: WatchReceiverOf<C, RaftServerMetrics<C>>,
+
+    /// The most recent events emitted by `RaftCore`.
+    pub(in crate::raft) rx_events: WatchReceiverOf<C, EventLog<C>>,
+
     pub(in crate::raft) progress_watcher: IoProgressWatcher<C>,
 
     pub(in crate::raft) tx_shutdown: Mutex<Option<OneshotSenderOf<C, ()>>>,
     pub(in crate::raft) core_state: Mutex<CoreState<C>>,
 
+    /// The most recent metrics snapshots recorded by `RaftCore`.
+    pub(in crate::raft) metrics_history: MetricsHistory<C>,
+
+    /// The responses of the most recently applied entries, recorded by the state machine worker.
+    pub(in crate::raft) applied_result_cache: AppliedResultCache<C>,
+
+    /// The log indexes still needed by log subscriptions, shared with `RaftCore`.
+    pub(in crate::raft) log_holds: LogHolds,
+
+    /// The meta of the current snapshot, replaced by `RaftCore`.
+    pub(in crate::raft) snapshot_meta_cache: SnapshotMetaCache<C>,
+
+    /// The meta of the most recent snapshots, recorded by `RaftCore`.
+    pub(in crate::raft) snapshot_history: SnapshotHistory<C>,
+
+    /// The replication throughput, recorded by the replication tasks.
+    pub(in crate::raft) replication_throughput: ReplicationThroughput<C>,
+
+    /// The report of the final state, filled by `RaftCore` when it quits.
+    pub(in crate::raft) shutdown_report: Arc<Mutex<Option<ShutdownReport<C>>>>,
+
     /// Type-map for storing user-defined extension data.
     ///
     /// External crates can access this via [`Raft::extensions()`](`crate::Raft::extensions`).
@@ -96,7 +129,8 @@ where C: RaftTypeConfig
         })
     }
 
-    /// Receive a message from RaftCore, return an error if RaftCore has stopped.
+    /// Receive a message from RaftCore, return an error if RaftCore has stopped or dropped the
+    /// request.
     pub(crate) async fn recv_msg<T, E>(&self, rx: impl Future<Output = Result<T, E>>) -> Result<T, Fatal<C>>
     where
         T: OptionalSend,
@@ -108,7 +142,7 @@ where C: RaftTypeConfig
         match recv_res {
             Ok(x) => Ok(x),
             Err(_) => {
-                let fatal = self.get_core_stop_error().await;
+                let fatal = self.get_dropped_request_error().await;
                 tracing::error!("{}: error: {}", func_name!(), fatal);
                 Err(fatal)
             }
@@ -134,6 +168,18 @@ where C: RaftTypeConfig
         state.is_running()
     }
 
+    /// Returns the error for a request whose responder is dropped by `RaftCore`.
+    ///
+    /// A `RaftCore` running degraded drops a request it can not answer without the failed
+    /// storage, and it is answered with the storage error at once. Otherwise, `RaftCore` has
+    /// quit, and it waits for the error it quit with.
+    pub(crate) async fn get_dropped_request_error(&self) -> Fatal<C> {
+        if let Some(error) = self.rx_metrics.borrow_watched().storage_error.clone() {
+            return Fatal::StorageError(error);
+        }
+        self.get_core_stop_error().await
+    }
+
     /// Get the error that caused RaftCore to stop.
     pub(crate) async fn get_core_stop_error(&self) -> Fatal<C> {
         // Wait for the core task to finish.
@@ -0,0 +1,42 @@
+use std::time::Duration;
+
+use openraft_macros::since;
+
+/// Requirements a local read has to meet, for [`Raft::local_read()`](crate::Raft::local_read).
+///
+/// A local read is served from the state machine of this node without contacting the leader, so
+/// it may lag behind the cluster. These options bound how far behind it may be; a requirement
+/// that is not set is not checked.
+#[since(version = "0.10.0")]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ReadOptions {
+    /// The state machine has to have applied at least up to this log index, e.g., the index of
+    /// the last write the client has observed.
+    pub min_applied_index: Option<u64>,
+
+    /// This node has to have heard from the leader within this duration.
+    ///
+    /// On the leader, it is the time since a quorum last acknowledged it; on a follower or
+    /// learner, it is the time since the last message from the leader.
+    pub max_staleness: Option<Duration>,
+}
+
+impl ReadOptions {
+    /// Create options that do not require anything.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Require the state machine to have applied at least up to `index`.
+    pub fn with_min_applied_index(mut self, index: u64) -> Self {
+        self.min_applied_index = Some(index);
+        self
+    }
+
+    /// Require this node to have heard from the leader within `staleness`.
+    pub fn with_max_staleness(mut self, staleness: Duration) -> Self {
+        self.max_staleness = Some(staleness);
+        self
+    }
+}
@@ -0,0 +1,33 @@
+use openraft_macros::since;
+
+use crate::RaftTypeConfig;
+use crate::type_config::alias::LogIdOf;
+
+/// The stages [`Raft::replace_node_with_progress()`](crate::Raft::replace_node_with_progress)
+/// goes through, reported to the caller as they are reached.
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(derive_more::Display)]
+pub enum ReplaceNodeProgress<C>
+where C: RaftTypeConfig
+{
+    /// The new node is added as a learner by the membership log at `log_id`.
+    #[display("LearnerAdded({})", log_id)]
+    LearnerAdded { log_id: LogIdOf<C> },
+
+    /// The new node has caught up with the leader and is about to be swapped in.
+    #[display("CaughtUp")]
+    CaughtUp,
+
+    /// The new node replaced the old one, by the uniform membership log at `log_id`.
+    #[display("Replaced({})", log_id)]
+    Replaced { log_id: LogIdOf<C> },
+
+    /// The swap failed, and the new node is being removed from the cluster.
+    #[display("RollingBack")]
+    RollingBack,
+
+    /// The rollback is done: the membership is the same as before the new node was added.
+    #[display("RolledBack")]
+    RolledBack,
+}
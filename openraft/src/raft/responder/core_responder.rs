@@ -1,35 +1,109 @@
 use crate::RaftTypeConfig;
 use crate::impls::ProgressResponder;
 use crate::raft::ClientWriteResult;
+use crate::raft::CorrelationId;
 use crate::raft::responder::Responder;
+use crate::type_config::alias::InstantOf;
 use crate::type_config::alias::LogIdOf;
 use crate::type_config::alias::WriteResponderOf;
 
 /// The responder used in RaftCore.
 ///
 /// RaftCore use this responder to send response to the caller.
-/// It is either a progress responder or a user-defined responder.
-pub(crate) enum CoreResponder<C>
+/// It wraps either a progress responder or a user-defined responder, along with the optional
+/// [`CorrelationId`] and deadline of the write request.
+pub(crate) struct CoreResponder<C>
+where C: RaftTypeConfig
+{
+    kind: CoreResponderKind<C>,
+    correlation_id: Option<CorrelationId>,
+
+    /// The deadline of the write, stored in its log entry.
+    deadline: Option<InstantOf<C>>,
+}
+
+pub(crate) enum CoreResponderKind<C>
 where C: RaftTypeConfig
 {
     Progress(ProgressResponder<C, ClientWriteResult<C>>),
     UserDefined(WriteResponderOf<C>),
 }
 
+impl<C> CoreResponder<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn progress(responder: ProgressResponder<C, ClientWriteResult<C>>) -> Self {
+        Self {
+            kind: CoreResponderKind::Progress(responder),
+            correlation_id: None,
+            deadline: None,
+        }
+    }
+
+    pub(crate) fn user_defined(responder: WriteResponderOf<C>) -> Self {
+        Self {
+            kind: CoreResponderKind::UserDefined(responder),
+            correlation_id: None,
+            deadline: None,
+        }
+    }
+
+    pub(crate) fn with_correlation_id(mut self, correlation_id: Option<CorrelationId>) -> Self {
+        self.correlation_id = correlation_id;
+        self
+    }
+
+    pub(crate) fn correlation_id(&self) -> Option<CorrelationId> {
+        self.correlation_id
+    }
+
+    pub(crate) fn with_deadline(mut self, deadline: Option<InstantOf<C>>) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    pub(crate) fn deadline(&self) -> Option<InstantOf<C>> {
+        self.deadline
+    }
+}
+
 impl<C> Responder<C, ClientWriteResult<C>> for CoreResponder<C>
 where C: RaftTypeConfig
 {
+    fn on_accept(&mut self, log_id: LogIdOf<C>) {
+        match &mut self.kind {
+            CoreResponderKind::Progress(responder) => responder.on_accept(log_id),
+            CoreResponderKind::UserDefined(responder) => responder.on_accept(log_id),
+        }
+    }
+
     fn on_commit(&mut self, log_id: LogIdOf<C>) {
-        match self {
-            Self::Progress(responder) => responder.on_commit(log_id),
-            Self::UserDefined(responder) => responder.on_commit(log_id),
+        if let Some(correlation_id) = self.correlation_id {
+            tracing::debug!(
+                correlation_id = display(correlation_id),
+                log_id = display(&log_id),
+                "write committed"
+            );
+        }
+
+        match &mut self.kind {
+            CoreResponderKind::Progress(responder) => responder.on_commit(log_id),
+            CoreResponderKind::UserDefined(responder) => responder.on_commit(log_id),
         }
     }
 
     fn on_complete(self, res: ClientWriteResult<C>) {
-        match self {
-            Self::Progress(responder) => responder.on_complete(res),
-            Self::UserDefined(responder) => responder.on_complete(res),
+        if let Some(correlation_id) = self.correlation_id {
+            tracing::debug!(
+                correlation_id = display(correlation_id),
+                ok = res.is_ok(),
+                "write completed"
+            );
+        }
+
+        match self.kind {
+            CoreResponderKind::Progress(responder) => responder.on_complete(res),
+            CoreResponderKind::UserDefined(responder) => responder.on_complete(res),
         }
     }
 }
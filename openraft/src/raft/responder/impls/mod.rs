@@ -1,5 +1,8 @@
 mod oneshot_responder;
 mod progress_responder;
+mod streaming_responder;
 
 pub use oneshot_responder::OneshotResponder;
 pub use progress_responder::ProgressResponder;
+pub use streaming_responder::StreamingResponder;
+pub use streaming_responder::WriteEvent;
@@ -11,7 +11,8 @@ use crate::type_config::alias::OneshotSenderOf;
 
 /// A [`Responder`] implementation that sends notifications via oneshot channels.
 ///
-/// This responder can provide both commit and completion notifications:
+/// This responder can provide accept, commit and completion notifications:
+/// - **Accept channel**: Notifies when the leader appends the request as a log entry
 /// - **Commit channel**: Notifies when the log entry is committed (replicated to a quorum)
 /// - **Complete channel**: Sends the final result when the request completes
 ///
@@ -21,6 +22,9 @@ use crate::type_config::alias::OneshotSenderOf;
 ///
 /// Use [`ProgressResponder::complete_only()`] when the caller only needs the final result.
 ///
+/// Use [`ProgressResponder::accept_and_complete()`] when the caller returns as soon as the leader
+/// assigns a log id, and awaits the final result later.
+///
 /// # Example
 ///
 /// ```ignore
@@ -42,6 +46,7 @@ where
     C: RaftTypeConfig,
     T: OptionalSend,
 {
+    accept_tx: Option<OneshotSenderOf<C, LogIdOf<C>>>,
     commit_tx: Option<OneshotSenderOf<C, LogIdOf<C>>>,
     complete_tx: OneshotSenderOf<C, T>,
 }
@@ -67,6 +72,7 @@ where
         let (complete_tx, complete_rx) = C::oneshot();
 
         let responder = Self {
+            accept_tx: None,
             commit_tx: Some(commit_tx),
             complete_tx,
         };
@@ -83,12 +89,33 @@ where
         let (complete_tx, complete_rx) = C::oneshot();
 
         let responder = Self {
+            accept_tx: None,
             commit_tx: None,
             complete_tx,
         };
 
         (responder, complete_rx)
     }
+
+    /// Create a new responder with an accept receiver and a complete receiver.
+    ///
+    /// The accept receiver receives the log id assigned by the leader as soon as the request is
+    /// appended to its log. If the request is rejected before that, e.g., with a
+    /// `ForwardToLeader` error, the accept sender is dropped and the error is sent to the complete
+    /// receiver. Commit notifications are ignored.
+    #[since(version = "0.10.0")]
+    pub fn accept_and_complete() -> (Self, OneshotReceiverOf<C, LogIdOf<C>>, OneshotReceiverOf<C, T>) {
+        let (accept_tx, accept_rx) = C::oneshot();
+        let (complete_tx, complete_rx) = C::oneshot();
+
+        let responder = Self {
+            accept_tx: Some(accept_tx),
+            commit_tx: None,
+            complete_tx,
+        };
+
+        (responder, accept_rx, complete_rx)
+    }
 }
 
 impl<C, T> Responder<C, T> for ProgressResponder<C, T>
@@ -96,6 +123,13 @@ where
     C: RaftTypeConfig,
     T: OptionalSend + 'static,
 {
+    fn on_accept(&mut self, log_id: LogIdOf<C>) {
+        if let Some(tx) = self.accept_tx.take() {
+            let res = tx.send(log_id);
+            tracing::debug!("ProgressResponder.accept_tx.send: is_ok: {}", res.is_ok());
+        }
+    }
+
     fn on_commit(&mut self, log_id: LogIdOf<C>) {
         if let Some(tx) = self.commit_tx.take() {
             let res = tx.send(log_id);
@@ -170,6 +204,38 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_twoshot_responder_accept_and_complete() {
+        UTConfig::<()>::run(async {
+            let (mut responder, accept_rx, complete_rx): (ProgressResponder<UTConfig, String>, _, _) =
+                ProgressResponder::accept_and_complete();
+
+            let test_log_id = log_id(1, 2, 3);
+            let test_result = "test_result".to_string();
+
+            responder.on_accept(test_log_id);
+            responder.on_commit(test_log_id);
+            assert_eq!(test_log_id, accept_rx.await.unwrap());
+
+            responder.on_complete(test_result.clone());
+            assert_eq!(test_result, complete_rx.await.unwrap());
+        });
+    }
+
+    #[test]
+    fn test_twoshot_responder_accept_and_complete_rejected() {
+        UTConfig::<()>::run(async {
+            let (responder, accept_rx, complete_rx): (ProgressResponder<UTConfig, String>, _, _) =
+                ProgressResponder::accept_and_complete();
+
+            // Completed without being accepted: the accept sender is dropped.
+            responder.on_complete("rejected".to_string());
+
+            assert!(accept_rx.await.is_err());
+            assert_eq!("rejected", complete_rx.await.unwrap());
+        });
+    }
+
     #[test]
     fn test_twoshot_responder_on_commit() {
         UTConfig::<()>::run(async {
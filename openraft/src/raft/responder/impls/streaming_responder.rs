@@ -0,0 +1,132 @@
+use std::fmt;
+
+use openraft_macros::since;
+
+use crate::OptionalSend;
+use crate::RaftTypeConfig;
+use crate::base::BoxFnMut;
+use crate::raft::responder::Responder;
+use crate::type_config::alias::LogIdOf;
+
+/// A progress event of a client write, delivered by a [`StreamingResponder`].
+#[since(version = "0.10.0")]
+pub enum WriteEvent<C, T>
+where C: RaftTypeConfig
+{
+    /// The leader appended the request as a log entry, not yet replicated.
+    Accepted(LogIdOf<C>),
+
+    /// The log entry is committed and safe to read.
+    Committed(LogIdOf<C>),
+
+    /// The request is completed with the final result. It is the last event.
+    Completed(T),
+}
+
+impl<C, T> fmt::Debug for WriteEvent<C, T>
+where
+    C: RaftTypeConfig,
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WriteEvent::Accepted(log_id) => f.debug_tuple("Accepted").field(log_id).finish(),
+            WriteEvent::Committed(log_id) => f.debug_tuple("Committed").field(log_id).finish(),
+            WriteEvent::Completed(res) => f.debug_tuple("Completed").field(res).finish(),
+        }
+    }
+}
+
+/// A [`Responder`] implementation that delivers every progress event of a write, in order, to a
+/// user-provided sink.
+///
+/// A gateway server can forward the events into a server stream, e.g., HTTP/2 or gRPC, without
+/// writing its own [`Responder`]. The sink is called on the `RaftCore` task and must not block:
+/// it is usually the sending half of an unbounded channel.
+///
+/// # Example
+///
+/// ```ignore
+/// let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+/// let responder = StreamingResponder::new(move |event| {
+///     let _ = tx.send(event);
+/// });
+///
+/// raft.client_write_ff(request, Some(responder)).await?;
+///
+/// while let Some(event) = rx.recv().await {
+///     match event {
+///         WriteEvent::Accepted(log_id) => stream.send(accepted(log_id)).await?,
+///         WriteEvent::Committed(log_id) => stream.send(committed(log_id)).await?,
+///         WriteEvent::Completed(res) => stream.send(completed(res)).await?,
+///     }
+/// }
+/// ```
+#[since(version = "0.10.0")]
+pub struct StreamingResponder<C, T>
+where C: RaftTypeConfig
+{
+    sink: BoxFnMut<'static, WriteEvent<C, T>>,
+}
+
+impl<C, T> StreamingResponder<C, T>
+where C: RaftTypeConfig
+{
+    /// Create a responder that calls `sink` with every [`WriteEvent`] of the write.
+    #[since(version = "0.10.0")]
+    pub fn new<F>(sink: F) -> Self
+    where F: FnMut(WriteEvent<C, T>) + OptionalSend + 'static {
+        Self { sink: Box::new(sink) }
+    }
+}
+
+impl<C, T> Responder<C, T> for StreamingResponder<C, T>
+where
+    C: RaftTypeConfig,
+    T: OptionalSend + 'static,
+{
+    fn on_accept(&mut self, log_id: LogIdOf<C>) {
+        (self.sink)(WriteEvent::Accepted(log_id));
+    }
+
+    fn on_commit(&mut self, log_id: LogIdOf<C>) {
+        (self.sink)(WriteEvent::Committed(log_id));
+    }
+
+    fn on_complete(mut self, res: T) {
+        (self.sink)(WriteEvent::Completed(res));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    use crate::engine::testing::UTConfig;
+    use crate::engine::testing::log_id;
+    use crate::raft::responder::Responder;
+    use crate::raft::responder::StreamingResponder;
+    use crate::raft::responder::WriteEvent;
+
+    #[test]
+    fn test_streaming_responder_events_in_order() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+
+        let mut responder: StreamingResponder<UTConfig, String> = {
+            let events = events.clone();
+            StreamingResponder::new(move |ev| events.lock().unwrap().push(format!("{:?}", ev)))
+        };
+
+        responder.on_accept(log_id(1, 2, 3));
+        responder.on_commit(log_id(1, 2, 3));
+        responder.on_complete("ok".to_string());
+
+        let want = vec![
+            format!("{:?}", WriteEvent::<UTConfig, String>::Accepted(log_id(1, 2, 3))),
+            format!("{:?}", WriteEvent::<UTConfig, String>::Committed(log_id(1, 2, 3))),
+            r#"Completed("ok")"#.to_string(),
+        ];
+        assert_eq!(want, *events.lock().unwrap());
+    }
+}
@@ -4,6 +4,8 @@ pub(crate) mod core_responder;
 pub(crate) mod impls;
 pub use impls::OneshotResponder;
 pub use impls::ProgressResponder;
+pub use impls::StreamingResponder;
+pub use impls::WriteEvent;
 use openraft_macros::since;
 
 use crate::OptionalSend;
@@ -17,6 +19,7 @@ use crate::type_config::alias::LogIdOf;
 ///
 /// ## Lifecycle Callbacks
 ///
+/// - [`on_accept()`](Self::on_accept): Called when the leader assigns a log id (optional)
 /// - [`on_commit()`](Self::on_commit): Called when locally committed (optional)
 /// - [`on_complete()`](Self::on_complete): Sends the final result
 ///
@@ -28,6 +31,19 @@ where
     Self: OptionalSend + Sized + 'static,
     C: RaftTypeConfig,
 {
+    /// Called when the leader accepts the request and appends it as a log entry.
+    ///
+    /// Invoked before the entry is replicated: the entry may still be lost, e.g., if the leader
+    /// steps down, in which case [`on_complete()`](Self::on_complete) receives an error.
+    ///
+    /// # Parameters
+    ///
+    /// - `log_id`: The log ID assigned by the proposing leader.
+    ///
+    /// Default implementation does nothing.
+    #[since(version = "0.10.0")]
+    fn on_accept(&mut self, _log_id: LogIdOf<C>) {}
+
     /// Called when the log entry is locally committed (safe to read).
     ///
     /// Invoked when the log has been replicated to a quorum. At this point, the log is guaranteed
@@ -0,0 +1,66 @@
+use std::fmt;
+
+use display_more::DisplayOptionExt;
+use openraft_macros::since;
+
+use crate::RaftTypeConfig;
+use crate::errors::Fatal;
+use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::SnapshotMetaOf;
+use crate::type_config::alias::VoteOf;
+
+/// The final state of a Raft node when `RaftCore` quits, returned by
+/// [`Raft::shutdown()`](crate::Raft::shutdown).
+///
+/// It is built and logged whether the node is shut down gracefully or quits on a fatal error, so
+/// that the state before a restart can be looked up when triaging it.
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShutdownReport<C>
+where C: RaftTypeConfig
+{
+    /// The id of this node.
+    pub id: C::NodeId,
+
+    /// The last vote of this node.
+    pub vote: VoteOf<C>,
+
+    /// The last log id in the log store.
+    pub last_log_id: Option<LogIdOf<C>>,
+
+    /// The last log id known to be committed on this node, i.e., the local committed log id.
+    pub committed: Option<LogIdOf<C>>,
+
+    /// The last log id applied to the state machine.
+    pub applied: Option<LogIdOf<C>>,
+
+    /// The meta of the current snapshot, or `None` if there is no snapshot.
+    pub snapshot: Option<SnapshotMetaOf<C>>,
+
+    /// The number of client writes that were not responded to when `RaftCore` quit.
+    ///
+    /// Their clients receive an error and do not know whether the writes are committed.
+    pub pending_proposals: u64,
+
+    /// Why `RaftCore` quit: [`Fatal::Stopped`] for a graceful shutdown.
+    pub reason: Fatal<C>,
+}
+
+impl<C> fmt::Display for ShutdownReport<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ShutdownReport{{id: {}, vote: {}, last_log_id: {}, committed: {}, applied: {}, snapshot: {}, pending_proposals: {}, reason: {}}}",
+            self.id,
+            self.vote,
+            self.last_log_id.display(),
+            self.committed.display(),
+            self.applied.display(),
+            self.snapshot.display(),
+            self.pending_proposals,
+            self.reason
+        )
+    }
+}
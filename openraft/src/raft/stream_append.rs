@@ -8,6 +8,8 @@ use futures_util::StreamExt;
 use crate::AsyncRuntime;
 use crate::OptionalSend;
 use crate::RaftTypeConfig;
+use crate::async_runtime::OneshotSender;
+use crate::batch::Batch;
 use crate::core::raft_msg::RaftMsg;
 use crate::errors::Fatal;
 use crate::raft::AppendEntriesRequest;
@@ -25,7 +27,7 @@ pub type StreamAppendResult<C> = Result<Option<LogIdOf<C>>, StreamAppendError<C>
 const PIPELINE_BUFFER_SIZE: usize = 64;
 
 struct Pending<C: RaftTypeConfig> {
-    response_rx: OneshotReceiverOf<C, StreamAppendResult<C>>,
+    response_rx: OneshotReceiverOf<C, Option<StreamAppendResult<C>>>,
 }
 
 /// Create a pipelined stream that processes AppendEntries requests.
@@ -33,9 +35,13 @@ struct Pending<C: RaftTypeConfig> {
 /// Spawns a background task that reads from input, sends to RaftCore,
 /// and forwards response receivers. The returned stream awaits responses in order.
 ///
-/// On API error (Conflict or HigherVote), the stream terminates with the error.
-/// On Fatal error (RaftCore stopped), the stream yields `Err(Fatal)` and terminates.
-/// The background task exits when it fails to send to the dropped channel.
+/// Requests merged by `RaftCore` are answered by a single response: the one to the last merged
+/// request, with the highest matching log id. Nothing is yielded for the earlier ones.
+///
+/// On API error (Conflict, HigherVote or Malformed), the stream terminates with the error.
+/// A malformed request is rejected without being sent to RaftCore.
+/// On Fatal error (RaftCore stopped or dropped the request), the stream yields `Err(Fatal)` and
+/// terminates. The background task exits when it fails to send to the dropped channel.
 pub(in crate::raft) fn stream_append<C, S>(
     inner: Arc<RaftInner<C>>,
     input: S,
@@ -54,7 +60,19 @@ where
         while let Some(req) = input.next().await {
             let (resp_tx, resp_rx) = C::oneshot();
 
-            if inner.send_msg(RaftMsg::AppendEntries { rpc: req, tx: resp_tx }).await.is_err() {
+            if let Err(e) = req.validate() {
+                tracing::error!("stream_append: reject malformed request: {}; req: {}", e, req);
+
+                resp_tx.send(Some(Err(StreamAppendError::Malformed(e)))).ok();
+                MpscSender::send(&tx, Pending { response_rx: resp_rx }).await.ok();
+                break;
+            }
+
+            let msg = RaftMsg::AppendEntries {
+                txs: Batch::of([resp_tx]),
+                rpc: req,
+            };
+            if inner.send_msg(msg).await.is_err() {
                 break;
             }
 
@@ -68,14 +86,19 @@ where
 
     futures_util::stream::unfold(Some((rx, unfold_inner)), |state| async move {
         let (mut rx, inner) = state?;
-        let p: Pending<C> = MpscReceiver::recv(&mut rx).await?;
-
-        let result: Result<StreamAppendResult<C>, Fatal<C>> = match p.response_rx.await {
-            Ok(r) => Ok(r),
-            Err(_) => {
-                let fatal = inner.get_core_stop_error().await;
-                tracing::error!("stream_append: RaftCore stopped: {}", fatal);
-                Err(fatal)
+
+        let result: Result<StreamAppendResult<C>, Fatal<C>> = loop {
+            let p: Pending<C> = MpscReceiver::recv(&mut rx).await?;
+
+            match p.response_rx.await {
+                Ok(Some(r)) => break Ok(r),
+                // Merged into a later request, whose response answers this one too.
+                Ok(None) => continue,
+                Err(_) => {
+                    let fatal = inner.get_dropped_request_error().await;
+                    tracing::error!("stream_append: RaftCore dropped the request: {}", fatal);
+                    break Err(fatal);
+                }
             }
         };
 
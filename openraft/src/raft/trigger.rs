@@ -1,5 +1,7 @@
 //! Trigger an action to RaftCore by an external caller.
 
+use std::fmt;
+
 use openraft_macros::since;
 
 use crate::RaftTypeConfig;
@@ -9,6 +11,8 @@ use crate::errors::Fatal;
 use crate::raft::RaftInner;
 use crate::type_config::TypeConfigExt;
 use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::OneshotReceiverOf;
+use crate::type_config::alias::SnapshotMetaOf;
 use crate::type_config::alias::VoteOf;
 
 /// Trigger is an interface to trigger an action to RaftCore by external caller.
@@ -28,6 +32,12 @@ use crate::type_config::alias::VoteOf;
 /// raft.trigger().purge_log().await?;
 /// ```
 ///
+/// To know which log id the triggered snapshot covers, await the returned handle:
+///
+/// ```ignore
+/// let meta = raft.trigger().snapshot().await?.built().await?;
+/// ```
+///
 /// [`Raft::trigger()`]: crate::Raft::trigger
 pub struct Trigger<'r, C>
 where C: RaftTypeConfig
@@ -55,8 +65,10 @@ where C: RaftTypeConfig
     /// in per call.
     ///
     /// Returns error when RaftCore has [`Fatal`] error, e.g., shut down or having storage error.
-    /// It is not affected by `Raft::enable_elect(false)`.
+    /// It is not affected by `Raft::enable_elect(false)`. No election is started if the term can
+    /// not be increased, which is reported in [`RaftMetrics::term_exhausted`].
     ///
+    /// [`RaftMetrics::term_exhausted`]: crate::metrics::RaftMetrics::term_exhausted
     /// [`Config::enable_pre_vote`]: crate::Config::enable_pre_vote
     pub async fn elect(&self, pre_vote: bool) -> Result<(), Fatal<C>> {
         self.raft_inner.send_external_command(ExternalCommand::Elect { pre_vote }).await
@@ -72,9 +84,20 @@ where C: RaftTypeConfig
 
     /// Trigger to build a snapshot at once and return at once.
     ///
+    /// The returned [`SnapshotBuildHandle`] can be dropped, or awaited with
+    /// [`built()`](SnapshotBuildHandle::built) for the meta of the snapshot once it is built.
+    /// If a snapshot is already being built, no new build is started and the handle resolves with
+    /// the one being built.
+    ///
     /// Returns error when RaftCore has [`Fatal`] error, e.g., shut down or having storage error.
-    pub async fn snapshot(&self) -> Result<(), Fatal<C>> {
-        self.raft_inner.send_external_command(ExternalCommand::Snapshot).await
+    pub async fn snapshot(&self) -> Result<SnapshotBuildHandle<'r, C>, Fatal<C>> {
+        let (tx, rx) = C::oneshot();
+        self.raft_inner.send_external_command(ExternalCommand::Snapshot { tx }).await?;
+
+        Ok(SnapshotBuildHandle {
+            raft_inner: self.raft_inner,
+            built_rx: rx,
+        })
     }
 
     /// Initiate the log purge up to and including the given `upto` log index.
@@ -195,3 +218,39 @@ where C: RaftTypeConfig
         self.raft_inner.send_external_command(cmd).await
     }
 }
+
+/// A snapshot build triggered with [`Trigger::snapshot()`].
+///
+/// Await [`built()`](Self::built) for the meta of the built snapshot, e.g., for a backup tool to
+/// learn which log id the snapshot it triggered covers.
+#[since(version = "0.10.0")]
+pub struct SnapshotBuildHandle<'r, C>
+where C: RaftTypeConfig
+{
+    raft_inner: &'r RaftInner<C>,
+    built_rx: OneshotReceiverOf<C, Option<SnapshotMetaOf<C>>>,
+}
+
+impl<C> fmt::Debug for SnapshotBuildHandle<'_, C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SnapshotBuildHandle").finish()
+    }
+}
+
+impl<C> SnapshotBuildHandle<'_, C>
+where C: RaftTypeConfig
+{
+    /// Wait until the snapshot is built, and return its meta.
+    ///
+    /// Returns `Ok(None)` if no snapshot is built: the state machine declined to build one, or
+    /// this node is a log-only node without a state machine.
+    ///
+    /// Returns error when RaftCore has [`Fatal`] error, e.g., shut down or having storage error
+    /// while building the snapshot.
+    #[since(version = "0.10.0")]
+    pub async fn built(self) -> Result<Option<SnapshotMetaOf<C>>, Fatal<C>> {
+        self.raft_inner.recv_msg(self.built_rx).await
+    }
+}
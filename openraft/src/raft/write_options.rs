@@ -0,0 +1,27 @@
+use openraft_macros::since;
+
+use crate::raft::WriteWait;
+
+/// Options for a write submitted with
+/// [`Raft::client_write_with_options()`](crate::Raft::client_write_with_options).
+#[since(version = "0.10.0")]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct WriteOptions {
+    /// The stage of the write to wait for before returning. Defaults to [`WriteWait::Applied`],
+    /// the same as [`Raft::client_write()`](crate::Raft::client_write).
+    pub wait: WriteWait,
+}
+
+impl WriteOptions {
+    /// Create options that wait for the write to be applied.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the stage of the write to wait for before returning.
+    pub fn with_wait(mut self, wait: WriteWait) -> Self {
+        self.wait = wait;
+        self
+    }
+}
@@ -0,0 +1,24 @@
+use openraft_macros::since;
+
+/// The stage of a write to wait for before returning, see [`WriteOptions`].
+///
+/// [`WriteOptions`]: crate::raft::WriteOptions
+#[since(version = "0.10.0")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(derive_more::Display)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum WriteWait {
+    /// Return once the leader appends the entry to its log.
+    ///
+    /// The entry is not yet replicated and may still be lost if the leader steps down.
+    Accepted,
+
+    /// Return once the entry is committed, i.e., replicated to a quorum.
+    ///
+    /// The entry will not be lost, but it may not yet be applied to the state machine.
+    Committed,
+
+    /// Return once the entry is applied to the state machine of the leader.
+    #[default]
+    Applied,
+}
@@ -138,6 +138,13 @@ where C: RaftTypeConfig
     /// Unlike `RaftState::last_purged_log_id()` (which is the queued purge target),
     /// this reflects the actually purged log id.
     pub(crate) purged: Option<LogIdOf<C>>,
+
+    /// The last committed log id a log-only node has saved without applying it.
+    ///
+    /// A log-only node has no state machine, it does not advance `apply_progress` so that it
+    /// still tells what the state machine has applied. See
+    /// [`NodeRole::LogOnly`](crate::NodeRole::LogOnly).
+    pub(crate) log_only_committed: Option<LogIdOf<C>>,
 }
 
 const LOG_PROGRESS_NAME: &str = "LogIO";
@@ -155,6 +162,7 @@ where C: RaftTypeConfig
             snapshot: new_progress(None, "xx", SNAPSHOT_PROGRESS_NAME),
             cluster_committed: MonotonicIncrease::default(),
             purged: None,
+            log_only_committed: None,
         }
     }
 }
@@ -178,7 +186,10 @@ where C: RaftTypeConfig
         // Snapshot must be included in applied.
         less_equal!(self.snapshot.submitted(), self.apply_progress.submitted());
 
-        less_equal!(&self.purged, &self.snapshot.flushed().cloned());
+        // Logs not in a snapshot may be purged if they are applied, with
+        // `PurgePolicy::DurableApplied`.
+        let purgeable = std::cmp::max(self.snapshot.flushed(), self.apply_progress.flushed());
+        less_equal!(&self.purged, &purgeable.cloned());
         Ok(())
     }
 }
@@ -201,9 +212,18 @@ where C: RaftTypeConfig
             snapshot: new_progress(snapshot, id, SNAPSHOT_PROGRESS_NAME),
             cluster_committed: MonotonicIncrease::default(),
             purged,
+            log_only_committed: None,
         }
     }
 
+    /// Re-synchronize the log IO progress to the durable state of the log storage.
+    ///
+    /// Used when leaving degraded mode: the IO submitted before the storage failed is lost, and
+    /// the progress may go backward.
+    pub(crate) fn reset_log_progress(&mut self, id: impl ToString, io_id: IOId<C>) {
+        self.log_progress = new_progress(Some(io_id), id, LOG_PROGRESS_NAME);
+    }
+
     pub(crate) fn applied(&self) -> Option<&LogIdOf<C>> {
         self.apply_progress.flushed()
     }
@@ -17,6 +17,9 @@ where
     N: Node,
 {
     pub(crate) state: &'m MembershipState<CLID, NID, N>,
+
+    /// Reject a new membership in which two nodes have equal node info.
+    pub(crate) reject_duplicate_nodes: bool,
 }
 
 impl<CLID, NID, N> ChangeHandler<'_, CLID, NID, N>
@@ -35,18 +38,40 @@ where
     /// `ChangeMembershipError` if an error occurs.
     ///
     /// This function ensures that the cluster will have at least one voter in the new membership
-    /// configuration.
+    /// configuration, and, if `reject_duplicate_nodes` is set, that no two nodes have equal node
+    /// info.
+    ///
+    /// Unlike other changes, [`ChangeMembers::RemoveLearners`] is applied even if the last
+    /// membership is not committed: it keeps the voter configs of the last membership as they are.
     pub(crate) fn apply(
         &self,
         change: ChangeMembers<NID, N>,
         retain: bool,
     ) -> Result<Membership<NID, N>, ChangeMembershipError<CLID, NID>> {
+        if let ChangeMembers::RemoveLearners(learner_ids) = &change {
+            let new_membership = self.state.effective().membership().clone().remove_learners(learner_ids)?;
+            return Ok(new_membership);
+        }
+
         self.ensure_committed()?;
 
         let new_membership = self.state.effective().membership().clone().change(change, retain)?;
+
+        if self.reject_duplicate_nodes {
+            new_membership.find_duplicate_node()?;
+        }
+
         Ok(new_membership)
     }
 
+    /// Set whether to reject a new membership in which two nodes have equal node info.
+    ///
+    /// See [`Config::reject_duplicate_nodes`](crate::Config::reject_duplicate_nodes).
+    pub(crate) fn reject_duplicate_nodes(mut self, reject: bool) -> Self {
+        self.reject_duplicate_nodes = reject;
+        self
+    }
+
     /// Ensures that the latest membership has been committed.
     ///
     /// Returns Ok if the last membership is committed, or an InProgress error
@@ -7,6 +7,7 @@ use crate::Membership;
 use crate::engine::testing::UTConfig;
 use crate::engine::testing::log_id;
 use crate::errors::ChangeMembershipError;
+use crate::errors::DuplicateNode;
 use crate::errors::EmptyMembership;
 use crate::errors::InProgress;
 use crate::errors::LearnerNotFound;
@@ -47,6 +48,41 @@ fn test_apply_not_committed() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_apply_not_committed_remove_learner() -> anyhow::Result<()> {
+    let m = Membership::new_with_defaults(vec![btreeset! {1,2,3}, btreeset! {3,4,5}], [6, 7]);
+    let new = || MembershipStateOf::<UTConfig>::new(effmem(2, 2, m1()), effmem(3, 4, m.clone()));
+
+    // RemoveNodes has to wait for the last membership to be committed.
+    let res = new().change_handler().apply(ChangeMembers::RemoveNodes(btreeset! {6}), false);
+    assert_eq!(
+        Err(ChangeMembershipError::InProgress(InProgress {
+            committed: Some(log_id(2, 1, 2)),
+            membership_log_id: Some(log_id(3, 1, 4))
+        })),
+        res
+    );
+
+    // RemoveLearners does not, and keeps the joint config.
+    let res = new().change_handler().apply(ChangeMembers::RemoveLearners(btreeset! {6}), false);
+    assert_eq!(
+        Ok(Membership::new_with_defaults(
+            vec![btreeset! {1,2,3}, btreeset! {3,4,5}],
+            [7]
+        )),
+        res
+    );
+
+    // A voter can not be removed.
+    let res = new().change_handler().apply(ChangeMembers::RemoveLearners(btreeset! {5}), false);
+    assert_eq!(
+        Err(ChangeMembershipError::LearnerNotFound(LearnerNotFound { node_id: 5 })),
+        res
+    );
+
+    Ok(())
+}
+
 #[test]
 fn test_apply_empty_voters() -> anyhow::Result<()> {
     let new = || MembershipStateOf::<UTConfig>::new(effmem(3, 4, m1()), effmem(3, 4, m1()));
@@ -70,6 +106,28 @@ fn test_apply_learner_not_found() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_apply_duplicate_node() -> anyhow::Result<()> {
+    let new = || MembershipStateOf::<UTConfig>::new(effmem(3, 4, m1()), effmem(3, 4, m1()));
+
+    // `()` node info of node 1 and node 2 are equal.
+    let change = || ChangeMembers::AddNodes(maplit::btreemap! {2=>()});
+
+    let res = new().change_handler().apply(change(), false);
+    assert!(res.is_ok(), "duplicate node info is allowed by default");
+
+    let res = new().change_handler().reject_duplicate_nodes(true).apply(change(), false);
+    assert_eq!(
+        Err(ChangeMembershipError::DuplicateNode(DuplicateNode {
+            node_id: 2,
+            other: 1
+        })),
+        res
+    );
+
+    Ok(())
+}
+
 #[test]
 fn test_apply_retain_learner() -> anyhow::Result<()> {
     let new = || MembershipStateOf::<UTConfig>::new(effmem(3, 4, m12()), effmem(3, 4, m123_345()));
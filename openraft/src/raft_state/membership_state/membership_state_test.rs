@@ -208,3 +208,71 @@ fn test_membership_state_truncate() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_membership_state_pending() -> anyhow::Result<()> {
+    let m123_345_6 = || Membership::new_with_defaults(vec![btreeset! {1,2,3}, btreeset! {3,4,5}], [6]);
+    let m123_345_67 = || Membership::new_with_defaults(vec![btreeset! {1,2,3}, btreeset! {3,4,5}], [6, 7]);
+
+    // The membership at 4 is not committed: removing learner 7 at 5 and learner 6 at 6 are
+    // stacked on it.
+    let new = || {
+        let mut ms = MembershipStateOf::<UTConfig>::new(effmem(2, 2, m1()), effmem(3, 4, m123_345_67()));
+        ms.append(effmem(3, 5, m123_345_6()));
+        ms.append(effmem(3, 6, m123_345()));
+        ms
+    };
+
+    // Append
+    {
+        let ms = new();
+        assert_eq!(&Some(log_id(2, 1, 2)), ms.committed().log_id());
+        assert_eq!(&Some(log_id(3, 1, 6)), ms.effective().log_id());
+        assert_eq!(2, ms.pending.len());
+    }
+
+    // A membership that changes the quorums: the previous ones must have been committed.
+    {
+        let mut ms = new();
+        ms.append(effmem(3, 7, m12()));
+        assert_eq!(&Some(log_id(3, 1, 6)), ms.committed().log_id());
+        assert_eq!(&Some(log_id(3, 1, 7)), ms.effective().log_id());
+        assert!(ms.pending.is_empty());
+    }
+
+    // Commit up to a pending one
+    {
+        let mut ms = new();
+        assert!(ms.commit(&Some(log_id(3, 1, 5))));
+        assert_eq!(&Some(log_id(3, 1, 5)), ms.committed().log_id());
+        assert_eq!(&Some(log_id(3, 1, 6)), ms.effective().log_id());
+        assert_eq!(1, ms.pending.len());
+
+        assert!(ms.commit(&Some(log_id(3, 1, 6))));
+        assert_eq!(&Some(log_id(3, 1, 6)), ms.committed().log_id());
+        assert!(ms.pending.is_empty());
+    }
+
+    // Truncate reverts to the last pending one before `since`
+    {
+        let mut ms = new();
+        let res = ms.truncate(6);
+        assert_eq!(&Some(log_id(3, 1, 5)), res.unwrap().log_id());
+        assert_eq!(&m123_345_6(), ms.effective().membership());
+        assert_eq!(&Some(log_id(2, 1, 2)), ms.committed().log_id());
+
+        let res = ms.truncate(4);
+        assert_eq!(&Some(log_id(2, 1, 2)), res.unwrap().log_id());
+        assert!(ms.pending.is_empty());
+    }
+
+    {
+        let mut ms = new();
+        let res = ms.truncate(4);
+        assert_eq!(&Some(log_id(2, 1, 2)), res.unwrap().log_id());
+        assert_eq!(&Some(log_id(2, 1, 2)), ms.effective().log_id());
+        assert!(ms.pending.is_empty());
+    }
+
+    Ok(())
+}
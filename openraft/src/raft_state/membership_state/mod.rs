@@ -48,6 +48,13 @@ use crate::vote::RaftCommittedLeaderId;
 /// From (2), a follower only needs to revert at most one membership log.
 ///
 /// Thus, a raft node will only need to store at most two recent membership logs.
+///
+/// The exception to (1) is a membership that only removes learners from the previous one, with
+/// [`ChangeMembers::RemoveLearners`]: it keeps the quorums, and may be proposed before the previous
+/// one is committed. The memberships between `committed` and `effective` that are not
+/// known to be committed are kept in `pending`, to revert to when the logs are truncated.
+///
+/// [`ChangeMembers::RemoveLearners`]: crate::ChangeMembers::RemoveLearners
 #[since(
     version = "0.10.0",
     change = "from `MembershipState<C>` to `MembershipState<CLID, NID, N>`"
@@ -62,6 +69,12 @@ where
 {
     committed: Arc<StoredMembership<CLID, NID, N>>,
 
+    /// The memberships after `committed` and before `effective`, not yet known to be committed.
+    ///
+    /// Every one of them has the same quorums as `effective`, which only removes learners from
+    /// them.
+    pending: Vec<Arc<StoredMembership<CLID, NID, N>>>,
+
     // Using `Arc` because the effective membership will be copied to RaftMetrics frequently.
     effective: Arc<StoredMembership<CLID, NID, N>>,
 }
@@ -75,6 +88,7 @@ where
     fn default() -> Self {
         Self {
             committed: Arc::new(StoredMembership::default()),
+            pending: Vec::new(),
             effective: Arc::new(StoredMembership::default()),
         }
     }
@@ -105,7 +119,11 @@ where
         committed: Arc<StoredMembership<CLID, NID, N>>,
         effective: Arc<StoredMembership<CLID, NID, N>>,
     ) -> Self {
-        Self { committed, effective }
+        Self {
+            committed,
+            pending: Vec::new(),
+            effective,
+        }
     }
 
     /// Return true if the given node id is either a voter or a learner.
@@ -122,8 +140,11 @@ where
     /// its log id.
     ///
     /// Committing replaces `self.committed`(the membership state machine) with
-    /// `self.effective`(the last membership log).
-    pub(crate) fn commit(&mut self, committed_log_id: &Option<LogId<CLID>>) {
+    /// `self.effective`(the last membership log), or with the last pending membership up to
+    /// `committed_log_id`.
+    ///
+    /// Returns `true` if the committed membership config changes.
+    pub(crate) fn commit(&mut self, committed_log_id: &Option<LogId<CLID>>) -> bool {
         let current = self.committed.log_id().clone();
         let last = self.effective().log_id().clone();
 
@@ -131,7 +152,17 @@ where
         if committed_log_id >= &last && current < last {
             debug_assert!(committed_log_id.index() >= last.index());
             self.committed = self.effective.clone();
+            self.pending.clear();
+            return true;
+        }
+
+        let n = self.pending.iter().take_while(|m| m.log_id() <= committed_log_id).count();
+        if n > 0 {
+            self.committed = self.pending.drain(..n).next_back().unwrap();
+            return true;
         }
+
+        false
     }
 
     /// Install the membership config carried by a snapshot.
@@ -147,7 +178,8 @@ where
         //   local_effective.log_id = (2, 10);
         if membership_snapshot.log_id().index() >= self.effective.log_id().index() {
             // The effective may override by a new leader with a different one.
-            self.effective = membership_snapshot.clone()
+            self.effective = membership_snapshot.clone();
+            self.pending.clear();
         }
 
         #[allow(clippy::collapsible_if)]
@@ -164,6 +196,7 @@ where
         // same log id implies the same membership,
         // so it only needs to compare log id.
         if membership_snapshot.log_id() > self.committed.log_id() {
+            self.pending.retain(|m| m.log_id() > membership_snapshot.log_id());
             self.committed = membership_snapshot
         }
     }
@@ -184,9 +217,20 @@ where
             "new membership has to have a greater index"
         );
 
-        // Openraft allows at most only one non-committed membership config.
-        // If there is another new config, self.effective must have been committed.
+        let effective_committed = self.effective.log_id() == self.committed.log_id();
+
+        if !effective_committed && m.membership().is_learner_removal_of(self.effective.membership()) {
+            // Removing learners does not change the quorums, and may be proposed before the
+            // previous membership is committed.
+            let prev = std::mem::replace(&mut self.effective, m);
+            self.pending.push(prev);
+            return;
+        }
+
+        // Openraft allows at most only one non-committed membership config that changes the
+        // quorums. If there is another new config, self.effective must have been committed.
         self.committed = self.effective.clone();
+        self.pending.clear();
         self.effective = m;
     }
 
@@ -194,8 +238,9 @@ where
     ///
     /// It returns the updated effective membership config if it is changed.
     ///
-    /// It will reset `self.effective` to `self.committed`. Only the effective could be truncated
-    /// when a new leader tries to truncate follower logs that the leader does not have.
+    /// It will reset `self.effective` to the last pending membership before `since`, or to
+    /// `self.committed`. Only the uncommitted ones could be truncated when a new leader tries to
+    /// truncate follower logs that the leader does not have.
     ///
     /// If the effective membership is from a conflicting log,
     /// the membership state has to revert to the last committed membership config.
@@ -221,7 +266,8 @@ where
                 self.committed()
             );
 
-            self.effective = self.committed.clone();
+            self.pending.retain(|m| m.log_id().index() < Some(since));
+            self.effective = self.pending.pop().unwrap_or_else(|| self.committed.clone());
             return Some(self.effective.clone());
         }
         None
@@ -251,7 +297,10 @@ where
     }
 
     pub(crate) fn change_handler(&self) -> ChangeHandler<'_, CLID, NID, N> {
-        ChangeHandler { state: self }
+        ChangeHandler {
+            state: self,
+            reject_duplicate_nodes: false,
+        }
     }
 }
 
@@ -264,6 +313,11 @@ where
     fn validate(&self) -> Result<(), Box<dyn Error>> {
         validit::less_equal!(self.committed.log_id(), self.effective.log_id());
         validit::less_equal!(self.committed.log_id().index(), self.effective.log_id().index());
+
+        for m in self.pending.iter() {
+            validit::less!(self.committed.log_id(), m.log_id());
+            validit::less!(m.log_id(), self.effective.log_id());
+        }
         Ok(())
     }
 }
@@ -42,6 +42,8 @@ pub(crate) use vote_state_reader::VoteStateReader;
 use crate::base::shared_id_generator::SharedIdGenerator;
 use crate::entry::RaftEntry;
 use crate::entry::raft_entry_ext::RaftEntryExt;
+use crate::membership::QuorumKind;
+use crate::membership::QuorumPolicy;
 use crate::progress::inflight_id::InflightId;
 use crate::proposer::Leader;
 use crate::proposer::LeaderQuorumSet;
@@ -90,6 +92,25 @@ where C: RaftTypeConfig
     /// field.
     pub(crate) purge_upto: Option<LogIdOf<C>>,
 
+    /// The first log index still needed by a log subscriber.
+    ///
+    /// The policy-based purge does not purge logs at or after this index.
+    pub(crate) purge_hold: Option<u64>,
+
+    /// The first log index a log-only voter may still need.
+    ///
+    /// A log-only voter does not install snapshot, thus no purge, whether by policy, by user or to
+    /// reclaim storage, removes logs at or after this index.
+    pub(crate) log_only_hold: Option<u64>,
+
+    /// The last applied log id the state machine has persisted.
+    ///
+    /// It is updated only with [`PurgePolicy::DurableApplied`], which purges logs up to it even
+    /// if they are not in a snapshot.
+    ///
+    /// [`PurgePolicy::DurableApplied`]: crate::PurgePolicy::DurableApplied
+    pub(crate) durable_applied: Option<LogIdOf<C>>,
+
     pub(crate) progress_id_gen: SharedIdGenerator,
 }
 
@@ -111,6 +132,9 @@ where
             server_state: ServerState::default(),
             io_state: Valid::new(IOState::default()),
             purge_upto: None,
+            purge_hold: None,
+            log_only_hold: None,
+            durable_applied: None,
             progress_id_gen: Default::default(),
         }
     }
@@ -180,7 +204,7 @@ where C: RaftTypeConfig
             // In such a case, we assert the monotonic relation without  snapshot-last-log-id
             validit::less_equal!(self.purge_upto(), self.local_committed());
         } else {
-            validit::less_equal!(self.purge_upto(), self.snapshot_last_log_id());
+            validit::less_equal!(self.purge_upto(), self.purgeable_log_id());
         }
         validit::less_equal!(self.snapshot_last_log_id(), self.local_committed());
         validit::less_equal!(self.local_committed(), self.last_log_id());
@@ -208,10 +232,19 @@ where C: RaftTypeConfig
             server_state: ServerState::default(),
             io_state: Valid::new(IOState::default()),
             purge_upto: None,
+            purge_hold: None,
+            log_only_hold: None,
+            durable_applied: None,
             progress_id_gen: Default::default(),
         }
     }
 
+    /// The last log id that may be purged: the last one in the snapshot, or the last persisted
+    /// applied one if it is greater.
+    pub(crate) fn purgeable_log_id(&self) -> Option<&LogIdOf<C>> {
+        std::cmp::max(self.snapshot_last_log_id(), self.durable_applied.as_ref())
+    }
+
     /// Get a reference to the current vote.
     pub fn vote_ref(&self) -> &VoteOf<C> {
         self.vote.deref()
@@ -327,7 +360,9 @@ where C: RaftTypeConfig
 
     /// This method first updates the cluster committed log ID, then uses it to calculate and
     /// update the local committed log ID.
-    pub(crate) fn update_committed(&mut self, cluster_committed: LogIOId<C>) {
+    ///
+    /// Returns `true` if a new membership config is committed.
+    pub(crate) fn update_committed(&mut self, cluster_committed: LogIOId<C>) -> bool {
         tracing::debug!(
             "{}: leader_committed: {}, my_accepted: {}, my_committed: {}",
             func_name!(),
@@ -340,7 +375,7 @@ where C: RaftTypeConfig
 
         let local_committed = self.io_state.calculate_local_committed();
 
-        self.update_local_committed(&local_committed);
+        self.update_local_committed(&local_committed)
     }
 
     /// Updates the committed log id for local use.
@@ -351,12 +386,16 @@ where C: RaftTypeConfig
     ///
     /// This method updates the committed log id only if the input is greater than the current
     /// value.
-    pub(crate) fn update_local_committed(&mut self, committed: &Option<LogIdOf<C>>) {
+    ///
+    /// Returns `true` if a new membership config is committed.
+    pub(crate) fn update_local_committed(&mut self, committed: &Option<LogIdOf<C>>) -> bool {
         if committed.as_ref() > self.local_committed() {
             // Safe unwrap(): committed > self.committed(), implies it cannot be None
             self.apply_progress_mut().accept(committed.clone().unwrap());
-            self.membership_state.commit(committed);
+            return self.membership_state.commit(committed);
         }
+
+        false
     }
 
     pub(crate) fn log_progress(&self) -> &IOProgress<IOId<C>> {
@@ -486,14 +525,19 @@ where C: RaftTypeConfig
     /// for example, node-1 elects node-2 as a Leader, node-2 will become a Leader when receives the
     /// vote.
     /// A Leader established with election using the state in `Engine.candidate`.
-    pub(crate) fn new_leader(&mut self) -> Leader<C, LeaderQuorumSet<C>> {
+    ///
+    /// The leader commits with the quorums decided by `quorum_policy`, if there is one.
+    pub(crate) fn new_leader(
+        &mut self,
+        quorum_policy: Option<&Arc<dyn QuorumPolicy<C>>>,
+    ) -> Leader<C, LeaderQuorumSet<C>> {
         let em = self.membership_state.effective().membership();
 
         let last_leader_log_ids = self.log_ids.by_last_leader();
 
         Leader::new(
             self.vote_ref().to_committed(),
-            Arc::new((*em).clone()),
+            LeaderQuorumSet::new(Arc::new((*em).clone()), quorum_policy, QuorumKind::Commit),
             em.learner_ids(),
             last_leader_log_ids,
             self.progress_id_gen.clone(),
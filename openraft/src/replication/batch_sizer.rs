@@ -0,0 +1,132 @@
+//! Adapts the number of log entries per AppendEntries request to how fast a follower acknowledges
+//! them.
+
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use crate::Config;
+
+/// Decides the maximum number of log entries per AppendEntries request sent to a follower.
+///
+/// Owned by `ReplicationCore`, which feeds it the outcome of every request. The request-stream
+/// generator reads the current size from the handle returned by [`Self::size_handle`].
+///
+/// With [`Config::min_payload_entries`] unset, the size is fixed at
+/// [`Config::max_payload_entries`]. Otherwise it starts at the minimum, grows by a quarter every
+/// time a request is acknowledged within [`Config::payload_target_rtt`], and is halved when a
+/// request times out or the follower accepts only part of it; staying within
+/// `[min_payload_entries, max_payload_entries]`.
+pub(crate) struct BatchSizer {
+    /// `None` if the size is fixed.
+    range: Option<(u64, u64)>,
+
+    target_rtt: Duration,
+
+    size: Arc<AtomicU64>,
+}
+
+impl BatchSizer {
+    pub(crate) fn new(config: &Config) -> Self {
+        let max = config.max_payload_entries;
+        let range = config.min_payload_entries.map(|min| (min, max));
+        let initial = range.map_or(max, |(min, _)| min);
+
+        Self {
+            range,
+            target_rtt: config.payload_target_rtt(),
+            size: Arc::new(AtomicU64::new(initial)),
+        }
+    }
+
+    /// Returns a handle to read the current size from.
+    pub(crate) fn size_handle(&self) -> Arc<AtomicU64> {
+        self.size.clone()
+    }
+
+    pub(crate) fn size(&self) -> u64 {
+        self.size.load(Ordering::Relaxed)
+    }
+
+    /// Called when a request is fully acknowledged `rtt` after it was sent.
+    pub(crate) fn on_ack(&mut self, rtt: Duration) {
+        let Some((_, max)) = self.range else {
+            return;
+        };
+
+        if rtt > self.target_rtt {
+            return;
+        }
+
+        let size = self.size();
+        self.set(std::cmp::min(size + std::cmp::max(size / 4, 1), max));
+    }
+
+    /// Called when a request times out or is only partially accepted.
+    pub(crate) fn on_overload(&mut self) {
+        let Some((min, _)) = self.range else {
+            return;
+        };
+
+        self.set(std::cmp::max(self.size() / 2, min));
+    }
+
+    fn set(&mut self, size: u64) {
+        let prev = self.size.swap(size, Ordering::Relaxed);
+        if prev != size {
+            tracing::debug!("AppendEntries batch size: {} -> {}", prev, size);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::BatchSizer;
+    use crate::Config;
+
+    #[test]
+    fn test_batch_sizer_fixed() {
+        let mut b = BatchSizer::new(&Config::default());
+        assert_eq!(300, b.size());
+
+        b.on_overload();
+        b.on_ack(Duration::ZERO);
+        assert_eq!(300, b.size());
+    }
+
+    #[test]
+    fn test_batch_sizer_adaptive() {
+        let config = Config {
+            max_payload_entries: 100,
+            min_payload_entries: Some(10),
+            payload_target_rtt: Some(50),
+            ..Default::default()
+        };
+        let mut b = BatchSizer::new(&config);
+        let handle = b.size_handle();
+        assert_eq!(10, b.size());
+
+        b.on_ack(Duration::from_millis(50));
+        assert_eq!(12, b.size());
+
+        b.on_ack(Duration::from_millis(51));
+        assert_eq!(12, b.size(), "slow ack does not grow");
+
+        for _ in 0..20 {
+            b.on_ack(Duration::from_millis(1));
+        }
+        assert_eq!(100, b.size(), "capped by max_payload_entries");
+        assert_eq!(100, handle.load(std::sync::atomic::Ordering::Relaxed));
+
+        b.on_overload();
+        assert_eq!(50, b.size());
+
+        for _ in 0..5 {
+            b.on_overload();
+        }
+        assert_eq!(10, b.size(), "capped by min_payload_entries");
+    }
+}
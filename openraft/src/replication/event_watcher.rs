@@ -10,6 +10,7 @@ where C: RaftTypeConfig
 {
     pub(crate) replicate_rx: WatchReceiverOf<C, Replicate<C>>,
     pub(crate) committed_rx: WatchReceiverOf<C, Option<LogIdOf<C>>>,
+    pub(crate) backup_barrier_rx: WatchReceiverOf<C, Option<LogIdOf<C>>>,
 
     pub(crate) io_accepted_rx: WatchReceiverOf<C, IOId<C>>,
     pub(crate) io_submitted_rx: WatchReceiverOf<C, IOId<C>>,
@@ -21,6 +21,12 @@ where C: RaftTypeConfig
 
     /// The last log id included in this request.
     pub(crate) last_log_id: Option<LogIdOf<C>>,
+
+    /// The number of log entries carried by this request.
+    pub(crate) entries: u64,
+
+    /// The total [`approx_size()`](crate::entry::RaftEntry::approx_size) of the log entries.
+    pub(crate) bytes: u64,
 }
 
 impl<C> fmt::Display for InflightAppend<C>
@@ -29,9 +35,11 @@ where C: RaftTypeConfig
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "InflightAppend{{sending_time:{}, last_log_id:{}}}",
+            "InflightAppend{{sending_time:{}, last_log_id:{}, entries:{}, bytes:{}}}",
             self.sending_time.display(),
-            self.last_log_id.display()
+            self.last_log_id.display(),
+            self.entries,
+            self.bytes
         )
     }
 }
@@ -39,10 +47,12 @@ where C: RaftTypeConfig
 impl<C> InflightAppend<C>
 where C: RaftTypeConfig
 {
-    pub(crate) fn new(last_log_id: Option<LogIdOf<C>>) -> Self {
+    pub(crate) fn new(last_log_id: Option<LogIdOf<C>>, entries: u64, bytes: u64) -> Self {
         Self {
             sending_time: C::now(),
             last_log_id,
+            entries,
+            bytes,
         }
     }
 }
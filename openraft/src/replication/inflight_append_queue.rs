@@ -4,10 +4,33 @@ use std::sync::Mutex;
 
 use display_more::DisplayOptionExt;
 
+use crate::OptionalSend;
 use crate::RaftTypeConfig;
+use crate::async_runtime::watch::WatchReceiver;
+use crate::async_runtime::watch::WatchSender;
 use crate::replication::inflight_append::InflightAppend;
+use crate::type_config::TypeConfigExt;
 use crate::type_config::alias::InstantOf;
 use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::WatchSenderOf;
+
+/// The AppendEntries requests drained from [`InflightAppendQueue`] by one response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Acked<C>
+where C: RaftTypeConfig
+{
+    /// The sending time of the first drained request.
+    pub(crate) first_sending_time: InstantOf<C>,
+
+    /// The sending time of the last drained request, for RTT calculation.
+    pub(crate) last_sending_time: InstantOf<C>,
+
+    /// The number of log entries in the drained requests.
+    pub(crate) entries: u64,
+
+    /// The total size of the log entries in the drained requests.
+    pub(crate) bytes: u64,
+}
 
 /// A queue tracking in-flight AppendEntries requests for measuring replication latency.
 ///
@@ -15,37 +38,102 @@ use crate::type_config::alias::LogIdOf;
 /// When a response arrives with a matching log id, all requests up to and including
 /// that log id are drained, and the sending time of the last drained request is returned
 /// for RTT calculation.
+///
+/// The number of queued requests and of the log entries in them is the in-flight window usage,
+/// which the request stream checks against [`Config::max_inflight_append_entries`] and
+/// [`Config::append_receive_window`] before sending more.
+///
+/// [`Config::max_inflight_append_entries`]: crate::Config::max_inflight_append_entries
+/// [`Config::append_receive_window`]: crate::Config::append_receive_window
 #[derive(Clone)]
 pub(crate) struct InflightAppendQueue<C>
 where C: RaftTypeConfig
 {
     queue: Arc<Mutex<VecDeque<InflightAppend<C>>>>,
+
+    /// The number of requests in `queue` and the total number of log entries in them, watched by
+    /// the request stream.
+    inflight: WatchSenderOf<C, (u64, u64)>,
 }
 
 impl<C> InflightAppendQueue<C>
 where C: RaftTypeConfig
 {
     pub(crate) fn new() -> Self {
+        let (inflight, _rx) = C::watch_channel((0, 0));
         Self {
             queue: Arc::new(Mutex::new(VecDeque::with_capacity(32))),
+            inflight,
         }
     }
 
-    /// Records a new in-flight AppendEntries request.
-    pub(crate) fn push(&self, log_id: Option<LogIdOf<C>>) {
+    /// Records a new in-flight AppendEntries request carrying `entries` log entries of `bytes` in
+    /// total.
+    pub(crate) fn push(&self, log_id: Option<LogIdOf<C>>, entries: u64, bytes: u64) {
         let mut q = self.queue.lock().unwrap();
-        let inflight = InflightAppend::new(log_id);
+        let inflight = InflightAppend::new(log_id, entries, bytes);
 
         tracing::debug!("Inflight queue push: {}", inflight);
 
-        q.push_back(inflight)
+        q.push_back(inflight);
+        self.inflight.send_if_modified(|(requests, n)| {
+            *requests += 1;
+            *n += entries;
+            true
+        });
+    }
+
+    /// Returns the number of log entries sent but not yet acknowledged.
+    #[cfg(test)]
+    pub(crate) fn inflight_entries(&self) -> u64 {
+        self.inflight.borrow_watched().1
+    }
+
+    /// Returns the number of requests sent but not yet acknowledged.
+    #[cfg(test)]
+    pub(crate) fn inflight_requests(&self) -> u64 {
+        self.inflight.borrow_watched().0
+    }
+
+    /// Returns a future that resolves once fewer than `max_requests` requests and fewer than
+    /// `window` log entries are in flight. A `None` limit is not checked.
+    ///
+    /// The returned future does not borrow `self`, because the watch sender is not `Sync`.
+    pub(crate) fn wait_for_window(
+        &self,
+        max_requests: Option<u64>,
+        window: Option<u64>,
+    ) -> impl Future<Output = ()> + OptionalSend + 'static {
+        let mut rx = self.inflight.subscribe();
+
+        async move {
+            loop {
+                let (requests, entries) = *rx.borrow_watched();
+                if max_requests.is_none_or(|m| requests < m) && window.is_none_or(|w| entries < w) {
+                    return;
+                }
+
+                tracing::debug!(
+                    "Inflight queue full: {} requests, {} entries in flight, max requests: {:?}, window: {:?}",
+                    requests,
+                    entries,
+                    max_requests,
+                    window
+                );
+
+                // The sender is owned by the queue, which outlives the request stream.
+                if rx.changed().await.is_err() {
+                    return;
+                }
+            }
+        }
     }
 
     /// Removes all requests with `last_log_id <= matching` and returns
-    /// the sending time of the last removed request.
+    /// the sending times of the first and last removed request and the log entries in them.
     ///
     /// Returns `None` if no requests were removed.
-    pub(crate) fn drain_acked(&self, matching: &Option<LogIdOf<C>>) -> Option<InstantOf<C>> {
+    pub(crate) fn drain_acked(&self, matching: &Option<LogIdOf<C>>) -> Option<Acked<C>> {
         let mut q = self.queue.lock().unwrap();
 
         tracing::debug!(
@@ -54,10 +142,23 @@ where C: RaftTypeConfig
             q.as_slices()
         );
 
-        let mut last = None;
+        let mut acked: Option<Acked<C>> = None;
+        let mut acked_requests = 0;
+        let mut acked_entries = 0;
         while let Some(first) = q.front() {
             if matching >= &first.last_log_id {
-                last = Some(first.sending_time)
+                let a = acked.get_or_insert(Acked {
+                    first_sending_time: first.sending_time,
+                    last_sending_time: first.sending_time,
+                    entries: 0,
+                    bytes: 0,
+                });
+                a.last_sending_time = first.sending_time;
+                a.entries += first.entries;
+                a.bytes += first.bytes;
+
+                acked_requests += 1;
+                acked_entries += first.entries;
             } else {
                 break;
             }
@@ -65,7 +166,13 @@ where C: RaftTypeConfig
             q.pop_front();
         }
 
-        last
+        self.inflight.send_if_modified(|(requests, n)| {
+            *requests -= acked_requests;
+            *n -= acked_entries;
+            acked_requests > 0
+        });
+
+        acked
     }
 }
 
@@ -84,8 +191,8 @@ mod tests {
     #[test]
     fn test_push_and_drain_acked_none_matching() {
         let q = InflightAppendQueue::<UTConfig>::new();
-        q.push(Some(log_id(1, 1, 5)));
-        q.push(Some(log_id(1, 1, 10)));
+        q.push(Some(log_id(1, 1, 5)), 0, 0);
+        q.push(Some(log_id(1, 1, 10)), 0, 0);
 
         // matching=None is less than any log_id, so nothing is acked
         assert_eq!(q.drain_acked(&None), None);
@@ -97,15 +204,15 @@ mod tests {
     #[test]
     fn test_drain_acked_partial() {
         let q = InflightAppendQueue::<UTConfig>::new();
-        q.push(Some(log_id(1, 1, 5)));
-        q.push(Some(log_id(1, 1, 10)));
-        q.push(Some(log_id(1, 1, 15)));
+        q.push(Some(log_id(1, 1, 5)), 0, 0);
+        q.push(Some(log_id(1, 1, 10)), 0, 0);
+        q.push(Some(log_id(1, 1, 15)), 0, 0);
 
         // Read the expected sending_time before calling drain_acked
         let expected_time = q.queue.lock().unwrap()[1].sending_time;
 
         // matching=10 should ack entries with last_log_id <= 10
-        let result = q.drain_acked(&Some(log_id(1, 1, 10)));
+        let result = q.drain_acked(&Some(log_id(1, 1, 10))).map(|a| a.last_sending_time);
 
         // Should return the sending_time of entry with log_id=10
         assert_eq!(result, Some(expected_time));
@@ -119,14 +226,14 @@ mod tests {
     #[test]
     fn test_drain_acked_all() {
         let q = InflightAppendQueue::<UTConfig>::new();
-        q.push(Some(log_id(1, 1, 5)));
-        q.push(Some(log_id(1, 1, 10)));
+        q.push(Some(log_id(1, 1, 5)), 0, 0);
+        q.push(Some(log_id(1, 1, 10)), 0, 0);
 
         // Read the expected sending_time before calling drain_acked
         let expected_time = q.queue.lock().unwrap()[1].sending_time;
 
         // matching=20 is greater than all entries
-        let result = q.drain_acked(&Some(log_id(1, 1, 20)));
+        let result = q.drain_acked(&Some(log_id(1, 1, 20))).map(|a| a.last_sending_time);
 
         // Should return the sending_time of the last entry (log_id=10)
         assert_eq!(result, Some(expected_time));
@@ -144,14 +251,14 @@ mod tests {
     #[test]
     fn test_drain_acked_with_none_log_id() {
         let q = InflightAppendQueue::<UTConfig>::new();
-        q.push(None);
-        q.push(Some(log_id(1, 1, 5)));
+        q.push(None, 0, 0);
+        q.push(Some(log_id(1, 1, 5)), 0, 0);
 
         // Read the expected sending_time before calling drain_acked
         let expected_time = q.queue.lock().unwrap()[0].sending_time;
 
         // matching=None should ack entries with last_log_id <= None (i.e., only None)
-        let result = q.drain_acked(&None);
+        let result = q.drain_acked(&None).map(|a| a.last_sending_time);
 
         // Should return the sending_time of entry with None log_id
         assert_eq!(result, Some(expected_time));
@@ -161,4 +268,51 @@ mod tests {
         assert_eq!(deque.len(), 1);
         assert_eq!(deque[0].last_log_id, Some(log_id(1, 1, 5)));
     }
+
+    #[test]
+    fn test_inflight_entries() {
+        let q = InflightAppendQueue::<UTConfig>::new();
+        q.push(Some(log_id(1, 1, 5)), 5, 0);
+        q.push(Some(log_id(1, 1, 10)), 5, 0);
+        q.push(Some(log_id(1, 1, 10)), 0, 0);
+        assert_eq!(q.inflight_entries(), 10);
+
+        q.drain_acked(&Some(log_id(1, 1, 7)));
+        assert_eq!(q.inflight_entries(), 5);
+
+        q.drain_acked(&Some(log_id(1, 1, 10)));
+        assert_eq!(q.inflight_entries(), 0);
+    }
+
+    #[test]
+    fn test_drain_acked_entries_and_bytes() {
+        let q = InflightAppendQueue::<UTConfig>::new();
+        q.push(Some(log_id(1, 1, 5)), 5, 50);
+        q.push(Some(log_id(1, 1, 10)), 5, 70);
+        q.push(Some(log_id(1, 1, 15)), 5, 90);
+
+        let first = q.queue.lock().unwrap()[0].sending_time;
+        let second = q.queue.lock().unwrap()[1].sending_time;
+
+        let acked = q.drain_acked(&Some(log_id(1, 1, 10))).unwrap();
+        assert_eq!(acked.first_sending_time, first);
+        assert_eq!(acked.last_sending_time, second);
+        assert_eq!(acked.entries, 10);
+        assert_eq!(acked.bytes, 120);
+    }
+
+    #[test]
+    fn test_inflight_requests() {
+        let q = InflightAppendQueue::<UTConfig>::new();
+        q.push(Some(log_id(1, 1, 5)), 5, 0);
+        q.push(Some(log_id(1, 1, 5)), 0, 0);
+        q.push(Some(log_id(1, 1, 10)), 5, 0);
+        assert_eq!(q.inflight_requests(), 3);
+
+        q.drain_acked(&Some(log_id(1, 1, 7)));
+        assert_eq!(q.inflight_requests(), 1);
+
+        q.drain_acked(&Some(log_id(1, 1, 10)));
+        assert_eq!(q.inflight_requests(), 0);
+    }
 }
@@ -2,10 +2,12 @@
 
 mod backoff_consumer;
 pub(crate) mod backoff_state;
+pub(crate) mod batch_sizer;
 pub(crate) mod event_watcher;
 pub(crate) mod inflight_append;
 pub(crate) mod inflight_append_queue;
 pub(crate) mod payload;
+pub(crate) mod rate_limiter;
 pub(crate) mod replicate;
 pub(crate) mod replication_context;
 pub(crate) mod replication_handle;
@@ -32,6 +34,7 @@ use response::ReplicationResult;
 use stream_state::StreamState;
 use tracing::Instrument;
 
+use crate::Instant;
 use crate::RaftNetworkFactory;
 use crate::RaftTypeConfig;
 use crate::async_runtime::Mutex;
@@ -39,8 +42,10 @@ use crate::async_runtime::watch::WatchReceiver;
 use crate::base::BoxStream;
 use crate::core::notification::Notification;
 use crate::display_ext::display_instant::DisplayInstantExt;
+use crate::entry::RaftEntry;
 use crate::errors::RPCError;
 use crate::errors::ReplicationClosed;
+use crate::errors::Unreachable;
 use crate::log_id_range::LogIdRange;
 use crate::network::NetBackoff;
 use crate::network::NetStreamAppend;
@@ -51,8 +56,10 @@ use crate::raft::StreamAppendError;
 use crate::raft::StreamAppendResult;
 use crate::raft_state::IOId;
 use crate::replication::backoff_state::BackoffState;
+use crate::replication::batch_sizer::BatchSizer;
 use crate::replication::event_watcher::EventWatcher;
 use crate::replication::inflight_append_queue::InflightAppendQueue;
+use crate::replication::rate_limiter::RateLimiter;
 use crate::replication::replication_context::ReplicationContext;
 use crate::replication::stream_context::StreamContext;
 use crate::storage::RaftLogStorage;
@@ -103,6 +110,14 @@ where
     /// See [issue #1723](https://github.com/databendlabs/openraft/issues/1723) for the
     /// invariant these two pieces must maintain together.
     backoff_state: BackoffState,
+
+    /// Adapts the number of entries per request, read by [`StreamState`].
+    ///
+    /// See [`Config::min_payload_entries`](crate::Config::min_payload_entries).
+    batch_sizer: BatchSizer,
+
+    /// When the last AppendEntries response is received, to measure how long the stream is busy.
+    last_acked_at: Option<InstantOf<C>>,
 }
 
 impl<C, N, LS> ReplicationCore<C, N, LS>
@@ -133,6 +148,8 @@ where
         );
 
         let backoff_state = BackoffState::new();
+        let batch_sizer = BatchSizer::new(&replication_context.config);
+        let rate_limiter = replication_context.config.max_replication_rate().and_then(RateLimiter::new);
 
         let this = Self {
             replication_context: replication_context.clone(),
@@ -144,16 +161,20 @@ where
                 inflight_id: None,
                 leader_committed: None,
                 backoff_consumer: backoff_state.consumer(),
+                payload_entries: batch_sizer.size_handle(),
+                rate_limiter,
             })),
             inflight_id: None,
             event_watcher,
             network: Some(network),
             replication_progress: progress,
             backoff_state,
+            batch_sizer,
+            last_acked_at: None,
             next_action: None,
         };
 
-        C::spawn(this.main().instrument(span))
+        C::spawn_io(this.main().instrument(span))
     }
 
     /// Creates a stream of AppendEntries requests from the given context.
@@ -164,10 +185,22 @@ where
 
     /// Generates the next AppendEntries request and records it in the inflight queue.
     ///
+    /// If [`Config::max_inflight_append_entries`] or [`Config::append_receive_window`] is set, it
+    /// waits until the follower has acknowledged enough requests or entries to free up the window.
+    ///
     /// Used as the unfold function for the request stream.
+    ///
+    /// [`Config::max_inflight_append_entries`]: crate::Config::max_inflight_append_entries
+    /// [`Config::append_receive_window`]: crate::Config::append_receive_window
     async fn next_append_request(
         stream_context: StreamContext<C, LS>,
     ) -> Option<(AppendEntriesRequest<C>, StreamContext<C, LS>)> {
+        let max_requests = stream_context.max_inflight_append_entries;
+        let window = stream_context.append_receive_window;
+        if max_requests.is_some() || window.is_some() {
+            stream_context.inflight_append_queue.wait_for_window(max_requests, window).await;
+        }
+
         let res = {
             let mut state = stream_context.stream_state.as_ref().lock().await;
             state.next_request().await
@@ -183,7 +216,8 @@ where
             }
         };
 
-        stream_context.inflight_append_queue.push(req.last_log_id());
+        let bytes = req.entries.iter().map(|e| e.approx_size()).sum();
+        stream_context.inflight_append_queue.push(req.last_log_id(), req.entries.len() as u64, bytes);
 
         Some((req, stream_context))
     }
@@ -260,13 +294,16 @@ where
             let stream_context = StreamContext {
                 stream_state: self.stream_state.clone(),
                 inflight_append_queue: inflight_queue.clone(),
+                max_inflight_append_entries: self.replication_context.config.max_inflight_append_entries,
+                append_receive_window: self.replication_context.config.append_receive_window,
                 fatal_error: fatal_error.clone(),
             };
 
             let req_strm = Self::new_request_stream(stream_context);
 
-            let rpc_timeout = Duration::from_millis(self.replication_context.config.heartbeat_interval);
-            let option = RPCOption::new(rpc_timeout);
+            let config = &self.replication_context.config;
+            let heartbeat_timeout = Duration::from_millis(config.heartbeat_interval);
+            let option = RPCOption::new(config.append_entries_timeout()).with_heartbeat_ttl(heartbeat_timeout);
 
             let resp_strm_res = network.stream_append(req_strm, option).await;
             // A custom streaming transport may poll the request stream while establishing
@@ -281,6 +318,9 @@ where
                 Ok(resp_strm) => resp_strm,
                 Err(rpc_err) => {
                     self.backoff_state.on_error(rpc_err.backoff_rank());
+                    if matches!(rpc_err, RPCError::Timeout(_)) {
+                        self.batch_sizer.on_overload();
+                    }
                     self.send_progress_error(rpc_err, "initiate-stream-replication").await;
 
                     continue;
@@ -327,6 +367,9 @@ where
             let append_res = match rpc_res {
                 Ok(stream_append_res) => stream_append_res,
                 Err(rpc_err) => {
+                    if matches!(rpc_err, RPCError::Timeout(_)) {
+                        self.batch_sizer.on_overload();
+                    }
                     self.send_progress_error(rpc_err, "stream-replication").await;
                     return Err("RPCError");
                 }
@@ -334,10 +377,32 @@ where
 
             match append_res {
                 Ok(matching) => {
-                    let last_acked_sending_time = inflight_queue.drain_acked(&matching);
+                    let acked = inflight_queue.drain_acked(&matching);
+
+                    if let Some(acked) = acked {
+                        let now = C::now();
+                        self.batch_sizer.on_ack(now.saturating_duration_since(acked.last_sending_time));
+
+                        if acked.entries > 0 {
+                            // The stream is idle between the previous response and sending these.
+                            let busy_since = match self.last_acked_at {
+                                Some(t) if t > acked.first_sending_time => t,
+                                _ => acked.first_sending_time,
+                            };
+                            self.replication_context.throughput.record_logs(
+                                &self.replication_context.target,
+                                acked.entries,
+                                acked.bytes,
+                                now.saturating_duration_since(busy_since),
+                            );
+                        }
+                        self.last_acked_at = Some(now);
 
-                    if let Some(last) = last_acked_sending_time {
-                        self.notify_heartbeat_progress(last).await;
+                        self.notify_heartbeat_progress(acked.last_sending_time).await;
+                    } else {
+                        // Every response acknowledges at least the request it answers, unless the
+                        // follower accepted only part of it.
+                        self.batch_sizer.on_overload();
                     }
 
                     self.replication_progress.remote_matched = matching.clone();
@@ -360,6 +425,17 @@ where
                                 .await
                                 .ok();
                         }
+                        StreamAppendError::Malformed(e) => {
+                            tracing::error!(
+                                "target {} rejected a malformed AppendEntries request: {}",
+                                self.replication_context.target,
+                                e
+                            );
+
+                            let rpc_err = RPCError::Unreachable(Unreachable::new(&e));
+                            self.backoff_state.observe::<(), C>(&Err(rpc_err.clone()));
+                            self.send_progress_error(rpc_err, "stream-replication").await;
+                        }
                     }
 
                     return Err("AppendError");
@@ -483,6 +559,8 @@ where
             committed_res = committed.fuse() => {
                 committed_res.map_err(|_e| ReplicationClosed::new("committed_rx closed"))?;
 
+                self.replication_context.coalesce_commit_notify(&self.event_watcher.committed_rx).await;
+
                 // Committed update: create an empty-range payload to sync commit index.
                 let committed = self.event_watcher.committed_rx.borrow_watched().clone();
                 self.replication_progress.local_committed = committed;
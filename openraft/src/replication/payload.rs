@@ -20,7 +20,13 @@ where C: RaftTypeConfig
     ///
     /// Used for streaming replication where the leader continuously sends new logs.
     /// The `prev` is updated as logs are acknowledged.
-    LogsSince { prev: Option<LogIdOf<C>> },
+    ///
+    /// `after_snapshot` is the last log id of a snapshot being sent at the same time, which these
+    /// logs follow.
+    LogsSince {
+        prev: Option<LogIdOf<C>>,
+        after_snapshot: Option<LogIdOf<C>>,
+    },
 }
 
 impl<C> fmt::Display for Payload<C>
@@ -31,7 +37,7 @@ where C: RaftTypeConfig
             Payload::LogIdRange { log_id_range } => {
                 write!(f, "LogIdRange{{{}}}", log_id_range)
             }
-            Payload::LogsSince { prev } => {
+            Payload::LogsSince { prev, .. } => {
                 write!(f, "LogsSince{{{}}}", prev.display(),)
             }
         }
@@ -44,7 +50,15 @@ where C: RaftTypeConfig
     pub(crate) fn update_matching(&mut self, matching: Option<LogIdOf<C>>) {
         match self {
             Payload::LogIdRange { log_id_range } => log_id_range.prev = matching,
-            Payload::LogsSince { prev } => *prev = matching,
+            Payload::LogsSince { prev, .. } => *prev = matching,
+        }
+    }
+
+    /// The last log id of the snapshot sent along with these logs, if any.
+    pub(crate) fn after_snapshot(&self) -> Option<LogIdOf<C>> {
+        match self {
+            Payload::LogIdRange { .. } => None,
+            Payload::LogsSince { after_snapshot, .. } => after_snapshot.clone(),
         }
     }
 
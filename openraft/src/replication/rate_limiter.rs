@@ -0,0 +1,84 @@
+//! Paces the bytes of log entries replicated to a follower.
+
+use std::time::Duration;
+
+use crate::RaftTypeConfig;
+use crate::type_config::alias::InstantOf;
+
+/// Paces replication to a target to a maximum number of bytes per second.
+///
+/// Catching up a lagging follower would otherwise send logs as fast as the network allows,
+/// saturating the leader's NIC and delaying the replication of client writes to the other
+/// followers. Each request is scheduled after the previous one has been given the time it takes
+/// at the maximum rate. A request without entries, e.g., a heartbeat, costs nothing.
+#[derive(Debug, Clone)]
+pub(crate) struct RateLimiter<C>
+where C: RaftTypeConfig
+{
+    /// Bytes per second.
+    rate: u64,
+
+    /// The earliest time the next request may be sent.
+    next_send_at: Option<InstantOf<C>>,
+}
+
+impl<C> RateLimiter<C>
+where C: RaftTypeConfig
+{
+    /// Create a limiter for `rate` bytes per second, `None` if `rate` is `0`.
+    pub(crate) fn new(rate: u64) -> Option<Self> {
+        if rate == 0 {
+            return None;
+        }
+
+        Some(Self {
+            rate,
+            next_send_at: None,
+        })
+    }
+
+    /// Schedule a request of `bytes` at `now`.
+    ///
+    /// Returns the time the request may be sent, or `None` if it may be sent at once.
+    pub(crate) fn schedule(&mut self, now: InstantOf<C>, bytes: u64) -> Option<InstantOf<C>> {
+        if bytes == 0 {
+            return None;
+        }
+
+        let start = match self.next_send_at {
+            Some(t) if t > now => t,
+            _ => now,
+        };
+
+        let cost = Duration::from_nanos((bytes as u128 * 1_000_000_000 / self.rate as u128) as u64);
+        self.next_send_at = Some(start + cost);
+
+        if start > now { Some(start) } else { None }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::engine::testing::UTConfig;
+    use crate::type_config::TypeConfigExt;
+
+    type RateLimiter = super::RateLimiter<UTConfig>;
+
+    #[test]
+    fn test_rate_limiter_schedule() {
+        assert!(RateLimiter::new(0).is_none());
+
+        let now = UTConfig::<()>::now();
+        let mut r = RateLimiter::new(1000).unwrap();
+
+        assert_eq!(None, r.schedule(now, 100), "the first request is sent at once");
+        assert_eq!(Some(now + Duration::from_millis(100)), r.schedule(now, 200));
+        assert_eq!(None, r.schedule(now, 0), "a heartbeat is not paced");
+        assert_eq!(Some(now + Duration::from_millis(300)), r.schedule(now, 100));
+
+        let later = now + Duration::from_secs(1);
+        assert_eq!(None, r.schedule(later, 100), "the rate is not reached");
+    }
+}
@@ -47,11 +47,12 @@ impl<C: RaftTypeConfig> fmt::Display for Replicate<C> {
                     log_id_range, self.inflight_id
                 )
             }
-            Payload::LogsSince { prev } => {
+            Payload::LogsSince { prev, after_snapshot } => {
                 write!(
                     f,
-                    "Replicate{{logs_since: {}, inflight_id: {}}}",
+                    "Replicate{{logs_since: {}, after_snapshot: {}, inflight_id: {}}}",
                     prev.display(),
+                    after_snapshot.display(),
                     self.inflight_id
                 )
             }
@@ -74,7 +75,22 @@ where C: RaftTypeConfig
     pub(crate) fn new_logs_since(prev: Option<LogIdOf<C>>, inflight_id: InflightId) -> Self {
         Self {
             inflight_id,
-            payload: Payload::LogsSince { prev },
+            payload: Payload::LogsSince {
+                prev,
+                after_snapshot: None,
+            },
+        }
+    }
+
+    /// Creates a request to replicate logs after the snapshot ending at `prev`, which is being
+    /// sent at the same time.
+    pub(crate) fn new_snapshot_tail(prev: Option<LogIdOf<C>>, inflight_id: InflightId) -> Self {
+        Self {
+            inflight_id,
+            payload: Payload::LogsSince {
+                after_snapshot: prev.clone(),
+                prev,
+            },
         }
     }
 }
@@ -1,12 +1,23 @@
 use std::fmt;
 use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
+use futures_util::FutureExt;
 
 use crate::Config;
 use crate::RaftTypeConfig;
+use crate::async_runtime::watch::WatchReceiver;
 use crate::core::SharedReplicateBatch;
 use crate::core::notification::Notification;
+use crate::core::replication_throughput::ReplicationThroughput;
+use crate::display_ext::display_instant::DisplayInstantExt;
 use crate::progress::stream_id::StreamId;
+use crate::type_config::TypeConfigExt;
 use crate::type_config::alias::CommittedVoteOf;
+use crate::type_config::alias::InstantOf;
+use crate::type_config::alias::LogIdOf;
 use crate::type_config::alias::MpscSenderOf;
 use crate::type_config::alias::WatchReceiverOf;
 
@@ -29,6 +40,13 @@ where C: RaftTypeConfig
     /// The leader this replication works for
     pub(crate) leader_vote: CommittedVoteOf<C>,
 
+    /// Whether the target is a witness, to which only log metadata is sent.
+    ///
+    /// It is shared with the `ReplicationHandle`, through which `RaftCore` updates it when a new
+    /// membership changes the role of a kept target.
+    /// See [`NodeRole::Witness`](crate::NodeRole::Witness).
+    pub(crate) witness: Arc<AtomicBool>,
+
     /// Identifies which session this replication belongs to.
     pub(crate) stream_id: StreamId,
 
@@ -46,6 +64,64 @@ where C: RaftTypeConfig
 
     /// Shared histogram for recording replication batch sizes.
     pub(crate) replicate_batch: SharedReplicateBatch,
+
+    /// Shared throughput accounting, recorded when the target acknowledges logs or a snapshot.
+    pub(crate) throughput: ReplicationThroughput<C>,
+
+    /// When the last commit-only request is sent, and the committed log id it carries.
+    ///
+    /// Shared by `ReplicationCore` and its request stream so that both coalesce commit
+    /// notifications with the same interval.
+    /// See [`Config::commit_notify_interval`](crate::Config::commit_notify_interval).
+    pub(crate) commit_notified: Arc<Mutex<(Option<InstantOf<C>>, Option<LogIdOf<C>>)>>,
+}
+
+impl<C> ReplicationContext<C>
+where C: RaftTypeConfig
+{
+    /// Whether the target is currently a witness.
+    pub(crate) fn is_witness(&self) -> bool {
+        self.witness.load(Ordering::Relaxed)
+    }
+
+    /// Waits until the next commit-only request is allowed by
+    /// [`Config::commit_notify_interval`](crate::Config::commit_notify_interval), so that the
+    /// commit advances in between are sent in one request.
+    ///
+    /// Returns at once if commit notifications are not coalesced, or if the current committed
+    /// log id is already sent in a commit-only request.
+    pub(crate) async fn coalesce_commit_notify(&mut self, committed_rx: &WatchReceiverOf<C, Option<LogIdOf<C>>>) {
+        let Some(interval) = self.config.commit_notify_interval() else {
+            return;
+        };
+
+        let deadline = {
+            let committed = committed_rx.borrow_watched().clone();
+            let notified = self.commit_notified.lock().unwrap();
+            if committed <= notified.1 {
+                return;
+            }
+            notified.0.map(|at| at + interval)
+        };
+
+        if let Some(deadline) = deadline
+            && C::now() < deadline
+        {
+            tracing::debug!("coalesce commit notification until: {}", deadline.display());
+
+            let sleep = C::sleep_until(deadline);
+            let cancel = self.cancel_rx.changed();
+
+            futures_util::select! {
+                _ = sleep.fuse() => {}
+                cancel_res = cancel.fuse() => {
+                    tracing::info!("Replication is canceled, res: {:?}, when:(coalesce_commit_notify)", cancel_res);
+                }
+            }
+        }
+
+        *self.commit_notified.lock().unwrap() = (Some(C::now()), committed_rx.borrow_watched().clone());
+    }
 }
 
 impl<C> fmt::Display for ReplicationContext<C>
@@ -1,3 +1,6 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
 use crate::RaftTypeConfig;
 use crate::errors::ReplicationClosed;
 use crate::progress::stream_id::StreamId;
@@ -19,6 +22,9 @@ where C: RaftTypeConfig
     /// Sender for the cancellation signal; dropping this stops replication.
     pub(crate) cancel_tx: WatchSenderOf<C, ()>,
 
+    /// Whether the target is a witness, shared with the replication tasks of this stream.
+    pub(crate) witness: Arc<AtomicBool>,
+
     /// The spawn handle of the `ReplicationCore` task.
     pub(crate) join_handle: Option<JoinHandleOf<C, Result<(), ReplicationClosed>>>,
 
@@ -33,9 +39,11 @@ where C: RaftTypeConfig
         stream_id: StreamId,
         replicate_tx: WatchSenderOf<C, Replicate<C>>,
         cancel_tx: WatchSenderOf<C, ()>,
+        witness: Arc<AtomicBool>,
     ) -> Self {
         Self {
             stream_id,
+            witness,
             join_handle: None,
             replicate_tx,
             snapshot_transmit_handle: None,
@@ -5,6 +5,7 @@ use futures_util::FutureExt;
 
 use crate::RaftNetworkFactory;
 use crate::RaftTypeConfig;
+use crate::SnapshotId;
 use crate::StorageError;
 use crate::async_runtime::MpscSender;
 use crate::async_runtime::watch::WatchReceiver;
@@ -19,12 +20,14 @@ use crate::network::NetBackoff;
 use crate::network::NetSnapshot;
 use crate::network::RPCOption;
 use crate::progress::inflight_id::InflightId;
+use crate::raft::SeedSnapshotRequest;
 use crate::replication::Progress;
 use crate::replication::replication_context::ReplicationContext;
 use crate::replication::response::ReplicationResult;
 use crate::replication::snapshot_transmitter_handle::SnapshotTransmitterHandle;
 use crate::type_config::TypeConfigExt;
 use crate::type_config::alias::InstantOf;
+use crate::type_config::alias::SnapshotMetaOf;
 use crate::type_config::alias::SnapshotOf;
 use crate::type_config::alias::VoteOf;
 use crate::type_config::alias::WatchSenderOf;
@@ -55,6 +58,26 @@ where
 
     /// The handle to get a snapshot directly from the state machine.
     snapshot_reader: SnapshotReader<C, SM>,
+
+    /// The snapshot the target has installed from this leader, which the snapshot to send can be
+    /// a delta relative to.
+    base: Option<SnapshotId>,
+
+    /// The request asking the target to fetch the snapshot from a seed node instead.
+    ///
+    /// It is tried once; the leader sends its own snapshot if seeding fails.
+    seed: Option<SeedSnapshotRequest<C>>,
+
+    /// Whether to send the locator of the snapshot, if the state machine stores it out of band,
+    /// instead of the snapshot data.
+    ///
+    /// It is cleared once sending the locator fails, and the snapshot data is sent instead.
+    use_locator: bool,
+
+    /// The metadata of the current snapshot, which is all that is sent to a witness.
+    ///
+    /// See [`NodeRole::Witness`](crate::NodeRole::Witness).
+    snapshot_meta: SnapshotMetaOf<C>,
 }
 
 impl<C, N, SM: 'static> SnapshotTransmitter<C, N, SM>
@@ -66,6 +89,9 @@ where
         replication_context: ReplicationContext<C>,
         network: N::Network,
         snapshot_reader: SnapshotReader<C, SM>,
+        base: Option<SnapshotId>,
+        seed: Option<SeedSnapshotRequest<C>>,
+        snapshot_meta: SnapshotMetaOf<C>,
         inflight_id: InflightId,
         cancel_tx: WatchSenderOf<C, ()>,
     ) -> SnapshotTransmitterHandle<C> {
@@ -75,11 +101,15 @@ where
             network,
             backoff: None,
             snapshot_reader,
+            base,
+            seed,
+            use_locator: true,
+            snapshot_meta,
         };
 
         // TODO: this function should just return join_handle and let the caller build
         //       SnapshotTransmitterHandle
-        let join_handle = C::spawn(snapshot_transmit.stream_snapshot());
+        let join_handle = C::spawn_io(snapshot_transmit.stream_snapshot_and_notify());
 
         SnapshotTransmitterHandle {
             _join_handle: join_handle,
@@ -87,8 +117,30 @@ where
         }
     }
 
+    /// Stream the snapshot, then notify `RaftCore` that this transfer has quit.
+    async fn stream_snapshot_and_notify(self) {
+        let tx_notify = self.replication_context.tx_notify.clone();
+        let target = self.replication_context.target.clone();
+        let inflight_id = self.inflight_id;
+        let leader_vote = self.replication_context.leader_vote.clone();
+
+        let installed = self.stream_snapshot().await;
+
+        tx_notify
+            .send(Notification::SnapshotTransmitDone {
+                target,
+                inflight_id,
+                leader_vote,
+                installed,
+            })
+            .await
+            .ok();
+    }
+
+    /// Send the snapshot until it is installed by the target, and return its id, or `None` if the
+    /// transfer is given up or the target installed a snapshot from a seed node.
     #[tracing::instrument(level = "info", skip_all)]
-    async fn stream_snapshot(mut self) {
+    async fn stream_snapshot(mut self) -> Option<SnapshotId> {
         tracing::info!("{}", func_name!());
 
         let mut ith: i32 = -1;
@@ -99,8 +151,8 @@ where
 
             let error = match res {
                 Err(error) => error,
-                Ok(_) => {
-                    return;
+                Ok(installed) => {
+                    return installed;
                 }
             };
 
@@ -109,7 +161,7 @@ where
             match error {
                 ReplicationError::Closed(closed) => {
                     tracing::info!("snapshot transmission canceled: {}", closed);
-                    return;
+                    return None;
                 }
                 ReplicationError::HigherVote(h) => {
                     tracing::info!("snapshot transmission aborted, higher vote seen: {}", h);
@@ -123,7 +175,7 @@ where
                         .await
                         .ok();
 
-                    return;
+                    return None;
                 }
                 ReplicationError::StorageError(error) => {
                     tracing::error!(
@@ -132,9 +184,18 @@ where
                         error
                     );
                     self.replication_context.tx_notify.send(Notification::StorageError { error }).await.ok();
-                    return;
+                    return None;
                 }
                 ReplicationError::RPCError(err) => {
+                    // The target may have failed to install the delta, e.g., it lost the base
+                    // snapshot. Retry with the full snapshot.
+                    if matches!(err, RPCError::RemoteError(_)) && self.base.take().is_some() {
+                        tracing::warn!(
+                            "delta snapshot transmission failed: {}; retry with the full snapshot",
+                            err
+                        );
+                    }
+
                     match &err {
                         RPCError::Unreachable(_unreachable) => {
                             // If there is an [`Unreachable`] error, we will backoff for a
@@ -168,7 +229,7 @@ where
                             }
                             _ = recv.fuse() => {
                                 tracing::info!("snapshot transmission canceled by RaftCore");
-                                return;
+                                return None;
                             }
                         }
                     }
@@ -177,8 +238,26 @@ where
         }
     }
 
-    async fn read_and_send_snapshot(&mut self, ith: i32) -> Result<(), ReplicationError<C>> {
-        let snapshot = self.snapshot_reader.get_snapshot().await.map_err(|reason| {
+    async fn read_and_send_snapshot(&mut self, ith: i32) -> Result<Option<SnapshotId>, ReplicationError<C>> {
+        // A witness keeps no state machine data: neither the snapshot nor a seed is of use to it.
+        if self.replication_context.is_witness() {
+            let snapshot_id = self.send_snapshot_meta().await?;
+            return Ok(Some(snapshot_id));
+        }
+
+        if let Some(req) = self.seed.take() {
+            if self.seed_snapshot(req).await? {
+                return Ok(None);
+            }
+        }
+
+        if self.use_locator
+            && let Some(snapshot_id) = self.send_snapshot_locator().await?
+        {
+            return Ok(Some(snapshot_id));
+        }
+
+        let snapshot = self.snapshot_reader.get_snapshot(self.base.clone()).await.map_err(|reason| {
             tracing::warn!("failed to get snapshot from state machine: {}", reason);
             ReplicationClosed::new(reason)
         })?;
@@ -199,11 +278,128 @@ where
 
         let mut option = RPCOption::new(self.replication_context.config.install_snapshot_timeout());
         option.snapshot_chunk_size = Some(self.replication_context.config.snapshot_max_chunk_size as usize);
+        option.max_bytes_rate = self.replication_context.config.max_replication_rate();
+
+        let snapshot_id = self.send_snapshot(snapshot, option).await?;
+        Ok(Some(snapshot_id))
+    }
+
+    /// Ask the target to fetch and install the snapshot from a seed node.
+    ///
+    /// Returns `true` if it is installed, or `false` if the leader has to send its own snapshot.
+    async fn seed_snapshot(&mut self, req: SeedSnapshotRequest<C>) -> Result<bool, ReplicationError<C>> {
+        let sender_vote = req.vote.clone();
+        let seed_id = req.seed_id.clone();
+        let option = RPCOption::new(self.replication_context.config.install_snapshot_timeout());
+
+        let start_time = C::now();
+
+        let resp = match self.network.seed_snapshot(req, option).await {
+            Ok(resp) => resp,
+            Err(err) => {
+                tracing::warn!("failed to seed snapshot from {}: {}; send it from leader", seed_id, err);
+                return Ok(false);
+            }
+        };
+
+        tracing::info!("finished seeding snapshot from {}, resp: {}", seed_id, resp);
+
+        if resp.vote.as_ref_vote() > sender_vote.as_ref_vote() {
+            return Err(ReplicationError::HigherVote(HigherVote {
+                higher: resp.vote,
+                sender_vote,
+            }));
+        }
+
+        let Some(meta) = resp.meta else {
+            tracing::warn!("seed {} has no snapshot to install; send it from leader", seed_id);
+            return Ok(false);
+        };
+
+        self.notify_heartbeat_progress(start_time).await;
+        self.notify_progress(ReplicationResult(Ok(meta.last_log_id))).await;
+        Ok(true)
+    }
 
-        self.send_snapshot(snapshot, option).await
+    /// Send the locator of the snapshot, if the state machine stores it out of band, for the
+    /// target to fetch the snapshot data by itself.
+    ///
+    /// Returns the id of the installed snapshot, or `None` if the snapshot data has to be sent.
+    async fn send_snapshot_locator(&mut self) -> Result<Option<SnapshotId>, ReplicationError<C>> {
+        let locator = self.snapshot_reader.get_snapshot_locator().await.map_err(|reason| {
+            tracing::warn!("failed to get snapshot locator from state machine: {}", reason);
+            ReplicationClosed::new(reason)
+        })?;
+
+        let Some(locator) = locator else {
+            self.use_locator = false;
+            return Ok(None);
+        };
+
+        let meta = locator.meta.clone();
+        let sender_vote: VoteOf<C> = self.replication_context.leader_vote.clone().into_vote();
+        let option = RPCOption::new(self.replication_context.config.install_snapshot_timeout());
+
+        let start_time = C::now();
+
+        let resp = match self.network.snapshot_locator(sender_vote.clone(), locator, option).await {
+            Ok(resp) => resp,
+            Err(err) => {
+                tracing::warn!("failed to send snapshot locator: {}; send the snapshot data", err);
+                self.use_locator = false;
+                return Ok(None);
+            }
+        };
+
+        tracing::info!("finished sending snapshot locator, resp: {}", resp);
+
+        if resp.vote.as_ref_vote() > sender_vote.as_ref_vote() {
+            return Err(ReplicationError::HigherVote(HigherVote {
+                higher: resp.vote,
+                sender_vote,
+            }));
+        }
+
+        self.notify_heartbeat_progress(start_time).await;
+        self.notify_progress(ReplicationResult(Ok(meta.last_log_id))).await;
+        Ok(Some(meta.snapshot_id))
+    }
+
+    /// Send only the metadata of the current snapshot to a witness.
+    async fn send_snapshot_meta(&mut self) -> Result<SnapshotId, ReplicationError<C>> {
+        let meta = self.snapshot_meta.clone();
+
+        if meta.last_log_id.is_none() {
+            let sto_err = StorageError::read_snapshot(None, C::err_from_string("snapshot not found"));
+            return Err(sto_err.into());
+        }
+
+        let sender_vote: VoteOf<C> = self.replication_context.leader_vote.clone().into_vote();
+        let option = RPCOption::new(self.replication_context.config.install_snapshot_timeout());
+
+        let start_time = C::now();
+
+        let resp = self.network.snapshot_meta(sender_vote.clone(), meta.clone(), option).await?;
+
+        tracing::info!("finished sending snapshot meta, resp: {}", resp);
+
+        if resp.vote.as_ref_vote() > sender_vote.as_ref_vote() {
+            return Err(ReplicationError::HigherVote(HigherVote {
+                higher: resp.vote,
+                sender_vote,
+            }));
+        }
+
+        self.notify_heartbeat_progress(start_time).await;
+        self.notify_progress(ReplicationResult(Ok(meta.last_log_id))).await;
+        Ok(meta.snapshot_id)
     }
 
-    async fn send_snapshot(&mut self, snapshot: SnapshotOf<C>, option: RPCOption) -> Result<(), ReplicationError<C>> {
+    async fn send_snapshot(
+        &mut self,
+        snapshot: SnapshotOf<C>,
+        option: RPCOption,
+    ) -> Result<SnapshotId, ReplicationError<C>> {
         let meta = snapshot.meta.clone();
 
         let mut c = self.replication_context.cancel_rx.clone();
@@ -228,9 +424,11 @@ where
             }));
         }
 
+        self.replication_context.throughput.record_snapshot(C::now().saturating_duration_since(start_time));
+
         self.notify_heartbeat_progress(start_time).await;
         self.notify_progress(ReplicationResult(Ok(meta.last_log_id))).await;
-        Ok(())
+        Ok(meta.snapshot_id)
     }
 
     async fn notify_heartbeat_progress(&mut self, sending_time: InstantOf<C>) {
@@ -20,9 +20,15 @@ where
     /// Shared state for generating the next request.
     pub(crate) stream_state: Arc<MutexOf<C, StreamState<C, LS>>>,
 
-    /// Tracks in-flight requests for RTT measurement.
+    /// Tracks in-flight requests for RTT measurement and the in-flight window.
     pub(crate) inflight_append_queue: InflightAppendQueue<C>,
 
+    /// The maximum number of requests in flight, copied from the config.
+    pub(crate) max_inflight_append_entries: Option<u64>,
+
+    /// The maximum number of log entries in flight, copied from the config.
+    pub(crate) append_receive_window: Option<u64>,
+
     /// Fatal error found while generating the request stream.
     pub(crate) fatal_error: Arc<MutexOf<C, Option<ReplicationClosed>>>,
 }
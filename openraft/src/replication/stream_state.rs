@@ -1,8 +1,13 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 use std::time::Duration;
 
 use display_more::DisplayOptionExt;
 use futures_util::FutureExt;
 
+use crate::ConfigDigest;
+use crate::Instant;
 use crate::LogIdOptionExt;
 use crate::RaftLogReader;
 use crate::RaftTypeConfig;
@@ -11,6 +16,7 @@ use crate::async_runtime::MpscSender;
 use crate::async_runtime::watch::WatchReceiver;
 use crate::core::notification::Notification;
 use crate::entry::RaftEntry;
+use crate::entry::RaftPayload;
 use crate::entry::raft_entry_ext::RaftEntryExt;
 use crate::errors::ReplicationClosed;
 use crate::errors::StorageIOResult;
@@ -21,6 +27,7 @@ use crate::raft_state::IOId;
 use crate::replication::backoff_consumer::BackoffConsumer;
 use crate::replication::event_watcher::EventWatcher;
 use crate::replication::payload::Payload;
+use crate::replication::rate_limiter::RateLimiter;
 use crate::replication::replication_context::ReplicationContext;
 use crate::storage::RaftLogStorage;
 use crate::type_config::TypeConfigExt;
@@ -60,6 +67,16 @@ where
     /// The consumer can only query the next delay; only `ReplicationCore` (via its
     /// owned `BackoffState`) enables or clears the backoff.
     pub(crate) backoff_consumer: BackoffConsumer,
+
+    /// The maximum number of entries per request, adapted by `ReplicationCore`.
+    ///
+    /// See [`Config::min_payload_entries`](crate::Config::min_payload_entries).
+    pub(crate) payload_entries: Arc<AtomicU64>,
+
+    /// Paces the requests by their bytes, `None` if not limited.
+    ///
+    /// See [`Config::max_replication_rate`](crate::Config::max_replication_rate).
+    pub(crate) rate_limiter: Option<RateLimiter<C>>,
 }
 
 impl<C, LS> StreamState<C, LS>
@@ -104,10 +121,19 @@ where
 
         self.update_log_id_range(sending_range.last);
 
+        let entries = if self.replication_context.is_witness() {
+            entries.into_iter().map(metadata_only::<C>).collect()
+        } else {
+            entries
+        };
+
         let payload: AppendEntriesRequest<C> = AppendEntriesRequest {
             vote: self.replication_context.leader_vote.clone().into_vote(),
             prev_log_id: sending_range.prev.clone(),
             leader_commit: self.event_watcher.committed_rx.borrow_watched().clone(),
+            backup_barrier: self.event_watcher.backup_barrier_rx.borrow_watched().clone(),
+            after_snapshot: self.payload.as_ref().and_then(|p| p.after_snapshot()),
+            config_digest: Some(ConfigDigest::new::<C>(&self.replication_context.config)),
             entries,
         };
 
@@ -127,6 +153,7 @@ where
         tracing::debug!("next_request: AppendEntries: {}", payload);
 
         self.backoff_if_enabled().await;
+        self.pace_if_limited(&payload.entries).await;
 
         Ok(Some(payload))
     }
@@ -139,7 +166,7 @@ where
 
         let prev = match payload {
             Payload::LogIdRange { log_id_range } => return Some(log_id_range.clone()),
-            Payload::LogsSince { prev } => prev.clone(),
+            Payload::LogsSince { prev, .. } => prev.clone(),
         };
 
         // pipeline mode:
@@ -156,9 +183,13 @@ where
                 committed.display()
             );
 
-            if last_log_id > prev || committed > self.leader_committed {
+            if last_log_id > prev {
                 self.leader_committed = committed;
                 return Some(non_reversed_log_id_range(prev, last_log_id));
+            } else if committed > self.leader_committed {
+                self.replication_context.coalesce_commit_notify(&self.event_watcher.committed_rx).await;
+                self.leader_committed = self.event_watcher.committed_rx.borrow_watched().clone();
+                return Some(non_reversed_log_id_range(prev, last_log_id));
             } else {
                 let data_change = self.event_watcher.replicate_rx.changed();
                 let io_change = self.event_watcher.io_submitted_rx.changed();
@@ -180,6 +211,7 @@ where
                     _committed_change = committed_change.fuse() => {
                         tracing::debug!("committed_rx changed");
                         // A notification may force an RPC even if no new readable logs are available.
+                        self.replication_context.coalesce_commit_notify(&self.event_watcher.committed_rx).await;
                         self.leader_committed = self.event_watcher.committed_rx.borrow_watched().clone();
                         return Some(non_reversed_log_id_range(prev, last_log_id));
                     }
@@ -198,17 +230,40 @@ where
             return;
         };
 
+        tracing::debug!("backoff timeout: {:?}", sleep_duration);
+
+        self.sleep_unless_canceled(sleep_duration, "backoff_if_enabled").await;
+    }
+
+    /// Waits until `entries` may be sent without exceeding the replication rate, or returns
+    /// immediately if the rate is not limited.
+    async fn pace_if_limited(&mut self, entries: &[EntryOf<C>]) {
+        let Some(rate_limiter) = self.rate_limiter.as_mut() else {
+            return;
+        };
+
+        let bytes = entries.iter().map(|ent| ent.approx_size()).sum();
+        let now = C::now();
+        let Some(send_at) = rate_limiter.schedule(now, bytes) else {
+            return;
+        };
+
+        let sleep_duration = send_at.saturating_duration_since(now);
+        tracing::debug!("pace {} bytes by max_replication_rate: {:?}", bytes, sleep_duration);
+
+        self.sleep_unless_canceled(sleep_duration, "pace_if_limited").await;
+    }
+
+    async fn sleep_unless_canceled(&mut self, sleep_duration: Duration, when: &str) {
         let sleep = C::sleep(sleep_duration);
         let cancel = self.replication_context.cancel_rx.changed();
 
-        tracing::debug!("backoff timeout: {:?}", sleep_duration);
-
         futures_util::select! {
             _ = sleep.fuse() => {
-                tracing::debug!("backoff timeout");
+                tracing::debug!("{} timeout", when);
             }
             cancel_res = cancel.fuse() => {
-                tracing::info!("Replication Stream is canceled, res: {:?}, when:(backoff_if_enabled:wait-for-changed)", cancel_res);
+                tracing::info!("Replication Stream is canceled, res: {:?}, when:({}:wait-for-changed)", cancel_res, when);
             }
         }
     }
@@ -259,7 +314,7 @@ where
             let r = LogIdRange::new(rng.prev.clone(), rng.prev.clone());
             Ok((vec![], r))
         } else {
-            let max_entries = self.replication_context.config.max_payload_entries;
+            let max_entries = self.payload_entries.load(Ordering::Relaxed);
             let end = std::cmp::min(end, start + max_entries);
 
             // limited_get_log_entries will return logs smaller than the range [start, end).
@@ -283,6 +338,11 @@ where
                 return Ok((vec![], r));
             }
 
+            let logs = match self.replication_context.config.max_payload_bytes {
+                Some(max_bytes) => limit_payload_bytes::<C>(logs, max_bytes),
+                None => logs,
+            };
+
             let first = logs.first().map(|ent| ent.ref_log_id()).unwrap();
             let last = logs.last().map(|ent| ent.log_id()).unwrap();
 
@@ -302,6 +362,43 @@ where
     }
 }
 
+/// Keep the leading entries whose total [`RaftEntry::approx_size`] is within `max_bytes`, and at
+/// least one entry.
+fn limit_payload_bytes<C>(mut entries: Vec<EntryOf<C>>, max_bytes: u64) -> Vec<EntryOf<C>>
+where C: RaftTypeConfig {
+    let mut bytes = 0;
+    let mut n = 0;
+    for ent in entries.iter() {
+        bytes += ent.approx_size();
+        if n > 0 && bytes > max_bytes {
+            break;
+        }
+        n += 1;
+    }
+
+    entries.truncate(n);
+    entries
+}
+
+/// Strip the payload of an entry sent to a witness.
+///
+/// A membership entry is kept as is, since a witness votes with it. Any other entry is replaced
+/// with a blank entry of the same log id and timestamp.
+///
+/// The chain hash is removed, since the blank entries do not follow it: a witness chains its log
+/// on its own.
+fn metadata_only<C>(mut entry: EntryOf<C>) -> EntryOf<C>
+where C: RaftTypeConfig {
+    if entry.get_membership().is_some() {
+        entry.set_chain_hash(None);
+        return entry;
+    }
+
+    let mut blank = EntryOf::<C>::new_blank(entry.log_id());
+    blank.set_timestamp(entry.timestamp());
+    blank
+}
+
 fn non_reversed_log_id_range<C>(prev: Option<LogIdOf<C>>, last: Option<LogIdOf<C>>) -> LogIdRange<C>
 where C: RaftTypeConfig {
     // `prev` is delivered through ReplicationCore's replication command channel, while `last` is
@@ -312,9 +409,30 @@ where C: RaftTypeConfig {
 
 #[cfg(test)]
 mod tests {
+    use super::limit_payload_bytes;
     use super::non_reversed_log_id_range;
     use crate::engine::testing::UTConfig;
     use crate::engine::testing::log_id;
+    use crate::entry::RaftEntry;
+    use crate::testing::blank_ent;
+
+    #[test]
+    fn test_limit_payload_bytes() {
+        let entries = (1..=5).map(|i| blank_ent::<UTConfig>(1, 1, i)).collect::<Vec<_>>();
+        let size = entries[0].approx_size();
+
+        let got = limit_payload_bytes::<UTConfig>(entries.clone(), size * 2);
+        assert_eq!(entries[..2], got);
+
+        let got = limit_payload_bytes::<UTConfig>(entries.clone(), size * 2 + 1);
+        assert_eq!(entries[..2], got);
+
+        let got = limit_payload_bytes::<UTConfig>(entries.clone(), 1);
+        assert_eq!(entries[..1], got, "at least one entry");
+
+        let got = limit_payload_bytes::<UTConfig>(entries.clone(), u64::MAX);
+        assert_eq!(entries, got);
+    }
 
     #[test]
     fn test_non_reversed_log_id_range() {
@@ -0,0 +1,71 @@
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::io;
+use std::ops::RangeBounds;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use openraft_macros::since;
+
+use crate::OptionalSend;
+use crate::RaftLogReader;
+use crate::RaftTypeConfig;
+use crate::entry::RaftEntry;
+use crate::type_config::alias::VoteOf;
+
+/// The [`RaftLogReader`] of a [`BufferedLogStorage`](super::BufferedLogStorage).
+///
+/// It reads the entries that are still in the in-memory tier from memory, and the others from the
+/// reader of the inner storage.
+#[since(version = "0.10.0")]
+pub struct BufferedLogReader<C, R>
+where
+    C: RaftTypeConfig,
+    R: RaftLogReader<C>,
+{
+    tier: Arc<Mutex<BTreeMap<u64, C::Entry>>>,
+    inner: R,
+}
+
+impl<C, R> BufferedLogReader<C, R>
+where
+    C: RaftTypeConfig,
+    R: RaftLogReader<C>,
+{
+    pub(crate) fn new(tier: Arc<Mutex<BTreeMap<u64, C::Entry>>>, inner: R) -> Self {
+        Self { tier, inner }
+    }
+}
+
+impl<C, R> RaftLogReader<C> for BufferedLogReader<C, R>
+where
+    C: RaftTypeConfig,
+    C::Entry: Clone,
+    R: RaftLogReader<C>,
+{
+    async fn try_get_log_entries<RB: RangeBounds<u64> + Clone + Debug + OptionalSend>(
+        &mut self,
+        range: RB,
+    ) -> Result<Vec<C::Entry>, io::Error> {
+        // Read the tier first: an entry leaves the tier only after it is submitted to the inner
+        // storage, so every entry before the first buffered one is readable from the inner reader.
+        let buffered: Vec<C::Entry> = {
+            let tier = self.tier.lock().unwrap();
+            tier.iter().filter(|(index, _)| range.contains(*index)).map(|(_, ent)| ent.clone()).collect()
+        };
+
+        let mut entries = self.inner.try_get_log_entries(range).await?;
+
+        if let Some(first) = buffered.first() {
+            let first_index = first.index();
+            entries.retain(|ent| ent.index() < first_index);
+            entries.extend(buffered);
+        }
+
+        Ok(entries)
+    }
+
+    async fn read_vote(&mut self) -> Result<Option<VoteOf<C>>, io::Error> {
+        self.inner.read_vote().await
+    }
+}
@@ -0,0 +1,225 @@
+use std::collections::BTreeMap;
+use std::io;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use openraft_macros::since;
+
+use crate::OptionalSend;
+use crate::RaftTypeConfig;
+use crate::async_runtime::MpscReceiver;
+use crate::async_runtime::MpscSender;
+use crate::async_runtime::Mutex as AsyncMutex;
+use crate::async_runtime::OneshotSender;
+use crate::entry::RaftEntry;
+use crate::storage::IOFlushed;
+use crate::storage::LogState;
+use crate::storage::RaftLogStorage;
+use crate::storage::buffered::BufferedLogReader;
+use crate::storage::buffered::spill::PendingFlush;
+use crate::storage::buffered::spill::Spill;
+use crate::type_config::TypeConfigExt;
+use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::MpscReceiverOf;
+use crate::type_config::alias::MpscSenderOf;
+use crate::type_config::alias::MutexOf;
+use crate::type_config::alias::VoteOf;
+
+/// A [`RaftLogStorage`] that keeps newly appended entries in a bounded in-memory tier and spills
+/// them to an inner storage asynchronously.
+///
+/// [`append()`](RaftLogStorage::append) returns as soon as the entries are in memory, where
+/// replication and the state machine can already read them. A background task submits them to
+/// the inner storage in order, and the callback is called only when the **inner storage** has
+/// persisted them. A log entry is therefore still committed only when a quorum has durably
+/// stored it: what is traded is the latency of submitting an append to the inner storage, not
+/// durability.
+///
+/// This is an opt-in write path for workloads whose inner storage is slow to accept an append,
+/// e.g., one that writes synchronously in `append()`. It holds up to `capacity` entries: once the
+/// tier is full, `append()` waits for the background task to spill it.
+///
+/// Saving the vote or the committed log id, truncating and purging wait for all the buffered
+/// entries to be submitted first, so that the inner storage sees every write in the order
+/// Openraft issued it.
+///
+/// It must be created within the async runtime, because it spawns the background tasks. They
+/// quit when the `BufferedLogStorage` is dropped.
+///
+/// ```ignore
+/// let log_store = BufferedLogStorage::new(MyLogStore::open(path).await?, 4096);
+/// let raft = Raft::new(id, config, network, log_store, state_machine).await?;
+/// ```
+#[since(version = "0.10.0")]
+pub struct BufferedLogStorage<C, S>
+where
+    C: RaftTypeConfig,
+    S: RaftLogStorage<C>,
+{
+    inner: Arc<MutexOf<C, S>>,
+
+    /// Entries accepted by `append()` but not yet submitted to `inner`, keyed by log index.
+    tier: Arc<Mutex<BTreeMap<u64, C::Entry>>>,
+
+    /// The number of entries the tier holds before `append()` waits for it to be spilled.
+    capacity: usize,
+
+    tx_spill: MpscSenderOf<C, Spill<C>>,
+}
+
+impl<C, S> BufferedLogStorage<C, S>
+where
+    C: RaftTypeConfig,
+    S: RaftLogStorage<C>,
+{
+    /// Wrap `inner` with an in-memory tier holding up to `capacity` entries.
+    #[since(version = "0.10.0")]
+    pub fn new(inner: S, capacity: usize) -> Self {
+        let inner = Arc::new(C::mutex(inner));
+        let tier = Arc::new(Mutex::new(BTreeMap::new()));
+
+        let (tx_spill, rx_spill) = C::mpsc(1024);
+        let (tx_flush, rx_flush) = C::mpsc(1024);
+
+        let _spill = C::spawn(Self::spill_loop(inner.clone(), tier.clone(), rx_spill, tx_flush));
+        let _flush = C::spawn(Self::flush_loop(rx_flush));
+
+        Self {
+            inner,
+            tier,
+            capacity,
+            tx_spill,
+        }
+    }
+
+    /// Submit the buffered entries to the inner storage, in the order they are appended.
+    async fn spill_loop(
+        inner: Arc<MutexOf<C, S>>,
+        tier: Arc<Mutex<BTreeMap<u64, C::Entry>>>,
+        mut rx_spill: MpscReceiverOf<C, Spill<C>>,
+        tx_flush: MpscSenderOf<C, PendingFlush<C>>,
+    ) {
+        while let Some(spill) = rx_spill.recv().await {
+            match spill {
+                Spill::Append { entries, callback } => {
+                    let last_index = entries.last().map(|ent| ent.index());
+
+                    let (tx, rx) = C::oneshot();
+                    let res = {
+                        let mut inner = inner.lock().await;
+                        inner.append(entries, IOFlushed::signal(tx)).await
+                    };
+
+                    if let Err(e) = res {
+                        tracing::error!("BufferedLogStorage: failed to spill entries: {}", e);
+                        callback.io_completed(Err(e));
+                        return;
+                    }
+
+                    // The inner storage serves these entries from now on.
+                    if let Some(last_index) = last_index {
+                        let mut tier = tier.lock().unwrap();
+                        *tier = tier.split_off(&(last_index + 1));
+                    }
+
+                    if tx_flush.send(PendingFlush { rx, callback }).await.is_err() {
+                        return;
+                    }
+                }
+                Spill::Barrier { tx } => {
+                    tx.send(()).ok();
+                }
+            }
+        }
+    }
+
+    /// Forward the flush results of the inner storage to Openraft, in the order they are
+    /// submitted.
+    async fn flush_loop(mut rx_flush: MpscReceiverOf<C, PendingFlush<C>>) {
+        while let Some(pending) = rx_flush.recv().await {
+            let res = match pending.rx.await {
+                Ok(res) => res,
+                Err(_) => Err(io::Error::other(
+                    "BufferedLogStorage: inner storage dropped the flush callback",
+                )),
+            };
+            pending.callback.io_completed(res);
+        }
+    }
+
+    /// Wait until every entry appended so far is submitted to the inner storage.
+    async fn barrier(&self) -> Result<(), io::Error> {
+        let (tx, rx) = C::oneshot();
+        self.tx_spill.send(Spill::Barrier { tx }).await.map_err(|_| Self::spill_stopped())?;
+        rx.await.map_err(|_| Self::spill_stopped())
+    }
+
+    fn spill_stopped() -> io::Error {
+        io::Error::other("BufferedLogStorage: spill task stopped")
+    }
+}
+
+impl<C, S> RaftLogStorage<C> for BufferedLogStorage<C, S>
+where
+    C: RaftTypeConfig,
+    C::Entry: Clone,
+    S: RaftLogStorage<C>,
+{
+    type LogReader = BufferedLogReader<C, S::LogReader>;
+
+    async fn get_log_state(&mut self) -> Result<LogState<C>, io::Error> {
+        self.barrier().await?;
+        self.inner.lock().await.get_log_state().await
+    }
+
+    async fn get_log_reader(&mut self) -> Self::LogReader {
+        let inner = self.inner.lock().await.get_log_reader().await;
+        BufferedLogReader::new(self.tier.clone(), inner)
+    }
+
+    async fn save_vote(&mut self, vote: &VoteOf<C>) -> Result<(), io::Error> {
+        self.barrier().await?;
+        self.inner.lock().await.save_vote(vote).await
+    }
+
+    async fn save_committed(&mut self, committed: Option<LogIdOf<C>>) -> Result<(), io::Error> {
+        self.barrier().await?;
+        self.inner.lock().await.save_committed(committed).await
+    }
+
+    async fn read_committed(&mut self) -> Result<Option<LogIdOf<C>>, io::Error> {
+        self.inner.lock().await.read_committed().await
+    }
+
+    async fn append<I>(&mut self, entries: I, callback: IOFlushed<C>) -> Result<(), io::Error>
+    where
+        I: IntoIterator<Item = C::Entry> + OptionalSend,
+        I::IntoIter: OptionalSend,
+    {
+        let entries: Vec<C::Entry> = entries.into_iter().collect();
+
+        let buffered = self.tier.lock().unwrap().len();
+        if buffered > 0 && buffered + entries.len() > self.capacity {
+            self.barrier().await?;
+        }
+
+        {
+            let mut tier = self.tier.lock().unwrap();
+            for ent in entries.iter() {
+                tier.insert(ent.index(), ent.clone());
+            }
+        }
+
+        self.tx_spill.send(Spill::Append { entries, callback }).await.map_err(|_| Self::spill_stopped())
+    }
+
+    async fn truncate_after(&mut self, last_log_id: Option<LogIdOf<C>>) -> Result<(), io::Error> {
+        self.barrier().await?;
+        self.inner.lock().await.truncate_after(last_log_id).await
+    }
+
+    async fn purge(&mut self, log_id: LogIdOf<C>) -> Result<(), io::Error> {
+        self.barrier().await?;
+        self.inner.lock().await.purge(log_id).await
+    }
+}
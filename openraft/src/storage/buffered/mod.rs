@@ -0,0 +1,8 @@
+//! An in-memory log tier in front of a [`RaftLogStorage`](crate::storage::RaftLogStorage).
+
+mod buffered_log_reader;
+mod buffered_log_storage;
+mod spill;
+
+pub use self::buffered_log_reader::BufferedLogReader;
+pub use self::buffered_log_storage::BufferedLogStorage;
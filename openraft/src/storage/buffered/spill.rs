@@ -0,0 +1,31 @@
+use std::io;
+
+use crate::RaftTypeConfig;
+use crate::storage::IOFlushed;
+use crate::type_config::alias::OneshotReceiverOf;
+use crate::type_config::alias::OneshotSenderOf;
+
+/// A request to the spill task of a [`BufferedLogStorage`](super::BufferedLogStorage).
+pub(crate) enum Spill<C>
+where C: RaftTypeConfig
+{
+    /// Append entries held in the in-memory tier to the inner storage.
+    Append {
+        entries: Vec<C::Entry>,
+        callback: IOFlushed<C>,
+    },
+
+    /// Reply once all the previously queued appends have been submitted to the inner storage.
+    Barrier { tx: OneshotSenderOf<C, ()> },
+}
+
+/// An append submitted to the inner storage, waiting for the inner storage to flush it.
+pub(crate) struct PendingFlush<C>
+where C: RaftTypeConfig
+{
+    /// Receives the flush result from the inner storage.
+    pub(crate) rx: OneshotReceiverOf<C, Result<(), io::Error>>,
+
+    /// The callback given to [`BufferedLogStorage`](super::BufferedLogStorage) by Openraft.
+    pub(crate) callback: IOFlushed<C>,
+}
@@ -7,6 +7,7 @@ use crate::StorageError;
 use crate::raft_state::IOId;
 use crate::type_config::alias::LogIdOf;
 use crate::type_config::alias::OneshotSenderOf;
+use crate::type_config::alias::SnapshotMetaOf;
 use crate::type_config::alias::WatchSenderOf;
 use crate::type_config::async_runtime::oneshot::OneshotSender;
 use crate::type_config::async_runtime::watch::WatchSender;
@@ -168,6 +169,40 @@ where C: RaftTypeConfig
     }
 }
 
+/// A oneshot callback for completion of fetching and installing a snapshot stored out of band.
+///
+/// See [`RaftStateMachine::install_snapshot_locator()`].
+///
+/// [`RaftStateMachine::install_snapshot_locator()`]: crate::storage::RaftStateMachine::install_snapshot_locator
+pub struct SnapshotFetched<C>
+where C: RaftTypeConfig
+{
+    meta: SnapshotMetaOf<C>,
+    tx: OneshotSenderOf<C, Result<(), io::Error>>,
+}
+
+impl<C> SnapshotFetched<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new(meta: SnapshotMetaOf<C>, tx: OneshotSenderOf<C, Result<(), io::Error>>) -> Self {
+        Self { meta, tx }
+    }
+
+    /// Report that the snapshot is fetched and installed, or that it failed.
+    ///
+    /// Openraft finishes installing the snapshot and responds to the leader once it is called.
+    pub fn completed(self, result: Result<(), io::Error>) {
+        match &result {
+            Ok(()) => tracing::info!("SnapshotFetched: {}", self.meta),
+            Err(e) => tracing::error!("SnapshotFetched error: {}, while fetching: {}", e, self.meta),
+        }
+
+        if let Err(_e) = self.tx.send(result) {
+            tracing::error!("failed to send snapshot fetched event, meta: {}", self.meta);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -16,6 +16,7 @@ use crate::base::shared_id_generator::SharedIdGenerator;
 use crate::engine::LogIdList;
 use crate::entry::RaftEntry;
 use crate::entry::RaftPayload;
+use crate::entry::expiry::Expiry;
 use crate::errors::StorageIOResult;
 use crate::raft_state::IOState;
 use crate::storage::RaftLogStorage;
@@ -122,7 +123,8 @@ where
 
         // Re-apply log entries to recover SM to latest state.
         // For transient state machines, this re-applies logs from snapshot position to committed.
-        if last_applied < committed {
+        // A witness or a log-only node has no state machine to re-apply logs to.
+        if last_applied < committed && !self.is_without_state_machine().await? {
             let start = last_applied.next_index();
             let end = committed.next_index();
 
@@ -219,10 +221,28 @@ where
             server_state: Default::default(),
             io_state: Valid::new(io_state),
             purge_upto: last_purged_log_id,
+            purge_hold: None,
+            log_only_hold: None,
+            // Logs purged beyond the snapshot were persisted by the state machine before, with
+            // `PurgePolicy::DurableApplied`.
+            durable_applied: last_purged_log_id.clone(),
             progress_id_gen: SharedIdGenerator::new(),
         })
     }
 
+    /// Whether this node is a witness or a log-only node in the effective membership, which has
+    /// no state machine to apply logs to. See [`NodeRole::Witness`](crate::NodeRole::Witness) and
+    /// [`NodeRole::LogOnly`](crate::NodeRole::LogOnly).
+    async fn is_without_state_machine(&mut self) -> Result<bool, StorageError<C>> {
+        let Some(id) = self.id.clone() else {
+            return Ok(false);
+        };
+
+        let mem_state = self.get_membership().await?;
+        let membership = mem_state.effective().membership();
+        Ok(membership.is_witness(&id) || membership.is_log_only(&id))
+    }
+
     /// Restore state machine by installing snapshot if available and newer than last_applied.
     ///
     /// For transient state machines, this installs the last persistent snapshot to efficiently
@@ -257,6 +277,9 @@ where
     }
 
     /// Read log entries from [`RaftLogReader`] in chunks and apply them to the state machine.
+    ///
+    /// An entry with a deadline expires as it does when it is applied by the state machine
+    /// worker, decided by the first entries of the leaders after it.
     pub(crate) async fn reapply_committed(&mut self, mut start: u64, end: u64) -> Result<(), StorageError<C>> {
         let chunk_size = 64;
 
@@ -269,6 +292,11 @@ where
 
         let mut log_reader = self.log_store.get_log_reader().await;
 
+        let range = log_reader.get_log_id(start).await?..=log_reader.get_log_id(end - 1).await?;
+        let key_log_ids = LogIdList::<CommittedLeaderIdOf<C>>::get_key_log_ids(range, &mut log_reader).await?;
+        let leader_starts = key_log_ids.iter().map(|log_id| log_id.index() + 1).filter(|index| *index < end);
+        let expiry = Expiry::read(&mut log_reader, &leader_starts.collect::<Vec<_>>()).await?;
+
         while start < end {
             let chunk_end = std::cmp::min(end, start + chunk_size);
             let entries = log_reader.try_get_log_entries(start..chunk_end).await.sto_read_logs()?;
@@ -300,7 +328,12 @@ where
                 chunk_end
             );
             let last_applied = entries.last().map(|e| e.log_id()).unwrap();
-            let apply_items = entries.into_iter().map(|entry| Ok((entry, None)));
+            let apply_items = entries.into_iter().map(|entry| {
+                if expiry.expires(&entry) {
+                    return Ok((C::Entry::new_blank(entry.log_id()), None));
+                }
+                Ok((entry, None))
+            });
             let apply_stream = futures_util::stream::iter(apply_items);
             self.state_machine.apply(apply_stream).await.sto_apply(last_applied)?;
 
@@ -339,6 +372,12 @@ where
 
         // There 2 membership configs in logs.
         if log_mem.len() == 2 {
+            // The last one may only remove learners from an uncommitted one: look further back
+            // for the committed one.
+            if log_mem[1].membership().is_learner_removal_of(log_mem[0].membership()) {
+                return self.replay_memberships_in_log(sm_mem, last_applied.next_index()).await;
+            }
+
             return Ok(MembershipStateOf::<C>::new(
                 Arc::new(log_mem[0].clone()),
                 Arc::new(log_mem[1].clone()),
@@ -356,6 +395,45 @@ where
         Ok(res)
     }
 
+    /// Rebuild the membership state by appending every membership config found in the log since
+    /// `since_index` to the one in the state machine.
+    ///
+    /// It is used when the last membership configs in the log only remove learners, which may be
+    /// appended before the previous membership is committed, see
+    /// [`ChangeMembers::RemoveLearners`](crate::ChangeMembers::RemoveLearners).
+    async fn replay_memberships_in_log(
+        &mut self,
+        sm_mem: StoredMembershipOf<C>,
+        since_index: u64,
+    ) -> Result<MembershipStateOf<C>, StorageError<C>> {
+        let st = self.log_store.get_log_state().await.sto_read_logs()?;
+
+        let start = std::cmp::max(st.last_purged_log_id.next_index(), since_index);
+        let end = st.last_log_id.next_index();
+
+        let sm_mem = Arc::new(sm_mem);
+        let mut res = MembershipStateOf::<C>::new(sm_mem.clone(), sm_mem);
+
+        let mut log_reader = self.log_store.get_log_reader().await;
+        let step = 64;
+        let mut step_start = start;
+
+        while step_start < end {
+            let step_end = std::cmp::min(end, step_start + step);
+            let entries = log_reader.try_get_log_entries(step_start..step_end).await.sto_read_logs()?;
+
+            for ent in entries.iter() {
+                if let Some(mem) = ent.get_membership() {
+                    res.append(Arc::new(StoredMembershipOf::<C>::new(Some(ent.log_id()), mem)));
+                }
+            }
+
+            step_start = step_end;
+        }
+
+        Ok(res)
+    }
+
     /// Get the last 2 membership configs found in the log.
     ///
     /// This method returns at most membership logs with the greatest log index which is
@@ -0,0 +1,36 @@
+use std::fmt;
+
+use display_more::DisplayOptionExt;
+use openraft_macros::since;
+
+use crate::RaftTypeConfig;
+use crate::entry::EntryKind;
+use crate::type_config::alias::LogIdOf;
+
+/// What a log entry is, without its payload: returned by
+/// [`Raft::local_log_tail()`](crate::Raft::local_log_tail) to show which log entries a node has.
+///
+/// It is read with [`RaftLogReader::get_log_meta()`](crate::storage::RaftLogReader::get_log_meta).
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct LogEntryMeta<C>
+where C: RaftTypeConfig
+{
+    /// The log id of the entry.
+    pub log_id: LogIdOf<C>,
+
+    /// The kind of the payload of the entry.
+    pub kind: EntryKind,
+
+    /// The size in bytes of the entry as stored, if the storage knows it.
+    pub size: Option<u64>,
+}
+
+impl<C> fmt::Display for LogEntryMeta<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}(size:{})", self.log_id, self.kind, self.size.display())
+    }
+}
@@ -25,29 +25,44 @@
 //! [State Machine Component](crate::docs::components::state_machine) documentation
 //! for implementation details and examples.
 
+pub mod buffered;
 mod callback;
 mod helper;
+mod log_entry_meta;
 mod log_reader_ext;
 mod log_state;
+mod node_remap;
 mod snapshot;
+mod snapshot_locator;
 mod snapshot_meta;
 mod snapshot_signature;
+mod snapshot_trigger;
+mod usage_probe;
 pub(crate) mod v2;
 
+pub use self::buffered::BufferedLogStorage;
 pub use self::callback::IOFlushed;
 pub use self::callback::LogApplied;
 #[allow(deprecated)]
 pub use self::callback::LogFlushed;
+pub use self::callback::SnapshotFetched;
 pub use self::helper::StorageHelper;
+pub use self::log_entry_meta::LogEntryMeta;
 pub use self::log_reader_ext::RaftLogReaderExt;
 pub use self::log_state::LogState;
+pub use self::node_remap::NodeRemap;
 pub use self::snapshot::Snapshot;
+pub use self::snapshot_locator::SnapshotLocator;
 pub use self::snapshot_meta::SnapshotMeta;
 pub use self::snapshot_signature::SnapshotSignature;
+pub use self::snapshot_trigger::SnapshotTrigger;
+pub use self::snapshot_trigger::SnapshotTriggerContext;
+pub use self::usage_probe::StorageUsageProbe;
 pub use self::v2::ApplyResponder;
 pub use self::v2::EntryResponder;
 pub use self::v2::LeaderBoundedStreamError;
 pub use self::v2::LeaderBoundedStreamResult;
+pub use self::v2::PayloadStream;
 pub use self::v2::RaftLogReader;
 pub use self::v2::RaftLogStorage;
 pub use self::v2::RaftLogStorageExt;
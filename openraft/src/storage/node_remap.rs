@@ -0,0 +1,267 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use openraft_macros::since;
+
+use crate::Membership;
+use crate::RaftLogReader;
+use crate::RaftSnapshotBuilder;
+use crate::RaftTypeConfig;
+use crate::StorageError;
+use crate::entry::RaftEntry;
+use crate::entry::RaftPayload;
+use crate::errors::StorageIOResult;
+use crate::storage::RaftLogStorage;
+use crate::storage::RaftLogStorageExt;
+use crate::storage::RaftStateMachine;
+use crate::type_config::alias::LeaderIdOf;
+use crate::type_config::alias::SnapshotMetaOf;
+use crate::type_config::alias::StoredMembershipOf;
+use crate::type_config::alias::VoteOf;
+use crate::vote::RaftLeaderId;
+use crate::vote::RaftVote;
+use crate::vote::raft_vote::RaftVoteExt;
+
+/// Rewrites the node ids and node addresses in the data a node stores, while the node is offline.
+///
+/// It is used to clone the data of a cluster, e.g., from production into a staging environment,
+/// where the nodes have different ids and addresses. The data of every node is rewritten with the
+/// same `NodeRemap`, before a [`Raft`](crate::Raft) is started on it:
+///
+/// - The vote is saved as not committed, so that the cloned cluster elects a new leader instead of
+///   restoring the old one.
+/// - The membership configs in the log are rewritten. The log ids are not: they are the same on
+///   every cloned node.
+/// - The current snapshot is rebuilt and installed with its meta rewritten. The state machine must
+///   restore its last applied membership config from the meta in
+///   [`RaftStateMachine::install_snapshot()`].
+///
+/// A node id that is not mapped is kept along with its address.
+///
+/// ```ignore
+/// let remap = NodeRemap::new()
+///     .map(1, 101, BasicNode::new("staging-1:5001"))
+///     .map(2, 102, BasicNode::new("staging-2:5001"))
+///     .map(3, 103, BasicNode::new("staging-3:5001"));
+///
+/// remap.remap_log_store(&mut log_store).await?;
+/// remap.remap_state_machine(&mut state_machine).await?;
+///
+/// let raft = Raft::new(101, config, network, log_store, state_machine).await?;
+/// ```
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone)]
+pub struct NodeRemap<C>
+where C: RaftTypeConfig
+{
+    nodes: BTreeMap<C::NodeId, (C::NodeId, C::Node)>,
+}
+
+impl<C> Default for NodeRemap<C>
+where C: RaftTypeConfig
+{
+    fn default() -> Self {
+        Self { nodes: BTreeMap::new() }
+    }
+}
+
+impl<C> NodeRemap<C>
+where C: RaftTypeConfig
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rewrite node id `from` to `to`, and its address to `node`.
+    pub fn map(mut self, from: C::NodeId, to: C::NodeId, node: C::Node) -> Self {
+        self.nodes.insert(from, (to, node));
+        self
+    }
+
+    /// Returns the node id `node_id` is rewritten to.
+    pub fn node_id(&self, node_id: &C::NodeId) -> C::NodeId {
+        match self.nodes.get(node_id) {
+            Some((to, _)) => to.clone(),
+            None => node_id.clone(),
+        }
+    }
+
+    /// Returns the rewritten vote, which is not committed.
+    pub fn vote(&self, vote: &VoteOf<C>) -> VoteOf<C> {
+        let leader_id = LeaderIdOf::<C>::new(vote.term(), self.node_id(vote.leader_node_id()));
+        VoteOf::<C>::from_leader_id(leader_id, false)
+    }
+
+    /// Returns the rewritten membership config.
+    pub fn membership(&self, membership: &Membership<C::NodeId, C::Node>) -> Membership<C::NodeId, C::Node> {
+        let configs = membership
+            .get_joint_config()
+            .iter()
+            .map(|config| config.iter().map(|id| self.node_id(id)).collect::<BTreeSet<_>>())
+            .collect::<Vec<_>>();
+
+        let nodes = membership
+            .nodes()
+            .map(|(id, node)| match self.nodes.get(id) {
+                Some((to, to_node)) => (to.clone(), to_node.clone()),
+                None => (id.clone(), node.clone()),
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        Membership::new_unchecked(configs, nodes)
+    }
+
+    /// Returns the rewritten membership config along with the log id of it.
+    pub fn stored_membership(&self, stored: &StoredMembershipOf<C>) -> StoredMembershipOf<C> {
+        StoredMembershipOf::<C>::new(stored.log_id().clone(), self.membership(stored.membership()))
+    }
+
+    /// Returns the rewritten snapshot meta.
+    pub fn snapshot_meta(&self, meta: &SnapshotMetaOf<C>) -> SnapshotMetaOf<C> {
+        SnapshotMetaOf::<C> {
+            last_log_id: meta.last_log_id.clone(),
+            last_membership: self.stored_membership(&meta.last_membership),
+            snapshot_id: meta.snapshot_id.clone(),
+            base_snapshot_id: meta.base_snapshot_id.clone(),
+        }
+    }
+
+    /// Rewrite the vote and the membership configs in the log.
+    ///
+    /// The log entries from the first membership config on are truncated and appended again; all
+    /// of them are loaded into memory.
+    pub async fn remap_log_store<LS>(&self, log_store: &mut LS) -> Result<(), StorageError<C>>
+    where LS: RaftLogStorage<C> {
+        let mut reader = log_store.get_log_reader().await;
+
+        if let Some(vote) = reader.read_vote().await.sto_read_vote()? {
+            log_store.save_vote(&self.vote(&vote)).await.sto_write_vote()?;
+        }
+
+        let st = log_store.get_log_state().await.sto_read_logs()?;
+        let Some(last) = st.last_log_id else {
+            return Ok(());
+        };
+        let start = st.last_purged_log_id.as_ref().map_or(0, |x| x.index() + 1);
+
+        let entries = reader.try_get_log_entries(start..=last.index()).await.sto_read_logs()?;
+        let Some(first) = entries.iter().position(|ent| ent.get_membership().is_some()) else {
+            return Ok(());
+        };
+
+        let truncate_after = match first {
+            0 => st.last_purged_log_id,
+            _ => Some(entries[first - 1].log_id()),
+        };
+
+        let rewritten = entries
+            .into_iter()
+            .skip(first)
+            .map(|ent| match ent.get_membership() {
+                Some(m) => C::Entry::new_membership(ent.log_id(), self.membership(&m)),
+                None => ent,
+            })
+            .collect::<Vec<_>>();
+
+        tracing::info!(
+            "rewrite {} log entries since index {}",
+            rewritten.len(),
+            start + first as u64
+        );
+
+        log_store.truncate_after(truncate_after).await.sto_write_logs()?;
+        log_store.blocking_append(rewritten).await?;
+
+        Ok(())
+    }
+
+    /// Build a snapshot of the state machine and install it with the snapshot meta rewritten.
+    ///
+    /// Nothing is done if the state machine has applied nothing.
+    pub async fn remap_state_machine<SM>(&self, state_machine: &mut SM) -> Result<(), StorageError<C>>
+    where SM: RaftStateMachine<C> {
+        let (applied, _) = state_machine.applied_state().await.sto_read_sm()?;
+        if applied.is_none() {
+            return Ok(());
+        }
+
+        let mut builder = state_machine.get_snapshot_builder().await;
+        let snapshot = builder.build_snapshot().await.sto_write_snapshot(None)?;
+
+        let meta = self.snapshot_meta(&snapshot.meta);
+        tracing::info!("install snapshot with rewritten meta: {}", meta);
+
+        state_machine
+            .install_snapshot(&meta, snapshot.snapshot)
+            .await
+            .sto_write_snapshot(Some(meta.signature()))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use maplit::btreemap;
+    use maplit::btreeset;
+
+    use super::NodeRemap;
+    use crate::Membership;
+    use crate::StoredMembership;
+    use crate::Vote;
+    use crate::engine::testing::UTConfig;
+    use crate::storage::SnapshotMeta;
+    use crate::testing::log_id;
+
+    type C = UTConfig<u64>;
+
+    #[test]
+    fn test_node_remap_membership() {
+        let remap = NodeRemap::<C>::new().map(1, 11, 110).map(2, 12, 120);
+
+        let m = Membership::<u64, u64>::new(
+            vec![btreeset! {1,2,3}, btreeset! {2,3}],
+            btreemap! {1=>10, 2=>20, 3=>30, 4=>40},
+        )
+        .unwrap();
+
+        let got = remap.membership(&m);
+        assert_eq!(&vec![btreeset! {11,12,3}, btreeset! {12,3}], got.get_joint_config());
+        assert_eq!(
+            btreemap! {11=>110, 12=>120, 3=>30, 4=>40},
+            got.nodes().map(|(id, n)| (*id, *n)).collect::<BTreeMap<_, _>>()
+        );
+    }
+
+    #[test]
+    fn test_node_remap_vote() {
+        let remap = NodeRemap::<C>::new().map(1, 11, 110);
+
+        assert_eq!(Vote::new(3, 11), remap.vote(&Vote::new_committed(3, 1)));
+        assert_eq!(Vote::new(3, 2), remap.vote(&Vote::new(3, 2)));
+    }
+
+    #[test]
+    fn test_node_remap_snapshot_meta() {
+        let remap = NodeRemap::<C>::new().map(1, 11, 110);
+
+        let m = Membership::<u64, u64>::new(vec![btreeset! {1,2}], btreemap! {1=>10, 2=>20}).unwrap();
+        let meta = SnapshotMeta {
+            last_log_id: Some(log_id::<C>(2, 1, 5)),
+            last_membership: StoredMembership::new(Some(log_id::<C>(1, 1, 3)), m),
+            snapshot_id: "s1".to_string(),
+            base_snapshot_id: None,
+        };
+
+        let got = remap.snapshot_meta(&meta);
+        assert_eq!(Some(log_id::<C>(2, 1, 5)), got.last_log_id, "log ids are not rewritten");
+        assert_eq!(&Some(log_id::<C>(1, 1, 3)), got.last_membership.log_id());
+        assert_eq!(
+            &vec![btreeset! {11,2}],
+            got.last_membership.membership().get_joint_config()
+        );
+        assert_eq!("s1", got.snapshot_id);
+    }
+}
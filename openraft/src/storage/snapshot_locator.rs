@@ -0,0 +1,36 @@
+use std::fmt;
+
+use openraft_macros::since;
+
+use crate::RaftTypeConfig;
+use crate::type_config::alias::SnapshotMetaOf;
+
+/// A snapshot stored out of band, e.g., in an object store, that a follower fetches by itself.
+///
+/// The leader's state machine returns it from
+/// [`RaftStateMachine::get_snapshot_locator()`], and the leader sends it instead of the snapshot
+/// data. The follower's state machine fetches the data with
+/// [`RaftStateMachine::install_snapshot_locator()`].
+///
+/// [`RaftStateMachine::get_snapshot_locator()`]: crate::storage::RaftStateMachine::get_snapshot_locator
+/// [`RaftStateMachine::install_snapshot_locator()`]: crate::storage::RaftStateMachine::install_snapshot_locator
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct SnapshotLocator<C>
+where C: RaftTypeConfig
+{
+    /// The meta of the snapshot.
+    pub meta: SnapshotMetaOf<C>,
+
+    /// Where to fetch the snapshot data from, e.g., an object store URL. It is opaque to Openraft.
+    pub locator: String,
+}
+
+impl<C> fmt::Display for SnapshotLocator<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SnapshotLocator{{meta: {}, locator: {}}}", self.meta, self.locator)
+    }
+}
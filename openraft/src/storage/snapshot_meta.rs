@@ -38,6 +38,17 @@ where
     /// Caveat: even when two snapshots are built with the same `last_log_id`, they still could be
     /// different in bytes.
     pub snapshot_id: SnapshotId,
+
+    /// The id of the snapshot this one is a delta relative to, or `None` for a full snapshot.
+    ///
+    /// A delta snapshot is built by [`RaftStateMachine::get_delta_snapshot()`] for a follower
+    /// that has installed the base snapshot, and its data contains only the changes since the
+    /// base.
+    ///
+    /// [`RaftStateMachine::get_delta_snapshot()`]: crate::storage::RaftStateMachine::get_delta_snapshot
+    #[since(version = "0.10.0")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub base_snapshot_id: Option<SnapshotId>,
 }
 
 impl<CLID, NID, N> Default for SnapshotMeta<CLID, NID, N>
@@ -51,6 +62,7 @@ where
             last_log_id: None,
             last_membership: StoredMembership::default(),
             snapshot_id: SnapshotId::default(),
+            base_snapshot_id: None,
         }
     }
 }
@@ -62,10 +74,13 @@ where
     N: Node,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{snapshot_id: {}", self.snapshot_id)?;
+        if let Some(base) = &self.base_snapshot_id {
+            write!(f, ", base: {}", base)?;
+        }
         write!(
             f,
-            "{{snapshot_id: {}, last_log:{}, last_membership: {}}}",
-            self.snapshot_id,
+            ", last_log:{}, last_membership: {}}}",
             self.last_log_id.display(),
             self.last_membership
         )
@@ -87,6 +102,12 @@ where
         }
     }
 
+    /// Returns `true` if this snapshot contains only the changes since a base snapshot.
+    #[since(version = "0.10.0")]
+    pub fn is_delta(&self) -> bool {
+        self.base_snapshot_id.is_some()
+    }
+
     /// Returns a ref to the id of the last log that is included in this snapshot.
     pub fn last_log_id(&self) -> Option<&LogId<CLID>> {
         self.last_log_id.as_ref()
@@ -0,0 +1,67 @@
+use std::fmt;
+use std::time::Duration;
+
+use openraft_macros::since;
+
+use crate::RaftTypeConfig;
+use crate::type_config::alias::LogIdOf;
+
+/// Decides when to build a snapshot, in addition to [`Config::snapshot_policy`].
+///
+/// Install it with [`Raft::set_snapshot_trigger()`] to build snapshots based on what the
+/// application knows best, e.g., the size of the state machine, the bytes of log appended, or the
+/// time since the last snapshot. When it returns `true`, Openraft builds a snapshot of the
+/// committed logs, subject to the same deferral as a policy-triggered build.
+///
+/// It is called from the RaftCore task, every time it finishes handling a batch of events while
+/// there are committed logs not included in a snapshot, and should return quickly.
+///
+/// # Examples
+///
+/// ```ignore
+/// use openraft::storage::SnapshotTrigger;
+/// use openraft::storage::SnapshotTriggerContext;
+///
+/// /// Build a snapshot once the state machine has grown by 64 MiB.
+/// #[derive(Debug)]
+/// struct SizeTrigger(Arc<AtomicU64>);
+///
+/// impl SnapshotTrigger<TypeConfig> for SizeTrigger {
+///     fn should_snapshot(&self, _ctx: &SnapshotTriggerContext<TypeConfig>) -> bool {
+///         self.0.load(Ordering::Relaxed) >= 64 << 20
+///     }
+/// }
+///
+/// raft.set_snapshot_trigger(Some(Arc::new(SizeTrigger(grown_bytes)))).await?;
+/// ```
+///
+/// [`Config::snapshot_policy`]: crate::Config::snapshot_policy
+/// [`Raft::set_snapshot_trigger()`]: crate::Raft::set_snapshot_trigger
+#[since(version = "0.10.0")]
+pub trait SnapshotTrigger<C>: Send + Sync + fmt::Debug
+where C: RaftTypeConfig
+{
+    /// Whether to build a snapshot now.
+    fn should_snapshot(&self, ctx: &SnapshotTriggerContext<C>) -> bool;
+}
+
+/// The state of this node that a [`SnapshotTrigger`] decides on.
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SnapshotTriggerContext<C>
+where C: RaftTypeConfig
+{
+    /// The last committed log id, up to which a snapshot would be built.
+    pub committed: Option<LogIdOf<C>>,
+
+    /// The last log id included in the current snapshot, or in the last snapshot build attempt.
+    pub snapshot_last_log_id: Option<LogIdOf<C>>,
+
+    /// The number of committed logs after `snapshot_last_log_id`.
+    pub logs_since_snapshot: u64,
+
+    /// The time since this node last built a snapshot, or `None` if it has not built one since
+    /// it started.
+    pub since_last_build: Option<Duration>,
+}
@@ -0,0 +1,21 @@
+use openraft_macros::since;
+
+/// Reports the disk space used by the Raft storage of this node.
+///
+/// Install it with [`Raft::set_storage_usage_probe()`] to enforce
+/// [`Config::storage_quota`]: when the reported usage exceeds the quota, Openraft builds a
+/// snapshot and purges the logs included in it, and if the usage is still above the quota, it
+/// rejects client writes with [`StorageFull`] instead of running out of disk space in the middle
+/// of an append.
+///
+/// It is called from the RaftCore task, every time it finishes handling a batch of events, and
+/// should return quickly, e.g., a value refreshed periodically by another task.
+///
+/// [`Raft::set_storage_usage_probe()`]: crate::Raft::set_storage_usage_probe
+/// [`Config::storage_quota`]: crate::Config::storage_quota
+/// [`StorageFull`]: crate::errors::StorageFull
+#[since(version = "0.10.0")]
+pub trait StorageUsageProbe: Send + Sync + std::fmt::Debug {
+    /// The number of bytes used by the logs and the snapshots of this node.
+    fn usage(&self) -> u64;
+}
@@ -0,0 +1,147 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use crate::RaftTypeConfig;
+use crate::raft::CorrelationId;
+use crate::type_config::alias::LogIdOf;
+
+/// A bounded cache of the responses of the most recently applied log entries.
+///
+/// It is shared between the state machine worker, whose [`ApplyResponder`] records a response
+/// with [`send_and_cache()`], and the `Raft` handle, which serves
+/// [`Raft::applied_result()`](crate::Raft::applied_result).
+///
+/// [`ApplyResponder`]: crate::storage::ApplyResponder
+/// [`send_and_cache()`]: crate::storage::ApplyResponder::send_and_cache
+#[derive(Clone)]
+pub(crate) struct AppliedResultCache<C>
+where C: RaftTypeConfig
+{
+    /// The max number of responses to retain. `0` disables caching.
+    capacity: usize,
+    inner: Arc<Mutex<Cached<C>>>,
+}
+
+struct Cached<C>
+where C: RaftTypeConfig
+{
+    /// Responses keyed by the log id of the applied entry, with the correlation id of the write.
+    results: BTreeMap<LogIdOf<C>, (Option<CorrelationId>, C::R)>,
+
+    /// The log id of the entry each correlation id was written with.
+    by_correlation_id: BTreeMap<CorrelationId, LogIdOf<C>>,
+}
+
+impl<C> AppliedResultCache<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            inner: Arc::new(Mutex::new(Cached {
+                results: BTreeMap::new(),
+                by_correlation_id: BTreeMap::new(),
+            })),
+        }
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+
+    /// Record the response of an applied entry, evicting the one with the smallest log id if the
+    /// cache is full.
+    pub(crate) fn insert(&self, log_id: LogIdOf<C>, correlation_id: Option<CorrelationId>, response: C::R) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        let mut cached = self.inner.lock().unwrap();
+
+        if let Some(correlation_id) = correlation_id {
+            cached.by_correlation_id.insert(correlation_id, log_id.clone());
+        }
+        cached.results.insert(log_id, (correlation_id, response));
+
+        while cached.results.len() > self.capacity {
+            let Some((evicted, (correlation_id, _))) = cached.results.pop_first() else {
+                break;
+            };
+
+            // The correlation id may have been reused by a later write.
+            if let Some(correlation_id) = correlation_id
+                && cached.by_correlation_id.get(&correlation_id) == Some(&evicted)
+            {
+                cached.by_correlation_id.remove(&correlation_id);
+            }
+        }
+    }
+
+    /// Returns a copy of the response of the entry at `log_id`, if it is still cached.
+    pub(crate) fn get(&self, log_id: &LogIdOf<C>) -> Option<C::R>
+    where C::R: Clone {
+        let cached = self.inner.lock().unwrap();
+        cached.results.get(log_id).map(|(_, response)| response.clone())
+    }
+
+    /// Returns the log id and a copy of the response of the entry written with `correlation_id`,
+    /// if it is still cached.
+    pub(crate) fn get_by_correlation_id(&self, correlation_id: CorrelationId) -> Option<(LogIdOf<C>, C::R)>
+    where C::R: Clone {
+        let cached = self.inner.lock().unwrap();
+        let log_id = cached.by_correlation_id.get(&correlation_id)?;
+        let (_, response) = cached.results.get(log_id)?;
+        Some((log_id.clone(), response.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::testing::UTConfig;
+    use crate::engine::testing::log_id;
+    use crate::raft::CorrelationId;
+
+    type AppliedResultCache = super::AppliedResultCache<UTConfig>;
+
+    #[test]
+    fn test_applied_result_cache_evicts_smallest_log_id() {
+        let c = AppliedResultCache::new(2);
+        let shared = c.clone();
+
+        c.insert(log_id(1, 1, 1), Some(CorrelationId(10)), ());
+        c.insert(log_id(1, 1, 2), None, ());
+        assert_eq!(Some(()), shared.get(&log_id(1, 1, 1)));
+        assert_eq!(
+            Some((log_id(1, 1, 1), ())),
+            shared.get_by_correlation_id(CorrelationId(10))
+        );
+
+        c.insert(log_id(1, 1, 3), Some(CorrelationId(11)), ());
+        assert_eq!(None, shared.get(&log_id(1, 1, 1)));
+        assert_eq!(None, shared.get_by_correlation_id(CorrelationId(10)));
+        assert_eq!(Some(()), shared.get(&log_id(1, 1, 2)));
+        assert_eq!(
+            Some((log_id(1, 1, 3), ())),
+            shared.get_by_correlation_id(CorrelationId(11))
+        );
+    }
+
+    #[test]
+    fn test_applied_result_cache_reused_correlation_id() {
+        let c = AppliedResultCache::new(1);
+
+        c.insert(log_id(1, 1, 1), Some(CorrelationId(10)), ());
+        c.insert(log_id(1, 1, 2), Some(CorrelationId(10)), ());
+        assert_eq!(Some((log_id(1, 1, 2), ())), c.get_by_correlation_id(CorrelationId(10)));
+    }
+
+    #[test]
+    fn test_applied_result_cache_disabled() {
+        let c = AppliedResultCache::new(0);
+
+        c.insert(log_id(1, 1, 1), Some(CorrelationId(10)), ());
+        assert_eq!(None, c.get(&log_id(1, 1, 1)));
+        assert_eq!(None, c.get_by_correlation_id(CorrelationId(10)));
+    }
+}
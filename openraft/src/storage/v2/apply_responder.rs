@@ -1,4 +1,8 @@
+use openraft_macros::since;
+
 use crate::RaftTypeConfig;
+use crate::raft::CorrelationId;
+use crate::storage::v2::applied_result_cache::AppliedResultCache;
 use crate::storage::v2::apply_responder_inner::ApplyResponderInner;
 
 /// Responder for sending client write responses after applying an entry.
@@ -50,6 +54,7 @@ use crate::storage::v2::apply_responder_inner::ApplyResponderInner;
 /// ```
 pub struct ApplyResponder<C: RaftTypeConfig> {
     pub(crate) inner: ApplyResponderInner<C>,
+    pub(crate) applied_result_cache: AppliedResultCache<C>,
 }
 
 impl<C: RaftTypeConfig> ApplyResponder<C> {
@@ -57,4 +62,29 @@ impl<C: RaftTypeConfig> ApplyResponder<C> {
     pub fn send(self, response: C::R) {
         self.inner.send(response)
     }
+
+    /// Send the response after applying an entry, and retain a copy of it for a client that
+    /// asks for it again.
+    ///
+    /// The copy is retained only if [`Config::applied_result_cache_size`] is set; it can be
+    /// retrieved with [`Raft::applied_result()`] or [`Raft::applied_result_by_correlation_id()`].
+    ///
+    /// [`Config::applied_result_cache_size`]: crate::Config::applied_result_cache_size
+    /// [`Raft::applied_result()`]: crate::Raft::applied_result
+    /// [`Raft::applied_result_by_correlation_id()`]: crate::Raft::applied_result_by_correlation_id
+    #[since(version = "0.10.0")]
+    pub fn send_and_cache(self, response: C::R)
+    where C::R: Clone {
+        if self.applied_result_cache.is_enabled() {
+            let log_id = self.inner.log_id().clone();
+            self.applied_result_cache.insert(log_id, self.inner.correlation_id(), response.clone());
+        }
+        self.inner.send(response)
+    }
+
+    /// The [`CorrelationId`] the application attached to the write of this entry, if any.
+    #[since(version = "0.10.0")]
+    pub fn correlation_id(&self) -> Option<CorrelationId> {
+        self.inner.correlation_id()
+    }
 }
@@ -1,5 +1,6 @@
 use crate::Membership;
 use crate::RaftTypeConfig;
+use crate::raft::CorrelationId;
 use crate::raft::responder::core_responder::CoreResponder;
 use crate::type_config::alias::LogIdOf;
 
@@ -20,6 +21,20 @@ pub(crate) enum ApplyResponderInner<C: RaftTypeConfig> {
 }
 
 impl<C: RaftTypeConfig> ApplyResponderInner<C> {
+    pub(crate) fn log_id(&self) -> &LogIdOf<C> {
+        match self {
+            ApplyResponderInner::Normal { log_id, .. } => log_id,
+            ApplyResponderInner::Membership { log_id, .. } => log_id,
+        }
+    }
+
+    pub(crate) fn correlation_id(&self) -> Option<CorrelationId> {
+        match self {
+            ApplyResponderInner::Normal { responder, .. } => responder.correlation_id(),
+            ApplyResponderInner::Membership { responder, .. } => responder.correlation_id(),
+        }
+    }
+
     /// Send the response after applying an entry.
     pub(crate) fn send(self, response: C::R) {
         use crate::raft::ClientWriteResponse;
@@ -2,6 +2,7 @@ use crate::RaftTypeConfig;
 use crate::entry::RaftEntry;
 use crate::entry::RaftPayload;
 use crate::raft::responder::core_responder::CoreResponder;
+use crate::storage::v2::applied_result_cache::AppliedResultCache;
 use crate::storage::v2::apply_responder::ApplyResponder;
 use crate::storage::v2::apply_responder_inner::ApplyResponderInner;
 use crate::type_config::alias::EntryOf;
@@ -20,6 +21,7 @@ pub type EntryResponder<C> = (EntryOf<C>, Option<ApplyResponder<C>>);
 pub(crate) struct EntryResponderBuilder<C: RaftTypeConfig> {
     pub(crate) entry: C::Entry,
     pub(crate) responder: Option<CoreResponder<C>>,
+    pub(crate) applied_result_cache: AppliedResultCache<C>,
 }
 
 impl<C: RaftTypeConfig> EntryResponderBuilder<C> {
@@ -48,7 +50,12 @@ impl<C: RaftTypeConfig> EntryResponderBuilder<C> {
             None => ApplyResponderInner::Normal { log_id, responder },
         };
 
-        (self.entry, Some(ApplyResponder { inner }))
+        let responder = ApplyResponder {
+            inner,
+            applied_result_cache: self.applied_result_cache,
+        };
+
+        (self.entry, Some(responder))
     }
 }
 
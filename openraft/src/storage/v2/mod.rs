@@ -3,8 +3,9 @@
 //! [`RaftLogStorage`] is responsible for storing logs,
 //! and [`RaftStateMachine`] is responsible for storing state machine and snapshot.
 
+pub(crate) mod applied_result_cache;
 mod apply_responder;
-mod apply_responder_inner;
+pub(crate) mod apply_responder_inner;
 pub(crate) mod entry_responder;
 mod raft_log_reader;
 mod raft_log_storage;
@@ -16,6 +17,7 @@ pub use self::apply_responder::ApplyResponder;
 pub use self::entry_responder::EntryResponder;
 pub use self::raft_log_reader::LeaderBoundedStreamError;
 pub use self::raft_log_reader::LeaderBoundedStreamResult;
+pub use self::raft_log_reader::PayloadStream;
 pub use self::raft_log_reader::RaftLogReader;
 pub use self::raft_log_storage::RaftLogStorage;
 pub use self::raft_log_storage_ext::RaftLogStorageExt;
@@ -12,7 +12,10 @@ use crate::OptionalSync;
 use crate::RaftTypeConfig;
 use crate::base::BoxStream;
 use crate::engine::LogIdList;
+use crate::entry::RaftEntry;
+use crate::entry::RaftPayload;
 use crate::errors::LeaderChanged;
+use crate::storage::LogEntryMeta;
 use crate::type_config::alias::CommittedLeaderIdOf;
 use crate::type_config::alias::EntryOf;
 use crate::type_config::alias::LeaderIdOf;
@@ -44,6 +47,12 @@ pub type LeaderBoundedStreamResult<C> = Result<EntryOf<C>, LeaderBoundedStreamEr
 /// Result type for [`RaftLogReader::entries_stream`] stream items.
 pub type EntriesStreamResult<C> = Result<EntryOf<C>, io::Error>;
 
+/// The encoded application payload of a log entry, read in chunks from the log storage.
+///
+/// Returned by [`RaftLogReader::payload_stream`] and applied by
+/// [`RaftStateMachine::apply_payload_stream`](crate::storage::RaftStateMachine::apply_payload_stream).
+pub type PayloadStream = BoxStream<'static, Result<Vec<u8>, io::Error>>;
+
 /// A trait defining the interface for a Raft log subsystem.
 ///
 /// This interface is accessed read-only by replication sub-task: `ReplicationCore`.
@@ -171,6 +180,9 @@ where C: RaftTypeConfig
     /// The default implementation calls [`Self::try_get_log_entries`] to get all entries and
     /// converts them to a stream. Applications may implement this for better performance, such as
     /// streaming entries incrementally.
+    ///
+    /// An entry whose payload is too large to decode at once can be applied without being read by
+    /// this stream, see [`Self::payload_stream`].
     #[since(version = "0.10.0")]
     async fn entries_stream<RB>(&mut self, range: RB) -> impl Stream<Item = EntriesStreamResult<C>> + OptionalSend
     where RB: RangeBounds<u64> + Clone + Debug + OptionalSend {
@@ -249,6 +261,55 @@ where C: RaftTypeConfig
         self.try_get_log_entries(start..end).await
     }
 
+    /// Returns what the log entries in `range` are, without their payload.
+    ///
+    /// It is called by [`Raft::local_log_tail()`] to show the log entries a node has, e.g., in a
+    /// debug endpoint; not when replicating or applying logs. Like [`Self::try_get_log_entries`],
+    /// it returns the entries found in `range`.
+    ///
+    /// The default implementation reads the entries with [`Self::try_get_log_entries`], and does
+    /// not know their size. A storage that keeps the encoded entries should implement it to
+    /// return the stored size, and to read the kind without decoding the payload.
+    ///
+    /// [`Raft::local_log_tail()`]: crate::Raft::local_log_tail
+    #[since(version = "0.10.0")]
+    async fn get_log_meta(&mut self, range: RangeInclusive<u64>) -> Result<Vec<LogEntryMeta<C>>, io::Error> {
+        let entries = self.try_get_log_entries(range).await?;
+
+        let metas = entries
+            .iter()
+            .map(|ent| LogEntryMeta {
+                log_id: ent.log_id(),
+                kind: ent.kind(),
+                size: None,
+            })
+            .collect();
+
+        Ok(metas)
+    }
+
+    /// Returns the encoded application payload of the entry `log_id` as a stream of chunks, for
+    /// the state machine to apply it without decoding the whole entry in memory.
+    ///
+    /// It is called when [`Config::stream_payload_threshold`] is set, for each application entry
+    /// to apply whose size reported by [`Self::get_log_meta`] is at least the threshold, e.g., a
+    /// 500MB blob. The entry is then applied with
+    /// [`RaftStateMachine::apply_payload_stream()`] instead of being read by
+    /// [`Self::entries_stream`]. The stream is read from the log storage while the state machine
+    /// consumes it, so that the payload is never held in memory at once.
+    ///
+    /// Return `None` to apply the entry as usual, e.g., an entry with an
+    /// [`ApplyScope`](crate::entry::ApplyScope) that this node is out of, which must be applied
+    /// as a blank entry. The default implementation always returns `None`.
+    ///
+    /// [`Config::stream_payload_threshold`]: crate::Config::stream_payload_threshold
+    /// [`RaftStateMachine::apply_payload_stream()`]: crate::storage::RaftStateMachine::apply_payload_stream
+    #[since(version = "0.10.0")]
+    async fn payload_stream(&mut self, log_id: &LogIdOf<C>) -> Result<Option<PayloadStream>, io::Error> {
+        let _ = log_id;
+        Ok(None)
+    }
+
     /// Retrieves a list of key log ids that mark the beginning of each Leader.
     ///
     /// This method returns log entries that represent leadership transitions in the log history,
@@ -8,7 +8,12 @@ use crate::OptionalSend;
 use crate::OptionalSync;
 use crate::RaftSnapshotBuilder;
 use crate::RaftTypeConfig;
+use crate::SnapshotId;
+use crate::storage::ApplyResponder;
 use crate::storage::EntryResponder;
+use crate::storage::PayloadStream;
+use crate::storage::SnapshotFetched;
+use crate::storage::SnapshotLocator;
 use crate::type_config::alias::LogIdOf;
 use crate::type_config::alias::SnapshotMetaOf;
 use crate::type_config::alias::SnapshotOf;
@@ -88,6 +93,53 @@ where C: RaftTypeConfig
     async fn apply<Strm>(&mut self, entries: Strm) -> Result<(), io::Error>
     where Strm: Stream<Item = Result<EntryResponder<C>, io::Error>> + Unpin + OptionalSend;
 
+    /// Apply an application entry whose encoded payload is read as a stream.
+    ///
+    /// With [`Config::stream_payload_threshold`] set, an entry at least that large, for which
+    /// [`RaftLogReader::payload_stream()`] returns a stream, is applied with this method instead
+    /// of [`Self::apply`], in the same order as the other entries. `payload` is the encoded
+    /// `C::D` of the entry `log_id`, i.e., what the log storage decodes the payload from.
+    ///
+    /// Like [`Self::apply`], an implementation stores `log_id` as the last applied log id, and
+    /// calls [`ApplyResponder::send`](crate::storage::ApplyResponder::send) with the response if
+    /// there is a `responder`.
+    ///
+    /// The default implementation returns an [`Unsupported`](io::ErrorKind::Unsupported) error:
+    /// it must be implemented by a state machine whose log storage returns a payload stream.
+    ///
+    /// [`Config::stream_payload_threshold`]: crate::Config::stream_payload_threshold
+    /// [`RaftLogReader::payload_stream()`]: crate::storage::RaftLogReader::payload_stream
+    #[since(version = "0.10.0")]
+    async fn apply_payload_stream(
+        &mut self,
+        log_id: LogIdOf<C>,
+        payload: PayloadStream,
+        responder: Option<ApplyResponder<C>>,
+    ) -> Result<(), io::Error> {
+        let _ = (payload, responder);
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("apply_payload_stream is not implemented: {}", log_id),
+        ))
+    }
+
+    /// Returns the last applied log id that is persisted, so that it survives a restart.
+    ///
+    /// It is called after every [`Self::apply`]. With
+    /// [`Config::purge_policy`] set to [`PurgePolicy::DurableApplied`], Openraft purges the logs
+    /// up to it without requiring a snapshot that includes them. A state machine that flushes
+    /// asynchronously, e.g., a RocksDB memtable, returns the last log id flushed to disk, not the
+    /// last one applied.
+    ///
+    /// The default implementation returns `None`: no log is purged beyond the snapshot.
+    ///
+    /// [`Config::purge_policy`]: crate::Config::purge_policy
+    /// [`PurgePolicy::DurableApplied`]: crate::PurgePolicy::DurableApplied
+    #[since(version = "0.10.0")]
+    async fn durable_applied(&mut self) -> Result<Option<LogIdOf<C>>, io::Error> {
+        Ok(None)
+    }
+
     /// Try to create a snapshot builder for the state machine.
     ///
     /// Returns a snapshot view of the state machine, or `None` to defer snapshot creation.
@@ -150,11 +202,154 @@ where C: RaftTypeConfig
     /// A snapshot created from an earlier call to `begin_receiving_snapshot` which provided the
     /// snapshot.
     ///
+    /// ### delta snapshot
+    ///
+    /// If [`meta.base_snapshot_id`] is `Some`, `snapshot` is a delta built by
+    /// [`Self::get_delta_snapshot`] on the leader, containing only the changes since that base
+    /// snapshot. The base is the last snapshot this state machine installed from the same leader,
+    /// which a state machine building deltas must keep until it installs another one. After
+    /// installing a delta, [`Self::get_current_snapshot`] should return the full snapshot.
+    ///
     /// [`StorageHelper::get_initial_state()`]: crate::StorageHelper::get_initial_state
     /// [`Raft`]: crate::Raft
+    /// [`meta.base_snapshot_id`]: crate::storage::SnapshotMeta::base_snapshot_id
     #[since(version = "0.10.0", change = "SnapshotData without Box")]
     async fn install_snapshot(&mut self, meta: &SnapshotMetaOf<C>, snapshot: C::SnapshotData) -> Result<(), io::Error>;
 
+    /// Create a warm standby instance of this state machine.
+    ///
+    /// A standby is a second instance with the same state as `self`, for fast failover of a heavy
+    /// in-memory state machine. Openraft feeds it the applied entries asynchronously in a separate
+    /// task, without client responders, and reports how far it lags behind via
+    /// [`MetricsRecorder::set_standby_lag`]. When the leader changes, Openraft swaps it in as the
+    /// primary once it has caught up, and the former primary becomes the standby; no snapshot is
+    /// replayed.
+    ///
+    /// It is called when the state machine worker starts, and again after a snapshot is installed
+    /// to the primary.
+    ///
+    /// Returns `None` (the default) to run without a standby.
+    ///
+    /// [`MetricsRecorder::set_standby_lag`]: crate::metrics::MetricsRecorder::set_standby_lag
+    #[since(version = "0.10.0")]
+    async fn create_standby(&mut self) -> Option<Self>
+    where Self: Sized {
+        None
+    }
+
+    /// Get the current snapshot as a delta relative to the snapshot `base`.
+    ///
+    /// It is called instead of [`Self::get_current_snapshot`] when sending a snapshot to a
+    /// follower that has installed the snapshot `base` from this leader. For a large state machine
+    /// that changes little between snapshots, a delta is much smaller than a full snapshot.
+    ///
+    /// The returned snapshot has the meta of the current snapshot, with
+    /// [`base_snapshot_id`](crate::storage::SnapshotMeta::base_snapshot_id) set to `base`. The
+    /// follower installs it with [`Self::install_snapshot`].
+    ///
+    /// Returns `None` (the default) if no delta can be built, e.g., `base` is no longer known;
+    /// the full snapshot is sent instead.
+    #[since(version = "0.10.0")]
+    async fn get_delta_snapshot(&mut self, base: &SnapshotId) -> Result<Option<SnapshotOf<C>>, io::Error> {
+        let _ = base;
+        Ok(None)
+    }
+
+    /// Get a locator of the current snapshot, which is stored out of band, e.g., in an object
+    /// store.
+    ///
+    /// It is called on the leader before sending a snapshot to a follower. If it returns a
+    /// locator, only the locator is sent, and the follower fetches the snapshot data by itself
+    /// with [`Self::install_snapshot_locator`]. If the follower fails to, the leader sends the
+    /// snapshot data instead.
+    ///
+    /// Returns `None` (the default) to always send the snapshot data.
+    #[since(version = "0.10.0")]
+    async fn get_snapshot_locator(&mut self) -> Result<Option<SnapshotLocator<C>>, io::Error> {
+        Ok(None)
+    }
+
+    /// Fetch the snapshot stored out of band at `locator` and install it.
+    ///
+    /// It is called on a follower when the leader sends a locator returned by
+    /// [`Self::get_snapshot_locator`]. The fetch may run in the background: the state machine
+    /// reports completion with [`SnapshotFetched::completed`], after it has replaced its state with
+    /// the snapshot, like [`Self::install_snapshot`] does. Openraft then finishes installing the
+    /// snapshot and responds to the leader. No other command is sent to the state machine until
+    /// then.
+    ///
+    /// Returning an error, or completing `done` with one, is treated like an error returned by
+    /// [`Self::install_snapshot`].
+    ///
+    /// The default implementation returns an [`io::ErrorKind::Unsupported`] error.
+    #[since(version = "0.10.0")]
+    async fn install_snapshot_locator(
+        &mut self,
+        locator: &SnapshotLocator<C>,
+        done: SnapshotFetched<C>,
+    ) -> Result<(), io::Error> {
+        let _ = done;
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("install_snapshot_locator is not implemented: {}", locator),
+        ))
+    }
+
+    /// Record the metadata of a snapshot without its data, on a witness.
+    ///
+    /// A [`NodeRole::Witness`] keeps no state machine data, and the leader sends it only the
+    /// metadata of a snapshot when it falls behind the purged logs. Before this method returns,
+    /// [`Self::applied_state`] should return `meta.last_log_id` and `meta.last_membership`, so that
+    /// the witness restores its membership config on restart, and [`Self::get_current_snapshot`]
+    /// should return a snapshot with this `meta`, whose data may be empty.
+    ///
+    /// The default implementation returns an [`io::ErrorKind::Unsupported`] error: a state machine
+    /// used on a witness has to implement it.
+    ///
+    /// [`NodeRole::Witness`]: crate::NodeRole::Witness
+    #[since(version = "0.10.0")]
+    async fn install_snapshot_meta(&mut self, meta: &SnapshotMetaOf<C>) -> Result<(), io::Error> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("install_snapshot_meta is not implemented: {}", meta),
+        ))
+    }
+
+    /// Warm up this state machine after a snapshot is installed, e.g., populate block caches.
+    ///
+    /// It is called after [`Self::install_snapshot`] or [`Self::install_snapshot_locator`] if
+    /// [`Config::warm_up_after_install`] is enabled. Until it returns, this node reports it is
+    /// not ready for reads with [`RaftMetrics::warming_up`]. No other command is sent to the state
+    /// machine until then.
+    ///
+    /// An error is logged and ignored: the state machine is still correct, only cold.
+    ///
+    /// [`Config::warm_up_after_install`]: crate::Config::warm_up_after_install
+    /// [`RaftMetrics::warming_up`]: crate::RaftMetrics::warming_up
+    #[since(version = "0.10.0")]
+    async fn warm_up(&mut self, meta: &SnapshotMetaOf<C>) -> Result<(), io::Error> {
+        let _ = meta;
+        Ok(())
+    }
+
+    /// Remove the stored snapshots older than `keep_from`.
+    ///
+    /// It is called when a new snapshot is built or installed and more than
+    /// [`Config::snapshot_keep_count`] snapshots are kept. `keep_from` and every snapshot after
+    /// it must be kept; the older ones are no longer tracked by Openraft and may be deleted.
+    /// A snapshot being sent to a follower holds its own [`SnapshotData`], so deleting its
+    /// source must not break the transfer, e.g., keep a file open until the data is dropped.
+    ///
+    /// An error is logged and ignored. The default implementation does nothing.
+    ///
+    /// [`Config::snapshot_keep_count`]: crate::Config::snapshot_keep_count
+    /// [`SnapshotData`]: crate::RaftTypeConfig::SnapshotData
+    #[since(version = "0.10.0")]
+    async fn remove_snapshots_before(&mut self, keep_from: &SnapshotMetaOf<C>) -> Result<(), io::Error> {
+        let _ = keep_from;
+        Ok(())
+    }
+
     /// Get a readable handle to the current snapshot.
     ///
     /// ### implementation algorithm
@@ -0,0 +1,201 @@
+//! Runtime checker of the leader lease invariant, for simulation runs.
+//!
+//! Enabled by the `lease-check` feature.
+
+use std::fmt;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use openraft_macros::since;
+
+use crate::RaftTypeConfig;
+use crate::type_config::alias::InstantOf;
+use crate::type_config::alias::LeaderIdOf;
+
+/// The period during which a leader served reads on its lease: from the first to the last
+/// [`ReadPolicy::LeaseRead`] it served.
+///
+/// [`ReadPolicy::LeaseRead`]: crate::ReadPolicy::LeaseRead
+#[since(version = "0.10.0")]
+#[derive(Clone, PartialEq, Eq)]
+pub struct LeaseSpan<C>
+where C: RaftTypeConfig
+{
+    /// The node that served the reads.
+    pub node_id: C::NodeId,
+
+    /// The leader the node was when serving the reads.
+    pub leader_id: LeaderIdOf<C>,
+
+    /// When the first read is served, by the clock of `node_id`.
+    pub start: InstantOf<C>,
+
+    /// When the last read is served, by the clock of `node_id`.
+    pub end: InstantOf<C>,
+}
+
+impl<C> LeaseSpan<C>
+where C: RaftTypeConfig
+{
+    /// Whether two spans overlap, when each clock may be off by up to `max_clock_skew`.
+    fn overlaps(&self, other: &Self, max_clock_skew: Duration) -> bool {
+        self.start < other.end + max_clock_skew && other.start < self.end + max_clock_skew
+    }
+}
+
+impl<C> fmt::Debug for LeaseSpan<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LeaseSpan")
+            .field("node_id", &self.node_id)
+            .field("leader_id", &self.leader_id)
+            .field("start", &self.start)
+            .field("end", &self.end)
+            .finish()
+    }
+}
+
+impl<C> fmt::Display for LeaseSpan<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "node {} as leader {}: [{:?}, {:?}]",
+            self.node_id, self.leader_id, self.start, self.end
+        )
+    }
+}
+
+/// Asserts that no two leaders believe they hold a valid lease at the same time.
+///
+/// Share one checker among all the nodes of a simulated cluster with
+/// [`Raft::set_lease_checker()`]. Every [`ReadPolicy::LeaseRead`] a leader serves extends its
+/// [`LeaseSpan`]; the checker panics as soon as the spans of two different leaders overlap,
+/// allowing each clock to be off by up to `max_clock_skew`. Such an overlap means one of them
+/// served a read that may miss writes committed by the other, so a subtle lease bug fails the
+/// test right where it happens instead of as a rare stale read.
+///
+/// The panic happens in the `RaftCore` task of the node that served the offending read, which
+/// then stops with [`Fatal::Panicked`](crate::errors::Fatal::Panicked).
+///
+/// [`Raft::set_lease_checker()`]: crate::Raft::set_lease_checker
+/// [`ReadPolicy::LeaseRead`]: crate::ReadPolicy::LeaseRead
+#[since(version = "0.10.0")]
+#[derive(Clone)]
+pub struct LeaseChecker<C>
+where C: RaftTypeConfig
+{
+    max_clock_skew: Duration,
+    spans: Arc<Mutex<Vec<LeaseSpan<C>>>>,
+}
+
+impl<C> LeaseChecker<C>
+where C: RaftTypeConfig
+{
+    /// Create a checker that tolerates clocks being off by up to `max_clock_skew`.
+    #[since(version = "0.10.0")]
+    pub fn new(max_clock_skew: Duration) -> Self {
+        Self {
+            max_clock_skew,
+            spans: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// The lease span of every leader that has served a lease read so far.
+    #[since(version = "0.10.0")]
+    pub fn spans(&self) -> Vec<LeaseSpan<C>> {
+        self.spans.lock().unwrap().clone()
+    }
+
+    /// Record a lease read served by `node_id` as leader `leader_id` at `now`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the extended span overlaps the span of another leader.
+    pub(crate) fn on_lease_read(&self, node_id: C::NodeId, leader_id: LeaderIdOf<C>, now: InstantOf<C>) {
+        let mut spans = self.spans.lock().unwrap();
+
+        let i = match spans.iter().position(|s| s.node_id == node_id && s.leader_id == leader_id) {
+            Some(i) => {
+                let span = &mut spans[i];
+                span.start = std::cmp::min(span.start, now);
+                span.end = std::cmp::max(span.end, now);
+                i
+            }
+            None => {
+                spans.push(LeaseSpan {
+                    node_id,
+                    leader_id,
+                    start: now,
+                    end: now,
+                });
+                spans.len() - 1
+            }
+        };
+
+        let span = &spans[i];
+        let violation = spans
+            .iter()
+            .find(|other| other.leader_id != span.leader_id && span.overlaps(other, self.max_clock_skew))
+            .map(|other| format!("{} overlaps {}", span, other));
+
+        // Release the lock before panicking, so that other nodes do not see it poisoned.
+        drop(spans);
+
+        if let Some(violation) = violation {
+            panic!(
+                "lease invariant violated: {}, max clock skew: {:?}",
+                violation, self.max_clock_skew
+            );
+        }
+    }
+}
+
+impl<C> fmt::Debug for LeaseChecker<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LeaseChecker")
+            .field("max_clock_skew", &self.max_clock_skew)
+            .field("spans", &*self.spans.lock().unwrap())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::LeaseChecker;
+    use crate::engine::testing::UTConfig;
+    use crate::impls::leader_id_adv::LeaderId;
+    use crate::type_config::TypeConfigExt;
+    use crate::vote::RaftLeaderId;
+
+    #[test]
+    fn test_lease_checker_disjoint_spans() {
+        let checker = LeaseChecker::<UTConfig>::new(Duration::from_millis(10));
+        let t = <UTConfig>::now();
+
+        checker.on_lease_read(1, LeaderId::new(1, 1), t);
+        checker.on_lease_read(1, LeaderId::new(1, 1), t + Duration::from_millis(100));
+        checker.on_lease_read(2, LeaderId::new(2, 2), t + Duration::from_millis(111));
+
+        let spans = checker.spans();
+        assert_eq!(2, spans.len());
+        assert_eq!(t + Duration::from_millis(100), spans[0].end);
+    }
+
+    #[test]
+    #[should_panic(expected = "lease invariant violated")]
+    fn test_lease_checker_overlap_within_skew() {
+        let checker = LeaseChecker::<UTConfig>::new(Duration::from_millis(10));
+        let t = <UTConfig>::now();
+
+        checker.on_lease_read(1, LeaderId::new(1, 1), t + Duration::from_millis(100));
+        checker.on_lease_read(2, LeaderId::new(2, 2), t + Duration::from_millis(105));
+    }
+}
@@ -6,6 +6,7 @@
 //!
 //! - [`common`] - Common test utilities and assertions
 //! - [`log`] - Log storage test suite
+//! - `lease_check` - Leader lease invariant checker for simulation runs, with feature `lease-check`
 //! - [`runtime`] - Runtime test utilities (re-exported from `openraft_rt::testing`)
 //!
 //! ## Overview
@@ -29,6 +30,8 @@
 //! These tests help ensure correctness and catch subtle protocol violations.
 
 pub mod common;
+#[cfg(feature = "lease-check")]
+pub mod lease_check;
 pub mod log;
 
 pub use common::*;
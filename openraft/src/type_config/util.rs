@@ -150,6 +150,19 @@ pub trait TypeConfigExt: RaftTypeConfig {
         AsyncRuntimeOf::<Self>::spawn(future)
     }
 
+    /// Spawn a Raft IO task: a replication stream, a heartbeat worker or the state machine worker.
+    ///
+    /// This is just a wrapper of
+    /// [`AsyncRuntime::spawn_io()`](`crate::AsyncRuntime::spawn_io`).
+    #[track_caller]
+    fn spawn_io<T>(future: T) -> JoinHandleOf<Self, T::Output>
+    where
+        T: Future + OptionalSend + 'static,
+        T::Output: OptionalSend + 'static,
+    {
+        AsyncRuntimeOf::<Self>::spawn_io(future)
+    }
+
     /// Create a runtime and run the given future to completion.
     ///
     /// This is a convenience method for testing. It creates a runtime with
@@ -3,6 +3,7 @@
 use std::fmt;
 
 use crate::NodeId;
+use crate::entry::EncodeContent;
 use crate::vote::RaftLeaderId;
 use crate::vote::RaftTerm;
 
@@ -35,6 +36,17 @@ where
     }
 }
 
+impl<Term, NID> EncodeContent for LeaderId<Term, NID>
+where
+    Term: RaftTerm + EncodeContent,
+    NID: NodeId + EncodeContent,
+{
+    fn encode_content(&self, buf: &mut Vec<u8>) {
+        self.term.encode_content(buf);
+        self.node_id.encode_content(buf);
+    }
+}
+
 /// The unique identifier of a leader that is already granted by a quorum in phase-1(voting).
 ///
 /// [`CommittedLeaderId`] may contain less information than [`LeaderId`], because it implies the
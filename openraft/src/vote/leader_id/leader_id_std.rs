@@ -9,6 +9,7 @@ use std::ops::DerefMut;
 use display_more::DisplayOptionExt;
 
 use crate::NodeId;
+use crate::entry::EncodeContent;
 use crate::vote::LeaderIdCompare;
 use crate::vote::RaftLeaderId;
 use crate::vote::RaftTerm;
@@ -175,6 +176,14 @@ where Term: RaftTerm
     }
 }
 
+impl<Term> EncodeContent for CommittedLeaderId<Term>
+where Term: RaftTerm + EncodeContent
+{
+    fn encode_content(&self, buf: &mut Vec<u8>) {
+        self.term.encode_content(buf);
+    }
+}
+
 impl<Term> Deref for CommittedLeaderId<Term>
 where Term: RaftTerm
 {
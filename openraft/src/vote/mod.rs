@@ -6,6 +6,7 @@
 //!
 //! - [`Vote`] - A vote for a leader candidate, including term and node ID
 //! - [`RaftTerm`] - Raft term number for tracking leadership epochs
+//! - [`EraTerm`] - An `(era, term)` term that rolls over into the next era
 //! - [`RaftLeaderId`] - Identifier for a leader (term + node ID)
 //! - [`RaftCommittedLeaderId`] - Leader ID that has been committed
 //!
@@ -37,6 +38,7 @@ pub(crate) mod vote_status;
 pub use leader_id::raft_committed_leader_id::RaftCommittedLeaderId;
 pub use leader_id::raft_leader_id::RaftLeaderId;
 pub use leader_id::raft_leader_id::RaftLeaderIdExt;
+pub use raft_term::EraTerm;
 pub use raft_term::RaftTerm;
 pub use raft_vote::RaftVote;
 
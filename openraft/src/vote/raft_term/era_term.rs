@@ -0,0 +1,96 @@
+use std::fmt;
+
+use openraft_macros::since;
+
+use crate::vote::RaftTerm;
+
+/// A [`RaftTerm`] made of an `era` and a `term` in that era, compared lexicographically.
+///
+/// When the `term` of the last era is exhausted, [`checked_next()`](RaftTerm::checked_next) rolls
+/// over into the first term of the next era, so that a cluster never runs out of terms.
+///
+/// An application that re-provisions a cluster reusing node ids can also start it in a new era,
+/// e.g., by saving a vote of [`EraTerm::first_of(era)`](Self::first_of) before starting the
+/// nodes. Every vote and leader id of the new era is greater than those of the previous
+/// incarnation, which are then rejected.
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct EraTerm {
+    /// The era, which is increased when the terms of the previous era are exhausted.
+    pub era: u64,
+
+    /// The term in the era.
+    pub term: u64,
+}
+
+impl EraTerm {
+    /// Create a term of the given era.
+    pub fn new(era: u64, term: u64) -> Self {
+        Self { era, term }
+    }
+
+    /// Returns the first term of an era, which is greater than every term of the previous eras.
+    pub fn first_of(era: u64) -> Self {
+        Self { era, term: 0 }
+    }
+}
+
+impl fmt::Display for EraTerm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.era, self.term)
+    }
+}
+
+impl RaftTerm for EraTerm {
+    fn next(&self) -> Self {
+        self.checked_next().expect("Raft term overflow")
+    }
+
+    fn checked_next(&self) -> Option<Self> {
+        match self.term.checked_add(1) {
+            Some(term) => Some(Self::new(self.era, term)),
+            None => self.era.checked_add(1).map(Self::first_of),
+        }
+    }
+
+    /// Only the terms of the first era are recorded in metrics: a term of a later era can not be
+    /// compared with them as a `u64`.
+    fn as_u64(&self) -> Option<u64> {
+        if self.era == 0 { Some(self.term) } else { None }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EraTerm;
+    use crate::vote::RaftTerm;
+
+    #[test]
+    fn test_era_term_ord() {
+        assert!(EraTerm::new(0, 1) < EraTerm::new(0, 2));
+        assert!(EraTerm::new(0, u64::MAX) < EraTerm::new(1, 0));
+        assert!(EraTerm::new(1, 0) < EraTerm::new(1, 1));
+        assert_eq!(EraTerm::new(0, 0), EraTerm::default());
+    }
+
+    #[test]
+    fn test_era_term_checked_next() {
+        assert_eq!(Some(EraTerm::new(0, 2)), EraTerm::new(0, 1).checked_next());
+        assert_eq!(Some(EraTerm::new(1, 0)), EraTerm::new(0, u64::MAX).checked_next());
+        assert_eq!(None, EraTerm::new(u64::MAX, u64::MAX).checked_next());
+
+        assert_eq!(EraTerm::new(3, 1), EraTerm::first_of(3).next());
+    }
+
+    #[test]
+    fn test_era_term_as_u64() {
+        assert_eq!(Some(5), EraTerm::new(0, 5).as_u64());
+        assert_eq!(None, EraTerm::new(1, 5).as_u64());
+    }
+
+    #[test]
+    fn test_era_term_display() {
+        assert_eq!("2.7", EraTerm::new(2, 7).to_string());
+    }
+}
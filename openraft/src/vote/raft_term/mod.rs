@@ -1,3 +1,4 @@
+mod era_term;
 mod raft_term_impls;
 
 use std::fmt::Debug;
@@ -5,6 +6,7 @@ use std::fmt::Display;
 
 use openraft_macros::since;
 
+pub use self::era_term::EraTerm;
 use crate::base::OptionalFeatures;
 
 /// Type representing a Raft term number.
@@ -13,6 +15,18 @@ use crate::base::OptionalFeatures;
 /// such as old leaders. It must be totally ordered and monotonically increasing.
 ///
 /// Common implementations are provided for standard integer types like `u64`, `u32`, etc.
+///
+/// # Term exhaustion
+///
+/// A term never wraps around: a wrapped term would compare less than the terms already seen by
+/// the cluster and break the ordering of [`Vote`](crate::Vote) and leader ids. Instead, when
+/// [`checked_next()`](Self::checked_next) returns `None`, the node refuses to start an election
+/// and reports it in [`RaftMetrics::term_exhausted`](crate::metrics::RaftMetrics::term_exhausted);
+/// it can still follow a leader, and a leader keeps serving in its term.
+///
+/// An application that needs more room, or wants to fence off the votes and leader ids of a
+/// previous incarnation of a cluster that reuses node ids, can use [`EraTerm`], an `(era, term)`
+/// pair compared lexicographically, whose `checked_next()` rolls over into the next era.
 #[since(version = "0.10.0")]
 pub trait RaftTerm
 where Self: OptionalFeatures + Ord + Debug + Display + Copy + Default + 'static
@@ -22,6 +36,15 @@ where Self: OptionalFeatures + Ord + Debug + Display + Copy + Default + 'static
     /// Must satisfy: `self < self.next()`
     fn next(&self) -> Self;
 
+    /// Returns the next term, or `None` if there is no greater term to use.
+    ///
+    /// Openraft calls this instead of [`next()`](Self::next) when starting an election. The
+    /// default implementation assumes the term space is never exhausted.
+    #[since(version = "0.10.0")]
+    fn checked_next(&self) -> Option<Self> {
+        Some(self.next())
+    }
+
     /// Convert to u64 for metrics recording.
     ///
     /// Returns `None` if the term cannot be represented as u64.
@@ -6,7 +6,11 @@ macro_rules! impl_raft_term_infallible {
         $(
             impl RaftTerm for $t {
                 fn next(&self) -> Self {
-                    self + 1
+                    self.checked_next().expect("Raft term overflow")
+                }
+
+                fn checked_next(&self) -> Option<Self> {
+                    self.checked_add(1)
                 }
 
                 fn as_u64(&self) -> Option<u64> {
@@ -23,7 +27,11 @@ macro_rules! impl_raft_term_fallible {
         $(
             impl RaftTerm for $t {
                 fn next(&self) -> Self {
-                    self + 1
+                    self.checked_next().expect("Raft term overflow")
+                }
+
+                fn checked_next(&self) -> Option<Self> {
+                    self.checked_add(1)
                 }
 
                 fn as_u64(&self) -> Option<u64> {
@@ -66,6 +74,22 @@ mod tests {
         assert_eq!(254_u8.next(), 255_u8);
     }
 
+    #[test]
+    fn test_raft_term_checked_next() {
+        assert_eq!(1_u64.checked_next(), Some(2_u64));
+        assert_eq!(254_u8.checked_next(), Some(255_u8));
+
+        assert_eq!(u8::MAX.checked_next(), None);
+        assert_eq!(u64::MAX.checked_next(), None);
+        assert_eq!(i64::MAX.checked_next(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Raft term overflow")]
+    fn test_raft_term_next_overflow() {
+        let _ = u64::MAX.next();
+    }
+
     #[test]
     fn test_as_u64() {
         // Infallible conversions
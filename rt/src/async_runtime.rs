@@ -64,6 +64,21 @@ pub trait AsyncRuntime: Debug + OptionalSend + OptionalSync + 'static {
         T: Future + OptionalSend + 'static,
         T::Output: OptionalSend + 'static;
 
+    /// Spawn a Raft IO task: a replication stream, a heartbeat worker or the state machine worker.
+    ///
+    /// The default implementation calls [`Self::spawn`]. A runtime may override it to run these
+    /// tasks on a dedicated runtime or thread pool, e.g., with `tokio::runtime::Handle::spawn()`,
+    /// to isolate Raft IO from the latency-sensitive tasks of the application.
+    #[since(version = "0.10.0")]
+    #[track_caller]
+    fn spawn_io<T>(future: T) -> Self::JoinHandle<T::Output>
+    where
+        T: Future + OptionalSend + 'static,
+        T::Output: OptionalSend + 'static,
+    {
+        Self::spawn(future)
+    }
+
     /// Wait until `duration` has elapsed.
     #[track_caller]
     fn sleep(duration: Duration) -> Self::Sleep;
@@ -132,6 +132,16 @@ where RT: AsyncRuntime
         RT::spawn(DETSIM_SEED.scope(Cell::new(child_seed), future))
     }
 
+    #[inline]
+    fn spawn_io<T>(future: T) -> Self::JoinHandle<T::Output>
+    where
+        T: Future + OptionalSend + 'static,
+        T::Output: OptionalSend + 'static,
+    {
+        let child_seed = derive_spawn_seed(take_and_advance_seed());
+        RT::spawn_io(DETSIM_SEED.scope(Cell::new(child_seed), future))
+    }
+
     #[inline]
     fn sleep(duration: Duration) -> Self::Sleep {
         RT::sleep(duration)
@@ -41,6 +41,7 @@ pub use oneshot::Oneshot;
 pub use oneshot::OneshotSender;
 pub use threaded::BoxAny;
 pub use threaded::BoxAsyncOnceMut;
+pub use threaded::BoxFnMut;
 pub use threaded::BoxFuture;
 pub use threaded::BoxIterator;
 pub use threaded::BoxMaybeAsyncOnceMut;
@@ -83,6 +84,8 @@ mod threaded {
     pub type BoxMaybeAsyncOnceMut<'a, A, T = ()> = Box<dyn FnOnce(&mut A) -> Option<BoxFuture<T>> + Send + 'a>;
     /// Type alias for a boxed function that takes an argument and is `Send`.
     pub type BoxOnce<'a, A, T = ()> = Box<dyn FnOnce(&A) -> T + Send + 'a>;
+    /// Type alias for a boxed function that can be called repeatedly and is `Send`.
+    pub type BoxFnMut<'a, A, T = ()> = Box<dyn FnMut(A) -> T + Send + 'a>;
     /// Type alias for a boxed value that is `Send` and can be any type.
     pub type BoxAny = Box<dyn Any + Send>;
 }
@@ -117,6 +120,8 @@ mod threaded {
     pub type BoxMaybeAsyncOnceMut<'a, A, T = ()> = Box<dyn FnOnce(&mut A) -> Option<BoxFuture<T>> + 'a>;
     /// Type alias for a boxed function that takes an argument.
     pub type BoxOnce<'a, A, T = ()> = Box<dyn FnOnce(&A) -> T + 'a>;
+    /// Type alias for a boxed function that can be called repeatedly.
+    pub type BoxFnMut<'a, A, T = ()> = Box<dyn FnMut(A) -> T + 'a>;
     /// Type alias for a boxed value that can be any type.
     pub type BoxAny = Box<dyn Any>;
 }
@@ -42,6 +42,7 @@ impl<Rt: AsyncRuntime> Suite<Rt> {
         let mut rt = Rt::new(1);
         rt.block_on(async {
             Self::test_spawn_join_handle().await;
+            Self::test_spawn_io().await;
             Self::test_thread_rng().await;
             Self::test_sleep().await;
             Self::test_instant().await;
@@ -101,6 +102,15 @@ impl<Rt: AsyncRuntime> Suite<Rt> {
         }
     }
 
+    /// Test `spawn_io()` runs the task and returns its output through the join handle.
+    pub async fn test_spawn_io() {
+        for ret_number in 0..10 {
+            let handle = Rt::spawn_io(async move { ret_number });
+            let ret_value = handle.await.unwrap();
+            assert_eq!(ret_value, ret_number);
+        }
+    }
+
     /// Test `thread_rng()` returns a working random number generator.
     pub async fn test_thread_rng() {
         use rand::RngExt;
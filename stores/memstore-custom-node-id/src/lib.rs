@@ -20,6 +20,7 @@ use openraft::alias::SnapshotDataOf;
 use openraft::alias::SnapshotMetaOf;
 use openraft::alias::SnapshotOf;
 use openraft::alias::StoredMembershipOf;
+use openraft::entry::EncodeContent;
 use openraft::entry::RaftEntry;
 use openraft::storage::EntryResponder;
 use openraft::storage::IOFlushed;
@@ -58,6 +59,12 @@ impl std::fmt::Display for NodeId {
     }
 }
 
+impl EncodeContent for NodeId {
+    fn encode_content(&self, buf: &mut Vec<u8>) {
+        self.0.encode_content(buf);
+    }
+}
+
 impl From<u64> for NodeId {
     fn from(v: u64) -> Self {
         NodeId(v)
@@ -74,6 +81,10 @@ impl std::fmt::Display for AppReq {
     }
 }
 
+impl EncodeContent for AppReq {
+    fn encode_content(&self, _buf: &mut Vec<u8>) {}
+}
+
 openraft::declare_raft_types!(
     pub TypeConfig:
         D = AppReq,
@@ -244,6 +255,7 @@ impl RaftSnapshotBuilder<TypeConfig> for Arc<MemStateMachine> {
             last_log_id: sm.last_applied_log,
             last_membership: sm.last_membership.clone(),
             snapshot_id,
+            base_snapshot_id: None,
         };
 
         *self.current_snapshot.write().await = Some((meta.clone(), data.clone()));
@@ -6,10 +6,12 @@ mod test;
 
 use std::collections::BTreeMap;
 use std::collections::HashMap;
+use std::collections::VecDeque;
 use std::fmt::Debug;
 use std::io;
 use std::io::Cursor;
 use std::ops::RangeBounds;
+use std::ops::RangeInclusive;
 use std::sync::Arc;
 use std::sync::Mutex;
 use std::sync::atomic::AtomicBool;
@@ -20,6 +22,7 @@ use std::time::Duration;
 use futures::Stream;
 use openraft::EntryPayload;
 use openraft::OptionalSend;
+use openraft::SnapshotId;
 use openraft::Vote;
 use openraft::alias::EntryOf;
 use openraft::alias::LogIdOf;
@@ -27,14 +30,20 @@ use openraft::alias::SnapshotDataOf;
 use openraft::alias::SnapshotMetaOf;
 use openraft::alias::SnapshotOf;
 use openraft::alias::StoredMembershipOf;
+use openraft::entry::EncodeContent;
 use openraft::entry::RaftEntry;
+use openraft::storage::ApplyResponder;
 use openraft::storage::EntryResponder;
 use openraft::storage::IOFlushed;
+use openraft::storage::LogEntryMeta;
 use openraft::storage::LogState;
+use openraft::storage::PayloadStream;
 use openraft::storage::RaftLogReader;
 use openraft::storage::RaftLogStorage;
 use openraft::storage::RaftSnapshotBuilder;
 use openraft::storage::RaftStateMachine;
+use openraft::storage::SnapshotFetched;
+use openraft::storage::SnapshotLocator;
 use openraft::type_config::TypeConfigExt;
 use serde::Deserialize;
 use serde::Serialize;
@@ -75,12 +84,24 @@ impl IntoMemClientRequest<ClientRequest> for ClientRequest {
     }
 }
 
+impl EncodeContent for ClientRequest {
+    fn encode_content(&self, buf: &mut Vec<u8>) {
+        self.client.encode_content(buf);
+        self.serial.encode_content(buf);
+        self.status.encode_content(buf);
+    }
+}
+
 /// The application data response type which the `MemStore` works with.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct ClientResponse(pub Option<String>);
 
 pub type MemNodeId = u64;
 
+/// A shared store of snapshot data by locator, standing in for an object store, that
+/// [`MemStateMachine`] uploads snapshots to and fetches them from.
+pub type ExternalSnapshotStore = Arc<Mutex<BTreeMap<String, Vec<u8>>>>;
+
 /// Choose a LeaderId implementation by feature flag.
 mod leader_id_mode {
     #[cfg(not(feature = "single-term-leader"))]
@@ -107,6 +128,23 @@ pub struct MemStoreSnapshot {
     pub data: Vec<u8>,
 }
 
+/// The changes of [`MemStoreStateMachine::client_status`] since a base snapshot, which is the data
+/// of a delta snapshot.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct MemStoreDelta {
+    /// The clients whose status is added or changed.
+    pub changed: HashMap<String, String>,
+
+    /// The clients whose status is removed.
+    pub removed: Vec<String>,
+}
+
+/// The number of the most recent snapshots kept as bases of delta snapshots.
+const SNAPSHOT_HISTORY: usize = 8;
+
+/// The size of the chunks a payload stream yields.
+const PAYLOAD_CHUNK_SIZE: usize = 64;
+
 /// The state machine of the `MemStore`.
 #[derive(Serialize, Deserialize, Debug, Default, Clone)]
 pub struct MemStoreStateMachine {
@@ -127,6 +165,8 @@ pub enum BlockOperation {
     DelayBuildingSnapshot,
     BuildSnapshot,
     PurgeLog,
+    /// Block warming up the state machine after installing a snapshot.
+    WarmUp,
 }
 
 /// Block operations for testing purposes.
@@ -179,6 +219,9 @@ pub struct MemLogStore {
 
     /// When set to true, the next `limited_get_log_entries` call will return an IO error.
     pub fail_next_limited_get: AtomicBool,
+
+    /// When set to true, every `append` call returns an IO error.
+    pub fail_append: AtomicBool,
 }
 
 impl MemLogStore {
@@ -194,6 +237,7 @@ impl MemLogStore {
             vote: RwLock::new(None),
             return_empty_limited_get: AtomicBool::new(false),
             fail_next_limited_get: AtomicBool::new(false),
+            fail_append: AtomicBool::new(false),
         }
     }
 
@@ -207,6 +251,11 @@ impl MemLogStore {
     pub fn set_fail_next_limited_get(&self, value: bool) {
         self.fail_next_limited_get.store(value, Ordering::Relaxed);
     }
+
+    /// Make every `append` call fail with an IO error.
+    pub fn set_fail_append(&self, value: bool) {
+        self.fail_append.store(value, Ordering::Relaxed);
+    }
 }
 
 /// An in-memory key-value storage implementing the `RaftStateMachine` trait.
@@ -221,11 +270,26 @@ pub struct MemStateMachine {
     /// The current snapshot.
     current_snapshot: RwLock<Option<MemStoreSnapshot>>,
 
+    /// Whether to build delta snapshots, see [`Self::enable_delta_snapshot`].
+    delta_snapshot: Arc<AtomicBool>,
+
+    /// Whether to report applied entries as persisted, see [`Self::enable_durable_applied`].
+    durable_applied: Arc<AtomicBool>,
+
+    /// The id and data of the most recent snapshots built or installed, the last is the latest.
+    snapshot_history: Mutex<VecDeque<(SnapshotId, Vec<u8>)>>,
+
+    /// Where to store snapshots out of band, see [`Self::set_external_snapshot_store`].
+    external_snapshot_store: Mutex<Option<ExternalSnapshotStore>>,
+
     /// Block operations for testing purposes.
     pub block: BlockConfig,
 
     /// Counter for testing: tracks how many times `try_create_snapshot_builder` is called.
     pub try_create_snapshot_builder_count: Arc<AtomicU64>,
+
+    /// Counter for testing: tracks how many entries are applied from a payload stream.
+    pub payload_stream_count: Arc<AtomicU64>,
 }
 
 impl MemStateMachine {
@@ -238,11 +302,57 @@ impl MemStateMachine {
             allow_build_snapshot: Arc::new(AtomicBool::new(true)),
             snapshot_idx: Arc::new(Mutex::new(0)),
             current_snapshot,
+            delta_snapshot: Arc::new(AtomicBool::new(false)),
+            durable_applied: Arc::new(AtomicBool::new(false)),
+            snapshot_history: Mutex::new(VecDeque::new()),
+            external_snapshot_store: Mutex::new(None),
             block,
             try_create_snapshot_builder_count: Arc::new(AtomicU64::new(0)),
+            payload_stream_count: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Build a delta snapshot for a follower that has installed one of the recent snapshots.
+    pub fn enable_delta_snapshot(&self, enabled: bool) {
+        self.delta_snapshot.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Report every applied entry as persisted, as a state machine backed by a disk store would.
+    ///
+    /// This store is in memory, so it is only for testing `PurgePolicy::DurableApplied`.
+    pub fn enable_durable_applied(&self, enabled: bool) {
+        self.durable_applied.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Store snapshots out of band in `store`, so that only their locators are sent to
+    /// followers, which fetch the snapshot data from the same `store`.
+    pub fn set_external_snapshot_store(&self, store: Option<ExternalSnapshotStore>) {
+        *self.external_snapshot_store.lock().unwrap() = store;
+    }
+
+    fn external_snapshot_store(&self) -> Option<ExternalSnapshotStore> {
+        self.external_snapshot_store.lock().unwrap().clone()
+    }
+
+    fn remember_snapshot(&self, snapshot_id: SnapshotId, data: Vec<u8>) {
+        let mut history = self.snapshot_history.lock().unwrap();
+        history.push_back((snapshot_id, data));
+        while history.len() > SNAPSHOT_HISTORY {
+            history.pop_front();
         }
     }
 
+    /// The ids of the snapshots kept for building delta snapshots, oldest first.
+    pub fn kept_snapshot_ids(&self) -> Vec<SnapshotId> {
+        let history = self.snapshot_history.lock().unwrap();
+        history.iter().map(|(id, _)| id.clone()).collect()
+    }
+
+    fn snapshot_in_history(&self, snapshot_id: &SnapshotId) -> Option<Vec<u8>> {
+        let history = self.snapshot_history.lock().unwrap();
+        history.iter().find(|(id, _)| id == snapshot_id).map(|(_, data)| data.clone())
+    }
+
     pub fn allow_build_snapshot(&self, allowed: bool) {
         self.allow_build_snapshot.store(allowed, Ordering::Relaxed);
     }
@@ -252,6 +362,11 @@ impl MemStateMachine {
         self.try_create_snapshot_builder_count.swap(0, Ordering::Relaxed)
     }
 
+    /// Get and reset the counter of entries applied from a payload stream.
+    pub fn take_payload_stream_count(&self) -> u64 {
+        self.payload_stream_count.swap(0, Ordering::Relaxed)
+    }
+
     /// Remove the current snapshot.
     ///
     /// This method is only used for testing purposes.
@@ -265,6 +380,63 @@ impl MemStateMachine {
         self.sm.write().await.clone()
     }
 
+    /// Install the snapshot `data` with `meta`, which is a delta if `meta.base_snapshot_id` is
+    /// set.
+    async fn install_snapshot_data(&self, meta: &SnapshotMetaOf<TypeConfig>, data: Vec<u8>) -> Result<(), io::Error> {
+        let data = match &meta.base_snapshot_id {
+            None => data,
+            Some(base) => {
+                let base_data = self.snapshot_in_history(base).ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::NotFound, format!("base snapshot not found: {}", base))
+                })?;
+                let mut sm: MemStoreStateMachine = serde_json::from_slice(&base_data)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+                let delta: MemStoreDelta = serde_json::from_slice(&data)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+                sm.client_status.extend(delta.changed);
+                for client in delta.removed {
+                    sm.client_status.remove(&client);
+                }
+                sm.last_applied_log = meta.last_log_id;
+                sm.last_membership = meta.last_membership.clone();
+                serde_json::to_vec(&sm).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?
+            }
+        };
+
+        let mut meta = meta.clone();
+        meta.base_snapshot_id = None;
+
+        let new_snapshot = MemStoreSnapshot { meta, data };
+        let meta = &new_snapshot.meta;
+
+        {
+            let t = &new_snapshot.data;
+            let y = std::str::from_utf8(t).unwrap();
+            tracing::debug!("SNAP META:{:?}", meta);
+            tracing::debug!("JSON SNAP DATA:{}", y);
+        }
+
+        // Update the state machine.
+        {
+            let mut new_sm: MemStoreStateMachine = serde_json::from_slice(&new_snapshot.data)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+            // The meta is authoritative, e.g., its membership may be rewritten by `NodeRemap`.
+            new_sm.last_applied_log = meta.last_log_id;
+            new_sm.last_membership = meta.last_membership.clone();
+
+            let mut sm = self.sm.write().await;
+            *sm = new_sm;
+        }
+
+        // Update current snapshot.
+        self.remember_snapshot(new_snapshot.meta.snapshot_id.clone(), new_snapshot.data.clone());
+        let mut current_snapshot = self.current_snapshot.write().await;
+        *current_snapshot = Some(new_snapshot);
+        Ok(())
+    }
+
     /// Clear the state machine for testing purposes.
     pub async fn clear_state_machine(&self) {
         let mut sm = self.sm.write().await;
@@ -302,6 +474,44 @@ impl RaftLogReader<TypeConfig> for Arc<MemLogStore> {
         Ok(*self.vote.read().await)
     }
 
+    async fn get_log_meta(&mut self, range: RangeInclusive<u64>) -> Result<Vec<LogEntryMeta<TypeConfig>>, io::Error> {
+        let log = self.log.read().await;
+
+        let mut metas = vec![];
+        for (_, serialized) in log.range(range) {
+            let ent: EntryOf<TypeConfig> = serde_json::from_str(serialized)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+            metas.push(LogEntryMeta {
+                log_id: ent.log_id(),
+                kind: ent.kind(),
+                size: Some(serialized.len() as u64),
+            });
+        }
+
+        Ok(metas)
+    }
+
+    async fn payload_stream(&mut self, log_id: &LogIdOf<TypeConfig>) -> Result<Option<PayloadStream>, io::Error> {
+        let ent: EntryOf<TypeConfig> = {
+            let log = self.log.read().await;
+            let serialized = log
+                .get(&log_id.index)
+                .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("log not found: {}", log_id)))?;
+            serde_json::from_str(serialized).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?
+        };
+
+        let EntryPayload::Normal(data) = ent.payload else {
+            return Ok(None);
+        };
+
+        // The log is in memory: split the encoded payload in chunks as a disk store would read it.
+        let encoded =
+            serde_json::to_vec(&data).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+        let chunks = encoded.chunks(PAYLOAD_CHUNK_SIZE).map(|c| Ok(c.to_vec())).collect::<Vec<_>>();
+
+        Ok(Some(Box::pin(futures::stream::iter(chunks))))
+    }
+
     async fn limited_get_log_entries(&mut self, start: u64, end: u64) -> Result<Vec<EntryOf<TypeConfig>>, io::Error> {
         if self.fail_next_limited_get.swap(false, Ordering::Relaxed) {
             tracing::info!(
@@ -368,6 +578,7 @@ impl RaftSnapshotBuilder<TypeConfig> for Arc<MemStateMachine> {
             last_log_id: last_applied_log,
             last_membership,
             snapshot_id,
+            base_snapshot_id: None,
         };
 
         let snapshot = MemStoreSnapshot {
@@ -375,6 +586,7 @@ impl RaftSnapshotBuilder<TypeConfig> for Arc<MemStateMachine> {
             data: data.clone(),
         };
 
+        self.remember_snapshot(meta.snapshot_id.clone(), data.clone());
         {
             let mut current_snapshot = self.current_snapshot.write().await;
             *current_snapshot = Some(snapshot);
@@ -455,6 +667,11 @@ impl RaftLogStorage<TypeConfig> for Arc<MemLogStore> {
     #[tracing::instrument(level = "trace", skip_all)]
     async fn append<I>(&mut self, entries: I, callback: IOFlushed<TypeConfig>) -> Result<(), io::Error>
     where I: IntoIterator<Item = EntryOf<TypeConfig>> + OptionalSend {
+        if self.fail_append.load(Ordering::Relaxed) {
+            tracing::info!("append: returning io::Error for testing");
+            return Err(io::Error::other("injected append error"));
+        }
+
         let mut log = self.log.write().await;
         for entry in entries {
             let s =
@@ -525,6 +742,15 @@ impl RaftStateMachine<TypeConfig> for Arc<MemStateMachine> {
         Ok((sm.last_applied_log, sm.last_membership.clone()))
     }
 
+    async fn durable_applied(&mut self) -> Result<Option<LogIdOf<TypeConfig>>, io::Error> {
+        if !self.durable_applied.load(Ordering::Relaxed) {
+            return Ok(None);
+        }
+
+        let sm = self.sm.read().await;
+        Ok(sm.last_applied_log)
+    }
+
     #[tracing::instrument(level = "trace", skip(self, entries))]
     async fn apply<Strm>(&mut self, mut entries: Strm) -> Result<(), io::Error>
     where Strm: Stream<Item = Result<EntryResponder<TypeConfig>, io::Error>> + Unpin + OptionalSend {
@@ -550,12 +776,40 @@ impl RaftStateMachine<TypeConfig> for Arc<MemStateMachine> {
             };
 
             if let Some(responder) = responder {
-                responder.send(response);
+                responder.send_and_cache(response);
             }
         }
         Ok(())
     }
 
+    #[tracing::instrument(level = "trace", skip(self, payload, responder))]
+    async fn apply_payload_stream(
+        &mut self,
+        log_id: LogIdOf<TypeConfig>,
+        mut payload: PayloadStream,
+        responder: Option<ApplyResponder<TypeConfig>>,
+    ) -> Result<(), io::Error> {
+        use futures::TryStreamExt;
+
+        let mut encoded = vec![];
+        while let Some(chunk) = payload.try_next().await? {
+            encoded.extend_from_slice(&chunk);
+        }
+        let data: ClientRequest =
+            serde_json::from_slice(&encoded).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+        self.payload_stream_count.fetch_add(1, Ordering::Relaxed);
+
+        let mut sm = self.sm.write().await;
+        sm.last_applied_log = Some(log_id);
+
+        let previous = sm.client_status.insert(data.client, data.status);
+        if let Some(responder) = responder {
+            responder.send_and_cache(ClientResponse(previous));
+        }
+        Ok(())
+    }
+
     async fn try_create_snapshot_builder(&mut self, force: bool) -> Option<Self::SnapshotBuilder> {
         self.try_create_snapshot_builder_count.fetch_add(1, Ordering::Relaxed);
 
@@ -586,29 +840,132 @@ impl RaftStateMachine<TypeConfig> for Arc<MemStateMachine> {
             "decoding snapshot for installation"
         );
 
-        let new_snapshot = MemStoreSnapshot {
-            meta: meta.clone(),
-            data: snapshot.into_inner(),
+        self.install_snapshot_data(meta, snapshot.into_inner()).await
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    async fn get_delta_snapshot(&mut self, base: &SnapshotId) -> Result<Option<SnapshotOf<TypeConfig>>, io::Error> {
+        if !self.delta_snapshot.load(Ordering::Relaxed) {
+            return Ok(None);
+        }
+
+        let Some(base_data) = self.snapshot_in_history(base) else {
+            return Ok(None);
         };
 
-        {
-            let t = &new_snapshot.data;
-            let y = std::str::from_utf8(t).unwrap();
-            tracing::debug!("SNAP META:{:?}", meta);
-            tracing::debug!("JSON SNAP DATA:{}", y);
+        let current = self.current_snapshot.read().await;
+        let Some(current) = current.as_ref() else {
+            return Ok(None);
+        };
+
+        let decode = |data: &[u8]| -> Result<MemStoreStateMachine, io::Error> {
+            serde_json::from_slice(data).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))
+        };
+        let base_sm = decode(&base_data)?;
+        let curr_sm = decode(&current.data)?;
+
+        let mut delta = MemStoreDelta::default();
+        for (client, status) in curr_sm.client_status.iter() {
+            if base_sm.client_status.get(client) != Some(status) {
+                delta.changed.insert(client.clone(), status.clone());
+            }
+        }
+        for client in base_sm.client_status.keys() {
+            if !curr_sm.client_status.contains_key(client) {
+                delta.removed.push(client.clone());
+            }
         }
 
-        // Update the state machine.
-        {
-            let new_sm: MemStoreStateMachine = serde_json::from_slice(&new_snapshot.data)
-                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
-            let mut sm = self.sm.write().await;
-            *sm = new_sm;
+        let data = serde_json::to_vec(&delta).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+        let mut meta = current.meta.clone();
+        meta.base_snapshot_id = Some(base.clone());
+
+        tracing::info!(delta_size = data.len(), "built delta snapshot: {}", meta);
+
+        Ok(Some(SnapshotOf::<TypeConfig> {
+            meta,
+            snapshot: Cursor::new(data),
+        }))
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    async fn get_snapshot_locator(&mut self) -> Result<Option<SnapshotLocator<TypeConfig>>, io::Error> {
+        let Some(store) = self.external_snapshot_store() else {
+            return Ok(None);
+        };
+
+        let current = self.current_snapshot.read().await;
+        let Some(current) = current.as_ref() else {
+            return Ok(None);
+        };
+
+        let locator = format!("mem://{}", current.meta.snapshot_id);
+        store.lock().unwrap().insert(locator.clone(), current.data.clone());
+
+        Ok(Some(SnapshotLocator {
+            meta: current.meta.clone(),
+            locator,
+        }))
+    }
+
+    #[tracing::instrument(level = "trace", skip(self, done))]
+    async fn install_snapshot_locator(
+        &mut self,
+        locator: &SnapshotLocator<TypeConfig>,
+        done: SnapshotFetched<TypeConfig>,
+    ) -> Result<(), io::Error> {
+        let Some(store) = self.external_snapshot_store() else {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "no external snapshot store is set",
+            ));
+        };
+
+        let this = self.clone();
+        let locator = locator.clone();
+
+        // Fetch in the background, as fetching from a remote store would.
+        TypeConfig::spawn(async move {
+            let data = store.lock().unwrap().get(&locator.locator).cloned();
+            let res = match data {
+                Some(data) => this.install_snapshot_data(&locator.meta, data).await,
+                None => Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("snapshot not found: {}", locator.locator),
+                )),
+            };
+            done.completed(res);
+        });
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    async fn install_snapshot_meta(&mut self, meta: &SnapshotMetaOf<TypeConfig>) -> Result<(), io::Error> {
+        // A witness keeps no data: install a blank state machine with the applied state of `meta`.
+        let mut meta = meta.clone();
+        meta.base_snapshot_id = None;
+
+        let data = serde_json::to_vec(&MemStoreStateMachine::default())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+        self.install_snapshot_data(&meta, data).await
+    }
+
+    async fn warm_up(&mut self, meta: &SnapshotMetaOf<TypeConfig>) -> Result<(), io::Error> {
+        if let Some(d) = self.block.get_blocking(&BlockOperation::WarmUp) {
+            tracing::info!(?d, "blocking warm up after installing snapshot: {}", meta);
+            TypeConfig::sleep(d).await;
         }
+        Ok(())
+    }
 
-        // Update current snapshot.
-        let mut current_snapshot = self.current_snapshot.write().await;
-        *current_snapshot = Some(new_snapshot);
+    async fn remove_snapshots_before(&mut self, keep_from: &SnapshotMetaOf<TypeConfig>) -> Result<(), io::Error> {
+        let mut history = self.snapshot_history.lock().unwrap();
+        if let Some(pos) = history.iter().position(|(id, _)| id == &keep_from.snapshot_id) {
+            history.drain(..pos);
+        }
         Ok(())
     }
 
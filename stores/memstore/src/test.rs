@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use openraft::StorageError;
+use openraft::storage::BufferedLogStorage;
 use openraft::testing::log::StoreBuilder;
 use openraft::testing::log::Suite;
 use openraft::type_config::TypeConfigExt;
@@ -24,3 +25,30 @@ pub fn test_mem_store() {
         Suite::test_all(MemStoreBuilder {}).await.unwrap();
     });
 }
+
+struct BufferedMemStoreBuilder {}
+
+impl StoreBuilder<TypeConfig, BufferedLogStorage<TypeConfig, Arc<MemLogStore>>, Arc<MemStateMachine>, ()>
+    for BufferedMemStoreBuilder
+{
+    async fn build(
+        &self,
+    ) -> Result<
+        (
+            (),
+            BufferedLogStorage<TypeConfig, Arc<MemLogStore>>,
+            Arc<MemStateMachine>,
+        ),
+        StorageError<TypeConfig>,
+    > {
+        let (log_store, sm) = crate::new_mem_store();
+        Ok(((), BufferedLogStorage::new(log_store, 4), sm))
+    }
+}
+
+#[test]
+pub fn test_buffered_mem_store() {
+    TypeConfig::run(async {
+        Suite::test_all(BufferedMemStoreBuilder {}).await.unwrap();
+    });
+}
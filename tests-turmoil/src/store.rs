@@ -184,6 +184,7 @@ impl RaftSnapshotBuilder<TypeConfig> for Arc<StateMachine> {
             last_log_id: data.last_applied,
             last_membership: data.last_membership.clone(),
             snapshot_id: snapshot_idx.to_string(),
+            base_snapshot_id: None,
         };
 
         *self.snapshot.lock().unwrap() = Some((meta.clone(), snapshot_data.clone()));
@@ -14,6 +14,8 @@ mod t11_append_conflicts;
 mod t11_append_entries_with_bigger_term;
 mod t11_append_inconsistent_log;
 mod t11_append_updates_membership;
+mod t12_relaxed_durability;
+mod t13_malformed_message;
 mod t30_replication_1_voter_to_isolated_learner;
 mod t60_enable_heartbeat;
 mod t61_heartbeat_reject_vote;
@@ -53,6 +53,9 @@ async fn conflict_with_empty_entries() -> Result<()> {
         prev_log_id: Some(log_id(1, 0, 5)),
         entries: vec![],
         leader_commit: Some(log_id(1, 0, 5)),
+        backup_barrier: None,
+        after_snapshot: None,
+        config_digest: None,
     };
 
     let node = router.get_raft_handle(&0)?;
@@ -75,9 +78,17 @@ async fn conflict_with_empty_entries() -> Result<()> {
                     serial: 1,
                     status: "bar".to_string(),
                 }),
+                apply_scope: None,
+                timestamp: None,
+                deadline: None,
+                expire_from: None,
+                chain_hash: None,
             },
         ],
         leader_commit: Some(log_id(1, 0, 5)),
+        backup_barrier: None,
+        after_snapshot: None,
+        config_digest: None,
     };
 
     let node = router.get_raft_handle(&0)?;
@@ -92,6 +103,9 @@ async fn conflict_with_empty_entries() -> Result<()> {
         prev_log_id: Some(log_id(1, 0, 3)),
         entries: vec![],
         leader_commit: Some(log_id(1, 0, 5)),
+        backup_barrier: None,
+        after_snapshot: None,
+        config_digest: None,
     };
 
     let node = router.get_raft_handle(&0)?;
@@ -45,6 +45,8 @@ async fn append_sees_higher_vote() -> Result<()> {
                 vote: Vote::new(10, 1),
                 last_log_id: Some(log_id(10, 1, 5)),
                 leadership_transfer: false,
+                relaxed_durability: false,
+                config_digest: None,
             })
             .await?;
 
@@ -5,6 +5,7 @@ use anyhow::Result;
 use futures::StreamExt;
 use openraft::Config;
 use openraft::Vote;
+use openraft::raft::AppendEntriesChunks;
 use openraft::raft::AppendEntriesRequest;
 use openraft::raft::StreamAppendError;
 use openraft::raft::VoteRequest;
@@ -40,6 +41,9 @@ async fn stream_append_success() -> Result<()> {
                 blank_ent::<openraft_memstore::TypeConfig>(1, 1, 1),
             ],
             leader_commit: None,
+            backup_barrier: None,
+            after_snapshot: None,
+            config_digest: None,
         },
         AppendEntriesRequest::<openraft_memstore::TypeConfig> {
             vote: Vote::new_committed(1, 1),
@@ -49,12 +53,18 @@ async fn stream_append_success() -> Result<()> {
                 blank_ent::<openraft_memstore::TypeConfig>(1, 1, 3),
             ],
             leader_commit: None,
+            backup_barrier: None,
+            after_snapshot: None,
+            config_digest: None,
         },
         AppendEntriesRequest::<openraft_memstore::TypeConfig> {
             vote: Vote::new_committed(1, 1),
             prev_log_id: Some(log_id(1, 1, 3)),
             entries: vec![blank_ent::<openraft_memstore::TypeConfig>(1, 1, 4)],
             leader_commit: Some(log_id(1, 1, 4)),
+            backup_barrier: None,
+            after_snapshot: None,
+            config_digest: None,
         },
     ];
 
@@ -71,6 +81,51 @@ async fn stream_append_success() -> Result<()> {
     Ok(())
 }
 
+/// Test stream_append with a large batch of entries split by `AppendEntriesChunks` from an
+/// iterator, without building the whole batch.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn stream_append_chunks() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+    router.new_raft_node(0).await;
+
+    let raft = router.get_raft_handle(&0)?;
+
+    let first = AppendEntriesRequest::<openraft_memstore::TypeConfig> {
+        vote: Vote::new_committed(1, 1),
+        prev_log_id: None,
+        entries: vec![blank_ent::<openraft_memstore::TypeConfig>(0, 0, 0)],
+        leader_commit: Some(log_id(1, 1, 1000)),
+        backup_barrier: None,
+        after_snapshot: None,
+        config_digest: None,
+    };
+    let entries = (1..=1000).map(|i| blank_ent::<openraft_memstore::TypeConfig>(1, 1, i));
+    let chunks = AppendEntriesChunks::new(first, entries, 300);
+
+    let output_stream = pin!(raft.stream_append(futures::stream::iter(chunks)));
+
+    let results: Vec<_> = output_stream.collect().await;
+    assert_eq!(results, vec![
+        Ok(Ok(Some(log_id(1, 1, 299)))),
+        Ok(Ok(Some(log_id(1, 1, 599)))),
+        Ok(Ok(Some(log_id(1, 1, 899)))),
+        Ok(Ok(Some(log_id(1, 1, 1000)))),
+    ]);
+
+    raft.wait(None).applied_index(Some(1000), "apply all chunks").await?;
+
+    Ok(())
+}
+
 /// Test stream_append terminates on conflict.
 #[tracing::instrument]
 #[test_harness::test(harness = ut_harness)]
@@ -98,6 +153,9 @@ async fn stream_append_conflict() -> Result<()> {
                 blank_ent::<openraft_memstore::TypeConfig>(1, 1, 1),
             ],
             leader_commit: None,
+            backup_barrier: None,
+            after_snapshot: None,
+            config_digest: None,
         },
         // This will conflict: prev_log_id at index 5 doesn't exist
         AppendEntriesRequest::<openraft_memstore::TypeConfig> {
@@ -105,6 +163,9 @@ async fn stream_append_conflict() -> Result<()> {
             prev_log_id: Some(log_id(1, 1, 5)),
             entries: vec![blank_ent::<openraft_memstore::TypeConfig>(1, 1, 6)],
             leader_commit: None,
+            backup_barrier: None,
+            after_snapshot: None,
+            config_digest: None,
         },
         // This should never be processed because stream terminates on conflict
         AppendEntriesRequest::<openraft_memstore::TypeConfig> {
@@ -112,6 +173,9 @@ async fn stream_append_conflict() -> Result<()> {
             prev_log_id: Some(log_id(1, 1, 6)),
             entries: vec![blank_ent::<openraft_memstore::TypeConfig>(1, 1, 7)],
             leader_commit: None,
+            backup_barrier: None,
+            after_snapshot: None,
+            config_digest: None,
         },
     ];
 
@@ -150,6 +214,8 @@ async fn stream_append_higher_vote() -> Result<()> {
             vote: Vote::new(10, 2),
             last_log_id: Some(log_id(10, 2, 100)),
             leadership_transfer: false,
+            relaxed_durability: false,
+            config_digest: None,
         })
         .await?;
     assert!(resp.is_granted_to(&Vote::new(10, 2)));
@@ -161,6 +227,9 @@ async fn stream_append_higher_vote() -> Result<()> {
             prev_log_id: None,
             entries: vec![blank_ent::<openraft_memstore::TypeConfig>(0, 0, 0)],
             leader_commit: None,
+            backup_barrier: None,
+            after_snapshot: None,
+            config_digest: None,
         },
         // This should never be processed
         AppendEntriesRequest::<openraft_memstore::TypeConfig> {
@@ -168,6 +237,9 @@ async fn stream_append_higher_vote() -> Result<()> {
             prev_log_id: Some(log_id(0, 0, 0)),
             entries: vec![blank_ent::<openraft_memstore::TypeConfig>(1, 1, 1)],
             leader_commit: None,
+            backup_barrier: None,
+            after_snapshot: None,
+            config_digest: None,
         },
     ];
 
@@ -44,10 +44,13 @@ async fn append_conflicts() -> Result<()> {
     tracing::info!("--- case 0: prev_log_id == None, no logs");
 
     let req = AppendEntriesRequest {
-        vote: Vote::new_committed(1, 2),
+        vote: Vote::new_committed(3, 2),
         prev_log_id: None,
         entries: vec![],
         leader_commit: Some(log_id(1, 0, 2)),
+        backup_barrier: None,
+        after_snapshot: None,
+        config_digest: None,
     };
 
     let resp = r0.append_entries(req).await?;
@@ -58,10 +61,13 @@ async fn append_conflicts() -> Result<()> {
     tracing::info!("--- case 0: prev_log_id == None, 1 logs");
 
     let req = AppendEntriesRequest {
-        vote: Vote::new_committed(1, 2),
+        vote: Vote::new_committed(3, 2),
         prev_log_id: None,
         entries: vec![blank_ent::<openraft_memstore::TypeConfig>(0, 0, 0)],
         leader_commit: Some(log_id(1, 0, 2)),
+        backup_barrier: None,
+        after_snapshot: None,
+        config_digest: None,
     };
 
     let resp = r0.append_entries(req).await?;
@@ -71,10 +77,13 @@ async fn append_conflicts() -> Result<()> {
     tracing::info!("--- case 0: prev_log_id == 1-1, 0 logs");
 
     let req = AppendEntriesRequest {
-        vote: Vote::new_committed(1, 2),
+        vote: Vote::new_committed(3, 2),
         prev_log_id: Some(log_id(0, 0, 0)),
         entries: vec![],
         leader_commit: Some(log_id(1, 0, 2)),
+        backup_barrier: None,
+        after_snapshot: None,
+        config_digest: None,
     };
 
     let resp = r0.append_entries(req).await?;
@@ -86,7 +95,7 @@ async fn append_conflicts() -> Result<()> {
     tracing::info!("--- case 0: prev_log_id.index == 0, ");
 
     let req = || AppendEntriesRequest {
-        vote: Vote::new_committed(1, 2),
+        vote: Vote::new_committed(3, 2),
         prev_log_id: Some(log_id(0, 0, 0)),
         entries: vec![
             blank_ent::<openraft_memstore::TypeConfig>(1, 0, 1),
@@ -96,6 +105,9 @@ async fn append_conflicts() -> Result<()> {
         ],
         // this set the last_applied to 2
         leader_commit: Some(log_id(1, 0, 2)),
+        backup_barrier: None,
+        after_snapshot: None,
+        config_digest: None,
     };
 
     let resp = r0.append_entries(req()).await?;
@@ -116,10 +128,13 @@ async fn append_conflicts() -> Result<()> {
     tracing::info!("--- case 1: 0 < prev_log_id.index < commit_index");
 
     let req = AppendEntriesRequest {
-        vote: Vote::new_committed(1, 2),
+        vote: Vote::new_committed(3, 2),
         prev_log_id: Some(log_id(1, 0, 1)),
         entries: vec![blank_ent::<openraft_memstore::TypeConfig>(1, 0, 2)],
         leader_commit: Some(log_id(1, 0, 2)),
+        backup_barrier: None,
+        after_snapshot: None,
+        config_digest: None,
     };
 
     let resp = r0.append_entries(req).await?;
@@ -131,11 +146,14 @@ async fn append_conflicts() -> Result<()> {
     tracing::info!("--- case 2:  prev_log_id.index == last_applied, inconsistent log should be removed");
 
     let req = AppendEntriesRequest {
-        vote: Vote::new_committed(1, 2),
+        vote: Vote::new_committed(3, 2),
         prev_log_id: Some(log_id(1, 0, 2)),
         entries: vec![blank_ent::<openraft_memstore::TypeConfig>(2, 0, 3)],
         // this set the last_applied to 2
         leader_commit: Some(log_id(1, 0, 2)),
+        backup_barrier: None,
+        after_snapshot: None,
+        config_digest: None,
     };
 
     let resp = r0.append_entries(req).await?;
@@ -146,10 +164,13 @@ async fn append_conflicts() -> Result<()> {
 
     // check last_log_id is updated:
     let req = AppendEntriesRequest {
-        vote: Vote::new_committed(1, 2),
+        vote: Vote::new_committed(3, 2),
         prev_log_id: Some(log_id(1, 0, 2000)),
         entries: vec![],
         leader_commit: Some(log_id(1, 0, 2)),
+        backup_barrier: None,
+        after_snapshot: None,
+        config_digest: None,
     };
 
     let resp = r0.append_entries(req).await?;
@@ -161,10 +182,13 @@ async fn append_conflicts() -> Result<()> {
     tracing::info!("--- case 3,4: prev_log_id.index <= last_log_id, prev_log_id mismatch, inconsistent log is removed");
 
     let req = AppendEntriesRequest {
-        vote: Vote::new_committed(1, 2),
+        vote: Vote::new_committed(3, 2),
         prev_log_id: Some(log_id(3, 0, 3)),
         entries: vec![],
         leader_commit: Some(log_id(1, 0, 2)),
+        backup_barrier: None,
+        after_snapshot: None,
+        config_digest: None,
     };
 
     let resp = r0.append_entries(req).await?;
@@ -176,7 +200,7 @@ async fn append_conflicts() -> Result<()> {
     tracing::info!("--- case 3,4: prev_log_id.index <= last_log_id, prev_log_id matches, inconsistent log is removed");
     // refill logs
     let req = AppendEntriesRequest {
-        vote: Vote::new_committed(1, 2),
+        vote: Vote::new_committed(3, 2),
         prev_log_id: Some(log_id(1, 0, 2)),
         entries: vec![
             blank_ent::<openraft_memstore::TypeConfig>(2, 0, 3),
@@ -184,6 +208,9 @@ async fn append_conflicts() -> Result<()> {
             blank_ent::<openraft_memstore::TypeConfig>(2, 0, 5),
         ],
         leader_commit: Some(log_id(1, 0, 2)),
+        backup_barrier: None,
+        after_snapshot: None,
+        config_digest: None,
     };
 
     let resp = r0.append_entries(req).await?;
@@ -195,10 +222,13 @@ async fn append_conflicts() -> Result<()> {
 
     // prev_log_id matches
     let req = AppendEntriesRequest {
-        vote: Vote::new_committed(1, 2),
+        vote: Vote::new_committed(3, 2),
         prev_log_id: Some(log_id(2, 0, 3)),
         entries: vec![blank_ent::<openraft_memstore::TypeConfig>(3, 0, 4)],
         leader_commit: Some(log_id(1, 0, 2)),
+        backup_barrier: None,
+        after_snapshot: None,
+        config_digest: None,
     };
 
     let resp = r0.append_entries(req).await?;
@@ -211,10 +241,13 @@ async fn append_conflicts() -> Result<()> {
 
     // refill logs
     let req = AppendEntriesRequest {
-        vote: Vote::new_committed(1, 2),
+        vote: Vote::new_committed(3, 2),
         prev_log_id: Some(log_id(1, 0, 200)),
         entries: vec![],
         leader_commit: Some(log_id(1, 0, 2)),
+        backup_barrier: None,
+        after_snapshot: None,
+        config_digest: None,
     };
 
     let resp = r0.append_entries(req).await?;
@@ -49,6 +49,9 @@ async fn append_entries_with_bigger_term() -> Result<()> {
         prev_log_id: Some(log_id(1, 0, log_index)),
         entries: vec![],
         leader_commit: Some(log_id(1, 0, log_index)),
+        backup_barrier: None,
+        after_snapshot: None,
+        config_digest: None,
     };
 
     let node = router.get_raft_handle(&0)?;
@@ -52,15 +52,28 @@ async fn append_updates_membership() -> Result<()> {
                 Entry {
                     log_id: log_id(1, 0, 2),
                     payload: EntryPayload::Membership(Membership::new_with_defaults(vec![btreeset! {1,2}], [])),
+                    apply_scope: None,
+                    timestamp: None,
+                    deadline: None,
+                    expire_from: None,
+                    chain_hash: None,
                 },
                 blank_ent::<openraft_memstore::TypeConfig>(1, 0, 3),
                 Entry {
                     log_id: log_id(1, 0, 4),
                     payload: EntryPayload::Membership(Membership::new_with_defaults(vec![btreeset! {1,2,3,4}], [])),
+                    apply_scope: None,
+                    timestamp: None,
+                    deadline: None,
+                    expire_from: None,
+                    chain_hash: None,
                 },
                 blank_ent::<openraft_memstore::TypeConfig>(1, 0, 5),
             ],
             leader_commit: Some(log_id(0, 0, 0)),
+            backup_barrier: None,
+            after_snapshot: None,
+            config_digest: None,
         };
 
         let resp = r0.append_entries(req).await?;
@@ -77,6 +90,9 @@ async fn append_updates_membership() -> Result<()> {
             prev_log_id: Some(log_id(1, 0, 2)),
             entries: vec![blank_ent::<openraft_memstore::TypeConfig>(2, 0, 3)],
             leader_commit: Some(log_id(0, 0, 0)),
+            backup_barrier: None,
+            after_snapshot: None,
+            config_digest: None,
         };
 
         let resp = r0.append_entries(req).await?;
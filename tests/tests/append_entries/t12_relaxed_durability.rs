@@ -0,0 +1,42 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// A cluster in which every node runs with `relaxed_durability` elects a leader and replicates
+/// logs as usual.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn relaxed_durability() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            relaxed_durability: Some(true),
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0, 1, 2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- write logs");
+    log_index += router.client_request_many(0, "foo", 10).await?;
+
+    for id in [0, 1, 2] {
+        router.wait(&id, timeout()).applied_index(Some(log_index), "logs replicated").await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
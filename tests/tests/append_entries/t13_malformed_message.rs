@@ -0,0 +1,138 @@
+use std::pin::pin;
+use std::sync::Arc;
+
+use anyhow::Result;
+use futures::StreamExt;
+use openraft::Config;
+use openraft::Vote;
+use openraft::alias::VoteOf;
+use openraft::raft::AppendEntriesRequest;
+use openraft::raft::AppendEntriesResponse;
+use openraft::raft::StreamAppendError;
+use openraft::raft::VoteRequest;
+use openraft::testing::blank_ent;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+fn req(vote: VoteOf<TypeConfig>, prev: Option<(u64, u64)>, entries: &[(u64, u64)]) -> AppendEntriesRequest<TypeConfig> {
+    AppendEntriesRequest {
+        vote,
+        prev_log_id: prev.map(|(term, index)| log_id(term, 1, index)),
+        entries: entries.iter().map(|(term, index)| blank_ent::<TypeConfig>(*term, 1, *index)).collect(),
+        leader_commit: None,
+        backup_barrier: None,
+        after_snapshot: None,
+        config_digest: None,
+    }
+}
+
+/// A corpus of AppendEntries requests, each breaks one invariant a request from a leader holds.
+fn malformed_corpus() -> Vec<AppendEntriesRequest<TypeConfig>> {
+    vec![
+        // Uncommitted vote
+        req(Vote::new(2, 1), Some((1, 3)), &[(1, 4)]),
+        // prev_log_id proposed by a greater leader
+        req(Vote::new_committed(2, 1), Some((3, 3)), &[]),
+        // An entry proposed by a greater leader
+        req(Vote::new_committed(2, 1), Some((1, 3)), &[(1, 4), (3, 5)]),
+        // Gap after prev_log_id
+        req(Vote::new_committed(2, 1), Some((1, 3)), &[(1, 5)]),
+        // Gap between entries
+        req(Vote::new_committed(2, 1), Some((1, 3)), &[(1, 4), (1, 6)]),
+        // Repeated index
+        req(Vote::new_committed(2, 1), Some((1, 3)), &[(1, 4), (1, 4)]),
+        // Decreasing leader id
+        req(Vote::new_committed(2, 1), Some((2, 3)), &[(1, 4)]),
+        // First entry is not at index 0 when there is no prev_log_id
+        req(Vote::new_committed(2, 1), None, &[(1, 1)]),
+    ]
+}
+
+/// A follower rejects malformed AppendEntries and Vote requests without changing its state.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn reject_malformed_message() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+    router.new_raft_node(0).await;
+
+    let raft = router.get_raft_handle(&0)?;
+
+    tracing::info!("--- feed logs");
+    {
+        let resp = raft.append_entries(req(Vote::new_committed(1, 1), None, &[(0, 0), (1, 1), (1, 2), (1, 3)])).await?;
+        assert!(resp.is_success());
+    }
+
+    let assert_unchanged = async |msg: String| -> Result<()> {
+        let (vote, last) = raft.with_raft_state(|st| (*st.vote_ref(), st.log_ids.last().cloned())).await?;
+        assert_eq!(Vote::new_committed(1, 1), vote, "vote unchanged: {}", msg);
+        assert_eq!(Some(log_id(1, 1, 3)), last, "log unchanged: {}", msg);
+        Ok(())
+    };
+
+    tracing::info!("--- append_entries rejects every malformed request");
+    for rpc in malformed_corpus() {
+        let msg = rpc.to_string();
+
+        let resp = raft.append_entries(rpc).await?;
+        assert!(
+            matches!(resp, AppendEntriesResponse::Malformed(_)),
+            "expect malformed: {}, got: {}",
+            msg,
+            resp
+        );
+
+        assert_unchanged(msg).await?;
+    }
+
+    tracing::info!("--- stream_append terminates at the first malformed request");
+    for rpc in malformed_corpus() {
+        let msg = rpc.to_string();
+
+        let input = futures::stream::iter(vec![rpc, req(Vote::new_committed(2, 1), Some((1, 3)), &[(2, 4)])]);
+        let results: Vec<_> = pin!(raft.stream_append(input)).collect().await;
+
+        assert_eq!(1, results.len(), "stream terminates: {}", msg);
+        assert!(
+            matches!(results[0], Ok(Err(StreamAppendError::Malformed(_)))),
+            "expect malformed: {}, got: {:?}",
+            msg,
+            results[0]
+        );
+
+        assert_unchanged(msg).await?;
+    }
+
+    tracing::info!("--- vote and pre-vote are not granted to a malformed request");
+    {
+        let rpc = VoteRequest {
+            vote: Vote::new(2, 1),
+            last_log_id: Some(log_id(3, 1, 5)),
+            leadership_transfer: true,
+            relaxed_durability: false,
+            config_digest: None,
+        };
+
+        let resp = raft.vote(rpc.clone()).await?;
+        assert!(!resp.is_granted_to(&rpc.vote));
+
+        let resp = raft.pre_vote(rpc).await?;
+        assert!(!resp.is_granted_to(&Vote::new(2, 1)));
+
+        assert_unchanged("vote".to_string()).await?;
+    }
+
+    Ok(())
+}
@@ -18,9 +18,26 @@ mod t13_trigger_snapshot;
 mod t13_trigger_snapshot_twice;
 mod t14_transfer_leader;
 mod t15_client_write_with_twoshot;
+mod t16_get_log_id_by_index;
 mod t16_with_raft_state;
 mod t16_with_state_machine;
+mod t17_applied_result_cache;
+mod t18_client_write_canary;
+mod t19_client_write_reserved;
 mod t20_raft_api;
+mod t21_client_write_accepted;
+mod t22_local_read;
+mod t23_client_write_with_options;
+mod t24_local_log_tail;
+mod t25_ordered_writer;
 mod t50_lagging_network_write;
 mod t51_write_when_leader_quit;
+mod t52_write_deadline;
+mod t53_storage_quota;
+mod t54_command_queue_limit;
+mod t55_lease_check;
+mod t56_leaderless_write_hold;
+mod t57_lease_read;
+mod t58_follower_read_index;
+mod t59_verify_quorum;
 mod t90_issue_1761_purge_stranded_responder;
@@ -50,6 +50,20 @@ async fn trigger_snapshot() -> anyhow::Result<()> {
         router.wait(&0, timeout()).snapshot(log_id(1, 0, log_index), "node-0 snapshot").await?;
     }
 
+    tracing::info!(log_index, "--- trigger snapshot for node-0 and wait for it to be built");
+    {
+        router.client_request_many(0, "0", 5).await?;
+        log_index += 5;
+
+        router.wait(&0, timeout()).applied_index(Some(log_index), "node-0 write logs").await?;
+
+        let n0 = router.get_raft_handle(&0)?;
+        let meta = n0.trigger().snapshot().await?.built().await?;
+
+        let meta = meta.expect("snapshot is built");
+        assert_eq!(Some(log_id(1, 0, log_index)), meta.last_log_id);
+    }
+
     Ok(())
 }
 
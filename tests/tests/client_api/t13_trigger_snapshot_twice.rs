@@ -3,8 +3,6 @@ use std::time::Duration;
 
 use maplit::btreeset;
 use openraft::Config;
-use openraft::type_config::TypeConfigExt;
-use openraft_memstore::TypeConfig;
 
 use crate::fixtures::RaftRouter;
 use crate::fixtures::log_id;
@@ -45,28 +43,15 @@ async fn trigger_snapshot_twice_at_same_last_applied() -> anyhow::Result<()> {
 
     tracing::info!(log_index, "--- first trigger().snapshot(): build at {}", snap_at);
     n0.trigger().snapshot().await?;
-    router.wait(&0, timeout()).snapshot(snap_at, "first snapshot built").await?;
+    router.wait(&0, timeout()).snapshot(snap_at.clone(), "first snapshot built").await?;
 
     tracing::info!(
         log_index,
         "--- second trigger().snapshot() at the same last_applied: must not panic"
     );
-    n0.trigger().snapshot().await?;
-
-    // The duplicate build must complete before we probe liveness. There is no
-    // deterministic signal available for this:
-    //
-    // - `build_snapshot` in the SM worker spawns the build into its own task (`core/sm/worker.rs`), so
-    //   a subsequent `Apply` command is not ordered after it — a client write can be applied before
-    //   build #2 finishes.
-    // - The `snapshot` metric is the last included log id; build #2 targets the same log id as build
-    //   #1, so the metric never changes.
-    // - `building_snapshot` is engine-internal and not surfaced via metrics.
-    //
-    // Sleep a generous window for build #2 to complete and raft_core to
-    // process its `BuildSnapshotDone`. memstore builds are sub-millisecond
-    // in practice, so this is conservative.
-    TypeConfig::sleep(Duration::from_millis(500)).await;
+    // Wait for the duplicate build to complete and raft_core to process its `BuildSnapshotDone`.
+    let meta = n0.trigger().snapshot().await?.built().await?;
+    assert_eq!(Some(snap_at), meta.and_then(|m| m.last_log_id));
 
     // Liveness probe: a client write proves the raft core task is still
     // servicing commands. Under a regression that re-introduced the strict
@@ -0,0 +1,48 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::errors::Fatal;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// Recover a full log id from a log index with
+/// [`Raft::get_log_id_by_index()`](openraft::Raft::get_log_id_by_index).
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn get_log_id_by_index() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    log_index += router.client_request_many(0, "foo", 3).await?;
+    router.wait(&1, None).applied_index(Some(log_index), "logs replicated to node 1").await?;
+
+    for id in [0, 1] {
+        let n = router.get_raft_handle(&id)?;
+
+        assert_eq!(Some(log_id(1, 0, log_index)), n.get_log_id_by_index(log_index).await?);
+        assert_eq!(None, n.get_log_id_by_index(log_index + 1).await?);
+    }
+
+    tracing::info!("--- shutting down node 0");
+    let n0 = router.get_raft_handle(&0)?;
+    n0.shutdown().await?;
+
+    let res = n0.get_log_id_by_index(log_index).await;
+    assert_eq!(Err(Fatal::Stopped), res);
+
+    Ok(())
+}
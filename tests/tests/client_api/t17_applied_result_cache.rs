@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::raft::CorrelationId;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::ClientResponse;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// With `applied_result_cache_size` set, the response of an applied write can be retrieved again
+/// by its log id or its correlation id, until it is evicted by later writes.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn applied_result_cache() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            applied_result_cache_size: Some(2),
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!("--- write with a correlation id");
+    let resp = n0.client_write_with_correlation_id(ClientRequest::make_request("foo", 1), CorrelationId(7)).await?;
+    let first = resp.log_id;
+
+    assert_eq!(Some(resp.data.clone()), n0.applied_result(&first));
+    assert_eq!(
+        Some((first, resp.data.clone())),
+        n0.applied_result_by_correlation_id(CorrelationId(7))
+    );
+
+    tracing::info!("--- a follower does not retain responses of writes it did not propose");
+    let n1 = router.get_raft_handle(&1)?;
+    assert_eq!(None, n1.applied_result(&first));
+
+    tracing::info!("--- later writes evict the oldest response");
+    let resp = n0.client_write(ClientRequest::make_request("foo", 2)).await?;
+    assert_eq!(ClientResponse(Some("request-1".to_string())), resp.data);
+    n0.client_write(ClientRequest::make_request("foo", 3)).await?;
+
+    assert_eq!(None, n0.applied_result(&first));
+    assert_eq!(None, n0.applied_result_by_correlation_id(CorrelationId(7)));
+    assert_eq!(Some(resp.data), n0.applied_result(&resp.log_id));
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// A canary write is applied only by the canary learner, until it is confirmed.
+///
+/// - brings up 3 voters and 2 learners, and uses learner 3 as the canary.
+/// - writes with `client_write_canary()`: only learner 3 applies it.
+/// - confirms with `confirm_canary()`: every other node applies it.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn client_write_canary() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {3,4}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let canary = btreeset! {3};
+
+    tracing::info!(log_index, "--- canary write is applied only by learner 3");
+    {
+        let req = ClientRequest::make_request("foo", 1);
+        let log_id = n0.client_write_canary(req, canary.clone()).await?;
+        log_index += 1;
+        assert_eq!(log_index, log_id.index);
+
+        for id in [0, 1, 2, 3, 4] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "canary write applied").await?;
+
+            let (_sto, sm) = router.get_storage_handle(&id)?;
+            let applied = sm.get_state_machine().await.client_status.contains_key("foo");
+            assert_eq!(id == 3, applied, "n{} applies the canary write: {}", id, id == 3);
+        }
+    }
+
+    tracing::info!(log_index, "--- confirmed write is applied by every other node");
+    {
+        let req = ClientRequest::make_request("foo", 1);
+        let resp = n0.confirm_canary(req, canary.clone()).await?;
+        log_index += 1;
+        assert_eq!(log_index, resp.log_id.index);
+
+        for id in [0, 1, 2, 3, 4] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "confirmed write applied").await?;
+
+            let (_sto, sm) = router.get_storage_handle(&id)?;
+            assert!(sm.get_state_machine().await.client_status.contains_key("foo"));
+        }
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
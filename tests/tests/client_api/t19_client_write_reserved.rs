@@ -0,0 +1,113 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::errors::ClientWriteError;
+use openraft::errors::RaftError;
+use openraft::errors::ReservedIndexMismatch;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// Log indexes reserved on the leader are filled in order by `client_write_reserved()`.
+///
+/// - reserves 3 indexes and fills them with 2 writes.
+/// - asserts a write that does not continue the reservation is rejected.
+/// - asserts a normal write drops the reservation.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn client_write_reserved() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- a follower can not reserve log indexes");
+    {
+        let res = router.get_raft_handle(&1)?.reserve_log_indexes(3).await;
+        assert!(matches!(
+            res,
+            Err(RaftError::APIError(ClientWriteError::ForwardToLeader(_)))
+        ));
+    }
+
+    tracing::info!(log_index, "--- reserve 3 log indexes and fill them in order");
+    {
+        let reserved = n0.reserve_log_indexes(3).await?;
+        assert_eq!(log_index + 1..log_index + 4, reserved);
+
+        let res = n0.client_write_reserved(reserved.start + 1, [ClientRequest::make_request("foo", 1)]).await;
+        assert_eq!(
+            Err(RaftError::APIError(ClientWriteError::ReservedIndexMismatch(
+                ReservedIndexMismatch {
+                    index: reserved.start + 1,
+                    count: 1,
+                    reserved: Some(reserved.clone()),
+                }
+            ))),
+            res.map(|_| ())
+        );
+
+        let resp = n0
+            .client_write_reserved(reserved.start, [
+                ClientRequest::make_request("foo", 1),
+                ClientRequest::make_request("foo", 2),
+            ])
+            .await?;
+        assert_eq!(
+            vec![reserved.start, reserved.start + 1],
+            resp.iter().map(|r| r.log_id.index).collect::<Vec<_>>()
+        );
+
+        let resp = n0.client_write_reserved(reserved.start + 2, [ClientRequest::make_request("foo", 3)]).await?;
+        assert_eq!(reserved.start + 2, resp[0].log_id.index);
+        log_index += 3;
+
+        for id in [0, 1, 2] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "reserved writes applied").await?;
+        }
+    }
+
+    tracing::info!(log_index, "--- a normal write drops the reservation");
+    {
+        let reserved = n0.reserve_log_indexes(2).await?;
+        assert_eq!(log_index + 1..log_index + 3, reserved);
+
+        let resp = n0.client_write(ClientRequest::make_request("foo", 4)).await?;
+        log_index += 1;
+        assert_eq!(reserved.start, resp.log_id.index);
+
+        let res = n0.client_write_reserved(reserved.start + 1, [ClientRequest::make_request("foo", 5)]).await;
+        assert_eq!(
+            Err(RaftError::APIError(ClientWriteError::ReservedIndexMismatch(
+                ReservedIndexMismatch {
+                    index: reserved.start + 1,
+                    count: 1,
+                    reserved: None,
+                }
+            ))),
+            res.map(|_| ())
+        );
+
+        router.wait(&0, timeout()).applied_index(Some(log_index), "normal write applied").await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1000))
+}
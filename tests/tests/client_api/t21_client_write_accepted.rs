@@ -0,0 +1,85 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::errors::ClientWriteError;
+use openraft::errors::RaftError;
+use openraft::type_config::TypeConfigExt;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// `Raft::client_write_accepted()` returns once the leader appends the entry, and the returned
+/// handle resolves once the entry is applied.
+///
+/// - isolates the followers so that nothing can be committed.
+/// - asserts the write is accepted with its log id, but not applied.
+/// - restores the followers, asserts the handle resolves with the applied result.
+/// - asserts a follower rejects the write with `ForwardToLeader`.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn client_write_accepted() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(
+        log_index,
+        "--- isolate followers, the write is accepted but not applied"
+    );
+    let handle = {
+        router.set_network_error(1, true);
+        router.set_network_error(2, true);
+
+        let handle = n0.client_write_accepted(ClientRequest::make_request("foo", 1)).await?;
+        log_index += 1;
+        assert_eq!(&log_id(1, 0, log_index), handle.log_id());
+
+        let applied = TypeConfig::timeout(Duration::from_millis(500), handle.applied()).await;
+        assert!(applied.is_err(), "not applied without a quorum");
+
+        n0.client_write_accepted(ClientRequest::make_request("foo", 2)).await?
+    };
+    log_index += 1;
+
+    tracing::info!(log_index, "--- restore followers, the write is applied");
+    {
+        router.set_network_error(1, false);
+        router.set_network_error(2, false);
+
+        let resp = TypeConfig::timeout(Duration::from_millis(5_000), handle.applied()).await??;
+        assert_eq!(log_id(1, 0, log_index), resp.log_id);
+    }
+
+    tracing::info!(log_index, "--- a follower rejects the write");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        let res = n1.client_write_accepted(ClientRequest::make_request("foo", 3)).await;
+
+        let err = res.unwrap_err();
+        assert!(
+            matches!(err, RaftError::APIError(ClientWriteError::ForwardToLeader(_))),
+            "got: {}",
+            err
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::errors::RaftError;
+use openraft::raft::ReadOptions;
+use openraft::type_config::TypeConfigExt;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// `Raft::local_read()` serves a read only if this node meets the `ReadOptions`, and otherwise
+/// tells how stale it is.
+///
+/// - asserts a follower serves a read that requires the applied index.
+/// - asserts a follower rejects a read that requires an index not yet applied.
+/// - isolates a follower, asserts it rejects a read bounded by `max_staleness`, while the leader
+///   still serves it.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn local_read() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_elect: false,
+            heartbeat_interval: 50,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let n1 = router.get_raft_handle(&1)?;
+
+    n0.client_write(ClientRequest::make_request("foo", 1)).await?;
+    log_index += 1;
+    router.wait(&1, timeout()).applied_index(Some(log_index), "write applied on n1").await?;
+
+    let staleness = Duration::from_millis(500);
+
+    tracing::info!(log_index, "--- a follower serves a read it is fresh enough for");
+    {
+        let opts = ReadOptions::new().with_min_applied_index(log_index).with_max_staleness(staleness);
+        let applied = n1.local_read(opts).await?;
+        assert_eq!(Some(log_id(1, 0, log_index)), applied);
+    }
+
+    tracing::info!(log_index, "--- a follower rejects a read requiring a not applied index");
+    {
+        let opts = ReadOptions::new().with_min_applied_index(log_index + 1);
+        let err = n1.local_read(opts).await.unwrap_err();
+
+        let RaftError::APIError(stale) = err else {
+            panic!("expect StaleRead, got: {}", err);
+        };
+        assert_eq!(Some(log_id(1, 0, log_index)), stale.applied);
+        assert_eq!(Some(log_index + 1), stale.min_applied_index);
+    }
+
+    tracing::info!(
+        log_index,
+        "--- an isolated follower rejects a read bounded by staleness"
+    );
+    {
+        router.set_network_error(1, true);
+        TypeConfig::sleep(staleness * 2).await;
+
+        let opts = ReadOptions::new().with_max_staleness(staleness);
+        let err = n1.local_read(opts.clone()).await.unwrap_err();
+
+        let RaftError::APIError(stale) = err else {
+            panic!("expect StaleRead, got: {}", err);
+        };
+        let since = stale.since_leader_contact.unwrap();
+        assert!(since > staleness, "since leader contact: {:?}", since);
+
+        let applied = n0.local_read(opts).await?;
+        assert_eq!(Some(log_id(1, 0, log_index)), applied, "the leader is acked by n2");
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,85 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::async_runtime::WatchReceiver;
+use openraft::raft::WriteOptions;
+use openraft::raft::WriteWait;
+use openraft::type_config::TypeConfigExt;
+use openraft_memstore::BlockOperation;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// `Raft::client_write_with_options()` returns once the write reaches the requested stage.
+///
+/// - asserts a write waiting for `Applied` returns after it is applied.
+/// - blocks the state machine with a snapshot build, asserts a write waiting for `Committed`
+///   returns before it is applied, and the handle resolves once it is applied.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn client_write_with_options() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- wait for applied");
+    {
+        let opts = WriteOptions::new();
+        assert_eq!(WriteWait::Applied, opts.wait);
+
+        let handle = n0.client_write_with_options(ClientRequest::make_request("foo", 1), opts).await?;
+        log_index += 1;
+        assert_eq!(&log_id(1, 0, log_index), handle.log_id());
+
+        let resp = handle.applied().await?;
+        assert_eq!(log_id(1, 0, log_index), resp.log_id);
+    }
+
+    tracing::info!(log_index, "--- block the state machine with a snapshot build");
+    {
+        let (_sto0, sm0) = router.get_storage_handle(&0)?;
+        sm0.block.set_blocking(BlockOperation::BuildSnapshot, Duration::from_millis(2_000));
+
+        n0.trigger().snapshot().await?;
+        TypeConfig::sleep(Duration::from_millis(200)).await;
+    }
+
+    tracing::info!(log_index, "--- wait for committed, return before applied");
+    {
+        let opts = WriteOptions::new().with_wait(WriteWait::Committed);
+        let fu = n0.client_write_with_options(ClientRequest::make_request("foo", 2), opts);
+        let handle = TypeConfig::timeout(Duration::from_millis(1_000), fu).await??;
+        log_index += 1;
+        assert_eq!(&log_id(1, 0, log_index), handle.log_id());
+
+        let m = n0.metrics().borrow_watched().clone();
+        assert!(
+            m.last_applied < Some(log_id(1, 0, log_index)),
+            "not yet applied: {:?}",
+            m.last_applied
+        );
+
+        let resp = TypeConfig::timeout(Duration::from_millis(5_000), handle.applied()).await??;
+        assert_eq!(log_id(1, 0, log_index), resp.log_id);
+    }
+
+    Ok(())
+}
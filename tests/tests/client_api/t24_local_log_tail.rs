@@ -0,0 +1,68 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::entry::EntryKind;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// `Raft::local_log_tail()` returns the log ids and payload kinds of the last log entries on a
+/// node.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn local_log_tail() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- write 3 logs");
+    log_index += router.client_request_many(0, "foo", 3).await?;
+    router.wait(&1, timeout()).applied_index(Some(log_index), "logs replicated").await?;
+
+    let n1 = router.get_raft_handle(&1)?;
+
+    tracing::info!(log_index, "--- the last 3 entries are the normal ones just written");
+    {
+        let tail = n1.local_log_tail(3).await?;
+        let want = (log_index - 2..=log_index).map(|i| (log_id(1, 0, i), EntryKind::Normal)).collect::<Vec<_>>();
+        let got = tail.iter().map(|m| (m.log_id.clone(), m.kind)).collect::<Vec<_>>();
+        assert_eq!(want, got);
+
+        // The store reports the size of the encoded entries.
+        assert!(tail.iter().all(|m| m.size.is_some_and(|size| size > 0)));
+    }
+
+    tracing::info!(log_index, "--- all entries, starting with the initial membership");
+    {
+        let tail = n1.local_log_tail(100).await?;
+        assert_eq!(log_index + 1, tail.len() as u64);
+        assert_eq!(0, tail[0].log_id.index);
+        assert_eq!(EntryKind::Membership, tail[0].kind);
+        assert_eq!(EntryKind::Blank, tail[1].kind, "the blank log of the new leader");
+    }
+
+    tracing::info!(log_index, "--- no entry");
+    {
+        let tail = n1.local_log_tail(0).await?;
+        assert!(tail.is_empty());
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,65 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// Writes through an `OrderedWriter` are assigned log indexes in call order, regardless of the
+/// order their futures are polled.
+///
+/// - calls `client_write()` several times, then polls the futures in reverse order.
+/// - asserts the log indexes follow the call order.
+/// - drops a write future before polling it, asserts the write is never proposed.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn ordered_writer() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let writer = n0.ordered_writer();
+
+    let mut log_index = log_index;
+
+    tracing::info!(log_index, "--- write in call order, poll in reverse order");
+    {
+        let mut futs = (0..10).map(|i| writer.client_write(ClientRequest::make_request("foo", i))).collect::<Vec<_>>();
+        futs.reverse();
+
+        let mut resps = futures::future::join_all(futs).await;
+        resps.reverse();
+
+        for (i, resp) in resps.into_iter().enumerate() {
+            let resp = resp?;
+            assert_eq!(log_id(1, 0, log_index + 1 + i as u64), resp.log_id);
+        }
+        log_index += 10;
+    }
+
+    tracing::info!(log_index, "--- a write whose future is dropped is not proposed");
+    {
+        let dropped = writer.client_write(ClientRequest::make_request("foo", 10));
+        drop(dropped);
+
+        let resp = writer.client_write(ClientRequest::make_request("foo", 11)).await?;
+        assert_eq!(log_id(1, 0, log_index + 1), resp.log_id);
+    }
+
+    Ok(())
+}
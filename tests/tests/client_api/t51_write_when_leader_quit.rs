@@ -73,6 +73,9 @@ async fn write_when_leader_quit_and_log_revert() -> Result<()> {
 
                 entries: vec![],
                 leader_commit: None,
+                backup_barrier: None,
+                after_snapshot: None,
+                config_digest: None,
             })
             .await?;
 
@@ -150,6 +153,9 @@ async fn write_when_leader_switched() -> Result<()> {
 
                 // Inform node-0 to commit the pending log.
                 leader_commit: Some(log_id(1, 0, log_index + 1)),
+                backup_barrier: None,
+                after_snapshot: None,
+                config_digest: None,
             })
             .await?;
 
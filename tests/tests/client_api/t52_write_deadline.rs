@@ -0,0 +1,113 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::errors::ClientWriteError;
+use openraft::errors::WriteExpired;
+use openraft::impls::ProgressResponder;
+use openraft::type_config::TypeConfigExt;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// A write with a deadline that is not committed in time is abandoned by the leader. Once the
+/// leader is elected again, the write is committed but applied as a blank entry by every node, and
+/// is responded with [`WriteExpired`].
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn write_deadline_expired() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            heartbeat_interval: 50,
+            election_timeout_min: 500,
+            election_timeout_max: 501,
+            enable_elect: false,
+            entry_timestamp: Some(true),
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- a write committed before its deadline");
+    {
+        let (responder, complete_rx) = ProgressResponder::complete_only();
+        n0.write(ClientRequest::make_request("cli", 1))
+            .deadline(TypeConfig::now() + Duration::from_secs(10))
+            .responder(responder)
+            .await?;
+        let got = complete_rx.await??;
+        log_index += 1;
+        assert_eq!(log_id(1, 0, log_index), got.log_id);
+    }
+
+    tracing::info!(log_index, "--- block replication so that no log will be committed");
+    router.set_unreachable(1, true);
+
+    tracing::info!(log_index, "--- a write not committed before its deadline");
+    let complete_rx = {
+        let (responder, complete_rx) = ProgressResponder::complete_only();
+        n0.write(ClientRequest::make_request("cli", 2))
+            .deadline(TypeConfig::now() + Duration::from_millis(200))
+            .responder(responder)
+            .await?;
+        log_index += 1;
+        complete_rx
+    };
+
+    tracing::info!(log_index, "--- the leader abandons its term once the deadline passes");
+    {
+        n0.wait(Some(Duration::from_secs(2)))
+            .metrics(|m| m.current_term > 1, "n0 starts an election")
+            .await?;
+    }
+
+    tracing::info!(
+        log_index,
+        "--- once n0 is elected again, the write is committed but applied as a blank entry"
+    );
+    {
+        router.set_unreachable(1, false);
+        n0.trigger().elect(false).await?;
+
+        let res = TypeConfig::timeout(Duration::from_secs(2), complete_rx).await??;
+        assert_eq!(
+            ClientWriteError::WriteExpired(WriteExpired {
+                log_id: log_id(1, 0, log_index)
+            }),
+            res.unwrap_err()
+        );
+
+        // The first entry of the new term of n0.
+        log_index += 1;
+        for id in [0, 1] {
+            router
+                .wait(&id, Some(Duration::from_secs(2)))
+                .applied_index(Some(log_index), "expired entry committed")
+                .await?;
+
+            let (_sto, sm) = router.get_storage_handle(&id)?;
+            let status = sm.get_state_machine().await.client_status.get("cli").cloned();
+            assert_eq!(
+                Some("request-1".to_string()),
+                status,
+                "n{} does not apply the expired write",
+                id
+            );
+        }
+    }
+
+    Ok(())
+}
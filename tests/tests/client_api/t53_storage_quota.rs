@@ -0,0 +1,121 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use anyhow::Result;
+use futures::TryStreamExt;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::SnapshotPolicy;
+use openraft::errors::ClientWriteError;
+use openraft::errors::RaftError;
+use openraft::errors::StorageFull;
+use openraft::errors::WriteError;
+use openraft::storage::StorageUsageProbe;
+use openraft::type_config::TypeConfigExt;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// A probe that reports the usage set by the test.
+#[derive(Debug, Default)]
+struct TestProbe {
+    usage: AtomicU64,
+}
+
+impl StorageUsageProbe for TestProbe {
+    fn usage(&self) -> u64 {
+        self.usage.load(Ordering::Relaxed)
+    }
+}
+
+/// When the storage usage exceeds the quota, the node builds a snapshot and purges logs, and then
+/// rejects writes with `StorageFull` until the usage is back under the quota.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn storage_quota() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            snapshot_policy: SnapshotPolicy::Never,
+            storage_quota: Some(1_000),
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let probe = Arc::new(TestProbe::default());
+    n0.set_storage_usage_probe(Some(probe.clone())).await?;
+
+    tracing::info!(log_index, "--- usage under quota, writes are accepted");
+    {
+        probe.usage.store(100, Ordering::Relaxed);
+        router.client_request_many(0, "foo", 10).await?;
+        log_index += 10;
+    }
+
+    tracing::info!(
+        log_index,
+        "--- usage above quota, a snapshot is built and logs are purged"
+    );
+    {
+        probe.usage.store(5_000, Ordering::Relaxed);
+        router
+            .wait(&0, timeout())
+            .snapshot(log_id(1, 0, log_index), "snapshot built to reclaim space")
+            .await?;
+        router
+            .wait(&0, timeout())
+            .purged(Some(log_id(1, 0, log_index)), "all logs in snapshot purged")
+            .await?;
+    }
+
+    tracing::info!(log_index, "--- usage still above quota, writes are rejected");
+    {
+        TypeConfig::sleep(Duration::from_millis(500)).await;
+
+        let res = n0.client_write(ClientRequest::make_request("foo", 100)).await;
+        assert_eq!(
+            Err(RaftError::APIError(ClientWriteError::StorageFull(StorageFull {
+                usage: 5_000,
+                quota: 1_000,
+            }))),
+            res.map(|_| ())
+        );
+
+        let mut stream = n0.client_write_many([ClientRequest::make_request("foo", 101)]).await?;
+        let res = stream.try_next().await?.unwrap();
+        assert_eq!(
+            Err(WriteError::StorageFull(StorageFull {
+                usage: 5_000,
+                quota: 1_000,
+            })),
+            res.map(|_| ())
+        );
+    }
+
+    tracing::info!(log_index, "--- usage back under quota, writes are accepted");
+    {
+        probe.usage.store(100, Ordering::Relaxed);
+        TypeConfig::sleep(Duration::from_millis(500)).await;
+
+        let resp = n0.client_write(ClientRequest::make_request("foo", 102)).await?;
+        log_index += 1;
+        assert_eq!(log_id(1, 0, log_index), resp.log_id);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(2_000))
+}
@@ -0,0 +1,56 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// With a tiny command queue limit, `RaftCore` stalls taking requests whenever commands are
+/// queued, but every write still completes once the queue drains.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn command_queue_limit_stalls_but_progresses() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            max_command_queue_bytes: Some(1),
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n = 100;
+    tracing::info!(log_index, "--- write {} logs concurrently", n);
+    {
+        let n0 = router.get_raft_handle(&0)?;
+
+        let writes = (0..n).map(|i| {
+            let n0 = n0.clone();
+            async move { n0.client_write(ClientRequest::make_request("foo", i)).await }
+        });
+
+        for res in futures::future::join_all(writes).await {
+            res?;
+        }
+        log_index += n;
+
+        for id in [0, 1, 2] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "all writes applied").await?;
+        }
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
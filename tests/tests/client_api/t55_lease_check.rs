@@ -0,0 +1,80 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ReadPolicy;
+use openraft::ServerState;
+use openraft::testing::lease_check::LeaseChecker;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// Lease reads served by the old and the new leader across a leadership transfer do not overlap,
+/// as asserted by a [`LeaseChecker`] shared by all nodes.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn lease_check_across_transfer_leader() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            election_timeout_min: 150,
+            election_timeout_max: 300,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let checker = LeaseChecker::<TypeConfig>::new(Duration::ZERO);
+    for id in [0, 1, 2] {
+        router.get_raft_handle(&id)?.set_lease_checker(Some(checker.clone())).await?;
+    }
+
+    let n0 = router.get_raft_handle(&0)?;
+    let n1 = router.get_raft_handle(&1)?;
+
+    tracing::info!("--- lease reads on n0");
+    {
+        n0.trigger().heartbeat().await?;
+        n0.wait(Some(Duration::from_millis(500)))
+            .metrics(|m| m.last_quorum_acked.is_some(), "n0 has fresh last_quorum_acked")
+            .await?;
+
+        for _ in 0..3 {
+            n0.ensure_linearizable(ReadPolicy::LeaseRead).await?;
+        }
+    }
+
+    tracing::info!("--- transfer leader to n1");
+    {
+        n0.trigger().transfer_leader(1).await?;
+        n1.wait(Some(Duration::from_millis(1_000))).state(ServerState::Leader, "n1 becomes leader").await?;
+    }
+
+    tracing::info!("--- lease reads on n1");
+    {
+        n1.trigger().heartbeat().await?;
+        n1.wait(Some(Duration::from_millis(500)))
+            .metrics(|m| m.last_quorum_acked.is_some(), "n1 has fresh last_quorum_acked")
+            .await?;
+
+        n1.ensure_linearizable(ReadPolicy::LeaseRead).await?;
+
+        let res = n0.ensure_linearizable(ReadPolicy::LeaseRead).await;
+        assert!(res.is_err(), "n0 no longer serves lease reads");
+    }
+
+    let spans = checker.spans();
+    assert_eq!(2, spans.len(), "{:?}", spans);
+    assert_eq!(0, spans[0].node_id);
+    assert_eq!(1, spans[1].node_id);
+    assert!(spans[0].end < spans[1].start);
+
+    Ok(())
+}
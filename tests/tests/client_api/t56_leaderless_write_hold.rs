@@ -0,0 +1,98 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::Instant;
+use openraft::async_runtime::OneshotSender;
+use openraft::errors::ClientWriteError;
+use openraft::errors::ForwardToLeader;
+use openraft::errors::RaftError;
+use openraft::type_config::TypeConfigExt;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// With `leaderless_write_hold`, a write received while no leader is known is held: it is proposed
+/// once this node becomes the leader, or rejected after the hold time if no leader is known by
+/// then.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn leaderless_write_hold() -> Result<()> {
+    let hold = Duration::from_millis(1_000);
+
+    let config = Arc::new(
+        Config {
+            enable_elect: false,
+            leaderless_write_hold: Some(hold.as_millis() as u64),
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- isolate node 1 and let it start an election it can not win");
+    let n1 = router.get_raft_handle(&1)?;
+    {
+        router.set_unreachable(1, true);
+        n1.trigger().elect(false).await?;
+        n1.wait(timeout()).metrics(|m| m.current_leader.is_none(), "node 1 knows no leader").await?;
+    }
+
+    tracing::info!(log_index, "--- a write to node 1 is held, not rejected");
+    let (tx, rx) = TypeConfig::oneshot();
+    {
+        let n1 = n1.clone();
+        TypeConfig::spawn(async move {
+            let res = n1.client_write(ClientRequest::make_request("cli", 1)).await;
+            tx.send(res).unwrap();
+        });
+        TypeConfig::sleep(Duration::from_millis(300)).await;
+    }
+
+    tracing::info!(log_index, "--- node 1 becomes the leader and proposes the held write");
+    {
+        router.set_unreachable(1, false);
+        n1.trigger().elect(false).await?;
+        n1.wait(timeout()).current_leader(1, "node 1 becomes leader").await?;
+        log_index += 1;
+
+        let resp = TypeConfig::timeout(timeout().unwrap(), rx).await??.unwrap();
+        log_index += 1;
+        assert_eq!(log_id(3, 1, log_index), resp.log_id);
+    }
+
+    tracing::info!(log_index, "--- a write held for too long is rejected");
+    {
+        let n2 = router.get_raft_handle(&2)?;
+        router.set_unreachable(2, true);
+        n2.trigger().elect(false).await?;
+        n2.wait(timeout()).metrics(|m| m.current_leader.is_none(), "node 2 knows no leader").await?;
+
+        let start = TypeConfig::now();
+        let res = n2.client_write(ClientRequest::make_request("cli", 2)).await;
+        assert!(start.elapsed() >= hold, "the write is held before being rejected");
+        assert_eq!(
+            RaftError::APIError(ClientWriteError::ForwardToLeader(ForwardToLeader {
+                leader_id: None,
+                leader_node: None,
+            })),
+            res.unwrap_err()
+        );
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(2_000))
+}
@@ -0,0 +1,67 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use openraft::Config;
+use openraft::async_runtime::WatchReceiver;
+use openraft::type_config::TypeConfigExt;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// `Raft::lease_read()` is served by the leader within its lease shortened by
+/// `lease_read_clock_drift`, and is rejected on a follower or after the shortened lease expires.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn lease_read() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            election_timeout_min: 500,
+            election_timeout_max: 1_000,
+            lease_read_clock_drift: Some(600),
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let n1 = router.get_raft_handle(&1)?;
+
+    tracing::info!("--- renew the lease of n0 and read");
+    {
+        let acked = n0.metrics().borrow_watched().last_quorum_acked;
+        n0.trigger().heartbeat().await?;
+        n0.wait(timeout()).metrics(|m| m.last_quorum_acked > acked, "n0 lease renewed").await?;
+
+        let applied = n0.lease_read().await?;
+        assert_eq!(n0.metrics().borrow_watched().last_applied, applied);
+    }
+
+    tracing::info!("--- a follower rejects lease read");
+    {
+        let err = n1.lease_read().await.unwrap_err();
+        assert!(err.forward_to_leader().is_some(), "expect ForwardToLeader, got: {}", err);
+    }
+
+    tracing::info!("--- lease read is rejected once the lease minus clock drift expires");
+    {
+        TypeConfig::sleep(Duration::from_millis(500)).await;
+
+        let err = n0.lease_read().await.unwrap_err();
+        assert!(err.forward_to_leader().is_some(), "expect ForwardToLeader, got: {}", err);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,107 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use openraft::Config;
+use openraft::RPCTypes;
+use openraft::ReadPolicy;
+use openraft::async_runtime::WatchReceiver;
+use openraft::base::BoxFuture;
+use openraft::errors::NetworkError;
+use openraft::errors::RPCError;
+use openraft::type_config::TypeConfigExt;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::rpc_request::RpcRequest;
+use crate::fixtures::ut_harness;
+
+/// A follower serves a linearizable read by getting the read log id from the leader with
+/// `Raft::follower_read_index()`, and waiting for its own state machine to apply up to it.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn follower_read_index() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let n1 = router.get_raft_handle(&1)?;
+
+    tracing::info!("--- follower gets the read log id from the leader");
+    {
+        log_index += router.client_request_many(0, "foo", 2).await?;
+
+        let before = router.get_rpc_count().get(&RPCTypes::ReadIndex).copied().unwrap_or_default();
+
+        let read_log_id = n1.follower_read_index(ReadPolicy::ReadIndex).await?;
+        assert_eq!(Some(log_index), read_log_id.as_ref().map(|x| x.index()));
+        assert!(n1.metrics().borrow_watched().last_applied >= read_log_id);
+
+        let after = router.get_rpc_count().get(&RPCTypes::ReadIndex).copied().unwrap_or_default();
+        assert_eq!(before + 1, after, "one ReadIndex RPC is sent to the leader");
+    }
+
+    tracing::info!("--- leader serves follower_read_index without ReadIndex RPC");
+    {
+        let before = router.get_rpc_count().get(&RPCTypes::ReadIndex).copied().unwrap_or_default();
+
+        let read_log_id = n0.follower_read_index(ReadPolicy::ReadIndex).await?;
+        assert_eq!(Some(log_index), read_log_id.as_ref().map(|x| x.index()));
+
+        let after = router.get_rpc_count().get(&RPCTypes::ReadIndex).copied().unwrap_or_default();
+        assert_eq!(before, after);
+    }
+
+    tracing::info!("--- follower waits for its state machine to catch up");
+    {
+        // Block log entries to n1, so that it can not apply the next log.
+        router
+            .set_rpc_pre_hook(RPCTypes::AppendEntries, |_router, req, _from, target| {
+                let res = match req {
+                    RpcRequest::AppendEntries(a) if target == 1 && !a.entries.is_empty() => Err(RPCError::Network(
+                        NetworkError::<TypeConfig>::from_string("block append-entries to node 1"),
+                    )),
+                    _ => Ok(()),
+                };
+                let fu: BoxFuture<_> = Box::pin(futures::future::ready(res));
+                fu
+            })
+            .await;
+
+        log_index += router.client_request_many(0, "foo", 1).await?;
+
+        let mut read = Box::pin(n1.follower_read_index(ReadPolicy::ReadIndex));
+
+        let res = TypeConfig::timeout(Duration::from_millis(300), &mut read).await;
+        assert!(res.is_err(), "n1 has not yet applied the read log id");
+
+        router.rpc_pre_hook(RPCTypes::AppendEntries, None).await;
+        n0.trigger().heartbeat().await?;
+
+        let read_log_id = read.await?;
+        assert_eq!(Some(log_index), read_log_id.as_ref().map(|x| x.index()));
+        assert!(n1.metrics().borrow_watched().last_applied >= read_log_id);
+    }
+
+    tracing::info!("--- follower returns ForwardToLeader if the leader is unreachable");
+    {
+        router.set_unreachable(0, true);
+
+        let err = n1.follower_read_index(ReadPolicy::ReadIndex).await.unwrap_err();
+        let forward = err.forward_to_leader().expect("ForwardToLeader");
+        assert_eq!(Some(0), forward.leader_id);
+    }
+
+    Ok(())
+}
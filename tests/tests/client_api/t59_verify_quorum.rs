@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use maplit::btreeset;
+use openraft::Config;
+use openraft::errors::LinearizableReadError;
+use openraft::type_config::TypeConfigExt;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// `Raft::verify_quorum()` confirms leadership with a round of heartbeats and returns the time
+/// before they are sent; it fails on a follower, or if a quorum does not respond.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn verify_quorum() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let n1 = router.get_raft_handle(&1)?;
+
+    tracing::info!("--- the leader confirms its leadership");
+    {
+        let before = TypeConfig::now();
+        let confirmed_at = n0.verify_quorum().await?;
+        assert!(confirmed_at >= before);
+        assert!(confirmed_at <= TypeConfig::now());
+    }
+
+    tracing::info!("--- a follower can not confirm leadership");
+    {
+        let err = n1.verify_quorum().await.unwrap_err();
+        assert!(err.forward_to_leader().is_some(), "expect ForwardToLeader, got: {}", err);
+    }
+
+    tracing::info!("--- the leader fails to confirm without a quorum");
+    {
+        router.set_unreachable(1, true);
+        router.set_unreachable(2, true);
+
+        let err = n0.verify_quorum().await.unwrap_err();
+        assert!(
+            matches!(err.api_error(), Some(LinearizableReadError::QuorumNotEnough(_))),
+            "expect QuorumNotEnough, got: {}",
+            err
+        );
+    }
+
+    tracing::info!("--- the leader confirms again once a quorum is reachable");
+    {
+        router.set_unreachable(2, false);
+        n0.verify_quorum().await?;
+    }
+
+    Ok(())
+}
@@ -113,6 +113,9 @@ async fn write_then_superseded_by_snapshot_install() -> Result<()> {
             prev_log_id: Some(log_id(2, 1, snap_index)),
             entries: vec![blank_ent::<TypeConfig>(2, 1, snap_index + 1)],
             leader_commit: Some(log_id(2, 1, snap_index + 1)),
+            backup_barrier: None,
+            after_snapshot: None,
+            config_digest: None,
         })
         .await?;
     }
@@ -14,6 +14,7 @@ use openraft::OptionalSend;
 use openraft::RaftTypeConfig;
 use openraft::batch::Batch;
 use openraft::impls::OneshotResponder;
+use openraft::impls::StreamingResponder;
 use openraft::impls::TokioRuntime;
 
 // ---------------------------------------------------------------------------
@@ -81,6 +82,31 @@ impl RaftTypeConfig for CustomConfig {
     type ErrorSource = openraft::AnyError;
 }
 
+/// A config whose client writes stream their progress events with the built-in
+/// [`StreamingResponder`].
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Ord, PartialOrd)]
+struct StreamingConfig;
+
+impl RaftTypeConfig for StreamingConfig {
+    type D = u64;
+    type R = u64;
+    type NodeId = u64;
+    type Node = openraft::BasicNode;
+    type Term = u64;
+    type LeaderId = openraft::impls::leader_id_adv::LeaderId<u64, u64>;
+    type Vote = openraft::impls::Vote<Self::LeaderId>;
+    type Entry = Entry<<Self::LeaderId as openraft::vote::RaftLeaderId>::Committed, Self::D, Self::NodeId, Self::Node>;
+    type SnapshotData = Cursor<Vec<u8>>;
+    type AsyncRuntime = TokioRuntime;
+    type Responder<T>
+        = StreamingResponder<Self, T>
+    where T: OptionalSend + 'static;
+    type Batch<T>
+        = VecBatch<T>
+    where T: OptionalSend + 'static;
+    type ErrorSource = openraft::AnyError;
+}
+
 // ---------------------------------------------------------------------------
 // Verify the custom config satisfies all trait bounds
 // ---------------------------------------------------------------------------
@@ -116,3 +142,13 @@ fn test_custom_type_config_compiles() {
     let b3 = <CustomConfig as RaftTypeConfig>::Batch::<u64>::of(0..5);
     assert_eq!(b3.as_ref(), &[0, 1, 2, 3, 4]);
 }
+
+#[test]
+fn test_streaming_responder_type_config_compiles() {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let _responder: openraft::type_config::alias::WriteResponderOf<StreamingConfig> =
+        StreamingResponder::new(move |event| {
+            let _ = tx.send(event);
+        });
+    drop(rx);
+}
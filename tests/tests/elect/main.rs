@@ -10,3 +10,9 @@ mod fixtures;
 mod t10_elect_compare_last_log;
 mod t11_elect_seize_leadership;
 mod t12_pre_vote;
+mod t13_two_voter_tie_breaker;
+mod t14_vote_hedging;
+mod t15_check_quorum;
+mod t16_adaptive_election_timeout;
+mod t17_candidate_metrics;
+mod t18_pre_vote_capability;
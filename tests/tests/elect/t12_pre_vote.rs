@@ -310,6 +310,8 @@ async fn pre_vote_rejection_with_higher_vote_catches_up_requester() -> Result<()
             vote: Vote::new(10, 2),
             last_log_id: Some(log_id(1, 0, log_index)),
             leadership_transfer: true,
+            relaxed_durability: false,
+            config_digest: None,
         })
         .await?;
     assert!(resp.vote_granted);
@@ -0,0 +1,87 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::Instant;
+use openraft::async_runtime::WatchReceiver;
+use openraft::type_config::TypeConfigExt;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// With `two_voter_tie_breaker`, the voter with the smaller id in a 2-voter cluster starts an
+/// election after `election_timeout_min`, and the other one after `election_timeout_max`.
+///
+/// Both wait for the leader lease, which is `election_timeout_max`, to expire first.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn two_voter_tie_breaker() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            heartbeat_interval: 100,
+            election_timeout_min: 500,
+            election_timeout_max: 1_500,
+            enable_pre_vote: Some(false),
+            two_voter_tie_breaker: Some(true),
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- create cluster of 0,1; node 0 becomes leader");
+    router.new_cluster(btreeset! {0,1}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let n1 = router.get_raft_handle(&1)?;
+
+    tracing::info!("--- isolate leader 0: node 1 waits for election_timeout_max");
+    {
+        let term = n1.metrics().borrow_watched().current_term;
+
+        router.set_unreachable(0, true);
+        let start = TypeConfig::now();
+        n1.wait(timeout()).metrics(|m| m.current_term > term, "node 1 starts election").await?;
+
+        let elapsed = start.elapsed();
+        assert!(
+            elapsed >= Duration::from_millis(2_900),
+            "node 1 has the greater id and must not compete, elapsed: {:?}",
+            elapsed
+        );
+    }
+
+    tracing::info!("--- restore node 0 and make node 1 the leader");
+    {
+        router.set_unreachable(0, false);
+        n1.trigger().elect(false).await?;
+        n1.wait(timeout()).current_leader(1, "node 1 becomes leader").await?;
+        n0.wait(timeout()).current_leader(1, "node 0 follows node 1").await?;
+    }
+
+    tracing::info!("--- isolate leader 1: node 0 starts election after election_timeout_min");
+    {
+        let term = n0.metrics().borrow_watched().current_term;
+
+        router.set_unreachable(1, true);
+        let start = TypeConfig::now();
+        n0.wait(timeout()).metrics(|m| m.current_term > term, "node 0 starts election").await?;
+
+        let elapsed = start.elapsed();
+        assert!(
+            elapsed < Duration::from_millis(2_500),
+            "node 0 has the smaller id and detects the loss of the leader early, elapsed: {:?}",
+            elapsed
+        );
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(5_000))
+}
@@ -0,0 +1,77 @@
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::RPCTypes;
+use openraft::base::BoxFuture;
+use openraft::type_config::TypeConfigExt;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// With `vote_hedge_delay`, a vote request stalled on one connection is sent again over a second
+/// connection, and the election completes without waiting for the stalled one.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn vote_hedging() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            heartbeat_interval: 50,
+            election_timeout_min: 300,
+            election_timeout_max: 600,
+            enable_elect: false,
+            enable_pre_vote: Some(false),
+            vote_hedge_delay: Some(50),
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- create cluster of 0,1,2; node 0 becomes leader");
+    router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!("--- stop heartbeat and wait for the leader lease to expire");
+    {
+        router.get_raft_handle(&0)?.runtime_config().heartbeat(false);
+        TypeConfig::sleep(Duration::from_millis(1_000)).await;
+    }
+
+    tracing::info!("--- stall the first vote request to every target");
+    {
+        let stalled = Arc::new(Mutex::new(BTreeSet::new()));
+
+        router
+            .set_rpc_pre_hook(RPCTypes::Vote, move |_router, _req, _from, target| {
+                let first = stalled.lock().unwrap().insert(target);
+                let fu = async move {
+                    if first {
+                        TypeConfig::sleep(Duration::from_millis(10_000)).await;
+                    }
+                    Ok(())
+                };
+                let x: BoxFuture<_> = Box::pin(fu);
+                x
+            })
+            .await;
+    }
+
+    tracing::info!("--- node 1 is elected by the hedged vote requests");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        n1.trigger().elect(false).await?;
+        n1.wait(timeout()).current_leader(1, "node 1 becomes leader").await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(2_000))
+}
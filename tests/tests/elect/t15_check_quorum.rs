@@ -0,0 +1,92 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+use openraft::async_runtime::WatchReceiver;
+use openraft::type_config::TypeConfigExt;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// With `check_quorum`, a Leader that can not reach a quorum for an election timeout steps down.
+///
+/// - brings a cluster of 3 voters online, with elections disabled on the followers.
+/// - asserts the Leader keeps leading while it hears from the followers.
+/// - isolates the Leader, asserts it becomes a Follower of the same term.
+/// - enables Pre-Vote elections on it, asserts it does not raise its term while isolated.
+/// - restores the network, asserts it is elected again at the next term.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn check_quorum() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            heartbeat_interval: 50,
+            election_timeout_min: 300,
+            election_timeout_max: 400,
+            enable_elect: false,
+            check_quorum: Some(true),
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- create cluster of 0,1,2; node 0 becomes leader");
+    router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!("--- the Leader hears from the followers and keeps leading");
+    {
+        TypeConfig::sleep(Duration::from_millis(1_000)).await;
+
+        let m = n0.metrics().borrow_watched().clone();
+        assert_eq!(ServerState::Leader, m.state);
+    }
+
+    let term = n0.metrics().borrow_watched().current_term;
+
+    tracing::info!("--- isolate leader 0: it steps down");
+    {
+        router.set_unreachable(0, true);
+
+        n0.wait(timeout()).state(ServerState::Follower, "node 0 steps down").await?;
+
+        let m = n0.metrics().borrow_watched().clone();
+        assert_eq!(term, m.current_term);
+        assert_eq!(None, m.current_leader);
+    }
+
+    tracing::info!("--- isolated node 0 does not raise its term");
+    {
+        n0.runtime_config().pre_vote(true);
+        n0.runtime_config().elect(true);
+
+        TypeConfig::sleep(Duration::from_millis(1_000)).await;
+
+        let m = n0.metrics().borrow_watched().clone();
+        assert_eq!(term, m.current_term);
+        assert_eq!(ServerState::Follower, m.state);
+    }
+
+    tracing::info!("--- restore the network: node 0 is elected at the next term");
+    {
+        router.set_unreachable(0, false);
+
+        n0.wait(timeout()).state(ServerState::Leader, "node 0 is elected").await?;
+
+        let m = n0.metrics().borrow_watched().clone();
+        assert_eq!(term + 1, m.current_term);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(2_000))
+}
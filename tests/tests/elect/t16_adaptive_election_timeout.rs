@@ -0,0 +1,66 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+use openraft::async_runtime::WatchReceiver;
+use openraft::type_config::TypeConfigExt;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// With `adaptive_election_timeout`, a cluster still elects a new leader when the leader is lost.
+///
+/// - brings a cluster of 3 voters online and keeps the leadership stable for a while.
+/// - isolates the leader, asserts one of the other voters becomes leader.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn adaptive_election_timeout() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            heartbeat_interval: 10,
+            election_timeout_min: 40,
+            election_timeout_max: 80,
+            adaptive_election_timeout: Some(true),
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- create cluster of 0,1,2; node 0 becomes leader");
+    router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!("--- keep the leadership stable");
+    {
+        TypeConfig::sleep(Duration::from_millis(1_000)).await;
+
+        let m = n0.metrics().borrow_watched().clone();
+        assert_eq!(ServerState::Leader, m.state);
+    }
+
+    tracing::info!("--- isolate leader 0: another voter becomes leader");
+    {
+        router.set_unreachable(0, true);
+
+        router
+            .wait(&1, timeout())
+            .metrics(
+                |m| m.current_leader.is_some() && m.current_leader != Some(0),
+                "node 1 follows a new leader",
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(5_000))
+}
@@ -0,0 +1,76 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+use openraft::type_config::TypeConfigExt;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// `RaftMetrics::candidate` reports the election a node is running, so that a stuck election is
+/// visible from the metrics of the candidate.
+///
+/// - isolates node 1, triggers an election on it, asserts it reports an election of term 2 granted
+///   only by itself.
+/// - restores node 1, which steps down leader 0 with its higher term; once the lease of node 0
+///   expires, triggers another election, asserts the candidate is cleared once node 1 becomes
+///   leader.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn candidate_metrics() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- create cluster of 0,1,2; node 0 becomes leader");
+    router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n1 = router.get_raft_handle(&1)?;
+
+    tracing::info!("--- isolate node 1, the election of node 1 is stuck");
+    {
+        router.set_unreachable(1, true);
+
+        n1.trigger().elect(false).await?;
+
+        let m = router.wait(&1, timeout()).metrics(|m| m.candidate.is_some(), "node 1 is a candidate").await?;
+
+        let candidate = m.candidate.unwrap();
+        assert_eq!(2, candidate.term);
+        assert!(!candidate.pre_vote);
+        assert_eq!(btreeset! {1}, candidate.granted);
+        assert_eq!(ServerState::Candidate, m.state);
+    }
+
+    tracing::info!("--- restore node 1, the candidate is cleared once elected");
+    {
+        router.set_unreachable(1, false);
+
+        router.wait(&0, timeout()).metrics(|m| m.state != ServerState::Leader, "node 0 steps down").await?;
+        TypeConfig::sleep(Duration::from_millis(config.election_timeout_max * 2)).await;
+
+        n1.trigger().elect(false).await?;
+
+        let m = router
+            .wait(&1, timeout())
+            .metrics(|m| m.state == ServerState::Leader, "node 1 becomes leader")
+            .await?;
+        assert_eq!(None, m.candidate);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
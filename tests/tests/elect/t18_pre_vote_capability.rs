@@ -0,0 +1,63 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+use openraft::async_runtime::WatchReceiver;
+use openraft::raft::Capabilities;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// A node does not run a Pre-Vote once a voter is seen without [`Capabilities::PRE_VOTE`].
+///
+/// Node 2 emulates an older version that does not advertise Pre-Vote support. After node 1 has
+/// heard from it, an isolated node 1 falls back to plain elections and inflates its term, which it
+/// would not do with Pre-Vote.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn pre_vote_is_not_used_with_peer_lacking_it() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_pre_vote: Some(true),
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+    router.set_capabilities(2, Some(Capabilities::empty()));
+
+    tracing::info!("--- create cluster of 0,1,2; node 0 becomes leader");
+    router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n1 = router.get_raft_handle(&1)?;
+    n1.wait(timeout()).state(ServerState::Follower, "node 1 is follower").await?;
+
+    tracing::info!("--- node 1 runs a Pre-Vote round and learns the capabilities of its peers");
+    n1.trigger().elect(true).await?;
+    n1.wait(timeout())
+        .metrics(
+            |m| m.peer_capabilities.len() == 2,
+            "node 1 knows the capabilities of 0 and 2",
+        )
+        .await?;
+
+    let caps = n1.metrics().borrow_watched().peer_capabilities.clone();
+    assert_eq!(Some(&Capabilities::supported()), caps.get(&0));
+    assert_eq!(Some(&Capabilities::empty()), caps.get(&2));
+
+    let term_before = n1.metrics().borrow_watched().current_term;
+
+    tracing::info!("--- isolate node 1; without Pre-Vote it inflates its term");
+    router.set_network_error(1, true);
+    n1.wait(timeout()).metrics(|m| m.current_term > term_before, "node 1 runs a plain election").await?;
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}
@@ -31,6 +31,7 @@ use openraft::RaftTypeConfig;
 use openraft::ReadPolicy;
 use openraft::ServerState;
 use openraft::Vote;
+use openraft::alias::SnapshotMetaOf;
 use openraft::alias::SnapshotOf;
 use openraft::async_runtime::Mutex as AsyncMutex;
 use openraft::errors::ClientWriteError;
@@ -42,17 +43,26 @@ use openraft::errors::RaftError;
 use openraft::errors::ReplicationClosed;
 use openraft::errors::StreamingError;
 use openraft::errors::Unreachable;
+use openraft::membership::QuorumPolicy;
 use openraft::metrics::Wait;
 use openraft::network::RPCOption;
 use openraft::network::RaftNetworkFactory;
 use openraft::raft::AppendEntriesRequest;
 use openraft::raft::AppendEntriesResponse;
+use openraft::raft::Capabilities;
 use openraft::raft::ClientWriteResponse;
+use openraft::raft::FetchSnapshotRequest;
+use openraft::raft::FetchSnapshotResponse;
+use openraft::raft::ReadIndexRequest;
+use openraft::raft::ReadIndexResponse;
+use openraft::raft::SeedSnapshotRequest;
+use openraft::raft::SeedSnapshotResponse;
 use openraft::raft::SnapshotResponse;
 use openraft::raft::TransferLeaderRequest;
 use openraft::raft::TransferLeaderResponse;
 use openraft::raft::VoteRequest;
 use openraft::raft::VoteResponse;
+use openraft::storage::SnapshotLocator;
 use openraft::type_config::TypeConfigExt;
 use openraft::type_config::alias::MutexOf;
 use openraft::vote::RaftLeaderId;
@@ -233,6 +243,9 @@ pub struct TypedRaftRouter {
     /// Count of RPCs sent.
     rpc_count: Arc<Mutex<HashMap<RPCTypes, u64>>>,
 
+    /// To emulate a node of another version, override the capabilities it advertises.
+    capabilities: Arc<Mutex<HashMap<MemNodeId, Capabilities>>>,
+
     /// A hook function to be called when before an RPC is sent to target node.
     rpc_pre_hook: Arc<MutexOf<TypeConfig, HashMap<RPCTypes, PreHook>>>,
 
@@ -273,6 +286,7 @@ impl Builder {
             send_delay: Arc::new(AtomicU64::new(send_delay)),
             append_entries_quota: Arc::new(Mutex::new(None)),
             rpc_count: Default::default(),
+            capabilities: Default::default(),
             rpc_pre_hook: Arc::new(TypeConfig::mutex(HashMap::new())),
             rpc_post_hook: Arc::new(TypeConfig::mutex(HashMap::new())),
         }
@@ -427,6 +441,31 @@ impl TypedRaftRouter {
         (log, sm)
     }
 
+    /// Create and register a new Raft node with its own config instead of the router's.
+    pub async fn new_raft_node_with_config(&mut self, id: MemNodeId, config: Arc<Config>) {
+        let (log_store, sm) = self.new_store();
+        let node = Raft::new(id, config, self.clone(), log_store.clone(), sm.clone()).await.unwrap();
+        let mut rt = self.nodes.lock().unwrap();
+        rt.insert(id, (node, log_store, sm));
+    }
+
+    /// Create and register a new Raft node that decides quorums with `policy`.
+    pub async fn new_raft_node_with_quorum_policy(&mut self, id: MemNodeId, policy: Arc<dyn QuorumPolicy<TypeConfig>>) {
+        let (log_store, sm) = self.new_store();
+        let node = Raft::new_with_quorum_policy(
+            id,
+            self.config.clone(),
+            self.clone(),
+            log_store.clone(),
+            sm.clone(),
+            policy,
+        )
+        .await
+        .unwrap();
+        let mut rt = self.nodes.lock().unwrap();
+        rt.insert(id, (node, log_store, sm));
+    }
+
     #[tracing::instrument(level = "debug", skip_all)]
     pub async fn new_raft_node_with_sto(&mut self, id: MemNodeId, log_store: MemLogStore, sm: MemStateMachine) {
         let node = Raft::new(id, self.config.clone(), self.clone(), log_store.clone(), sm.clone()).await.unwrap();
@@ -500,6 +539,23 @@ impl TypedRaftRouter {
         }
     }
 
+    /// Override the capabilities node `id` advertises in its `VoteResponse`, e.g., to emulate an
+    /// older version. `None` restores the real ones.
+    pub fn set_capabilities(&self, id: MemNodeId, capabilities: Option<Capabilities>) {
+        let mut caps = self.capabilities.lock().unwrap();
+        if let Some(c) = capabilities {
+            caps.insert(id, c);
+        } else {
+            caps.remove(&id);
+        }
+    }
+
+    fn override_capabilities(&self, id: MemNodeId, resp: &mut VoteResponse<MemConfig>) {
+        if let Some(c) = self.capabilities.lock().unwrap().get(&id) {
+            resp.capabilities = *c;
+        }
+    }
+
     /// Set a hook function to be called when before an RPC is sent to target node.
     pub async fn set_rpc_pre_hook<F>(&self, rpc_type: RPCTypes, hook: F)
     where F: Fn(&TypedRaftRouter, RpcRequest<TypeConfig>, MemNodeId, MemNodeId) -> PreHookResult + Send + 'static {
@@ -676,6 +732,13 @@ impl TypedRaftRouter {
         Ok(())
     }
 
+    /// Set whether every `append` call should fail for a node.
+    pub fn set_fail_append(&self, node_id: &MemNodeId, value: bool) -> anyhow::Result<()> {
+        let (log_store, _) = self.get_storage_handle(node_id)?;
+        log_store.set_fail_append(value);
+        Ok(())
+    }
+
     pub fn wait(&self, node_id: &MemNodeId, timeout: Option<Duration>) -> Wait<MemConfig> {
         let node = {
             let rt = self.nodes.lock().unwrap();
@@ -966,12 +1029,13 @@ impl RaftNetworkV2<MemConfig> for RaftRouterNetwork {
         let node = self.owner.get_raft_handle(&self.target)?;
 
         let resp = node.vote(rpc.clone()).await;
-        let resp = resp.map_err(|e| {
+        let mut resp = resp.map_err(|e| {
             RPCError::Unreachable(Unreachable::<MemConfig>::from_string(format!(
                 "error: {} target={}",
                 e, self.target
             )))
         })?;
+        self.owner.override_capabilities(self.target, &mut resp);
 
         self.owner.call_rpc_post_hook(rpc, resp.clone(), from_id, self.target).await?;
 
@@ -993,12 +1057,13 @@ impl RaftNetworkV2<MemConfig> for RaftRouterNetwork {
         let node = self.owner.get_raft_handle(&self.target)?;
 
         let resp = node.pre_vote(rpc.clone()).await;
-        let resp = resp.map_err(|e| {
+        let mut resp = resp.map_err(|e| {
             RPCError::Unreachable(Unreachable::<MemConfig>::from_string(format!(
                 "error: {} target={}",
                 e, self.target
             )))
         })?;
+        self.owner.override_capabilities(self.target, &mut resp);
 
         self.owner.call_rpc_post_hook(rpc, resp.clone(), from_id, self.target).await?;
 
@@ -1031,6 +1096,143 @@ impl RaftNetworkV2<MemConfig> for RaftRouterNetwork {
 
         Ok(resp)
     }
+
+    async fn read_index(
+        &mut self,
+        rpc: ReadIndexRequest<MemConfig>,
+        _option: RPCOption,
+    ) -> Result<ReadIndexResponse<MemConfig>, RPCError<MemConfig>> {
+        let from_id = *rpc.from();
+
+        self.owner.count_rpc(RPCTypes::ReadIndex);
+        self.owner.call_rpc_pre_hook(rpc.clone(), from_id, self.target).await?;
+        self.owner.emit_rpc_error(from_id, self.target)?;
+        self.owner.rand_send_delay().await;
+
+        let node = self.owner.get_raft_handle(&self.target)?;
+
+        let resp = node.handle_read_index(rpc.clone()).await;
+        let resp = resp.map_err(|err| {
+            RPCError::Unreachable(Unreachable::<MemConfig>::from_string(format!(
+                "error: {} target={}",
+                err, self.target
+            )))
+        })?;
+
+        self.owner.call_rpc_post_hook(rpc, resp.clone(), from_id, self.target).await?;
+
+        Ok(resp)
+    }
+
+    async fn seed_snapshot(
+        &mut self,
+        rpc: SeedSnapshotRequest<MemConfig>,
+        _option: RPCOption,
+    ) -> Result<SeedSnapshotResponse<MemConfig>, RPCError<MemConfig>> {
+        let from_id = rpc.vote.leader_id().to_node_id();
+
+        self.owner.count_rpc(RPCTypes::SeedSnapshot);
+        self.owner.call_rpc_pre_hook(rpc.clone(), from_id, self.target).await?;
+        self.owner.emit_rpc_error(from_id, self.target)?;
+        self.owner.rand_send_delay().await;
+
+        let node = self.owner.get_raft_handle(&self.target)?;
+
+        let resp = node.handle_seed_snapshot(rpc.clone()).await;
+        let resp = resp.map_err(|err| {
+            RPCError::Unreachable(Unreachable::<MemConfig>::from_string(format!(
+                "error: {} target={}",
+                err, self.target
+            )))
+        })?;
+
+        self.owner.call_rpc_post_hook(rpc, resp.clone(), from_id, self.target).await?;
+
+        Ok(resp)
+    }
+
+    async fn fetch_snapshot(
+        &mut self,
+        rpc: FetchSnapshotRequest<MemConfig>,
+        _option: RPCOption,
+    ) -> Result<FetchSnapshotResponse<MemConfig>, RPCError<MemConfig>> {
+        let from_id = rpc.vote.leader_id().to_node_id();
+
+        self.owner.count_rpc(RPCTypes::FetchSnapshot);
+        self.owner.call_rpc_pre_hook(rpc.clone(), from_id, self.target).await?;
+        self.owner.emit_rpc_error(from_id, self.target)?;
+        self.owner.rand_send_delay().await;
+
+        let node = self.owner.get_raft_handle(&self.target)?;
+
+        let resp = node.handle_fetch_snapshot(rpc.clone()).await;
+        let resp = resp.map_err(|err| {
+            RPCError::Unreachable(Unreachable::<MemConfig>::from_string(format!(
+                "error: {} target={}",
+                err, self.target
+            )))
+        })?;
+
+        self.owner.call_rpc_post_hook(rpc, resp.clone(), from_id, self.target).await?;
+
+        Ok(resp)
+    }
+
+    async fn snapshot_locator(
+        &mut self,
+        vote: Vote<<MemConfig as RaftTypeConfig>::LeaderId>,
+        locator: SnapshotLocator<MemConfig>,
+        _option: RPCOption,
+    ) -> Result<SnapshotResponse<MemConfig>, RPCError<MemConfig>> {
+        let from_id = vote.leader_id().to_node_id();
+
+        self.owner.count_rpc(RPCTypes::SnapshotLocator);
+        self.owner.call_rpc_pre_hook(locator.clone(), from_id, self.target).await?;
+        self.owner.emit_rpc_error(from_id, self.target)?;
+        self.owner.rand_send_delay().await;
+
+        let node = self.owner.get_raft_handle(&self.target)?;
+
+        let resp = node.install_snapshot_locator(vote, locator.clone()).await;
+        let resp = resp.map_err(|err| {
+            RPCError::Unreachable(Unreachable::<MemConfig>::from_string(format!(
+                "error: {} target={}",
+                err, self.target
+            )))
+        })?;
+
+        self.owner.call_rpc_post_hook(locator, resp.clone(), from_id, self.target).await?;
+
+        Ok(resp)
+    }
+
+    async fn snapshot_meta(
+        &mut self,
+        vote: Vote<<MemConfig as RaftTypeConfig>::LeaderId>,
+        meta: SnapshotMetaOf<MemConfig>,
+        _option: RPCOption,
+    ) -> Result<SnapshotResponse<MemConfig>, RPCError<MemConfig>> {
+        let from_id = vote.leader_id().to_node_id();
+
+        self.owner.count_rpc(RPCTypes::SnapshotMeta);
+        self.owner.call_rpc_pre_hook(meta.clone(), from_id, self.target).await?;
+        self.owner.emit_rpc_error(from_id, self.target)?;
+        self.owner.rand_send_delay().await;
+
+        let node = self.owner.get_raft_handle(&self.target)?;
+
+        let resp = node.install_snapshot_meta(vote, meta.clone()).await;
+        let resp = resp.map_err(|err| {
+            RPCError::Unreachable(Unreachable::<MemConfig>::from_string(format!(
+                "error: {} target={}",
+                err, self.target
+            )))
+        })?;
+
+        self.owner.call_rpc_post_hook(meta, resp.clone(), from_id, self.target).await?;
+
+        Ok(resp)
+    }
 }
 
 fn timeout() -> Option<Duration> {
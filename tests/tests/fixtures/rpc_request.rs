@@ -4,11 +4,16 @@ use std::fmt;
 
 use openraft::RPCTypes;
 use openraft::RaftTypeConfig;
+use openraft::alias::SnapshotMetaOf;
 use openraft::alias::SnapshotOf;
 use openraft::raft::AppendEntriesRequest;
+use openraft::raft::FetchSnapshotRequest;
 use openraft::raft::InstallSnapshotRequest;
+use openraft::raft::ReadIndexRequest;
+use openraft::raft::SeedSnapshotRequest;
 use openraft::raft::TransferLeaderRequest;
 use openraft::raft::VoteRequest;
+use openraft::storage::SnapshotLocator;
 
 /// Unified enum for all RPC request types in the test framework.
 #[derive(Debug)]
@@ -21,6 +26,11 @@ where C::SnapshotData: fmt::Debug
     InstallFullSnapshot(SnapshotOf<C>),
     Vote(VoteRequest<C>),
     TransferLeader(TransferLeaderRequest<C>),
+    ReadIndex(ReadIndexRequest<C>),
+    SeedSnapshot(SeedSnapshotRequest<C>),
+    FetchSnapshot(FetchSnapshotRequest<C>),
+    SnapshotLocator(SnapshotLocator<C>),
+    SnapshotMeta(SnapshotMetaOf<C>),
 }
 
 impl<C: RaftTypeConfig> RpcRequest<C>
@@ -33,6 +43,11 @@ where C::SnapshotData: fmt::Debug
             RpcRequest::InstallFullSnapshot(_) => RPCTypes::InstallSnapshot,
             RpcRequest::Vote(_) => RPCTypes::Vote,
             RpcRequest::TransferLeader(_) => RPCTypes::TransferLeader,
+            RpcRequest::ReadIndex(_) => RPCTypes::ReadIndex,
+            RpcRequest::SeedSnapshot(_) => RPCTypes::SeedSnapshot,
+            RpcRequest::FetchSnapshot(_) => RPCTypes::FetchSnapshot,
+            RpcRequest::SnapshotLocator(_) => RPCTypes::SnapshotLocator,
+            RpcRequest::SnapshotMeta(_) => RPCTypes::SnapshotMeta,
         }
     }
 }
@@ -49,6 +64,11 @@ where
             RpcRequest::InstallFullSnapshot(req) => write!(f, "InstallFullSnapshot({})", req.meta),
             RpcRequest::Vote(req) => write!(f, "Vote({})", req),
             RpcRequest::TransferLeader(req) => write!(f, "TransferLeader({})", req),
+            RpcRequest::ReadIndex(req) => write!(f, "ReadIndex({})", req),
+            RpcRequest::SeedSnapshot(req) => write!(f, "SeedSnapshot({})", req),
+            RpcRequest::FetchSnapshot(req) => write!(f, "FetchSnapshot({})", req),
+            RpcRequest::SnapshotLocator(req) => write!(f, "SnapshotLocator({})", req),
+            RpcRequest::SnapshotMeta(req) => write!(f, "SnapshotMeta({})", req),
         }
     }
 }
@@ -5,7 +5,10 @@ use std::fmt;
 use openraft::RPCTypes;
 use openraft::RaftTypeConfig;
 use openraft::raft::AppendEntriesResponse;
+use openraft::raft::FetchSnapshotResponse;
 use openraft::raft::InstallSnapshotResponse;
+use openraft::raft::ReadIndexResponse;
+use openraft::raft::SeedSnapshotResponse;
 use openraft::raft::SnapshotResponse;
 use openraft::raft::TransferLeaderResponse;
 use openraft::raft::VoteResponse;
@@ -23,6 +26,9 @@ where
     InstallFullSnapshot(SnapshotResponse<C>),
     Vote(VoteResponse<C>),
     TransferLeader(TransferLeaderResponse<C>),
+    ReadIndex(ReadIndexResponse<C>),
+    SeedSnapshot(SeedSnapshotResponse<C>),
+    FetchSnapshot(FetchSnapshotResponse<C>),
 }
 
 impl<C> RpcResponse<C>
@@ -37,6 +43,9 @@ where
             RpcResponse::InstallFullSnapshot(_) => RPCTypes::InstallSnapshot,
             RpcResponse::Vote(_) => RPCTypes::Vote,
             RpcResponse::TransferLeader(_) => RPCTypes::TransferLeader,
+            RpcResponse::ReadIndex(_) => RPCTypes::ReadIndex,
+            RpcResponse::SeedSnapshot(_) => RPCTypes::SeedSnapshot,
+            RpcResponse::FetchSnapshot(_) => RPCTypes::FetchSnapshot,
         }
     }
 }
@@ -53,6 +62,10 @@ where
             RpcResponse::InstallFullSnapshot(resp) => write!(f, "InstallFullSnapshot({})", resp),
             RpcResponse::Vote(resp) => write!(f, "Vote({})", resp),
             RpcResponse::TransferLeader(resp) => write!(f, "TransferLeader({:?})", resp),
+            RpcResponse::ReadIndex(resp) => write!(f, "ReadIndex({:?})", resp),
+            RpcResponse::SeedSnapshot(resp) => write!(f, "SeedSnapshot({})", resp),
+            RpcResponse::FetchSnapshot(Some(resp)) => write!(f, "FetchSnapshot({})", resp.meta),
+            RpcResponse::FetchSnapshot(None) => write!(f, "FetchSnapshot(None)"),
         }
     }
 }
@@ -16,6 +16,7 @@ mod t50_leader_restart_leader_restore_disabled;
 mod t50_single_follower_restart;
 mod t50_single_leader_restart_re_apply_logs;
 mod t50_wait_for_recovery;
+mod t60_node_remap;
 mod t90_issue_607_single_restart;
 mod t90_issue_881_transient_state_machine;
 mod t90_issue_920_non_voter_leader_restart;
@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Result;
 use maplit::btreeset;
@@ -38,6 +39,98 @@ async fn shutdown() -> Result<()> {
     Ok(())
 }
 
+/// `shutdown()` returns the report of the final state of the node.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn shutdown_report() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    log_index += router.client_request_many(0, "foo", 3).await?;
+    router.wait(&0, timeout()).applied_index(Some(log_index), "write 3 logs").await?;
+
+    tracing::info!(log_index, "--- shutdown and check the report");
+    {
+        let n = router.get_raft_handle(&0)?;
+        let last_log_id = n.metrics().borrow_watched().last_applied;
+
+        let report = n.shutdown().await?.unwrap();
+        assert_eq!(0, report.id);
+        assert_eq!(Some(log_index), report.last_log_id.map(|x| x.index));
+        assert_eq!(last_log_id, report.last_log_id);
+        assert_eq!(last_log_id, report.committed);
+        assert_eq!(last_log_id, report.applied);
+        assert_eq!(None, report.snapshot);
+        assert_eq!(0, report.pending_proposals);
+        assert_eq!(Fatal::Stopped, report.reason);
+
+        tracing::info!("--- a second shutdown returns the same report");
+        assert_eq!(Some(report), n.shutdown().await?);
+    }
+
+    Ok(())
+}
+
+/// `shutdown_graceful()` on a leader hands leadership off to the most caught up voter before the
+/// node stops.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn shutdown_graceful_hands_off_leadership() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- node-1 falls behind");
+    {
+        router.set_unreachable(1, true);
+        log_index += router.client_request_many(0, "foo", 3).await?;
+        router.wait(&2, timeout()).applied_index(Some(log_index), "node-2 is up to date").await?;
+    }
+
+    tracing::info!(log_index, "--- shutdown_graceful the leader");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        let report = n0.shutdown_graceful(Duration::from_millis(3_000)).await?.unwrap();
+        assert_eq!(Fatal::Stopped, report.reason);
+        assert_eq!(ServerState::Shutdown, n0.metrics().borrow_watched().state);
+
+        let n2 = router.get_raft_handle(&2)?;
+        assert_eq!(
+            ServerState::Leader,
+            n2.metrics().borrow_watched().state,
+            "the most caught up voter is the leader once shutdown_graceful returns"
+        );
+    }
+
+    tracing::info!(log_index, "--- shutdown_graceful a non-leader just shuts it down");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        n1.shutdown_graceful(Duration::from_millis(3_000)).await?;
+        assert_eq!(ServerState::Shutdown, n1.metrics().borrow_watched().state);
+    }
+
+    Ok(())
+}
+
 /// A panicked RaftCore should also return a proper error the next time accessing the `Raft`.
 #[tracing::instrument]
 #[test_harness::test(harness = ut_harness)]
@@ -75,6 +168,12 @@ async fn return_error_after_panic() -> Result<()> {
         assert_eq!(Fatal::Panicked, err.into_fatal().unwrap());
     }
 
+    tracing::info!(log_index, "--- a panicked RaftCore has no shutdown report");
+    {
+        let n = router.get_raft_handle(&0)?;
+        assert_eq!(None, n.shutdown().await?);
+    }
+
     Ok(())
 }
 
@@ -114,3 +213,7 @@ async fn return_error_after_shutdown() -> Result<()> {
 
     Ok(())
 }
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,100 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use openraft::Config;
+use openraft::async_runtime::WatchReceiver;
+use openraft::storage::NodeRemap;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// The data of a stopped cluster is cloned into a cluster with different node ids with
+/// [`NodeRemap`]: the cloned cluster elects a leader among the new ids and keeps working.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn node_remap() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            max_in_snapshot_log_to_keep: 0,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- bring up cluster of 3 nodes");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(
+        log_index,
+        "--- write logs, build snapshots and purge the membership logs"
+    );
+    {
+        log_index += router.client_request_many(0, "foo", 5).await?;
+        for id in [0, 1, 2] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "apply logs").await?;
+            let n = router.get_raft_handle(&id)?;
+            n.trigger().snapshot().await?;
+            router.wait(&id, timeout()).snapshot(log_id(1, 0, log_index), "build snapshot").await?;
+            router.wait(&id, timeout()).purged(Some(log_id(1, 0, log_index)), "purge logs").await?;
+        }
+    }
+
+    tracing::info!(log_index, "--- add a learner, whose membership log stays in the log");
+    {
+        router.new_raft_node(3).await;
+        router.add_learner(0, 3).await?;
+        log_index += 1;
+        for id in [0, 1, 2, 3] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "add learner").await?;
+        }
+    }
+
+    tracing::info!(log_index, "--- stop all nodes and remap their data to new ids");
+    {
+        let remap = NodeRemap::<TypeConfig>::new().map(0, 10, ()).map(1, 11, ()).map(2, 12, ()).map(3, 13, ());
+
+        for id in [0, 1, 2, 3] {
+            let (node, mut ls, mut sm) = router.remove_node(id).unwrap();
+            node.shutdown().await?;
+
+            remap.remap_log_store(&mut ls).await?;
+            remap.remap_state_machine(&mut sm).await?;
+
+            router.new_raft_node_with_sto(id + 10, ls, sm).await;
+        }
+    }
+
+    tracing::info!(log_index, "--- the cloned cluster elects a leader and accepts writes");
+    {
+        let n10 = router.get_raft_handle(&10)?;
+        n10.trigger().elect(false).await?;
+        n10.wait(timeout()).current_leader(10, "node 10 becomes leader").await?;
+        log_index += 1;
+
+        let metrics = n10.metrics().borrow_watched().clone();
+        assert_eq!(
+            btreeset! {10,11,12},
+            metrics.membership_config.membership().voter_ids().collect()
+        );
+        assert_eq!(
+            btreeset! {13},
+            metrics.membership_config.membership().learner_ids().collect()
+        );
+
+        log_index += router.client_request_many(10, "bar", 2).await?;
+        for id in [10, 11, 12, 13] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "cloned cluster works").await?;
+        }
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(2_000))
+}
@@ -8,3 +8,8 @@ mod fixtures;
 // The later tests may depend on the earlier ones.
 
 mod t10_save_committed;
+mod t20_log_subscription;
+mod t30_degraded_follower;
+mod t40_purge_durable_applied;
+#[cfg(feature = "audit-log-chain")]
+mod t50_audit_log_chain;
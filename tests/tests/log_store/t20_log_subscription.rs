@@ -0,0 +1,82 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::async_runtime::WatchReceiver;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// A log subscription is notified of durable logs, and holds them from being purged until it
+/// advances.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn log_subscription_holds_purge() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            max_in_snapshot_log_to_keep: 0,
+            purge_batch_size: 1,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initialize cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n1 = router.get_raft_handle(&1)?;
+    let mut sub = n1.subscribe_log(0).await?;
+    assert_eq!(0, sub.next_index());
+
+    let n = 10;
+    tracing::info!(log_index, "--- write {} logs", n);
+    log_index += router.client_request_many(0, "foo", n).await?;
+
+    tracing::info!(log_index, "--- subscriber is notified of durable logs");
+    {
+        router.wait(&1, timeout()).applied_index(Some(log_index), "write logs").await?;
+
+        let last = sub.wait_durable().await?;
+        assert_eq!(log_id(1, 0, log_index), last);
+    }
+
+    tracing::info!(
+        log_index,
+        "--- build snapshot, logs held by the subscriber are not purged"
+    );
+    {
+        n1.trigger().snapshot().await?;
+        n1.wait(timeout()).snapshot(log_id(1, 0, log_index), "build snapshot").await?;
+
+        let purged = n1.metrics().borrow_watched().purged;
+        assert_eq!(None, purged, "no log is purged");
+    }
+
+    tracing::info!(log_index, "--- advance the subscriber, the consumed logs are purged");
+    {
+        sub.advance(5).await?;
+        assert_eq!(6, sub.next_index());
+
+        n1.wait(timeout())
+            .metrics(|m| m.purged == Some(log_id(1, 0, 5)), "purged up to the consumed log")
+            .await?;
+    }
+
+    tracing::info!(log_index, "--- a new subscription starts after the purged logs");
+    {
+        let sub2 = n1.subscribe_log(0).await?;
+        assert_eq!(6, sub2.next_index());
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,114 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+use openraft::Vote;
+use openraft::async_runtime::WatchReceiver;
+use openraft::raft::VoteRequest;
+use openraft::type_config::TypeConfigExt;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// With `degrade_on_storage_error`, a follower whose log storage fails keeps acknowledging
+/// heartbeats and answering requests at once, instead of stopping, and leaves degraded mode once
+/// the storage works again.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn degraded_follower_on_storage_error() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            heartbeat_interval: 100,
+            election_timeout_min: 1000,
+            election_timeout_max: 1001,
+            degrade_on_storage_error: Some(true),
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initialize cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- fail appending on n2, then write");
+    {
+        router.set_fail_append(&2, true)?;
+        log_index += router.client_request_many(0, "foo", 1).await?;
+        router.wait(&0, timeout()).applied_index(Some(log_index), "committed by n0 and n1").await?;
+    }
+
+    let n2 = router.get_raft_handle(&2)?;
+
+    tracing::info!(log_index, "--- n2 keeps running degraded");
+    {
+        n2.wait(timeout()).metrics(|m| m.storage_error.is_some(), "n2 reports the storage error").await?;
+
+        let m = n2.metrics().borrow_watched().clone();
+        assert_eq!(Ok(()), m.running_state);
+        assert_eq!(ServerState::Follower, m.state);
+    }
+
+    tracing::info!(log_index, "--- n2 still acknowledges heartbeats");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        let acked_at =
+            |m: &openraft::RaftMetrics<TypeConfig>| m.heartbeat.as_ref().and_then(|h| h.get(&2).cloned().flatten());
+
+        let before = acked_at(&n0.metrics().borrow_watched());
+        TypeConfig::sleep(Duration::from_millis(300)).await;
+
+        n0.wait(timeout()).metrics(|m| acked_at(m) > before, "n0 receives heartbeat acks from n2").await?;
+    }
+
+    tracing::info!(log_index, "--- n2 answers a client write at once");
+    {
+        let err = n2.client_write(ClientRequest::make_request("foo", 100)).await.unwrap_err();
+        assert!(
+            err.forward_to_leader().is_some(),
+            "expect ForwardToLeader, got: {}",
+            err
+        );
+    }
+
+    tracing::info!(log_index, "--- isolate n2: no append retries the storage");
+    {
+        router.set_network_error(2, true);
+        n2.wait(timeout()).metrics(|m| m.storage_error.is_some(), "n2 stays degraded").await?;
+    }
+
+    tracing::info!(log_index, "--- n2 rejects a vote request, which it can not persist");
+    {
+        let resp = n2.vote(VoteRequest::new(Vote::new(10, 1), Some(log_id(10, 1, 100)))).await?;
+        assert!(!resp.is_granted_to(&Vote::new(10, 1)));
+    }
+
+    tracing::info!(
+        log_index,
+        "--- heal the storage: n2 leaves degraded mode on the next append"
+    );
+    {
+        router.set_fail_append(&2, false)?;
+        router.set_network_error(2, false);
+
+        n2.wait(timeout()).metrics(|m| m.storage_error.is_none(), "n2 is recovered").await?;
+        n2.wait(timeout()).applied_index(Some(log_index), "n2 catches up").await?;
+
+        let m = n2.metrics().borrow_watched().clone();
+        assert_eq!(Ok(()), m.running_state);
+        assert_eq!(ServerState::Follower, m.state);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,58 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::PurgePolicy;
+use openraft::SnapshotPolicy;
+use openraft::storage::RaftLogStorage;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// With `PurgePolicy::DurableApplied`, logs the state machine reports as persisted are purged
+/// without building a snapshot.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn purge_durable_applied() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            snapshot_policy: SnapshotPolicy::Never,
+            max_in_snapshot_log_to_keep: 0,
+            purge_batch_size: 1,
+            purge_policy: Some(PurgePolicy::DurableApplied),
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initialize cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let (mut sto0, sm0) = router.get_storage_handle(&0)?;
+
+    tracing::info!(log_index, "--- state machine reports applied logs as persisted");
+    {
+        sm0.enable_durable_applied(true);
+
+        log_index += router.client_request_many(0, "foo", 10).await?;
+
+        n0.wait(timeout()).purged(Some(log_id(1, 0, log_index)), "purged up to applied").await?;
+
+        let state = sto0.get_log_state().await?;
+        assert_eq!(state.last_purged_log_id, Some(log_id(1, 0, log_index)));
+        assert_eq!(None, n0.current_snapshot_meta(), "no snapshot is built");
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,60 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// With `Config::audit_log_chain` enabled, every node chains the replicated logs with the same
+/// hashes, and `Raft::verify_log_chain()` returns the hash of the last entry.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn audit_log_chain() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            audit_log_chain: Some(true),
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- write 10 logs");
+    log_index += router.client_request_many(0, "foo", 10).await?;
+    for id in [0, 1, 2] {
+        router.wait(&id, timeout()).applied_index(Some(log_index), "logs replicated").await?;
+    }
+
+    tracing::info!(log_index, "--- all nodes have the same chain");
+    let want = router.get_raft_handle(&0)?.verify_log_chain(0..u64::MAX).await?;
+    assert!(want.is_some());
+
+    for id in [1, 2] {
+        let got = router.get_raft_handle(&id)?.verify_log_chain(0..u64::MAX).await?;
+        assert_eq!(want, got, "node-{} has the same chain as the leader", id);
+    }
+
+    tracing::info!(log_index, "--- a sub range is verified against the entry before it");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        let got = n1.verify_log_chain(5..log_index + 1).await?;
+        assert_eq!(want, got);
+
+        let got = n1.verify_log_chain(log_index + 1..u64::MAX).await?;
+        assert_eq!(None, got, "no entry in range");
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
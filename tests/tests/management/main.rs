@@ -8,3 +8,4 @@ mod fixtures;
 // The later tests may depend on the earlier ones.
 
 mod t10_raft_config;
+mod t20_durability_report;
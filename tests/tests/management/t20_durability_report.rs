@@ -0,0 +1,81 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::LogIdOptionExt;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// [`Raft::durability_report`](openraft::Raft::durability_report) tells which voters must keep
+/// their data for the committed logs to stay recoverable.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn durability_report() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- all voters have every committed log");
+    {
+        router
+            .wait(&0, timeout())
+            .metrics(
+                |m| m.replication().is_some_and(|r| r.values().all(|x| x.index() == Some(log_index))),
+                "all voters replicated",
+            )
+            .await?;
+
+        let report = n0.durability_report()?;
+        assert_eq!(Some(log_id(1, 0, log_index)), report.committed);
+        assert_eq!(btreeset! {0,1,2}, report.holders);
+        assert_eq!(btreeset! {0,1}, report.must_survive);
+    }
+
+    tracing::info!(log_index, "--- node-2 falls behind, node-0 and node-1 must survive");
+    {
+        router.set_network_error(2, true);
+        router.client_request_many(0, "foo", 5).await?;
+        log_index += 5;
+
+        router
+            .wait(&0, timeout())
+            .metrics(
+                |m| m.replication().is_some_and(|r| r.get(&1).and_then(|x| x.index()) == Some(log_index)),
+                "node-1 replicated",
+            )
+            .await?;
+
+        let report = n0.durability_report()?;
+        assert_eq!(Some(log_id(1, 0, log_index)), report.committed);
+        assert_eq!(btreeset! {0,1}, report.holders);
+        assert_eq!(btreeset! {0,1}, report.must_survive);
+    }
+
+    tracing::info!(log_index, "--- a follower can not report");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        let res = n1.durability_report();
+        assert!(res.is_err());
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
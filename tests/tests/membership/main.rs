@@ -19,6 +19,13 @@ mod t30_elect_with_new_config;
 mod t31_add_remove_follower;
 mod t31_remove_leader;
 mod t31_removed_follower;
+mod t31_replace_node;
+mod t32_promote_when_caught_up;
+mod t33_witness;
+mod t34_log_only;
+mod t35_evict_unreachable;
+mod t36_quorum_policy;
+mod t37_remove_learners;
 mod t51_remove_unreachable_follower;
 mod t52_change_membership_on_uninitialized_node;
 mod t99_issue_471_adding_learner_uses_uninit_leader_id;
@@ -0,0 +1,171 @@
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::SnapshotPolicy;
+use openraft::async_runtime::watch::WatchReceiver;
+use openraft::errors::ChangeMembershipError;
+use openraft::errors::ClientWriteError;
+use openraft::raft::ReplaceNodeProgress;
+use openraft_memstore::MemNodeId;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// Replace a voter with a new node that has to be bootstrapped by a snapshot.
+///
+/// - brings 3 nodes online and writes logs, the leader builds a snapshot and purges logs.
+/// - replace voter node-2 with a new node-3.
+/// - asserts the stages of the replacement are reported.
+/// - asserts node-3 catches up and becomes a voter, and node-2 is removed from the cluster.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn replace_node() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            snapshot_policy: SnapshotPolicy::LogsSinceLast(10),
+            max_in_snapshot_log_to_keep: 0,
+            purge_batch_size: 1,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- write 20 logs");
+    {
+        router.client_request_many(0, "client", 20).await?;
+        log_index += 20;
+
+        router.wait(&0, timeout()).applied_index(Some(log_index), "write 20 logs").await?;
+        router
+            .wait(&0, timeout())
+            .metrics(|m| m.purged.as_ref().map(|x| x.index()) >= Some(10), "logs are purged")
+            .await?;
+    }
+
+    tracing::info!(log_index, "--- replace node-2 with node-3");
+    {
+        router.new_raft_node(3).await;
+
+        let leader = router.get_raft_handle(&0)?;
+        let mut stages = vec![];
+        let resp = leader.replace_node_with_progress(2, 3, (), |p| stages.push(p)).await?;
+        log_index += 3; // add-learner log and two member-change logs
+
+        assert_eq!(log_index, resp.log_id.index());
+        assert_eq!(
+            vec![
+                ReplaceNodeProgress::LearnerAdded {
+                    log_id: log_id(1, 0, log_index - 2)
+                },
+                ReplaceNodeProgress::CaughtUp,
+                ReplaceNodeProgress::Replaced {
+                    log_id: log_id(1, 0, log_index)
+                },
+            ],
+            stages
+        );
+
+        for id in [0, 1, 3] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "node-3 replaced node-2").await?;
+        }
+    }
+
+    tracing::info!(log_index, "--- node-3 is a voter and node-2 is removed");
+    {
+        let m = router.get_raft_handle(&0)?.metrics().borrow_watched().clone();
+        let membership = m.membership_config.membership();
+
+        assert_eq!(
+            btreeset! {0,1,3},
+            membership.voter_ids().collect::<BTreeSet<MemNodeId>>()
+        );
+        assert!(membership.get_node(&2).is_none());
+    }
+
+    Ok(())
+}
+
+/// Replace a voter with a new node that can not catch up in time.
+///
+/// - brings 3 nodes online, makes the new node-3 unreachable.
+/// - replace voter node-2 with node-3.
+/// - asserts it fails with `LearnerNotCaughtUp` and node-3 is removed again.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn replace_node_not_caught_up() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            replication_lag_threshold: 0,
+            replace_node_timeout: Some(500),
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- replace node-2 with an unreachable node-3");
+    {
+        router.new_raft_node(3).await;
+        router.set_network_error(3, true);
+
+        let leader = router.get_raft_handle(&0)?;
+        let mut stages = vec![];
+        let res = leader.replace_node_with_progress(2, 3, (), |p| stages.push(p)).await;
+        let raft_err = res.unwrap_err();
+
+        match raft_err.api_error().unwrap() {
+            ClientWriteError::ChangeMembershipError(ChangeMembershipError::LearnerNotCaughtUp(err)) => {
+                assert_eq!(3, err.node_id);
+            }
+            _ => {
+                unreachable!("expect LearnerNotCaughtUp")
+            }
+        }
+
+        assert_eq!(
+            vec![
+                ReplaceNodeProgress::LearnerAdded {
+                    log_id: log_id(1, 0, log_index + 1)
+                },
+                ReplaceNodeProgress::RollingBack,
+                ReplaceNodeProgress::RolledBack,
+            ],
+            stages
+        );
+    }
+
+    tracing::info!(log_index, "--- node-3 is removed, node-2 is still a voter");
+    {
+        let m = router
+            .wait(&0, timeout())
+            .metrics(
+                |m| m.membership_config.membership().get_node(&3).is_none(),
+                "node-3 removed",
+            )
+            .await?;
+        let membership = m.membership_config.membership();
+
+        assert_eq!(
+            btreeset! {0,1,2},
+            membership.voter_ids().collect::<BTreeSet<MemNodeId>>()
+        );
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(2000))
+}
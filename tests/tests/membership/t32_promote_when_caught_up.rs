@@ -0,0 +1,92 @@
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreemap;
+use maplit::btreeset;
+use openraft::ChangeMembers;
+use openraft::Config;
+use openraft::async_runtime::watch::WatchReceiver;
+use openraft_memstore::MemNodeId;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// A node added with `AddVotersWhenCaughtUp` is promoted to a voter by the leader once it has
+/// caught up.
+///
+/// - brings 3 nodes online and writes logs.
+/// - adds an unreachable node-3 with `AddVotersWhenCaughtUp`, asserts it stays a learner.
+/// - makes node-3 reachable, asserts the leader promotes it to a voter in a uniform config.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn promote_when_caught_up() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            promote_lag_threshold: Some(0),
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- write 20 logs");
+    {
+        router.client_request_many(0, "client", 20).await?;
+        log_index += 20;
+    }
+
+    tracing::info!(log_index, "--- add unreachable node-3, it stays a learner");
+    {
+        router.new_raft_node(3).await;
+        router.set_unreachable(3, true);
+
+        let leader = router.get_raft_handle(&0)?;
+        let resp = leader.change_membership(ChangeMembers::AddVotersWhenCaughtUp(btreemap! {3=>()}), false).await?;
+        log_index += 1;
+
+        assert_eq!(log_index, resp.log_id.index());
+
+        router.client_request_many(0, "client", 5).await?;
+        log_index += 5;
+
+        router.wait(&0, timeout()).applied_index(Some(log_index), "write logs").await?;
+
+        let m = leader.metrics().borrow_watched().clone();
+        let membership = m.membership_config.membership();
+        assert_eq!(
+            btreeset! {0,1,2},
+            membership.voter_ids().collect::<BTreeSet<MemNodeId>>()
+        );
+        assert_eq!(btreeset! {3}, membership.learner_ids().collect::<BTreeSet<MemNodeId>>());
+    }
+
+    tracing::info!(log_index, "--- node-3 catches up and is promoted to a voter");
+    {
+        router.set_unreachable(3, false);
+        log_index += 2; // the joint and the uniform membership logs
+
+        for id in [0, 1, 2, 3] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "node-3 promoted").await?;
+        }
+
+        let m = router.get_raft_handle(&0)?.metrics().borrow_watched().clone();
+        let membership = m.membership_config.membership();
+
+        assert_eq!(1, membership.get_joint_config().len(), "uniform config");
+        assert_eq!(
+            btreeset! {0,1,2,3},
+            membership.voter_ids().collect::<BTreeSet<MemNodeId>>()
+        );
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(2000))
+}
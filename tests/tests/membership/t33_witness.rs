@@ -0,0 +1,197 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreemap;
+use maplit::btreeset;
+use openraft::ChangeMembers;
+use openraft::Config;
+use openraft::EntryPayload;
+use openraft::LogIdOptionExt;
+use openraft::NodeRole;
+use openraft::RPCTypes;
+use openraft::RaftLogReader;
+use openraft::ReadPolicy;
+use openraft::ServerState;
+use openraft::SnapshotPolicy;
+use openraft::async_runtime::WatchReceiver;
+use openraft::raft::ReadOptions;
+use openraft::type_config::TypeConfigExt;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// A witness votes and acknowledges logs, but only stores log metadata and never becomes leader.
+///
+/// - brings 2 nodes online and adds node-2 as a witness.
+/// - asserts node-2 stores the written logs as blank entries, without applying them.
+/// - asserts node-2 rejects local reads.
+/// - isolates node-1, asserts logs are committed by the leader and the witness.
+/// - asserts the witness does not start an election or accept leadership.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn witness() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- add node-2 as a witness");
+    {
+        router.new_raft_node(2).await;
+
+        let leader = router.get_raft_handle(&0)?;
+        leader.change_membership(ChangeMembers::AddWitnesses(btreemap! {2=>()}), false).await?;
+        log_index += 2; // the joint and the uniform membership logs
+
+        let m = leader.metrics().borrow_watched().clone();
+        assert_eq!(Some(NodeRole::Witness), m.membership_config.membership().node_role(&2));
+    }
+
+    let membership_index = log_index;
+
+    tracing::info!(log_index, "--- node-2 stores the logs without payload");
+    {
+        log_index += router.client_request_many(0, "foo", 5).await? as u64;
+
+        for id in [0, 1] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "write logs").await?;
+        }
+        let m2 = router.wait(&2, timeout()).committed_index(Some(log_index), "witness commits logs").await?;
+        assert!(
+            m2.last_applied.index() < Some(membership_index),
+            "the witness does not apply logs"
+        );
+
+        let (mut sto0, _sm0) = router.get_storage_handle(&0)?;
+        let (mut sto2, _sm2) = router.get_storage_handle(&2)?;
+
+        let logs0 = sto0.try_get_log_entries(membership_index + 1..).await?;
+        let logs2 = sto2.try_get_log_entries(membership_index + 1..).await?;
+
+        assert_eq!(5, logs0.len());
+        assert_eq!(
+            logs0.iter().map(|e| e.log_id).collect::<Vec<_>>(),
+            logs2.iter().map(|e| e.log_id).collect::<Vec<_>>()
+        );
+        assert!(logs0.iter().all(|e| matches!(e.payload, EntryPayload::Normal(_))));
+        assert!(logs2.iter().all(|e| matches!(e.payload, EntryPayload::Blank)));
+    }
+
+    tracing::info!(log_index, "--- the witness rejects local reads");
+    {
+        let n2 = router.get_raft_handle(&2)?;
+
+        let err = n2.local_read(ReadOptions::new()).await.unwrap_err();
+        assert!(err.api_error().unwrap().witness);
+
+        let err = n2.follower_read_index(ReadPolicy::ReadIndex).await.unwrap_err();
+        assert_eq!(Some(0), err.forward_to_leader().unwrap().leader_id);
+    }
+
+    tracing::info!(
+        log_index,
+        "--- isolate node-1, the leader and the witness form a quorum"
+    );
+    {
+        router.set_network_error(1, true);
+
+        log_index += router.client_request_many(0, "foo", 1).await? as u64;
+        router.wait(&0, timeout()).applied_index(Some(log_index), "committed without node-1").await?;
+    }
+
+    tracing::info!(log_index, "--- the witness never becomes leader");
+    {
+        let n2 = router.get_raft_handle(&2)?;
+        n2.trigger().elect(false).await?;
+        router.get_raft_handle(&0)?.trigger().transfer_leader(2).await?;
+
+        TypeConfig::sleep(Duration::from_millis(500)).await;
+
+        let m2 = n2.metrics().borrow_watched().clone();
+        assert_eq!(ServerState::Follower, m2.state);
+        assert_eq!(Some(0), m2.current_leader);
+    }
+
+    Ok(())
+}
+
+/// A witness that falls behind the purged logs of the leader catches up with only the snapshot
+/// metadata.
+///
+/// - builds a snapshot on the leader and purges logs.
+/// - adds node-2 as a witness.
+/// - asserts node-2 installs the snapshot meta, without the snapshot data, and follows the logs.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn witness_catch_up_with_snapshot_meta() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            snapshot_policy: SnapshotPolicy::Never,
+            max_in_snapshot_log_to_keep: 0,
+            purge_batch_size: 1,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- build a snapshot, purge logs");
+    {
+        log_index += router.client_request_many(0, "foo", 10).await? as u64;
+
+        n0.trigger().snapshot().await?;
+        router.wait(&0, timeout()).snapshot(log_id(1, 0, log_index), "node-0 snapshot").await?;
+
+        n0.trigger().purge_log(log_index).await?;
+        router.wait(&0, timeout()).purged(Some(log_id(1, 0, log_index)), "purge").await?;
+    }
+
+    let snapshot_index = log_index;
+
+    tracing::info!(log_index, "--- add node-2 as a witness");
+    {
+        router.new_raft_node(2).await;
+
+        n0.change_membership(ChangeMembers::AddWitnesses(btreemap! {2=>()}), false).await?;
+        log_index += 2;
+
+        log_index += router.client_request_many(0, "foo", 1).await? as u64;
+        router.wait(&2, timeout()).committed_index(Some(log_index), "witness follows logs").await?;
+    }
+
+    tracing::info!(log_index, "--- the witness installed only the snapshot meta");
+    {
+        let counts = router.get_rpc_count();
+        assert_eq!(Some(&1), counts.get(&RPCTypes::SnapshotMeta));
+        assert_eq!(None, counts.get(&RPCTypes::InstallSnapshot));
+
+        let m2 = router.get_raft_handle(&2)?.metrics().borrow_watched().clone();
+        assert_eq!(Some(snapshot_index), m2.last_applied.index());
+        assert_eq!(Some(snapshot_index), m2.snapshot.index());
+
+        let (_sto2, sm2) = router.get_storage_handle(&2)?;
+        assert!(sm2.get_state_machine().await.client_status.is_empty());
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1000))
+}
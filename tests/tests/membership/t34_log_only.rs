@@ -0,0 +1,181 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreemap;
+use maplit::btreeset;
+use openraft::ChangeMembers;
+use openraft::Config;
+use openraft::EntryPayload;
+use openraft::NodeRole;
+use openraft::RaftLogReader;
+use openraft::ServerState;
+use openraft::SnapshotPolicy;
+use openraft::async_runtime::WatchReceiver;
+use openraft::errors::ChangeMembershipError;
+use openraft::errors::ClientWriteError;
+use openraft::storage::RaftStateMachine;
+use openraft::type_config::TypeConfigExt;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// A log-only node stores the full log and counts in quorums, but never applies logs, builds a
+/// snapshot or becomes leader.
+///
+/// - brings 2 nodes online and adds node-2 as a log-only node.
+/// - asserts node-2 stores the written logs but its state machine applies none, and its applied log
+///   id stays `None`.
+/// - isolates node-1, asserts logs are committed by the leader and the log-only node.
+/// - asserts the log-only node does not build a snapshot, start an election or accept leadership.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn log_only() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- add node-2 as a log-only node");
+    {
+        router.new_raft_node(2).await;
+
+        let leader = router.get_raft_handle(&0)?;
+        leader.change_membership(ChangeMembers::AddLogOnly(btreemap! {2=>()}), false).await?;
+        log_index += 2; // the joint and the uniform membership logs
+
+        let m = leader.metrics().borrow_watched().clone();
+        assert_eq!(Some(NodeRole::LogOnly), m.membership_config.membership().node_role(&2));
+    }
+
+    let membership_index = log_index;
+
+    tracing::info!(log_index, "--- node-2 stores the logs but does not apply them");
+    {
+        log_index += router.client_request_many(0, "foo", 5).await? as u64;
+
+        for id in [0, 1] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "write logs").await?;
+        }
+        router.wait(&2, timeout()).committed_index(Some(log_index), "write logs").await?;
+
+        let m2 = router.get_raft_handle(&2)?.metrics().borrow_watched().clone();
+        assert_eq!(None, m2.last_applied, "node-2 applies nothing");
+
+        let (mut sto2, mut sm2) = router.get_storage_handle(&2)?;
+
+        let logs2 = sto2.try_get_log_entries(membership_index + 1..).await?;
+        assert_eq!(5, logs2.len());
+        assert!(logs2.iter().all(|e| matches!(e.payload, EntryPayload::Normal(_))));
+
+        let (last_applied, _) = sm2.applied_state().await?;
+        assert_eq!(None, last_applied);
+    }
+
+    tracing::info!(log_index, "--- node-2 does not build a snapshot");
+    {
+        let n2 = router.get_raft_handle(&2)?;
+        n2.trigger().snapshot().await?;
+
+        TypeConfig::sleep(Duration::from_millis(500)).await;
+
+        let m2 = n2.metrics().borrow_watched().clone();
+        assert_eq!(None, m2.snapshot);
+    }
+
+    tracing::info!(
+        log_index,
+        "--- isolate node-1, the leader and the log-only node form a quorum"
+    );
+    {
+        router.set_network_error(1, true);
+
+        log_index += router.client_request_many(0, "foo", 1).await? as u64;
+        router.wait(&0, timeout()).applied_index(Some(log_index), "committed without node-1").await?;
+    }
+
+    tracing::info!(log_index, "--- the log-only node never becomes leader");
+    {
+        let n2 = router.get_raft_handle(&2)?;
+        n2.trigger().elect(false).await?;
+        router.get_raft_handle(&0)?.trigger().transfer_leader(2).await?;
+
+        TypeConfig::sleep(Duration::from_millis(500)).await;
+
+        let m2 = n2.metrics().borrow_watched().clone();
+        assert_eq!(ServerState::Follower, m2.state);
+        assert_eq!(Some(0), m2.current_leader);
+    }
+
+    Ok(())
+}
+
+/// A log-only node does not install snapshot, thus it can not be added after the leader purged
+/// logs.
+///
+/// - brings 2 nodes online, builds a snapshot on the leader and purges logs.
+/// - asserts adding node-2 as a log-only node is rejected.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn log_only_after_purge() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            snapshot_policy: SnapshotPolicy::Never,
+            max_in_snapshot_log_to_keep: 0,
+            purge_batch_size: 1,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- build a snapshot, purge logs");
+    {
+        log_index += router.client_request_many(0, "foo", 10).await? as u64;
+
+        n0.trigger().snapshot().await?;
+        router.wait(&0, timeout()).snapshot(log_id(1, 0, log_index), "node-0 snapshot").await?;
+
+        n0.trigger().purge_log(log_index).await?;
+        router.wait(&0, timeout()).purged(Some(log_id(1, 0, log_index)), "purge").await?;
+    }
+
+    tracing::info!(log_index, "--- adding node-2 as a log-only node is rejected");
+    {
+        router.new_raft_node(2).await;
+
+        let res = n0.change_membership(ChangeMembers::AddLogOnly(btreemap! {2=>()}), false).await;
+        let raft_err = res.unwrap_err();
+
+        match raft_err.api_error().unwrap() {
+            ClientWriteError::ChangeMembershipError(ChangeMembershipError::LogOnlyLogsPurged(err)) => {
+                assert_eq!(2, err.node_id);
+                assert_eq!(log_index, err.last_purged_index);
+            }
+            _ => {
+                unreachable!("expect LogOnlyLogsPurged")
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1000))
+}
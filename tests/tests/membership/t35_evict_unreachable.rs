@@ -0,0 +1,80 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use futures::StreamExt;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::raft::RaftEvent;
+use openraft::type_config::TypeConfigExt;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// With `evict_unreachable_after` set, the leader removes a voter that has been unreachable for
+/// longer than that from the membership.
+///
+/// - brings 3 nodes online.
+/// - isolates node-2.
+/// - asserts the leader emits `NodeEvicted` for node-2 and commits a uniform config without it.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn evict_unreachable() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            heartbeat_interval: 50,
+            evict_unreachable_after: Some(1_000),
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let mut events = n0.subscribe_events();
+
+    tracing::info!(log_index, "--- isolate node-2");
+    {
+        router.set_network_error(2, true);
+    }
+
+    tracing::info!(log_index, "--- leader evicts node-2");
+    {
+        loop {
+            let ev = TypeConfig::timeout(Duration::from_millis(5_000), events.next()).await?;
+            let ev = ev.expect("event stream ends")?;
+            tracing::info!("received event: {}", ev);
+
+            if let RaftEvent::NodeEvicted {
+                node_id,
+                unreachable_for,
+                demoted,
+            } = ev
+            {
+                assert_eq!(2, node_id);
+                assert!(unreachable_for > Duration::from_millis(1_000));
+                assert!(!demoted);
+                break;
+            }
+        }
+
+        n0.wait(timeout())
+            .metrics(
+                |m| {
+                    let membership = m.membership_config.membership();
+                    membership.get_joint_config() == &vec![btreeset! {0,1}] && membership.get_node(&2).is_none()
+                },
+                "node-2 is removed",
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}
@@ -0,0 +1,149 @@
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::Membership;
+use openraft::ServerState;
+use openraft::errors::InitializeError;
+use openraft::errors::QuorumPolicyError;
+use openraft::membership::QuorumKind;
+use openraft::membership::QuorumPolicy;
+use openraft::type_config::TypeConfigExt;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::MemNodeId;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// Commit on node-0 alone, elect with every voter.
+#[derive(Debug)]
+struct Primary;
+
+impl QuorumPolicy<TypeConfig> for Primary {
+    fn is_quorum(
+        &self,
+        kind: QuorumKind,
+        _membership: &Membership<MemNodeId, ()>,
+        voters: &BTreeSet<MemNodeId>,
+        granted: &BTreeSet<MemNodeId>,
+    ) -> bool {
+        match kind {
+            QuorumKind::Commit => voters.contains(&0) && granted.contains(&0),
+            QuorumKind::Election => voters.is_subset(granted),
+        }
+    }
+}
+
+/// Commit on node-0, elect with any voter: two candidates may both be elected.
+#[derive(Debug)]
+struct Disjoint;
+
+impl QuorumPolicy<TypeConfig> for Disjoint {
+    fn is_quorum(
+        &self,
+        kind: QuorumKind,
+        _membership: &Membership<MemNodeId, ()>,
+        voters: &BTreeSet<MemNodeId>,
+        granted: &BTreeSet<MemNodeId>,
+    ) -> bool {
+        match kind {
+            QuorumKind::Commit => voters.contains(&0) && granted.contains(&0),
+            QuorumKind::Election => voters.intersection(granted).next().is_some(),
+        }
+    }
+}
+
+/// A leader commits with the quorums decided by the `QuorumPolicy` the nodes are created with.
+///
+/// - brings 3 nodes online with a policy that commits on node-0 alone.
+/// - isolates the followers, and asserts the leader still commits.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn quorum_policy() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- create every node with the policy");
+    for id in [0, 1, 2] {
+        router.new_raft_node_with_quorum_policy(id, Arc::new(Primary)).await;
+    }
+
+    let mut log_index = 0;
+
+    tracing::info!(log_index, "--- initialize, node-0 is elected by every voter");
+    {
+        router.initialize(0).await?;
+        log_index += 1;
+
+        let n0 = router.get_raft_handle(&0)?;
+        n0.wait(timeout()).state(ServerState::Leader, "node-0 is leader").await?;
+        n0.wait(timeout()).applied_index(Some(log_index), "blank log committed").await?;
+    }
+
+    tracing::info!(log_index, "--- isolate the followers");
+    {
+        router.set_network_error(1, true);
+        router.set_network_error(2, true);
+    }
+
+    tracing::info!(log_index, "--- the leader commits alone");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        let fu = n0.client_write(ClientRequest::make_request("foo", 1));
+        TypeConfig::timeout(Duration::from_millis(1_000), fu).await??;
+        log_index += 1;
+
+        n0.wait(timeout()).applied_index(Some(log_index), "committed by node-0 alone").await?;
+    }
+
+    Ok(())
+}
+
+/// A membership whose election and commit quorums do not intersect is rejected.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn quorum_policy_reject_disjoint_quorums() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    router.new_raft_node_with_quorum_policy(0, Arc::new(Disjoint)).await;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let res = n0.initialize(btreeset! {0,1,2}).await;
+
+    let err = res.unwrap_err();
+    assert!(
+        matches!(
+            err.api_error(),
+            Some(InitializeError::QuorumPolicy(
+                QuorumPolicyError::ElectionCommitDisjoint { .. }
+            ))
+        ),
+        "unexpected error: {}",
+        err
+    );
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
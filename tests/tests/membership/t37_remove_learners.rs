@@ -0,0 +1,115 @@
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::ChangeMembers;
+use openraft::Config;
+use openraft::type_config::TypeConfigExt;
+use openraft_memstore::TypeConfig;
+use tracing::Instrument;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// `RemoveLearners` is accepted while a membership change is in progress, and keeps the joint
+/// config of the pending change.
+///
+/// - brings a cluster of voters 0,1,2 and learners 3,4 online.
+/// - isolates 1,2 so that promoting 3 stays in the joint config.
+/// - removes learner 4 while the joint config is not committed.
+/// - heals the network and asserts both changes complete.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn remove_learners_during_membership_change() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {3,4}).await?;
+
+    tracing::info!(log_index, "--- isolate 1,2; promoting 3 can not be committed");
+    {
+        router.set_network_error(1, true);
+        router.set_network_error(2, true);
+
+        TypeConfig::spawn({
+            let router = router.clone();
+            async move {
+                let node = router.get_raft_handle(&0).unwrap();
+                let _x = node.change_membership(ChangeMembers::AddVoterIds(btreeset! {3}), false).await;
+            }
+            .instrument(tracing::debug_span!("spawn-promote-3"))
+        });
+
+        router
+            .wait(&0, timeout())
+            .metrics(
+                |m| m.membership_config.membership().get_joint_config().len() == 2,
+                "joint config is proposed",
+            )
+            .await?;
+    }
+
+    tracing::info!(
+        log_index,
+        "--- remove learner 4 while the joint config is not committed"
+    );
+    {
+        TypeConfig::spawn({
+            let router = router.clone();
+            async move {
+                let node = router.get_raft_handle(&0).unwrap();
+                let _x = node.change_membership(ChangeMembers::RemoveLearners(btreeset! {4}), false).await;
+            }
+            .instrument(tracing::debug_span!("spawn-remove-learner-4"))
+        });
+
+        router
+            .wait(&0, timeout())
+            .metrics(
+                |m| {
+                    let mem = m.membership_config.membership();
+                    mem.get_node(&4).is_none() && mem.get_joint_config().len() == 2
+                },
+                "learner 4 is removed, the joint config is kept",
+            )
+            .await?;
+    }
+
+    tracing::info!(log_index, "--- heal the network; both changes complete");
+    {
+        router.set_network_error(1, false);
+        router.set_network_error(2, false);
+
+        for id in [0, 1, 2, 3] {
+            router
+                .wait(&id, timeout())
+                .metrics(
+                    |m| {
+                        let mem = m.membership_config.membership();
+                        let voters = mem.voter_ids().collect::<BTreeSet<_>>();
+                        m.committed_membership_config.log_id() == m.membership_config.log_id()
+                            && mem.get_joint_config().len() == 1
+                            && voters == btreeset! {0,1,2,3}
+                            && mem.get_node(&4).is_none()
+                    },
+                    "3 is a voter and 4 is removed",
+                )
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
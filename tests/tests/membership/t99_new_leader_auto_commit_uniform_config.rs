@@ -46,6 +46,11 @@ async fn new_leader_auto_commit_uniform_config() -> Result<()> {
                 vec![btreeset! {0}, btreeset! {0,1,2}],
                 btreeset! {},
             )),
+            apply_scope: None,
+            timestamp: None,
+            deadline: None,
+            expire_from: None,
+            chain_hash: None,
         }])
         .await?;
     }
@@ -7,10 +7,16 @@ mod fixtures;
 // The number indicate the preferred running order for these case.
 // The later tests may depend on the earlier ones.
 
+mod t10_config_mismatch;
 mod t10_current_leader;
+mod t10_estimate_catchup;
 mod t10_leader_last_ack;
+mod t10_leader_since;
+mod t10_metrics_flush_interval;
 mod t10_metrics_recorder;
 mod t10_purged;
+mod t10_quorum_ack_latency;
+mod t10_replication_targets;
 mod t10_server_metrics_and_data_metrics;
 mod t20_metrics_state_machine_consistency;
 mod t30_leader_metrics;
@@ -19,6 +25,7 @@ mod t50_apply_progress_api;
 mod t50_commit_progress_api;
 mod t50_log_progress_api;
 mod t50_snapshot_progress_api;
+mod t50_subscribe_events;
 mod t50_watch_leader_api;
 #[cfg(feature = "runtime-stats")]
 mod t60_runtime_stats;
@@ -0,0 +1,69 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ConfigDigest;
+use openraft::async_runtime::watch::WatchReceiver;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// A node reports a peer whose safety-relevant config differs from its own in metrics.
+///
+/// - brings a cluster of node-0 online.
+/// - adds node-1 as a learner with a different election timeout.
+/// - asserts node-1 reports node-0's config digest as a mismatch, and node-0 reports none.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn config_mismatch() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- add node-1 with a different election timeout");
+    {
+        let config1 = Arc::new(
+            Config {
+                election_timeout_min: config.election_timeout_min * 2,
+                election_timeout_max: config.election_timeout_max * 2,
+                ..config.as_ref().clone()
+            }
+            .validate()?,
+        );
+        router.new_raft_node_with_config(1, config1.clone()).await;
+
+        router.get_raft_handle(&0)?.add_learner(1, (), true).await?;
+
+        let digest0 = ConfigDigest::new::<TypeConfig>(&config);
+        let digest1 = ConfigDigest::new::<TypeConfig>(&config1);
+        assert_eq!(vec!["election_timeout"], digest1.diff(&digest0));
+
+        router
+            .wait(&1, timeout())
+            .metrics(
+                |m| m.config_mismatches.get(&0) == Some(&digest0),
+                "node-1 reports node-0 config",
+            )
+            .await?;
+
+        let m0 = router.get_raft_handle(&0)?.metrics().borrow_watched().clone();
+        assert!(m0.config_mismatches.is_empty());
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1000))
+}
@@ -0,0 +1,93 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::LogIdOptionExt;
+use openraft::RPCTypes;
+use openraft::type_config::TypeConfigExt;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// The leader estimates how long a node takes to catch up from the replication throughput it
+/// measured.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn estimate_catchup() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- replicate logs slowly enough to measure the throughput");
+    {
+        router
+            .set_rpc_pre_hook(RPCTypes::AppendEntries, move |_router, _req, _from, _to| {
+                Box::pin(async move {
+                    TypeConfig::sleep(Duration::from_millis(5)).await;
+                    Ok(())
+                })
+            })
+            .await;
+
+        log_index += router.client_request_many(0, "foo", 20).await?;
+
+        for id in [1, 2] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "logs replicated").await?;
+        }
+        router
+            .wait(&0, timeout())
+            .metrics(
+                |m| m.replication().is_some_and(|r| r.values().all(|x| x.index() == Some(log_index))),
+                "leader sees all logs replicated",
+            )
+            .await?;
+    }
+
+    tracing::info!(log_index, "--- a caught up follower has nothing to catch up");
+    {
+        let est = n0.estimate_catchup(&1).unwrap();
+
+        assert_eq!(0, est.lag_entries);
+        assert!(!est.snapshot_required);
+        assert!(est.entries_per_sec.is_some());
+        assert!(est.bytes_per_entry.is_some());
+        assert_eq!(Some(Duration::ZERO), est.by_logs);
+        assert_eq!(None, est.by_snapshot, "no snapshot is sent");
+        assert_eq!(Some(Duration::ZERO), est.duration());
+    }
+
+    tracing::info!(log_index, "--- a new node lacks all of the logs");
+    {
+        let est = n0.estimate_catchup(&3).unwrap();
+
+        assert_eq!(log_index + 1, est.lag_entries);
+        assert!(!est.snapshot_required);
+        assert!(est.by_logs.is_some(), "use the throughput to the other nodes");
+        assert!(!est.prefer_snapshot());
+    }
+
+    tracing::info!(log_index, "--- a follower does not estimate");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        assert_eq!(None, n1.estimate_catchup(&2));
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,66 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+use openraft::type_config::TypeConfigExt;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// `Raft::leader_since()` returns when the leader established its leadership, and `None` on
+/// other nodes. It is reset when another node becomes leader.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn leader_since() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            election_timeout_min: 150,
+            election_timeout_max: 300,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let n1 = router.get_raft_handle(&1)?;
+
+    let since = n0.leader_since().expect("node-0 is leader");
+    assert_eq!(None, n1.leader_since());
+
+    tracing::info!(log_index, "--- it does not change while node-0 is leader");
+    {
+        TypeConfig::sleep(Duration::from_millis(300)).await;
+        router.client_request_many(0, "foo", 1).await?;
+
+        assert_eq!(Some(since), n0.leader_since());
+        assert!(since.elapsed() >= Duration::from_millis(300));
+    }
+
+    tracing::info!(log_index, "--- transfer leadership to node-1");
+    {
+        n0.trigger().transfer_leader(1).await?;
+
+        n1.wait(timeout()).state(ServerState::Leader, "node-1 become leader").await?;
+        n0.wait(timeout()).state(ServerState::Follower, "node-0 become follower").await?;
+
+        let n1_since = n1.leader_since().expect("node-1 is leader");
+        assert!(n1_since.instant > since.instant);
+        assert!(n1_since.system_time >= since.system_time);
+        assert_eq!(None, n0.leader_since());
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
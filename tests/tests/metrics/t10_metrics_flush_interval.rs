@@ -0,0 +1,53 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// With `metrics_flush_interval` set, metrics are published less often than state changes, but the
+/// last change is still published once the interval expires.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn metrics_flush_interval() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            metrics_flush_interval: Some(200),
+            metrics_history_size: Some(1000),
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initialize cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let before = n0.metrics_history().len();
+
+    let n = 100;
+    tracing::info!(log_index, "--- write {} logs", n);
+    log_index += router.client_request_many(0, "foo", n).await?;
+
+    tracing::info!(log_index, "--- the last state is published after the interval");
+    n0.wait(timeout()).applied_index(Some(log_index), "all logs applied").await?;
+
+    let published = n0.metrics_history().len() - before;
+    assert!(
+        published < n,
+        "metrics are published less often than every write: {} publications",
+        published
+    );
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1000))
+}
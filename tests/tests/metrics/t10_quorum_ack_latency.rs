@@ -0,0 +1,81 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::RPCTypes;
+use openraft::async_runtime::WatchReceiver;
+use openraft::type_config::TypeConfigExt;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// The leader reports the time entries take from being appended to being acknowledged by a
+/// quorum, and counts the entries exceeding `quorum_ack_slo`.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn quorum_ack_latency() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            quorum_ack_slo: Some(100),
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(
+        log_index,
+        "--- latency is reported for the entries written by the leader"
+    );
+    {
+        log_index += router.client_request_many(0, "foo", 10).await?;
+
+        let m = n0.wait(timeout()).metrics(|m| m.quorum_ack_latency.samples >= 10, "latency of 10 entries").await?;
+        let latency = m.quorum_ack_latency;
+        assert!(latency.p50 <= latency.p99 && latency.p99 <= latency.max, "{}", latency);
+
+        let m1 = router.get_raft_handle(&1)?.metrics().borrow_watched().clone();
+        assert_eq!(0, m1.quorum_ack_latency.samples, "a follower does not report latency");
+    }
+
+    let breaches_before = n0.metrics().borrow_watched().quorum_ack_latency.slo_breaches;
+
+    tracing::info!(log_index, "--- slow replication breaches the SLO");
+    {
+        router
+            .set_rpc_pre_hook(RPCTypes::AppendEntries, move |_router, _req, _from, _to| {
+                Box::pin(async move {
+                    TypeConfig::sleep(Duration::from_millis(200)).await;
+                    Ok(())
+                })
+            })
+            .await;
+
+        router.client_request_many(0, "foo", 5).await?;
+
+        let m = n0
+            .wait(timeout())
+            .metrics(
+                |m| m.quorum_ack_latency.slo_breaches >= breaches_before + 5,
+                "5 more entries breach the SLO",
+            )
+            .await?;
+        let latency = m.quorum_ack_latency;
+        assert!(latency.max >= Duration::from_millis(200), "{}", latency);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(5_000))
+}
@@ -0,0 +1,95 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::LogIdOptionExt;
+use openraft::async_runtime::WatchReceiver;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// The leader reports the replication state of every target in `RaftMetrics::replication_targets`.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn replication_targets() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {3}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- every target catches up");
+    {
+        log_index += router.client_request_many(0, "foo", 10).await?;
+
+        n0.wait(timeout())
+            .metrics(
+                |m| {
+                    m.replication_targets.as_ref().is_some_and(|targets| {
+                        targets.len() == 3
+                            && targets.values().all(|t| t.matched.index() == Some(log_index) && t.lag_entries == 0)
+                    })
+                },
+                "all targets caught up",
+            )
+            .await?;
+
+        let metrics = n0.metrics().borrow_watched().clone();
+        let targets = metrics.replication_targets.unwrap();
+        assert_eq!(btreeset! {1,2,3}, targets.keys().copied().collect());
+
+        for (id, t) in targets.iter() {
+            assert_eq!(log_index + 1, t.next_index, "target {}", id);
+            assert_eq!(0, t.inflight_entries, "target {}", id);
+            assert!(!t.sending_snapshot, "target {}", id);
+            assert!(t.last_ack.is_some(), "target {}", id);
+            assert!(t.rtt.is_some(), "target {}", id);
+        }
+    }
+
+    tracing::info!(log_index, "--- a target that is unreachable lags behind");
+    {
+        router.remove_node(3);
+
+        let caught_up = log_index;
+        log_index += router.client_request_many(0, "foo", 10).await?;
+
+        n0.wait(timeout())
+            .metrics(
+                |m| {
+                    m.replication_targets.as_ref().is_some_and(|targets| {
+                        targets[&1].lag_entries == 0 && targets[&2].lag_entries == 0 && targets[&3].lag_entries == 10
+                    })
+                },
+                "target 3 lags 10 entries",
+            )
+            .await?;
+
+        let metrics = n0.metrics().borrow_watched().clone();
+        let t3 = &metrics.replication_targets.unwrap()[&3];
+        assert_eq!(Some(caught_up), t3.matched.index());
+        assert_eq!(log_index - caught_up, t3.lag_entries);
+    }
+
+    tracing::info!(log_index, "--- a follower does not report replication targets");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        assert_eq!(None, n1.metrics().borrow_watched().replication_targets);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -49,7 +49,7 @@ async fn leader_metrics() -> Result<()> {
         .wait(&0, timeout())
         .metrics(
             |x| {
-                if let Some(ref q) = x.replication {
+                if let Some(q) = x.replication() {
                     q == &btreemap! {
                         0u64 => Some(log_id(1,0,1)),
                     }
@@ -95,7 +95,7 @@ async fn leader_metrics() -> Result<()> {
         .wait(&0, timeout())
         .metrics(
             |x| {
-                if let Some(ref q) = x.replication {
+                if let Some(q) = x.replication() {
                     q == &want_repl
                 } else {
                     false
@@ -134,7 +134,7 @@ async fn leader_metrics() -> Result<()> {
             .wait(&0, timeout())
             .metrics(
                 |x| {
-                    if let Some(ref q) = x.replication {
+                    if let Some(q) = x.replication() {
                         q == &want_repl
                     } else {
                         false
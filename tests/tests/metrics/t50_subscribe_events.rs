@@ -0,0 +1,130 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use futures::Stream;
+use futures::StreamExt;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+use openraft::errors::EventsLagged;
+use openraft::raft::RaftEvent;
+use openraft::type_config::TypeConfigExt;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// `Raft::subscribe_events()` delivers the leader, membership, snapshot and purge events of a node.
+///
+/// - subscribes on node-0 before initializing it, and asserts `LeaderElected` and the initial
+///   `MembershipCommitted`.
+/// - adds node-1 as a voter, and asserts `MembershipCommitted`.
+/// - builds a snapshot, and asserts `SnapshotBuilt` and `LogPurged`.
+/// - transfers leadership to node-1, and asserts `SteppedDown`.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn subscribe_events() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            max_in_snapshot_log_to_keep: 0,
+            purge_batch_size: 1,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    router.new_raft_node(0).await;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let mut events = n0.subscribe_events();
+
+    tracing::info!("--- node-0 becomes leader");
+    {
+        router.initialize(0).await?;
+
+        let ev = next_event(&mut events, |ev| matches!(ev, RaftEvent::LeaderElected { .. })).await?;
+        let RaftEvent::LeaderElected { vote } = ev else {
+            unreachable!()
+        };
+        assert_eq!(0, vote.leader_id().node_id);
+
+        next_event(&mut events, |ev| match ev {
+            RaftEvent::MembershipCommitted { membership } => membership.log_id() == &Some(log_id(0, 0, 0)),
+            _ => false,
+        })
+        .await?;
+    }
+
+    let mut log_index = 1;
+    router.wait(&0, timeout()).applied_index(Some(log_index), "leader log applied").await?;
+
+    tracing::info!(log_index, "--- add node-1 as a voter");
+    {
+        router.new_raft_node(1).await;
+        n0.add_learner(1, (), true).await?;
+        n0.change_membership(btreeset! {0,1}, false).await?;
+        log_index += 3; // add learner, joint and uniform membership logs
+
+        next_event(&mut events, |ev| match ev {
+            RaftEvent::MembershipCommitted { membership } => {
+                membership.log_id() == &Some(log_id(1, 0, log_index))
+                    && membership.membership().voter_ids().collect::<Vec<_>>() == vec![0, 1]
+            }
+            _ => false,
+        })
+        .await?;
+    }
+
+    tracing::info!(log_index, "--- build a snapshot and purge logs");
+    {
+        n0.trigger().snapshot().await?;
+
+        next_event(&mut events, |ev| match ev {
+            RaftEvent::SnapshotBuilt { meta } => meta.last_log_id == Some(log_id(1, 0, log_index)),
+            _ => false,
+        })
+        .await?;
+
+        next_event(&mut events, |ev| match ev {
+            RaftEvent::LogPurged { upto } => upto == &log_id(1, 0, log_index),
+            _ => false,
+        })
+        .await?;
+    }
+
+    tracing::info!(log_index, "--- transfer leadership to node-1");
+    {
+        n0.trigger().transfer_leader(1).await?;
+        router.wait(&1, timeout()).state(ServerState::Leader, "node-1 becomes leader").await?;
+
+        next_event(&mut events, |ev| matches!(ev, RaftEvent::SteppedDown { .. })).await?;
+    }
+
+    Ok(())
+}
+
+/// Read events until one satisfies `f`.
+async fn next_event<S, F>(events: &mut S, f: F) -> Result<RaftEvent<TypeConfig>>
+where
+    S: Stream<Item = Result<RaftEvent<TypeConfig>, EventsLagged>> + Unpin + Send,
+    F: Fn(&RaftEvent<TypeConfig>) -> bool,
+{
+    loop {
+        let ev = TypeConfig::timeout(Duration::from_millis(2_000), events.next()).await?;
+        let ev = ev.expect("event stream ends")?;
+        tracing::info!("received event: {}", ev);
+
+        if f(&ev) {
+            return Ok(ev);
+        }
+    }
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
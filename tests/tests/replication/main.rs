@@ -6,6 +6,12 @@ mod fixtures;
 
 mod t10_append_entries_partial_success;
 mod t20_empty_log_entries;
+mod t21_append_receive_window;
+mod t21_max_inflight_append_entries;
+mod t22_commit_notify_interval;
+mod t23_adaptive_payload_entries;
+mod t24_max_payload_bytes;
+mod t25_max_replication_rate;
 mod t50_append_entries_backoff;
 mod t50_append_entries_backoff_rejoin;
 mod t51_backoff_cleared_after_success;
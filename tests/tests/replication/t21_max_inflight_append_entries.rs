@@ -0,0 +1,44 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// With `max_inflight_append_entries` set, a leader keeps at most that many `AppendEntries`
+/// requests in flight to a slow follower, and the follower still catches up with all the logs.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn max_inflight_append_entries() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            max_payload_entries: 2,
+            max_inflight_append_entries: Some(3),
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+    router.network_send_delay(10);
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0, 1, 2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- write more logs than the in-flight requests carry");
+    log_index += router.client_request_many(0, "foo", 40).await?;
+
+    for id in [0, 1, 2] {
+        router.wait(&id, timeout()).applied_index(Some(log_index), "all logs replicated").await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(5_000))
+}
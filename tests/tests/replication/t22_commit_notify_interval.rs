@@ -0,0 +1,61 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::async_runtime::WatchReceiver;
+use openraft::type_config::TypeConfigExt;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// With `commit_notify_interval` set, a commit advance is sent to an up-to-date follower at once
+/// if no commit-only request was sent within the interval, otherwise at the end of the interval.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn commit_notify_interval() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            commit_notify_interval: Some(1_000),
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- commit is notified at once after an idle interval");
+    {
+        TypeConfig::sleep(Duration::from_millis(1_100)).await;
+
+        log_index += router.client_request_many(0, "foo", 1).await?;
+        router
+            .wait(&1, Some(Duration::from_millis(500)))
+            .applied_index(Some(log_index), "node-1 applies at once")
+            .await?;
+    }
+
+    tracing::info!(log_index, "--- the next commit is notified at the end of the interval");
+    {
+        let prev = log_index;
+        log_index += router.client_request_many(0, "foo", 1).await?;
+
+        TypeConfig::sleep(Duration::from_millis(200)).await;
+        let applied = router.get_raft_handle(&1)?.metrics().borrow_watched().last_applied;
+        assert_eq!(Some(prev), applied.map(|x| x.index), "node-1 has not yet been notified");
+
+        router
+            .wait(&1, Some(Duration::from_millis(2_000)))
+            .applied_index(Some(log_index), "node-1 applies after the interval")
+            .await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::RPCTypes;
+use openraft::base::BoxFuture;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::rpc_request::RpcRequest;
+use crate::fixtures::ut_harness;
+
+/// With `min_payload_entries` set, a leader starts replicating to a lagging follower with small
+/// payloads, and grows them up to `max_payload_entries` while the follower acknowledges them fast.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn adaptive_payload_entries() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            max_payload_entries: 64,
+            min_payload_entries: Some(4),
+            payload_target_rtt: Some(1_000),
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing single node cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- write logs for a learner to catch up with");
+    log_index += router.client_request_many(0, "foo", 600).await?;
+
+    let sizes = Arc::new(Mutex::new(Vec::new()));
+    {
+        let sizes = sizes.clone();
+        router
+            .set_rpc_pre_hook(RPCTypes::AppendEntries, move |_router, req, _from, target| {
+                if let RpcRequest::AppendEntries(a) = req
+                    && target == 1
+                    && !a.entries.is_empty()
+                {
+                    sizes.lock().unwrap().push(a.entries.len() as u64);
+                }
+                let fu: BoxFuture<_> = Box::pin(futures::future::ready(Ok(())));
+                fu
+            })
+            .await;
+    }
+
+    tracing::info!(log_index, "--- add learner 1");
+    router.new_raft_node(1).await;
+    router.add_learner(0, 1).await?;
+    log_index += 1;
+
+    router.wait(&1, timeout()).applied_index(Some(log_index), "learner caught up").await?;
+
+    let sizes = sizes.lock().unwrap().clone();
+    tracing::info!("payload sizes: {:?}", sizes);
+
+    // Acknowledged requests without entries, e.g., the probe, already grow the size a bit.
+    assert!(sizes[0] < 64, "starts small: {:?}", sizes);
+    assert_eq!(
+        Some(&64),
+        sizes.iter().max(),
+        "grows up to max_payload_entries: {:?}",
+        sizes
+    );
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(5_000))
+}
@@ -0,0 +1,82 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::RPCTypes;
+use openraft::base::BoxFuture;
+use openraft::entry::RaftEntry;
+use openraft::testing::blank_ent;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::rpc_request::RpcRequest;
+use crate::fixtures::ut_harness;
+
+/// With `max_payload_bytes` set, an AppendEntries request carries no more entries than fit in it,
+/// even though `max_payload_entries` allows more.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn max_payload_bytes() -> Result<()> {
+    let entry_size = blank_ent::<TypeConfig>(1, 0, 1).approx_size();
+
+    let config = Arc::new(
+        Config {
+            max_payload_entries: 64,
+            max_payload_bytes: Some(entry_size * 2),
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing single node cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- write logs for a learner to catch up with");
+    log_index += router.client_request_many(0, "foo", 20).await?;
+
+    let sizes = Arc::new(Mutex::new(Vec::new()));
+    {
+        let sizes = sizes.clone();
+        router
+            .set_rpc_pre_hook(RPCTypes::AppendEntries, move |_router, req, _from, target| {
+                if let RpcRequest::AppendEntries(a) = req
+                    && target == 1
+                    && !a.entries.is_empty()
+                {
+                    sizes.lock().unwrap().push(a.entries.len());
+                }
+                let fu: BoxFuture<_> = Box::pin(futures::future::ready(Ok(())));
+                fu
+            })
+            .await;
+    }
+
+    tracing::info!(log_index, "--- add learner 1");
+    router.new_raft_node(1).await;
+    router.add_learner(0, 1).await?;
+    log_index += 1;
+
+    router.wait(&1, timeout()).applied_index(Some(log_index), "learner caught up").await?;
+
+    let sizes = sizes.lock().unwrap().clone();
+    tracing::info!("payload sizes: {:?}", sizes);
+
+    assert!(
+        sizes.iter().all(|n| *n <= 2),
+        "limited by max_payload_bytes: {:?}",
+        sizes
+    );
+    assert!(sizes.contains(&2), "fills the byte budget: {:?}", sizes);
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(5_000))
+}
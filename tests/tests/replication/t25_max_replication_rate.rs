@@ -0,0 +1,64 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::Instant;
+use openraft::entry::RaftEntry;
+use openraft::testing::blank_ent;
+use openraft::type_config::TypeConfigExt;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// With `max_replication_rate` set, a lagging learner is caught up no faster than the rate allows.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn max_replication_rate() -> Result<()> {
+    let entry_size = blank_ent::<TypeConfig>(1, 0, 1).approx_size();
+
+    let config = Arc::new(
+        Config {
+            max_payload_entries: 10,
+            // 100 entries per second
+            max_replication_rate: Some(entry_size * 100),
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing single node cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- write logs for a learner to catch up with");
+    log_index += router.client_request_many(0, "foo", 60).await?;
+
+    tracing::info!(log_index, "--- add learner 1");
+    let start = TypeConfig::now();
+    router.new_raft_node(1).await;
+    router.add_learner(0, 1).await?;
+    log_index += 1;
+
+    router.wait(&1, timeout()).applied_index(Some(log_index), "learner caught up").await?;
+
+    let elapsed = start.elapsed();
+    tracing::info!("learner caught up in {:?}", elapsed);
+
+    // The first request is sent at once; the other 50 entries take at least 500ms at the rate.
+    assert!(
+        elapsed >= Duration::from_millis(450),
+        "paced by max_replication_rate: {:?}",
+        elapsed
+    );
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(5_000))
+}
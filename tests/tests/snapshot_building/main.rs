@@ -6,6 +6,12 @@ mod fixtures;
 
 mod t10_build_snapshot;
 mod t11_snapshot_builder_control;
+mod t12_current_snapshot_meta;
 mod t35_building_snapshot_does_not_block_append;
 mod t35_building_snapshot_does_not_block_apply;
 mod t60_snapshot_policy_never;
+mod t61_snapshot_deferred_under_load;
+mod t62_backup;
+mod t63_snapshot_deferred_by_compaction;
+mod t64_snapshot_trigger;
+mod t65_snapshot_keep_count;
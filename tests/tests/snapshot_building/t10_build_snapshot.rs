@@ -77,6 +77,11 @@ async fn build_snapshot() -> Result<()> {
     sto1.blocking_append([blank_ent::<openraft_memstore::TypeConfig>(0, 0, 0), Entry {
         log_id: log_id(1, 0, 1),
         payload: EntryPayload::Membership(Membership::new_with_defaults(vec![btreeset! {0}], [])),
+        apply_scope: None,
+        timestamp: None,
+        deadline: None,
+        expire_from: None,
+        chain_hash: None,
     }])
     .await?;
 
@@ -131,6 +136,9 @@ async fn build_snapshot() -> Result<()> {
                 prev_log_id: Some(log_id(1, 0, 2)),
                 entries: vec![],
                 leader_commit: Some(log_id(0, 0, 0)),
+                backup_barrier: None,
+                after_snapshot: None,
+                config_digest: None,
             })
             .await?;
 
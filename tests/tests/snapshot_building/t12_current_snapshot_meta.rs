@@ -0,0 +1,68 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// `Raft::current_snapshot_meta()` returns the meta of the snapshot built by this node, or
+/// installed from the leader.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn current_snapshot_meta() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            max_in_snapshot_log_to_keep: 0,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- bring up cluster of 1 node");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    assert_eq!(None, n0.current_snapshot_meta(), "no snapshot yet");
+
+    tracing::info!(log_index, "--- build a snapshot");
+    {
+        log_index += router.client_request_many(0, "foo", 5).await?;
+        n0.trigger().snapshot().await?;
+        n0.wait(timeout()).snapshot(log_id(1, 0, log_index), "build snapshot").await?;
+
+        let meta = n0.current_snapshot_meta().unwrap();
+        assert_eq!(Some(log_id(1, 0, log_index)), meta.last_log_id);
+        assert_eq!(
+            btreeset! {0},
+            meta.last_membership.membership().voter_ids().collect()
+        );
+    }
+
+    tracing::info!(log_index, "--- a learner installs the snapshot from the leader");
+    {
+        n0.wait(timeout()).purged(Some(log_id(1, 0, log_index)), "purge logs").await?;
+
+        router.new_raft_node(1).await;
+        router.add_learner(0, 1).await?;
+        log_index += 1;
+
+        let n1 = router.get_raft_handle(&1)?;
+        n1.wait(timeout()).applied_index(Some(log_index), "learner catches up").await?;
+
+        let meta = n1.current_snapshot_meta().unwrap();
+        assert_eq!(Some(log_id(1, 0, log_index - 1)), meta.last_log_id);
+        assert_eq!(n0.current_snapshot_meta().unwrap().snapshot_id, meta.snapshot_id);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(2_000))
+}
@@ -65,6 +65,9 @@ async fn building_snapshot_does_not_block_append() -> Result<()> {
             prev_log_id: Some(log_id(1, 0, log_index)),
             entries: vec![blank_ent::<openraft_memstore::TypeConfig>(1, 0, 15)],
             leader_commit: None,
+            backup_barrier: None,
+            after_snapshot: None,
+            config_digest: None,
         };
 
         let node = router.get_raft_handle(&1)?;
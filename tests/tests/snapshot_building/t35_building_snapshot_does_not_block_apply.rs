@@ -70,6 +70,9 @@ async fn building_snapshot_does_not_block_apply() -> Result<()> {
             entries: vec![blank_ent::<openraft_memstore::TypeConfig>(1, 0, next)],
             // Append and commit this entry
             leader_commit: Some(log_id(1, 0, next)),
+            backup_barrier: None,
+            after_snapshot: None,
+            config_digest: None,
         };
 
         let node = router.get_raft_handle(&1)?;
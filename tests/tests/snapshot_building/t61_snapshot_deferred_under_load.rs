@@ -0,0 +1,68 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::SnapshotPolicy;
+use openraft::type_config::TypeConfigExt;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// A snapshot build triggered by the policy is deferred while the write rate is above
+/// `snapshot_defer_write_rate`, and is built once the writes stop.
+///
+/// - build a single node cluster and let it idle, so that the measured write rate is 0.
+/// - keep writing, beyond the policy threshold: the snapshot build is deferred.
+/// - stop writing: the snapshot is built.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn snapshot_deferred_under_load() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            snapshot_policy: SnapshotPolicy::LogsSinceLast(300),
+            snapshot_defer_write_rate: Some(0),
+            snapshot_max_defer: Some(60_000),
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    TypeConfig::sleep(Duration::from_millis(1_500)).await;
+
+    tracing::info!("--- keep writing in another task");
+    let writer = {
+        let r = router.clone();
+        TypeConfig::spawn(async move {
+            for i in 0..600 {
+                r.client_request(0, "cli", i).await.unwrap();
+                TypeConfig::sleep(Duration::from_millis(5)).await;
+            }
+        })
+    };
+
+    router
+        .wait(&0, Some(Duration::from_secs(10)))
+        .metrics(
+            |m| m.snapshot_deferred_since.is_some() && m.snapshot.is_none(),
+            "snapshot build is deferred while writing",
+        )
+        .await?;
+
+    writer.await?;
+
+    tracing::info!("--- writes stopped, the snapshot is built");
+    let m = router
+        .wait(&0, Some(Duration::from_secs(5)))
+        .metrics(|m| m.snapshot.is_some(), "snapshot built after writes stop")
+        .await?;
+    assert_eq!(None, m.snapshot_deferred_since);
+
+    Ok(())
+}
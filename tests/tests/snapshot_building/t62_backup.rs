@@ -0,0 +1,78 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use futures::StreamExt;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::SnapshotPolicy;
+use openraft::storage::RaftStateMachine;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// Backup builds a snapshot at the same log id on every node.
+///
+/// - Bring up a 3-node cluster with automatic snapshots disabled.
+/// - Call `backup()` while clients are writing.
+/// - Every node builds a snapshot at exactly the returned barrier, while writes after it are
+///   applied as usual.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn backup() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            snapshot_policy: SnapshotPolicy::Never,
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- backup while writing");
+    let barrier = {
+        let mut clients = futures::stream::FuturesUnordered::new();
+        for i in 0..5 {
+            let r = router.clone();
+            clients.push(async move { r.client_request_many(0, &format!("{}", i), 20).await });
+        }
+        log_index += 100;
+
+        let n0 = router.get_raft_handle(&0)?;
+        let barrier = n0.backup().await?;
+        log_index += 1;
+
+        while let Some(res) = clients.next().await {
+            res?;
+        }
+        barrier
+    };
+
+    tracing::info!(log_index, "--- backup barrier: {}", barrier);
+
+    for id in [0, 1, 2] {
+        router
+            .wait(&id, timeout())
+            .metrics(
+                |m| m.last_backup.as_ref() == Some(&barrier),
+                format!("n{} backup at {}", id, barrier),
+            )
+            .await?;
+
+        router.wait(&id, timeout()).applied_index(Some(log_index), format!("n{} applied all", id)).await?;
+
+        let (_sto, mut sm) = router.get_storage_handle(&id)?;
+        let snap = sm.get_current_snapshot().await?.unwrap();
+        assert_eq!(Some(barrier), snap.meta.last_log_id, "n{} snapshot at barrier", id);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(5_000))
+}
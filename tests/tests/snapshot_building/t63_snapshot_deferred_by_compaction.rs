@@ -0,0 +1,69 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::SnapshotPolicy;
+use openraft::async_runtime::WatchReceiver;
+use openraft::type_config::TypeConfigExt;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// A snapshot build triggered by the policy is deferred while the state machine reports it is
+/// compacting, and is built once the compaction is over.
+///
+/// - build a single node cluster and notify it of a compaction.
+/// - write beyond the policy threshold: the snapshot build is deferred.
+/// - notify the end of the compaction: the snapshot is built.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn snapshot_deferred_by_compaction() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            snapshot_policy: SnapshotPolicy::LogsSinceLast(10),
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- the state machine starts compacting");
+    {
+        n0.notify_compaction(Duration::from_secs(60)).await?;
+
+        n0.wait(timeout()).metrics(|m| m.compacting_until.is_some(), "compaction reported").await?;
+    }
+
+    tracing::info!(log_index, "--- write beyond the policy threshold");
+    {
+        log_index += router.client_request_many(0, "cli", 20).await? as u64;
+        router.wait(&0, timeout()).applied_index(Some(log_index), "logs applied").await?;
+
+        TypeConfig::sleep(Duration::from_millis(500)).await;
+
+        let m = n0.metrics().borrow_watched().clone();
+        assert_eq!(None, m.snapshot, "snapshot build is deferred while compacting");
+    }
+
+    tracing::info!(log_index, "--- the compaction is over, the snapshot is built");
+    {
+        n0.notify_compaction(Duration::ZERO).await?;
+
+        let m = n0.wait(timeout()).metrics(|m| m.snapshot.is_some(), "snapshot built after compaction").await?;
+        assert_eq!(None, m.compacting_until);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(5_000))
+}
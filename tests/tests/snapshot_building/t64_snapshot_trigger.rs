@@ -0,0 +1,91 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::SnapshotPolicy;
+use openraft::storage::SnapshotTrigger;
+use openraft::storage::SnapshotTriggerContext;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// Build a snapshot once 10 logs are committed after the last snapshot.
+#[derive(Debug)]
+struct EveryTenLogs;
+
+impl SnapshotTrigger<TypeConfig> for EveryTenLogs {
+    fn should_snapshot(&self, ctx: &SnapshotTriggerContext<TypeConfig>) -> bool {
+        ctx.logs_since_snapshot >= 10
+    }
+}
+
+/// A snapshot is built when the installed `SnapshotTrigger` decides to, even with
+/// `SnapshotPolicy::Never`.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn snapshot_trigger() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            snapshot_policy: SnapshotPolicy::Never,
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    n0.set_snapshot_trigger(Some(Arc::new(EveryTenLogs))).await?;
+
+    tracing::info!(log_index, "--- write logs below the threshold, no snapshot is built");
+    {
+        let n = 8 - log_index;
+        router.client_request_many(0, "0", n as usize).await?;
+        log_index += n;
+
+        router.wait(&0, timeout()).applied_index(Some(log_index), "write logs").await?;
+
+        let res = router
+            .wait(&0, Some(Duration::from_millis(500)))
+            .metrics(|m| m.snapshot.is_some(), "no snapshot is built")
+            .await;
+        assert!(res.is_err(), "no snapshot should be built");
+    }
+
+    tracing::info!(log_index, "--- write one more log, the trigger builds a snapshot");
+    {
+        router.client_request_many(0, "0", 1).await?;
+        log_index += 1;
+
+        router.wait(&0, timeout()).snapshot(log_id(1, 0, log_index), "snapshot built by trigger").await?;
+    }
+
+    tracing::info!(log_index, "--- remove the trigger, no more snapshot is built");
+    {
+        n0.set_snapshot_trigger(None).await?;
+
+        router.client_request_many(0, "0", 10).await?;
+        log_index += 10;
+
+        router.wait(&0, timeout()).applied_index(Some(log_index), "write logs").await?;
+
+        let res = router
+            .wait(&0, Some(Duration::from_millis(500)))
+            .snapshot(log_id(1, 0, log_index), "no new snapshot")
+            .await;
+        assert!(res.is_err(), "no new snapshot should be built");
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// With `Config::snapshot_keep_count`, `Raft::snapshot_history()` lists the most recent snapshots
+/// and the state machine is told to remove the older ones.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn snapshot_keep_count() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            snapshot_keep_count: Some(2),
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- bring up cluster of 1 node");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    assert!(n0.snapshot_history().is_empty(), "no snapshot yet");
+
+    let mut built = vec![];
+
+    tracing::info!(log_index, "--- build 3 snapshots");
+    for _ in 0..3 {
+        log_index += router.client_request_many(0, "foo", 5).await?;
+        let meta = n0.trigger().snapshot().await?.built().await?.unwrap();
+        assert_eq!(Some(log_index), meta.last_log_id.as_ref().map(|x| x.index()));
+        built.push(meta);
+    }
+
+    tracing::info!(log_index, "--- only the last 2 snapshots are kept");
+    {
+        assert_eq!(built[1..].to_vec(), n0.snapshot_history());
+        assert_eq!(Some(built[2].clone()), n0.current_snapshot_meta());
+
+        // A state machine command after the removal, to wait for it to finish.
+        n0.get_snapshot().await?;
+
+        let (_, sm) = router.get_storage_handle(&0)?;
+        let kept = sm.kept_snapshot_ids();
+        assert_eq!(
+            vec![built[1].snapshot_id.clone(), built[2].snapshot_id.clone()],
+            kept,
+            "the oldest snapshot is removed from the state machine"
+        );
+    }
+
+    Ok(())
+}
@@ -6,6 +6,7 @@ mod fixtures;
 
 mod t10_api_install_snapshot;
 mod t10_api_install_snapshot_with_lower_vote;
+mod t11_api_install_snapshot_resume;
 mod t20_startup_snapshot;
 mod t30_purge_in_snapshot_logs;
 mod t31_snapshot_overrides_membership;
@@ -15,5 +16,11 @@ mod t34_replication_does_not_block_purge;
 mod t50_snapshot_line_rate_to_snapshot;
 mod t50_snapshot_when_lacking_log;
 mod t51_after_snapshot_add_learner_and_request_a_log;
+mod t52_pipeline_snapshot_tail;
+mod t53_max_inflight_snapshots;
+mod t54_delta_snapshot;
+mod t55_seed_snapshot;
+mod t56_snapshot_locator;
+mod t57_warm_up_after_install;
 mod t60_snapshot_chunk_size;
 mod t90_issue_808_snapshot_to_unreachable_node_should_not_block;
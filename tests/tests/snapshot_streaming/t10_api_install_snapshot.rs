@@ -45,6 +45,7 @@ async fn snapshot_arguments() -> Result<()> {
             snapshot_id: "ss1".into(),
             last_log_id: Some(log_id(1, 0, 0)),
             last_membership: Default::default(),
+            base_snapshot_id: None,
         },
         offset: 0,
         data: vec![1, 2, 3],
@@ -42,6 +42,7 @@ async fn install_snapshot_lower_vote() -> Result<()> {
             snapshot_id: "ss1".into(),
             last_log_id: Some(log_id(1, 0, 0)),
             last_membership: Default::default(),
+            base_snapshot_id: None,
         },
         offset: 0,
         data: vec![1, 2, 3],
@@ -56,6 +57,9 @@ async fn install_snapshot_lower_vote() -> Result<()> {
                 prev_log_id: None,
                 entries: vec![],
                 leader_commit: None,
+                backup_barrier: None,
+                after_snapshot: None,
+                config_digest: None,
             })
             .await;
         let vote = n0.with_raft_state(|st| *st.vote_ref()).await?;
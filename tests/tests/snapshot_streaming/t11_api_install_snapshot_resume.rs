@@ -0,0 +1,90 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::Vote;
+use openraft::raft::InstallSnapshotRequest;
+use openraft::storage::SnapshotMeta;
+use openraft_legacy::prelude::*;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// API test: install_snapshot returns the received bytes so that an interrupted transfer resumes.
+///
+/// What does this test do?
+///
+/// - build a stable single node cluster.
+/// - send install_snapshot chunks and empty probe requests, and check the returned progress.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn install_snapshot_resume() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let (raft, _, _) = router.remove_node(0).unwrap();
+    let make_req = |snapshot_id: &str, offset: u64, data: Vec<u8>| InstallSnapshotRequest {
+        // force it to be a follower
+        vote: Vote::new_committed(2, 1),
+        meta: SnapshotMeta {
+            snapshot_id: snapshot_id.into(),
+            last_log_id: Some(log_id(1, 0, 0)),
+            last_membership: Default::default(),
+            base_snapshot_id: None,
+        },
+        offset,
+        data,
+        done: false,
+    };
+
+    tracing::info!("--- a probe begins a new transfer");
+    {
+        let resp = raft.install_snapshot(make_req("ss1", 0, vec![])).await?;
+        assert_eq!(Some(0), resp.received);
+    }
+
+    tracing::info!("--- write ss1:[0,6)");
+    {
+        let resp = raft.install_snapshot(make_req("ss1", 0, vec![1, 2, 3])).await?;
+        assert_eq!(Some(3), resp.received);
+
+        let resp = raft.install_snapshot(make_req("ss1", 3, vec![4, 5, 6])).await?;
+        assert_eq!(Some(6), resp.received);
+    }
+
+    tracing::info!("--- a probe of an interrupted transfer returns the progress");
+    {
+        let resp = raft.install_snapshot(make_req("ss1", 0, vec![])).await?;
+        assert_eq!(Some(6), resp.received);
+
+        let resp = raft.install_snapshot(make_req("ss1", 6, vec![7])).await?;
+        assert_eq!(Some(7), resp.received, "resumed from the received offset");
+    }
+
+    tracing::info!("--- a resent chunk does not move the progress back");
+    {
+        let resp = raft.install_snapshot(make_req("ss1", 3, vec![4, 5, 6])).await?;
+        assert_eq!(Some(7), resp.received);
+    }
+
+    tracing::info!("--- a probe of another snapshot id starts over");
+    {
+        let resp = raft.install_snapshot(make_req("ss2", 0, vec![])).await?;
+        assert_eq!(Some(0), resp.received);
+    }
+
+    Ok(())
+}
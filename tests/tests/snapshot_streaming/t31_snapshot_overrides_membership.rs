@@ -81,8 +81,16 @@ async fn snapshot_overrides_membership() -> Result<()> {
                 entries: vec![blank_ent::<openraft_memstore::TypeConfig>(0, 0, 0), Entry {
                     log_id: log_id(1, 0, 1),
                     payload: EntryPayload::Membership(Membership::new_with_defaults(vec![btreeset! {2,3}], [])),
+                    apply_scope: None,
+                    timestamp: None,
+                    deadline: None,
+                    expire_from: None,
+                    chain_hash: None,
                 }],
                 leader_commit: Some(log_id(0, 0, 0)),
+                backup_barrier: None,
+                after_snapshot: None,
+                config_digest: None,
             };
 
             let node = router.get_raft_handle(&1)?;
@@ -92,6 +92,11 @@ async fn snapshot_delete_conflicting_logs() -> Result<()> {
                 Entry {
                     log_id: log_id(1, 0, 2),
                     payload: EntryPayload::Membership(Membership::new_with_defaults(vec![btreeset! {2,3}], [])),
+                    apply_scope: None,
+                    timestamp: None,
+                    deadline: None,
+                    expire_from: None,
+                    chain_hash: None,
                 },
                 blank_ent::<openraft_memstore::TypeConfig>(1, 0, 3),
                 blank_ent::<openraft_memstore::TypeConfig>(1, 0, 4),
@@ -105,9 +110,17 @@ async fn snapshot_delete_conflicting_logs() -> Result<()> {
                 Entry {
                     log_id: log_id(1, 0, 11),
                     payload: EntryPayload::Membership(Membership::new_with_defaults(vec![btreeset! {4,5}], [])),
+                    apply_scope: None,
+                    timestamp: None,
+                    deadline: None,
+                    expire_from: None,
+                    chain_hash: None,
                 },
             ],
             leader_commit: Some(log_id(1, 0, 2)),
+            backup_barrier: None,
+            after_snapshot: None,
+            config_digest: None,
         };
 
         let node = router.get_raft_handle(&1)?;
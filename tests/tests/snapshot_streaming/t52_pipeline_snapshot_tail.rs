@@ -0,0 +1,117 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::SnapshotPolicy;
+use openraft::network::RPCTypes;
+use openraft::type_config::TypeConfigExt;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::rpc_request::RpcRequest;
+use crate::fixtures::ut_harness;
+
+/// With `pipeline_snapshot_tail`, the logs after the snapshot are sent to a new learner while the
+/// snapshot is still being sent, and the learner installs both.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn pipeline_snapshot_tail() -> Result<()> {
+    let snapshot_threshold: u64 = 10;
+
+    let config = Arc::new(
+        Config {
+            snapshot_policy: SnapshotPolicy::LogsSinceLast(snapshot_threshold),
+            max_in_snapshot_log_to_keep: 0,
+            purge_batch_size: 1,
+            enable_heartbeat: false,
+            pipeline_snapshot_tail: Some(true),
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- build a snapshot and purge the logs in it");
+    let snapshot_index = {
+        log_index += router.client_request_many(0, "0", (snapshot_threshold - 1 - log_index) as usize).await?;
+
+        router.wait(&0, timeout()).snapshot(log_id(1, 0, log_index), "leader-0 builds snapshot").await?;
+        router.wait(&0, timeout()).purged(Some(log_id(1, 0, log_index)), "leader-0 purges logs").await?;
+        log_index
+    };
+
+    tracing::info!(log_index, "--- write logs after the snapshot");
+    {
+        log_index += router.client_request_many(0, "0", 3).await?;
+        router.wait(&0, timeout()).applied_index(Some(log_index), "write logs after snapshot").await?;
+    }
+
+    tracing::info!(log_index, "--- delay sending snapshot, count logs sent before it");
+    let snapshot_sent = Arc::new(AtomicBool::new(false));
+    let tail_before_snapshot = Arc::new(AtomicU64::new(0));
+    {
+        let sent = snapshot_sent.clone();
+        router
+            .set_rpc_pre_hook(RPCTypes::InstallSnapshot, move |_router, _req, _from, _to| {
+                let sent = sent.clone();
+                Box::pin(async move {
+                    TypeConfig::sleep(Duration::from_millis(500)).await;
+                    sent.store(true, Ordering::Relaxed);
+                    Ok(())
+                })
+            })
+            .await;
+
+        let sent = snapshot_sent.clone();
+        let tail = tail_before_snapshot.clone();
+        router
+            .set_rpc_pre_hook(RPCTypes::AppendEntries, move |_router, req, _from, to| {
+                if let RpcRequest::AppendEntries(a) = req
+                    && to == 1
+                    && a.after_snapshot.is_some()
+                    && !sent.load(Ordering::Relaxed)
+                {
+                    tail.fetch_add(1, Ordering::Relaxed);
+                }
+                Box::pin(async { Ok(()) })
+            })
+            .await;
+    }
+
+    tracing::info!(log_index, "--- add learner to receive the snapshot and the logs after it");
+    {
+        router.new_raft_node(1).await;
+        router.add_learner(0, 1).await?;
+        log_index += 1;
+
+        router
+            .wait(&1, timeout())
+            .snapshot(log_id(1, 0, snapshot_index), "learner-1 installs snapshot")
+            .await?;
+        router.wait(&1, timeout()).applied_index(Some(log_index), "learner-1 applies the tail").await?;
+
+        assert!(
+            tail_before_snapshot.load(Ordering::Relaxed) > 0,
+            "logs after the snapshot are sent before the snapshot"
+        );
+        assert_eq!(
+            Some(&1),
+            router.get_rpc_count().get(&RPCTypes::InstallSnapshot),
+            "snapshot is sent once"
+        );
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}
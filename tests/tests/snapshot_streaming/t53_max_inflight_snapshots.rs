@@ -0,0 +1,104 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::RPCTypes;
+use openraft::SnapshotPolicy;
+use openraft::base::BoxFuture;
+use openraft::metrics::SnapshotTransferState;
+use openraft::type_config::TypeConfigExt;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// A leader sends at most `max_inflight_snapshots` snapshots at the same time, and queues the
+/// others.
+///
+/// - brings a single node cluster online, builds a snapshot and purges logs.
+/// - slows down sending snapshots and adds 2 learners that need a snapshot.
+/// - asserts one transfer is sending and the other is queued in the metrics.
+/// - asserts both learners receive the snapshot and the transfers are cleared.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn max_inflight_snapshots() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            snapshot_policy: SnapshotPolicy::LogsSinceLast(10),
+            max_in_snapshot_log_to_keep: 0,
+            purge_batch_size: 1,
+            max_inflight_snapshots: Some(1),
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- write logs to build a snapshot and purge logs");
+    {
+        log_index += router.client_request_many(0, "0", (9 - log_index) as usize).await?;
+
+        router.wait(&0, timeout()).snapshot(log_id(1, 0, log_index), "build snapshot").await?;
+        router
+            .wait(&0, timeout())
+            .metrics(|m| m.purged == Some(log_id(1, 0, log_index)), "purge logs")
+            .await?;
+    }
+
+    tracing::info!(log_index, "--- add 2 learners, only one snapshot is sent at a time");
+    {
+        router
+            .set_rpc_pre_hook(RPCTypes::InstallSnapshot, |_router, _req, _from, _target| {
+                let fu: BoxFuture<_> = Box::pin(async move {
+                    TypeConfig::sleep(Duration::from_millis(500)).await;
+                    Ok(())
+                });
+                fu
+            })
+            .await;
+
+        router.new_raft_node(1).await;
+        router.new_raft_node(2).await;
+
+        let leader = router.get_raft_handle(&0)?;
+        leader.add_learner(1, (), false).await?;
+        leader.add_learner(2, (), false).await?;
+        log_index += 2;
+
+        let m = router
+            .wait(&0, timeout())
+            .metrics(|m| m.snapshot_transfers.len() == 2, "two snapshot transfers requested")
+            .await?;
+
+        let mut states = m.snapshot_transfers.values().copied().collect::<Vec<_>>();
+        states.sort_by_key(|s| *s == SnapshotTransferState::Queued);
+        assert_eq!(
+            vec![SnapshotTransferState::Sending, SnapshotTransferState::Queued],
+            states
+        );
+    }
+
+    tracing::info!(log_index, "--- both learners receive the snapshot");
+    {
+        for id in [1, 2] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "learner catches up").await?;
+        }
+
+        router
+            .wait(&0, timeout())
+            .metrics(|m| m.snapshot_transfers.is_empty(), "snapshot transfers cleared")
+            .await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3000))
+}
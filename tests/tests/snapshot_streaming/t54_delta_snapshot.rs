@@ -0,0 +1,118 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::RPCTypes;
+use openraft::SnapshotId;
+use openraft::SnapshotPolicy;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::rpc_request::RpcRequest;
+use crate::fixtures::ut_harness;
+
+/// A snapshot sent to a follower that has installed an earlier snapshot from the leader is a delta
+/// relative to it, if the state machine can build one.
+///
+/// - Add a learner that receives a full snapshot.
+/// - Isolate the learner, write logs, build another snapshot and purge the logs.
+/// - Restore the learner: it receives a delta snapshot relative to the first one.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn delta_snapshot() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            snapshot_policy: SnapshotPolicy::Never,
+            max_in_snapshot_log_to_keep: 0,
+            purge_batch_size: 1,
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let (_sto0, sm0) = router.get_storage_handle(&0)?;
+    sm0.enable_delta_snapshot(true);
+
+    // The (snapshot_id, base_snapshot_id) of the snapshots installed by node-1.
+    let installed = Arc::new(Mutex::new(Vec::<(SnapshotId, Option<SnapshotId>)>::new()));
+    {
+        let installed = installed.clone();
+        router
+            .set_rpc_post_hook(RPCTypes::InstallSnapshot, move |_router, req, _resp, _from, _to| {
+                if let RpcRequest::InstallFullSnapshot(snapshot) = req {
+                    let meta = snapshot.meta;
+                    installed.lock().unwrap().push((meta.snapshot_id, meta.base_snapshot_id));
+                }
+                Box::pin(async { Ok(()) })
+            })
+            .await;
+    }
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- write logs, build a snapshot and purge logs");
+    {
+        log_index += router.client_request_many(0, "a", 10).await?;
+        n0.trigger().snapshot().await?;
+        router.wait(&0, timeout()).snapshot(log_id(1, 0, log_index), "first snapshot").await?;
+        n0.trigger().purge_log(log_index).await?;
+        router.wait(&0, timeout()).purged(Some(log_id(1, 0, log_index)), "purge").await?;
+    }
+
+    tracing::info!(log_index, "--- add learner-1, it receives a full snapshot");
+    let first = {
+        router.new_raft_node(1).await;
+        router.add_learner(0, 1).await?;
+        log_index += 1;
+        router.wait(&1, timeout()).applied_index(Some(log_index), "learner-1 caught up").await?;
+
+        let installed = installed.lock().unwrap().clone();
+        assert_eq!(1, installed.len());
+        assert_eq!(None, installed[0].1, "the first snapshot is full");
+        installed[0].0.clone()
+    };
+
+    tracing::info!(
+        log_index,
+        "--- isolate learner-1, write logs, build a snapshot and purge logs"
+    );
+    {
+        router.set_unreachable(1, true);
+
+        log_index += router.client_request_many(0, "b", 10).await?;
+        n0.trigger().snapshot().await?;
+        router.wait(&0, timeout()).snapshot(log_id(1, 0, log_index), "second snapshot").await?;
+        n0.trigger().purge_log(log_index).await?;
+        router.wait(&0, timeout()).purged(Some(log_id(1, 0, log_index)), "purge").await?;
+    }
+
+    tracing::info!(log_index, "--- restore learner-1, it receives a delta snapshot");
+    {
+        router.set_unreachable(1, false);
+        log_index += router.client_request_many(0, "c", 1).await?;
+        router.wait(&1, timeout()).applied_index(Some(log_index), "learner-1 caught up").await?;
+
+        let installed = installed.lock().unwrap().clone();
+        assert_eq!(2, installed.len());
+        assert_eq!(Some(first), installed[1].1, "the second snapshot is a delta");
+
+        let (_sto1, sm1) = router.get_storage_handle(&1)?;
+        let sm0 = sm0.get_state_machine().await;
+        let sm1 = sm1.get_state_machine().await;
+        assert_eq!(sm0.client_status, sm1.client_status);
+        assert_eq!(3, sm1.client_status.len());
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,135 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::RPCTypes;
+use openraft::SnapshotPolicy;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// A learner with a snapshot seed fetches its snapshot from the seed, not from the leader.
+///
+/// - Build a snapshot on the leader and the follower-1, and purge logs on the leader.
+/// - Add learner-2 and set follower-1 as its seed.
+/// - Learner-2 catches up by fetching the snapshot from follower-1.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn seed_snapshot() -> Result<()> {
+    let mut router = RaftRouter::new(config()?);
+
+    let mut log_index = router.new_cluster(btreeset! {0,1}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let n1 = router.get_raft_handle(&1)?;
+
+    tracing::info!(
+        log_index,
+        "--- build snapshots on node-0 and node-1, purge logs on node-0"
+    );
+    {
+        log_index += router.client_request_many(0, "a", 10).await?;
+        router.wait(&1, timeout()).applied_index(Some(log_index), "node-1 applied").await?;
+
+        n0.trigger().snapshot().await?;
+        n1.trigger().snapshot().await?;
+        router.wait(&0, timeout()).snapshot(log_id(1, 0, log_index), "node-0 snapshot").await?;
+        router.wait(&1, timeout()).snapshot(log_id(1, 0, log_index), "node-1 snapshot").await?;
+
+        n0.trigger().purge_log(log_index).await?;
+        router.wait(&0, timeout()).purged(Some(log_id(1, 0, log_index)), "purge").await?;
+    }
+
+    tracing::info!(log_index, "--- add learner-2 seeded from node-1");
+    {
+        router.new_raft_node(2).await;
+        router.set_unreachable(2, true);
+
+        n0.add_learner(2, (), false).await?;
+        log_index += 1;
+        n0.set_snapshot_seed(2, Some(1)).await?;
+
+        router.set_unreachable(2, false);
+        router.wait(&2, timeout()).applied_index(Some(log_index), "learner-2 caught up").await?;
+    }
+
+    tracing::info!(log_index, "--- learner-2 received the snapshot from node-1");
+    {
+        let counts = router.get_rpc_count();
+        assert_eq!(Some(&1), counts.get(&RPCTypes::SeedSnapshot));
+        assert_eq!(Some(&1), counts.get(&RPCTypes::FetchSnapshot));
+        assert_eq!(None, counts.get(&RPCTypes::InstallSnapshot));
+
+        let (_sto0, sm0) = router.get_storage_handle(&0)?;
+        let (_sto2, sm2) = router.get_storage_handle(&2)?;
+        let sm0 = sm0.get_state_machine().await;
+        let sm2 = sm2.get_state_machine().await;
+        assert_eq!(sm0.client_status, sm2.client_status);
+    }
+
+    Ok(())
+}
+
+/// If the seed has no snapshot that includes the purged logs, the leader sends its own.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn seed_snapshot_fallback_to_leader() -> Result<()> {
+    let mut router = RaftRouter::new(config()?);
+
+    let mut log_index = router.new_cluster(btreeset! {0,1}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- build a snapshot and purge logs only on node-0");
+    {
+        log_index += router.client_request_many(0, "a", 10).await?;
+
+        n0.trigger().snapshot().await?;
+        router.wait(&0, timeout()).snapshot(log_id(1, 0, log_index), "node-0 snapshot").await?;
+
+        n0.trigger().purge_log(log_index).await?;
+        router.wait(&0, timeout()).purged(Some(log_id(1, 0, log_index)), "purge").await?;
+    }
+
+    tracing::info!(log_index, "--- add learner-2 seeded from node-1");
+    {
+        router.new_raft_node(2).await;
+        router.set_unreachable(2, true);
+
+        n0.add_learner(2, (), false).await?;
+        log_index += 1;
+        n0.set_snapshot_seed(2, Some(1)).await?;
+
+        router.set_unreachable(2, false);
+        router.wait(&2, timeout()).applied_index(Some(log_index), "learner-2 caught up").await?;
+    }
+
+    tracing::info!(log_index, "--- learner-2 received the snapshot from the leader");
+    {
+        let counts = router.get_rpc_count();
+        assert_eq!(Some(&1), counts.get(&RPCTypes::FetchSnapshot));
+        assert_eq!(Some(&1), counts.get(&RPCTypes::InstallSnapshot));
+    }
+
+    Ok(())
+}
+
+fn config() -> Result<Arc<Config>> {
+    let config = Config {
+        snapshot_policy: SnapshotPolicy::Never,
+        max_in_snapshot_log_to_keep: 0,
+        purge_batch_size: 1,
+        enable_heartbeat: false,
+        ..Default::default()
+    }
+    .validate()?;
+
+    Ok(Arc::new(config))
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,91 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::RPCTypes;
+use openraft::SnapshotPolicy;
+use openraft_memstore::ExternalSnapshotStore;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// When the state machine stores snapshots out of band, the leader sends only the locator, and
+/// the learner's state machine fetches the snapshot data by itself.
+///
+/// - Build a snapshot on the leader, and purge logs.
+/// - Add learner-1, which shares the external snapshot store with the leader.
+/// - Learner-1 catches up by fetching the snapshot from the external store.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn snapshot_locator() -> Result<()> {
+    let mut router = RaftRouter::new(config()?);
+
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let store = ExternalSnapshotStore::default();
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- build a snapshot in the external store, purge logs");
+    {
+        let (_sto0, sm0) = router.get_storage_handle(&0)?;
+        sm0.set_external_snapshot_store(Some(store.clone()));
+
+        log_index += router.client_request_many(0, "a", 10).await?;
+
+        n0.trigger().snapshot().await?;
+        router.wait(&0, timeout()).snapshot(log_id(1, 0, log_index), "node-0 snapshot").await?;
+
+        n0.trigger().purge_log(log_index).await?;
+        router.wait(&0, timeout()).purged(Some(log_id(1, 0, log_index)), "purge").await?;
+    }
+
+    tracing::info!(log_index, "--- add learner-1 sharing the external store");
+    {
+        router.new_raft_node(1).await;
+        let (_sto1, sm1) = router.get_storage_handle(&1)?;
+        sm1.set_external_snapshot_store(Some(store.clone()));
+
+        n0.add_learner(1, (), true).await?;
+        log_index += 1;
+
+        router.wait(&1, timeout()).applied_index(Some(log_index), "learner-1 caught up").await?;
+    }
+
+    tracing::info!(log_index, "--- learner-1 fetched the snapshot from the external store");
+    {
+        let counts = router.get_rpc_count();
+        assert_eq!(Some(&1), counts.get(&RPCTypes::SnapshotLocator));
+        assert_eq!(None, counts.get(&RPCTypes::InstallSnapshot));
+
+        let snapshot_id = n0.current_snapshot_meta().unwrap().snapshot_id;
+        assert!(store.lock().unwrap().contains_key(&format!("mem://{}", snapshot_id)));
+
+        let (_sto0, sm0) = router.get_storage_handle(&0)?;
+        let (_sto1, sm1) = router.get_storage_handle(&1)?;
+        let sm0 = sm0.get_state_machine().await;
+        let sm1 = sm1.get_state_machine().await;
+        assert_eq!(sm0.client_status, sm1.client_status);
+    }
+
+    Ok(())
+}
+
+fn config() -> Result<Arc<Config>> {
+    let config = Config {
+        snapshot_policy: SnapshotPolicy::Never,
+        max_in_snapshot_log_to_keep: 0,
+        purge_batch_size: 1,
+        enable_heartbeat: false,
+        ..Default::default()
+    }
+    .validate()?;
+
+    Ok(Arc::new(config))
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
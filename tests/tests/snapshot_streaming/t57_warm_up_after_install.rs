@@ -0,0 +1,128 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use futures::Stream;
+use futures::StreamExt;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::SnapshotPolicy;
+use openraft::errors::EventsLagged;
+use openraft::raft::RaftEvent;
+use openraft::type_config::TypeConfigExt;
+use openraft_memstore::BlockOperation;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// With `warm_up_after_install` enabled, a learner reports it is warming up after installing a
+/// snapshot, until the state machine finishes warming up.
+///
+/// - Build a snapshot on the leader, and purge logs.
+/// - Add learner-1, whose state machine warm-up is blocked for a while.
+/// - Learner-1 reports `warming_up` and emits `WarmUpStarted`, then `WarmUpFinished`.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn warm_up_after_install() -> Result<()> {
+    let mut router = RaftRouter::new(config()?);
+
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- build a snapshot, purge logs");
+    {
+        log_index += router.client_request_many(0, "a", 10).await?;
+
+        n0.trigger().snapshot().await?;
+        router.wait(&0, timeout()).snapshot(log_id(1, 0, log_index), "node-0 snapshot").await?;
+
+        n0.trigger().purge_log(log_index).await?;
+        router.wait(&0, timeout()).purged(Some(log_id(1, 0, log_index)), "purge").await?;
+    }
+
+    let snapshot_log_id = log_id(1, 0, log_index);
+
+    tracing::info!(log_index, "--- add learner-1, whose warm-up is blocked");
+    let mut events = {
+        router.new_raft_node(1).await;
+        let (_sto1, sm1) = router.get_storage_handle(&1)?;
+        sm1.block.set_blocking(BlockOperation::WarmUp, Duration::from_millis(1_000));
+
+        let n1 = router.get_raft_handle(&1)?;
+        let events = n1.subscribe_events();
+
+        n0.add_learner(1, (), false).await?;
+        log_index += 1;
+
+        events
+    };
+
+    tracing::info!(log_index, "--- learner-1 is warming up after installing the snapshot");
+    {
+        router
+            .wait(&1, timeout())
+            .metrics(
+                |m| m.warming_up.as_ref() == Some(&snapshot_log_id),
+                "learner-1 is warming up",
+            )
+            .await?;
+
+        next_event(&mut events, |ev| match ev {
+            RaftEvent::WarmUpStarted { meta } => meta.last_log_id.as_ref() == Some(&snapshot_log_id),
+            _ => false,
+        })
+        .await?;
+    }
+
+    tracing::info!(log_index, "--- learner-1 finishes warming up");
+    {
+        next_event(&mut events, |ev| match ev {
+            RaftEvent::WarmUpFinished { meta } => meta.last_log_id.as_ref() == Some(&snapshot_log_id),
+            _ => false,
+        })
+        .await?;
+
+        router.wait(&1, timeout()).metrics(|m| m.warming_up.is_none(), "learner-1 is warmed up").await?;
+        router.wait(&1, timeout()).applied_index(Some(log_index), "learner-1 caught up").await?;
+    }
+
+    Ok(())
+}
+
+/// Read events until one satisfies `f`.
+async fn next_event<S, F>(events: &mut S, f: F) -> Result<RaftEvent<TypeConfig>>
+where
+    S: Stream<Item = Result<RaftEvent<TypeConfig>, EventsLagged>> + Unpin + Send,
+    F: Fn(&RaftEvent<TypeConfig>) -> bool,
+{
+    loop {
+        let ev = TypeConfig::timeout(Duration::from_millis(2_000), events.next()).await?;
+        let ev = ev.expect("event stream ends")?;
+        tracing::info!("received event: {}", ev);
+
+        if f(&ev) {
+            return Ok(ev);
+        }
+    }
+}
+
+fn config() -> Result<Arc<Config>> {
+    let config = Config {
+        snapshot_policy: SnapshotPolicy::Never,
+        max_in_snapshot_log_to_keep: 0,
+        purge_batch_size: 1,
+        enable_heartbeat: false,
+        warm_up_after_install: Some(true),
+        ..Default::default()
+    }
+    .validate()?;
+
+    Ok(Arc::new(config))
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
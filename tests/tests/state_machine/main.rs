@@ -9,3 +9,6 @@ mod fixtures;
 
 mod t10_total_order_apply;
 mod t20_state_machine_apply_membership;
+mod t30_delayed_apply;
+mod t31_throttled_apply;
+mod t40_stream_payload_apply;
@@ -0,0 +1,161 @@
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::async_runtime::watch::WatchReceiver;
+use openraft::type_config::TypeConfigExt;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// A learner with `apply_delay` acknowledges replicated logs at once, but applies them only after
+/// the delay.
+///
+/// - brings up a leader and a learner configured with `apply_delay`.
+/// - writes a log and asserts it is replicated to the learner but not yet applied.
+/// - asserts the learner applies it after the delay.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn delayed_apply() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let delayed_config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            apply_delay: Some(1_000),
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- add a delayed learner");
+    {
+        router.new_raft_node_with_config(1, delayed_config).await;
+        router.add_learner(0, 1).await?;
+        log_index += 1;
+    }
+
+    tracing::info!(log_index, "--- wait for the learner to apply the membership log");
+    router
+        .wait(&1, Some(Duration::from_millis(3_000)))
+        .applied_index(Some(log_index), "learner applied")
+        .await?;
+
+    tracing::info!(
+        log_index,
+        "--- write a log, it is replicated but not applied on the learner"
+    );
+    let written_at = Instant::now();
+    {
+        router.client_request_many(0, "foo", 1).await?;
+        log_index += 1;
+
+        router
+            .wait(&0, timeout())
+            .metrics(
+                |m| m.replication().and_then(|r| r.get(&1).cloned()).flatten() == Some(log_id(1, 0, log_index)),
+                "replicated to the learner",
+            )
+            .await?;
+
+        let learner = router.get_raft_handle(&1)?;
+        let applied = learner.metrics().borrow_watched().last_applied;
+        assert!(
+            applied.map(|x| x.index()) < Some(log_index),
+            "the learner does not apply at once"
+        );
+    }
+
+    tracing::info!(log_index, "--- the learner applies the log after the delay");
+    {
+        router
+            .wait(&1, Some(Duration::from_millis(3_000)))
+            .applied_index(Some(log_index), "learner applied after the delay")
+            .await?;
+
+        assert!(written_at.elapsed() >= Duration::from_millis(1_000));
+    }
+
+    Ok(())
+}
+
+/// The snapshot commands on a learner with `apply_delay` do not wait for the delayed applies.
+///
+/// - brings up a leader and a learner configured with a long `apply_delay`.
+/// - writes a log, which the learner delays applying.
+/// - asserts getting a snapshot and receiving one on the learner are served at once.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn delayed_apply_does_not_block_snapshot() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let delayed_config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            apply_delay: Some(60_000),
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- add a delayed learner and write a log");
+    {
+        router.new_raft_node_with_config(1, delayed_config).await;
+        router.add_learner(0, 1).await?;
+        log_index += 1;
+
+        router.client_request_many(0, "foo", 1).await?;
+        log_index += 1;
+
+        router
+            .wait(&0, timeout())
+            .metrics(
+                |m| m.replication().and_then(|r| r.get(&1).cloned()).flatten() == Some(log_id(1, 0, log_index)),
+                "replicated to the learner",
+            )
+            .await?;
+    }
+
+    tracing::info!(log_index, "--- snapshot commands are served while the apply is delayed");
+    {
+        let learner = router.get_raft_handle(&1)?;
+
+        TypeConfig::timeout(Duration::from_millis(1_000), learner.get_snapshot()).await??;
+        TypeConfig::timeout(Duration::from_millis(1_000), learner.begin_receiving_snapshot()).await??;
+
+        let applied = learner.metrics().borrow_watched().last_applied;
+        assert!(
+            applied.map(|x| x.index()) < Some(log_index),
+            "the learner still delays the apply"
+        );
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(500))
+}
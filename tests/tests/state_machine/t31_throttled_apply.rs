@@ -0,0 +1,83 @@
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// A learner with `max_apply_rate` applies a backlog of logs no faster than the rate, and reports
+/// the throttling in metrics.
+///
+/// - brings up a leader and a learner configured with `max_apply_rate`.
+/// - writes a burst of logs.
+/// - asserts the learner lags behind while throttled, and applies all the logs at the rate.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn throttled_apply() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let throttled_config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            max_apply_rate: Some(100),
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- add a throttled learner");
+    {
+        router.new_raft_node_with_config(1, throttled_config).await;
+        router.add_learner(0, 1).await?;
+        log_index += 1;
+
+        router.wait(&1, timeout()).applied_index(Some(log_index), "learner applied").await?;
+    }
+
+    tracing::info!(log_index, "--- write a burst of logs, the learner lags behind");
+    let written_at = Instant::now();
+    {
+        router.client_request_many(0, "foo", 200).await?;
+        log_index += 200;
+
+        router
+            .wait(&1, timeout())
+            .metrics(
+                |m| m.apply_throttled_until.is_some() && m.last_applied.map(|x| x.index()) < Some(log_index),
+                "learner apply is throttled",
+            )
+            .await?;
+    }
+
+    tracing::info!(log_index, "--- the learner applies all logs at the rate");
+    {
+        router
+            .wait(&1, Some(Duration::from_millis(10_000)))
+            .applied_index(Some(log_index), "learner applied all")
+            .await?;
+
+        assert!(
+            written_at.elapsed() >= Duration::from_millis(1_500),
+            "200 logs at 100 per second"
+        );
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}
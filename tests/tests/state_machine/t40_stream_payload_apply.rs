@@ -0,0 +1,73 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// With `stream_payload_threshold`, an application entry at least that large is applied from a
+/// stream of its payload, on the leader and on the followers; smaller entries are applied decoded.
+///
+/// - brings up a leader and a learner with a threshold.
+/// - writes a small and a large entry.
+/// - asserts only the large one is applied from a payload stream, with the same result.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn stream_payload_apply() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            stream_payload_threshold: Some(1024),
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {1}).await?;
+
+    for id in [0, 1] {
+        let (_sto, sm) = router.get_storage_handle(&id)?;
+        sm.take_payload_stream_count();
+    }
+
+    tracing::info!(log_index, "--- write a small and a large entry");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+
+        n0.client_write(ClientRequest::make_request("small", 1)).await?;
+
+        let large = ClientRequest {
+            client: "large".to_string(),
+            serial: 1,
+            status: "x".repeat(4096),
+        };
+        let resp = n0.client_write(large.clone()).await?;
+        log_index += 2;
+
+        assert_eq!(log_index, resp.log_id.index);
+
+        for id in [0, 1] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "both entries applied").await?;
+
+            let (_sto, sm) = router.get_storage_handle(&id)?;
+            assert_eq!(1, sm.take_payload_stream_count(), "node {}", id);
+
+            let state = sm.get_state_machine().await;
+            assert_eq!(Some(&large.status), state.client_status.get("large"), "node {}", id);
+            assert!(state.client_status.contains_key("small"), "node {}", id);
+        }
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}